@@ -0,0 +1,59 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Large file benchmark for the `mmap` feature.
+//!
+//! Compares reading a multi-megabyte JSON file into a `String` before parsing
+//! with `load_file`, which parses it from a memory map.
+
+use confers::loader::{load_file, parse_content, Format, LoaderConfig};
+use confers::SourceId;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::path::PathBuf;
+
+/// Write a JSON config of roughly `megabytes` MB to the temp directory.
+fn create_large_config(megabytes: usize) -> PathBuf {
+    let mut json = String::from("{\"services\": [");
+    let mut i = 0;
+    while json.len() < megabytes * 1024 * 1024 {
+        if i > 0 {
+            json.push(',');
+        }
+        json.push_str(&format!(
+            "{{\"name\": \"service_{i}\", \"host\": \"10.0.{}.{}\", \"port\": {}, \"tags\": [\"a\", \"b\"]}}",
+            i / 256 % 256,
+            i % 256,
+            8000 + i % 1000
+        ));
+        i += 1;
+    }
+    json.push_str("]}");
+    let path = std::env::temp_dir().join(format!("bench_mmap_{}mb.json", megabytes));
+    std::fs::write(&path, json).unwrap();
+    path
+}
+
+fn bench_large_json(c: &mut Criterion) {
+    let path = create_large_config(8);
+    let config = LoaderConfig::default().allow_absolute();
+    let mut group = c.benchmark_group("load_8mb_json");
+    group.sample_size(20);
+
+    group.bench_function("read_to_string", |b| {
+        b.iter(|| {
+            let content = std::fs::read_to_string(&path).unwrap();
+            parse_content(&content, Format::Json, SourceId::new("bench"), Some(&path)).unwrap()
+        });
+    });
+    group.bench_function("mmap", |b| {
+        b.iter(|| load_file(&path, &config).unwrap());
+    });
+
+    group.finish();
+    std::fs::remove_file(path).ok();
+}
+
+criterion_group!(benches, bench_large_json);
+criterion_main!(benches);
@@ -14,6 +14,55 @@ use syn::{Fields, Ident};
 
 use crate::parse::{FieldAttrs, StructAttrs};
 
+/// Arguments every generated CLI struct has, as field and flag names.
+const BUILTIN_ARGS: &[(&str, &str)] = &[
+    ("config_file", "config"),
+    ("set", "set"),
+    ("print_config", "print-config"),
+];
+
+/// Reject fields that would clash with the built-in `--config`, `--set` and
+/// `--print-config` arguments.
+pub fn check_builtin_args(fields: &Fields) -> syn::Result<()> {
+    for field in fields {
+        let (Some(ident), Ok(attrs)) = (&field.ident, FieldAttrs::from_field(field)) else {
+            continue;
+        };
+        if attrs.skip {
+            continue;
+        }
+        let cli_name = attrs
+            .name_clap_long
+            .clone()
+            .unwrap_or_else(|| attrs.effective_name().replace('.', "-"));
+        for (builtin_field, builtin_flag) in BUILTIN_ARGS {
+            if ident == builtin_field || (!attrs.positional && cli_name == *builtin_flag) {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!(
+                        "`{}` clashes with the built-in `--{}` argument of ConfigClap; \
+                         rename the field or set `name_clap_long`",
+                        ident, builtin_flag
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Whether the field has a `default` that a missing CLI argument keeps. Such
+/// arguments are `Option`s in the CLI struct; flags and lists are already
+/// optional.
+fn defaulted_arg(attrs: &FieldAttrs) -> bool {
+    let is_bool = matches!(&attrs.ty, syn::Type::Path(p) if p.path.is_ident("bool"));
+    attrs.default.is_some()
+        && !attrs.required
+        && !is_bool
+        && !crate::parse::is_option_type(&attrs.ty)
+        && !crate::parse::is_vec_type(&attrs.ty)
+}
+
 /// Generate ClapArgs struct for CLI argument parsing.
 pub fn generate_clap_impl(
     struct_ident: &Ident,
@@ -39,33 +88,45 @@ pub fn generate_clap_impl(
                 .clone()
                 .unwrap_or_else(|| field_name.replace('.', "-"));
 
-            // Build arg attributes
-            let mut arg_parts = vec![quote! { long = #cli_name }];
+            // Build arg attributes; a positional argument has no flag
+            let mut arg_parts = Vec::new();
 
-            if let Some(short) = field_attrs.name_clap_short {
-                arg_parts.push(quote! { short = #short });
+            if !field_attrs.positional {
+                arg_parts.push(quote! { long = #cli_name });
+
+                if let Some(short) = field_attrs.name_clap_short {
+                    arg_parts.push(quote! { short = #short });
+                }
             }
 
-            if let Some(desc) = &field_attrs.description {
+            if let Some(desc) = field_attrs.effective_description() {
                 arg_parts.push(quote! { help = #desc });
             }
 
+            if field_attrs.value_enum {
+                arg_parts.push(quote! { value_enum });
+            }
+
             // Check if field has a default
             let has_default = field_attrs.default.is_some();
 
             let ty = &field.ty;
-            let type_str = quote!(#ty).to_string();
 
-            // Handle optional types - make them optional in CLI
-            if type_str.contains("Option") {
-                arg_parts.push(quote! { required = false });
-            } else if has_default {
-                // Fields with defaults are optional
+            if field_attrs.required {
+                arg_parts.push(quote! { required = true });
+            } else if crate::parse::is_option_type(ty) || has_default {
+                // Optional types and fields with defaults are optional in CLI
                 arg_parts.push(quote! { required = false });
             }
 
             let arg_attr = quote! { #[arg(#(#arg_parts),*)] };
 
+            if defaulted_arg(&field_attrs) {
+                return Some(quote! {
+                    #arg_attr
+                    pub #ident: Option<#ty>
+                });
+            }
             Some(quote! {
                 #arg_attr
                 pub #ident: #ty
@@ -73,8 +134,9 @@ pub fn generate_clap_impl(
         })
         .collect();
 
-    // Generate field names for to_config_map
-    let field_idents: Vec<TokenStream> = fields
+    // Generate the to_config_map entries; value enums are stored under
+    // their CLI name, as they have no `ConfigValue` conversion
+    let map_inserts: Vec<TokenStream> = fields
         .iter()
         .filter_map(|field| {
             let ident = field.ident.as_ref()?;
@@ -82,10 +144,51 @@ pub fn generate_clap_impl(
             if attrs.skip {
                 return None;
             }
-            Some(quote! { #ident })
+            if defaulted_arg(&attrs) {
+                // Only an argument that was given overrides the default
+                let value = if attrs.value_enum {
+                    quote! {
+                        clap::ValueEnum::to_possible_value(value).map(|p| p.get_name().to_string())
+                    }
+                } else {
+                    quote! { value.clone() }
+                };
+                return Some(quote! {
+                    if let Some(value) = &self.#ident {
+                        map.insert(stringify!(#ident).to_string(), confers::ConfigValue::from(#value));
+                    }
+                });
+            }
+            let value = if !attrs.value_enum {
+                quote! { confers::ConfigValue::from(self.#ident.clone()) }
+            } else if crate::parse::is_option_type(&field.ty) {
+                quote! {
+                    confers::ConfigValue::from(self.#ident.as_ref().and_then(|v| {
+                        clap::ValueEnum::to_possible_value(v).map(|p| p.get_name().to_string())
+                    }))
+                }
+            } else {
+                quote! {
+                    confers::ConfigValue::from(
+                        clap::ValueEnum::to_possible_value(&self.#ident)
+                            .map(|p| p.get_name().to_string())
+                    )
+                }
+            };
+            Some(quote! {
+                map.insert(stringify!(#ident).to_string(), #value);
+            })
         })
         .collect();
 
+    // Keys redacted by --print-config, besides values that look like secrets
+    let sensitive_paths: Vec<String> = fields
+        .iter()
+        .filter_map(|field| FieldAttrs::from_field(field).ok())
+        .filter(|attrs| !attrs.skip && attrs.is_sensitive_effective())
+        .map(|attrs| attrs.effective_name())
+        .collect();
+
     // Create a unique type name based on struct name
     let cli_args_ident = quote::format_ident!("{}CliArgs", struct_ident);
 
@@ -121,6 +224,23 @@ pub fn generate_clap_impl(
                 <#cli_args_ident as clap::CommandFactory>::command()
             }
 
+            /// Add this configuration's arguments to an existing clap
+            /// command, so an application with its own CLI keeps one parser.
+            /// Read them back with [`from_arg_matches`](Self::from_arg_matches).
+            #[allow(dead_code)]
+            pub fn augment_args(cmd: clap::Command) -> clap::Command {
+                <#cli_args_ident as clap::Args>::augment_args(cmd)
+            }
+
+            /// Extract the arguments added by [`augment_args`](Self::augment_args)
+            /// from the matches of the application's command or subcommand.
+            #[allow(dead_code)]
+            pub fn from_arg_matches(
+                matches: &clap::ArgMatches,
+            ) -> Result<#cli_args_ident, clap::Error> {
+                <#cli_args_ident as clap::FromArgMatches>::from_arg_matches(matches)
+            }
+
             /// Create clap args from iterator of strings (for testing).
             #[allow(dead_code)]
             pub fn clap_args_from<I>(iter: I) -> #cli_args_ident
@@ -141,7 +261,23 @@ pub fn generate_clap_impl(
         #[command(name = #app_name)]
         #[allow(dead_code)]
         pub struct #cli_args_ident {
-            #(#clap_field_defs),*
+            #(#clap_field_defs,)*
+
+            /// Configuration file to load
+            #[arg(long = "config", value_name = "FILE")]
+            pub config_file: Option<std::path::PathBuf>,
+
+            /// Override a configuration key, e.g. `--set server.port=9000` (repeatable)
+            #[arg(
+                long = "set",
+                value_name = "KEY=VALUE",
+                value_parser = confers::__private::parse_set_arg
+            )]
+            pub set: Vec<(String, String)>,
+
+            /// Print the merged configuration, with secrets redacted, and exit
+            #[arg(long = "print-config")]
+            pub print_config: bool,
         }
 
         impl #cli_args_ident {
@@ -149,14 +285,60 @@ pub fn generate_clap_impl(
             #[allow(dead_code)]
             pub fn to_config_map(&self) -> std::collections::HashMap<String, confers::ConfigValue> {
                 let mut map = std::collections::HashMap::new();
-                #(
-                    map.insert(
-                        stringify!(#field_idents).to_string(),
-                        confers::ConfigValue::from(self.#field_idents.clone())
-                    );
-                )*
+                #(#map_inserts)*
                 map
             }
+
+            /// Add the `--config` file and the `--set` overrides to `builder`.
+            /// The file comes after the builder's own files; the overrides
+            /// win over every source.
+            #[allow(dead_code)]
+            pub fn apply<T>(&self, mut builder: confers::ConfigBuilder<T>) -> confers::ConfigBuilder<T> {
+                if let Some(path) = &self.config_file {
+                    builder = builder.file(path);
+                }
+                for (key, value) in &self.set {
+                    builder = builder.with_override(
+                        key.as_str(),
+                        confers::EnvSource::infer_config_value(value),
+                    );
+                }
+                builder
+            }
+
+            /// Build `builder` with these arguments [applied](Self::apply).
+            /// With `--print-config`, print the
+            /// [sanitized configuration](Self::sanitized_config) as JSON and
+            /// exit instead.
+            #[allow(dead_code)]
+            pub fn load<T>(&self, builder: confers::ConfigBuilder<T>) -> confers::ConfigResult<T>
+            where
+                T: serde::de::DeserializeOwned + Default,
+            {
+                if self.print_config {
+                    let config = self.sanitized_config(builder)?;
+                    println!(
+                        "{}",
+                        serde_json::to_string_pretty(&config).unwrap_or_else(|_| config.to_string())
+                    );
+                    std::process::exit(0);
+                }
+                self.apply(builder).build()
+            }
+
+            /// The merged configuration `--print-config` prints: sensitive
+            /// fields and values that look like secrets are redacted.
+            #[allow(dead_code)]
+            pub fn sanitized_config<T>(
+                &self,
+                builder: confers::ConfigBuilder<T>,
+            ) -> confers::ConfigResult<serde_json::Value>
+            where
+                T: serde::de::DeserializeOwned + Default,
+            {
+                let tree = self.apply(builder).build_annotated()?;
+                Ok(confers::__private::sanitized_config(&tree, &[#(#sensitive_paths),*]))
+            }
         }
     }
 }
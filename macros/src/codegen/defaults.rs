@@ -5,46 +5,39 @@
 
 //! Default value generation for Config derive macro.
 
+use darling::FromField;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Ident, Type};
+use syn::{parse_quote, Fields, Generics, Ident, Type, Variant};
 
+use super::split_generics;
 use crate::parse::FieldAttrs;
 
 /// Generate default implementation for a struct.
+///
+/// On generic structs, fields falling back to `Default::default()` require
+/// their type to implement `Default`.
 pub fn generate_defaults_impl(
     struct_ident: &Ident,
+    generics: &Generics,
     fields: &[(&Ident, &Type, FieldAttrs)],
 ) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) = split_generics(
+        generics,
+        default_bounds(fields.iter().map(|(_, ty, attrs)| (*ty, attrs))),
+    );
     let field_inits: Vec<TokenStream> = fields
         .iter()
         .map(|(ident, ty, attrs)| {
-            if let Some(ref default_expr) = attrs.default {
-                // Use provided default expression
-                quote! {
-                    #ident: #default_expr
-                }
-            } else if crate::parse::is_option_type(ty) {
-                // Option<T> defaults to None
-                quote! {
-                    #ident: None
-                }
-            } else if crate::parse::is_vec_type(ty) {
-                // Vec<T> defaults to empty
-                quote! {
-                    #ident: Vec::new()
-                }
-            } else {
-                // Try Default::default()
-                quote! {
-                    #ident: Default::default()
-                }
+            let value = default_value(ty, attrs);
+            quote! {
+                #ident: #value
             }
         })
         .collect();
 
     quote! {
-        impl Default for #struct_ident {
+        impl #impl_generics Default for #struct_ident #ty_generics #where_clause {
             fn default() -> Self {
                 Self {
                     #(#field_inits),*
@@ -54,6 +47,86 @@ pub fn generate_defaults_impl(
     }
 }
 
+/// Generate the `Default` implementation of an enum from the variant marked
+/// `#[config(default)]`, whose fields take their `#[config(default = ...)]`
+/// values like struct fields do.
+pub fn generate_enum_defaults_impl(
+    enum_ident: &Ident,
+    generics: &Generics,
+    variant: &Variant,
+) -> syn::Result<TokenStream> {
+    let variant_ident = &variant.ident;
+    let field_attrs = variant
+        .fields
+        .iter()
+        .map(|field| {
+            FieldAttrs::from_field(field)
+                .map(|attrs| (field, attrs))
+                .map_err(|e| syn::Error::new_spanned(field, e.to_string()))
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+    let values: Vec<_> = field_attrs
+        .iter()
+        .map(|(field, attrs)| (field.ident.as_ref(), default_value(&field.ty, attrs)))
+        .collect();
+    let (impl_generics, ty_generics, where_clause) = split_generics(
+        generics,
+        default_bounds(field_attrs.iter().map(|(field, attrs)| (&field.ty, attrs))),
+    );
+
+    let value = match &variant.fields {
+        Fields::Named(_) => {
+            let inits = values
+                .iter()
+                .map(|(ident, value)| quote! { #ident: #value });
+            quote! { Self::#variant_ident { #(#inits),* } }
+        }
+        Fields::Unnamed(_) => {
+            let inits = values.iter().map(|(_, value)| value);
+            quote! { Self::#variant_ident(#(#inits),*) }
+        }
+        Fields::Unit => quote! { Self::#variant_ident },
+    };
+
+    Ok(quote! {
+        impl #impl_generics Default for #enum_ident #ty_generics #where_clause {
+            fn default() -> Self {
+                #value
+            }
+        }
+    })
+}
+
+/// `Type: Default` for each field whose default is `Default::default()`.
+fn default_bounds<'a>(
+    fields: impl Iterator<Item = (&'a Type, &'a FieldAttrs)> + 'a,
+) -> impl Iterator<Item = syn::WherePredicate> + 'a {
+    fields
+        .filter(|(ty, attrs)| {
+            attrs.default.is_none()
+                && !crate::parse::is_option_type(ty)
+                && !crate::parse::is_vec_type(ty)
+        })
+        .map(|(ty, _)| parse_quote! { #ty: Default })
+}
+
+/// The default value expression of one field.
+fn default_value(ty: &Type, attrs: &FieldAttrs) -> TokenStream {
+    if let Some(ref default_expr) = attrs.default {
+        // Use provided default expression
+        quote! { #default_expr }
+    } else if crate::parse::is_option_type(ty) {
+        // Option<T> defaults to None
+        quote! { None }
+    } else if crate::parse::is_vec_type(ty) {
+        // Vec<T> defaults to empty
+        quote! { Vec::new() }
+    } else {
+        // Try Default::default()
+        quote! { Default::default() }
+    }
+}
+
 /// Generate a function to check if a field has an explicit default
 #[allow(dead_code)]
 pub fn has_explicit_default(attrs: &FieldAttrs) -> bool {
@@ -8,9 +8,10 @@
 use darling::FromField;
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
-use syn::{Fields, Ident};
+use syn::{parse_quote, Fields, Ident};
 
-use crate::parse::{FieldAttrs, StructAttrs};
+use super::split_generics;
+use crate::parse::{map_value_type, FieldAttrs, StructAttrs};
 
 /// Generate the load methods for a struct.
 pub fn generate_load_impl(
@@ -44,13 +45,56 @@ pub fn generate_load_impl(
     let load_file_impl = generate_load_file_method(struct_ident, attrs, &field_info);
 
     // Generate env_mapping() method
-    let env_mapping_impl = generate_env_mapping(struct_ident, env_prefix, &field_info);
+    let env_mapping_impl = generate_env_mapping(struct_ident, attrs, env_prefix, &field_info);
+
+    // Let structs holding this one list its environment variables
+    let env_paths_impl = generate_env_paths_impl(struct_ident, attrs, &field_info);
 
     quote! {
         #load_impl
         #load_sync_impl
         #load_file_impl
         #env_mapping_impl
+        #env_paths_impl
+    }
+}
+
+/// Generate the load methods for an enum.
+///
+/// Variants have no fixed keys, so there are no per-field defaults or
+/// environment variables: `load_sync()` reads `<env_prefix>*` variables when a
+/// prefix is set, and the file loaders behave as they do for structs.
+pub fn generate_enum_load_impl(enum_ident: &Ident, attrs: &StructAttrs) -> TokenStream {
+    let profile_call = generate_profile_call(attrs);
+    let build_call = generate_build_call(attrs, &[]);
+    let env_call = attrs.env_prefix.as_ref().map(|prefix| {
+        quote! {
+            builder = builder.env_prefix(#prefix);
+        }
+    });
+    let load_file_impl = generate_load_file_method(enum_ident, attrs, &[]);
+    let (impl_generics, ty_generics, where_clause) = load_generics(attrs);
+
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Load configuration from all sources.
+            pub fn load() -> impl std::future::Future<Output = confers::ConfigResult<Self>> {
+                async {
+                    Self::load_sync()
+                }
+            }
+
+            /// Load configuration synchronously.
+            pub fn load_sync() -> confers::ConfigResult<Self> {
+                #[allow(unused_mut)]
+                let mut builder = confers::ConfigBuilder::<Self>::new();
+                #profile_call
+                #env_call
+                #build_call
+            }
+        }
+
+        #load_file_impl
     }
 }
 
@@ -118,10 +162,17 @@ fn generate_load_method(
                 }
             }
         })
+        .chain(alias_env_calls(env_prefix, fields))
+        .chain(map_env_calls(env_prefix, fields))
+        .chain(nested_env_calls(env_prefix, fields))
         .collect();
 
+    let profile_call = generate_profile_call(attrs);
+    let build_call = generate_build_call(attrs, fields);
+    let (impl_generics, ty_generics, where_clause) = load_generics(attrs);
+
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             /// Load configuration from all sources.
             ///
             /// This method loads configuration in priority order:
@@ -137,6 +188,7 @@ fn generate_load_method(
             /// Load configuration synchronously.
             pub fn load_sync() -> confers::ConfigResult<Self> {
                 let mut builder = confers::ConfigBuilder::<Self>::new();
+                #profile_call
 
                 // Add defaults first (lowest priority)
                 #(#default_calls)*
@@ -148,7 +200,7 @@ fn generate_load_method(
                     builder = builder.memory(env_map);
                 }
 
-                builder.build()
+                #build_call
             }
         }
     }
@@ -218,13 +270,21 @@ fn generate_load_sync_method(
                 }
             }
         })
+        .chain(alias_env_calls(env_prefix, fields))
+        .chain(map_env_calls(env_prefix, fields))
+        .chain(nested_env_calls(env_prefix, fields))
         .collect();
 
+    let profile_call = generate_profile_call(attrs);
+    let build_call = generate_build_call(attrs, fields);
+    let (impl_generics, ty_generics, where_clause) = load_generics(attrs);
+
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             /// Build configuration with environment variables and defaults.
             pub fn build_config() -> confers::ConfigResult<Self> {
                 let mut builder = confers::ConfigBuilder::<Self>::new();
+                #profile_call
 
                 // Add defaults first (lowest priority)
                 #(#default_calls)*
@@ -236,65 +296,327 @@ fn generate_load_sync_method(
                     builder = builder.memory(env_map);
                 }
 
-                builder.build()
+                #build_call
+            }
+        }
+    }
+}
+
+/// Build `builder`, registering field aliases, rejecting unknown keys with
+/// `deny_unknown` and running the `validate_with` checks when they are set.
+fn generate_build_call(
+    attrs: &StructAttrs,
+    fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> TokenStream {
+    let alias_calls = fields
+        .iter()
+        .filter(|(_, _, f)| !f.skip)
+        .flat_map(|(_, _, f)| {
+            let config_key = f.effective_name();
+            f.alias.iter().map(move |old| match &f.deprecated_since {
+                Some(since) => quote! { .with_deprecated_alias(#old, #config_key, #since) },
+                None => quote! { .with_alias(#old, #config_key) },
+            })
+        });
+    let deny_unknown = if attrs.deny_unknown {
+        quote! { .with_deny_unknown(true) }
+    } else {
+        quote! {}
+    };
+    let sensitive_keys: Vec<String> = fields
+        .iter()
+        .filter(|(_, _, f)| !f.skip && f.is_sensitive_effective())
+        .map(|(_, _, f)| f.effective_name())
+        .collect();
+    let sensitive = if sensitive_keys.is_empty() {
+        quote! {}
+    } else {
+        quote! { .with_sensitive_keys([#(#sensitive_keys),*]) }
+    };
+    let builder = quote! { builder #(#alias_calls)* #deny_unknown #sensitive };
+    let mut checks = Vec::new();
+    if attrs.validate_with_path().is_some() {
+        checks.push(quote! { config.validate_fields()?; });
+    }
+    if fields
+        .iter()
+        .any(|(_, ty, f)| !f.skip && map_value_type(ty).is_some())
+    {
+        checks.push(quote! { config.validate_entries()?; });
+    }
+    if checks.is_empty() {
+        quote! { #builder.build() }
+    } else {
+        quote! {
+            #builder.build().and_then(|config| {
+                #(#checks)*
+                Ok(config)
+            })
+        }
+    }
+}
+
+/// Read the environment variables named after field aliases into `env_map`
+/// under the old key, so the builder can report them as deprecated.
+fn alias_env_calls<'a>(
+    env_prefix: &'a str,
+    fields: &'a [(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> impl Iterator<Item = TokenStream> + 'a {
+    fields
+        .iter()
+        .filter(|(_, _, f)| !f.skip && f.name_env.is_none())
+        .flat_map(move |(_, _, f)| {
+            f.alias.iter().map(move |old| {
+                let env_name = format!("{}{}", env_prefix, old.to_uppercase().replace('.', "_"));
+                quote! {
+                    if let Ok(val) = std::env::var(#env_name) {
+                        env_map.insert(#old.to_string(), confers::EnvSource::infer_config_value(&val));
+                    }
+                }
+            })
+        })
+}
+
+/// Read the entries of map fields from the environment into `env_map`:
+/// `APP_DATABASES__PRIMARY__HOST` sets `databases.primary.host`, each `__`
+/// separating one level below the field.
+fn map_env_calls<'a>(
+    env_prefix: &'a str,
+    fields: &'a [(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> impl Iterator<Item = TokenStream> + 'a {
+    fields
+        .iter()
+        .filter(|(_, ty, f)| !f.skip && map_value_type(ty).is_some())
+        .map(move |(_, _, f)| {
+            let entry_prefix = format!("{}__", f.effective_env_name(env_prefix));
+            let config_key = f.effective_name();
+            quote! {
+                for (name, val) in std::env::vars() {
+                    if let Some(path) = name.strip_prefix(#entry_prefix).filter(|path| !path.is_empty()) {
+                        let path: Vec<String> = path.split("__").map(str::to_lowercase).collect();
+                        env_map.insert(
+                            format!("{}.{}", #config_key, path.join(".")),
+                            confers::EnvSource::infer_config_value(&val),
+                        );
+                    }
+                }
+            }
+        })
+}
+
+/// The `Option<Vec<EnvPath>>` of a field whose type is itself a `Config`
+/// struct, with its paths placed under the field; `None` for plain values.
+fn nested_env_paths(ty: &syn::Type, f: &FieldAttrs) -> TokenStream {
+    let config_key = f.effective_name();
+    let segment = f.env_segment();
+    let absolute = f.explicit_env_name().is_some();
+    let flatten = f.is_flattened();
+    quote! {
+        {
+            #[allow(unused_imports)]
+            use confers::__private::{NestedEnvPaths as _, NoNestedEnvPaths as _};
+            (&&confers::__private::EnvProbe::<#ty>::new())
+                .nested_env_paths()
+                .map(|paths| {
+                    paths
+                        .into_iter()
+                        .map(|path| path.under(#config_key, #segment, #absolute, #flatten))
+                        .collect::<Vec<_>>()
+                })
+        }
+    }
+}
+
+/// Fields that may hold a nested `Config` struct.
+fn nestable<'a>(
+    fields: &'a [(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> impl Iterator<Item = &'a (&'a syn::Ident, &'a syn::Type, FieldAttrs)> {
+    fields
+        .iter()
+        .filter(|(_, ty, f)| !f.skip && map_value_type(ty).is_none())
+}
+
+/// Read the variables of nested and flattened `Config` structs into
+/// `env_map`: `APP_DB_POOL_MAX` sets `db.pool_max`.
+fn nested_env_calls<'a>(
+    env_prefix: &'a str,
+    fields: &'a [(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> impl Iterator<Item = TokenStream> + 'a {
+    nestable(fields).map(move |(_, ty, f)| {
+        let paths = nested_env_paths(ty, f);
+        quote! {
+            for path in #paths.unwrap_or_default() {
+                if let Ok(val) = std::env::var(path.env_name(#env_prefix)) {
+                    env_map.insert(path.key, confers::EnvSource::infer_config_value(&val));
+                }
+            }
+        }
+    })
+}
+
+/// Implement `EnvPaths`, so that structs holding this one can read its
+/// variables under their own names.
+fn generate_env_paths_impl(
+    struct_ident: &Ident,
+    attrs: &StructAttrs,
+    fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> TokenStream {
+    let pushes = nestable(fields).map(|(_, ty, f)| {
+        let paths = nested_env_paths(ty, f);
+        let config_key = f.effective_name();
+        let segment = f.env_segment();
+        let absolute = f.explicit_env_name().is_some();
+        quote! {
+            match #paths {
+                Some(nested) => paths.extend(nested),
+                None => paths.push(confers::__private::EnvPath::leaf(#config_key, #segment, #absolute)),
             }
         }
+    });
+    let (impl_generics, ty_generics, where_clause) = split_generics(&attrs.generics, []);
+
+    quote! {
+        impl #impl_generics confers::__private::EnvPaths for #struct_ident #ty_generics #where_clause {
+            fn env_paths() -> Vec<confers::__private::EnvPath> {
+                #[allow(unused_mut)]
+                let mut paths = Vec::new();
+                #(#pushes)*
+                paths
+            }
+        }
+    }
+}
+
+/// Impl generics for the load methods, which need `ConfigBuilder<Self>::build`.
+fn load_generics(attrs: &StructAttrs) -> (TokenStream, TokenStream, TokenStream) {
+    split_generics(
+        &attrs.generics,
+        [parse_quote! { Self: serde::de::DeserializeOwned + Default }],
+    )
+}
+
+/// Select the active profile on `builder` when `#[config(profiles)]` is set.
+fn generate_profile_call(attrs: &StructAttrs) -> TokenStream {
+    if attrs.profiles_enabled() {
+        quote! {
+            builder = builder.with_profile(Self::active_profile());
+        }
+    } else {
+        quote! {}
     }
 }
 
 /// Generate the load_file() method
 fn generate_load_file_method(
     struct_ident: &Ident,
-    _attrs: &StructAttrs,
-    _fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
+    attrs: &StructAttrs,
+    fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
 ) -> TokenStream {
+    let profile_call = generate_profile_call(attrs);
+    let build_call = generate_build_call(attrs, fields);
+
+    let profile_methods = if attrs.profiles_enabled() {
+        let profile_env = attrs.effective_profile_env();
+        quote! {
+            /// The profile named by the profile environment variable, or
+            /// `"default"` when it is unset.
+            pub fn active_profile() -> String {
+                std::env::var(#profile_env)
+                    .ok()
+                    .filter(|profile| !profile.is_empty())
+                    .unwrap_or_else(|| "default".to_string())
+            }
+
+            /// Load configuration from a specific file with an explicit profile
+            /// and environment overrides.
+            pub fn load_file_with_profile(
+                path: impl AsRef<std::path::Path>,
+                profile: &str,
+            ) -> confers::ConfigResult<Self> {
+                let builder = confers::ConfigBuilder::<Self>::new()
+                    .file(path.as_ref())
+                    .env()
+                    .with_profile(profile);
+                #build_call
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let (impl_generics, ty_generics, where_clause) = load_generics(attrs);
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             /// Load configuration from a specific file.
             pub fn load_file(path: impl AsRef<std::path::Path>) -> confers::ConfigResult<Self> {
-                let builder = confers::ConfigBuilder::<Self>::new()
+                #[allow(unused_mut)]
+                let mut builder = confers::ConfigBuilder::<Self>::new()
                     .file(path.as_ref());
-                builder.build()
+                #profile_call
+                #build_call
             }
 
             /// Load configuration from a specific file with environment overrides.
             pub fn load_file_with_env(path: impl AsRef<std::path::Path>) -> confers::ConfigResult<Self> {
-                let builder = confers::ConfigBuilder::<Self>::new()
+                #[allow(unused_mut)]
+                let mut builder = confers::ConfigBuilder::<Self>::new()
                     .file(path.as_ref())
                     .env();
-                builder.build()
+                #profile_call
+                #build_call
             }
+
+            #profile_methods
         }
     }
 }
 
 /// Generate the env_mapping() method
+///
+/// Fields holding a nested `Config` struct list one entry per nested key,
+/// e.g. `("db", "db.pool.max", "APP_DB_POOL_MAX")`.
 fn generate_env_mapping(
     struct_ident: &Ident,
+    attrs: &StructAttrs,
     env_prefix: &str,
     fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
 ) -> TokenStream {
     let mappings: Vec<TokenStream> = fields
         .iter()
         .filter(|(_, _, f)| !f.skip)
-        .map(|(ident, _, f)| {
+        .map(|(ident, ty, f)| {
             let config_key = f.effective_name();
             let env_name = f.effective_env_name(env_prefix);
             let field_name = ident.to_string();
+            let leaf = quote! {
+                mapping.push((#field_name.to_string(), #config_key.to_string(), #env_name.to_string()));
+            };
+            if map_value_type(ty).is_some() {
+                return leaf;
+            }
 
+            let paths = nested_env_paths(ty, f);
             quote! {
-                (#field_name.to_string(), #config_key.to_string(), #env_name.to_string())
+                match #paths {
+                    Some(nested) => mapping.extend(nested.into_iter().map(|path| {
+                        let env_name = path.env_name(#env_prefix);
+                        (#field_name.to_string(), path.key, env_name)
+                    })),
+                    None => { #leaf }
+                }
             }
         })
         .collect();
 
+    let (impl_generics, ty_generics, where_clause) = split_generics(&attrs.generics, []);
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             /// Get the mapping of field names to configuration keys and environment variables.
             pub fn env_mapping() -> Vec<(String, String, String)> {
-                vec![
-                    #(#mappings),*
-                ]
+                #[allow(unused_mut)]
+                let mut mapping = Vec::new();
+                #(#mappings)*
+                mapping
             }
         }
     }
@@ -5,6 +5,10 @@
 
 //! Code generation modules for the Config derive macro.
 
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Generics, WherePredicate};
+
 mod clap;
 mod defaults;
 mod load;
@@ -13,6 +17,7 @@ mod modules;
 mod schema;
 mod security;
 mod validate;
+mod watch;
 
 pub use clap::*;
 pub use defaults::*;
@@ -23,3 +28,25 @@ pub use schema::*;
 #[allow(unused_imports)]
 pub(crate) use security::*;
 pub use validate::*;
+pub use watch::*;
+
+/// The impl generics, type generics and where clause of an impl for a type
+/// declared with `generics`.
+///
+/// `bounds` are what the impl needs from the type parameters; they are only
+/// added when the type has any, so impls of non-generic types stay plain.
+pub(crate) fn split_generics(
+    generics: &Generics,
+    bounds: impl IntoIterator<Item = WherePredicate>,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let mut generics = generics.clone();
+    if generics.type_params().next().is_some() {
+        generics.make_where_clause().predicates.extend(bounds);
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    (
+        quote! { #impl_generics },
+        quote! { #ty_generics },
+        quote! { #where_clause },
+    )
+}
@@ -10,26 +10,43 @@
 use darling::FromField;
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{Fields, Ident, Type};
+use syn::meta::ParseNestedMeta;
+use syn::{token, Attribute, DataEnum, Expr, Field, Fields, Generics, Ident, LitStr, Token, Type};
 
-use crate::parse::{FieldAttrs, StructAttrs};
+use super::split_generics;
+use crate::parse::{
+    doc_comment, map_value_type, EnumTagging, FieldAttrs, SerdeEnumAttrs, StructAttrs,
+};
 
 /// Generate JSON Schema for a configuration struct.
 pub fn generate_schema_impl(
     struct_ident: &Ident,
-    _attrs: &StructAttrs,
+    attrs: &StructAttrs,
     fields: &Fields,
 ) -> TokenStream {
     let field_schemas = generate_field_schemas(fields);
+    let (impl_generics, ty_generics, where_clause) =
+        split_generics(&attrs.generics, schema_bounds(&attrs.generics));
+    let description = attrs
+        .doc_comment()
+        .map(|description| quote! { "description": #description, });
 
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             /// Generate JSON Schema for this configuration struct.
+            ///
+            /// Field descriptions, `#[config(default = ...)]` values and
+            /// `#[garde(...)]` constraints are included, and sensitive fields
+            /// are marked `writeOnly`, so the schema can drive
+            /// `confers generate` and `confers wizard`.
             pub fn json_schema() -> serde_json::Value {
+                let mut properties = serde_json::Map::new();
+                #field_schemas
                 serde_json::json!({
                     "type": "object",
                     "title": stringify!(#struct_ident),
-                    "properties": { #field_schemas }
+                    #description
+                    "properties": properties
                 })
             }
 
@@ -41,10 +58,153 @@ pub fn generate_schema_impl(
                 )
             }
         }
+
+        impl #impl_generics confers::ConfigSchema for #struct_ident #ty_generics #where_clause {
+            fn json_schema() -> serde_json::Value {
+                Self::json_schema()
+            }
+        }
     }
 }
 
-/// Generate schema for each field.
+/// Generate JSON Schema for a configuration enum: a `oneOf` with one schema
+/// per variant, laid out the way the enum's `#[serde(...)]` tagging reads it.
+pub fn generate_enum_schema_impl(
+    enum_ident: &Ident,
+    generics: &Generics,
+    attrs: &[Attribute],
+    data: &DataEnum,
+) -> TokenStream {
+    let (impl_generics, ty_generics, where_clause) =
+        split_generics(generics, schema_bounds(generics));
+    let description = doc_comment(attrs).map(|description| quote! { "description": #description, });
+    let serde = SerdeEnumAttrs::from_attrs(attrs);
+    let variants: Vec<TokenStream> = data
+        .variants
+        .iter()
+        .filter(|variant| !crate::parse::serde_skipped(&variant.attrs))
+        .map(|variant| variant_schema(&serde, variant))
+        .collect();
+
+    quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            /// Generate JSON Schema for this configuration enum, one `oneOf`
+            /// entry per variant.
+            pub fn json_schema() -> serde_json::Value {
+                let variants: Vec<serde_json::Value> = vec![#(#variants),*];
+                serde_json::json!({
+                    "title": stringify!(#enum_ident),
+                    #description
+                    "oneOf": variants
+                })
+            }
+
+            /// Generate TypeScript type definition for this configuration enum.
+            pub fn typescript_type() -> String {
+                format!(
+                    "export type {} = /* variants */ unknown;",
+                    stringify!(#enum_ident)
+                )
+            }
+        }
+
+        impl #impl_generics confers::ConfigSchema for #enum_ident #ty_generics #where_clause {
+            fn json_schema() -> serde_json::Value {
+                Self::json_schema()
+            }
+        }
+    }
+}
+
+/// Type parameters must describe themselves, so that fields of a parameter
+/// type embed its schema.
+fn schema_bounds(generics: &Generics) -> Vec<syn::WherePredicate> {
+    generics
+        .type_params()
+        .map(|param| {
+            let ident = &param.ident;
+            syn::parse_quote! { #ident: confers::ConfigSchema }
+        })
+        .collect()
+}
+
+/// The schema expression of one enum variant.
+fn variant_schema(serde: &SerdeEnumAttrs, variant: &syn::Variant) -> TokenStream {
+    let name = serde.variant_name(variant);
+    let name_schema = quote! { serde_json::json!({ "type": "string", "enum": [#name] }) };
+    let payload = match &variant.fields {
+        Fields::Unit => None,
+        Fields::Named(_) => {
+            let field_schemas = generate_field_schemas(&variant.fields);
+            Some(quote! {
+                {
+                    let mut properties = serde_json::Map::new();
+                    #field_schemas
+                    serde_json::json!({ "type": "object", "properties": properties })
+                }
+            })
+        }
+        Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+            Some(generate_type_schema(&fields.unnamed[0].ty))
+        }
+        Fields::Unnamed(_) => Some(quote! { serde_json::json!({ "type": "array" }) }),
+    };
+
+    match (&serde.tagging, payload) {
+        (EnumTagging::External, None) => name_schema,
+        (EnumTagging::External, Some(payload)) => quote! {
+            {
+                let payload: serde_json::Value = #payload;
+                serde_json::json!({
+                    "type": "object",
+                    "properties": { #name: payload },
+                    "required": [#name]
+                })
+            }
+        },
+        (EnumTagging::Internal { tag }, payload) => {
+            let payload = payload.unwrap_or_else(|| quote! { serde_json::json!({}) });
+            quote! {
+                {
+                    let mut schema = #payload;
+                    if let serde_json::Value::Object(schema) = &mut schema {
+                        schema.insert("type".to_string(), serde_json::json!("object"));
+                        if let Some(properties) = schema
+                            .entry("properties")
+                            .or_insert_with(|| serde_json::json!({}))
+                            .as_object_mut()
+                        {
+                            properties.insert(#tag.to_string(), #name_schema);
+                        }
+                        schema.insert("required".to_string(), serde_json::json!([#tag]));
+                    }
+                    schema
+                }
+            }
+        }
+        (EnumTagging::Adjacent { tag, .. }, None) => quote! {
+            serde_json::json!({
+                "type": "object",
+                "properties": { #tag: #name_schema },
+                "required": [#tag]
+            })
+        },
+        (EnumTagging::Adjacent { tag, content }, Some(payload)) => quote! {
+            {
+                let payload: serde_json::Value = #payload;
+                serde_json::json!({
+                    "type": "object",
+                    "properties": { #tag: #name_schema, #content: payload },
+                    "required": [#tag, #content]
+                })
+            }
+        },
+        (EnumTagging::Untagged, None) => quote! { serde_json::json!({ "type": "null" }) },
+        (EnumTagging::Untagged, Some(payload)) => payload,
+    }
+}
+
+/// Generate the statements inserting each field's schema into `properties`.
 fn generate_field_schemas(fields: &Fields) -> TokenStream {
     let field_defs: Vec<TokenStream> = fields
         .iter()
@@ -58,56 +218,252 @@ fn generate_field_schemas(fields: &Fields) -> TokenStream {
             let field_name = attrs.effective_name();
             let field_type = &field.ty;
             let schema = generate_type_schema(field_type);
+            let description = attrs.effective_description().map(|description| {
+                quote! {
+                    schema.insert("description".to_string(), serde_json::json!(#description));
+                }
+            });
+            let default = attrs.default.as_ref().map(|default_expr| {
+                quote! {
+                    schema.insert(
+                        "default".to_string(),
+                        serde_json::to_value(#default_expr).unwrap_or(serde_json::Value::Null),
+                    );
+                }
+            });
+
+            // Sensitive values can be set but are never shown back
+            let write_only = attrs.is_sensitive_effective().then(|| {
+                quote! {
+                    schema.insert("writeOnly".to_string(), serde_json::Value::Bool(true));
+                }
+            });
+
+            let constraints = garde_constraints(field)
+                .into_iter()
+                .map(|(keyword, value)| {
+                    quote! {
+                        schema.insert(#keyword.to_string(), serde_json::json!(#value));
+                    }
+                });
 
             Some(quote! {
-                #field_name: #schema
+                {
+                    let mut schema = match #schema {
+                        serde_json::Value::Object(schema) => schema,
+                        _ => serde_json::Map::new(),
+                    };
+                    #description
+                    #default
+                    #write_only
+                    #(#constraints)*
+                    properties.insert(#field_name.to_string(), serde_json::Value::Object(schema));
+                }
             })
         })
         .collect();
 
-    quote! { #(#field_defs),* }
+    quote! { #(#field_defs)* }
 }
 
-/// Generate JSON Schema for a Rust type.
-fn generate_type_schema(ty: &Type) -> TokenStream {
-    let type_str = quote!(#ty).to_string();
-
-    // Handle common types
-    if type_str.contains("String") || type_str.contains("str") {
-        return quote! { { "type": "string" } };
-    }
-    if type_str.contains("i8")
-        || type_str.contains("i16")
-        || type_str.contains("i32")
-        || type_str.contains("i64")
-        || type_str.contains("isize")
+/// Map a field's `#[garde(...)]` rules to JSON Schema keywords.
+///
+/// `range(min, max)` becomes `minimum`/`maximum`, `length(min, max)` becomes
+/// `minLength`/`maxLength` (`minItems`/`maxItems` for `Vec`), `email` and
+/// `url` become `format: "email"` / `format: "uri"`, and a literal
+/// `pattern("...")` becomes `pattern`. Other rules have no schema equivalent
+/// and are ignored, as are attributes that do not parse.
+fn garde_constraints(field: &Field) -> Vec<(&'static str, TokenStream)> {
+    let is_array = crate::parse::is_vec_type(&field.ty);
+    let mut constraints = Vec::new();
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("garde"))
     {
-        return quote! { { "type": "integer" } };
+        let mut found = Vec::new();
+        let parsed = attr.parse_nested_meta(|meta| {
+            let bounds = if meta.path.is_ident("range") {
+                Some(("minimum", "maximum"))
+            } else if meta.path.is_ident("length") {
+                Some(if is_array {
+                    ("minItems", "maxItems")
+                } else {
+                    ("minLength", "maxLength")
+                })
+            } else {
+                None
+            };
+            if let Some((min_keyword, max_keyword)) = bounds {
+                return meta.parse_nested_meta(|bound| {
+                    if bound.path.is_ident("min") {
+                        let value: Expr = bound.value()?.parse()?;
+                        found.push((min_keyword, quote!(#value)));
+                    } else if bound.path.is_ident("max") {
+                        let value: Expr = bound.value()?.parse()?;
+                        found.push((max_keyword, quote!(#value)));
+                    } else {
+                        skip_rule_arguments(&bound)?;
+                    }
+                    Ok(())
+                });
+            }
+            if meta.path.is_ident("email") {
+                found.push(("format", quote!("email")));
+            } else if meta.path.is_ident("url") {
+                found.push(("format", quote!("uri")));
+            } else if meta.path.is_ident("pattern") && meta.input.peek(token::Paren) {
+                // Only literal patterns can be copied; a `Regex` static is skipped
+                let content;
+                syn::parenthesized!(content in meta.input);
+                if let Ok(pattern) = content.parse::<LitStr>() {
+                    found.push(("pattern", quote!(#pattern)));
+                }
+                let _: TokenStream = content.parse()?;
+                return Ok(());
+            }
+            skip_rule_arguments(&meta)
+        });
+        if parsed.is_ok() {
+            constraints.extend(found);
+        }
     }
-    if type_str.contains("u8")
-        || type_str.contains("u16")
-        || type_str.contains("u32")
-        || type_str.contains("u64")
-        || type_str.contains("usize")
-    {
-        return quote! { { "type": "integer", "minimum": 0 } };
+    constraints
+}
+
+/// Consume the `= value` or `(...)` following a rule this module ignores.
+fn skip_rule_arguments(meta: &ParseNestedMeta) -> syn::Result<()> {
+    if meta.input.peek(Token![=]) {
+        let _: Expr = meta.value()?.parse()?;
+    } else if meta.input.peek(token::Paren) {
+        let _: proc_macro2::Group = meta.input.parse()?;
     }
-    if type_str.contains("f32") || type_str.contains("f64") {
-        return quote! { { "type": "number" } };
+    Ok(())
+}
+
+/// Generate an expression producing the JSON Schema of a Rust type.
+///
+/// Types are recognised by their last path segment, so `Distribution` or
+/// `StringPool` are not mistaken for strings. Types not recognised use their
+/// `ConfigSchema` implementation when they have one (nested
+/// `#[derive(ConfigSchema)]` structs and enums), and are described as strings
+/// otherwise.
+fn generate_type_schema(ty: &Type) -> TokenStream {
+    if let Some(value_ty) = map_value_type(ty) {
+        let values = generate_type_schema(value_ty);
+        return quote! {
+            {
+                let values: serde_json::Value = #values;
+                serde_json::json!({ "type": "object", "additionalProperties": values })
+            }
+        };
     }
-    if type_str.contains("bool") {
-        return quote! { { "type": "boolean" } };
+
+    let segment = match ty {
+        Type::Reference(reference) => return generate_type_schema(&reference.elem),
+        Type::Array(array) => return array_schema(&array.elem),
+        Type::Slice(slice) => return array_schema(&slice.elem),
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    };
+    let (name, inner) = match segment {
+        Some(segment) => (
+            segment.ident.to_string(),
+            crate::parse::extract_inner_type(ty),
+        ),
+        None => (String::new(), None),
+    };
+
+    match (name.as_str(), inner) {
+        ("String" | "str" | "char" | "PathBuf" | "Path", _) => {
+            quote! { serde_json::json!({ "type": "string" }) }
+        }
+        ("i8" | "i16" | "i32" | "i64" | "i128" | "isize", _) => {
+            quote! { serde_json::json!({ "type": "integer" }) }
+        }
+        ("u8" | "u16" | "u32" | "u64" | "u128" | "usize", _) => {
+            quote! { serde_json::json!({ "type": "integer", "minimum": 0 }) }
+        }
+        ("f32" | "f64", _) => quote! { serde_json::json!({ "type": "number" }) },
+        ("bool", _) => quote! { serde_json::json!({ "type": "boolean" }) },
+        ("Vec" | "VecDeque" | "HashSet" | "BTreeSet" | "IndexSet", Some(item)) => {
+            array_schema(item)
+        }
+        ("Box" | "Arc" | "Rc", Some(inner)) => generate_type_schema(inner),
+        ("Option", Some(inner)) => {
+            let inner = generate_type_schema(inner);
+            quote! {
+                {
+                    let mut schema: serde_json::Value = #inner;
+                    match schema.get("type").cloned() {
+                        Some(serde_json::Value::String(ty)) => {
+                            schema["type"] = serde_json::json!([ty, "null"]);
+                            schema
+                        }
+                        _ => serde_json::json!({ "anyOf": [schema, { "type": "null" }] }),
+                    }
+                }
+            }
+        }
+        _ => quote! {
+            {
+                #[allow(unused_imports)]
+                use confers::__private::{NestedSchema as _, NoNestedSchema as _};
+                (&&confers::__private::SchemaProbe::<#ty>::new())
+                    .nested_schema()
+                    .unwrap_or_else(|| serde_json::json!({ "type": "string" }))
+            }
+        },
     }
-    if type_str.contains("Vec") || type_str.contains("Array") {
-        return quote! { { "type": "array" } };
+}
+
+/// The schema of a sequence of `item`.
+fn array_schema(item: &Type) -> TokenStream {
+    let items = generate_type_schema(item);
+    quote! {
+        {
+            let items: serde_json::Value = #items;
+            serde_json::json!({ "type": "array", "items": items })
+        }
     }
-    if type_str.contains("HashMap") || type_str.contains("Map") || type_str.contains("BTreeMap") {
-        return quote! { { "type": "object" } };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn schema_of(ty: Type) -> String {
+        generate_type_schema(&ty).to_string()
     }
-    if type_str.contains("Option") {
-        return quote! { { "type": ["string", "null"] } };
+
+    #[test]
+    fn test_type_names_are_matched_whole() {
+        // Names merely containing a primitive's name are nested types
+        for ty in [
+            parse_quote!(Distribution),
+            parse_quote!(StringPool),
+            parse_quote!(Vector3),
+            parse_quote!(Optional),
+            parse_quote!(Bu8fer),
+        ] {
+            assert!(schema_of(ty).contains("SchemaProbe"));
+        }
+
+        assert!(schema_of(parse_quote!(std::string::String)).contains("\"string\""));
+        assert!(schema_of(parse_quote!(u16)).contains("\"minimum\""));
+        assert!(schema_of(parse_quote!(&'static str)).contains("\"string\""));
     }
 
-    // Default to string for unknown types
-    quote! { { "type": "string" } }
+    #[test]
+    fn test_wrapper_types_describe_their_contents() {
+        let vec = schema_of(parse_quote!(Vec<u32>));
+        assert!(vec.contains("\"array\"") && vec.contains("\"items\""));
+        assert!(vec.contains("\"minimum\""));
+
+        let option = schema_of(parse_quote!(Option<bool>));
+        assert!(option.contains("\"boolean\"") && option.contains("\"null\""));
+
+        assert!(schema_of(parse_quote!(Box<f64>)).contains("\"number\""));
+    }
 }
@@ -10,18 +10,61 @@
 //! and use garde's attributes for validation rules.
 
 use proc_macro2::TokenStream;
+use quote::quote;
 
-use crate::parse::{FieldAttrs, StructAttrs};
+use super::split_generics;
+use crate::parse::{map_value_type, FieldAttrs, StructAttrs};
 
 /// Generate validation implementation for the struct.
 ///
+/// `#[config(validate_with = "...")]` becomes `validate_fields()`, and map
+/// fields get `validate_entries()`, which runs those checks on every entry.
+/// Both also implement `confers::__private::CrossFieldChecks`, so the
+/// structs can themselves be map entries.
+///
 /// Note: We don't generate a Validate impl ourselves. Instead, the user should
-/// add `#[derive(garde::Validate)]` to their struct. This function exists for
-/// future extensions where we might want to generate validation-related code.
+/// add `#[derive(garde::Validate)]` to their struct.
 pub fn generate_validate_impl(
     struct_attrs: &StructAttrs,
-    _fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
+    fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
 ) -> Option<TokenStream> {
+    let struct_ident = &struct_attrs.ident;
+    let validate_fields = struct_attrs.validate_with_path().map(|validate_fn| {
+        quote! {
+            /// Run the cross-field checks from `#[config(validate_with = "...")]`.
+            ///
+            /// Called by the generated load methods after deserialization.
+            pub fn validate_fields(&self) -> Result<(), confers::ValidationErrors> {
+                #validate_fn(self)
+            }
+        }
+    });
+    let validate_entries = generate_validate_entries(fields);
+
+    if validate_fields.is_some() || validate_entries.is_some() {
+        let run_fields = validate_fields
+            .as_ref()
+            .map(|_| quote! { self.validate_fields()?; });
+        let run_entries = validate_entries
+            .as_ref()
+            .map(|_| quote! { self.validate_entries()?; });
+        let (impl_generics, ty_generics, where_clause) = split_generics(&struct_attrs.generics, []);
+        return Some(quote! {
+            impl #impl_generics #struct_ident #ty_generics #where_clause {
+                #validate_fields
+                #validate_entries
+            }
+
+            impl #impl_generics confers::__private::CrossFieldChecks for #struct_ident #ty_generics #where_clause {
+                fn check_fields(&self) -> Result<(), confers::ValidationErrors> {
+                    #run_fields
+                    #run_entries
+                    Ok(())
+                }
+            }
+        });
+    }
+
     // Only generate if validation is enabled at struct level
     if !struct_attrs.validate {
         return None;
@@ -36,6 +79,45 @@ pub fn generate_validate_impl(
     None
 }
 
+/// Generate `validate_entries()` for the map fields, if any.
+fn generate_validate_entries(
+    fields: &[(&syn::Ident, &syn::Type, FieldAttrs)],
+) -> Option<TokenStream> {
+    let loops: Vec<TokenStream> = fields
+        .iter()
+        .filter(|(_, ty, f)| !f.skip && map_value_type(ty).is_some())
+        .map(|(ident, _, f)| {
+            let config_key = f.effective_name();
+            quote! {
+                for (key, value) in &self.#ident {
+                    if let Err(entry_errors) =
+                        (&&confers::__private::ChecksProbe::new(value)).entry_checks()
+                    {
+                        errors.extend_nested(&format!("{}.{}", #config_key, key), entry_errors);
+                    }
+                }
+            }
+        })
+        .collect();
+    if loops.is_empty() {
+        return None;
+    }
+
+    Some(quote! {
+        /// Run the cross-field checks of every map entry, reporting fields
+        /// as `<map>.<key>.<field>`.
+        ///
+        /// Called by the generated load methods after deserialization.
+        pub fn validate_entries(&self) -> Result<(), confers::ValidationErrors> {
+            #[allow(unused_imports)]
+            use confers::__private::{EntryChecks as _, NoEntryChecks as _};
+            let mut errors = confers::ValidationErrors::new();
+            #(#loops)*
+            errors.into_result()
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +127,8 @@ mod tests {
     fn test_empty_struct_no_validation() {
         let attrs = StructAttrs {
             ident: parse_quote!(TestStruct),
+            generics: Default::default(),
+            attrs: Vec::new(),
             validate: false,
             env_prefix: None,
             app_name: None,
@@ -52,10 +136,37 @@ mod tests {
             watch: false,
             version: None,
             profile: false,
+            profiles: false,
             profile_env: None,
+            validate_with: None,
+            deny_unknown: false,
         };
 
         let result = generate_validate_impl(&attrs, &[]);
         assert!(result.is_none());
     }
+
+    #[test]
+    fn test_validate_with_generates_validate_fields() {
+        let attrs = StructAttrs {
+            ident: parse_quote!(TestStruct),
+            generics: Default::default(),
+            attrs: Vec::new(),
+            validate: false,
+            env_prefix: None,
+            app_name: None,
+            strict: false,
+            watch: false,
+            version: None,
+            profile: false,
+            profiles: false,
+            profile_env: None,
+            validate_with: Some("checks::tls".to_string()),
+            deny_unknown: false,
+        };
+
+        let tokens = generate_validate_impl(&attrs, &[]).unwrap().to_string();
+        assert!(tokens.contains("fn validate_fields"));
+        assert!(tokens.contains("checks :: tls (self)"));
+    }
 }
@@ -0,0 +1,62 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Typed change hooks for fields marked `#[config(watch)]`.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::ext::IdentExt;
+use syn::{parse_quote, Generics, Ident, Type};
+
+use super::split_generics;
+use crate::parse::FieldAttrs;
+
+/// Generate an `on_<field>_change` method for every watched field, registering
+/// a typed callback on a `ProgressiveReloader<Self>`.
+pub fn generate_watch_impl(
+    struct_ident: &Ident,
+    generics: &Generics,
+    fields: &[(&Ident, &Type, FieldAttrs)],
+) -> TokenStream {
+    let hooks: Vec<TokenStream> = fields
+        .iter()
+        .filter(|(_, _, attrs)| attrs.watch)
+        .map(|(ident, ty, _)| {
+            let method = format_ident!("on_{}_change", ident.unraw(), span = ident.span());
+            let doc = format!(
+                " Call `callback` with the old and new `{}` after each reload \
+                 committed by `reloader` that changes it. Drop the returned guard \
+                 to unsubscribe.",
+                ident.unraw()
+            );
+            quote! {
+                #[doc = #doc]
+                pub fn #method(
+                    reloader: &::confers::watcher::ProgressiveReloader<Self>,
+                    callback: impl Fn(&#ty, &#ty) + Send + Sync + 'static,
+                ) -> ::confers::watcher::ReloadSubscription {
+                    reloader.on_field_change(|config: &Self| &config.#ident, callback)
+                }
+            }
+        })
+        .collect();
+
+    if hooks.is_empty() {
+        return TokenStream::new();
+    }
+
+    let bounds = std::iter::once(parse_quote! { Self: Clone + Send + Sync + 'static }).chain(
+        fields
+            .iter()
+            .filter(|(_, _, attrs)| attrs.watch)
+            .map(|(_, ty, _)| parse_quote! { #ty: PartialEq + 'static }),
+    );
+    let (impl_generics, ty_generics, where_clause) = split_generics(generics, bounds);
+    quote! {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
+            #(#hooks)*
+        }
+    }
+}
@@ -54,6 +54,9 @@
 //!
 //!     #[config(encrypt = "xchacha20")]
 //!     secret_key: String,  // Encrypted at rest
+//!
+//!     #[config(secret)]
+//!     db_password: SecureString,  // Zeroized on drop, redacted in Debug/Serialize
 //! }
 //! ```
 //!
@@ -79,16 +82,18 @@
 //!   - `load`: Configuration loading methods
 //!   - `schema`: JSON Schema generation
 //!   - `clap`: CLI argument generation
+//!   - `watch`: Typed change hooks for `#[config(watch)]` fields
 //!
 //! # Derive Macros
 //!
 //! ## `Config`
 //!
-//! Main derive macro for configuration loading.
+//! Main derive macro for configuration loading. Works on named structs and on
+//! enums, whose `#[config(default)]` variant becomes their `Default`.
 //!
 //! ## `ConfigSchema`
 //!
-//! Generate JSON Schema from configuration structs.
+//! Generate JSON Schema from configuration structs and enums.
 //!
 //! ## `ConfigMigration`
 //!
@@ -104,7 +109,7 @@
 
 #![forbid(unsafe_code)]
 
-use darling::FromDeriveInput;
+use darling::{FromDeriveInput, FromVariant};
 use proc_macro::TokenStream;
 use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
@@ -114,11 +119,13 @@ mod codegen;
 mod parse;
 
 use codegen::{
-    generate_clap_impl, generate_defaults_impl, generate_load_impl, generate_migration_impl,
-    generate_modules_impl, generate_schema_impl, generate_validate_impl,
+    check_builtin_args, generate_clap_impl, generate_defaults_impl, generate_enum_defaults_impl,
+    generate_enum_load_impl, generate_enum_schema_impl, generate_load_impl,
+    generate_migration_impl, generate_modules_impl, generate_schema_impl, generate_validate_impl,
+    generate_watch_impl,
 };
 use darling::FromField;
-use parse::{FieldAttrs, StructAttrs};
+use parse::{FieldAttrs, StructAttrs, VariantAttrs};
 
 /// Derive macro for configuration loading.
 ///
@@ -152,21 +159,36 @@ use parse::{FieldAttrs, StructAttrs};
 /// - `validate` - Enable validation with garde
 /// - `watch` - Enable file watching for hot reload
 /// - `version = 1` - Configuration version for migrations
-/// - `profile` - Enable APP_ENV profile overlay
+/// - `profiles` (or `profile`) - Apply the `[profile.<name>]` section selected by `APP_ENV`
+/// - `profile_env = "VAR"` - Read the active profile from `VAR` instead of `APP_ENV`
+/// - `validate_with = "path::to_fn"` - Cross-field check run after loading; the function
+///   takes `&Self` and returns `Result<(), confers::ValidationErrors>`
+/// - `deny_unknown` - Fail loading with `ConfigError::UnknownKeys` when a source sets a key
+///   no field consumes
 ///
 /// # Field Attributes
 ///
 /// - `default = <expr>` - Default value expression
-/// - `description = "..."` - Field description for docs
+/// - `description = "..."` - Field description for the schema, generated templates and
+///   `--help`; defaults to the field's `///` doc comment
 /// - `name = "key"` - Override configuration key name
-/// - `name_env = "VAR"` - Override environment variable name
+/// - `env = "VAR"` (or `name_env = "VAR"`) - Override environment variable name, used
+///   without the prefix, also when the struct is nested; names `EnvSecurityValidator` always
+///   rejects fail the build
 /// - `sensitive = true` - Mark as sensitive (hidden in logs)
+/// - `secret` - Hold the value in a `SecureString` (requires the `security` feature); implies `sensitive`
 /// - `encrypt = "xchacha20"` - Enable encryption for this field
-/// - `flatten` - Flatten nested struct into parent namespace
+/// - `flatten` - Flatten nested struct into parent namespace (as does `#[serde(flatten)]`)
 /// - `skip` - Skip this field during loading
 /// - `interpolate = true` - Enable `${VAR:default}` interpolation
 /// - `dynamic` - Generate DynamicField handle
+/// - `watch` - Generate `on_<field>_change(&reloader, |old, new| ..)`, a typed change hook on a
+///   `ProgressiveReloader<Self>` (requires the `progressive-reload` feature; the struct must be
+///   `Clone` and the field `PartialEq`)
 /// - `module_group = "group"` - Assign field to a config module group
+/// - `alias = "old_name"` - Also load the field from a renamed key (repeatable); each use
+///   raises a `DEPRECATED_KEY` warning
+/// - `deprecated_since = "1.2"` - Version the aliases were deprecated in
 #[proc_macro_derive(Config, attributes(config))]
 pub fn config_derive(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
@@ -227,9 +249,7 @@ fn impl_config_derive(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStre
         .map_err(|e| syn::Error::new_spanned(input, e.to_string()))?;
 
     // Validate struct attributes
-    struct_attrs
-        .validate(input)
-        .map_err(|e| syn::Error::new_spanned(input, e.to_string()))?;
+    struct_attrs.validate(input)?;
 
     // Get the struct identifier
     let struct_ident = &input.ident;
@@ -237,62 +257,99 @@ fn impl_config_derive(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStre
     // Get fields if it's a named struct
     let fields = match &input.data {
         Data::Struct(data) => &data.fields,
+        Data::Enum(data) => return impl_config_enum_derive(input, &struct_attrs, data),
         _ => {
             return Err(syn::Error::new_spanned(
                 input,
-                "Config can only be derived for named structs",
+                "Config can only be derived for named structs and enums",
             ))
         }
     };
 
-    // Parse field attributes
+    // Parse and validate field attributes, reporting every misconfigured
+    // field at the attribute at fault
+    let mut errors = darling::Error::accumulator();
     let field_info: Vec<(&syn::Ident, &syn::Type, FieldAttrs)> = fields
         .iter()
         .filter_map(|field| {
             let ident = field.ident.as_ref()?;
-            let attrs = FieldAttrs::from_field(field).ok()?;
+            let attrs = errors.handle(FieldAttrs::from_field(field))?;
+            errors.handle(attrs.validate(field))?;
             Some((ident, &field.ty, attrs))
         })
         .collect();
-
-    // Validate field attributes
-    for (ident, _ty, attrs) in &field_info {
-        attrs
-            .validate(
-                fields
-                    .iter()
-                    .find(|f| f.ident.as_ref() == Some(ident))
-                    .expect("field must exist in parsed fields"),
-            )
-            .map_err(|e| syn::Error::new_spanned(ident, e.to_string()))?;
-    }
+    errors.finish()?;
 
     // Generate code
-    let defaults_impl = generate_defaults_impl(struct_ident, &field_info);
+    let defaults_impl = generate_defaults_impl(struct_ident, &input.generics, &field_info);
     let load_impl = generate_load_impl(struct_ident, &struct_attrs, fields);
     let validate_impl = generate_validate_impl(&struct_attrs, &field_info);
+    let watch_impl = generate_watch_impl(struct_ident, &input.generics, &field_info);
     // Generate sensitive_paths() for ConfigProvider::keys() filtering
-    let sensitive_paths = generate_sensitive_paths(struct_ident, &field_info);
+    let sensitive_paths = generate_sensitive_paths(struct_ident, &input.generics, &field_info);
     Ok(quote! {
         #defaults_impl
         #load_impl
         #validate_impl
+        #watch_impl
         #sensitive_paths
     })
 }
 
+/// `Config` for an enum: `Default` is the `#[config(default)]` variant, and
+/// loading, validation and profiles work as for structs.
+fn impl_config_enum_derive(
+    input: &DeriveInput,
+    struct_attrs: &StructAttrs,
+    data: &syn::DataEnum,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut default_variant = None;
+    for variant in &data.variants {
+        let attrs = VariantAttrs::from_variant(variant)
+            .map_err(|e| syn::Error::new_spanned(variant, e.to_string()))?;
+        if !attrs.default {
+            continue;
+        }
+        if default_variant.is_some() {
+            return Err(syn::Error::new_spanned(
+                &attrs.ident,
+                "only one variant can be #[config(default)]",
+            ));
+        }
+        default_variant = Some(variant);
+    }
+    let default_variant = default_variant.ok_or_else(|| {
+        syn::Error::new_spanned(
+            input,
+            "Config on an enum needs a default variant: mark one with #[config(default)]",
+        )
+    })?;
+
+    let enum_ident = &input.ident;
+    let defaults_impl = generate_enum_defaults_impl(enum_ident, &input.generics, default_variant)?;
+    let load_impl = generate_enum_load_impl(enum_ident, struct_attrs);
+    let validate_impl = generate_validate_impl(struct_attrs, &[]);
+    Ok(quote! {
+        #defaults_impl
+        #load_impl
+        #validate_impl
+    })
+}
+
 /// Generate a `sensitive_paths()` method that returns paths of all
-/// fields marked `#[config(sensitive = true)]` or `#[config(encrypt = "...")]`.
+/// fields marked `#[config(sensitive = true)]`, `#[config(secret)]` or
+/// `#[config(encrypt = "...")]`, plus fields of a secret type.
 /// Used by ConfigProvider backends to filter `keys()` output.
 fn generate_sensitive_paths(
     struct_ident: &Ident,
+    generics: &syn::Generics,
     fields: &[(&Ident, &Type, FieldAttrs)],
 ) -> TokenStream2 {
     use syn::LitStr;
 
     let sensitive_paths: Vec<LitStr> = fields
         .iter()
-        .filter(|(_, _, attrs)| attrs.sensitive || attrs.encrypt.is_some())
+        .filter(|(_, _, attrs)| attrs.is_sensitive_effective())
         .map(|(ident, _, attrs)| {
             let name = attrs.name.clone().unwrap_or_else(|| ident.to_string());
             LitStr::new(&name, ident.span())
@@ -303,8 +360,9 @@ fn generate_sensitive_paths(
         return TokenStream2::new();
     }
 
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote! {
-        impl #struct_ident {
+        impl #impl_generics #struct_ident #ty_generics #where_clause {
             fn sensitive_paths() -> &'static [&'static str] {
                 &[#(#sensitive_paths),*]
             }
@@ -320,10 +378,18 @@ fn impl_config_schema_derive(input: &DeriveInput) -> syn::Result<proc_macro2::To
 
     let fields = match &input.data {
         Data::Struct(data) => &data.fields,
+        Data::Enum(data) => {
+            return Ok(generate_enum_schema_impl(
+                struct_ident,
+                &input.generics,
+                &input.attrs,
+                data,
+            ))
+        }
         _ => {
             return Err(syn::Error::new_spanned(
                 input,
-                "ConfigSchema can only be derived for named structs",
+                "ConfigSchema can only be derived for named structs and enums",
             ))
         }
     };
@@ -397,6 +463,7 @@ fn impl_config_clap_derive(input: &DeriveInput) -> syn::Result<proc_macro2::Toke
         }
     };
 
+    check_builtin_args(fields)?;
     let clap_impl = generate_clap_impl(struct_ident, &struct_attrs, fields);
 
     Ok(quote! {
@@ -7,8 +7,8 @@
 //!
 //! Uses darling for derive-aware attribute parsing with precise error spans.
 
-use darling::{FromDeriveInput, FromField};
-use syn::{GenericArgument, Ident, PathArguments, Type};
+use darling::{FromDeriveInput, FromField, FromVariant};
+use syn::{Attribute, Expr, ExprLit, GenericArgument, Ident, Lit, PathArguments, Type};
 
 /// Maximum allowed length for environment variable prefix.
 const MAX_PREFIX_LENGTH: usize = 64;
@@ -18,12 +18,22 @@ const MAX_NAME_LENGTH: usize = 256;
 
 /// Parsed attributes from the struct level.
 #[derive(Debug, FromDeriveInput)]
-#[darling(attributes(config), supports(struct_named))]
+#[darling(
+    attributes(config),
+    forward_attrs(doc),
+    supports(struct_named, enum_any)
+)]
 #[allow(dead_code)]
 pub struct StructAttrs {
     /// The struct identifier
     pub ident: Ident,
 
+    /// Generic parameters and where clause of the struct
+    pub generics: syn::Generics,
+
+    /// The struct's `///` doc attributes
+    pub attrs: Vec<Attribute>,
+
     /// Whether to enable validation
     #[darling(default)]
     pub validate: bool,
@@ -49,22 +59,49 @@ pub struct StructAttrs {
     #[darling(default)]
     pub profile: bool,
 
+    /// Whether to apply `[profile.<name>]` sections (same as `profile`)
+    #[darling(default)]
+    pub profiles: bool,
+
     /// Profile environment variable name
     pub profile_env: Option<String>,
+
+    /// Cross-field validation function, called with `&Self` after loading
+    pub validate_with: Option<String>,
+
+    /// Whether keys no field consumes fail the load
+    #[darling(default)]
+    pub deny_unknown: bool,
 }
 
 impl StructAttrs {
+    /// The struct's doc comment, if it has one.
+    pub fn doc_comment(&self) -> Option<String> {
+        doc_comment(&self.attrs)
+    }
+
     /// Get the effective environment prefix.
     pub fn effective_env_prefix(&self) -> &str {
         self.env_prefix.as_deref().unwrap_or("")
     }
 
     /// Get the effective profile environment variable name.
-    #[allow(dead_code)]
     pub fn effective_profile_env(&self) -> &str {
         self.profile_env.as_deref().unwrap_or("APP_ENV")
     }
 
+    /// Whether `[profile.<name>]` sections are applied on load.
+    pub fn profiles_enabled(&self) -> bool {
+        self.profiles || self.profile
+    }
+
+    /// The `validate_with` function as a path.
+    pub fn validate_with_path(&self) -> Option<syn::Path> {
+        self.validate_with
+            .as_deref()
+            .and_then(|path| syn::parse_str(path).ok())
+    }
+
     /// Validate struct attributes.
     ///
     /// This method performs comprehensive validation of all struct-level attributes:
@@ -72,6 +109,7 @@ impl StructAttrs {
     /// - env_prefix must not be empty, must not exceed max length, and must only contain
     ///   alphanumeric characters and underscores
     /// - app_name must not be empty and must not exceed max length
+    /// - validate_with must be a function path
     ///
     /// # Arguments
     ///
@@ -139,6 +177,16 @@ impl StructAttrs {
             }
         }
 
+        // Validate validate_with
+        if self.validate_with.is_some() && self.validate_with_path().is_none() {
+            errors.push(
+                darling::Error::custom(
+                    "validate_with must name a function, e.g. validate_with = \"check_tls\"",
+                )
+                .with_span(&input.ident),
+            );
+        }
+
         // Validate app_name
         if let Some(ref app_name) = self.app_name {
             if app_name.len() > MAX_NAME_LENGTH {
@@ -158,13 +206,24 @@ impl StructAttrs {
             }
         }
 
+        // profile_env only names where the profile is read from
+        if self.profile_env.is_some() && !self.profiles_enabled() {
+            let error = darling::Error::custom(
+                "`profile_env` has no effect without `profiles`: add #[config(profiles)]",
+            );
+            errors.push(match config_attr_path(&input.attrs, "profile_env") {
+                Some(path) => error.with_span(&path),
+                None => error.with_span(&input.ident),
+            });
+        }
+
         errors.finish()
     }
 }
 
 /// Parsed attributes from a field.
 #[derive(Debug, FromField)]
-#[darling(attributes(config))]
+#[darling(attributes(config), forward_attrs(serde, doc))]
 #[allow(dead_code)]
 pub struct FieldAttrs {
     /// Field identifier
@@ -173,10 +232,13 @@ pub struct FieldAttrs {
     /// Field type
     pub ty: Type,
 
+    /// The field's `#[serde(...)]` and `///` doc attributes
+    pub attrs: Vec<Attribute>,
+
     /// Default value expression
     pub default: Option<syn::Expr>,
 
-    /// Field description for documentation
+    /// Field description for documentation (defaults to the doc comment)
     pub description: Option<String>,
 
     /// Override configuration key name
@@ -185,16 +247,37 @@ pub struct FieldAttrs {
     /// Override environment variable name
     pub name_env: Option<String>,
 
+    /// Override environment variable name (same as `name_env`)
+    pub env: Option<String>,
+
     /// CLI long argument name
     pub name_clap_long: Option<String>,
 
     /// CLI short argument character
     pub name_clap_short: Option<char>,
 
+    /// Take the CLI argument by position instead of as a `--flag`
+    #[darling(default)]
+    pub positional: bool,
+
+    /// Fail CLI parsing when the argument is missing
+    #[darling(default)]
+    pub required: bool,
+
+    /// Parse the CLI argument as a `clap::ValueEnum`, listing its
+    /// variants in `--help`
+    #[darling(default)]
+    pub value_enum: bool,
+
     /// Whether this field is sensitive (hidden in logs)
     #[darling(default)]
     pub sensitive: bool,
 
+    /// Whether this field is a secret held in a `SecureString`
+    /// (zeroized on drop, implies `sensitive`)
+    #[darling(default)]
+    pub secret: bool,
+
     /// Encryption algorithm for this field
     pub encrypt: Option<String>,
 
@@ -217,8 +300,19 @@ pub struct FieldAttrs {
     #[darling(default)]
     pub dynamic: bool,
 
+    /// Whether to generate an `on_<field>_change` hook
+    #[darling(default)]
+    pub watch: bool,
+
     /// Module group for this field (config groups)
     pub module_group: Option<String>,
+
+    /// Old configuration key names still accepted for this field
+    #[darling(multiple)]
+    pub alias: Vec<String>,
+
+    /// Version the aliases were deprecated in
+    pub deprecated_since: Option<String>,
 }
 
 impl FieldAttrs {
@@ -232,16 +326,43 @@ impl FieldAttrs {
         })
     }
 
+    /// The `description`, or else the field's `///` doc comment. Feeds the
+    /// schema, and through it `confers generate` templates, and `--help`.
+    pub fn effective_description(&self) -> Option<String> {
+        self.description
+            .clone()
+            .or_else(|| doc_comment(&self.attrs))
+    }
+
+    /// The environment variable name set with `env` or `name_env`, used
+    /// as is without the struct's prefix.
+    pub fn explicit_env_name(&self) -> Option<&str> {
+        self.env.as_deref().or(self.name_env.as_deref())
+    }
+
     /// Get the effective environment variable name
     pub fn effective_env_name(&self, prefix: &str) -> String {
-        if let Some(ref name_env) = self.name_env {
-            name_env.clone()
-        } else {
-            let key = self.effective_name();
-            format!("{}{}", prefix, key.to_uppercase().replace('.', "_"))
+        match self.explicit_env_name() {
+            Some(name) => name.to_string(),
+            None => format!("{}{}", prefix, self.env_segment()),
+        }
+    }
+
+    /// The environment variable name of this field without the struct's
+    /// prefix: the explicit name if set, else the upper-cased key.
+    pub fn env_segment(&self) -> String {
+        match self.explicit_env_name() {
+            Some(name) => name.to_string(),
+            None => self.effective_name().to_uppercase().replace('.', "_"),
         }
     }
 
+    /// Whether the field's keys live in the parent's namespace, via
+    /// `#[config(flatten)]` or `#[serde(flatten)]`.
+    pub fn is_flattened(&self) -> bool {
+        self.flatten || serde_flag(&self.attrs, "flatten")
+    }
+
     /// Check if this field is a SecretString type
     pub fn is_secret_string(&self) -> bool {
         is_secret_type(&self.ty)
@@ -249,13 +370,99 @@ impl FieldAttrs {
 
     /// Check if this field should be treated as sensitive
     pub fn is_sensitive_effective(&self) -> bool {
-        self.sensitive || self.encrypt.is_some() || self.is_secret_string()
+        self.sensitive || self.secret || self.encrypt.is_some() || self.is_secret_string()
     }
 
     /// Validate field attributes and return errors with helpful suggestions
-    pub fn validate(&self, _field: &syn::Field) -> darling::Result<()> {
+    pub fn validate(&self, field: &syn::Field) -> darling::Result<()> {
         let mut errors = darling::Error::accumulator();
 
+        // Reject attribute combinations that contradict each other, pointing
+        // at the attribute that has to go
+        let mut conflict = |name: &str, message: String| {
+            if let Some(path) = config_attr_path(&field.attrs, name) {
+                errors.push(darling::Error::custom(message).with_span(&path));
+            }
+        };
+        if self.skip {
+            for name in [
+                "name",
+                "name_env",
+                "env",
+                "alias",
+                "deprecated_since",
+                "flatten",
+                "sensitive",
+                "secret",
+                "encrypt",
+                "interpolate",
+                "merge_strategy",
+                "dynamic",
+                "watch",
+                "module_group",
+                "name_clap_long",
+                "name_clap_short",
+                "positional",
+                "required",
+                "value_enum",
+            ] {
+                conflict(
+                    name,
+                    format!(
+                        "`{}` has no effect on a `skip` field, which is never loaded",
+                        name
+                    ),
+                );
+            }
+        }
+        if self.is_flattened() {
+            for name in ["name", "name_env", "env", "alias"] {
+                conflict(
+                    name,
+                    format!(
+                        "`{}` cannot be combined with `flatten`: a flattened field has no key \
+                         of its own, its fields are read from the parent",
+                        name
+                    ),
+                );
+            }
+        }
+        if self.positional {
+            for name in ["name_clap_long", "name_clap_short"] {
+                conflict(
+                    name,
+                    format!(
+                        "`{}` cannot be combined with `positional`: a positional argument \
+                         has no flag",
+                        name
+                    ),
+                );
+            }
+        }
+        if self.env.is_some() && self.name_env.is_some() {
+            conflict(
+                "name_env",
+                "`env` and `name_env` both set the environment variable; keep one".to_string(),
+            );
+        }
+        if self.skip {
+            let rules = field.attrs.iter().find(|attr| {
+                attr.path().is_ident("garde")
+                    && attr
+                        .parse_args::<syn::Path>()
+                        .map_or(true, |rule| !rule.is_ident("skip"))
+            });
+            if let Some(rules) = rules {
+                errors.push(
+                    darling::Error::custom(
+                        "validation rules on a `skip` field never see a loaded value; \
+                         remove them or use #[garde(skip)]",
+                    )
+                    .with_span(rules),
+                );
+            }
+        }
+
         // Validate encrypt algorithm
         if let Some(ref algo) = self.encrypt {
             match algo.as_str() {
@@ -300,6 +507,34 @@ impl FieldAttrs {
             }
         }
 
+        // Explicit env names must pass EnvSecurityValidator at runtime;
+        // reject the ones it always would
+        if let Some(name) = self.explicit_env_name() {
+            if let Err(reason) = check_env_name(name) {
+                if let Some(ident) = self.ident.as_ref() {
+                    errors.push(
+                        darling::Error::custom(format!(
+                            "env name '{}' is rejected by EnvSecurityValidator: {}",
+                            name, reason
+                        ))
+                        .with_span(ident),
+                    );
+                }
+            }
+        }
+
+        // Validate deprecated_since
+        if self.deprecated_since.is_some() && self.alias.is_empty() {
+            if let Some(ident) = self.ident.as_ref() {
+                errors.push(
+                    darling::Error::custom(
+                        "deprecated_since requires an alias, e.g. alias = \"old_name\"",
+                    )
+                    .with_span(ident),
+                );
+            }
+        }
+
         // Validate sensitive field type
         if self.sensitive && !self.is_secret_string() {
             if let Some(ident) = self.ident.as_ref() {
@@ -313,15 +548,331 @@ impl FieldAttrs {
             }
         }
 
+        // Validate secret field type
+        if self.secret && !is_secure_string_type(&self.ty) {
+            if let Some(ident) = self.ident.as_ref() {
+                errors.push(
+                    darling::Error::custom(format!(
+                        "secret field '{}' must be declared as `SecureString` or `Option<SecureString>`\n\
+                         use confers::security::SecureString",
+                        ident
+                    ))
+                    .with_span(ident),
+                );
+            }
+        }
+
         errors.finish()
     }
 }
 
-/// Check if a type is SecretString or SecretBytes (optimized version)
+/// Parsed attributes from an enum variant.
+#[derive(Debug, FromVariant)]
+#[darling(attributes(config))]
+pub struct VariantAttrs {
+    /// Variant identifier
+    pub ident: Ident,
+
+    /// Whether this variant is the enum's `Default`
+    #[darling(default)]
+    pub default: bool,
+}
+
+/// How serde represents an enum, from `#[serde(...)]` on the enum.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum EnumTagging {
+    /// `{ "variant": { ... } }`, or `"variant"` for unit variants
+    #[default]
+    External,
+    /// `#[serde(tag = "kind")]`: `{ "kind": "variant", ... }`
+    Internal { tag: String },
+    /// `#[serde(tag = "kind", content = "value")]`
+    Adjacent { tag: String, content: String },
+    /// `#[serde(untagged)]`
+    Untagged,
+}
+
+/// The serde attributes that decide an enum's data layout.
+#[derive(Debug, Clone, Default)]
+pub struct SerdeEnumAttrs {
+    /// Variant tagging
+    pub tagging: EnumTagging,
+    /// `rename_all` rule for variant names (the `deserialize` one if split)
+    pub rename_all: Option<String>,
+}
+
+impl SerdeEnumAttrs {
+    /// Read `tag`, `content`, `untagged` and `rename_all` from the enum's
+    /// `#[serde(...)]` attributes, ignoring everything else.
+    pub fn from_attrs(attrs: &[Attribute]) -> Self {
+        let (mut tag, mut content, mut untagged, mut rename_all) = (None, None, false, None);
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+            let _ = attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    tag = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("content") {
+                    content = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                } else if meta.path.is_ident("untagged") {
+                    untagged = true;
+                } else if meta.path.is_ident("rename_all") {
+                    if meta.input.peek(syn::Token![=]) {
+                        rename_all = Some(meta.value()?.parse::<syn::LitStr>()?.value());
+                    } else {
+                        meta.parse_nested_meta(|rule| {
+                            let value = rule.value()?.parse::<syn::LitStr>()?.value();
+                            if rule.path.is_ident("deserialize") {
+                                rename_all = Some(value);
+                            }
+                            Ok(())
+                        })?;
+                    }
+                } else if meta.input.peek(syn::Token![=]) {
+                    let _: Expr = meta.value()?.parse()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let _: proc_macro2::Group = meta.input.parse()?;
+                }
+                Ok(())
+            });
+        }
+
+        let tagging = match (untagged, tag, content) {
+            (true, _, _) => EnumTagging::Untagged,
+            (false, Some(tag), Some(content)) => EnumTagging::Adjacent { tag, content },
+            (false, Some(tag), None) => EnumTagging::Internal { tag },
+            (false, None, _) => EnumTagging::External,
+        };
+        Self {
+            tagging,
+            rename_all,
+        }
+    }
+
+    /// The name serde expects for `variant`: its `#[serde(rename)]`, or its
+    /// identifier under the enum's `rename_all` rule.
+    pub fn variant_name(&self, variant: &syn::Variant) -> String {
+        serde_rename(&variant.attrs).unwrap_or_else(|| {
+            let name = variant.ident.to_string();
+            match &self.rename_all {
+                Some(rule) => apply_rename_rule(&name, rule),
+                None => name,
+            }
+        })
+    }
+}
+
+/// Join `///` doc comment lines: lines of a paragraph with spaces, and
+/// paragraphs with a newline.
+pub fn doc_comment(attrs: &[Attribute]) -> Option<String> {
+    let mut paragraphs: Vec<String> = vec![String::new()];
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("doc")) {
+        let syn::Meta::NameValue(syn::MetaNameValue {
+            value:
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(line),
+                    ..
+                }),
+            ..
+        }) = &attr.meta
+        else {
+            continue;
+        };
+        let line = line.value();
+        let line = line.trim();
+        let paragraph = paragraphs.last_mut().expect("never empty");
+        if line.is_empty() {
+            if !paragraph.is_empty() {
+                paragraphs.push(String::new());
+            }
+        } else {
+            if !paragraph.is_empty() {
+                paragraph.push(' ');
+            }
+            paragraph.push_str(line);
+        }
+    }
+    let doc = paragraphs
+        .into_iter()
+        .filter(|paragraph| !paragraph.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// The path of the `#[config(...)]` item called `name`, for error spans.
+pub fn config_attr_path(attrs: &[Attribute], name: &str) -> Option<syn::Path> {
+    let mut found = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("config")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if found.is_none() && meta.path.is_ident(name) {
+                found = Some(meta.path.clone());
+            }
+            if meta.input.peek(syn::Token![=]) {
+                let _: Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _: proc_macro2::Group = meta.input.parse()?;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// Whether serde never deserializes this item (`skip` or
+/// `skip_deserializing`).
+pub fn serde_skipped(attrs: &[Attribute]) -> bool {
+    serde_flag(attrs, "skip") || serde_flag(attrs, "skip_deserializing")
+}
+
+/// Whether the `#[serde(...)]` attributes hold the bare flag `name`, such as
+/// `flatten` or `skip`.
+pub fn serde_flag(attrs: &[Attribute], name: &str) -> bool {
+    let mut found = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.input.peek(syn::Token![=]) {
+                let _: Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _: proc_macro2::Group = meta.input.parse()?;
+            } else if meta.path.is_ident(name) {
+                found = true;
+            }
+            Ok(())
+        });
+    }
+    found
+}
+
+/// Environment variable names `EnvSecurityValidator` never accepts: the
+/// allowed shape is `^[A-Z][A-Z0-9_]*$` of at most 256 characters, and
+/// shell and system variables are blocked.
+pub fn check_env_name(name: &str) -> Result<(), String> {
+    const BLOCKED: &[&str] = &[
+        "PATH",
+        "LD_LIBRARY_PATH",
+        "LD_PRELOAD",
+        "SHELL",
+        "HOME",
+        "USER",
+        "LOGNAME",
+        "PWD",
+        "OLDPWD",
+        "MAIL",
+        "MAILCHECK",
+        "TERM",
+        "TERMCAP",
+        "DISPLAY",
+        "XAUTHORITY",
+        "SSH_AUTH_SOCK",
+        "SSH_AGENT_PID",
+        "DOCKER_HOST",
+        "KUBECONFIG",
+    ];
+
+    if name.len() > 256 {
+        return Err(format!("longer than 256 characters ({})", name.len()));
+    }
+    let mut chars = name.chars();
+    let well_formed = chars.next().is_some_and(|c| c.is_ascii_uppercase())
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+    if !well_formed {
+        return Err("expected upper-case letters, digits and '_', starting with a letter".into());
+    }
+    if BLOCKED.contains(&name) || name.starts_with("BASH_FUNC_") {
+        return Err("reserved shell or system variable".into());
+    }
+    Ok(())
+}
+
+/// The `#[serde(rename = "...")]` of an item (the `deserialize` one if split).
+fn serde_rename(attrs: &[Attribute]) -> Option<String> {
+    let mut rename = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("serde")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                if let Ok(value) = meta.value() {
+                    if let Expr::Lit(ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    }) = value.parse::<Expr>()?
+                    {
+                        rename = Some(name.value());
+                    }
+                } else {
+                    meta.parse_nested_meta(|rule| {
+                        let value = rule.value()?.parse::<syn::LitStr>()?.value();
+                        if rule.path.is_ident("deserialize") {
+                            rename = Some(value);
+                        }
+                        Ok(())
+                    })?;
+                }
+            } else if meta.input.peek(syn::Token![=]) {
+                let _: Expr = meta.value()?.parse()?;
+            } else if meta.input.peek(syn::token::Paren) {
+                let _: proc_macro2::Group = meta.input.parse()?;
+            }
+            Ok(())
+        });
+    }
+    rename
+}
+
+/// Apply a serde `rename_all` rule to a `PascalCase` variant name. Unknown
+/// rules leave the name unchanged, as serde rejects them anyway.
+pub fn apply_rename_rule(name: &str, rule: &str) -> String {
+    let snake = || {
+        let mut snake = String::new();
+        for (i, c) in name.chars().enumerate() {
+            if c.is_uppercase() && i > 0 {
+                snake.push('_');
+            }
+            snake.push(c.to_ascii_lowercase());
+        }
+        snake
+    };
+    match rule {
+        "lowercase" => name.to_ascii_lowercase(),
+        "UPPERCASE" => name.to_ascii_uppercase(),
+        "camelCase" => {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        }
+        "snake_case" => snake(),
+        "SCREAMING_SNAKE_CASE" => snake().to_ascii_uppercase(),
+        "kebab-case" => snake().replace('_', "-"),
+        "SCREAMING-KEBAB-CASE" => snake().replace('_', "-").to_ascii_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Check if a type is SecretString, SecretBytes or SecureString (optimized version)
 pub fn is_secret_type(ty: &Type) -> bool {
     if let Type::Path(type_path) = ty {
         if let Some(segment) = type_path.path.segments.last() {
-            return segment.ident == "SecretString" || segment.ident == "SecretBytes";
+            return segment.ident == "SecretString"
+                || segment.ident == "SecretBytes"
+                || segment.ident == "SecureString";
+        }
+    }
+    false
+}
+
+/// Check if a type is SecureString or Option<SecureString>
+pub fn is_secure_string_type(ty: &Type) -> bool {
+    let ty = if is_option_type(ty) {
+        match extract_inner_type(ty) {
+            Some(inner) => inner,
+            None => return false,
+        }
+    } else {
+        ty
+    };
+    if let Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            return segment.ident == "SecureString";
         }
     }
     false
@@ -357,7 +908,7 @@ impl TypeCategory {
                     "Option" => return Self::Option,
                     "Vec" => return Self::Vec,
                     "HashMap" | "BTreeMap" | "Map" => return Self::Map,
-                    "SecretString" | "SecretBytes" => return Self::Secret,
+                    "SecretString" | "SecretBytes" | "SecureString" => return Self::Secret,
                     _ => {}
                 }
             }
@@ -386,6 +937,31 @@ pub fn is_vec_type(ty: &Type) -> bool {
     false
 }
 
+/// The value type of a `HashMap<K, V>`, `BTreeMap<K, V>` or `IndexMap<K, V>`.
+pub fn map_value_type(ty: &Type) -> Option<&Type> {
+    if let syn::Type::Path(type_path) = ty {
+        if let Some(segment) = type_path.path.segments.last() {
+            if !matches!(
+                segment.ident.to_string().as_str(),
+                "HashMap" | "BTreeMap" | "IndexMap"
+            ) {
+                return None;
+            }
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                return args
+                    .args
+                    .iter()
+                    .filter_map(|arg| match arg {
+                        GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                    .nth(1);
+            }
+        }
+    }
+    None
+}
+
 /// Extract the inner type from Option<T> or Vec<T>
 #[allow(dead_code)]
 pub fn extract_inner_type(ty: &Type) -> Option<&Type> {
@@ -462,6 +1038,203 @@ mod tests {
         assert!(inner.is_some());
     }
 
+    #[test]
+    fn test_is_secure_string_type() {
+        let ty: Type = parse_quote!(SecureString);
+        assert!(is_secure_string_type(&ty));
+
+        let ty: Type = parse_quote!(Option<confers::security::SecureString>);
+        assert!(is_secure_string_type(&ty));
+
+        let ty: Type = parse_quote!(String);
+        assert!(!is_secure_string_type(&ty));
+    }
+
+    #[test]
+    fn test_secret_field_implies_sensitive() {
+        let field: syn::Field = parse_quote! {
+            #[config(secret)]
+            password: SecureString
+        };
+        let attrs = FieldAttrs::from_field(&field).unwrap();
+        assert!(attrs.is_sensitive_effective());
+        assert!(attrs.validate(&field).is_ok());
+    }
+
+    #[test]
+    fn test_secret_field_rejects_plain_string() {
+        let field: syn::Field = parse_quote! {
+            #[config(secret)]
+            password: String
+        };
+        let attrs = FieldAttrs::from_field(&field).unwrap();
+        let err = attrs.validate(&field).unwrap_err();
+        assert!(err.to_string().contains("SecureString"));
+    }
+
+    #[test]
+    fn test_serde_enum_attrs() {
+        let input: syn::DeriveInput = parse_quote! {
+            #[serde(tag = "kind", rename_all = "snake_case")]
+            enum Storage {
+                LocalDisk,
+                #[serde(rename = "s3")]
+                S3 { bucket: String },
+            }
+        };
+        let attrs = SerdeEnumAttrs::from_attrs(&input.attrs);
+        assert_eq!(
+            attrs.tagging,
+            EnumTagging::Internal {
+                tag: "kind".to_string()
+            }
+        );
+        let syn::Data::Enum(data) = &input.data else {
+            unreachable!()
+        };
+        let names: Vec<String> = data
+            .variants
+            .iter()
+            .map(|v| attrs.variant_name(v))
+            .collect();
+        assert_eq!(names, ["local_disk", "s3"]);
+
+        let untagged: syn::DeriveInput = parse_quote! {
+            #[serde(untagged, deny_unknown_fields)]
+            enum Port { Number(u16), Name(String) }
+        };
+        assert_eq!(
+            SerdeEnumAttrs::from_attrs(&untagged.attrs).tagging,
+            EnumTagging::Untagged
+        );
+    }
+
+    #[test]
+    fn test_apply_rename_rule() {
+        assert_eq!(apply_rename_rule("ReadOnly", "kebab-case"), "read-only");
+        assert_eq!(apply_rename_rule("ReadOnly", "camelCase"), "readOnly");
+        assert_eq!(
+            apply_rename_rule("ReadOnly", "SCREAMING_SNAKE_CASE"),
+            "READ_ONLY"
+        );
+    }
+
+    fn field_errors(field: syn::Field) -> Vec<String> {
+        let attrs = FieldAttrs::from_field(&field).unwrap();
+        match attrs.validate(&field) {
+            Ok(()) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|e| e.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_conflicting_field_attributes() {
+        let errors = field_errors(parse_quote! {
+            #[config(skip, name_env = "APP_CACHE")]
+            cache: Vec<String>
+        });
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("`name_env` has no effect on a `skip` field"));
+
+        let errors = field_errors(parse_quote! {
+            #[config(flatten, env = "APP_INNER")]
+            inner: Inner
+        });
+        assert!(errors[0].contains("`env` cannot be combined with `flatten`"));
+
+        let errors = field_errors(parse_quote! {
+            #[serde(flatten)]
+            #[config(alias = "old")]
+            inner: Inner
+        });
+        assert!(errors[0].contains("`alias` cannot be combined with `flatten`"));
+
+        let errors = field_errors(parse_quote! {
+            #[config(env = "APP_A", name_env = "APP_B")]
+            a: String
+        });
+        assert!(errors[0].contains("keep one"));
+
+        let errors = field_errors(parse_quote! {
+            #[config(positional, name_clap_short = 'r')]
+            root: String
+        });
+        assert!(errors[0].contains("`name_clap_short` cannot be combined with `positional`"));
+
+        let errors = field_errors(parse_quote! {
+            #[config(skip)]
+            #[garde(length(min = 1))]
+            cache: String
+        });
+        assert!(errors[0].contains("validation rules on a `skip` field"));
+
+        assert!(field_errors(parse_quote! {
+            #[config(skip, default = Vec::new())]
+            #[garde(skip)]
+            cache: Vec<String>
+        })
+        .is_empty());
+    }
+
+    #[test]
+    fn test_doc_comment_feeds_description() {
+        let field: syn::Field = parse_quote! {
+            /// Listen port,
+            /// 1-65535.
+            ///
+            /// Restart required.
+            port: u16
+        };
+        let attrs = FieldAttrs::from_field(&field).unwrap();
+        assert_eq!(
+            attrs.effective_description().as_deref(),
+            Some("Listen port, 1-65535.\nRestart required.")
+        );
+
+        let field: syn::Field = parse_quote! {
+            /// Ignored
+            #[config(description = "Listen port")]
+            port: u16
+        };
+        let attrs = FieldAttrs::from_field(&field).unwrap();
+        assert_eq!(
+            attrs.effective_description().as_deref(),
+            Some("Listen port")
+        );
+    }
+
+    #[test]
+    fn test_check_env_name() {
+        assert!(check_env_name("APP_DB_POOL_MAX").is_ok());
+        assert!(check_env_name("app_db").is_err());
+        assert!(check_env_name("1APP").is_err());
+        assert!(check_env_name("LD_PRELOAD").is_err());
+        assert!(check_env_name("BASH_FUNC_X").is_err());
+    }
+
+    #[test]
+    fn test_serde_flag() {
+        let field: syn::Field = parse_quote! {
+            #[serde(rename = "flattened_view", flatten)]
+            inner: Inner
+        };
+        assert!(serde_flag(&field.attrs, "flatten"));
+        let field: syn::Field = parse_quote! {
+            #[serde(rename = "flatten")]
+            inner: Inner
+        };
+        assert!(!serde_flag(&field.attrs, "flatten"));
+    }
+
+    #[test]
+    fn test_map_value_type() {
+        let map: Type = parse_quote!(std::collections::HashMap<String, Database>);
+        let value: Type = parse_quote!(Database);
+        assert_eq!(map_value_type(&map), Some(&value));
+        assert!(map_value_type(&parse_quote!(BTreeMap<String, u16>)).is_some());
+        assert!(map_value_type(&parse_quote!(Vec<Database>)).is_none());
+    }
+
     #[test]
     fn test_merge_strategy_from_str() {
         assert_eq!(
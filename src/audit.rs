@@ -5,8 +5,16 @@
 
 //! Audit logging — public facade.
 //!
-//! Implementation lives in `crate::impl_::audit`.
+//! Implementation lives in `crate::impl_::audit`. Events go to the
+//! [`AuditSink`]s of an [`AuditWriter`]: a daily JSON-lines file
+//! ([`FileSink`], also created by `log_dir`), stdout/stderr
+//! ([`JsonStreamSink`]), syslog ([`SyslogSink`]) or an HTTP endpoint
+//! ([`WebhookSink`], `remote` feature).
 
 pub use crate::impl_::audit::{
-    AuditConfig, AuditConfigBuilder, AuditEvent, AuditLevel, AuditWriter, AuditWriterBuilder,
+    new_correlation_id, AuditConfig, AuditConfigBuilder, AuditEvent, AuditLevel, AuditWriter,
+    AuditWriterBuilder,
 };
+pub use crate::impl_::audit_sink::{AuditSink, FileSink, JsonStreamSink, SyslogSink};
+#[cfg(feature = "remote")]
+pub use crate::impl_::audit_sink::{WebhookSink, WebhookSinkBuilder};
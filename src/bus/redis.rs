@@ -80,10 +80,20 @@ impl RedisConfigBus {
     ) -> ConfigResult<Self> {
         let safe_host = Self::sanitize_url(url);
 
-        let client = redis::Client::open(url).map_err(|e| ConfigError::RemoteUnavailable {
-            error_type: format!("redis_connection_failed: host={}, error={}", safe_host, e),
-            retryable: true,
-        })?;
+        // A `#insecure` fragment would turn off TLS verification when
+        // another crate enables redis' insecure TLS support.
+        let url = match url::Url::parse(url) {
+            Ok(mut parsed) => {
+                parsed.set_fragment(None);
+                parsed.to_string()
+            }
+            Err(_) => url.to_string(),
+        };
+        let client =
+            redis::Client::open(url.as_str()).map_err(|e| ConfigError::RemoteUnavailable {
+                error_type: format!("redis_connection_failed: host={}, error={}", safe_host, e),
+                retryable: true,
+            })?;
 
         Ok(Self {
             client,
@@ -421,6 +431,22 @@ mod tests {
         ));
     }
 
+    #[cfg(feature = "redis")]
+    #[tokio::test]
+    async fn test_insecure_fragment_in_url_is_ignored() {
+        let bus =
+            RedisConfigBus::connect_with_config("rediss://127.0.0.1:6380/#insecure", "chan", 10, 1)
+                .await
+                .unwrap();
+        assert!(matches!(
+            bus.client.get_connection_info().addr(),
+            redis::ConnectionAddr::TcpTls {
+                insecure: false,
+                ..
+            }
+        ));
+    }
+
     #[tokio::test]
     async fn test_connect_with_config_dead_port_opens_client() {
         // Client::open succeeds for a syntactically valid URL pointing at a
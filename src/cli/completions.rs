@@ -0,0 +1,703 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Shell completion scripts for `confers completions`.
+//!
+//! Subcommands and options are written into the script from the clap
+//! definition. Values that depend on the project are completed by calling
+//! back into the binary with the hidden `__complete <kind> -- <words>`
+//! command, which prints one candidate per line, optionally followed by a
+//! tab and a description:
+//!
+//! - `keys`: dotted configuration keys, from `--schema`, the schema passed
+//!   to `run_with_schema`, or the `-c` files;
+//! - `profiles`: the sections under `profile` in the `-c` files;
+//! - `key-ids`: the key rings of `--key-store`, with their current version.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{bail, Result};
+use clap::{Arg, ArgAction, Command, ValueHint};
+use serde_json::Value;
+
+use crate::impl_::config::chain::PROFILE_SECTION;
+use crate::impl_::template::resolve;
+use crate::loader::{detect_format_from_path, parse_content};
+use crate::types::SourceId;
+
+/// Nesting followed into a schema or document before stopping; guards
+/// against recursive `$ref`s.
+const MAX_DEPTH: usize = 32;
+
+/// Name of the hidden subcommand the scripts call for dynamic values.
+pub(crate) const COMPLETE_COMMAND: &str = "__complete";
+
+/// Values completed by calling back into the binary.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Dynamic {
+    Keys,
+    Profiles,
+    KeyIds,
+}
+
+impl Dynamic {
+    fn of(arg: &Arg) -> Option<Self> {
+        match arg.get_id().as_str() {
+            "key" => Some(Dynamic::Keys),
+            "profile" => Some(Dynamic::Profiles),
+            "key_id" => Some(Dynamic::KeyIds),
+            _ => None,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Dynamic::Keys => "keys",
+            Dynamic::Profiles => "profiles",
+            Dynamic::KeyIds => "key-ids",
+        }
+    }
+}
+
+/// The completion script for `shell`, registered for `bin`.
+pub(crate) fn script(command: &Command, shell: &str, bin: &str) -> Result<String> {
+    let spec = Spec::new(command);
+    match shell {
+        "bash" => Ok(bash(&spec, bin)),
+        "zsh" => Ok(zsh(&spec, bin)),
+        "fish" => Ok(fish(&spec, bin)),
+        other => bail!("Unsupported shell: {} (expected bash, zsh or fish)", other),
+    }
+}
+
+/// What the scripts need from the clap definition.
+struct Spec<'a> {
+    global: Vec<&'a Arg>,
+    subcommands: Vec<&'a Command>,
+}
+
+impl<'a> Spec<'a> {
+    fn new(command: &'a Command) -> Self {
+        Self {
+            global: visible_args(command).collect(),
+            subcommands: command
+                .get_subcommands()
+                .filter(|sub| !sub.is_hide_set())
+                .collect(),
+        }
+    }
+
+    /// Every argument, nested subcommands included.
+    fn all_args(&self) -> impl Iterator<Item = &'a Arg> + '_ {
+        self.global
+            .iter()
+            .copied()
+            .chain(self.subcommands.iter().flat_map(|sub| args_of(sub)))
+    }
+
+    /// Option spellings, `|`-separated, whose values are `kind`.
+    fn dynamic_options(&self, kind: Dynamic) -> String {
+        spellings(self.all_args().filter(|arg| Dynamic::of(arg) == Some(kind)))
+    }
+
+    /// Option spellings, `|`-separated, that take a path.
+    fn path_options(&self) -> String {
+        spellings(self.all_args().filter(|arg| takes_path(arg)))
+    }
+
+    /// Option spellings, space-separated, that take a value.
+    fn value_options(&self) -> String {
+        spellings(self.all_args().filter(|arg| takes_value(arg))).replace('|', " ")
+    }
+
+    /// Subcommands whose positional argument is a configuration key.
+    fn key_commands(&self) -> Vec<&str> {
+        self.subcommands
+            .iter()
+            .filter(|sub| {
+                sub.get_positionals()
+                    .any(|arg| Dynamic::of(arg) == Some(Dynamic::Keys))
+            })
+            .map(|sub| sub.get_name())
+            .collect()
+    }
+}
+
+fn visible_args(command: &Command) -> impl Iterator<Item = &Arg> {
+    command
+        .get_arguments()
+        .filter(|arg| !arg.is_hide_set() && !arg.is_positional())
+}
+
+/// The options of `sub` and of its own subcommands, whose names are
+/// offered as words.
+fn args_of(sub: &Command) -> Vec<&Arg> {
+    let mut args: Vec<&Arg> = visible_args(sub).collect();
+    for nested in sub.get_subcommands() {
+        for arg in visible_args(nested) {
+            if !args.iter().any(|seen| seen.get_id() == arg.get_id()) {
+                args.push(arg);
+            }
+        }
+    }
+    args
+}
+
+fn takes_value(arg: &Arg) -> bool {
+    !arg.is_positional() && matches!(arg.get_action(), ArgAction::Set | ArgAction::Append)
+}
+
+fn takes_path(arg: &Arg) -> bool {
+    takes_value(arg)
+        && matches!(
+            arg.get_value_hint(),
+            ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath
+        )
+}
+
+fn flags(arg: &Arg) -> Vec<String> {
+    let mut flags = Vec::new();
+    if let Some(short) = arg.get_short() {
+        flags.push(format!("-{}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        flags.push(format!("--{}", long));
+    }
+    flags
+}
+
+fn spellings<'a>(args: impl Iterator<Item = &'a Arg>) -> String {
+    let set: BTreeSet<String> = args.flat_map(flags).collect();
+    set.into_iter().collect::<Vec<_>>().join("|")
+}
+
+/// The words a subcommand completes to: its options and nested
+/// subcommands.
+fn words_of(sub: &Command) -> String {
+    let mut words: Vec<String> = sub
+        .get_subcommands()
+        .filter(|nested| !nested.is_hide_set())
+        .map(|nested| nested.get_name().to_string())
+        .collect();
+    for flag in args_of(sub).into_iter().flat_map(flags) {
+        if !words.contains(&flag) {
+            words.push(flag);
+        }
+    }
+    words.join(" ")
+}
+
+fn global_words(spec: &Spec<'_>) -> String {
+    let mut words: Vec<String> = spec.global.iter().copied().flat_map(flags).collect();
+    words.push("--help".to_string());
+    words.join(" ")
+}
+
+/// `bin` as a shell function name.
+fn function_name(bin: &str) -> String {
+    bin.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
+
+fn help(arg: &Arg) -> String {
+    arg.get_help()
+        .map(|help| help.to_string())
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .replace('\'', "\\'")
+}
+
+fn about(command: &Command) -> String {
+    command
+        .get_about()
+        .map(|about| about.to_string())
+        .unwrap_or_default()
+        .replace('\'', "\\'")
+}
+
+/// `case` pattern lines sending `prev` to the dynamic completer or to
+/// file completion.
+fn prev_cases(spec: &Spec<'_>, func: &str, files: &str) -> String {
+    let mut out = String::new();
+    for kind in [Dynamic::Keys, Dynamic::Profiles, Dynamic::KeyIds] {
+        let options = spec.dynamic_options(kind);
+        if !options.is_empty() {
+            let _ = writeln!(
+                out,
+                "        {}) __{}_dynamic {}; return ;;",
+                options,
+                func,
+                kind.name()
+            );
+        }
+    }
+    let paths = spec.path_options();
+    if !paths.is_empty() {
+        let _ = writeln!(out, "        {}) {}; return ;;", paths, files);
+    }
+    out
+}
+
+fn bash(spec: &Spec<'_>, bin: &str) -> String {
+    let func = function_name(bin);
+    let subcommands: Vec<&str> = spec.subcommands.iter().map(|s| s.get_name()).collect();
+    let mut cases = String::new();
+    for sub in &spec.subcommands {
+        let _ = writeln!(
+            cases,
+            "        {}) opts=\"{}\" ;;",
+            sub.get_name(),
+            words_of(sub)
+        );
+    }
+    format!(
+        r#"# bash completion for {bin}, generated by `{bin} completions bash`
+
+__{func}_dynamic() {{
+    local line value
+    COMPREPLY=()
+    while IFS= read -r line; do
+        value="${{line%%$'\t'*}}"
+        [[ "$value" == "$cur"* ]] && COMPREPLY+=("$value")
+    done < <("${{COMP_WORDS[0]}}" {complete} "$1" -- "${{COMP_WORDS[@]}}" 2>/dev/null)
+}}
+
+_{func}() {{
+    local cur="${{COMP_WORDS[COMP_CWORD]}}" prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local cmd="" opts="" i w
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        w="${{COMP_WORDS[i]}}"
+        if [[ " {value_options} " == *" $w "* ]]; then
+            ((i++))
+            continue
+        fi
+        [[ "$w" == -* ]] && continue
+        cmd="$w"
+        break
+    done
+
+    case "$prev" in
+{prev_cases}    esac
+
+    if [[ -z "$cmd" ]]; then
+        COMPREPLY=($(compgen -W "{subcommands} {global}" -- "$cur"))
+        return
+    fi
+    if [[ " {key_commands} " == *" $cmd "* && "$prev" == "$cmd" && "$cur" != -* ]]; then
+        __{func}_dynamic keys
+        return
+    fi
+    case "$cmd" in
+{cases}    esac
+    COMPREPLY=($(compgen -W "$opts {global}" -- "$cur"))
+}}
+
+complete -F _{func} {bin}
+"#,
+        bin = bin,
+        func = func,
+        complete = COMPLETE_COMMAND,
+        value_options = spec.value_options(),
+        prev_cases = prev_cases(spec, &func, "COMPREPLY=($(compgen -f -- \"$cur\"))"),
+        subcommands = subcommands.join(" "),
+        global = global_words(spec),
+        key_commands = spec.key_commands().join(" "),
+        cases = cases,
+    )
+}
+
+fn zsh(spec: &Spec<'_>, bin: &str) -> String {
+    let func = function_name(bin);
+    let subcommands: Vec<&str> = spec.subcommands.iter().map(|s| s.get_name()).collect();
+    let mut cases = String::new();
+    for sub in &spec.subcommands {
+        let _ = writeln!(
+            cases,
+            "        {}) opts=({}) ;;",
+            sub.get_name(),
+            words_of(sub)
+        );
+    }
+    format!(
+        r#"#compdef {bin}
+# zsh completion for {bin}, generated by `{bin} completions zsh`
+
+__{func}_dynamic() {{
+    local -a values
+    local line
+    for line in "${{(@f)$("${{words[1]}}" {complete} "$1" -- "${{words[@]}}" 2>/dev/null)}}"; do
+        [[ -n "$line" ]] && values+=("${{line/$'\t'/:}}")
+    done
+    _describe -t "$1" "$1" values
+}}
+
+_{func}() {{
+    local prev="${{words[CURRENT-1]}}" cmd="" i w
+    local -a opts
+    for ((i = 2; i < CURRENT; i++)); do
+        w="${{words[i]}}"
+        if [[ " {value_options} " == *" $w "* ]]; then
+            ((i++))
+            continue
+        fi
+        [[ "$w" == -* ]] && continue
+        cmd="$w"
+        break
+    done
+
+    case "$prev" in
+{prev_cases}    esac
+
+    if [[ -z "$cmd" ]]; then
+        compadd -- {subcommands} {global}
+        return
+    fi
+    if [[ " {key_commands} " == *" $cmd "* && "$prev" == "$cmd" && "$PREFIX" != -* ]]; then
+        __{func}_dynamic keys
+        return
+    fi
+    case "$cmd" in
+{cases}    esac
+    compadd -- "${{opts[@]}}" {global}
+}}
+
+if [[ "$funcstack[1]" == "_{func}" ]]; then
+    _{func} "$@"
+else
+    compdef _{func} {bin}
+fi
+"#,
+        bin = bin,
+        func = func,
+        complete = COMPLETE_COMMAND,
+        value_options = spec.value_options(),
+        prev_cases = prev_cases(spec, &func, "_files"),
+        subcommands = subcommands.join(" "),
+        global = global_words(spec),
+        key_commands = spec.key_commands().join(" "),
+        cases = cases,
+    )
+}
+
+fn fish(spec: &Spec<'_>, bin: &str) -> String {
+    let func = function_name(bin);
+    let mut out = format!(
+        r#"# fish completion for {bin}, generated by `{bin} completions fish`
+
+function __{func}_dynamic
+    set -l words (commandline -opc)
+    $words[1] {complete} $argv[1] -- $words 2>/dev/null
+end
+
+complete -c {bin} -f
+"#,
+        bin = bin,
+        func = func,
+        complete = COMPLETE_COMMAND,
+    );
+    for arg in &spec.global {
+        fish_arg(&mut out, bin, &func, None, arg);
+    }
+    for sub in &spec.subcommands {
+        let _ = writeln!(
+            out,
+            "complete -c {} -n __fish_use_subcommand -a {} -d '{}'",
+            bin,
+            sub.get_name(),
+            about(sub)
+        );
+        let condition = format!("__fish_seen_subcommand_from {}", sub.get_name());
+        for nested in sub.get_subcommands().filter(|n| !n.is_hide_set()) {
+            let _ = writeln!(
+                out,
+                "complete -c {} -n '{}' -a {} -d '{}'",
+                bin,
+                condition,
+                nested.get_name(),
+                about(nested)
+            );
+        }
+        for arg in args_of(sub) {
+            fish_arg(&mut out, bin, &func, Some(&condition), arg);
+        }
+        if sub
+            .get_positionals()
+            .any(|arg| Dynamic::of(arg) == Some(Dynamic::Keys))
+        {
+            let _ = writeln!(
+                out,
+                "complete -c {} -n '{}' -a '(__{}_dynamic keys)'",
+                bin, condition, func
+            );
+        }
+    }
+    out
+}
+
+fn fish_arg(out: &mut String, bin: &str, func: &str, condition: Option<&str>, arg: &Arg) {
+    let mut line = format!("complete -c {}", bin);
+    if let Some(condition) = condition {
+        let _ = write!(line, " -n '{}'", condition);
+    }
+    if let Some(short) = arg.get_short() {
+        let _ = write!(line, " -s {}", short);
+    }
+    if let Some(long) = arg.get_long() {
+        let _ = write!(line, " -l {}", long);
+    }
+    if let Some(kind) = Dynamic::of(arg).filter(|_| takes_value(arg)) {
+        let _ = write!(line, " -x -a '(__{}_dynamic {})'", func, kind.name());
+    } else if takes_path(arg) {
+        line.push_str(" -r -F");
+    } else if takes_value(arg) {
+        line.push_str(" -x");
+    }
+    let _ = writeln!(out, "{} -d '{}'", line, help(arg));
+}
+
+/// Candidates of `kind` for the command line `words`, one per line with an
+/// optional tab-separated description. Anything that cannot be read is
+/// skipped: completion never fails.
+pub(crate) fn candidates(kind: &str, words: &[String], schema: Option<&Value>) -> Vec<String> {
+    let configs = option_values(words, &["-c", "--config"]);
+    match kind {
+        "keys" => {
+            let schema = option_values(words, &["--schema"])
+                .last()
+                .and_then(|path| std::fs::read_to_string(path).ok())
+                .and_then(|text| serde_json::from_str(&text).ok())
+                .or_else(|| schema.cloned());
+            let mut keys = BTreeSet::new();
+            match schema {
+                Some(schema) => schema_keys(&schema, &schema, "", &mut keys, 0),
+                None => {
+                    for document in configs.iter().filter_map(|path| read(Path::new(path))) {
+                        document_keys(&document, "", &mut keys, 0);
+                    }
+                }
+            }
+            keys.into_iter().collect()
+        }
+        "profiles" => {
+            let mut profiles = BTreeSet::new();
+            for document in configs.iter().filter_map(|path| read(Path::new(path))) {
+                if let Some(Value::Object(sections)) = document.get(PROFILE_SECTION) {
+                    profiles.extend(sections.keys().cloned());
+                }
+            }
+            profiles.into_iter().collect()
+        }
+        "key-ids" => key_ids(words),
+        _ => Vec::new(),
+    }
+}
+
+/// The values given to any of `names` in `words`, as `-c a` or `--config=a`.
+fn option_values(words: &[String], names: &[&str]) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut words = words.iter();
+    while let Some(word) = words.next() {
+        if names.contains(&word.as_str()) {
+            if let Some(value) = words.next() {
+                values.push(value.clone());
+            }
+        } else if let Some((name, value)) = word.split_once('=') {
+            if names.contains(&name) {
+                values.push(value.to_string());
+            }
+        }
+    }
+    values
+}
+
+fn read(path: &Path) -> Option<Value> {
+    let format = detect_format_from_path(path)?;
+    let text = std::fs::read_to_string(path).ok()?;
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    parse_content(&text, format, SourceId::new(name), Some(path))
+        .ok()
+        .map(|value| value.to_json())
+}
+
+fn join(prefix: &str, key: &str) -> String {
+    if prefix.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", prefix, key)
+    }
+}
+
+fn schema_keys(
+    schema: &Value,
+    root: &Value,
+    prefix: &str,
+    keys: &mut BTreeSet<String>,
+    depth: usize,
+) {
+    if depth > MAX_DEPTH {
+        return;
+    }
+    let schema = resolve(schema, root);
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    for (name, property) in properties {
+        let key = join(prefix, name);
+        schema_keys(property, root, &key, keys, depth + 1);
+        keys.insert(key);
+    }
+}
+
+fn document_keys(value: &Value, prefix: &str, keys: &mut BTreeSet<String>, depth: usize) {
+    let Value::Object(map) = value else {
+        return;
+    };
+    if depth > MAX_DEPTH {
+        return;
+    }
+    for (name, child) in map {
+        // Profile sections repeat the keys under `profile.<name>`
+        if depth == 0 && name == PROFILE_SECTION {
+            continue;
+        }
+        let key = join(prefix, name);
+        document_keys(child, &key, keys, depth + 1);
+        keys.insert(key);
+    }
+}
+
+/// Key rings of `--key-store`, opened with the master key the command line
+/// names (or `CONFERS_MASTER_KEY`).
+#[cfg(feature = "key")]
+fn key_ids(words: &[String]) -> Vec<String> {
+    let Some(store) = option_values(words, &["--key-store"]).pop() else {
+        return Vec::new();
+    };
+    let master_key_env = option_values(words, &["--master-key-env"])
+        .pop()
+        .unwrap_or_else(|| "CONFERS_MASTER_KEY".to_string());
+    let keychain = words.iter().any(|word| word == "--keychain");
+    let Ok((storage, _)) = super::open_key_store(Path::new(&store), &master_key_env, keychain)
+    else {
+        return Vec::new();
+    };
+    let mut keys = storage.get_key_manager().list_keys();
+    keys.sort_by(|a, b| a.key_id.cmp(&b.key_id));
+    keys.into_iter()
+        .map(|key| format!("{}\tversion {}", key.key_id, key.current_version))
+        .collect()
+}
+
+#[cfg(not(feature = "key"))]
+fn key_ids(_words: &[String]) -> Vec<String> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn words(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_keys_come_from_the_schema() {
+        let schema = json!({
+            "properties": {
+                "server": { "$ref": "#/$defs/Server" },
+                "name": { "type": "string" }
+            },
+            "$defs": {
+                "Server": { "properties": { "port": {}, "tls": { "properties": { "cert": {} } } } }
+            }
+        });
+        assert_eq!(
+            candidates("keys", &words("confers inspect -k"), Some(&schema)),
+            [
+                "name",
+                "server",
+                "server.port",
+                "server.tls",
+                "server.tls.cert"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keys_and_profiles_come_from_config_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app.toml");
+        std::fs::write(
+            &app,
+            "port = 1\n[db]\nurl = \"x\"\n[profile.prod]\nport = 2\n[profile.dev]\nport = 3\n",
+        )
+        .unwrap();
+        let schema = dir.path().join("app.schema.json");
+        std::fs::write(&schema, r#"{"properties": {"only": {}}}"#).unwrap();
+
+        let line = words(&format!("confers --config={} get", app.display()));
+        assert_eq!(candidates("keys", &line, None), ["db", "db.url", "port"]);
+        assert_eq!(candidates("profiles", &line, None), ["dev", "prod"]);
+
+        let line = words(&format!(
+            "confers -c {} validate --schema {} -k",
+            app.display(),
+            schema.display()
+        ));
+        assert_eq!(candidates("keys", &line, None), ["only"]);
+        assert!(candidates("keys", &words("confers -c missing.toml get"), None).is_empty());
+        assert!(candidates("unknown", &line, None).is_empty());
+    }
+
+    #[cfg(feature = "key")]
+    #[test]
+    fn test_key_ids_come_from_the_key_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let master = *b"0123456789abcdef0123456789abcdef";
+        let env = "CONFERS_COMPLETIONS_TEST_MASTER_KEY";
+        std::env::set_var(env, std::str::from_utf8(&master).unwrap());
+        crate::key::KeyStorage::new(dir.path().to_path_buf())
+            .unwrap()
+            .initialize_with_master_key(&master, "primary".to_string(), "test".to_string())
+            .unwrap();
+
+        let line = words(&format!(
+            "confers decrypt --key-store {} --master-key-env {} --key-id",
+            dir.path().display(),
+            env
+        ));
+        assert_eq!(candidates("key-ids", &line, None), ["primary\tversion 1"]);
+        assert!(candidates("key-ids", &words("confers decrypt --key-id"), None).is_empty());
+    }
+
+    #[test]
+    fn test_scripts_route_dynamic_options() {
+        let command = <super::super::Cli as clap::CommandFactory>::command();
+        let bash = script(&command, "bash", "my-app").unwrap();
+        assert!(bash.contains("--key|-k) __my_app_dynamic keys; return ;;"));
+        assert!(bash.contains("--profile|-p) __my_app_dynamic profiles; return ;;"));
+        assert!(bash.contains(" get set "));
+        assert!(bash.contains("complete -F _my_app my-app"));
+
+        let zsh = script(&command, "zsh", "my-app").unwrap();
+        assert!(zsh.starts_with("#compdef my-app\n"));
+        assert!(zsh.contains("compdef _my_app my-app"));
+
+        let fish = script(&command, "fish", "my-app").unwrap();
+        assert!(fish.contains(
+            "complete -c my-app -n '__fish_seen_subcommand_from inspect' -s k -l key -x -a '(__my_app_dynamic keys)'"
+        ));
+        assert!(!fish.contains("-a __complete"));
+
+        assert!(script(&command, "powershell", "my-app").is_err());
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Machine-readable diagnostics for `confers validate --format json|sarif`.
+//!
+//! Every problem becomes a [`Diagnostic`] with a rule id, a severity, the
+//! dotted key and, when the source tracks it, the file, line and column.
+//! [`to_sarif`] renders them as a SARIF 2.1.0 log, which GitHub code
+//! scanning and most CI systems turn into inline annotations.
+
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use super::lint::Severity;
+use crate::error::{ConfigError, ValidationIssue};
+use crate::types::SourceLocation;
+
+/// Rule of the built-in checks that are not part of a schema.
+pub(crate) const CONFIG_CHECK: &str = "config-check";
+/// Rule of a configuration that could not be loaded at all.
+pub(crate) const LOAD_ERROR: &str = "load-error";
+
+/// One problem found in a configuration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub(crate) struct Diagnostic {
+    pub rule: String,
+    pub severity: Severity,
+    /// Dotted key, empty when the problem is not tied to one
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub path: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl Diagnostic {
+    pub fn new(rule: impl Into<String>, severity: Severity, message: impl Into<String>) -> Self {
+        Self {
+            rule: rule.into(),
+            severity,
+            path: String::new(),
+            message: message.into(),
+            file: None,
+            line: None,
+            column: None,
+        }
+    }
+
+    /// A schema violation; its rule is `schema/<keyword>`.
+    pub fn from_issue(issue: &ValidationIssue) -> Self {
+        let mut message = issue.constraint.clone();
+        if let Some(suggestion) = &issue.suggestion {
+            message.push_str(&format!(" (did you mean `{}`?)", suggestion));
+        }
+        let mut diagnostic = Self::new(
+            format!("schema/{}", issue.rule.as_deref().unwrap_or("invalid")),
+            Severity::Error,
+            message,
+        );
+        diagnostic.path = issue.path.clone();
+        match &issue.location {
+            Some(location) => diagnostic.at(location),
+            None => diagnostic,
+        }
+    }
+
+    /// A configuration that failed to load, located when it failed to parse.
+    pub fn from_error(error: &ConfigError) -> Self {
+        let diagnostic = Self::new(LOAD_ERROR, Severity::Error, error.to_string());
+        match error {
+            ConfigError::ParseError {
+                location: Some(location),
+                ..
+            } => diagnostic.at(location),
+            _ => diagnostic,
+        }
+    }
+
+    fn at(mut self, location: &SourceLocation) -> Self {
+        let file = match &location.file_path {
+            Some(path) => relative(path),
+            None => location.source_name.to_string(),
+        };
+        self.file = Some(file);
+        self.line = Some(location.line);
+        self.column = Some(location.column);
+        self
+    }
+
+    /// Find the line defining the key of the diagnostic in `files`, when it
+    /// has no location of its own. Only the files named `source` are
+    /// searched if any is; later files win, as they do when merging.
+    pub fn locate(mut self, files: &[PathBuf], source: Option<&str>) -> Self {
+        if self.line.is_some() || self.path.is_empty() {
+            return self;
+        }
+        let named = |file: &&PathBuf| {
+            source.is_some_and(|source| file.file_name().is_some_and(|name| name == source))
+        };
+        let candidates: Vec<&PathBuf> = if files.iter().any(|f| named(&f)) {
+            files.iter().filter(named).collect()
+        } else {
+            files.iter().collect()
+        };
+        for file in candidates.into_iter().rev() {
+            let Ok(content) = std::fs::read_to_string(file) else {
+                continue;
+            };
+            if let Some((line, column)) = find_key(&content, &self.path) {
+                self.file = Some(relative(file));
+                self.line = Some(line);
+                self.column = Some(column);
+                break;
+            }
+        }
+        self
+    }
+
+    /// Attribute the diagnostic to `file` when it has no location of its own.
+    pub fn or_file(mut self, file: Option<&Path>) -> Self {
+        if self.file.is_none() {
+            self.file = file.map(relative);
+        }
+        self
+    }
+}
+
+impl std::fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}", file)?;
+            if let (Some(line), Some(column)) = (self.line, self.column) {
+                write!(f, ":{}:{}", line, column)?;
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{}[{}] ", self.severity, self.rule)?;
+        if !self.path.is_empty() {
+            write!(f, "{}: ", self.path)?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
+
+/// `path` relative to the working directory when it is below it, with `/`
+/// separators, as SARIF and code-scanning tools expect.
+fn relative(path: &Path) -> String {
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok())
+        .unwrap_or(path);
+    path.to_string_lossy().replace('\\', "/")
+}
+
+/// 1-based line and column of the last segment of dotted `path` in a TOML,
+/// YAML or JSON document, found by searching for each segment as a key or
+/// table header after the previous one. Array indices are skipped.
+fn find_key(content: &str, path: &str) -> Option<(usize, usize)> {
+    let lines: Vec<&str> = content.lines().collect();
+    // Position of the previous segment; `[server.tls]` holds two on one line
+    let (mut from_line, mut from_column) = (0, 0);
+    let mut found = None;
+    for segment in path.split('.').filter(|s| s.parse::<usize>().is_err()) {
+        let key = regex::Regex::new(&format!(
+            r#"^\s*(?:-\s+)?(?:\[\[?\s*)?(?:["']?[\w-]+["']?\.)*["']?(?P<key>{})["']?(?:\.["']?[\w-]+["']?)*\s*(?:\]\]?|[:=])"#,
+            regex::escape(segment)
+        ))
+        .ok()?;
+        let (line, column) = lines
+            .iter()
+            .enumerate()
+            .skip(from_line)
+            .find_map(|(i, line)| {
+                let start = key.captures(line)?.name("key")?.start();
+                (i > from_line || start >= from_column).then_some((i, start))
+            })?;
+        (from_line, from_column) = (line, column + segment.len());
+        found = Some((line + 1, column + 1));
+    }
+    found
+}
+
+/// A SARIF 2.1.0 log with one run of `confers` and one result per diagnostic.
+pub(crate) fn to_sarif(diagnostics: &[Diagnostic]) -> serde_json::Value {
+    let mut rules: Vec<&str> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    rules.sort_unstable();
+    rules.dedup();
+    let results: Vec<serde_json::Value> = diagnostics
+        .iter()
+        .map(|d| {
+            let mut text = d.message.clone();
+            if !d.path.is_empty() {
+                text = format!("{}: {}", d.path, text);
+            }
+            let mut result = serde_json::json!({
+                "ruleId": d.rule,
+                "level": sarif_level(d.severity),
+                "message": { "text": text },
+            });
+            if let Some(file) = &d.file {
+                let mut physical = serde_json::json!({ "artifactLocation": { "uri": file } });
+                if let (Some(line), Some(column)) = (d.line, d.column) {
+                    physical["region"] = serde_json::json!({
+                        "startLine": line.max(1),
+                        "startColumn": column.max(1),
+                    });
+                }
+                result["locations"] = serde_json::json!([{ "physicalLocation": physical }]);
+            }
+            result
+        })
+        .collect();
+    serde_json::json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "confers",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules.iter().map(|id| serde_json::json!({ "id": id })).collect::<Vec<_>>(),
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn sarif_level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info | Severity::Off => "note",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_from_issue_keeps_rule_and_location() {
+        let issue = ValidationIssue::new("server.port", "70000 is greater than the maximum 65535")
+            .with_rule("maximum")
+            .with_location(SourceLocation::new("app.toml", 3, 8));
+        let diagnostic = Diagnostic::from_issue(&issue);
+        assert_eq!(diagnostic.rule, "schema/maximum");
+        assert_eq!(diagnostic.file.as_deref(), Some("app.toml"));
+        assert_eq!((diagnostic.line, diagnostic.column), (Some(3), Some(8)));
+        assert_eq!(
+            diagnostic.to_string(),
+            "app.toml:3:8: error[schema/maximum] server.port: 70000 is greater than the maximum 65535"
+        );
+    }
+
+    #[test]
+    fn test_find_key_per_format() {
+        let toml = "name = \"x\"\n[server]\nhost = \"a\"\nport = 1\n[server.tls]\ncert = \"c\"\n";
+        assert_eq!(find_key(toml, "server.port"), Some((4, 1)));
+        assert_eq!(find_key(toml, "server.tls.cert"), Some((6, 1)));
+        assert_eq!(find_key("db.pool.max = 3\n", "db.pool.max"), Some((1, 9)));
+
+        let yaml = "server:\n  port: 1\nclient:\n  port: 2\n";
+        assert_eq!(find_key(yaml, "client.port"), Some((4, 3)));
+        let yaml = "upstreams:\n  - host: a\n    port: x\n";
+        assert_eq!(find_key(yaml, "upstreams.0.port"), Some((3, 5)));
+
+        let json = "{\n  \"server\": {\n    \"port\": \"8080\"\n  }\n}\n";
+        assert_eq!(find_key(json, "server.port"), Some((3, 6)));
+        assert_eq!(find_key(json, "server.missing"), None);
+    }
+
+    #[test]
+    fn test_locate_prefers_the_named_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.toml");
+        let local = dir.path().join("local.toml");
+        std::fs::write(&base, "port = 1\n").unwrap();
+        std::fs::write(&local, "\nport = \"x\"\n").unwrap();
+        let files = [base.clone(), local];
+        let issue = ValidationIssue::new("port", "expected integer, found string");
+
+        let diagnostic = Diagnostic::from_issue(&issue).locate(&files, Some("base.toml"));
+        assert!(diagnostic.file.unwrap().ends_with("base.toml"));
+        assert_eq!(diagnostic.line, Some(1));
+        let diagnostic = Diagnostic::from_issue(&issue).locate(&files, Some("env"));
+        assert!(diagnostic.file.unwrap().ends_with("local.toml"));
+        assert_eq!(diagnostic.line, Some(2));
+    }
+
+    #[test]
+    fn test_sarif_log_shape() {
+        let diagnostics = [
+            Diagnostic::from_issue(
+                &ValidationIssue::new("port", "expected integer, found string")
+                    .with_rule("type")
+                    .with_location(SourceLocation::new("app.toml", 2, 8)),
+            ),
+            Diagnostic::new(CONFIG_CHECK, Severity::Warning, "Key 'a' has null value")
+                .or_file(Some(Path::new("app.toml"))),
+        ];
+        let log = to_sarif(&diagnostics);
+        assert_eq!(log["version"], "2.1.0");
+        let run = &log["runs"][0];
+        assert_eq!(run["tool"]["driver"]["name"], "confers");
+        assert_eq!(
+            run["tool"]["driver"]["rules"],
+            serde_json::json!([{ "id": "config-check" }, { "id": "schema/type" }])
+        );
+        let results = run["results"].as_array().unwrap();
+        assert_eq!(results[0]["ruleId"], "schema/type");
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(
+            results[0]["message"]["text"],
+            "port: expected integer, found string"
+        );
+        let physical = &results[0]["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "app.toml");
+        assert_eq!(physical["region"]["startLine"], 2);
+        assert_eq!(results[1]["level"], "warning");
+        assert!(results[1]["locations"][0]["physicalLocation"]
+            .get("region")
+            .is_none());
+    }
+}
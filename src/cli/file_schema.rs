@@ -0,0 +1,235 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Constraints shipped with the configuration files, for `confers validate`
+//! in repositories that do not build the application.
+//!
+//! Two places are read for every `-c` file:
+//!
+//! - a top-level `__schema__` block in the file itself;
+//! - a sidecar next to it named after the file, e.g. `app.schema.yaml` for
+//!   `app.toml` (`.yaml`, `.yml`, `.toml` and `.json` are tried).
+//!
+//! Both map dotted keys to JSON Schema keywords, with `required = true`
+//! marking keys that must be present:
+//!
+//! ```toml
+//! [__schema__."server.port"]
+//! type = "integer"
+//! minimum = 1
+//! maximum = 65535
+//! required = true
+//! ```
+//!
+//! A sidecar that is a complete JSON Schema (it has `$schema` or
+//! `properties` at the top) is used as is.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::{json, Map, Value};
+
+use crate::loader::{detect_format_from_path, parse_content, EMBEDDED_SCHEMA_KEY};
+use crate::types::SourceId;
+
+/// Sidecar extensions, in lookup order.
+const SIDECAR_EXTENSIONS: [&str; 4] = ["yaml", "yml", "toml", "json"];
+
+/// A schema found for one of the configuration files.
+pub(crate) struct FileSchema {
+    /// The file the constraints were read from.
+    pub(crate) origin: PathBuf,
+    pub(crate) schema: Value,
+}
+
+/// The embedded blocks and sidecars of `config_paths`. Missing files are
+/// skipped, as the build skips them.
+pub(crate) fn collect(config_paths: &[PathBuf]) -> Result<Vec<FileSchema>> {
+    let mut schemas = Vec::new();
+    for path in config_paths.iter().filter(|p| p.exists()) {
+        if let Some(block) = read(path)?
+            .as_ref()
+            .and_then(|value| value.get(EMBEDDED_SCHEMA_KEY))
+        {
+            schemas.push(FileSchema {
+                origin: path.clone(),
+                schema: from_constraints(block).with_context(|| {
+                    format!("Invalid {} in {}", EMBEDDED_SCHEMA_KEY, path.display())
+                })?,
+            });
+        }
+        for sidecar in sidecars(path).filter(|p| p.is_file()) {
+            let Some(content) = read(&sidecar)? else {
+                continue;
+            };
+            let schema = if content.get("$schema").is_some() || content.get("properties").is_some()
+            {
+                content
+            } else {
+                from_constraints(&content)
+                    .with_context(|| format!("Invalid schema {}", sidecar.display()))?
+            };
+            schemas.push(FileSchema {
+                origin: sidecar,
+                schema,
+            });
+        }
+    }
+    Ok(schemas)
+}
+
+/// `app.schema.yaml` and friends for `app.toml`.
+fn sidecars(path: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    SIDECAR_EXTENSIONS
+        .iter()
+        .map(move |ext| path.with_file_name(format!("{}.schema.{}", stem, ext)))
+        .filter(move |sidecar| sidecar != path)
+}
+
+/// Parse `path` without dropping its `__schema__` block. `None` for
+/// formats the build would not read either.
+fn read(path: &Path) -> Result<Option<Value>> {
+    let Some(format) = detect_format_from_path(path) else {
+        return Ok(None);
+    };
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let value = parse_content(&content, format, SourceId::new(name), Some(path))?;
+    Ok(Some(value.to_json()))
+}
+
+/// Turn a map of dotted keys to constraints into a JSON Schema.
+pub(crate) fn from_constraints(constraints: &Value) -> Result<Value> {
+    let Value::Object(entries) = constraints else {
+        anyhow::bail!("expected a table of dotted keys");
+    };
+    let mut root = json!({});
+    for (key, constraint) in entries {
+        let Value::Object(constraint) = constraint else {
+            anyhow::bail!("the constraint for '{}' must be a table", key);
+        };
+        if key.split('.').any(str::is_empty) {
+            anyhow::bail!("'{}' is not a dotted key", key);
+        }
+        let required = constraint.get("required") == Some(&Value::Bool(true));
+
+        let mut node = &mut root;
+        for segment in key.split('.') {
+            let object = as_object(node, key)?;
+            if required {
+                let list = object.entry("required").or_insert_with(|| json!([]));
+                if let Some(list) = list.as_array_mut() {
+                    if !list.iter().any(|k| k == segment) {
+                        list.push(json!(segment));
+                    }
+                }
+            }
+            let properties = object.entry("properties").or_insert_with(|| json!({}));
+            node = as_object(properties, key)?
+                .entry(segment)
+                .or_insert_with(|| json!({}));
+        }
+        let leaf = as_object(node, key)?;
+        for (keyword, value) in constraint {
+            // `required = true` was handled above; a list is the JSON Schema
+            // keyword for a table's own keys
+            if keyword == "required" && value.is_boolean() {
+                continue;
+            }
+            leaf.insert(keyword.clone(), value.clone());
+        }
+    }
+    Ok(root)
+}
+
+fn as_object<'a>(node: &'a mut Value, key: &str) -> Result<&'a mut Map<String, Value>> {
+    node.as_object_mut()
+        .with_context(|| format!("conflicting constraints for '{}'", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraints_become_nested_schema() {
+        let schema = from_constraints(&json!({
+            "server.port": { "type": "integer", "maximum": 65535, "required": true },
+            "server": { "type": "object" },
+            "log.level": { "enum": ["info", "debug"] },
+        }))
+        .unwrap();
+        assert_eq!(
+            schema,
+            json!({
+                "required": ["server"],
+                "properties": {
+                    "server": {
+                        "type": "object",
+                        "required": ["port"],
+                        "properties": {
+                            "port": { "type": "integer", "maximum": 65535 }
+                        }
+                    },
+                    "log": {
+                        "properties": { "level": { "enum": ["info", "debug"] } }
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_constraints_are_rejected() {
+        assert!(from_constraints(&json!(["port"])).is_err());
+        assert!(from_constraints(&json!({ "port": 1 })).is_err());
+        assert!(from_constraints(&json!({ "server..port": {} })).is_err());
+        assert!(from_constraints(&json!({
+            "a": { "properties": 1 },
+            "a.b": {},
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_collect_reads_embedded_blocks_and_sidecars() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app.toml");
+        std::fs::write(&app, "port = 1\n\n[__schema__.port]\nminimum = 1024\n").unwrap();
+        std::fs::write(
+            dir.path().join("app.schema.yaml"),
+            "name:\n  type: string\n  required: true\n",
+        )
+        .unwrap();
+        let other = dir.path().join("other.toml");
+        std::fs::write(&other, "x = 1\n").unwrap();
+        std::fs::write(
+            dir.path().join("other.schema.json"),
+            r#"{"$schema": "https://json-schema.org/draft/2020-12/schema", "required": ["y"]}"#,
+        )
+        .unwrap();
+
+        let schemas = collect(&[app.clone(), other, dir.path().join("missing.toml")]).unwrap();
+        let origins: Vec<_> = schemas
+            .iter()
+            .map(|s| s.origin.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            origins,
+            ["app.toml", "app.schema.yaml", "other.schema.json"]
+        );
+        assert_eq!(schemas[0].schema["properties"]["port"]["minimum"], 1024);
+        assert_eq!(schemas[1].schema["required"], json!(["name"]));
+        assert_eq!(schemas[2].schema["required"], json!(["y"]));
+    }
+}
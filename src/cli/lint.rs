@@ -0,0 +1,799 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! `confers lint` - checks configuration files for problems that schema
+//! validation does not catch.
+//!
+//! Each rule has an id, a default severity and optionally an automatic fix.
+//! Severities can be changed per rule with `--rule <id>=<severity>`.
+
+use anyhow::{Context, Result};
+use serde::de::{DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde_yaml_ng::{Mapping, Value};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use crate::loader::{detect_format_from_path, Format};
+
+/// How serious a finding is. `Error` findings make the command fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Severity {
+    Off,
+    Info,
+    Warning,
+    Error,
+}
+
+impl std::str::FromStr for Severity {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(Severity::Off),
+            "info" => Ok(Severity::Info),
+            "warn" | "warning" => Ok(Severity::Warning),
+            "error" => Ok(Severity::Error),
+            other => anyhow::bail!("Unknown severity: {} (off, info, warning, error)", other),
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Off => write!(f, "off"),
+            Severity::Info => write!(f, "info"),
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// A lint rule and its default severity.
+pub(crate) struct Rule {
+    pub id: &'static str,
+    pub severity: Severity,
+}
+
+/// A key is not defined by the `--schema`.
+pub(crate) const UNKNOWN_KEY: &str = "unknown-key";
+/// A key is deprecated by `--deprecated` or by `deprecated` in the schema.
+pub(crate) const DEPRECATED_KEY: &str = "deprecated-key";
+/// A sensitive value is stored without `enc:` encryption.
+pub(crate) const PLAINTEXT_SECRET: &str = "plaintext-secret";
+/// A key is defined more than once in the same table.
+pub(crate) const DUPLICATE_KEY: &str = "duplicate-key";
+/// A production configuration points at a loopback or wildcard address.
+pub(crate) const LOCALHOST_IN_PROD: &str = "localhost-in-prod";
+
+/// All rules with their default severity.
+pub(crate) const RULES: &[Rule] = &[
+    Rule {
+        id: DUPLICATE_KEY,
+        severity: Severity::Error,
+    },
+    Rule {
+        id: UNKNOWN_KEY,
+        severity: Severity::Warning,
+    },
+    Rule {
+        id: DEPRECATED_KEY,
+        severity: Severity::Warning,
+    },
+    Rule {
+        id: PLAINTEXT_SECRET,
+        severity: Severity::Error,
+    },
+    Rule {
+        id: LOCALHOST_IN_PROD,
+        severity: Severity::Warning,
+    },
+];
+
+/// Matches strings whose host is a loopback or wildcard address.
+static LOCAL_ADDRESS_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"(?i)^(?:[a-z][a-z0-9+.-]*://)?(?:[^@/\s]*@)?(?:localhost|127\.0\.0\.1|0\.0\.0\.0|\[::1\])(?::\d+)?(?:[/?#].*)?$",
+    )
+    .expect("LOCAL_ADDRESS_RE regex is valid")
+});
+
+/// Settings for a lint run.
+#[derive(Debug, Default)]
+pub(crate) struct LintOptions {
+    /// Severity overrides by rule id.
+    pub severities: HashMap<&'static str, Severity>,
+    /// JSON Schema the keys are checked against.
+    pub schema: Option<serde_json::Value>,
+    /// Deprecated keys and their optional replacement.
+    pub deprecated: Vec<(String, Option<String>)>,
+    /// Active profile; `prod` / `production` enables the localhost rule.
+    pub profile: Option<String>,
+}
+
+impl LintOptions {
+    /// Apply a `<rule>=<severity>` override.
+    pub fn set_rule(&mut self, spec: &str) -> Result<()> {
+        let (id, severity) = spec
+            .split_once('=')
+            .with_context(|| format!("Expected <rule>=<severity>, got {}", spec))?;
+        let rule = RULES
+            .iter()
+            .find(|r| r.id == id.trim())
+            .with_context(|| format!("Unknown lint rule: {}", id))?;
+        self.severities.insert(rule.id, severity.trim().parse()?);
+        Ok(())
+    }
+
+    /// Add a `<old>` or `<old>=<new>` deprecation.
+    pub fn add_deprecated(&mut self, spec: &str) {
+        match spec.split_once('=') {
+            Some((old, new)) => self
+                .deprecated
+                .push((old.trim().to_string(), Some(new.trim().to_string()))),
+            None => self.deprecated.push((spec.trim().to_string(), None)),
+        }
+    }
+
+    fn severity(&self, id: &str) -> Severity {
+        self.severities.get(id).copied().unwrap_or_else(|| {
+            RULES
+                .iter()
+                .find(|r| r.id == id)
+                .map(|r| r.severity)
+                .unwrap_or(Severity::Warning)
+        })
+    }
+
+    fn is_production(&self, path: &Path) -> bool {
+        let is_prod = |name: &str| {
+            let name = name.to_lowercase();
+            name.split(|c: char| !c.is_ascii_alphanumeric())
+                .any(|part| part == "prod" || part == "production")
+        };
+        match &self.profile {
+            Some(profile) => is_prod(profile),
+            None => path
+                .file_name()
+                .is_some_and(|name| is_prod(&name.to_string_lossy())),
+        }
+    }
+}
+
+/// An automatic fix for a finding.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub(crate) enum Fix {
+    /// Move the value to a new key.
+    Rename { to: String },
+}
+
+/// A single finding.
+#[derive(Debug, Clone, serde::Serialize)]
+pub(crate) struct Issue {
+    pub rule: &'static str,
+    pub severity: Severity,
+    pub file: PathBuf,
+    pub key: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Fix>,
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}[{}] {}",
+            self.severity,
+            self.rule,
+            self.file.display()
+        )?;
+        if !self.key.is_empty() {
+            write!(f, ": {}", self.key)?;
+        }
+        write!(f, ": {}", self.message)?;
+        if self.fix.is_some() {
+            write!(f, " (fixable)")?;
+        }
+        Ok(())
+    }
+}
+
+/// Lint one file. Returns the parsed document (last value wins for duplicate
+/// keys) when the file could be read as a tree, so fixes can be applied.
+pub(crate) fn lint_file(path: &Path, options: &LintOptions) -> Result<(Vec<Issue>, Option<Value>)> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut issues = Vec::new();
+    let mut report = |rule: &'static str, key: String, message: String, fix: Option<Fix>| {
+        let severity = options.severity(rule);
+        if severity != Severity::Off {
+            issues.push(Issue {
+                rule,
+                severity,
+                file: path.to_path_buf(),
+                key,
+                message,
+                fix,
+            });
+        }
+    };
+
+    let duplicates = RefCell::new(Vec::new());
+    let document = match detect_format_from_path(path) {
+        Some(Format::Toml) => match toml::from_str::<Value>(&content) {
+            Ok(document) => Some(document),
+            // TOML rejects duplicate keys while parsing; report those as a
+            // finding rather than a failure.
+            Err(e) if e.message().contains("duplicate key") => {
+                report(DUPLICATE_KEY, String::new(), e.message().to_string(), None);
+                None
+            }
+            Err(e) => return Err(e).with_context(|| format!("Failed to parse {}", path.display())),
+        },
+        Some(Format::Json) => {
+            let mut de = serde_json::Deserializer::from_str(&content);
+            let document = Tree::root(&duplicates)
+                .deserialize(&mut de)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            Some(document)
+        }
+        Some(Format::Yaml) => {
+            let de = serde_yaml_ng::Deserializer::from_str(&content);
+            let document = Tree::root(&duplicates)
+                .deserialize(de)
+                .with_context(|| format!("Failed to parse {}", path.display()))?;
+            Some(document)
+        }
+        _ => anyhow::bail!("Unsupported configuration format: {}", path.display()),
+    };
+    for key in duplicates.into_inner() {
+        report(
+            DUPLICATE_KEY,
+            key,
+            "defined more than once; only the last value is used".to_string(),
+            None,
+        );
+    }
+
+    let Some(document) = document else {
+        return Ok((issues, None));
+    };
+
+    let mut leaves = Vec::new();
+    collect_leaves(&document, String::new(), &mut leaves);
+
+    if let Some(schema) = &options.schema {
+        let mut unknown = Vec::new();
+        let mut deprecated = Vec::new();
+        check_schema(
+            &document,
+            schema,
+            schema,
+            String::new(),
+            &mut unknown,
+            &mut deprecated,
+        );
+        for (key, suggestion) in unknown {
+            let message = match suggestion {
+                Some(suggestion) => {
+                    format!("not defined by the schema (did you mean `{}`?)", suggestion)
+                }
+                None => "not defined by the schema".to_string(),
+            };
+            report(UNKNOWN_KEY, key, message, None);
+        }
+        for key in deprecated {
+            report(
+                DEPRECATED_KEY,
+                key,
+                "deprecated by the schema".to_string(),
+                None,
+            );
+        }
+    }
+
+    for (old, new) in &options.deprecated {
+        if lookup(&document, old).is_none() {
+            continue;
+        }
+        match new {
+            Some(new) => {
+                let fix = lookup(&document, new)
+                    .is_none()
+                    .then(|| Fix::Rename { to: new.clone() });
+                report(
+                    DEPRECATED_KEY,
+                    old.clone(),
+                    format!("deprecated; use {} instead", new),
+                    fix,
+                );
+            }
+            None => report(DEPRECATED_KEY, old.clone(), "deprecated".to_string(), None),
+        }
+    }
+
+    for (key, value) in &leaves {
+        let Value::String(s) = value else {
+            continue;
+        };
+        let plaintext = !s.is_empty() && !s.starts_with("enc:") && !s.contains("${");
+        if plaintext && crate::error::is_sensitive_value(key, s) {
+            report(
+                PLAINTEXT_SECRET,
+                key.clone(),
+                "sensitive value is stored in plaintext; encrypt it with `confers encrypt` or `confers set --encrypt`".to_string(),
+                None,
+            );
+        }
+    }
+
+    if options.is_production(path) {
+        for (key, value) in &leaves {
+            if let Value::String(s) = value {
+                if LOCAL_ADDRESS_RE.is_match(s.trim()) {
+                    report(
+                        LOCALHOST_IN_PROD,
+                        key.clone(),
+                        format!("points at local address `{}` in a production config", s),
+                        None,
+                    );
+                }
+            }
+        }
+    }
+
+    Ok((issues, Some(document)))
+}
+
+/// Apply the fixes of `issues` to `document`. Returns the number applied.
+pub(crate) fn apply_fixes(document: &mut Value, issues: &[Issue]) -> usize {
+    let mut applied = 0;
+    for issue in issues {
+        let Some(Fix::Rename { to }) = &issue.fix else {
+            continue;
+        };
+        if let Some(value) = remove(document, &issue.key) {
+            if insert(document, to, value) {
+                applied += 1;
+            }
+        }
+    }
+    applied
+}
+
+/// Every non-table value with its dotted key.
+fn collect_leaves(value: &Value, path: String, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, child) in map {
+                collect_leaves(child, join(&path, &key_string(key)), out);
+            }
+        }
+        Value::Sequence(items) => {
+            for (i, child) in items.iter().enumerate() {
+                collect_leaves(child, join(&path, &i.to_string()), out);
+            }
+        }
+        other => out.push((path, other.clone())),
+    }
+}
+
+/// Compare a document with a JSON Schema node, collecting keys the schema
+/// does not define (with the closest defined key) and keys it marks as
+/// deprecated.
+fn check_schema(
+    value: &Value,
+    node: &serde_json::Value,
+    root: &serde_json::Value,
+    path: String,
+    unknown: &mut Vec<(String, Option<String>)>,
+    deprecated: &mut Vec<String>,
+) {
+    let nodes = expand_schema(node, root, 0);
+    match value {
+        Value::Mapping(map) => {
+            let mut properties = serde_json::Map::new();
+            let mut additional = Vec::new();
+            let mut defines_table = false;
+            let mut allows_any = false;
+            for node in &nodes {
+                if let Some(props) = node.get("properties").and_then(|p| p.as_object()) {
+                    properties.extend(props.iter().map(|(k, v)| (k.clone(), v.clone())));
+                    defines_table = true;
+                }
+                match node.get("additionalProperties") {
+                    Some(schema @ serde_json::Value::Object(_)) => {
+                        additional.push(schema);
+                        defines_table = true;
+                    }
+                    Some(serde_json::Value::Bool(true)) => allows_any = true,
+                    _ => {}
+                }
+            }
+            // Nodes without a table shape (e.g. free-form objects) accept any key.
+            let open = !defines_table || allows_any;
+            for (key, child) in map {
+                let key = key_string(key);
+                let child_path = join(&path, &key);
+                let schema = properties.get(&key).or_else(|| additional.first().copied());
+                match schema {
+                    Some(schema) => {
+                        let is_deprecated = expand_schema(schema, root, 0).iter().any(|n| {
+                            n.get("deprecated")
+                                .and_then(|d| d.as_bool())
+                                .unwrap_or(false)
+                        });
+                        if is_deprecated {
+                            deprecated.push(child_path.clone());
+                        }
+                        check_schema(child, schema, root, child_path, unknown, deprecated);
+                    }
+                    None if open => {}
+                    None => {
+                        let suggestion =
+                            crate::error::suggest_key(&key, properties.keys().map(String::as_str));
+                        unknown.push((child_path, suggestion));
+                    }
+                }
+            }
+        }
+        Value::Sequence(items) => {
+            let item_schema = nodes.iter().find_map(|n| n.get("items"));
+            if let Some(item_schema) = item_schema {
+                for (i, item) in items.iter().enumerate() {
+                    check_schema(
+                        item,
+                        item_schema,
+                        root,
+                        join(&path, &i.to_string()),
+                        unknown,
+                        deprecated,
+                    );
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Resolve `$ref` and flatten `allOf` / `anyOf` / `oneOf` into the list of
+/// schema objects that apply to a value.
+fn expand_schema<'a>(
+    node: &'a serde_json::Value,
+    root: &'a serde_json::Value,
+    depth: usize,
+) -> Vec<&'a serde_json::Value> {
+    // Recursive schemas reference themselves; stop well before the stack does.
+    if depth > 32 || !node.is_object() {
+        return Vec::new();
+    }
+    let mut nodes = vec![node];
+    if let Some(target) = node
+        .get("$ref")
+        .and_then(|r| r.as_str())
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|pointer| root.pointer(pointer))
+    {
+        nodes.extend(expand_schema(target, root, depth + 1));
+    }
+    for combinator in ["allOf", "anyOf", "oneOf"] {
+        if let Some(variants) = node.get(combinator).and_then(|v| v.as_array()) {
+            for variant in variants {
+                nodes.extend(expand_schema(variant, root, depth + 1));
+            }
+        }
+    }
+    nodes
+}
+
+fn lookup<'a>(document: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(document, |current, segment| {
+        current.as_mapping()?.get(segment)
+    })
+}
+
+fn remove(document: &mut Value, key: &str) -> Option<Value> {
+    let (parent, last) = match key.rsplit_once('.') {
+        Some((parent, last)) => (
+            parent
+                .split('.')
+                .try_fold(&mut *document, |current, segment| {
+                    current.as_mapping_mut()?.get_mut(segment)
+                })?,
+            last,
+        ),
+        None => (document, key),
+    };
+    parent.as_mapping_mut()?.shift_remove(last)
+}
+
+fn insert(document: &mut Value, key: &str, value: Value) -> bool {
+    let mut current = document;
+    let segments: Vec<&str> = key.split('.').collect();
+    let (last, parents) = segments.split_last().expect("split yields a segment");
+    for segment in parents {
+        let Some(map) = current.as_mapping_mut() else {
+            return false;
+        };
+        current = map
+            .entry(Value::String(segment.to_string()))
+            .or_insert_with(|| Value::Mapping(Mapping::new()));
+    }
+    match current.as_mapping_mut() {
+        Some(map) => {
+            map.insert(Value::String(last.to_string()), value);
+            true
+        }
+        None => false,
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn key_string(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Number(n) => n.to_string(),
+        other => serde_yaml_ng::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+/// Deserializes any self-describing format into a YAML value tree, keeping
+/// the last value for duplicate keys and recording their paths.
+///
+/// `serde_yaml_ng::Value` itself fails on the first duplicate, which would
+/// hide every other finding in the file.
+struct Tree<'a> {
+    path: String,
+    duplicates: &'a RefCell<Vec<String>>,
+}
+
+impl<'a> Tree<'a> {
+    fn root(duplicates: &'a RefCell<Vec<String>>) -> Self {
+        Self {
+            path: String::new(),
+            duplicates,
+        }
+    }
+
+    fn child(&self, key: &str) -> Self {
+        Self {
+            path: join(&self.path, key),
+            duplicates: self.duplicates,
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Tree<'_> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Tree<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a configuration value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Value, E> {
+        Ok(Value::Bool(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Value, E> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Value, E> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Value, E> {
+        Ok(Value::Number(v.into()))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Value, E> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Value, E> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut items = Vec::new();
+        while let Some(item) = seq.next_element_seed(self.child(&items.len().to_string()))? {
+            items.push(item);
+        }
+        Ok(Value::Sequence(items))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut mapping = Mapping::new();
+        while let Some(key) = map.next_key::<Value>()? {
+            let name = key_string(&key);
+            let value = map.next_value_seed(self.child(&name))?;
+            if mapping.insert(key, value).is_some() {
+                self.duplicates.borrow_mut().push(join(&self.path, &name));
+            }
+        }
+        Ok(Value::Mapping(mapping))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &tempfile::TempDir, name: &str, content: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn rules(issues: &[Issue]) -> Vec<(&'static str, String)> {
+        issues.iter().map(|i| (i.rule, i.key.clone())).collect()
+    }
+
+    #[test]
+    fn test_duplicate_keys_are_reported_per_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = LintOptions::default();
+
+        let json = write(&dir, "app.json", r#"{"server": {"port": 1, "port": 2}}"#);
+        let (issues, document) = lint_file(&json, &options).unwrap();
+        assert_eq!(rules(&issues), [(DUPLICATE_KEY, "server.port".to_string())]);
+        assert_eq!(
+            lookup(&document.unwrap(), "server.port"),
+            Some(&Value::from(2))
+        );
+
+        let yaml = write(&dir, "app.yaml", "a: 1\nb: 2\na: 3\n");
+        let (issues, _) = lint_file(&yaml, &options).unwrap();
+        assert_eq!(rules(&issues), [(DUPLICATE_KEY, "a".to_string())]);
+
+        let toml = write(&dir, "app.toml", "a = 1\na = 2\n");
+        let (issues, document) = lint_file(&toml, &options).unwrap();
+        assert_eq!(issues[0].rule, DUPLICATE_KEY);
+        assert!(document.is_none());
+    }
+
+    #[test]
+    fn test_plaintext_secrets_skip_encrypted_and_interpolated_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(
+            &dir,
+            "app.toml",
+            "[db]\npassword = \"hunter2\"\ntoken = \"enc:XCHACHA20:abc\"\napi_key = \"${API_KEY}\"\nhost = \"db\"\n",
+        );
+        let (issues, _) = lint_file(&path, &LintOptions::default()).unwrap();
+        assert_eq!(
+            rules(&issues),
+            [(PLAINTEXT_SECRET, "db.password".to_string())]
+        );
+        assert_eq!(issues[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_localhost_only_in_production() {
+        let dir = tempfile::tempdir().unwrap();
+        let content = "[api]\nurl = \"http://localhost:8080/v1\"\nname = \"localhost-tools\"\n";
+        let dev = write(&dir, "app.toml", content);
+        let prod = write(&dir, "app.prod.toml", content);
+
+        let (issues, _) = lint_file(&dev, &LintOptions::default()).unwrap();
+        assert!(issues.is_empty());
+        let (issues, _) = lint_file(&prod, &LintOptions::default()).unwrap();
+        assert_eq!(rules(&issues), [(LOCALHOST_IN_PROD, "api.url".to_string())]);
+
+        let options = LintOptions {
+            profile: Some("production".to_string()),
+            ..Default::default()
+        };
+        let (issues, _) = lint_file(&dev, &options).unwrap();
+        assert_eq!(issues.len(), 1);
+    }
+
+    #[test]
+    fn test_schema_unknown_and_deprecated_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": {
+                "server": { "$ref": "#/$defs/Server" },
+                "timeout": { "type": "integer", "deprecated": true },
+                "labels": { "type": "object", "additionalProperties": { "type": "string" } }
+            },
+            "$defs": {
+                "Server": {
+                    "type": "object",
+                    "properties": { "port": { "type": "integer" } }
+                }
+            }
+        });
+        let path = write(
+            &dir,
+            "app.yaml",
+            "server:\n  port: 1\n  prot: 2\ntimeout: 3\nlabels:\n  team: core\nextra: true\n",
+        );
+        let options = LintOptions {
+            schema: Some(schema),
+            ..Default::default()
+        };
+        let (issues, _) = lint_file(&path, &options).unwrap();
+        assert_eq!(
+            rules(&issues),
+            [
+                (UNKNOWN_KEY, "server.prot".to_string()),
+                (UNKNOWN_KEY, "extra".to_string()),
+                (DEPRECATED_KEY, "timeout".to_string()),
+            ]
+        );
+        assert_eq!(
+            issues[0].message,
+            "not defined by the schema (did you mean `port`?)"
+        );
+    }
+
+    #[test]
+    fn test_deprecated_rename_fix_and_severity_override() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write(&dir, "app.toml", "[server]\nlisten_port = 8080\n");
+        let mut options = LintOptions::default();
+        options.add_deprecated("server.listen_port=server.port");
+        options.set_rule("deprecated-key=error").unwrap();
+        assert!(options.set_rule("no-such-rule=off").is_err());
+        assert!(options.set_rule("deprecated-key=loud").is_err());
+
+        let (issues, document) = lint_file(&path, &options).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(
+            issues[0].fix,
+            Some(Fix::Rename {
+                to: "server.port".to_string()
+            })
+        );
+
+        let mut document = document.unwrap();
+        assert_eq!(apply_fixes(&mut document, &issues), 1);
+        assert!(lookup(&document, "server.listen_port").is_none());
+        assert_eq!(lookup(&document, "server.port"), Some(&Value::from(8080)));
+
+        options.set_rule("deprecated-key=off").unwrap();
+        let (issues, _) = lint_file(&path, &options).unwrap();
+        assert!(issues.is_empty());
+    }
+}
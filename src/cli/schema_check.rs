@@ -0,0 +1,451 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! JSON Schema checks for `confers validate --schema`.
+//!
+//! Covers the keywords `#[derive(ConfigSchema)]` emits (`type`, `required`,
+//! `properties`, `items`, `enum`, `minimum`/`maximum`, `minLength`/
+//! `maxLength`, `minItems`/`maxItems`, `pattern` and the `email`/`uri`
+//! formats) plus `$ref`, `allOf`, `anyOf` and `oneOf`. Keys the schema does
+//! not define are only reported under `additionalProperties: false`, with
+//! the closest defined key as a suggestion; otherwise they are left to
+//! `confers lint`.
+
+use serde_json::Value;
+
+use crate::error::{suggest_key, ValidationIssue, ValidationReport};
+
+/// Constraint reported for keys a closed schema does not define.
+pub(crate) const UNKNOWN_KEY: &str = "not defined by the schema";
+
+/// Nesting followed through `$ref`s before giving up on a node.
+const MAX_DEPTH: usize = 32;
+
+/// Check `value` against `schema`, returning one issue per violation.
+pub(crate) fn validate(value: &Value, schema: &Value) -> ValidationReport {
+    let mut issues = Vec::new();
+    check(value, schema, schema, "", &mut issues, 0);
+    let mut report = ValidationReport::new();
+    for issue in issues {
+        report.push(issue);
+    }
+    report
+}
+
+/// Check `value` against `node`, a subschema of `root`, reporting issues at
+/// `path`.
+pub(crate) fn validate_node(
+    value: &Value,
+    node: &Value,
+    root: &Value,
+    path: &str,
+) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    check(value, node, root, path, &mut issues, 0);
+    issues
+}
+
+fn display_path(path: &str) -> &str {
+    if path.is_empty() {
+        "<root>"
+    } else {
+        path
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn check(
+    value: &Value,
+    node: &Value,
+    root: &Value,
+    path: &str,
+    issues: &mut Vec<ValidationIssue>,
+    depth: usize,
+) {
+    if depth > MAX_DEPTH || !node.is_object() {
+        return;
+    }
+    let at = display_path(path);
+
+    if let Some(target) = node
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix('#'))
+        .and_then(|pointer| root.pointer(pointer))
+    {
+        check(value, target, root, path, issues, depth + 1);
+    }
+    if let Some(branches) = node.get("allOf").and_then(Value::as_array) {
+        for branch in branches {
+            check(value, branch, root, path, issues, depth + 1);
+        }
+    }
+    for keyword in ["anyOf", "oneOf"] {
+        let Some(branches) = node.get(keyword).and_then(Value::as_array) else {
+            continue;
+        };
+        let results: Vec<Vec<ValidationIssue>> = branches
+            .iter()
+            .map(|branch| {
+                let mut branch_issues = Vec::new();
+                check(value, branch, root, path, &mut branch_issues, depth + 1);
+                branch_issues
+            })
+            .collect();
+        let matching = results.iter().filter(|r| r.is_empty()).count();
+        if matching == 0 {
+            // Report the branch that came closest
+            if let Some(closest) = results.into_iter().min_by_key(Vec::len) {
+                issues.extend(closest);
+            }
+        } else if keyword == "oneOf" && matching > 1 {
+            issues.push(
+                ValidationIssue::new(at, "matches more than one oneOf schema").with_rule("oneOf"),
+            );
+        }
+    }
+
+    if let Some(expected) = node.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(ts) => ts.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|t| has_type(value, t)) {
+            issues.push(
+                ValidationIssue::new(
+                    at,
+                    format!(
+                        "expected {}, found {}",
+                        types.join(" or "),
+                        type_name(value)
+                    ),
+                )
+                .with_rule("type"),
+            );
+            return;
+        }
+    }
+
+    if let Some(allowed) = node.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            issues.push(
+                ValidationIssue::new(
+                    at,
+                    format!("{} is not one of {}", value, allowed.join(", ")),
+                )
+                .with_rule("enum"),
+            );
+        }
+    }
+
+    match value {
+        Value::Number(number) => {
+            if let Some(n) = number.as_f64() {
+                check_number(n, node, at, issues);
+            }
+        }
+        Value::String(s) => check_string(s, node, at, issues),
+        Value::Array(items) => {
+            let len = items.len() as u64;
+            if let Some(min) = node.get("minItems").and_then(Value::as_u64) {
+                if len < min {
+                    issues.push(
+                        ValidationIssue::new(at, format!("has {} items, fewer than {}", len, min))
+                            .with_rule("minItems"),
+                    );
+                }
+            }
+            if let Some(max) = node.get("maxItems").and_then(Value::as_u64) {
+                if len > max {
+                    issues.push(
+                        ValidationIssue::new(at, format!("has {} items, more than {}", len, max))
+                            .with_rule("maxItems"),
+                    );
+                }
+            }
+            if let Some(item_schema) = node.get("items") {
+                for (i, item) in items.iter().enumerate() {
+                    let item_path = join(path, &i.to_string());
+                    check(item, item_schema, root, &item_path, issues, depth + 1);
+                }
+            }
+        }
+        Value::Object(map) => {
+            if let Some(required) = node.get("required").and_then(Value::as_array) {
+                for key in required.iter().filter_map(Value::as_str) {
+                    if !map.contains_key(key) {
+                        issues.push(
+                            ValidationIssue::new(join(path, key), "missing required key")
+                                .with_rule("required"),
+                        );
+                    }
+                }
+            }
+            let closed = node.get("additionalProperties") == Some(&Value::Bool(false));
+            if let Some(properties) = node.get("properties").and_then(Value::as_object) {
+                for (key, child) in map {
+                    let child_path = join(path, key);
+                    match properties.get(key) {
+                        Some(child_schema) => {
+                            check(child, child_schema, root, &child_path, issues, depth + 1);
+                        }
+                        None if closed => issues.push(
+                            ValidationIssue::new(child_path, UNKNOWN_KEY)
+                                .with_rule("additionalProperties")
+                                .with_suggestion(suggest_key(
+                                    key,
+                                    properties.keys().map(String::as_str),
+                                )),
+                        ),
+                        None => {}
+                    }
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64() || value.is_u64() || value.as_f64().is_some_and(|n| n.fract() == 0.0)
+        }
+        "array" => value.is_array(),
+        "object" => value.is_object(),
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn check_number(n: f64, node: &Value, at: &str, issues: &mut Vec<ValidationIssue>) {
+    let bound = |keyword| node.get(keyword).and_then(Value::as_f64);
+    if let Some(min) = bound("minimum").filter(|min| n < *min) {
+        issues.push(
+            ValidationIssue::new(at, format!("{} is less than the minimum {}", n, min))
+                .with_rule("minimum"),
+        );
+    }
+    if let Some(max) = bound("maximum").filter(|max| n > *max) {
+        issues.push(
+            ValidationIssue::new(at, format!("{} is greater than the maximum {}", n, max))
+                .with_rule("maximum"),
+        );
+    }
+    if let Some(min) = bound("exclusiveMinimum").filter(|min| n <= *min) {
+        issues.push(
+            ValidationIssue::new(at, format!("{} must be greater than {}", n, min))
+                .with_rule("exclusiveMinimum"),
+        );
+    }
+    if let Some(max) = bound("exclusiveMaximum").filter(|max| n >= *max) {
+        issues.push(
+            ValidationIssue::new(at, format!("{} must be less than {}", n, max))
+                .with_rule("exclusiveMaximum"),
+        );
+    }
+}
+
+fn check_string(s: &str, node: &Value, at: &str, issues: &mut Vec<ValidationIssue>) {
+    let len = s.chars().count() as u64;
+    if let Some(min) = node.get("minLength").and_then(Value::as_u64) {
+        if len < min {
+            issues.push(
+                ValidationIssue::new(
+                    at,
+                    format!("length {} is shorter than the minimum {}", len, min),
+                )
+                .with_rule("minLength"),
+            );
+        }
+    }
+    if let Some(max) = node.get("maxLength").and_then(Value::as_u64) {
+        if len > max {
+            issues.push(
+                ValidationIssue::new(
+                    at,
+                    format!("length {} is longer than the maximum {}", len, max),
+                )
+                .with_rule("maxLength"),
+            );
+        }
+    }
+    if let Some(pattern) = node.get("pattern").and_then(Value::as_str) {
+        match regex::Regex::new(pattern) {
+            Ok(re) if !re.is_match(s) => {
+                issues.push(
+                    ValidationIssue::new(at, format!("does not match pattern {}", pattern))
+                        .with_rule("pattern"),
+                );
+            }
+            Ok(_) => {}
+            Err(e) => issues.push(
+                ValidationIssue::new(at, format!("invalid schema pattern {} ({})", pattern, e))
+                    .with_rule("pattern"),
+            ),
+        }
+    }
+    match node.get("format").and_then(Value::as_str) {
+        Some("email") if !is_email(s) => {
+            issues.push(ValidationIssue::new(at, "not a valid email address").with_rule("format"));
+        }
+        Some("uri" | "url") if url::Url::parse(s).is_err() => {
+            issues.push(ValidationIssue::new(at, "not a valid URL").with_rule("format"));
+        }
+        _ => {}
+    }
+}
+
+/// Shape check only: `local@domain.tld` without whitespace.
+fn is_email(s: &str) -> bool {
+    let Some((local, domain)) = s.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !s.chars().any(char::is_whitespace)
+        && !domain.contains('@')
+        && domain.split('.').filter(|label| !label.is_empty()).count() >= 2
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["server"],
+            "properties": {
+                "server": { "$ref": "#/$defs/Server" },
+                "admin": { "type": "string", "format": "email" },
+                "homepage": { "type": ["string", "null"], "format": "uri" },
+                "region": { "type": "string", "pattern": "^[a-z]+$" },
+                "upstreams": { "type": "array", "minItems": 1, "items": { "type": "string", "minLength": 1 } }
+            },
+            "$defs": {
+                "Server": {
+                    "type": "object",
+                    "required": ["port"],
+                    "properties": {
+                        "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                        "host": { "type": "string", "minLength": 1, "maxLength": 253 }
+                    }
+                }
+            }
+        })
+    }
+
+    fn messages(report: ValidationReport) -> Vec<String> {
+        report.issues().iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_valid_document() {
+        let value = json!({
+            "server": { "port": 8080, "host": "localhost" },
+            "admin": "ops@example.com",
+            "homepage": null,
+            "region": "eu",
+            "upstreams": ["a"],
+            "unknown": true
+        });
+        assert!(validate(&value, &schema()).is_empty());
+    }
+
+    #[test]
+    fn test_constraint_violations() {
+        let value = json!({
+            "server": { "port": 70000, "host": "" },
+            "admin": "not-an-email",
+            "homepage": "no scheme",
+            "region": "EU",
+            "upstreams": [""]
+        });
+        let issues = messages(validate(&value, &schema()));
+        assert_eq!(
+            issues,
+            vec![
+                "admin: not a valid email address",
+                "homepage: not a valid URL",
+                "region: does not match pattern ^[a-z]+$",
+                "server.host: length 0 is shorter than the minimum 1",
+                "server.port: 70000 is greater than the maximum 65535",
+                "upstreams.0: length 0 is shorter than the minimum 1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_required_type_and_any_of() {
+        let issues = messages(validate(&json!({ "upstreams": "a" }), &schema()));
+        assert_eq!(
+            issues,
+            vec![
+                "server: missing required key",
+                "upstreams: expected array, found string",
+            ]
+        );
+
+        let schema = json!({ "anyOf": [{ "type": "integer" }, { "type": "null" }] });
+        assert!(validate(&json!(null), &schema).is_empty());
+        assert_eq!(
+            messages(validate(&json!("x"), &schema)),
+            vec!["<root>: expected integer, found string"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_with_closed_schema() {
+        let schema = json!({
+            "type": "object",
+            "additionalProperties": false,
+            "properties": { "port": { "type": "integer" }, "host": { "type": "string" } }
+        });
+        let report = validate(&json!({ "prot": 80, "colour": "red" }), &schema);
+        assert_eq!(
+            messages(report.clone()),
+            vec![
+                "colour: not defined by the schema",
+                "prot: not defined by the schema (did you mean `port`?)",
+            ]
+        );
+        assert_eq!(report.issues()[1].suggestion.as_deref(), Some("port"));
+    }
+
+    #[test]
+    fn test_is_email() {
+        assert!(is_email("a@b.co"));
+        assert!(!is_email("a@b"));
+        assert!(!is_email("@b.co"));
+        assert!(!is_email("a b@c.co"));
+        assert!(!is_email("a@@b.co"));
+    }
+}
@@ -0,0 +1,153 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! `confers watch` - change events for every reload of the `-c` files or a
+//! remote URL.
+//!
+//! Each reload that changes the configuration prints one event: the time, what
+//! triggered it and the key-level changes, as text lines or one JSON object
+//! per line. Values of sensitive keys are redacted unless `--raw` is given.
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::{json, Value};
+
+use crate::diff::KeyChange;
+
+/// What one reload changed.
+#[derive(Debug, Clone)]
+pub(crate) struct WatchEvent {
+    pub timestamp: DateTime<Utc>,
+    /// File path or URL that triggered the reload.
+    pub trigger: String,
+    pub changes: Vec<KeyChange>,
+}
+
+impl WatchEvent {
+    /// The event for a reload from `old` to `new`, or `None` if nothing
+    /// changed. With `sanitize`, sensitive values are redacted after diffing,
+    /// so a changed secret still shows up as changed.
+    pub fn between(old: &Value, new: &Value, trigger: &str, sanitize: bool) -> Option<Self> {
+        let diff = crate::diff::diff_values(old, new);
+        if diff.is_empty() {
+            return None;
+        }
+        let changes = diff
+            .changes()
+            .iter()
+            .cloned()
+            .map(|mut change| {
+                if sanitize {
+                    let key = change.key();
+                    match &mut change {
+                        KeyChange::Added { value, .. } | KeyChange::Removed { value, .. } => {
+                            crate::error::redact_sensitive(value, &key);
+                        }
+                        KeyChange::Changed { old, new, .. } => {
+                            crate::error::redact_sensitive(old, &key);
+                            crate::error::redact_sensitive(new, &key);
+                        }
+                    }
+                }
+                change
+            })
+            .collect();
+        Some(Self {
+            timestamp: Utc::now(),
+            trigger: trigger.to_string(),
+            changes,
+        })
+    }
+
+    /// Render as text lines or as a single JSON line.
+    pub fn render(&self, format: &str) -> Result<String> {
+        let timestamp = self.timestamp.format("%Y-%m-%dT%H:%M:%SZ");
+        match format {
+            "json" => {
+                let changes: Vec<Value> = self.changes.iter().map(change_json).collect();
+                let event = json!({
+                    "timestamp": timestamp.to_string(),
+                    "trigger": self.trigger,
+                    "changes": changes,
+                });
+                Ok(format!("{}\n", event))
+            }
+            "text" => {
+                let mut out = format!(
+                    "[{}] {}: {} key(s) changed\n",
+                    timestamp,
+                    self.trigger,
+                    self.changes.len()
+                );
+                for change in &self.changes {
+                    out.push_str(&format!("  {}\n", change));
+                }
+                Ok(out)
+            }
+            _ => anyhow::bail!("Unsupported format: {}", format),
+        }
+    }
+}
+
+fn change_json(change: &KeyChange) -> Value {
+    match change {
+        KeyChange::Added { value, .. } => {
+            json!({ "key": change.key(), "kind": "added", "new": value })
+        }
+        KeyChange::Removed { value, .. } => {
+            json!({ "key": change.key(), "kind": "removed", "old": value })
+        }
+        KeyChange::Changed { old, new, .. } => {
+            json!({ "key": change.key(), "kind": "changed", "old": old, "new": new })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unchanged_reload_has_no_event() {
+        let config = json!({ "server": { "port": 8080 } });
+        assert!(WatchEvent::between(&config, &config, "app.toml", true).is_none());
+    }
+
+    #[test]
+    fn test_event_lists_changes() {
+        let old = json!({ "server": { "port": 8080, "host": "a" } });
+        let new = json!({ "server": { "port": 9090, "tls": true } });
+        let event = WatchEvent::between(&old, &new, "app.toml", true).unwrap();
+
+        let text = event.render("text").unwrap();
+        assert!(text.contains("app.toml: 3 key(s) changed"), "{text}");
+        assert!(text.contains("  - server.host = \"a\"\n"), "{text}");
+        assert!(text.contains("  ~ server.port: 8080 → 9090\n"), "{text}");
+        assert!(text.contains("  + server.tls = true\n"), "{text}");
+
+        let json: Value = serde_json::from_str(&event.render("json").unwrap()).unwrap();
+        assert_eq!(json["trigger"], "app.toml");
+        assert_eq!(json["changes"][1]["kind"], "changed");
+        assert_eq!(json["changes"][1]["old"], 8080);
+        assert_eq!(json["changes"][1]["new"], 9090);
+        assert!(json["changes"][2].get("old").is_none());
+
+        assert!(event.render("yaml").is_err());
+    }
+
+    #[test]
+    fn test_secrets_are_redacted_unless_raw() {
+        let old = json!({ "db": { "password": "hunter2" } });
+        let new = json!({ "db": { "password": "hunter3" } });
+
+        let event = WatchEvent::between(&old, &new, "app.toml", true).unwrap();
+        let text = event.render("text").unwrap();
+        assert!(text.contains("~ db.password"), "{text}");
+        assert!(!text.contains("hunter"), "{text}");
+
+        let event = WatchEvent::between(&old, &new, "app.toml", false).unwrap();
+        assert!(event.render("text").unwrap().contains("hunter3"));
+    }
+}
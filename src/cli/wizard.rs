@@ -0,0 +1,824 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Interactive configuration wizard driven by a JSON Schema, for
+//! `confers wizard`.
+//!
+//! The wizard asks one question per property, in key order, descending
+//! into nested objects. Answers are parsed for the property's type, `enum`
+//! properties are offered as a numbered list, an empty answer takes the
+//! default (or leaves an optional key out), and every answer is checked
+//! against the property's constraints before moving on.
+//!
+//! With `--edit` the current values of an existing file are offered
+//! instead of the defaults, and only the keys whose value changed are
+//! written back. TOML files are edited in place, keeping comments and
+//! layout; YAML and JSON files are rewritten.
+
+use std::io::{BufRead, IsTerminal, Write};
+
+use anyhow::{bail, Context, Result};
+use serde_json::{Map, Value};
+
+use super::schema_check::validate_node;
+use crate::error::is_sensitive_field_name;
+use crate::impl_::template::{is_nullable, resolve};
+use crate::loader::Format;
+
+/// Object nesting followed before a property is asked for as a JSON value;
+/// guards against recursive `$ref`s.
+const MAX_DEPTH: usize = 32;
+
+/// Builds a configuration document by asking for each property of a JSON
+/// Schema.
+///
+/// Properties marked `writeOnly` (sensitive fields of
+/// `#[derive(ConfigSchema)]`), with `format: password`, or with a secret
+/// name such as `password` or `api_key` are read without echo when stdin is
+/// a terminal, and their defaults are not shown.
+///
+/// # Examples
+///
+/// ```rust
+/// use confers::cli::ConfigWizard;
+/// use serde_json::json;
+///
+/// let wizard = ConfigWizard::new(json!({
+///     "type": "object",
+///     "required": ["name"],
+///     "properties": {
+///         "name": { "type": "string" },
+///         "port": { "type": "integer", "default": 8080, "maximum": 65535 },
+///         "log": { "enum": ["info", "debug"] }
+///     }
+/// }));
+/// // Questions come in key order: log, name, port
+/// let config = wizard.run_with_values(["2", "api", ""]).unwrap();
+/// assert_eq!(config, json!({ "name": "api", "port": 8080, "log": "debug" }));
+/// ```
+pub struct ConfigWizard {
+    schema: Value,
+    current: Option<Value>,
+}
+
+impl ConfigWizard {
+    /// Create a wizard for `schema`.
+    pub fn new(schema: Value) -> Self {
+        Self {
+            schema,
+            current: None,
+        }
+    }
+
+    /// Offer the values of `current`, an existing document, in place of the
+    /// defaults. Questions about keys that differ from their default show
+    /// the default next to the current value.
+    pub fn with_current(mut self, current: Value) -> Self {
+        self.current = Some(current);
+        self
+    }
+
+    /// Create a wizard for the schema of `#[derive(ConfigSchema)]` type `T`.
+    pub fn for_type<T: crate::ConfigSchema>() -> Self {
+        Self::new(T::json_schema())
+    }
+
+    /// Ask on the terminal: questions go to stderr, answers come from
+    /// stdin. An invalid answer is explained and asked again.
+    pub fn run(&self) -> Result<Value> {
+        let stdin = std::io::stdin();
+        let terminal = stdin.is_terminal();
+        let mut prompter = Prompter {
+            input: stdin.lock(),
+            output: std::io::stderr(),
+            terminal,
+            retry: true,
+        };
+        self.ask_object(
+            &mut prompter,
+            &self.schema,
+            "",
+            self.current.as_ref(),
+            None,
+            0,
+        )
+        .map(Value::Object)
+    }
+
+    /// Ask on `output`, reading one answer per line from `input`. An invalid
+    /// answer is explained and asked again; input that ends before every
+    /// required value is given is an error.
+    pub fn run_with<R: BufRead, W: Write>(&self, input: R, output: W) -> Result<Value> {
+        let mut prompter = Prompter {
+            input,
+            output,
+            terminal: false,
+            retry: true,
+        };
+        self.ask_object(
+            &mut prompter,
+            &self.schema,
+            "",
+            self.current.as_ref(),
+            None,
+            0,
+        )
+        .map(Value::Object)
+    }
+
+    /// Answer the questions from `answers`, in order, for scripted setups.
+    ///
+    /// An empty answer, or running out of answers, takes the default or
+    /// leaves an optional key out. An invalid answer is an error.
+    pub fn run_with_values<I, S>(&self, answers: I) -> Result<Value>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let input: String = answers
+            .into_iter()
+            .map(|answer| format!("{}\n", answer.as_ref()))
+            .collect();
+        let mut prompter = Prompter {
+            input: input.as_bytes(),
+            output: std::io::sink(),
+            terminal: false,
+            retry: false,
+        };
+        self.ask_object(
+            &mut prompter,
+            &self.schema,
+            "",
+            self.current.as_ref(),
+            None,
+            0,
+        )
+        .map(Value::Object)
+    }
+
+    fn ask_object<R: BufRead, W: Write>(
+        &self,
+        prompter: &mut Prompter<R, W>,
+        schema: &Value,
+        path: &str,
+        current: Option<&Value>,
+        parent_default: Option<&Value>,
+        depth: usize,
+    ) -> Result<Map<String, Value>> {
+        let schema = resolve(schema, &self.schema);
+        let mut values = Map::new();
+        let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+            return Ok(values);
+        };
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+
+        for (key, property) in properties {
+            let key_path = join(path, key);
+            let resolved = resolve(property, &self.schema);
+            let default = parent_default
+                .and_then(|parent| parent.get(key))
+                .or_else(|| property.get("default"))
+                .or_else(|| resolved.get("default"));
+            let is_required = required.contains(&key.as_str());
+            let current = current.and_then(|current| current.get(key));
+
+            if resolved.get("properties").is_some() && depth < MAX_DEPTH {
+                let children =
+                    self.ask_object(prompter, resolved, &key_path, current, default, depth + 1)?;
+                if !children.is_empty() || is_required {
+                    values.insert(key.clone(), Value::Object(children));
+                }
+                continue;
+            }
+            let question = Question {
+                path: &key_path,
+                property,
+                schema: resolved,
+                current: current.filter(|value| !value.is_null()),
+                default: default.filter(|value| !value.is_null()),
+                required: is_required && !is_nullable(property) && !is_nullable(resolved),
+            };
+            if let Some(value) = self.ask(prompter, &question)? {
+                values.insert(key.clone(), value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Ask for one value until a valid one is given. `None` leaves the key
+    /// out.
+    fn ask<R: BufRead, W: Write>(
+        &self,
+        prompter: &mut Prompter<R, W>,
+        question: &Question<'_>,
+    ) -> Result<Option<Value>> {
+        let secret = question.is_secret();
+        let choices: Vec<&Value> = question
+            .schema
+            .get("enum")
+            .and_then(Value::as_array)
+            .map(|values| values.iter().filter(|v| !v.is_null()).collect())
+            .unwrap_or_default();
+
+        writeln!(prompter.output)?;
+        if let Some(description) = ["description", "title"].iter().find_map(|keyword| {
+            question
+                .property
+                .get(keyword)
+                .or_else(|| question.schema.get(keyword))
+                .and_then(Value::as_str)
+        }) {
+            writeln!(prompter.output, "{}", description)?;
+        }
+        for (i, choice) in choices.iter().enumerate() {
+            writeln!(prompter.output, "  {}) {}", i + 1, display(choice))?;
+        }
+
+        loop {
+            write!(prompter.output, "{}", question.path)?;
+            if choices.is_empty() {
+                if let Some(kind) = kind(question.schema) {
+                    write!(prompter.output, " ({})", kind)?;
+                }
+            }
+            let offered = question.current.or(question.default);
+            match offered {
+                Some(_) if secret => write!(prompter.output, " [********]")?,
+                Some(value) => write!(prompter.output, " [{}]", display(value))?,
+                None if !question.required => write!(prompter.output, " [skip]")?,
+                None => {}
+            }
+            match (question.current, question.default) {
+                (Some(current), Some(default)) if current != default => {
+                    if secret {
+                        write!(prompter.output, " (differs from default)")?;
+                    } else {
+                        write!(prompter.output, " (default: {})", display(default))?;
+                    }
+                }
+                _ => {}
+            }
+            write!(prompter.output, ": ")?;
+
+            let answer = prompter.read(secret)?;
+            let text = answer.as_deref().unwrap_or("");
+            // Offered values are checked too: a current value may no longer
+            // satisfy the schema
+            let result = if text.trim().is_empty() {
+                match offered {
+                    Some(value) => Ok(value.clone()),
+                    None if !question.required => return Ok(None),
+                    None => Err("a value is required".to_string()),
+                }
+            } else if choices.is_empty() {
+                parse(text, question.schema, &self.schema)
+            } else {
+                choose(text.trim(), &choices)
+            };
+
+            let message = match result {
+                Ok(value) => {
+                    let issues =
+                        validate_node(&value, question.property, &self.schema, question.path);
+                    if issues.is_empty() {
+                        return Ok(Some(value));
+                    }
+                    issues
+                        .iter()
+                        .map(|issue| issue.constraint.as_str())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                }
+                Err(message) => message,
+            };
+            if answer.is_none() || !prompter.retry {
+                bail!("{}: {}", question.path, message);
+            }
+            writeln!(prompter.output, "  {}", message)?;
+        }
+    }
+}
+
+/// A property being asked for.
+struct Question<'a> {
+    path: &'a str,
+    /// The property as written, for constraints next to a `$ref`.
+    property: &'a Value,
+    /// The property with `$ref`s followed.
+    schema: &'a Value,
+    /// The value in the document being edited.
+    current: Option<&'a Value>,
+    default: Option<&'a Value>,
+    required: bool,
+}
+
+impl Question<'_> {
+    fn is_secret(&self) -> bool {
+        [self.property, self.schema].iter().any(|schema| {
+            schema.get("writeOnly") == Some(&Value::Bool(true))
+                || schema.get("format").and_then(Value::as_str) == Some("password")
+        }) || is_sensitive_field_name(self.path)
+    }
+}
+
+struct Prompter<R, W> {
+    input: R,
+    output: W,
+    /// Whether `input` is the terminal on stdin, so echo can be turned off.
+    terminal: bool,
+    /// Ask again after an invalid answer instead of failing.
+    retry: bool,
+}
+
+impl<R: BufRead, W: Write> Prompter<R, W> {
+    /// Read one answer without its line ending. `None` at end of input.
+    fn read(&mut self, secret: bool) -> Result<Option<String>> {
+        self.output.flush()?;
+        let mut line = String::new();
+        let read = if secret && self.terminal {
+            let _echo = EchoOff::new();
+            let read = self.input.read_line(&mut line);
+            // The newline the user typed was not echoed either
+            writeln!(self.output)?;
+            read?
+        } else {
+            self.input.read_line(&mut line)?
+        };
+        if read == 0 {
+            return Ok(None);
+        }
+        Ok(Some(line.trim_end_matches(['\r', '\n']).to_string()))
+    }
+}
+
+/// Turns off terminal echo on stdin until dropped.
+#[cfg(unix)]
+struct EchoOff(Option<libc::termios>);
+
+#[cfg(unix)]
+impl EchoOff {
+    fn new() -> Self {
+        // SAFETY: termios is plain data filled in by tcgetattr, and the
+        // flags are only changed on the copy passed to tcsetattr.
+        unsafe {
+            let mut original: libc::termios = std::mem::zeroed();
+            if libc::tcgetattr(libc::STDIN_FILENO, &mut original) != 0 {
+                return Self(None);
+            }
+            let mut silent = original;
+            silent.c_lflag &= !libc::ECHO;
+            if libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &silent) != 0 {
+                return Self(None);
+            }
+            Self(Some(original))
+        }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for EchoOff {
+    fn drop(&mut self) {
+        if let Some(original) = &self.0 {
+            // SAFETY: restores the settings read by tcgetattr above.
+            unsafe {
+                libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, original);
+            }
+        }
+    }
+}
+
+/// Echo cannot be turned off portably; secrets are read as typed.
+#[cfg(not(unix))]
+struct EchoOff;
+
+#[cfg(not(unix))]
+impl EchoOff {
+    fn new() -> Self {
+        EchoOff
+    }
+}
+
+/// A key the wizard set to a new value.
+pub(crate) struct Change {
+    pub(crate) path: Vec<String>,
+    pub(crate) value: Value,
+}
+
+impl Change {
+    pub(crate) fn key(&self) -> String {
+        self.path.join(".")
+    }
+}
+
+/// The values of `after` that are missing from or different in `before`.
+pub(crate) fn changes(before: &Value, after: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    collect_changes(Some(before), after, &mut Vec::new(), &mut changes);
+    changes
+}
+
+fn collect_changes(
+    before: Option<&Value>,
+    after: &Value,
+    path: &mut Vec<String>,
+    changes: &mut Vec<Change>,
+) {
+    match after {
+        Value::Object(map) if !map.is_empty() && before.is_none_or(Value::is_object) => {
+            for (key, value) in map {
+                path.push(key.clone());
+                collect_changes(before.and_then(|b| b.get(key)), value, path, changes);
+                path.pop();
+            }
+        }
+        _ if before == Some(after) => {}
+        _ => changes.push(Change {
+            path: path.clone(),
+            value: after.clone(),
+        }),
+    }
+}
+
+/// Write `changes` into `text`, a document in `format` that parsed to
+/// `before`. Keys that did not change are left as they were.
+pub(crate) fn apply_changes(
+    text: &str,
+    format: Format,
+    before: &Value,
+    changes: &[Change],
+) -> Result<String> {
+    if format == Format::Toml {
+        return edit_toml(text, changes);
+    }
+    let mut document = before.clone();
+    for change in changes {
+        let mut node = &mut document;
+        for segment in &change.path {
+            if !node.is_object() {
+                *node = Value::Object(Map::new());
+            }
+            node = &mut node[segment.as_str()];
+        }
+        *node = change.value.clone();
+    }
+    Ok(crate::loader::serialize_content(&document, format)?)
+}
+
+/// Edit `text` in place, so comments and layout survive.
+fn edit_toml(text: &str, changes: &[Change]) -> Result<String> {
+    let mut document: toml_edit::DocumentMut = text.parse()?;
+    for change in changes {
+        let key = change.key();
+        let (name, parents) = change.path.split_last().context("empty key")?;
+        let mut table: &mut dyn toml_edit::TableLike = document.as_table_mut();
+        for parent in parents {
+            table = table
+                .entry(parent)
+                .or_insert(toml_edit::table())
+                .as_table_like_mut()
+                .with_context(|| format!("'{}' is not a table", key))?;
+        }
+        let value = to_toml(&change.value).with_context(|| format!("Cannot write '{}'", key))?;
+        match table.get_mut(name) {
+            // Keep the spacing and trailing comment of the old value
+            Some(toml_edit::Item::Value(old)) => {
+                let decor = old.decor().clone();
+                *old = value;
+                *old.decor_mut() = decor;
+            }
+            _ => {
+                table.insert(name, toml_edit::Item::Value(value));
+            }
+        }
+    }
+    Ok(document.to_string())
+}
+
+fn to_toml(value: &Value) -> Result<toml_edit::Value> {
+    Ok(match value {
+        Value::Null => bail!("TOML has no null"),
+        Value::Bool(b) => (*b).into(),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => i.into(),
+            None => n.as_f64().context("number out of range")?.into(),
+        },
+        Value::String(s) => s.as_str().into(),
+        Value::Array(items) => {
+            let mut array = toml_edit::Array::new();
+            for item in items {
+                array.push(to_toml(item)?);
+            }
+            array.into()
+        }
+        Value::Object(map) => {
+            let mut table = toml_edit::InlineTable::new();
+            for (key, item) in map {
+                table.insert(key, to_toml(item)?);
+            }
+            table.into()
+        }
+    })
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+/// The non-null type of `schema`, if it names one.
+fn kind(schema: &Value) -> Option<&str> {
+    match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        Some(Value::String(t)) => Some(t.as_str()),
+        _ => None,
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Parse `text` as a value of `schema`'s type.
+fn parse(text: &str, schema: &Value, root: &Value) -> Result<Value, String> {
+    let trimmed = text.trim();
+    match kind(schema) {
+        Some("integer") => trimmed
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("'{}' is not an integer", trimmed)),
+        Some("number") => trimmed
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("'{}' is not a number", trimmed)),
+        Some("boolean") => match trimmed.to_lowercase().as_str() {
+            "y" | "yes" | "true" | "on" | "1" => Ok(Value::Bool(true)),
+            "n" | "no" | "false" | "off" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("'{}' is not yes or no", trimmed)),
+        },
+        Some("array") if trimmed.starts_with('[') => {
+            serde_json::from_str(trimmed).map_err(|e| format!("invalid JSON list: {}", e))
+        }
+        Some("array") => {
+            let items = schema.get("items").map(|items| resolve(items, root));
+            trimmed
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(|item| match items {
+                    Some(items) => parse(item, items, root),
+                    None => Ok(Value::String(item.to_string())),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array)
+        }
+        Some("object") => {
+            serde_json::from_str(trimmed).map_err(|e| format!("invalid JSON object: {}", e))
+        }
+        Some("string") => Ok(Value::String(text.to_string())),
+        _ => Ok(serde_json::from_str(trimmed).unwrap_or_else(|_| Value::String(text.to_string()))),
+    }
+}
+
+/// Pick one of `choices` by value or by its number in the list.
+fn choose(text: &str, choices: &[&Value]) -> Result<Value, String> {
+    if let Some(choice) = choices.iter().find(|choice| display(choice) == text) {
+        return Ok((*choice).clone());
+    }
+    match text.parse::<usize>() {
+        Ok(n) if (1..=choices.len()).contains(&n) => Ok(choices[n - 1].clone()),
+        _ => Err(format!(
+            "choose 1-{} or one of: {}",
+            choices.len(),
+            choices
+                .iter()
+                .map(|choice| display(choice))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "required": ["server"],
+            "properties": {
+                "server": { "$ref": "#/$defs/Server" },
+                "log": {
+                    "type": "object",
+                    "properties": {
+                        "level": { "enum": ["info", "debug", "trace"], "default": "info" },
+                        "json": { "type": "boolean", "default": false }
+                    }
+                },
+                "tags": { "type": "array", "items": { "type": "integer" } },
+                "db_password": { "type": ["string", "null"] }
+            },
+            "$defs": {
+                "Server": {
+                    "type": "object",
+                    "required": ["host", "port"],
+                    "properties": {
+                        "host": { "type": "string", "description": "Listen address" },
+                        "port": { "type": "integer", "minimum": 1, "maximum": 65535 },
+                        "ratio": { "type": "number", "default": 0.5 }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_answers_are_typed_and_nested() {
+        let config = ConfigWizard::new(schema())
+            .run_with_values(["hunter2", "yes", "debug", "localhost", "8080", "", "1, 2"])
+            .unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "server": { "host": "localhost", "port": 8080, "ratio": 0.5 },
+                "log": { "level": "debug", "json": true },
+                "tags": [1, 2],
+                "db_password": "hunter2"
+            })
+        );
+    }
+
+    #[test]
+    fn test_empty_answers_take_defaults_and_skip_optional_keys() {
+        let config = ConfigWizard::new(schema())
+            .run_with_values(["", "", "", "localhost", "443"])
+            .unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "server": { "host": "localhost", "port": 443, "ratio": 0.5 },
+                "log": { "level": "info", "json": false }
+            })
+        );
+    }
+
+    #[test]
+    fn test_enum_choices_by_number() {
+        let config = ConfigWizard::new(schema())
+            .run_with_values(["", "", "3", "h", "1"])
+            .unwrap();
+        assert_eq!(config["log"]["level"], "trace");
+    }
+
+    #[test]
+    fn test_invalid_answers_fail_scripted_runs() {
+        let wizard = ConfigWizard::new(schema());
+        let error = wizard
+            .run_with_values(["", "", "", "localhost", "70000"])
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("server.port:"), "{}", error);
+        assert!(error.contains("65535"), "{}", error);
+
+        let error = wizard
+            .run_with_values(["", "", "", "localhost", "eighty"])
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("not an integer"), "{}", error);
+
+        let error = wizard
+            .run_with_values(["", "", "", "localhost"])
+            .unwrap_err()
+            .to_string();
+        assert_eq!(error, "server.port: a value is required");
+    }
+
+    #[test]
+    fn test_invalid_answers_are_asked_again() {
+        let input = "\n\nverbose\n2\nlocalhost\n0\n8080\n\n\n";
+        let mut output = Vec::new();
+        let config = ConfigWizard::new(schema())
+            .run_with(input.as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(config["server"]["port"], 8080);
+        assert_eq!(config["log"]["level"], "debug");
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("Listen address\nserver.host (string): "));
+        assert_eq!(output.matches("server.port (integer): ").count(), 2);
+        assert!(output.contains("choose 1-3 or one of: info, debug, trace"));
+        assert!(output.contains("  2) debug\n"));
+        assert!(output.contains("log.level [info]: "));
+        assert!(output.contains("db_password (string) [skip]: "));
+    }
+
+    #[test]
+    fn test_secret_defaults_are_not_shown() {
+        let mut output = Vec::new();
+        ConfigWizard::new(json!({
+            "properties": {
+                "token": { "type": "string", "writeOnly": true, "default": "s3cret" }
+            }
+        }))
+        .run_with("\n".as_bytes(), &mut output)
+        .unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("token (string) [********]: "));
+        assert!(!output.contains("s3cret"));
+    }
+
+    #[test]
+    fn test_current_values_are_offered() {
+        let current = json!({
+            "server": { "host": "db.internal", "port": 5432, "ratio": 0.5 },
+            "log": { "level": "debug" },
+            "db_password": "old",
+            "extra": true
+        });
+        let mut output = Vec::new();
+        let config = ConfigWizard::new(schema())
+            .with_current(current)
+            .run_with("\n\n\n\n6432\n\n\n".as_bytes(), &mut output)
+            .unwrap();
+        assert_eq!(
+            config,
+            json!({
+                "server": { "host": "db.internal", "port": 6432, "ratio": 0.5 },
+                "log": { "level": "debug", "json": false },
+                "db_password": "old"
+            })
+        );
+
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("log.level [debug] (default: info): "));
+        assert!(output.contains("server.ratio (number) [0.5]: "));
+        assert!(output.contains("db_password (string) [********]: "));
+        assert!(!output.contains("old"));
+    }
+
+    #[test]
+    fn test_current_values_are_validated() {
+        let error = ConfigWizard::new(schema())
+            .with_current(json!({ "server": { "host": "h", "port": 0 } }))
+            .run_with_values(Vec::<&str>::new())
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("server.port:"), "{}", error);
+    }
+
+    #[test]
+    fn test_changes_are_the_new_and_different_values() {
+        let before = json!({ "server": { "port": 1, "host": "h" }, "name": "x" });
+        let after =
+            json!({ "server": { "port": 2, "host": "h", "tls": {} }, "name": "x", "tags": [1] });
+        let keys: Vec<String> = changes(&before, &after).iter().map(Change::key).collect();
+        assert_eq!(keys, ["server.port", "server.tls", "tags"]);
+    }
+
+    #[test]
+    fn test_toml_edits_keep_comments() {
+        let text = "# Service settings\nname = \"api\" # shown in logs\n\n[server]\n# Bind address\nhost = \"h\"\nport = 1 # was 80\n";
+        let before = json!({ "name": "api", "server": { "host": "h", "port": 1 } });
+        let after = json!({
+            "name": "api",
+            "server": { "host": "h", "port": 8080 },
+            "log": { "level": "debug", "targets": ["a", "b"] }
+        });
+        let edited = apply_changes(text, Format::Toml, &before, &changes(&before, &after)).unwrap();
+        assert_eq!(
+            edited,
+            "# Service settings\nname = \"api\" # shown in logs\n\n[server]\n# Bind address\nhost = \"h\"\nport = 8080 # was 80\n\n[log]\nlevel = \"debug\"\ntargets = [\"a\", \"b\"]\n"
+        );
+    }
+
+    #[test]
+    fn test_yaml_edits_keep_unchanged_keys() {
+        let before =
+            json!({ "name": "api", "server": { "port": 1 }, "__schema__": { "name": {} } });
+        let after = json!({ "name": "api", "server": { "port": 2 } });
+        let edited = apply_changes("", Format::Yaml, &before, &changes(&before, &after)).unwrap();
+        let edited: Value = serde_yaml_ng::from_str(&edited).unwrap();
+        assert_eq!(
+            edited,
+            json!({ "name": "api", "server": { "port": 2 }, "__schema__": { "name": {} } })
+        );
+    }
+}
@@ -10,8 +10,10 @@
 //! and resource limits.
 
 pub use crate::impl_::config::{
-    config, ConfigBuilder, ConfigLimits, DefaultSource, EnvSource, FileSource, MemorySource,
-    ReloadStrategy, SourceChain, SourceChainBuilder,
+    config, ConfigBuilder, ConfigDirSource, ConfigLimits, DefaultSource, EnvSource, FileGlobSource,
+    FileSource, LazyConfig, LoadPlan, MemorySource, PlannedProvider, PlannedSource, ProviderHealth,
+    ProviderKind, ReloadStrategy, SectionValidator, SourceChain, SourceChainBuilder, StringSource,
+    INCLUDE_KEY, OVERRIDE_SOURCE,
 };
 pub use crate::interface::Source;
 pub use crate::types::SourceKind;
@@ -19,5 +21,8 @@ pub use crate::types::SourceKind;
 #[cfg(feature = "remote")]
 pub use crate::impl_::config::AsyncSource;
 
+#[cfg(feature = "parse-cache")]
+pub use crate::impl_::config::ParseCache;
+
 #[cfg(feature = "snapshot")]
 pub use crate::snapshot::SnapshotConfig;
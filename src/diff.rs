@@ -0,0 +1,13 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Key-level diff of configuration trees.
+//!
+//! Implementation lives in `crate::impl_::diff`. `confers diff --format
+//! semantic` and `--format patch` use the same engine. In a reload callback,
+//! [`ConfigDiff::between`] compares the old and new config structs and
+//! [`ConfigDiff::touches`] tells whether a section changed.
+
+pub use crate::impl_::diff::{diff_values, ConfigDiff, KeyChange};
@@ -0,0 +1,530 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Redaction of secret values from error and audit messages.
+//!
+//! Parsers and deserializers quote what they choke on: a TOML error shows
+//! the offending line, and serde reports `invalid type: string "hunter2"`.
+//! A [`Redactor`] removes such values before an error leaves the builder or
+//! an event reaches the audit log. It knows which keys are sensitive from
+//! their names ([`is_sensitive_field_name`]) and from keys flagged with
+//! `#[config(sensitive)]` or [`ConfigBuilder::with_sensitive_keys`], and
+//! masks
+//!
+//! - `key = value`, `key: value` and `"key": "value"` assignments to a
+//!   sensitive key, wherever they appear in a message, and
+//! - the values the loaded configuration holds at sensitive keys, once a
+//!   tree is known ([`Redactor::collect_values`]).
+//!
+//! ```rust
+//! use confers::error::Redactor;
+//!
+//! let redactor = Redactor::new().with_sensitive_keys(["database.dsn"]);
+//! assert_eq!(
+//!     redactor.redact("line 3: dsn = \"postgres://db\" is not valid"),
+//!     "line 3: dsn = \"[REDACTED]\" is not valid"
+//! );
+//! assert_eq!(redactor.redact("password: hunter2"), "password: [REDACTED]");
+//! ```
+//!
+//! [`ConfigBuilder::with_sensitive_keys`]: crate::ConfigBuilder::with_sensitive_keys
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+
+use super::{ConfigError, FieldViolation, MultiSourceError, ValidationErrors, ValidationReport};
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// Replacement for redacted values.
+const REDACTED: &str = "[REDACTED]";
+
+/// Values shorter than this are not searched for in messages, since they
+/// would match unrelated text.
+const MIN_VALUE_CHARS: usize = 4;
+
+/// `key = value`, `key: value` or `"key": "value"`.
+static ASSIGNMENT_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r#"(?P<key>[A-Za-z_][A-Za-z0-9_.\-]*)(?P<sep>"?[ \t]*[:=][ \t]*)(?P<value>"[^"\n]*"|'[^'\n]*'|[^\s,;}\]"']+)"#,
+    )
+    .expect("ASSIGNMENT_RE regex is valid")
+});
+
+/// Canonical keywords naming sensitive fields, shared by redaction, the
+/// audit log and the `security` module. Multi-word keywords are written in
+/// snake case.
+pub(crate) static SENSITIVE_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
+    HashSet::from([
+        "password",
+        "passwd",
+        "secret",
+        "token",
+        "key",
+        "apikey",
+        "credential",
+        "auth",
+        "bearer",
+        "private",
+        "encryption",
+        "private_key",
+        "master_key",
+        "encryption_key",
+        "encrypt_key",
+        "access_key",
+        "api_key",
+        "api_secret",
+        "access_token",
+        "refresh_token",
+        "client_secret",
+        "session_id",
+        "sessionid",
+        "service_account",
+        "db_password",
+        "admin_password",
+    ])
+});
+
+/// Lower-case words of `name`, split at `_`, `-` and camel-case humps.
+fn name_words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_alphanumeric() {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut word));
+        }
+        prev_lower = c.is_lowercase() || c.is_ascii_digit();
+        word.extend(c.to_lowercase());
+    }
+    words.extend((!word.is_empty()).then_some(word));
+    words
+}
+
+/// Whether the last segment of dotted `key` names a secret: one of
+/// [`SENSITIVE_KEYWORDS`] appears in it as whole words, as in `password`,
+/// `api_key`, `clientSecret` or `auth_token`. `public_key` and `keyboard`
+/// are not secrets.
+pub fn is_sensitive_field_name(key: &str) -> bool {
+    let words = name_words(key.rsplit('.').next().unwrap_or(key));
+    if words.ends_with(&["public".to_string(), "key".to_string()]) {
+        return false;
+    }
+    SENSITIVE_KEYWORDS.iter().any(|keyword| {
+        let keyword: Vec<&str> = keyword.split('_').collect();
+        words
+            .windows(keyword.len())
+            .any(|window| window.iter().zip(&keyword).all(|(w, k)| w == k))
+    })
+}
+
+/// Removes the values of sensitive keys from messages and errors.
+#[derive(Debug, Clone, Default)]
+pub struct Redactor {
+    /// Dotted keys flagged sensitive
+    keys: Vec<String>,
+    /// Values held at sensitive keys, longest first
+    values: Vec<String>,
+}
+
+impl Redactor {
+    /// A redactor that only knows sensitive keys by their names.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Treat the dotted `keys`, and everything below them, as sensitive.
+    pub fn with_sensitive_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Remember the scalar values `tree` holds at sensitive keys, so that
+    /// they are also redacted where a message quotes them without their key.
+    pub fn collect_values(mut self, tree: &AnnotatedValue) -> Self {
+        self.collect(tree, "", false);
+        self.values
+            .sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        self.values.dedup();
+        self
+    }
+
+    fn collect(&mut self, node: &AnnotatedValue, path: &str, sensitive: bool) {
+        let child = |key: &str| {
+            if path.is_empty() {
+                key.to_string()
+            } else {
+                format!("{}.{}", path, key)
+            }
+        };
+        match &node.inner {
+            ConfigValue::Map(map) => {
+                for (key, value) in map.iter() {
+                    let path = child(key);
+                    let sensitive = sensitive || self.is_sensitive_key(&path);
+                    self.collect(value, &path, sensitive);
+                }
+            }
+            ConfigValue::Array(items) => {
+                for (i, value) in items.iter().enumerate() {
+                    self.collect(value, &child(&i.to_string()), sensitive);
+                }
+            }
+            scalar if sensitive => {
+                let text = match scalar {
+                    ConfigValue::String(s) => s.clone(),
+                    ConfigValue::I64(n) => n.to_string(),
+                    ConfigValue::U64(n) => n.to_string(),
+                    ConfigValue::F64(n) => n.to_string(),
+                    _ => return,
+                };
+                if text.chars().count() >= MIN_VALUE_CHARS {
+                    self.values.push(text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether dotted `key` is sensitive, by name or because it or a parent
+    /// was flagged.
+    pub fn is_sensitive_key(&self, key: &str) -> bool {
+        is_sensitive_field_name(key)
+            || self.keys.iter().any(|flagged| {
+                key == flagged
+                    || key
+                        .strip_prefix(flagged.as_str())
+                        .is_some_and(|rest| rest.starts_with('.'))
+            })
+    }
+
+    /// Whether `name`, a key as written in a message (possibly only its last
+    /// segment), may be a sensitive key.
+    fn is_sensitive_name(&self, name: &str) -> bool {
+        let last = name.rsplit('.').next().unwrap_or(name);
+        self.is_sensitive_key(name)
+            || self
+                .keys
+                .iter()
+                .any(|flagged| flagged.rsplit('.').next() == Some(last))
+    }
+
+    /// `message` with the values of sensitive keys replaced by `[REDACTED]`.
+    pub fn redact(&self, message: &str) -> String {
+        let mut result = message.to_string();
+        for value in &self.values {
+            if result.contains(value.as_str()) {
+                result = result.replace(value.as_str(), REDACTED);
+            }
+        }
+        self.mask_assignments(&result)
+    }
+
+    fn mask_assignments(&self, message: &str) -> String {
+        ASSIGNMENT_RE
+            .replace_all(message, |caps: &regex::Captures| {
+                let (key, sep, value) = (&caps["key"], &caps["sep"], &caps["value"]);
+                if !self.is_sensitive_name(key) {
+                    // `reason: password=x` nests an assignment in the value
+                    return format!("{}{}{}", key, sep, self.mask_assignments(value));
+                }
+                let masked = match value.chars().next() {
+                    Some(quote @ ('"' | '\'')) => format!("{}{}{}", quote, REDACTED, quote),
+                    _ => REDACTED.to_string(),
+                };
+                format!("{}{}{}", key, sep, masked)
+            })
+            .into_owned()
+    }
+
+    /// `error` with its messages [redacted](Self::redact). The source of a
+    /// parse or migration error is dropped when it would reveal a value.
+    pub fn redact_error(&self, error: ConfigError) -> ConfigError {
+        let boxed = |source: Option<Box<dyn std::error::Error + Send + Sync>>| {
+            source.filter(|e| {
+                let text = e.to_string();
+                self.redact(&text) == text
+            })
+        };
+        match error {
+            ConfigError::ParseError {
+                format,
+                message,
+                location,
+                source,
+            } => ConfigError::ParseError {
+                format,
+                message: self.redact(&message),
+                location,
+                source: boxed(source),
+            },
+            ConfigError::ValidationFailed {
+                field,
+                rule,
+                message,
+            } => ConfigError::ValidationFailed {
+                message: if self.is_sensitive_key(&field) {
+                    self.redact(&message)
+                } else {
+                    message
+                },
+                field,
+                rule,
+            },
+            ConfigError::CrossFieldValidation { errors } => ConfigError::CrossFieldValidation {
+                errors: ValidationErrors {
+                    violations: errors
+                        .violations
+                        .into_iter()
+                        .map(|v| FieldViolation {
+                            message: self.redact(&v.message),
+                            fields: v.fields,
+                        })
+                        .collect(),
+                },
+            },
+            ConfigError::UnknownKeys { report } => {
+                let mut redacted = ValidationReport::new();
+                for mut issue in report.issues().iter().cloned() {
+                    if self.is_sensitive_key(&issue.path) && issue.value.is_some() {
+                        issue.value = Some(REDACTED.to_string());
+                    }
+                    issue.constraint = self.redact(&issue.constraint);
+                    redacted.push(issue);
+                }
+                ConfigError::UnknownKeys { report: redacted }
+            }
+            ConfigError::DecryptionFailed { message } => ConfigError::DecryptionFailed {
+                message: self.redact(&message),
+            },
+            ConfigError::MigrationFailed {
+                from,
+                to,
+                reason,
+                source,
+            } => ConfigError::MigrationFailed {
+                from,
+                to,
+                reason: self.redact(&reason),
+                source: boxed(source),
+            },
+            ConfigError::ReloadRolledBack { reason } => ConfigError::ReloadRolledBack {
+                reason: self.redact(&reason),
+            },
+            ConfigError::InvalidValue {
+                key,
+                expected_type,
+                message,
+            } => ConfigError::InvalidValue {
+                key,
+                expected_type,
+                message: self.redact(&message),
+            },
+            ConfigError::SourceChainError {
+                message,
+                source_index,
+            } => ConfigError::SourceChainError {
+                message: self.redact(&message),
+                source_index,
+            },
+            ConfigError::InterpolationError { variable, message } => {
+                ConfigError::InterpolationError {
+                    variable,
+                    message: self.redact(&message),
+                }
+            }
+            ConfigError::TemplateError { key, message } => ConfigError::TemplateError {
+                key,
+                message: self.redact(&message),
+            },
+            ConfigError::SecretReferenceError { key, message } => {
+                ConfigError::SecretReferenceError {
+                    key,
+                    message: self.redact(&message),
+                }
+            }
+            ConfigError::MultiSource { source } => ConfigError::MultiSource {
+                source: MultiSourceError {
+                    errors: source
+                        .errors
+                        .into_iter()
+                        .map(|(name, e)| (name, self.redact_error(e)))
+                        .collect(),
+                    ..source
+                },
+            },
+            ConfigError::ConcurrencyConflict {
+                key,
+                message,
+                expected_type,
+            } => ConfigError::ConcurrencyConflict {
+                key,
+                message: self.redact(&message),
+                expected_type,
+            },
+            ConfigError::WatcherError {
+                message,
+                path,
+                recoverable,
+            } => ConfigError::WatcherError {
+                message: self.redact(&message),
+                path,
+                recoverable,
+            },
+            ConfigError::OverrideBlocked {
+                key,
+                reason,
+                override_source,
+            } => ConfigError::OverrideBlocked {
+                key,
+                reason: self.redact(&reason),
+                override_source,
+            },
+            ConfigError::HealthCheckFailed { reason } => ConfigError::HealthCheckFailed {
+                reason: self.redact(&reason),
+            },
+            other => other,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+    use std::sync::Arc;
+
+    fn tree(entries: &[(&str, ConfigValue)]) -> AnnotatedValue {
+        let mut map = indexmap::IndexMap::new();
+        for (key, value) in entries {
+            let parts: Vec<&str> = key.split('.').collect();
+            crate::EnvSource::insert_nested(
+                &mut map,
+                &parts,
+                AnnotatedValue::new(value.clone(), SourceId::new("test"), *key),
+            );
+        }
+        AnnotatedValue::new(ConfigValue::Map(Arc::new(map)), SourceId::new("test"), "")
+    }
+
+    #[test]
+    fn test_is_sensitive_field_name() {
+        assert!(is_sensitive_field_name("db.password"));
+        assert!(is_sensitive_field_name("github_token"));
+        assert!(is_sensitive_field_name("signing_key"));
+        assert!(is_sensitive_field_name("clientSecret"));
+        assert!(is_sensitive_field_name("apikey"));
+        assert!(!is_sensitive_field_name("ssh.public_key"));
+        assert!(!is_sensitive_field_name("keyboard"));
+        assert!(!is_sensitive_field_name("monkey"));
+        assert!(!is_sensitive_field_name("host"));
+    }
+
+    #[test]
+    fn test_redacts_assignments_to_sensitive_keys() {
+        let redactor = Redactor::new().with_sensitive_keys(["db.dsn"]);
+        assert_eq!(
+            redactor.redact("TOML parse error at line 2\n2 | password = hunter2x\n  |"),
+            "TOML parse error at line 2\n2 | password = [REDACTED]\n  |"
+        );
+        assert_eq!(
+            redactor.redact(r#"at {"api_key": "abc123", "host": "db"}"#),
+            r#"at {"api_key": "[REDACTED]", "host": "db"}"#
+        );
+        assert_eq!(
+            redactor.redact("dsn='postgres://u:p@db' port=5432"),
+            "dsn='[REDACTED]' port=5432"
+        );
+        assert_eq!(redactor.redact("host = db1"), "host = db1");
+        assert_eq!(
+            redactor.redact("bad response: token=abc123"),
+            "bad response: token=[REDACTED]"
+        );
+        // The key of our own messages is quoted and followed by the message
+        assert_eq!(
+            redactor.redact("value for 'password': must not be empty"),
+            "value for 'password': must not be empty"
+        );
+    }
+
+    #[test]
+    fn test_redacts_collected_values() {
+        let redactor = Redactor::new()
+            .with_sensitive_keys(["database"])
+            .collect_values(&tree(&[
+                ("database.dsn", ConfigValue::String("postgres://db".into())),
+                ("service.token", ConfigValue::String("t0k3n-value".into())),
+                ("service.pin", ConfigValue::String("12".into())),
+                ("service.name", ConfigValue::String("billing".into())),
+            ]));
+        assert_eq!(
+            redactor.redact(r#"invalid type: string "t0k3n-value", expected u16"#),
+            r#"invalid type: string "[REDACTED]", expected u16"#
+        );
+        assert_eq!(
+            redactor.redact("cannot reach postgres://db from billing"),
+            "cannot reach [REDACTED] from billing"
+        );
+        assert!(redactor.is_sensitive_key("database.dsn"));
+        assert!(!redactor.is_sensitive_key("databases.dsn"));
+    }
+
+    #[test]
+    fn test_collected_values_are_deduplicated() {
+        let redactor = Redactor::new().collect_values(&tree(&[
+            ("a.password", ConfigValue::String("hunter22".into())),
+            ("b.token", ConfigValue::String("abcd".into())),
+            ("c.secret", ConfigValue::String("hunter22".into())),
+        ]));
+        assert_eq!(redactor.values, ["hunter22", "abcd"]);
+    }
+
+    #[test]
+    fn test_redact_error_rewrites_messages_and_drops_leaking_sources() {
+        let redactor = Redactor::new();
+        let source: Box<dyn std::error::Error + Send + Sync> =
+            "expected value at `secret = oops`".into();
+        let err = redactor.redact_error(ConfigError::ParseError {
+            format: "toml".into(),
+            message: "line 1: secret = oops".into(),
+            location: None,
+            source: Some(source),
+        });
+        let ConfigError::ParseError {
+            message, source, ..
+        } = &err
+        else {
+            panic!("expected ParseError, got {:?}", err);
+        };
+        assert_eq!(message, "line 1: secret = [REDACTED]");
+        assert!(source.is_none());
+
+        let err = redactor.redact_error(ConfigError::MultiSource {
+            source: MultiSourceError::new(
+                2,
+                vec![(
+                    "vault",
+                    ConfigError::InvalidValue {
+                        key: "token".into(),
+                        expected_type: "string".into(),
+                        message: "token=s.abcdef is expired".into(),
+                    },
+                )],
+            ),
+        });
+        let ConfigError::MultiSource { source } = &err else {
+            panic!("expected MultiSource, got {:?}", err);
+        };
+        assert!(!format!("{}", source.errors[0].1).contains("s.abcdef"));
+    }
+}
@@ -0,0 +1,383 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Structured validation reports.
+//!
+//! A [`ValidationReport`] keeps one [`ValidationIssue`] per failed check with
+//! the field path, the offending value, the broken constraint, where the
+//! value came from and, for unknown keys, the closest known key:
+//!
+//! ```rust
+//! use confers::error::{ValidationIssue, ValidationReport};
+//!
+//! let mut report = ValidationReport::new();
+//! report.push(
+//!     ValidationIssue::new("server.prot", "not defined by the schema")
+//!         .with_suggestion(confers::error::suggest_key("prot", ["host", "port"])),
+//! );
+//! assert_eq!(
+//!     report.to_string(),
+//!     "server.prot: not defined by the schema (did you mean `port`?)"
+//! );
+//! ```
+//!
+//! Values are sanitized before they are stored: secrets (by key name or
+//! content) become `[REDACTED]` and long values are truncated.
+
+use serde::Serialize;
+
+use super::{is_sensitive_value, ValidationErrors};
+use crate::types::{AnnotatedValue, ConfigValue, SourceLocation};
+
+/// Longest value kept in an issue, in characters.
+const MAX_VALUE_CHARS: usize = 64;
+
+/// A single failed check.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationIssue {
+    /// Dotted path of the field, e.g. `server.port`
+    pub path: String,
+    /// The sanitized value, when the field has a scalar value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The constraint that failed, e.g. `70000 is greater than the maximum 65535`
+    pub constraint: String,
+    /// Identifier of the failed check, e.g. the JSON Schema keyword `maximum`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    /// Source that provided the value, e.g. `app.toml` or `env`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// File, line and column of the value, when the source tracks them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<SourceLocation>,
+    /// Closest known key, for unknown keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub suggestion: Option<String>,
+}
+
+impl ValidationIssue {
+    /// Create an issue for `path`.
+    pub fn new(path: impl Into<String>, constraint: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            value: None,
+            constraint: constraint.into(),
+            rule: None,
+            source: None,
+            location: None,
+            suggestion: None,
+        }
+    }
+
+    /// Record the offending value, sanitized.
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value = Some(sanitize_value(&self.path, value));
+        self
+    }
+
+    /// Record the identifier of the failed check.
+    pub fn with_rule(mut self, rule: impl Into<String>) -> Self {
+        self.rule = Some(rule.into());
+        self
+    }
+
+    /// Record the source that provided the value.
+    pub fn with_source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Record where the value is defined.
+    pub fn with_location(mut self, location: SourceLocation) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Record a "did you mean" suggestion.
+    pub fn with_suggestion(mut self, suggestion: Option<String>) -> Self {
+        self.suggestion = suggestion;
+        self
+    }
+}
+
+impl std::fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.path, self.constraint)?;
+        if let Some(value) = &self.value {
+            write!(f, " (value: {})", value)?;
+        }
+        match (&self.location, &self.source) {
+            (Some(location), _) => write!(f, " [{}]", location)?,
+            (None, Some(source)) => write!(f, " [{}]", source)?,
+            (None, None) => {}
+        }
+        if let Some(suggestion) = &self.suggestion {
+            write!(f, " (did you mean `{}`?)", suggestion)?;
+        }
+        Ok(())
+    }
+}
+
+/// Every issue found while validating a configuration.
+///
+/// `Display` prints one issue per line; the `Serialize` output is
+/// `{"issues": [...]}` for machine-readable reports.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationReport {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    /// Create an empty report.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an issue.
+    pub fn push(&mut self, issue: ValidationIssue) {
+        self.issues.push(issue);
+    }
+
+    /// The issues, in the order they were added.
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+
+    /// Whether no issue was found.
+    pub fn is_empty(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Number of issues.
+    pub fn len(&self) -> usize {
+        self.issues.len()
+    }
+
+    /// Keep only the issues for which `keep` returns `true`.
+    pub fn retain(&mut self, keep: impl FnMut(&ValidationIssue) -> bool) {
+        self.issues.retain(keep);
+    }
+
+    /// Fill in the value, source and location of each issue from the
+    /// merged configuration tree.
+    ///
+    /// Issues whose path is not in `tree` (e.g. missing keys) are left as is.
+    pub fn attach_sources(&mut self, tree: &AnnotatedValue) {
+        for issue in &mut self.issues {
+            let Some(node) = lookup(tree, &issue.path) else {
+                continue;
+            };
+            if issue.value.is_none() {
+                if let Some(value) = scalar_text(&node.inner) {
+                    issue.value = Some(sanitize_value(&issue.path, &value));
+                }
+            }
+            if issue.source.is_none() {
+                issue.source = Some(node.source.as_str().to_string());
+            }
+            if issue.location.is_none() {
+                issue.location = node.location.clone();
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (i, issue) in self.issues.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl From<ValidationErrors> for ValidationReport {
+    fn from(errors: ValidationErrors) -> Self {
+        let issues = errors
+            .violations()
+            .iter()
+            .map(|violation| ValidationIssue::new(violation.fields.join(", "), &violation.message))
+            .collect();
+        Self { issues }
+    }
+}
+
+#[cfg(feature = "validation")]
+impl From<&garde::Report> for ValidationReport {
+    fn from(report: &garde::Report) -> Self {
+        let issues = report
+            .iter()
+            .map(|(path, error)| ValidationIssue::new(path.to_string(), error.message()))
+            .collect();
+        Self { issues }
+    }
+}
+
+/// The candidate closest to `key`, if it is a plausible typo.
+///
+/// Uses edit distance, allowing one edit per three characters (at least one).
+pub fn suggest_key<'a>(key: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<String> {
+    let max_distance = (key.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != key)
+        .map(|candidate| (edit_distance(key, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate.to_string())
+}
+
+/// Edit distance counting insertions, deletions, substitutions and swaps of
+/// adjacent characters (so `prot` is one edit from `port`).
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in d[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Redact secrets and truncate long values.
+fn sanitize_value(path: &str, value: &str) -> String {
+    if is_sensitive_value(path, value) {
+        return "[REDACTED]".to_string();
+    }
+    if value.chars().count() > MAX_VALUE_CHARS {
+        let truncated: String = value.chars().take(MAX_VALUE_CHARS).collect();
+        return format!("{}...", truncated);
+    }
+    value.to_string()
+}
+
+/// Find the node at a dotted path; numeric segments index arrays.
+fn lookup<'a>(tree: &'a AnnotatedValue, path: &str) -> Option<&'a AnnotatedValue> {
+    path.split('.')
+        .try_fold(tree, |node, segment| match &node.inner {
+            ConfigValue::Map(map) => map.get(segment),
+            ConfigValue::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            _ => None,
+        })
+}
+
+fn scalar_text(value: &ConfigValue) -> Option<String> {
+    match value {
+        ConfigValue::Null => Some("null".to_string()),
+        ConfigValue::Bool(b) => Some(b.to_string()),
+        ConfigValue::I64(n) => Some(n.to_string()),
+        ConfigValue::U64(n) => Some(n.to_string()),
+        ConfigValue::F64(n) => Some(n.to_string()),
+        ConfigValue::String(s) => Some(s.clone()),
+        ConfigValue::Bytes(_) | ConfigValue::Array(_) | ConfigValue::Map(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+    use indexmap::IndexMap;
+    use std::sync::Arc;
+
+    fn tree() -> AnnotatedValue {
+        let file = SourceId::new("app.toml");
+        let server = IndexMap::from([
+            (
+                Arc::from("port"),
+                AnnotatedValue::new(ConfigValue::U64(70000), file.clone(), "server.port")
+                    .with_location(SourceLocation::new("app.toml", 3, 8)),
+            ),
+            (
+                Arc::from("password"),
+                AnnotatedValue::new(
+                    ConfigValue::String("hunter2".into()),
+                    SourceId::new("env"),
+                    "server.password",
+                ),
+            ),
+        ]);
+        let root = IndexMap::from([(
+            Arc::from("server"),
+            AnnotatedValue::new(ConfigValue::Map(Arc::new(server)), file.clone(), "server"),
+        )]);
+        AnnotatedValue::new(ConfigValue::Map(Arc::new(root)), file, "")
+    }
+
+    #[test]
+    fn test_attach_sources() {
+        let mut report = ValidationReport::new();
+        report.push(ValidationIssue::new(
+            "server.port",
+            "70000 is greater than the maximum 65535",
+        ));
+        report.push(ValidationIssue::new("server.password", "too short"));
+        report.push(ValidationIssue::new("server.host", "missing required key"));
+        report.attach_sources(&tree());
+
+        let issues = report.issues();
+        assert_eq!(issues[0].value.as_deref(), Some("70000"));
+        assert_eq!(issues[0].source.as_deref(), Some("app.toml"));
+        assert_eq!(issues[0].location.as_ref().map(|l| l.line), Some(3));
+        assert_eq!(issues[1].value.as_deref(), Some("[REDACTED]"));
+        assert_eq!(issues[1].source.as_deref(), Some("env"));
+        assert_eq!(issues[2].source, None);
+
+        assert_eq!(
+            report.to_string(),
+            "server.port: 70000 is greater than the maximum 65535 (value: 70000) [app.toml:3:8]\n\
+             server.password: too short (value: [REDACTED]) [env]\n\
+             server.host: missing required key"
+        );
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["issues"][1]["value"], "[REDACTED]");
+        assert!(json["issues"][2].get("source").is_none());
+    }
+
+    #[test]
+    fn test_suggest_key() {
+        assert_eq!(
+            suggest_key("prot", ["host", "port"]),
+            Some("port".to_string())
+        );
+        assert_eq!(
+            suggest_key("databse_url", ["database_url", "timeout"]),
+            Some("database_url".to_string())
+        );
+        assert_eq!(suggest_key("colour", ["host", "port"]), None);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("prot", "port"), 1);
+    }
+
+    #[test]
+    fn test_from_validation_errors_and_truncation() {
+        let mut errors = ValidationErrors::new();
+        errors.add(&["tls_enabled", "tls_cert"], "tls_cert is required");
+        let report = ValidationReport::from(errors);
+        assert_eq!(report.len(), 1);
+        assert_eq!(report.issues()[0].path, "tls_enabled, tls_cert");
+
+        let issue = ValidationIssue::new("banner", "too long").with_value(&"x".repeat(100));
+        assert_eq!(issue.value.unwrap().len(), MAX_VALUE_CHARS + 3);
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Feature flags — public facade.
+//!
+//! Implementation lives in `crate::impl_::flags`.
+
+pub use crate::impl_::flags::{
+    Experiment, FlagDefinition, FlagEvaluation, FlagOperator, FlagReason, FlagRule, FlagSet, Flags,
+    Variant,
+};
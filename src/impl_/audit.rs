@@ -3,9 +3,19 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+use crate::error::{is_sensitive_field_name, Redactor};
+use crate::impl_::audit_sink::{AuditSink, FileSink};
+
+/// An audit event. Sinks serialize it as a JSON object whose `event` field
+/// holds the variant name.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event")]
 pub enum AuditEvent {
     KeyAccess {
         key: String,
@@ -29,6 +39,131 @@ pub enum AuditEvent {
         source: String,
         timestamp: DateTime<Utc>,
     },
+    /// A remote source was unreachable and a cached (stale/offline) payload was used.
+    OfflineFallback {
+        source: String,
+        cached_at: DateTime<Utc>,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A remote provider fetch finished, successfully or not, after
+    /// `duration_ms` milliseconds.
+    ProviderFetched {
+        source: String,
+        duration_ms: u64,
+        success: bool,
+        timestamp: DateTime<Utc>,
+    },
+    /// A remote source was unreachable and its degradation policy let the
+    /// build continue without it (`skip`) or with fallback values (`fallback`).
+    ProviderDegraded {
+        source: String,
+        policy: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A remote provider had not answered when the load deadline elapsed
+    /// and was abandoned.
+    LoadDeadlineExceeded {
+        source: String,
+        deadline_ms: u64,
+        timestamp: DateTime<Utc>,
+    },
+    /// A configuration key was set under a deprecated alias.
+    DeprecatedKey {
+        key: String,
+        replacement: String,
+        since: Option<String>,
+        source: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A feature-flag experiment assigned `subject` (a targeting key) to
+    /// `variant`.
+    ExperimentExposure {
+        experiment: String,
+        variant: String,
+        subject: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A configuration build finished and produced a value.
+    ConfigLoaded {
+        correlation_id: String,
+        sources: Vec<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A progressive reload committed the candidate configuration.
+    ConfigReloaded {
+        correlation_id: String,
+        strategy: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A progressive reload was rolled back and the previous configuration kept.
+    ReloadRejected {
+        correlation_id: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+    /// A reload was rejected or failed and the last known-good configuration
+    /// is still served.
+    ConfigRolledBack {
+        correlation_id: String,
+        reason: String,
+        /// Checksum of the known-good snapshot, if one has been saved.
+        checksum: Option<String>,
+        timestamp: DateTime<Utc>,
+    },
+    /// A key ring was rotated and its encrypted values re-encrypted.
+    KeyRotated {
+        correlation_id: String,
+        key_id: String,
+        from_version: u32,
+        to_version: u32,
+        reencrypted_values: usize,
+        timestamp: DateTime<Utc>,
+    },
+    /// An encrypted value was decrypted with a key version other than the
+    /// primary one, or without naming its version (`versioned: false`).
+    StaleEncryptedValue {
+        key: String,
+        version: u32,
+        current_version: u32,
+        versioned: bool,
+        timestamp: DateTime<Utc>,
+    },
+    /// An encrypted value could not be decrypted while loading.
+    DecryptionFailed {
+        correlation_id: String,
+        reason: String,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl AuditEvent {
+    /// The correlation ID tying this event to one load, reload or rotation.
+    /// `None` for events that predate correlation IDs.
+    pub fn correlation_id(&self) -> Option<&str> {
+        match self {
+            AuditEvent::ConfigLoaded { correlation_id, .. }
+            | AuditEvent::ConfigReloaded { correlation_id, .. }
+            | AuditEvent::ReloadRejected { correlation_id, .. }
+            | AuditEvent::ConfigRolledBack { correlation_id, .. }
+            | AuditEvent::KeyRotated { correlation_id, .. }
+            | AuditEvent::DecryptionFailed { correlation_id, .. } => Some(correlation_id),
+            _ => None,
+        }
+    }
+}
+
+/// A new correlation ID, unique within the process and unlikely to collide
+/// across processes.
+pub fn new_correlation_id() -> String {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    format!(
+        "{:x}-{:x}-{:x}",
+        Utc::now().timestamp_micros(),
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    )
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -45,6 +180,19 @@ impl AuditLevel {
             AuditEvent::Decrypt { .. } => AuditLevel::Durable,
             AuditEvent::LoadSuccess { .. } => AuditLevel::BestEffort,
             AuditEvent::ReloadTrigger { .. } => AuditLevel::BestEffort,
+            AuditEvent::OfflineFallback { .. } => AuditLevel::Durable,
+            AuditEvent::ProviderFetched { .. } => AuditLevel::BestEffort,
+            AuditEvent::ProviderDegraded { .. } => AuditLevel::Durable,
+            AuditEvent::LoadDeadlineExceeded { .. } => AuditLevel::Durable,
+            AuditEvent::DeprecatedKey { .. } => AuditLevel::BestEffort,
+            AuditEvent::ExperimentExposure { .. } => AuditLevel::BestEffort,
+            AuditEvent::ConfigLoaded { .. } => AuditLevel::BestEffort,
+            AuditEvent::ConfigReloaded { .. } => AuditLevel::Durable,
+            AuditEvent::ReloadRejected { .. } => AuditLevel::Durable,
+            AuditEvent::ConfigRolledBack { .. } => AuditLevel::Durable,
+            AuditEvent::KeyRotated { .. } => AuditLevel::Durable,
+            AuditEvent::StaleEncryptedValue { .. } => AuditLevel::BestEffort,
+            AuditEvent::DecryptionFailed { .. } => AuditLevel::Durable,
         }
     }
 }
@@ -129,6 +277,7 @@ impl Default for AuditConfigBuilder {
 
 pub struct AuditWriter {
     config: AuditConfig,
+    sinks: Vec<Arc<dyn AuditSink>>,
 }
 
 impl AuditWriter {
@@ -140,8 +289,32 @@ impl AuditWriter {
         AuditWriterBuilder::new()
     }
 
+    /// Create a writer; a `log_dir` in `config` adds a [`FileSink`].
     pub fn with_config(config: AuditConfig) -> Self {
-        Self { config }
+        let sinks: Vec<Arc<dyn AuditSink>> = match &config.log_dir {
+            Some(dir) => vec![Arc::new(
+                FileSink::new(dir.clone()).durable(config.durable_wal),
+            )],
+            None => Vec::new(),
+        };
+        Self { config, sinks }
+    }
+
+    /// A copy of this writer that also sends events to `sink`.
+    pub fn with_sink(&self, sink: Arc<dyn AuditSink>) -> Self {
+        let mut sinks = self.sinks.clone();
+        sinks.push(sink);
+        Self {
+            config: self.config.clone(),
+            sinks,
+        }
+    }
+
+    /// Flush every sink.
+    pub fn flush(&self) {
+        for sink in &self.sinks {
+            let _ = sink.flush();
+        }
     }
 
     pub fn is_enabled(&self) -> bool {
@@ -173,69 +346,52 @@ impl AuditWriter {
     }
 
     /// Shared write path for both Durable and BestEffort events.
-    /// Writes the sanitized event to `audit_YYYYMMDD.log` in `log_dir` if configured.
-    /// Silently drops the event if `log_dir` is None or the write fails.
+    /// Hands the sanitized event to every sink. Silently drops the event if
+    /// there are no sinks; a failing sink does not affect the others.
     fn write_to_log(&self, event: &AuditEvent) {
-        let Some(ref dir) = self.config.log_dir else {
+        if self.sinks.is_empty() {
             return;
-        };
+        }
         let sanitized = self.sanitize(event);
-        let filename = format!("audit_{}.log", Utc::now().format("%Y%m%d"));
-        let path = dir.join(filename);
-        if let Ok(mut file) = std::fs::OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(path)
-        {
-            use std::io::Write;
-            let _ = writeln!(file, "{} {:?}", Utc::now(), sanitized);
+        for sink in &self.sinks {
+            let _ = sink.write(&sanitized);
         }
     }
 
     fn sanitize(&self, event: &AuditEvent) -> AuditEvent {
-        // Extended list of sensitive field keywords for redaction
-        const SENSITIVE_KEYWORDS: &[&str] = &[
-            "password",
-            "secret",
-            "key",
-            "token",
-            "credential",
-            "auth",
-            "api_key",
-            "apikey",
-            "access_key",
-            "private_key",
-            "session_id",
-            "sessionid",
-            "bearer",
-            "refresh_token",
-            "client_secret",
-            "encryption_key",
-            "encrypt_key",
-            "master_key",
-            "service_account",
-        ];
-
         match event {
             AuditEvent::Decrypt {
                 field,
                 success,
                 timestamp,
             } => {
-                let lower_field = field.to_lowercase();
-                let sanitized_field =
-                    if SENSITIVE_KEYWORDS.iter().any(|kw| lower_field.contains(kw)) {
-                        "***REDACTED***".to_string()
-                    } else {
-                        field.clone()
-                    };
+                let sanitized_field = if field.split('.').any(is_sensitive_field_name) {
+                    "***REDACTED***".to_string()
+                } else {
+                    field.clone()
+                };
                 AuditEvent::Decrypt {
                     field: sanitized_field,
                     success: *success,
                     timestamp: *timestamp,
                 }
             }
-            other => other.clone(),
+            other => {
+                // Free-text reasons quote provider and parser errors; mask
+                // any secret-looking assignment they carry
+                let mut event = other.clone();
+                match &mut event {
+                    AuditEvent::OfflineFallback { reason, .. }
+                    | AuditEvent::ProviderDegraded { reason, .. }
+                    | AuditEvent::ReloadRejected { reason, .. }
+                    | AuditEvent::ConfigRolledBack { reason, .. }
+                    | AuditEvent::DecryptionFailed { reason, .. } => {
+                        *reason = Redactor::new().redact(reason);
+                    }
+                    _ => {}
+                }
+                event
+            }
         }
     }
 
@@ -246,6 +402,13 @@ impl AuditWriter {
         });
     }
 
+    pub fn log_reload_trigger(&self, source: &str) {
+        self.write(AuditEvent::ReloadTrigger {
+            source: source.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
     pub fn log_key_access(&self, key: &str) {
         self.write(AuditEvent::KeyAccess {
             key: key.to_string(),
@@ -261,6 +424,82 @@ impl AuditWriter {
         });
     }
 
+    pub fn log_offline_fallback(&self, source: &str, cached_at: DateTime<Utc>, reason: &str) {
+        self.write(AuditEvent::OfflineFallback {
+            source: source.to_string(),
+            cached_at,
+            reason: reason.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_provider_fetched(&self, source: &str, duration: std::time::Duration, success: bool) {
+        self.write(AuditEvent::ProviderFetched {
+            source: source.to_string(),
+            duration_ms: u64::try_from(duration.as_millis()).unwrap_or(u64::MAX),
+            success,
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_provider_degraded(&self, source: &str, policy: &str, reason: &str) {
+        self.write(AuditEvent::ProviderDegraded {
+            source: source.to_string(),
+            policy: policy.to_string(),
+            reason: reason.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_load_deadline_exceeded(&self, source: &str, deadline: std::time::Duration) {
+        self.write(AuditEvent::LoadDeadlineExceeded {
+            source: source.to_string(),
+            deadline_ms: u64::try_from(deadline.as_millis()).unwrap_or(u64::MAX),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_deprecated_key(
+        &self,
+        key: &str,
+        replacement: &str,
+        since: Option<&str>,
+        source: &str,
+    ) {
+        self.write(AuditEvent::DeprecatedKey {
+            key: key.to_string(),
+            replacement: replacement.to_string(),
+            since: since.map(str::to_string),
+            source: source.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_stale_encrypted_value(
+        &self,
+        key: &str,
+        version: u32,
+        current_version: u32,
+        versioned: bool,
+    ) {
+        self.write(AuditEvent::StaleEncryptedValue {
+            key: key.to_string(),
+            version,
+            current_version,
+            versioned,
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_experiment_exposure(&self, experiment: &str, variant: &str, subject: &str) {
+        self.write(AuditEvent::ExperimentExposure {
+            experiment: experiment.to_string(),
+            variant: variant.to_string(),
+            subject: subject.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
     pub fn log_key_rotation(&self, old_ver: &str, new_ver: &str) {
         self.write(AuditEvent::KeyRotation {
             old_version: old_ver.to_string(),
@@ -268,6 +507,70 @@ impl AuditWriter {
             timestamp: Utc::now(),
         });
     }
+
+    pub fn log_config_loaded(&self, correlation_id: &str, sources: Vec<String>) {
+        self.write(AuditEvent::ConfigLoaded {
+            correlation_id: correlation_id.to_string(),
+            sources,
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_config_reloaded(&self, correlation_id: &str, strategy: &str) {
+        self.write(AuditEvent::ConfigReloaded {
+            correlation_id: correlation_id.to_string(),
+            strategy: strategy.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_reload_rejected(&self, correlation_id: &str, reason: &str) {
+        self.write(AuditEvent::ReloadRejected {
+            correlation_id: correlation_id.to_string(),
+            reason: reason.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_config_rolled_back(
+        &self,
+        correlation_id: &str,
+        reason: &str,
+        checksum: Option<&str>,
+    ) {
+        self.write(AuditEvent::ConfigRolledBack {
+            correlation_id: correlation_id.to_string(),
+            reason: reason.to_string(),
+            checksum: checksum.map(str::to_string),
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_key_rotated(
+        &self,
+        correlation_id: &str,
+        key_id: &str,
+        from_version: u32,
+        to_version: u32,
+        reencrypted_values: usize,
+    ) {
+        self.write(AuditEvent::KeyRotated {
+            correlation_id: correlation_id.to_string(),
+            key_id: key_id.to_string(),
+            from_version,
+            to_version,
+            reencrypted_values,
+            timestamp: Utc::now(),
+        });
+    }
+
+    pub fn log_decryption_failed(&self, correlation_id: &str, reason: &str) {
+        self.write(AuditEvent::DecryptionFailed {
+            correlation_id: correlation_id.to_string(),
+            reason: reason.to_string(),
+            timestamp: Utc::now(),
+        });
+    }
 }
 
 impl Default for AuditWriter {
@@ -278,15 +581,23 @@ impl Default for AuditWriter {
 
 pub struct AuditWriterBuilder {
     config: AuditConfig,
+    sinks: Vec<Arc<dyn AuditSink>>,
 }
 
 impl AuditWriterBuilder {
     pub fn new() -> Self {
         Self {
             config: AuditConfig::default(),
+            sinks: Vec::new(),
         }
     }
 
+    /// Also send events to `sink`. May be called repeatedly.
+    pub fn sink(mut self, sink: impl AuditSink + 'static) -> Self {
+        self.sinks.push(Arc::new(sink));
+        self
+    }
+
     pub fn enabled(mut self, enabled: bool) -> Self {
         self.config.enabled = enabled;
         self
@@ -303,7 +614,9 @@ impl AuditWriterBuilder {
     }
 
     pub fn build(self) -> AuditWriter {
-        AuditWriter::with_config(self.config)
+        let mut writer = AuditWriter::with_config(self.config);
+        writer.sinks.extend(self.sinks);
+        writer
     }
 }
 
@@ -0,0 +1,470 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Audit event destinations.
+//!
+//! An [`AuditWriter`](crate::audit::AuditWriter) sanitizes each event once and
+//! hands it to every registered [`AuditSink`]. Built-in sinks write one JSON
+//! object per event:
+//!
+//! | Sink | Destination |
+//! |------|-------------|
+//! | [`FileSink`] | `audit_YYYYMMDD.log` in a directory |
+//! | [`JsonStreamSink`] | stdout or stderr |
+//! | [`SyslogSink`] | the local syslog socket or a UDP collector (RFC 5424) |
+//! | [`WebhookSink`] | HTTP POST to a URL (`remote` feature) |
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use chrono::Utc;
+
+use crate::impl_::audit::{AuditEvent, AuditLevel};
+
+/// A destination for audit events.
+///
+/// Events arrive already sanitized. Errors are not propagated to the code that
+/// raised the event; a failing sink must not block configuration loading.
+pub trait AuditSink: Send + Sync {
+    /// Record one event.
+    fn write(&self, event: &AuditEvent) -> std::io::Result<()>;
+
+    /// Push buffered events to their destination.
+    fn flush(&self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Appends JSON lines to a daily `audit_YYYYMMDD.log` file in a directory.
+pub struct FileSink {
+    dir: PathBuf,
+    durable: bool,
+}
+
+impl FileSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            durable: false,
+        }
+    }
+
+    /// Sync the file to disk after each [`AuditLevel::Durable`] event.
+    pub fn durable(mut self, durable: bool) -> Self {
+        self.durable = durable;
+        self
+    }
+}
+
+impl AuditSink for FileSink {
+    fn write(&self, event: &AuditEvent) -> std::io::Result<()> {
+        let path = self
+            .dir
+            .join(format!("audit_{}.log", Utc::now().format("%Y%m%d")));
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", to_json(event)?)?;
+        if self.durable && AuditLevel::for_event(event) == AuditLevel::Durable {
+            file.sync_data()?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes JSON lines to stdout or stderr, e.g. for container log collectors.
+pub struct JsonStreamSink {
+    stderr: bool,
+}
+
+impl JsonStreamSink {
+    pub fn stdout() -> Self {
+        Self { stderr: false }
+    }
+
+    pub fn stderr() -> Self {
+        Self { stderr: true }
+    }
+}
+
+impl AuditSink for JsonStreamSink {
+    fn write(&self, event: &AuditEvent) -> std::io::Result<()> {
+        let line = to_json(event)?;
+        if self.stderr {
+            writeln!(std::io::stderr().lock(), "{}", line)
+        } else {
+            writeln!(std::io::stdout().lock(), "{}", line)
+        }
+    }
+
+    fn flush(&self) -> std::io::Result<()> {
+        if self.stderr {
+            std::io::stderr().flush()
+        } else {
+            std::io::stdout().flush()
+        }
+    }
+}
+
+/// Syslog facility `log audit` (RFC 5424, table 1).
+const FACILITY_LOG_AUDIT: u8 = 13;
+
+/// Sends RFC 5424 messages with the JSON event as the message body.
+///
+/// [`AuditLevel::Durable`] events are sent with severity `notice`, all others
+/// with `informational`.
+pub struct SyslogSink {
+    transport: SyslogTransport,
+    facility: u8,
+    app_name: String,
+    hostname: String,
+}
+
+enum SyslogTransport {
+    #[cfg(unix)]
+    Local(std::os::unix::net::UnixDatagram),
+    Udp(std::net::UdpSocket),
+}
+
+impl SyslogSink {
+    /// Connect to the local syslog daemon through `/dev/log`.
+    #[cfg(unix)]
+    pub fn local() -> std::io::Result<Self> {
+        Self::unix_socket("/dev/log")
+    }
+
+    /// Connect to a syslog daemon listening on a Unix datagram socket.
+    #[cfg(unix)]
+    pub fn unix_socket(path: impl AsRef<std::path::Path>) -> std::io::Result<Self> {
+        let socket = std::os::unix::net::UnixDatagram::unbound()?;
+        socket.connect(path)?;
+        Ok(Self::with_transport(SyslogTransport::Local(socket)))
+    }
+
+    /// Send to a remote collector over UDP, e.g. `"siem.internal:514"`.
+    pub fn udp(addr: impl std::net::ToSocketAddrs) -> std::io::Result<Self> {
+        let socket = std::net::UdpSocket::bind(("0.0.0.0", 0))?;
+        socket.connect(addr)?;
+        Ok(Self::with_transport(SyslogTransport::Udp(socket)))
+    }
+
+    fn with_transport(transport: SyslogTransport) -> Self {
+        Self {
+            transport,
+            facility: FACILITY_LOG_AUDIT,
+            app_name: "confers".to_string(),
+            hostname: std::env::var("HOSTNAME").unwrap_or_else(|_| "-".to_string()),
+        }
+    }
+
+    /// Syslog facility code (0-23). Defaults to 13, `log audit`.
+    pub fn facility(mut self, facility: u8) -> Self {
+        self.facility = facility.min(23);
+        self
+    }
+
+    /// APP-NAME field of each message. Defaults to `confers`.
+    pub fn app_name(mut self, app_name: impl Into<String>) -> Self {
+        self.app_name = app_name.into();
+        self
+    }
+
+    fn format(&self, event: &AuditEvent) -> std::io::Result<String> {
+        let severity = match AuditLevel::for_event(event) {
+            AuditLevel::Durable => 5,
+            AuditLevel::BestEffort => 6,
+        };
+        Ok(format!(
+            "<{}>1 {} {} {} {} - - {}",
+            u16::from(self.facility) * 8 + severity,
+            Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+            self.hostname,
+            self.app_name,
+            std::process::id(),
+            to_json(event)?
+        ))
+    }
+}
+
+impl AuditSink for SyslogSink {
+    fn write(&self, event: &AuditEvent) -> std::io::Result<()> {
+        let message = self.format(event)?;
+        match &self.transport {
+            #[cfg(unix)]
+            SyslogTransport::Local(socket) => socket.send(message.as_bytes()).map(drop),
+            SyslogTransport::Udp(socket) => socket.send(message.as_bytes()).map(drop),
+        }
+    }
+}
+
+/// Default number of events a [`WebhookSink`] queues before dropping new ones.
+#[cfg(feature = "remote")]
+const DEFAULT_WEBHOOK_QUEUE: usize = 1024;
+
+/// Default timeout of one webhook request.
+#[cfg(feature = "remote")]
+const DEFAULT_WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// POSTs each event as a JSON body to a URL.
+///
+/// Requests are sent from a background thread so a slow endpoint never delays
+/// configuration loading. When the queue is full, new events are dropped and
+/// `write` returns `WouldBlock`. The thread exits when the sink is dropped.
+#[cfg(feature = "remote")]
+pub struct WebhookSink {
+    sender: std::sync::mpsc::SyncSender<String>,
+}
+
+#[cfg(feature = "remote")]
+impl WebhookSink {
+    /// Send events to `url` with default settings.
+    pub fn new(url: impl Into<String>) -> crate::ConfigResult<Self> {
+        Self::builder().url(url).build()
+    }
+
+    pub fn builder() -> WebhookSinkBuilder {
+        WebhookSinkBuilder::default()
+    }
+}
+
+#[cfg(feature = "remote")]
+impl AuditSink for WebhookSink {
+    fn write(&self, event: &AuditEvent) -> std::io::Result<()> {
+        let body = to_json(event)?;
+        self.sender.try_send(body).map_err(|e| match e {
+            std::sync::mpsc::TrySendError::Full(_) => {
+                std::io::Error::new(std::io::ErrorKind::WouldBlock, "webhook queue full")
+            }
+            std::sync::mpsc::TrySendError::Disconnected(_) => {
+                std::io::Error::new(std::io::ErrorKind::BrokenPipe, "webhook thread stopped")
+            }
+        })
+    }
+}
+
+/// Builder for [`WebhookSink`].
+#[cfg(feature = "remote")]
+#[derive(Default)]
+pub struct WebhookSinkBuilder {
+    url: Option<String>,
+    headers: Vec<(String, String)>,
+    timeout: Option<std::time::Duration>,
+    queue_size: Option<usize>,
+    proxy: Option<crate::remote::ProxyConfig>,
+}
+
+#[cfg(feature = "remote")]
+impl WebhookSinkBuilder {
+    /// Endpoint receiving the events. Must be `http://` or `https://`.
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Add a request header, e.g. an `Authorization` token.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Timeout of one request. Defaults to 5 seconds.
+    pub fn timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Number of events queued before new ones are dropped. Defaults to 1024.
+    pub fn queue_size(mut self, size: usize) -> Self {
+        self.queue_size = Some(size);
+        self
+    }
+
+    /// Send events through `proxy` instead of the one named by the proxy
+    /// environment variables.
+    pub fn proxy(mut self, proxy: crate::remote::ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Build the sink and start its sender thread.
+    pub fn build(self) -> crate::ConfigResult<WebhookSink> {
+        use crate::error::ConfigError;
+
+        let invalid = |expected: &str, message: String| ConfigError::InvalidValue {
+            key: "webhook".to_string(),
+            expected_type: expected.to_string(),
+            message,
+        };
+        let url = self
+            .url
+            .ok_or_else(|| invalid("URL", "webhook URL is required".to_string()))?;
+        let parsed =
+            url::Url::parse(&url).map_err(|e| invalid("URL", format!("{}: {}", url, e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(invalid(
+                "http or https URL",
+                format!("unsupported scheme '{}'", parsed.scheme()),
+            ));
+        }
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        for (name, value) in &self.headers {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| invalid("header name", e.to_string()))?;
+            let value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|e| invalid("header value", e.to_string()))?;
+            headers.insert(name, value);
+        }
+        let client = reqwest::Client::builder()
+            .timeout(self.timeout.unwrap_or(DEFAULT_WEBHOOK_TIMEOUT))
+            .default_headers(headers);
+        let client = crate::remote::proxy::apply_proxy(client, self.proxy.as_ref())?
+            .build()
+            .map_err(|e| invalid("HTTP client", e.to_string()))?;
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| invalid("tokio runtime", e.to_string()))?;
+
+        let (sender, receiver) = std::sync::mpsc::sync_channel::<String>(
+            self.queue_size.unwrap_or(DEFAULT_WEBHOOK_QUEUE),
+        );
+        std::thread::Builder::new()
+            .name("confers-audit-webhook".to_string())
+            .spawn(move || {
+                while let Ok(body) = receiver.recv() {
+                    // Best effort: a failed delivery is not retried.
+                    let _ = runtime.block_on(async {
+                        client
+                            .post(parsed.clone())
+                            .header(reqwest::header::CONTENT_TYPE, "application/json")
+                            .body(body)
+                            .send()
+                            .await
+                    });
+                }
+            })
+            .map_err(|e| invalid("thread", e.to_string()))?;
+
+        Ok(WebhookSink { sender })
+    }
+}
+
+fn to_json(event: &AuditEvent) -> std::io::Result<String> {
+    serde_json::to_string(event).map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event() -> AuditEvent {
+        AuditEvent::KeyAccess {
+            key: "db.password".to_string(),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_file_sink_writes_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let sink = FileSink::new(dir.path()).durable(true);
+        sink.write(&event()).unwrap();
+        sink.write(&event()).unwrap();
+
+        let file = std::fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        let lines: Vec<serde_json::Value> = content
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["event"], "KeyAccess");
+        assert_eq!(lines[0]["key"], "db.password");
+    }
+
+    #[test]
+    fn test_syslog_sink_udp() {
+        let collector = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+        let sink = SyslogSink::udp(collector.local_addr().unwrap())
+            .unwrap()
+            .app_name("svc");
+        sink.write(&event()).unwrap();
+
+        let mut buf = [0u8; 2048];
+        let len = collector.recv(&mut buf).unwrap();
+        let message = std::str::from_utf8(&buf[..len]).unwrap();
+        // facility 13 (log audit) * 8 + severity 5 (notice)
+        assert!(message.starts_with("<109>1 "), "{message}");
+        assert!(message.contains(" svc "), "{message}");
+        assert!(
+            message.contains(" - - {\"event\":\"KeyAccess\""),
+            "{message}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_syslog_sink_unix_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("log.sock");
+        let daemon = std::os::unix::net::UnixDatagram::bind(&path).unwrap();
+        let sink = SyslogSink::unix_socket(&path).unwrap();
+        sink.write(&event()).unwrap();
+
+        let mut buf = [0u8; 2048];
+        let len = daemon.recv(&mut buf).unwrap();
+        assert!(std::str::from_utf8(&buf[..len])
+            .unwrap()
+            .contains("\"key\":\"db.password\""));
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_webhook_sink_posts_event() {
+        use std::io::Read;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 4096];
+            while !String::from_utf8_lossy(&request).contains("KeyAccess") {
+                let n = stream.read(&mut buf).unwrap();
+                if n == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buf[..n]);
+            }
+            let _ = stream.write_all(b"HTTP/1.1 204 No Content\r\nContent-Length: 0\r\n\r\n");
+            String::from_utf8(request).unwrap()
+        });
+
+        let sink = WebhookSink::builder()
+            .url(format!("http://127.0.0.1:{}/audit", port))
+            .header("Authorization", "Bearer t")
+            .build()
+            .unwrap();
+        sink.write(&event()).unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /audit "), "{request}");
+        assert!(request.to_lowercase().contains("authorization: bearer t"));
+        assert!(request.contains("\"event\":\"KeyAccess\""));
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_webhook_sink_rejects_other_schemes() {
+        assert!(WebhookSink::new("ftp://example.com/audit").is_err());
+        assert!(WebhookSink::builder().build().is_err());
+    }
+}
@@ -0,0 +1,212 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Deprecated key aliases for [`ConfigBuilder::with_alias`].
+//!
+//! Aliases are applied to the merged tree before deserialization: a value
+//! found under an old key is moved to its new key, unless the new key is set
+//! too, in which case the new key wins. Every old key found produces a
+//! [`WarningCode::DeprecatedKey`] warning naming the source that set it.
+//!
+//! [`ConfigBuilder::with_alias`]: super::builder::ConfigBuilder::with_alias
+
+use std::sync::Arc;
+
+use crate::error::{SourceWarning, WarningCode};
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// A renamed key that is still accepted under its old name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct KeyAlias {
+    /// Dotted path of the old key
+    pub old: String,
+    /// Dotted path the value is moved to
+    pub new: String,
+    /// Version the old key was deprecated in
+    pub deprecated_since: Option<String>,
+}
+
+impl KeyAlias {
+    /// Message shown when the old key is used.
+    pub(crate) fn message(&self) -> String {
+        match &self.deprecated_since {
+            Some(since) => format!(
+                "`{}` is deprecated since {}, use `{}` instead",
+                self.old, since, self.new
+            ),
+            None => format!("`{}` is deprecated, use `{}` instead", self.old, self.new),
+        }
+    }
+}
+
+/// Move values set under old keys to their new keys.
+///
+/// Returns each alias whose old key was set, with its warning.
+pub(crate) fn apply_aliases<'a>(
+    tree: &mut AnnotatedValue,
+    aliases: &'a [KeyAlias],
+) -> Vec<(&'a KeyAlias, SourceWarning)> {
+    let mut warnings = Vec::new();
+    for alias in aliases {
+        let old: Vec<&str> = alias.old.split('.').collect();
+        let Some(mut value) = take_path(tree, &old) else {
+            continue;
+        };
+        warnings.push((
+            alias,
+            SourceWarning {
+                message: alias.message(),
+                source: Some(value.source.as_str().to_string()),
+                code: WarningCode::DeprecatedKey,
+            },
+        ));
+
+        let new: Vec<&str> = alias.new.split('.').collect();
+        if !contains_path(tree, &new) {
+            value.path = Arc::from(alias.new.as_str());
+            insert_path(tree, &new, value);
+        }
+    }
+    warnings
+}
+
+fn contains_path(tree: &AnnotatedValue, path: &[&str]) -> bool {
+    let mut node = tree;
+    for key in path {
+        match node.inner.as_map().and_then(|map| map.get(*key)) {
+            Some(child) => node = child,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Remove the value at `path`, dropping maps it leaves empty.
+fn take_path(tree: &mut AnnotatedValue, path: &[&str]) -> Option<AnnotatedValue> {
+    let (first, rest) = path.split_first()?;
+    let ConfigValue::Map(map) = &mut tree.inner else {
+        return None;
+    };
+    if !map.contains_key(*first) {
+        return None;
+    }
+    let map = Arc::make_mut(map);
+    if rest.is_empty() {
+        return map.shift_remove(*first);
+    }
+    let child = map.get_mut(*first)?;
+    let taken = take_path(child, rest)?;
+    if child.inner.as_map().is_some_and(|m| m.is_empty()) {
+        map.shift_remove(*first);
+    }
+    Some(taken)
+}
+
+/// Insert `value` at `path`, creating intermediate maps as needed.
+fn insert_path(tree: &mut AnnotatedValue, path: &[&str], value: AnnotatedValue) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    if !tree.is_map() {
+        tree.inner = ConfigValue::Map(Arc::default());
+    }
+    let ConfigValue::Map(map) = &mut tree.inner else {
+        return;
+    };
+    let map = Arc::make_mut(map);
+    if rest.is_empty() {
+        map.insert(Arc::from(*first), value);
+        return;
+    }
+    let child_path = if tree.path.is_empty() {
+        first.to_string()
+    } else {
+        format!("{}.{}", tree.path, first)
+    };
+    let child = map.entry(Arc::from(*first)).or_insert_with(|| {
+        AnnotatedValue::new(
+            ConfigValue::Map(Arc::default()),
+            value.source.clone(),
+            child_path,
+        )
+    });
+    insert_path(child, rest, value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+
+    fn leaf(value: ConfigValue, source: &str) -> AnnotatedValue {
+        AnnotatedValue::new(value, SourceId::new(source), "")
+    }
+
+    fn tree(entries: Vec<(&str, AnnotatedValue)>) -> AnnotatedValue {
+        AnnotatedValue::new(ConfigValue::map(entries), SourceId::new("app.toml"), "")
+    }
+
+    fn alias(old: &str, new: &str) -> KeyAlias {
+        KeyAlias {
+            old: old.to_string(),
+            new: new.to_string(),
+            deprecated_since: Some("1.2".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_old_key_moves_to_new_key() {
+        let mut root = tree(vec![(
+            "server",
+            tree(vec![(
+                "hostname",
+                leaf(ConfigValue::String("db".into()), "app.toml"),
+            )]),
+        )]);
+        let aliases = [alias("server.hostname", "server.host")];
+        let warnings: Vec<_> = apply_aliases(&mut root, &aliases)
+            .into_iter()
+            .map(|(_, warning)| warning)
+            .collect();
+
+        let server = root.inner.as_map().unwrap().get("server").unwrap();
+        let server = server.inner.as_map().unwrap();
+        assert!(!server.contains_key("hostname"));
+        assert_eq!(server.get("host").unwrap().as_str(), Some("db"));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].code, WarningCode::DeprecatedKey);
+        assert_eq!(warnings[0].source.as_deref(), Some("app.toml"));
+        assert_eq!(
+            warnings[0].message,
+            "`server.hostname` is deprecated since 1.2, use `server.host` instead"
+        );
+    }
+
+    #[test]
+    fn test_new_key_wins_and_empty_parent_is_dropped() {
+        let mut root = tree(vec![
+            (
+                "legacy",
+                tree(vec![("port", leaf(ConfigValue::U64(1), "env"))]),
+            ),
+            ("port", leaf(ConfigValue::U64(2), "app.toml")),
+        ]);
+        let aliases = [alias("legacy.port", "port")];
+        let warnings = apply_aliases(&mut root, &aliases);
+
+        let map = root.inner.as_map().unwrap();
+        assert!(!map.contains_key("legacy"));
+        assert_eq!(map.get("port").unwrap().as_u64(), Some(2));
+        assert_eq!(warnings[0].0.old, "legacy.port");
+        assert_eq!(warnings[0].1.source.as_deref(), Some("env"));
+    }
+
+    #[test]
+    fn test_unused_alias_is_silent() {
+        let mut root = tree(vec![("port", leaf(ConfigValue::U64(2), "app.toml"))]);
+        assert!(apply_aliases(&mut root, &[alias("old_port", "port")]).is_empty());
+        assert!(apply_aliases(&mut root, &[alias("port.inner", "x")]).is_empty());
+    }
+}
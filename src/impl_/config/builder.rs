@@ -12,7 +12,7 @@ use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 #[cfg(any(
     feature = "remote",
@@ -24,18 +24,38 @@ use crate::impl_::lifecycle::LifecycleRegistry;
 
 #[cfg(feature = "config-bus")]
 use crate::bus::ConfigBus;
-use crate::error::{BuildResult, ConfigError, ConfigResult, SourceWarning, WarningCode};
+use crate::error::{BuildResult, ConfigError, ConfigResult, Redactor, SourceWarning, WarningCode};
+use crate::impl_::loader::PermissionPolicy;
 use crate::impl_::merger::MergeStrategy;
 #[cfg(feature = "snapshot")]
 use crate::impl_::snapshot::SnapshotConfig;
+#[cfg(feature = "async-validation")]
+use crate::impl_::validator::AsyncValidate;
 use crate::interface::{KeyProvider, MetricsBackend};
+#[cfg(feature = "remote")]
+use crate::remote::cache::RemoteCache;
+#[cfg(feature = "remote")]
+use crate::remote::provider::{FetchedSource, RemoteConfigSource};
+#[cfg(feature = "remote")]
+use crate::remote::resilience::{CircuitState, DegradationPolicy, RemoteConfig};
+#[cfg(feature = "remote")]
+use crate::remote::ssrf::SsrfPolicy;
+#[cfg(feature = "encryption")]
+use crate::secret::ConfigEncryption;
 use crate::types::NoOpMetrics;
-use crate::types::{AnnotatedValue, ConfigValue};
+use crate::types::{AnnotatedValue, ConfigValue, SourceKind};
 #[cfg(feature = "progressive-reload")]
 use crate::watcher::ReloadHealthCheck;
 
-use super::chain::SourceChainBuilder;
+use super::aliases::{self, KeyAlias};
+use super::chain::{order_priority, SourceChain, SourceChainBuilder};
+use super::health::{ProviderHealth, ProviderKind};
+use super::lazy::LazyConfig;
 use super::limits::ConfigLimits;
+#[cfg(feature = "remote")]
+use super::plan::PlannedProvider;
+use super::plan::{LoadPlan, PlannedSource};
+use super::unknown_keys::{self, EnvNaming};
 use crate::interface::Source;
 
 /// Reload strategy for hot reload.
@@ -71,10 +91,23 @@ pub struct ConfigBuilder<T> {
     limits: ConfigLimits,
     /// Encryption key provider (sync).
     key_provider: Option<Arc<dyn KeyProvider>>,
+    /// Decryption settings for `enc:` values.
+    #[cfg(feature = "encryption")]
+    encryption: Option<ConfigEncryption>,
+    /// age identity files used to decrypt `enc:AGE:` values.
+    #[cfg(feature = "age")]
+    age_identity_files: Vec<PathBuf>,
     /// Metrics backend.
     metrics: Arc<dyn MetricsBackend>,
     /// Whether to validate on load.
     validate: bool,
+    /// Whether keys no field consumes fail the build.
+    deny_unknown: bool,
+    /// Naming of the last prefixed env source, used to name variables in
+    /// unknown key errors.
+    env_naming: Option<EnvNaming>,
+    /// Keys whose values are redacted from build errors.
+    sensitive_keys: Vec<String>,
     /// Reload strategy.
     reload_strategy: ReloadStrategy,
     /// Build timeout.
@@ -90,6 +123,8 @@ pub struct ConfigBuilder<T> {
     accumulated_memory: HashMap<String, ConfigValue>,
     /// Memory source priority.
     memory_priority: u8,
+    /// Values set with `with_override`, merged above every source.
+    overrides: HashMap<String, ConfigValue>,
     /// Configuration bus for multi-instance sync.
     #[cfg(feature = "config-bus")]
     config_bus: Option<Arc<dyn ConfigBus>>,
@@ -99,6 +134,64 @@ pub struct ConfigBuilder<T> {
     /// Health check for reload operations.
     #[cfg(feature = "progressive-reload")]
     reload_health_check: Option<Arc<dyn ReloadHealthCheck>>,
+    /// User-defined remote backends, fetched by `build_async`.
+    #[cfg(feature = "remote")]
+    remote_providers: Vec<Box<dyn RemoteConfigSource>>,
+    /// Retry, timeout and circuit breaker settings for remote providers.
+    #[cfg(feature = "remote")]
+    remote_config: Option<RemoteConfig>,
+    /// Last-known-good cache of remote payloads.
+    #[cfg(feature = "remote")]
+    remote_cache: Option<RemoteCache>,
+    /// Endpoints remote providers may connect to.
+    #[cfg(feature = "remote")]
+    ssrf_policy: Option<SsrfPolicy>,
+    /// Pinned payload checksums, by provider name.
+    #[cfg(feature = "remote")]
+    remote_checksums: HashMap<String, String>,
+    /// Time remote providers may take before they are abandoned.
+    #[cfg(feature = "remote")]
+    load_deadline: Option<Duration>,
+    /// Audit writer for events recorded during build.
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<crate::audit::AuditWriter>>,
+    /// Correlation ID attached to the audit events of this build.
+    #[cfg(feature = "audit")]
+    correlation_id: Option<String>,
+    /// Prometheus metrics for provider health and validation failures.
+    #[cfg(feature = "prometheus")]
+    config_metrics: Option<Arc<crate::metrics::ConfigMetrics>>,
+    /// Validators run by `build_validated`.
+    #[cfg(feature = "async-validation")]
+    async_validators: Vec<Box<dyn AsyncValidate<T>>>,
+    /// Time each async validator may take.
+    #[cfg(feature = "async-validation")]
+    async_validation_timeout: Duration,
+    /// Whether async validation failures fail the build.
+    #[cfg(feature = "async-validation")]
+    async_validation_strict: bool,
+    /// Expands templates in the merged tree before deserialization.
+    #[cfg(feature = "interpolation")]
+    interpolator: Option<crate::interpolation::Interpolator>,
+    /// Resolves `ref+...://` secret references at the end of post-processing.
+    #[cfg(feature = "interpolation")]
+    secret_resolver: Option<crate::interpolation::Interpolator>,
+    /// Renamed keys still accepted under their old names.
+    aliases: Vec<KeyAlias>,
+    /// How config files with loose permissions are treated.
+    permission_policy: PermissionPolicy,
+    /// Key config files must be signed with.
+    #[cfg(feature = "signing")]
+    signature_key: Option<ed25519_dalek::VerifyingKey>,
+    /// Whether unsigned or tampered files fail the build.
+    #[cfg(feature = "signing")]
+    signature_strict: bool,
+    /// Deprecation and permission warnings raised by the last build.
+    warnings: Vec<SourceWarning>,
+    /// Locations searched by [`discover`](Self::discover), for [`plan`](Self::plan).
+    search_paths: Vec<PathBuf>,
+    /// Source kinds in merge priority order, highest first.
+    priority_order: Vec<SourceKind>,
     /// Type marker.
     _marker: PhantomData<T>,
     /// Lifecycle registry for managing component startup/shutdown.
@@ -111,6 +204,14 @@ pub struct ConfigBuilder<T> {
     lifecycle_registry: LifecycleRegistry,
 }
 
+/// Source name of the values set with
+/// [`with_override`](ConfigBuilder::with_override), as shown in provenance.
+pub const OVERRIDE_SOURCE: &str = "programmatic override";
+
+/// Default time each async validator may take.
+#[cfg(feature = "async-validation")]
+const DEFAULT_ASYNC_VALIDATION_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl<T> Default for ConfigBuilder<T> {
     fn default() -> Self {
         Self::new()
@@ -124,8 +225,15 @@ impl<T> ConfigBuilder<T> {
             chain_builder: SourceChainBuilder::new(),
             limits: ConfigLimits::default(),
             key_provider: None,
+            #[cfg(feature = "encryption")]
+            encryption: None,
+            #[cfg(feature = "age")]
+            age_identity_files: Vec::new(),
             metrics: Arc::new(NoOpMetrics),
             validate: true,
+            deny_unknown: false,
+            env_naming: None,
+            sensitive_keys: Vec::new(),
             reload_strategy: ReloadStrategy::default(),
             build_timeout: None,
             #[cfg(feature = "snapshot")]
@@ -134,6 +242,7 @@ impl<T> ConfigBuilder<T> {
             accumulated_defaults: HashMap::new(),
             accumulated_memory: HashMap::new(),
             memory_priority: 50,
+            overrides: HashMap::new(),
             #[cfg(feature = "config-bus")]
             config_bus: None,
             #[cfg(feature = "progressive-reload")]
@@ -147,6 +256,43 @@ impl<T> ConfigBuilder<T> {
                 feature = "watch"
             ))]
             lifecycle_registry: LifecycleRegistry::new(),
+            #[cfg(feature = "remote")]
+            remote_providers: Vec::new(),
+            #[cfg(feature = "remote")]
+            remote_config: None,
+            #[cfg(feature = "remote")]
+            remote_cache: None,
+            #[cfg(feature = "remote")]
+            ssrf_policy: None,
+            #[cfg(feature = "remote")]
+            remote_checksums: HashMap::new(),
+            #[cfg(feature = "remote")]
+            load_deadline: None,
+            #[cfg(feature = "audit")]
+            audit: None,
+            #[cfg(feature = "audit")]
+            correlation_id: None,
+            #[cfg(feature = "prometheus")]
+            config_metrics: None,
+            #[cfg(feature = "async-validation")]
+            async_validators: Vec::new(),
+            #[cfg(feature = "async-validation")]
+            async_validation_timeout: DEFAULT_ASYNC_VALIDATION_TIMEOUT,
+            #[cfg(feature = "async-validation")]
+            async_validation_strict: true,
+            #[cfg(feature = "interpolation")]
+            interpolator: None,
+            #[cfg(feature = "interpolation")]
+            secret_resolver: None,
+            aliases: Vec::new(),
+            permission_policy: PermissionPolicy::Off,
+            #[cfg(feature = "signing")]
+            signature_key: None,
+            #[cfg(feature = "signing")]
+            signature_strict: true,
+            warnings: Vec::new(),
+            search_paths: Vec::new(),
+            priority_order: Vec::new(),
             _marker: PhantomData,
         }
     }
@@ -158,6 +304,10 @@ impl<T> ConfigBuilder<T> {
     }
 
     /// Add a file source.
+    ///
+    /// `path` may be a glob pattern such as `configs/*.yaml`; matches are
+    /// merged in sorted path order, and a pattern matching nothing fails the
+    /// build. Use [`file_optional`](Self::file_optional) to allow zero matches.
     pub fn file(mut self, path: impl Into<PathBuf>) -> Self {
         self.chain_builder = self.chain_builder.file(path);
         self
@@ -169,6 +319,57 @@ impl<T> ConfigBuilder<T> {
         self
     }
 
+    /// Add the configuration files found for `app_name`.
+    ///
+    /// Every existing path from [`discovery_paths`](crate::discovery_paths)
+    /// is added as an optional file, so a project file overrides the user
+    /// file, which overrides the system file. The system and user locations
+    /// are absolute, so they are only loaded together with
+    /// [`allow_absolute_paths`](Self::allow_absolute_paths).
+    pub fn discover(mut self, app_name: &str) -> Self {
+        let paths = crate::impl_::loader::discovery_paths(app_name);
+        self.search_paths.extend(paths.iter().cloned());
+        paths
+            .into_iter()
+            .filter(|path| path.is_file())
+            .fold(self, |builder, path| builder.file_optional(path))
+    }
+
+    /// Add a drop-in directory (`conf.d`).
+    ///
+    /// Every file with a recognized extension is loaded in lexical file name
+    /// order, so `20-db.toml` overrides `10-base.toml`. The directory and its
+    /// files are included in [`watch_paths`](Self::watch_paths), letting a
+    /// watcher react to files being added or removed.
+    pub fn with_config_dir(mut self, path: impl Into<PathBuf>) -> Self {
+        self.chain_builder = self.chain_builder.config_dir(path);
+        self
+    }
+
+    /// Add TOML text as a source, merged like a file added at this point.
+    #[cfg(feature = "toml")]
+    pub fn with_toml_str(self, content: impl Into<String>) -> Self {
+        use super::source::StringSource;
+        use crate::impl_::loader::Format;
+        self.source(Box::new(StringSource::new(content, Format::Toml)))
+    }
+
+    /// Add YAML text as a source, merged like a file added at this point.
+    #[cfg(feature = "yaml")]
+    pub fn with_yaml_str(self, content: impl Into<String>) -> Self {
+        use super::source::StringSource;
+        use crate::impl_::loader::Format;
+        self.source(Box::new(StringSource::new(content, Format::Yaml)))
+    }
+
+    /// Add JSON text as a source, merged like a file added at this point.
+    #[cfg(feature = "json")]
+    pub fn with_json_str(self, content: impl Into<String>) -> Self {
+        use super::source::StringSource;
+        use crate::impl_::loader::Format;
+        self.source(Box::new(StringSource::new(content, Format::Json)))
+    }
+
     /// Add an environment source.
     pub fn env(mut self) -> Self {
         self.chain_builder = self.chain_builder.env();
@@ -177,10 +378,215 @@ impl<T> ConfigBuilder<T> {
 
     /// Add an environment source with prefix.
     pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        self.env_naming = Some(EnvNaming {
+            prefix: prefix.clone(),
+            separator: "_".to_string(),
+        });
         self.chain_builder = self.chain_builder.env_with_prefix(prefix);
         self
     }
 
+    /// Add an environment source with prefix whose variables separate nested
+    /// keys with `separator`.
+    ///
+    /// With `"__"`, `APP_SERVER__HTTP__PORT` sets `server.http.port` and
+    /// `APP_SERVER__MAX_CONNECTIONS` sets `server.max_connections`, so deep
+    /// structs can be overridden without listing every key. The prefix may
+    /// leave out its trailing `_`.
+    ///
+    /// ```rust
+    /// use confers::ConfigBuilder;
+    ///
+    /// # std::env::set_var("DOCAPP_SERVER__HTTP__PORT", "8080");
+    /// let config = ConfigBuilder::<serde_json::Value>::new()
+    ///     .env_with_separator("DOCAPP", "__")
+    ///     .build()?;
+    /// assert_eq!(config["server"]["http"]["port"], 8080);
+    /// # std::env::remove_var("DOCAPP_SERVER__HTTP__PORT");
+    /// # Ok::<(), confers::ConfigError>(())
+    /// ```
+    pub fn env_with_separator(
+        mut self,
+        prefix: impl Into<String>,
+        separator: impl Into<String>,
+    ) -> Self {
+        let (prefix, separator) = (prefix.into(), separator.into());
+        self.env_naming = Some(EnvNaming {
+            prefix: prefix.clone(),
+            separator: separator.clone(),
+        });
+        self.chain_builder = self.chain_builder.env_with_separator(prefix, separator);
+        self
+    }
+
+    /// Coerce environment variables to the types of `schema`, the JSON
+    /// Schema of the configuration, e.g. `AppConfig::json_schema()` from
+    /// `#[derive(ConfigSchema)]`.
+    ///
+    /// Applies to every env source of this builder. `APP_DEBUG=1` sets a
+    /// boolean `debug` to `true`, `APP_TAGS=a,b` sets a list of strings, and
+    /// `APP_CODE=0123` stays a string. A variable that does not fit its key
+    /// fails the build with [`ConfigError::InvalidValue`] naming the
+    /// variable:
+    ///
+    /// ```rust
+    /// use confers::{ConfigBuilder, ConfigError};
+    ///
+    /// let schema = serde_json::json!({
+    ///     "type": "object",
+    ///     "properties": {
+    ///         "debug": { "type": "boolean" },
+    ///         "tags": { "type": "array", "items": { "type": "string" } }
+    ///     }
+    /// });
+    /// # std::env::set_var("COERCEDOC_DEBUG", "yes");
+    /// # std::env::set_var("COERCEDOC_TAGS", "a,b");
+    /// let config = ConfigBuilder::<serde_json::Value>::new()
+    ///     .env_prefix("COERCEDOC_")
+    ///     .with_env_schema(schema.clone())
+    ///     .build()?;
+    /// assert_eq!(config["debug"], true);
+    /// assert_eq!(config["tags"], serde_json::json!(["a", "b"]));
+    ///
+    /// # std::env::set_var("COERCEDOC_DEBUG", "maybe");
+    /// let err = ConfigBuilder::<serde_json::Value>::new()
+    ///     .env_prefix("COERCEDOC_")
+    ///     .with_env_schema(schema)
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert!(err.to_string().contains("COERCEDOC_DEBUG"));
+    /// # std::env::remove_var("COERCEDOC_DEBUG");
+    /// # std::env::remove_var("COERCEDOC_TAGS");
+    /// # Ok::<(), ConfigError>(())
+    /// ```
+    #[cfg(feature = "schema")]
+    pub fn with_env_schema(mut self, schema: serde_json::Value) -> Self {
+        self.chain_builder = self.chain_builder.env_schema(schema);
+        self
+    }
+
+    /// Treat the dotted `keys`, and everything below them, as sensitive.
+    ///
+    /// Build errors are passed through a [`Redactor`]: values of sensitive
+    /// keys are replaced with `[REDACTED]` wherever a parser, deserializer or
+    /// provider quotes them, and so never reach error messages or the audit
+    /// log. Keys whose names look secret (see
+    /// [`is_sensitive_field_name`](crate::error::is_sensitive_field_name)) are
+    /// always sensitive; `#[derive(Config)]` passes its
+    /// `#[config(sensitive)]` fields here.
+    ///
+    /// ```rust
+    /// use confers::ConfigBuilder;
+    ///
+    /// #[derive(Debug, Default, serde::Deserialize)]
+    /// struct Db {
+    ///     dsn: u32,
+    /// }
+    ///
+    /// let err = ConfigBuilder::<Db>::new()
+    ///     .with_toml_str("dsn = \"postgres://admin:pw@db\"")
+    ///     .with_sensitive_keys(["dsn"])
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert!(!err.to_string().contains("admin:pw"));
+    /// ```
+    pub fn with_sensitive_keys<I, S>(mut self, keys: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.sensitive_keys.extend(keys.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reject keys that no field of `T` consumes (default: `false`).
+    ///
+    /// The build fails with [`ConfigError::UnknownKeys`], listing each key
+    /// with the file or env variable that set it and the closest field name.
+    /// Keys that only come from an unprefixed [`env`](Self::env) source are
+    /// not reported, since that source merges the whole process environment.
+    /// Keys captured by `#[serde(flatten)]` or untagged enums are not checked.
+    pub fn with_deny_unknown(mut self, deny: bool) -> Self {
+        self.deny_unknown = deny;
+        self
+    }
+
+    /// Validate the section at dotted `section` of each source while the
+    /// sources are loaded, instead of only after the whole configuration has
+    /// been merged and deserialized.
+    ///
+    /// The check runs on every source that sets keys under `section`, seeing
+    /// only that source's values, right after the source is parsed. The first
+    /// violation fails [`build`](Self::build) with
+    /// [`ConfigError::ValidationFailed`] naming the full key and the source
+    /// that set it, without loading later sources.
+    /// [`build_resilient`](Self::build_resilient) skips the offending source
+    /// instead. The field paths given to [`ValidationErrors::add`] are
+    /// relative to `section`:
+    ///
+    /// ```rust
+    /// use confers::{ConfigBuilder, ConfigError, ValidationErrors};
+    ///
+    /// let err = ConfigBuilder::<serde_json::Value>::new()
+    ///     .with_toml_str("[server]\nport = 80\n")
+    ///     .with_section_validator("server", |server| {
+    ///         let mut errors = ValidationErrors::new();
+    ///         let port = server.get_path("port").and_then(|port| port.as_i64());
+    ///         if matches!(port, Some(port) if port < 1024) {
+    ///             errors.add(&["port"], "must be an unprivileged port");
+    ///         }
+    ///         errors.into_result()
+    ///     })
+    ///     .build()
+    ///     .unwrap_err();
+    /// assert!(matches!(err, ConfigError::ValidationFailed { field, .. } if field == "server.port"));
+    /// ```
+    ///
+    /// [`ValidationErrors::add`]: crate::ValidationErrors::add
+    pub fn with_section_validator(
+        mut self,
+        section: impl Into<String>,
+        validator: impl Fn(&AnnotatedValue) -> Result<(), crate::ValidationErrors>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.chain_builder =
+            std::mem::take(&mut self.chain_builder).section_validator(section, Arc::new(validator));
+        self
+    }
+
+    /// Accept `old` as a deprecated name for the dotted key `new`.
+    ///
+    /// A value set under `old` is moved to `new`; when both are set, `new`
+    /// wins. Each use of `old` is reported as a [`WarningCode::DeprecatedKey`]
+    /// warning from [`build_resilient`](Self::build_resilient), a `tracing`
+    /// event (`tracing` feature) and an audit event (`audit` feature).
+    pub fn with_alias(self, old: impl Into<String>, new: impl Into<String>) -> Self {
+        self.push_alias(old.into(), new.into(), None)
+    }
+
+    /// Like [`with_alias`](Self::with_alias), naming the version `old` was
+    /// deprecated in.
+    pub fn with_deprecated_alias(
+        self,
+        old: impl Into<String>,
+        new: impl Into<String>,
+        since: impl Into<String>,
+    ) -> Self {
+        self.push_alias(old.into(), new.into(), Some(since.into()))
+    }
+
+    fn push_alias(mut self, old: String, new: String, deprecated_since: Option<String>) -> Self {
+        self.aliases.push(KeyAlias {
+            old,
+            new,
+            deprecated_since,
+        });
+        self
+    }
+
     /// Add default values.
     pub fn defaults(mut self, defaults: HashMap<String, ConfigValue>) -> Self {
         self.accumulated_defaults.extend(defaults);
@@ -200,6 +606,129 @@ impl<T> ConfigBuilder<T> {
         self
     }
 
+    /// Select the active profile.
+    ///
+    /// Every source may carry a top-level `profile` table with one section per
+    /// profile (`[profile.production]` in TOML, `profile: {production: ...}` in
+    /// YAML). The table is dropped from the result, and the selected section
+    /// is merged over the values of the source that defines it. Higher-priority
+    /// sources such as env vars still win over profile values.
+    pub fn with_profile(mut self, profile: impl Into<Arc<str>>) -> Self {
+        self.chain_builder = self.chain_builder.profile(profile);
+        self
+    }
+
+    /// Honour the `__include` directive in file sources.
+    ///
+    /// A file may list fragments under a top-level `__include` key, as a string
+    /// or an array of strings. Paths are relative to the including file and may
+    /// be glob patterns (`"features/*.yaml"`); fragments are merged in order and
+    /// the including file's own values win. Include cycles are an error.
+    pub fn with_includes(mut self, enabled: bool) -> Self {
+        self.chain_builder = self.chain_builder.includes(enabled);
+        self
+    }
+
+    /// Check the permissions and owner of every config file before loading.
+    ///
+    /// Under [`PermissionPolicy::Warn`] each file that other users can read
+    /// or that another user owns is reported as a
+    /// [`WarningCode::InsecurePermissions`] warning by
+    /// [`build_resilient`](Self::build_resilient); under
+    /// [`PermissionPolicy::Strict`] the build fails with
+    /// [`ConfigError::InsecurePermissions`].
+    pub fn with_permission_policy(mut self, policy: PermissionPolicy) -> Self {
+        self.permission_policy = policy;
+        self
+    }
+
+    /// Require every config file to carry a valid Ed25519 signature by `key`.
+    ///
+    /// Files are signed with `confers sign`, either with a detached
+    /// `<file>.sig` or an embedded footer comment (see
+    /// [`crate::signing`]). By default an unsigned or tampered file fails the
+    /// build with [`ConfigError::SignatureInvalid`]; see
+    /// [`signature_verification_strict`](Self::signature_verification_strict).
+    #[cfg(feature = "signing")]
+    pub fn with_signature_verification(mut self, key: ed25519_dalek::VerifyingKey) -> Self {
+        self.signature_key = Some(key);
+        self
+    }
+
+    /// When `false`, files failing
+    /// [signature verification](Self::with_signature_verification) are loaded
+    /// anyway and reported as [`WarningCode::UnverifiedSignature`] warnings
+    /// by [`build_resilient`](Self::build_resilient). Defaults to `true`.
+    #[cfg(feature = "signing")]
+    pub fn signature_verification_strict(mut self, strict: bool) -> Self {
+        self.signature_strict = strict;
+        self
+    }
+
+    /// Set `key` (a dotted path such as `database.pool.max`) to `value`,
+    /// overriding every source.
+    ///
+    /// Meant for integration tests and feature toggles set from code. The
+    /// values are merged last, even above a
+    /// [priority order](Self::with_priority_order), and show up in provenance
+    /// as [`OVERRIDE_SOURCE`]. Setting the same key again replaces the value.
+    ///
+    /// ```rust
+    /// use confers::ConfigBuilder;
+    ///
+    /// let config: serde_json::Value = ConfigBuilder::new()
+    ///     .with_json_str(r#"{"database": {"pool": {"max": 10}}}"#)
+    ///     .with_override("database.pool.max", 1)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(config["database"]["pool"]["max"], 1);
+    /// ```
+    pub fn with_override(mut self, key: impl Into<String>, value: impl Into<ConfigValue>) -> Self {
+        self.overrides.insert(key.into(), value.into());
+        self
+    }
+
+    /// Merge the source added last at `priority` instead of its own.
+    ///
+    /// Built-in priorities are 0 for defaults and files (later files win), and
+    /// 50 for environment variables, memory values and remote providers. To
+    /// let a file override environment variables:
+    ///
+    /// ```rust,no_run
+    /// # use confers::ConfigBuilder;
+    /// let builder = ConfigBuilder::<serde_json::Value>::new()
+    ///     .env_prefix("APP_")
+    ///     .file("app.toml")
+    ///     .priority(60);
+    /// ```
+    ///
+    /// Applies to sources added with [`file`](Self::file),
+    /// [`env`](Self::env), [`source`](Self::source) and similar methods, not
+    /// to [`default`](Self::default) or [`memory`](Self::memory) values,
+    /// whose priority is set with [`memory_priority`](Self::memory_priority).
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.chain_builder = self.chain_builder.priority(priority);
+        self
+    }
+
+    /// Merge sources by kind, in `order` from highest to lowest priority.
+    ///
+    /// ```rust
+    /// use confers::{ConfigBuilder, SourceKind};
+    ///
+    /// // Files override environment variables, which override defaults.
+    /// let builder = ConfigBuilder::<serde_json::Value>::new()
+    ///     .with_priority_order([SourceKind::File, SourceKind::Environment, SourceKind::Default]);
+    /// ```
+    ///
+    /// Listed kinds are merged above every kind that is not listed, and their
+    /// order replaces priorities set with [`priority`](Self::priority).
+    /// Sources of the same kind keep the order they were added in.
+    pub fn with_priority_order(mut self, order: impl IntoIterator<Item = SourceKind>) -> Self {
+        self.priority_order = order.into_iter().collect();
+        self
+    }
+
     /// Set memory source priority.
     pub fn memory_priority(mut self, priority: u8) -> Self {
         self.memory_priority = priority;
@@ -218,6 +747,28 @@ impl<T> ConfigBuilder<T> {
         self
     }
 
+    /// Decrypt `enc:` values with the given settings after merging.
+    ///
+    /// The same settings decrypt whole-file `*.enc` sources (e.g. `app.toml.enc`).
+    #[cfg(feature = "encryption")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "encryption")))]
+    pub fn encryption(mut self, encryption: ConfigEncryption) -> Self {
+        self.encryption = Some(encryption);
+        self
+    }
+
+    /// Add an age identity file used to decrypt `enc:AGE:` values.
+    ///
+    /// The file uses the `age-keygen` layout; it is read when the
+    /// configuration is built. Identity files replace any age cipher passed
+    /// through [`ConfigBuilder::encryption`].
+    #[cfg(feature = "age")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "age")))]
+    pub fn age_identity_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.age_identity_files.push(path.into());
+        self
+    }
+
     /// Set the metrics backend.
     pub fn metrics(mut self, metrics: Arc<dyn MetricsBackend>) -> Self {
         self.metrics = metrics;
@@ -268,6 +819,265 @@ impl<T> ConfigBuilder<T> {
         self
     }
 
+    /// Files to watch for changes: every file source, plus the fragments it
+    /// includes when [`with_includes`](Self::with_includes) is enabled, and
+    /// each [`with_config_dir`](Self::with_config_dir) directory with its files.
+    pub fn watch_paths(&self) -> Vec<PathBuf> {
+        self.chain_builder.get_watch_paths()
+    }
+
+    /// Report what a build would load, without loading it.
+    ///
+    /// Lists the locations searched by [`discover`](Self::discover), the
+    /// sources in merge order with the files each would read, the remote
+    /// providers [`build_async`](Self::build_async) would fetch and the names
+    /// of the environment variables that would apply. No remote fetch or
+    /// decryption is performed and file contents are not merged.
+    pub fn plan(&self) -> LoadPlan {
+        use super::source::{DefaultSource, MemorySource};
+
+        let chain = self.chain_builder.chain();
+        let mut sources: Vec<PlannedSource> = Vec::new();
+        if !self.accumulated_defaults.is_empty() {
+            sources.push(PlannedSource::from_source(&DefaultSource::with_defaults(
+                self.accumulated_defaults.clone(),
+            )));
+        }
+        sources.extend(
+            chain
+                .sources()
+                .iter()
+                .map(|source| PlannedSource::from_source(source.as_ref())),
+        );
+        if !self.accumulated_memory.is_empty() {
+            sources.push(PlannedSource::from_source(
+                &MemorySource::with_values(self.accumulated_memory.clone())
+                    .with_priority(self.memory_priority),
+            ));
+        }
+        for source in &mut sources {
+            if let Some(priority) = order_priority(&self.priority_order, source.kind) {
+                source.priority = priority;
+            }
+        }
+        // Same order as the merge: by priority, then by insertion.
+        sources.sort_by_key(|source| source.priority);
+        if !self.overrides.is_empty() {
+            sources.push(PlannedSource::from_source(&Self::override_source(
+                self.overrides.clone(),
+            )));
+        }
+
+        let mut env_vars: Vec<String> = chain
+            .sources()
+            .iter()
+            .flat_map(|source| source.env_var_names())
+            .collect();
+        env_vars.sort();
+        env_vars.dedup();
+
+        LoadPlan {
+            search_paths: self.search_paths.clone(),
+            sources,
+            #[cfg(feature = "remote")]
+            remote_providers: self
+                .remote_providers
+                .iter()
+                .map(|provider| PlannedProvider {
+                    name: provider.name().to_string(),
+                    priority: provider.priority(),
+                })
+                .collect(),
+            #[cfg(not(feature = "remote"))]
+            remote_providers: Vec::new(),
+            env_vars,
+            profile: chain.profile().map(str::to_string),
+        }
+    }
+
+    /// Check that the configured sources can be loaded, without building.
+    ///
+    /// Reports one entry per file a file source would read (readable and,
+    /// with strict [signature verification](Self::with_signature_verification),
+    /// validly signed), per key source and per file whose `enc:` values it
+    /// decrypts, and per remote provider (endpoints allowed by the
+    /// [SSRF policy](Self::with_ssrf_policy) and
+    /// [`health`](crate::remote::RemoteConfigSource::health) passing within
+    /// the [`RemoteConfig`](crate::RemoteConfig) timeout). A missing required
+    /// file is reported as unhealthy. Remote providers are checked
+    /// concurrently and nothing is merged, so this is cheap enough for
+    /// readiness probes.
+    pub async fn health_check(&mut self) -> Vec<ProviderHealth> {
+        let mut report = Vec::new();
+        #[cfg(feature = "encryption")]
+        let encryption = self.health_check_keys(&mut report);
+
+        let chain = self.chain_builder.chain();
+        for source in chain.sources() {
+            if source.source_kind() != SourceKind::File {
+                continue;
+            }
+            let files: Vec<PathBuf> = source
+                .watch_paths()
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect();
+            if files.is_empty() && !source.is_optional() {
+                let name = source
+                    .file_path()
+                    .map_or_else(|| source.name().to_string(), |p| p.display().to_string());
+                report.push(ProviderHealth::new(
+                    name,
+                    ProviderKind::File,
+                    Instant::now(),
+                    Err("not found".to_string()),
+                ));
+            }
+            for path in files {
+                let started = Instant::now();
+                let content =
+                    std::fs::read_to_string(&path).map_err(|e| format!("not readable: {}", e));
+                #[cfg(feature = "signing")]
+                let content = content.and_then(|content| match self.signature_key {
+                    Some(key) if self.signature_strict => {
+                        crate::impl_::signing::verify_config_file(&path, &key)
+                            .map(|()| content)
+                            .map_err(|e| e.to_string())
+                    }
+                    _ => Ok(content),
+                });
+                report.push(ProviderHealth::new(
+                    path.display().to_string(),
+                    ProviderKind::File,
+                    started,
+                    content
+                        .as_ref()
+                        .map(|_| "readable".to_string())
+                        .map_err(String::clone),
+                ));
+
+                #[cfg(feature = "encryption")]
+                if let (Some(encryption), Ok(content)) = (&encryption, &content) {
+                    let started = Instant::now();
+                    match encryption.decrypt_text(content) {
+                        Ok((_, 0)) => {}
+                        Ok((_, count)) => report.push(ProviderHealth::new(
+                            path.display().to_string(),
+                            ProviderKind::Key,
+                            started,
+                            Ok(format!("{} encrypted value(s) decrypted", count)),
+                        )),
+                        Err(e) => report.push(ProviderHealth::new(
+                            path.display().to_string(),
+                            ProviderKind::Key,
+                            started,
+                            Err(format!("cannot decrypt: {}", e)),
+                        )),
+                    }
+                }
+            }
+        }
+
+        #[cfg(feature = "remote")]
+        report.extend(self.health_check_remote().await);
+        report
+    }
+
+    /// Check the key provider and age identity files, returning the
+    /// decryption settings the file checks use.
+    #[cfg(feature = "encryption")]
+    fn health_check_keys(&self, report: &mut Vec<ProviderHealth>) -> Option<ConfigEncryption> {
+        if let Some(provider) = &self.key_provider {
+            let started = Instant::now();
+            report.push(ProviderHealth::new(
+                provider.provider_type(),
+                ProviderKind::Key,
+                started,
+                provider
+                    .get_key()
+                    .map(|_| "key available".to_string())
+                    .map_err(|e| e.to_string()),
+            ));
+        }
+
+        #[allow(unused_mut)]
+        let mut encryption = self.encryption.clone();
+        #[cfg(feature = "age")]
+        if !self.age_identity_files.is_empty() {
+            let mut age = crate::secret::AgeCipher::new();
+            for path in &self.age_identity_files {
+                let started = Instant::now();
+                report.push(ProviderHealth::new(
+                    path.display().to_string(),
+                    ProviderKind::Key,
+                    started,
+                    age.add_identity_file(path)
+                        .map(|()| "age identity loaded".to_string())
+                        .map_err(|e| e.to_string()),
+                ));
+            }
+            encryption = Some(encryption.unwrap_or_default().with_age(age));
+        }
+        encryption
+    }
+
+    /// Run the health checks of the remote providers concurrently.
+    #[cfg(feature = "remote")]
+    async fn health_check_remote(&mut self) -> Vec<ProviderHealth> {
+        let proxy = self.remote_config.as_ref().and_then(RemoteConfig::proxy);
+        let proxy_errors: Vec<Option<String>> = self
+            .remote_providers
+            .iter_mut()
+            .map(|provider| match &proxy {
+                Some(proxy) => provider.use_proxy(proxy).err().map(|e| e.to_string()),
+                None => None,
+            })
+            .collect();
+
+        let ssrf_policy = self.ssrf_policy.as_ref();
+        let remote_config = self.remote_config.as_ref();
+        let checks = self.remote_providers.iter().zip(proxy_errors).map(
+            |(provider, proxy_error)| async move {
+                let started = Instant::now();
+                let name = provider.name();
+                let result = async {
+                    if let Some(e) = proxy_error {
+                        return Err(e);
+                    }
+                    if let Some(policy) = ssrf_policy {
+                        for endpoint in provider.endpoints() {
+                            policy
+                                .check_endpoint(&endpoint, "http")
+                                .map_err(|e| e.to_string())?;
+                        }
+                    }
+                    let health = match remote_config.and_then(|c| c.timeout_for(name)) {
+                        Some(timeout) => tokio::time::timeout(timeout, provider.health())
+                            .await
+                            .unwrap_or(Err(ConfigError::Timeout {
+                                duration_ms: timeout.as_millis() as u64,
+                            })),
+                        None => provider.health().await,
+                    };
+                    health
+                        .map(|()| "healthy".to_string())
+                        .map_err(|e| e.to_string())
+                }
+                .await;
+                ProviderHealth::new(name, ProviderKind::Remote, started, result)
+            },
+        );
+        let report = futures_util::future::join_all(checks).await;
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.config_metrics {
+            for entry in &report {
+                metrics.set_provider_health(&entry.name, entry.healthy);
+            }
+        }
+        report
+    }
+
     /// Enable fail-fast mode (stop on first error).
     pub fn fail_fast(mut self, fail_fast: bool) -> Self {
         self.chain_builder = self.chain_builder.fail_fast(fail_fast);
@@ -307,6 +1117,216 @@ impl<T> ConfigBuilder<T> {
         self
     }
 
+    /// Register a validator run by [`build_validated`](Self::build_validated).
+    #[cfg(feature = "async-validation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-validation")))]
+    pub fn with_async_validator(mut self, validator: Box<dyn AsyncValidate<T>>) -> Self {
+        self.async_validators.push(validator);
+        self
+    }
+
+    /// Set how long each async validator may run (default: 5 seconds).
+    #[cfg(feature = "async-validation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-validation")))]
+    pub fn async_validation_timeout(mut self, timeout: Duration) -> Self {
+        self.async_validation_timeout = timeout;
+        self
+    }
+
+    /// Choose whether async validation failures fail the build (default: `true`).
+    ///
+    /// With `false`, failed and timed-out validators are reported as
+    /// [`WarningCode::AsyncValidationFailed`] warnings on a degraded
+    /// [`BuildResult`] instead, e.g. for starting up while a dependency is
+    /// still unavailable.
+    #[cfg(feature = "async-validation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-validation")))]
+    pub fn async_validation_strict(mut self, strict: bool) -> Self {
+        self.async_validation_strict = strict;
+        self
+    }
+
+    /// Register a custom remote backend.
+    ///
+    /// The provider is fetched by [`build_async`](Self::build_async) and its
+    /// tree merged at [`RemoteConfigSource::priority`]. The synchronous build
+    /// methods fail while providers are registered.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_remote_provider(mut self, provider: Box<dyn RemoteConfigSource>) -> Self {
+        self.remote_providers.push(provider);
+        self
+    }
+
+    /// Drop every remote provider registered so far, e.g. so a builder set up
+    /// by application code loads only local sources in tests.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn without_remote_providers(mut self) -> Self {
+        self.remote_providers.clear();
+        self
+    }
+
+    /// Apply retry, timeout and circuit breaker settings to remote providers.
+    ///
+    /// [`build_async`](Self::build_async) retries failed fetches with backoff
+    /// and skips providers whose circuit is open. Reuse the same
+    /// [`RemoteConfig`] across builds to keep breaker state. A proxy set
+    /// with [`RemoteConfig::with_proxy`] is passed to every provider.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_remote_config(mut self, config: RemoteConfig) -> Self {
+        self.remote_config = Some(config);
+        self
+    }
+
+    /// Cache remote payloads at `path` for offline startup.
+    ///
+    /// Every successful provider fetch in [`build_async`](Self::build_async)
+    /// is written to the cache. If a provider is unreachable, its cached
+    /// payload is used instead when it is at most `ttl` old, and an
+    /// `OfflineFallback` audit event marks the configuration as stale.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_remote_cache(mut self, path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        self.remote_cache = Some(RemoteCache::new(path, ttl));
+        self
+    }
+
+    /// Check the [endpoints](RemoteConfigSource::endpoints) of every remote
+    /// provider against `policy` before [`build_async`](Self::build_async)
+    /// fetches them. A violation fails the build whatever the degradation
+    /// policy.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_ssrf_policy(mut self, policy: SsrfPolicy) -> Self {
+        self.ssrf_policy = Some(policy);
+        self
+    }
+
+    /// Pin the payload of the remote provider named `provider` to
+    /// `checksum` (`sha256:<hex>`, see [`payload_checksum`]).
+    ///
+    /// [`build_async`](Self::build_async) checks the fetched tree, or the
+    /// cached payload it falls back to, before merging it. A mismatch fails
+    /// the build with [`ConfigError::ChecksumMismatch`] whatever the
+    /// degradation policy, so a compromised config server cannot change the
+    /// configuration.
+    ///
+    /// [`payload_checksum`]: crate::remote::payload_checksum
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_remote_checksum(
+        mut self,
+        provider: impl Into<String>,
+        checksum: impl Into<String>,
+    ) -> Self {
+        self.remote_checksums
+            .insert(provider.into(), checksum.into());
+        self
+    }
+
+    /// Bound the time [`build_async`](Self::build_async) waits for remote
+    /// providers.
+    ///
+    /// Providers are fetched concurrently against one deadline, `deadline`
+    /// after fetching starts, retries included. A provider that has not
+    /// answered by then is abandoned and handled like a failed fetch: its
+    /// cached payload is used if there is one, otherwise its
+    /// [`DegradationPolicy`] applies. With the default `Fail` policy the
+    /// build fails with [`ConfigError::Timeout`] instead of hanging. Each
+    /// abandoned provider is recorded as a `LoadDeadlineExceeded` audit event.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub fn with_load_deadline(mut self, deadline: Duration) -> Self {
+        self.load_deadline = Some(deadline);
+        self
+    }
+
+    /// Record audit events raised during build (e.g. remote cache fallbacks).
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_audit(mut self, audit: Arc<crate::audit::AuditWriter>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Send audit events raised during build to `sink`, in addition to the
+    /// sinks of a writer set with [`with_audit`](Self::with_audit).
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_audit_sink(mut self, sink: impl crate::audit::AuditSink + 'static) -> Self {
+        let sink: Arc<dyn crate::audit::AuditSink> = Arc::new(sink);
+        let writer = match self.audit.take() {
+            Some(audit) => audit.with_sink(sink),
+            None => crate::audit::AuditWriter::builder().build().with_sink(sink),
+        };
+        self.audit = Some(Arc::new(writer));
+        self
+    }
+
+    /// Tag the `ConfigLoaded` / `DecryptionFailed` audit events of this build
+    /// with `id` instead of a generated correlation ID, e.g. to tie them to a
+    /// deployment or request ID.
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Expand `${...}` templates in the merged configuration with
+    /// `interpolator`, after decryption and before deserialization and
+    /// validation.
+    ///
+    /// Besides environment variables and the interpolator's functions,
+    /// `${self:dotted.key}` refers to another key of the merged configuration.
+    /// See [`Interpolator::expand_templates`](crate::interpolation::Interpolator::expand_templates).
+    #[cfg(feature = "interpolation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "interpolation")))]
+    pub fn with_interpolation(mut self, interpolator: crate::interpolation::Interpolator) -> Self {
+        self.interpolator = Some(interpolator);
+        self
+    }
+
+    /// Resolve secret references such as `ref+file:///run/secrets/db_password`
+    /// or `ref+env://DB_PASSWORD` with `resolver`'s functions, as the last
+    /// step before deserialization and validation.
+    ///
+    /// Files then hold only the location of each secret, and a rotated secret
+    /// is picked up by the next build or reload without rewriting them.
+    /// Register a `vault` function on `resolver` to accept `ref+vault://`.
+    /// See [`Interpolator::resolve_secret_refs`](crate::interpolation::Interpolator::resolve_secret_refs).
+    #[cfg(feature = "interpolation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "interpolation")))]
+    pub fn with_secret_refs(mut self, resolver: crate::interpolation::Interpolator) -> Self {
+        self.secret_resolver = Some(resolver);
+        self
+    }
+
+    /// Reuse parsed files from `cache`, which outlives this builder.
+    ///
+    /// Share one cache between the builders of successive reloads: files
+    /// whose modification time and length are unchanged are not read again,
+    /// and the rest are only re-parsed when their content changed. Applies to
+    /// files added before and after this call. See
+    /// [`ParseCache`](super::ParseCache).
+    #[cfg(feature = "parse-cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parse-cache")))]
+    pub fn with_parse_cache(mut self, cache: Arc<super::ParseCache>) -> Self {
+        self.chain_builder = std::mem::take(&mut self.chain_builder).parse_cache(cache);
+        self
+    }
+
+    /// Publish remote provider health (`build_async`) and async validator
+    /// failures (`build_validated`) to `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+    pub fn with_config_metrics(mut self, metrics: Arc<crate::metrics::ConfigMetrics>) -> Self {
+        self.config_metrics = Some(metrics);
+        self
+    }
+
     /// Register a lifecycle component for managed startup/shutdown.
     #[cfg(any(
         feature = "remote",
@@ -322,6 +1342,286 @@ impl<T> ConfigBuilder<T> {
         self.lifecycle_registry.register(name, component);
         self
     }
+
+    /// Move the accumulated defaults/memory values into the chain and build it.
+    fn take_chain(&mut self) -> ConfigResult<super::chain::SourceChain> {
+        #[cfg(feature = "remote")]
+        if let Some(provider) = self.remote_providers.first() {
+            return Err(ConfigError::SourceChainError {
+                message: format!(
+                    "remote provider '{}' must be fetched with build_async()",
+                    provider.name()
+                ),
+                source_index: 0,
+            });
+        }
+
+        #[cfg(feature = "encryption")]
+        self.prepare_encryption()?;
+
+        let mut chain_builder = std::mem::take(&mut self.chain_builder);
+
+        if !self.accumulated_defaults.is_empty() {
+            chain_builder = chain_builder.defaults(std::mem::take(&mut self.accumulated_defaults));
+        }
+
+        if !self.accumulated_memory.is_empty() {
+            chain_builder = chain_builder.memory_with_priority(
+                std::mem::take(&mut self.accumulated_memory),
+                self.memory_priority,
+            );
+        }
+
+        if !self.priority_order.is_empty() {
+            chain_builder = chain_builder.priority_order(&self.priority_order);
+        }
+
+        if !self.overrides.is_empty() {
+            chain_builder = chain_builder.source(Box::new(Self::override_source(std::mem::take(
+                &mut self.overrides,
+            ))));
+        }
+
+        Ok(chain_builder.build())
+    }
+
+    /// The source holding [`with_override`](Self::with_override) values.
+    fn override_source(overrides: HashMap<String, ConfigValue>) -> super::source::MemorySource {
+        super::source::MemorySource::with_values(overrides)
+            .with_priority(u8::MAX)
+            .with_name(OVERRIDE_SOURCE)
+    }
+
+    /// Load deferred key material and hand the encryption settings to file
+    /// sources so `*.enc` files can be decrypted.
+    #[cfg(feature = "encryption")]
+    fn prepare_encryption(&mut self) -> ConfigResult<()> {
+        #[cfg(feature = "age")]
+        if !self.age_identity_files.is_empty() {
+            let mut age = crate::secret::AgeCipher::new();
+            for path in std::mem::take(&mut self.age_identity_files) {
+                age.add_identity_file(&path)?;
+            }
+            let encryption = self.encryption.take().unwrap_or_default();
+            self.encryption = Some(encryption.with_age(age));
+        }
+
+        if let Some(encryption) = &self.encryption {
+            let _ = self
+                .chain_builder
+                .encryption_slot()
+                .set(Arc::new(encryption.clone()));
+        }
+        Ok(())
+    }
+
+    /// Apply the [permission policy](Self::with_permission_policy) to the
+    /// files of the file sources in `chain`.
+    fn check_permissions(&mut self, chain: &SourceChain) -> ConfigResult<()> {
+        if self.permission_policy == PermissionPolicy::Off {
+            return Ok(());
+        }
+        for source in chain.sources() {
+            if source.source_kind() != SourceKind::File {
+                continue;
+            }
+            for path in source.watch_paths().into_iter().filter(|p| p.is_file()) {
+                let Some(problem) = self.permission_policy.check(&path, false)? else {
+                    continue;
+                };
+                #[cfg(feature = "tracing")]
+                tracing::warn!(path = %path.display(), "insecure config file: {}", problem);
+                self.warnings.push(SourceWarning {
+                    message: format!("{}: {}", path.display(), problem),
+                    source: Some(source.name().to_string()),
+                    code: WarningCode::InsecurePermissions,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the signatures of the files of the file sources in `chain`
+    /// against the [verification key](Self::with_signature_verification).
+    #[cfg(feature = "signing")]
+    fn verify_signatures(&mut self, chain: &SourceChain) -> ConfigResult<()> {
+        let Some(key) = self.signature_key else {
+            return Ok(());
+        };
+        for source in chain.sources() {
+            if source.source_kind() != SourceKind::File {
+                continue;
+            }
+            for path in source.watch_paths().into_iter().filter(|p| p.is_file()) {
+                let Err(err) = crate::impl_::signing::verify_config_file(&path, &key) else {
+                    continue;
+                };
+                if self.signature_strict {
+                    return Err(err);
+                }
+                #[cfg(feature = "tracing")]
+                tracing::warn!(path = %path.display(), "unverified config file: {}", err);
+                self.warnings.push(SourceWarning {
+                    message: err.to_string(),
+                    source: Some(source.name().to_string()),
+                    code: WarningCode::UnverifiedSignature,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Redactor for errors of this build.
+    fn redactor(&self) -> Redactor {
+        Redactor::new().with_sensitive_keys(self.sensitive_keys.iter().cloned())
+    }
+
+    /// `Some(env_naming)` when unknown keys are denied.
+    fn deny_unknown_prefix(&self) -> Option<Option<EnvNaming>> {
+        self.deny_unknown.then(|| self.env_naming.clone())
+    }
+
+    /// Transformations applied to the merged tree before deserialization.
+    fn post_process(&mut self, mut merged: AnnotatedValue) -> ConfigResult<AnnotatedValue> {
+        for (alias, warning) in aliases::apply_aliases(&mut merged, &self.aliases) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                key = %alias.old,
+                replacement = %alias.new,
+                since = alias.deprecated_since.as_deref(),
+                source = warning.source.as_deref(),
+                "deprecated configuration key"
+            );
+            #[cfg(feature = "audit")]
+            if let Some(audit) = &self.audit {
+                audit.log_deprecated_key(
+                    &alias.old,
+                    &alias.new,
+                    alias.deprecated_since.as_deref(),
+                    warning.source.as_deref().unwrap_or_default(),
+                );
+            }
+            #[cfg(not(any(feature = "tracing", feature = "audit")))]
+            let _ = alias;
+            self.warnings.push(warning);
+        }
+
+        #[cfg(feature = "encryption")]
+        let merged = self.decrypt_values(merged)?;
+
+        #[cfg(feature = "interpolation")]
+        let merged = match &self.interpolator {
+            Some(interpolator) => interpolator.expand_templates(&merged)?,
+            None => merged,
+        };
+
+        #[cfg(feature = "interpolation")]
+        let merged = match &self.secret_resolver {
+            Some(resolver) => resolver.resolve_secret_refs(merged)?,
+            None => merged,
+        };
+
+        Ok(merged)
+    }
+
+    /// Decrypt `enc:` values with the configured encryption settings.
+    #[cfg(feature = "encryption")]
+    fn decrypt_values(&mut self, merged: AnnotatedValue) -> ConfigResult<AnnotatedValue> {
+        #[cfg(feature = "otel")]
+        let _span = self
+            .encryption
+            .is_some()
+            .then(|| tracing::info_span!("confers.decrypt").entered());
+        let Some(encryption) = &self.encryption else {
+            return Ok(merged);
+        };
+        let (merged, stale) = encryption.decrypt_tree_reporting(merged)?;
+
+        // Values left on older key versions still decrypt; report them so
+        // they can be re-encrypted before those versions are retired.
+        let current_version = encryption.primary_version().unwrap_or_default();
+        for value in stale {
+            let message = if value.versioned {
+                format!(
+                    "'{}' is encrypted with key version {} (current: {})",
+                    value.path, value.version, current_version
+                )
+            } else {
+                format!(
+                    "'{}' has no key version header (decrypted with version {}, current: {})",
+                    value.path, value.version, current_version
+                )
+            };
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                key = %value.path,
+                version = value.version,
+                current_version,
+                "encrypted value uses an old key version"
+            );
+            #[cfg(feature = "audit")]
+            if let Some(audit) = &self.audit {
+                audit.log_stale_encrypted_value(
+                    &value.path,
+                    value.version,
+                    current_version,
+                    value.versioned,
+                );
+            }
+            self.warnings.push(SourceWarning {
+                message,
+                source: None,
+                code: WarningCode::StaleEncryptionKey,
+            });
+        }
+        Ok(merged)
+    }
+
+    /// Collect and merge every source, then post-process the tree. With
+    /// `resilient`, failing sources are skipped instead of aborting.
+    fn merge(&mut self, resilient: bool) -> ConfigResult<AnnotatedValue> {
+        #[cfg(feature = "otel")]
+        let _span = tracing::info_span!("confers.load", resilient).entered();
+        #[cfg(feature = "otel")]
+        let started = std::time::Instant::now();
+
+        let mut chain = self.take_chain()?;
+        self.check_permissions(&chain)?;
+        #[cfg(feature = "signing")]
+        self.verify_signatures(&chain)?;
+        if resilient {
+            chain = chain.fail_fast(false);
+        }
+        #[cfg(feature = "audit")]
+        let sources: Vec<String> = chain
+            .source_names()
+            .into_iter()
+            .map(str::to_string)
+            .collect();
+
+        let merged = chain
+            .collect()
+            .and_then(|merged| self.post_process(merged))
+            .map_err(|e| self.redactor().redact_error(e));
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &self.audit {
+            let correlation_id = self
+                .correlation_id
+                .clone()
+                .unwrap_or_else(crate::audit::new_correlation_id);
+            match &merged {
+                Ok(_) => audit.log_config_loaded(&correlation_id, sources),
+                Err(ConfigError::DecryptionFailed { message }) => {
+                    audit.log_decryption_failed(&correlation_id, message)
+                }
+                Err(_) => {}
+            }
+        }
+        #[cfg(feature = "otel")]
+        crate::impl_::otel::record_load(started.elapsed(), merged.is_ok());
+        merged
+    }
 }
 
 impl<T> ConfigBuilder<T>
@@ -338,50 +1638,48 @@ where
     /// Build the configuration and return the annotated value with location information.
     ///
     /// This method returns the raw AnnotatedValue which contains source location
-    /// information (line and column numbers) for each value.
+    /// information (line and column numbers) for each value. Decrypted values are
+    /// returned as plain strings; wrap the result in
+    /// [`ZeroizingValue`](crate::secret::ZeroizingValue) to wipe them on drop.
     pub fn build_annotated(self) -> ConfigResult<AnnotatedValue> {
         self.do_build_annotated()
     }
 
-    fn do_build(mut self) -> ConfigResult<T> {
-        if !self.accumulated_defaults.is_empty() {
-            self.chain_builder = self.chain_builder.defaults(self.accumulated_defaults);
-        }
-
-        if !self.accumulated_memory.is_empty() {
-            self.chain_builder = self
-                .chain_builder
-                .memory_with_priority(self.accumulated_memory, self.memory_priority);
-        }
-
-        let chain = self.chain_builder.build();
-        let merged = chain.collect()?;
-
-        let json = value_to_json(&merged);
-        let config: T = serde_json::from_value(json).map_err(|e| ConfigError::InvalidValue {
-            key: String::new(),
-            expected_type: std::any::type_name::<T>().to_string(),
-            message: e.to_string(),
-        })?;
-
-        Ok(config)
+    /// Build, then deserialize only the section at dotted `path` into `S`,
+    /// e.g. `build_section::<DatabaseConfig>("database")`.
+    ///
+    /// Every source is still loaded and merged, but the rest of the tree is
+    /// never converted or deserialized, and [`with_deny_unknown`](Self::with_deny_unknown)
+    /// checks only the section. A missing section is a
+    /// [`ConfigError::InvalidValue`] for `path`.
+    pub fn build_section<S: serde::de::DeserializeOwned>(self, path: &str) -> ConfigResult<S> {
+        let deny_unknown = self.deny_unknown_prefix();
+        let redactor = self.redactor();
+        let merged = self.do_build_annotated()?;
+        #[cfg(feature = "encryption")]
+        let merged = crate::secret::ZeroizingValue::new(merged);
+        deserialize_section(&merged, path, &deny_unknown, &redactor)
+    }
+
+    /// Build the merged tree now and defer deserializing `T` until
+    /// [`LazyConfig::get`] is first called. Sections can be read on their own
+    /// in the meantime with [`LazyConfig::section`].
+    pub fn build_lazy(self) -> ConfigResult<LazyConfig<T>> {
+        let deny_unknown = self.deny_unknown_prefix();
+        let redactor = self.redactor();
+        let merged = self.do_build_annotated()?;
+        Ok(LazyConfig::new(merged, deny_unknown, redactor))
+    }
+
+    fn do_build(self) -> ConfigResult<T> {
+        let deny_unknown = self.deny_unknown_prefix();
+        let redactor = self.redactor();
+        let merged = self.do_build_annotated()?;
+        deserialize_merged(merged, deny_unknown, &redactor)
     }
 
     fn do_build_annotated(mut self) -> ConfigResult<AnnotatedValue> {
-        if !self.accumulated_defaults.is_empty() {
-            self.chain_builder = self.chain_builder.defaults(self.accumulated_defaults);
-        }
-
-        if !self.accumulated_memory.is_empty() {
-            self.chain_builder = self
-                .chain_builder
-                .memory_with_priority(self.accumulated_memory, self.memory_priority);
-        }
-
-        let chain = self.chain_builder.build();
-        let merged = chain.collect()?;
-
-        Ok(merged)
+        self.merge(false)
     }
 
     /// Build with a fallback configuration.
@@ -403,31 +1701,307 @@ where
         }
     }
 
-    /// Build resiliently, collecting warnings instead of failing.
-    pub fn build_resilient(mut self) -> ConfigResult<BuildResult<T>> {
-        // Add accumulated defaults if any
-        if !self.accumulated_defaults.is_empty() {
-            self.chain_builder = self.chain_builder.defaults(self.accumulated_defaults);
+    /// Fetch every [remote provider](Self::with_remote_provider), then build.
+    ///
+    /// Providers are fetched concurrently and merged in registration order,
+    /// so a slow provider does not delay the others and the result does not
+    /// depend on which one answers first. With an audit writer, each fetch
+    /// is logged as a `ProviderFetched` event with its duration.
+    ///
+    /// With [`with_remote_config`](Self::with_remote_config), providers whose
+    /// circuit is open are skipped. With
+    /// [`with_remote_cache`](Self::with_remote_cache), an unreachable provider
+    /// falls back to its cached payload. Otherwise the provider's
+    /// [`DegradationPolicy`] applies; the default fails the build. In
+    /// [strict](RemoteConfig::strict) mode every unavailable provider fails
+    /// the build. Degraded providers are added to the build warnings and
+    /// logged through `tracing` and the audit writer; `build_validated`
+    /// returns the warnings with the configuration.
+    #[cfg(feature = "remote")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "remote")))]
+    pub async fn build_async(mut self) -> ConfigResult<T> {
+        let warnings = self.fetch_remote_providers().await?;
+        self.warnings.extend(warnings);
+        self.build()
+    }
+
+    /// Fetch the remote providers into the chain, returning a warning for
+    /// each provider replaced by a cached payload, skipped or replaced by
+    /// fallback values.
+    #[cfg(feature = "remote")]
+    async fn fetch_remote_providers(&mut self) -> ConfigResult<Vec<SourceWarning>> {
+        if let Some(policy) = &self.ssrf_policy {
+            for provider in &self.remote_providers {
+                for endpoint in provider.endpoints() {
+                    policy.check_endpoint(&endpoint, "http")?;
+                }
+            }
+        }
+        let remote_config = self.remote_config.as_ref();
+        let mut providers = std::mem::take(&mut self.remote_providers);
+        if let Some(proxy) = remote_config.and_then(RemoteConfig::proxy) {
+            for provider in &mut providers {
+                provider.use_proxy(&proxy)?;
+            }
         }
+        let load_deadline = self.load_deadline;
+        let deadline = load_deadline.map(|limit| (tokio::time::Instant::now() + limit, limit));
+        let fetches = providers.into_iter().map(|provider| {
+            let name = provider.name().to_string();
+            let priority = provider.priority();
+            #[cfg(feature = "otel")]
+            let span = tracing::info_span!("confers.remote.fetch", provider = %name);
+            let fetch = async move {
+                let started = std::time::Instant::now();
+                let attempt = async {
+                    match remote_config {
+                        Some(config) => {
+                            let provider = config.wrap(provider);
+                            let open = provider.circuit_state() == Some(CircuitState::Open);
+                            (provider.fetch().await, open)
+                        }
+                        None => (provider.fetch().await, false),
+                    }
+                };
+                let (result, skippable, missed) = match deadline {
+                    Some((deadline, limit)) => {
+                        match tokio::time::timeout_at(deadline, attempt).await {
+                            Ok((result, skippable)) => (result, skippable, false),
+                            Err(_) => {
+                                let timeout = ConfigError::Timeout {
+                                    duration_ms: limit.as_millis() as u64,
+                                };
+                                (Err(timeout), false, true)
+                            }
+                        }
+                    }
+                    None => {
+                        let (result, skippable) = attempt.await;
+                        (result, skippable, false)
+                    }
+                };
+                (name, priority, result, skippable, missed, started.elapsed())
+            };
+            #[cfg(feature = "otel")]
+            let fetch = tracing::Instrument::instrument(fetch, span);
+            fetch
+        });
+        // Providers are fetched concurrently but merged in registration
+        // order, so the result does not depend on which answers first.
+        let fetched = futures_util::future::join_all(fetches).await;
+
+        let mut warnings = Vec::new();
+        for (name, priority, result, skippable, missed, elapsed) in fetched {
+            #[cfg(feature = "audit")]
+            if let Some(audit) = &self.audit {
+                audit.log_provider_fetched(&name, elapsed, result.is_ok());
+                if let (true, Some(deadline)) = (missed, load_deadline) {
+                    audit.log_load_deadline_exceeded(&name, deadline);
+                }
+            }
+            #[cfg(not(feature = "audit"))]
+            let _ = (elapsed, missed);
+            #[cfg(feature = "tracing")]
+            if missed {
+                tracing::warn!(provider = %name, "remote provider missed the load deadline");
+            }
+            #[cfg(feature = "otel")]
+            if result.is_err() {
+                crate::impl_::otel::record_remote_fetch_failure(&name);
+            }
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &self.config_metrics {
+                metrics.set_provider_health(&name, result.is_ok());
+            }
 
-        // Add accumulated memory values if any
-        if !self.accumulated_memory.is_empty() {
-            self.chain_builder = self
-                .chain_builder
-                .memory_with_priority(self.accumulated_memory, self.memory_priority);
+            let checksum = self.remote_checksums.get(&name);
+            let verify = |value: &AnnotatedValue| match checksum {
+                Some(expected) => {
+                    crate::remote::checksum::verify_payload_checksum(&name, value, expected)
+                }
+                None => Ok(()),
+            };
+            let strict = self
+                .remote_config
+                .as_ref()
+                .is_some_and(RemoteConfig::is_strict);
+            let value = match result {
+                Ok(value) => {
+                    verify(&value)?;
+                    if let Some(cache) = &self.remote_cache {
+                        // A failed cache write only costs the offline fallback.
+                        let _ = cache.store(&name, &value);
+                    }
+                    value
+                }
+                Err(e) if strict => return Err(e),
+                Err(e) => match self.remote_cache.as_ref().and_then(|c| c.load(&name)) {
+                    Some(cached) => {
+                        verify(&cached.value)?;
+                        #[cfg(feature = "audit")]
+                        if let Some(audit) = &self.audit {
+                            audit.log_offline_fallback(
+                                &name,
+                                cached.fetched_at.into(),
+                                &e.to_string(),
+                            );
+                        }
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(provider = %name, "using cached remote payload: {}", e);
+                        warnings.push(SourceWarning {
+                            message: format!("Using cached payload for {}: {}", name, e),
+                            source: None,
+                            code: WarningCode::RemoteFallback,
+                        });
+                        cached.value
+                    }
+                    None => {
+                        let policy = match &self.remote_config {
+                            _ if skippable => &DegradationPolicy::Skip,
+                            Some(config) => config.degradation_for(&name),
+                            None => &DegradationPolicy::Fail,
+                        };
+                        let value = match policy {
+                            DegradationPolicy::Fail => return Err(e),
+                            DegradationPolicy::Skip => None,
+                            DegradationPolicy::Fallback(values) => Some(
+                                super::source::MemorySource::with_values(values.clone())
+                                    .with_name(format!("{} (fallback)", name))
+                                    .collect()?,
+                            ),
+                        };
+                        #[cfg(feature = "audit")]
+                        if let Some(audit) = &self.audit {
+                            audit.log_provider_degraded(&name, policy.name(), &e.to_string());
+                        }
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(
+                            provider = %name,
+                            policy = policy.name(),
+                            "remote provider degraded: {}",
+                            e
+                        );
+                        warnings.push(SourceWarning {
+                            message: format!(
+                                "Remote provider {} degraded ({}): {}",
+                                name,
+                                policy.name(),
+                                e
+                            ),
+                            source: None,
+                            code: WarningCode::RemoteFallback,
+                        });
+                        match value {
+                            Some(value) => value,
+                            None => continue,
+                        }
+                    }
+                },
+            };
+            // A staged rollout only delays running instances; a fresh one
+            // applies the current version, whatever its stanza says. An
+            // invalid stanza is still an error, as it is for the watcher.
+            let mut value = value;
+            crate::remote::Rollout::take(&mut value)?;
+            let source = FetchedSource::new(value, name, priority);
+            self.chain_builder = std::mem::take(&mut self.chain_builder).source(Box::new(source));
+        }
+        Ok(warnings)
+    }
+
+    /// Build, then run the [async validators](Self::with_async_validator).
+    ///
+    /// Validators run concurrently, each bounded by
+    /// [`async_validation_timeout`](Self::async_validation_timeout). In strict
+    /// mode the first failure is returned as
+    /// [`ConfigError::CrossFieldValidation`] and a timeout as
+    /// [`ConfigError::Timeout`]; otherwise they become warnings. Remote
+    /// providers are fetched first when the `remote` feature is enabled; a
+    /// provider that was degraded (see [`build_async`](Self::build_async))
+    /// also marks the result degraded.
+    #[cfg(feature = "async-validation")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "async-validation")))]
+    pub async fn build_validated(mut self) -> ConfigResult<BuildResult<T>> {
+        let validators = std::mem::take(&mut self.async_validators);
+        let timeout = self.async_validation_timeout;
+        let strict = self.async_validation_strict;
+        #[cfg(feature = "prometheus")]
+        let config_metrics = self.config_metrics.clone();
+
+        #[cfg(feature = "remote")]
+        let mut warnings = self.fetch_remote_providers().await?;
+        #[cfg(not(feature = "remote"))]
+        let mut warnings = Vec::new();
+        let degraded_providers = warnings.len();
+        let config = self.build()?;
+
+        let validate = futures_util::future::join_all(validators.iter().map(|validator| {
+            let config = &config;
+            async move {
+                let outcome = tokio::time::timeout(timeout, validator.validate(config)).await;
+                (validator.name(), outcome)
+            }
+        }));
+        #[cfg(feature = "otel")]
+        let validate = tracing::Instrument::instrument(
+            validate,
+            tracing::info_span!("confers.validate", validators = validators.len()),
+        );
+        let outcomes = validate.await;
+
+        for (name, outcome) in outcomes {
+            let error = match outcome {
+                Ok(Ok(())) => continue,
+                Ok(Err(errors)) => ConfigError::from(errors),
+                Err(_) => ConfigError::Timeout {
+                    duration_ms: timeout.as_millis() as u64,
+                },
+            };
+            #[cfg(feature = "otel")]
+            crate::impl_::otel::record_validation_failure(name);
+            #[cfg(feature = "prometheus")]
+            if let Some(metrics) = &config_metrics {
+                metrics.record_validation_failure(name);
+            }
+            if strict {
+                return Err(error);
+            }
+            warnings.push(SourceWarning {
+                message: format!("Async validator {} failed: {}", name, error),
+                source: None,
+                code: WarningCode::AsyncValidationFailed,
+            });
+        }
+
+        if warnings.is_empty() {
+            return Ok(BuildResult::ok(config));
+        }
+        let failed_validators = warnings.len() - degraded_providers;
+        let mut reasons = Vec::new();
+        if degraded_providers > 0 {
+            reasons.push(format!(
+                "{} remote provider(s) degraded",
+                degraded_providers
+            ));
+        }
+        if failed_validators > 0 {
+            reasons.push(format!("{} async validator(s) failed", failed_validators));
         }
+        let reason = reasons.join(", ");
+        Ok(BuildResult {
+            config,
+            warnings,
+            degraded: true,
+            degraded_reason: Some(reason),
+        })
+    }
 
-        let chain = self.chain_builder.fail_fast(false).build();
-        let merged = chain.collect()?;
-
-        let json = value_to_json(&merged);
-        let config: T = serde_json::from_value(json).map_err(|e| ConfigError::InvalidValue {
-            key: String::new(),
-            expected_type: std::any::type_name::<T>().to_string(),
-            message: e.to_string(),
-        })?;
+    /// Build resiliently, collecting warnings instead of failing.
+    pub fn build_resilient(mut self) -> ConfigResult<BuildResult<T>> {
+        let merged = self.merge(true)?;
+        let config: T = deserialize_merged(merged, self.deny_unknown_prefix(), &self.redactor())?;
 
-        Ok(BuildResult::ok(config))
+        Ok(BuildResult::with_warnings(config, self.warnings))
     }
 }
 
@@ -465,6 +2039,59 @@ where
     }
 }
 
+/// Deserialize the merged tree into `T`.
+///
+/// With `encryption` enabled the tree and its JSON form may hold decrypted
+/// plaintext, so both are zeroized once `T` has been built. `T` is read from
+/// a borrowed JSON tree so that every intermediate buffer can be wiped,
+/// leaving the only remaining copies in `T` itself.
+fn deserialize_merged<T: serde::de::DeserializeOwned>(
+    merged: AnnotatedValue,
+    deny_unknown: Option<Option<EnvNaming>>,
+    redactor: &Redactor,
+) -> ConfigResult<T> {
+    #[cfg(feature = "encryption")]
+    let merged = crate::secret::ZeroizingValue::new(merged);
+    deserialize_section(&merged, "", &deny_unknown, redactor)
+}
+
+/// Deserialize the sub-tree at dotted `path` of `merged` into `T`, leaving
+/// the rest of the tree untouched. Unknown keys are reported with their full
+/// path, and errors pass through `redactor` with the values of `merged`.
+pub(super) fn deserialize_section<T: serde::de::DeserializeOwned>(
+    merged: &AnnotatedValue,
+    path: &str,
+    deny_unknown: &Option<Option<EnvNaming>>,
+    redactor: &Redactor,
+) -> ConfigResult<T> {
+    let invalid = |message: String| ConfigError::InvalidValue {
+        key: path.to_string(),
+        expected_type: std::any::type_name::<T>().to_string(),
+        message,
+    };
+    let section = merged
+        .get_path(path)
+        .ok_or_else(|| invalid("section not found".to_string()))?;
+
+    #[allow(unused_mut)]
+    let mut json = value_to_json(section);
+    let config = match deny_unknown {
+        None => T::deserialize(&json).map_err(|e| invalid(e.to_string())),
+        Some(env_naming) => unknown_keys::deserialize_tracking(&json, path)
+            .map_err(|e| invalid(e.to_string()))
+            .and_then(|(config, unknown)| {
+                match unknown_keys::unknown_keys_error(unknown, merged, env_naming.as_ref()) {
+                    Some(e) => Err(e),
+                    None => Ok(config),
+                }
+            }),
+    };
+
+    #[cfg(feature = "encryption")]
+    crate::secret::zeroizing::zeroize_json(&mut json);
+    config.map_err(|e| redactor.clone().collect_values(merged).redact_error(e))
+}
+
 /// Convert an AnnotatedValue to a JSON value for deserialization.
 fn value_to_json(value: &AnnotatedValue) -> serde_json::Value {
     match &value.inner {
@@ -670,6 +2297,187 @@ mod tests {
         assert_eq!(config.name, "single");
     }
 
+    #[test]
+    fn test_builder_files_override_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "name = \"file\"\n").unwrap();
+
+        let config = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .default("name", ConfigValue::string("default"))
+            .default("port", ConfigValue::uint(80))
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "file");
+        assert_eq!(config.port, 80);
+    }
+
+    #[test]
+    fn test_builder_source_priority() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "port = 1\n").unwrap();
+        std::env::set_var("CONFERS_PRIORITY_TEST_PORT", "2");
+
+        let build = |file_priority: Option<u8>| {
+            let mut builder = ConfigBuilder::<TestConfig>::new()
+                .allow_absolute_paths()
+                .env_prefix("CONFERS_PRIORITY_TEST_")
+                .file(&path);
+            if let Some(priority) = file_priority {
+                builder = builder.priority(priority);
+            }
+            builder.build().unwrap().port
+        };
+        assert_eq!(build(None), 2);
+        assert_eq!(build(Some(60)), 1);
+        std::env::remove_var("CONFERS_PRIORITY_TEST_PORT");
+    }
+
+    #[test]
+    fn test_builder_priority_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "port = 1\n").unwrap();
+        std::env::set_var("CONFERS_ORDER_TEST_PORT", "2");
+
+        let builder = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .env_prefix("CONFERS_ORDER_TEST_")
+            .memory(HashMap::from([("port".to_string(), ConfigValue::uint(3))]))
+            .with_priority_order([SourceKind::File, SourceKind::Environment]);
+        let kinds: Vec<SourceKind> = builder.plan().sources.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                SourceKind::Memory,
+                SourceKind::Environment,
+                SourceKind::File
+            ]
+        );
+        assert_eq!(builder.build().unwrap().port, 1);
+        std::env::remove_var("CONFERS_ORDER_TEST_PORT");
+    }
+
+    #[test]
+    fn test_builder_with_override() {
+        std::env::set_var("CONFERS_OVERRIDE_TEST_PORT", "2");
+        let builder = ConfigBuilder::<TestConfig>::new()
+            .env_prefix("CONFERS_OVERRIDE_TEST_")
+            .memory_priority(u8::MAX)
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string("memory"),
+            )]))
+            .with_override("port", 3)
+            .with_override("name", "override")
+            .with_priority_order([SourceKind::Environment, SourceKind::Memory]);
+        assert_eq!(builder.plan().sources.last().unwrap().name, OVERRIDE_SOURCE);
+
+        let annotated = builder.build_annotated().unwrap();
+        std::env::remove_var("CONFERS_OVERRIDE_TEST_PORT");
+        let map = annotated.inner.as_map().unwrap();
+        assert_eq!(map["port"].as_i64(), Some(3));
+        assert_eq!(map["port"].source.as_str(), OVERRIDE_SOURCE);
+        assert_eq!(map["name"].as_str(), Some("override"));
+    }
+
+    #[cfg(all(feature = "interpolation", feature = "toml"))]
+    #[test]
+    fn test_builder_expands_self_references() {
+        let build = |toml: &str| {
+            ConfigBuilder::<serde_json::Value>::new()
+                .with_toml_str(toml)
+                .with_interpolation(crate::interpolation::Interpolator::new())
+                .build()
+        };
+
+        let config = build(
+            "url = \"http://${self:server.host}:${self:server.port}/\"\n\
+             [server]\nhost = \"example.com\"\nport = 8080\n",
+        )
+        .unwrap();
+        assert_eq!(config["url"], "http://example.com:8080/");
+
+        let err = build("a = \"${self:b}\"\nb = \"${self:a}\"\n").unwrap_err();
+        assert!(matches!(err, ConfigError::TemplateError { .. }), "{err}");
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_section_validator_aborts_at_first_invalid_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let invalid = dir.path().join("invalid.toml");
+        std::fs::write(&invalid, "name = \"app\"\n\n[server]\nport = 80\n").unwrap();
+        let builder = || {
+            ConfigBuilder::<serde_json::Value>::new()
+                .allow_absolute_paths()
+                .file(&invalid)
+                .with_toml_str("[server]\nport = 8080\n")
+                .with_section_validator("server", |server| {
+                    let mut errors = crate::ValidationErrors::new();
+                    if server.get_path("port").and_then(|port| port.as_i64()) == Some(80) {
+                        errors.add(&["port"], "must be an unprivileged port");
+                    }
+                    errors.into_result()
+                })
+        };
+
+        // The file after the invalid source is never loaded.
+        let err = builder()
+            .file(dir.path().join("missing.toml"))
+            .build()
+            .unwrap_err();
+        match err {
+            ConfigError::ValidationFailed { field, message, .. } => {
+                assert_eq!(field, "server.port");
+                assert!(message.contains("invalid.toml"), "{message}");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+
+        let result = builder().build_resilient().unwrap();
+        assert_eq!(result.config["server"]["port"], 8080);
+    }
+
+    #[cfg(all(feature = "interpolation", feature = "toml"))]
+    #[test]
+    fn test_builder_resolves_secret_refs() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("db_password"), "hunter2\n").unwrap();
+        std::env::set_var("CONFERS_SECRET_REF_TEST_TOKEN", "t0ken");
+        let build = |toml: &str| {
+            let resolver = crate::interpolation::Interpolator::new().with_function(
+                "file",
+                crate::interpolation::FileFunction::new([dir.path()]),
+            );
+            ConfigBuilder::<serde_json::Value>::new()
+                .with_toml_str(toml)
+                .with_secret_refs(resolver)
+                .build()
+        };
+
+        let config = build(&format!(
+            "password = \"ref+file://{}\"\ntoken = \"ref+env://CONFERS_SECRET_REF_TEST_TOKEN\"\n\
+             note = \"ref+ is kept\"\n",
+            dir.path().join("db_password").display()
+        ))
+        .unwrap();
+        std::env::remove_var("CONFERS_SECRET_REF_TEST_TOKEN");
+        assert_eq!(config["password"], "hunter2");
+        assert_eq!(config["token"], "t0ken");
+        assert_eq!(config["note"], "ref+ is kept");
+
+        let err = build("[db]\npassword = \"ref+vault://secret/db#password\"\n").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::SecretReferenceError { key, .. } if key == "db.password"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_builder_memory_priority_setter() {
         let config = ConfigBuilder::<TestConfig>::new()
@@ -870,6 +2678,111 @@ mod tests {
         assert!(matches!(cloned, ReloadStrategy::Immediate));
     }
 
+    #[cfg(feature = "async-validation")]
+    mod async_validation {
+        use super::*;
+        use crate::error::ValidationErrors;
+
+        struct PortCheck {
+            delay: Duration,
+        }
+
+        #[async_trait::async_trait]
+        impl AsyncValidate<TestConfig> for PortCheck {
+            fn name(&self) -> &str {
+                "port-check"
+            }
+
+            async fn validate(&self, config: &TestConfig) -> Result<(), ValidationErrors> {
+                tokio::time::sleep(self.delay).await;
+                let mut errors = ValidationErrors::new();
+                if config.port == 0 {
+                    errors.add(&["port"], "nothing listens on port 0");
+                }
+                errors.into_result()
+            }
+        }
+
+        fn builder(port: u64, delay: Duration) -> ConfigBuilder<TestConfig> {
+            ConfigBuilder::<TestConfig>::new()
+                .default("port", ConfigValue::uint(port))
+                .with_async_validator(Box::new(PortCheck { delay }))
+        }
+
+        #[tokio::test]
+        async fn test_build_validated_passes() {
+            let result = builder(8080, Duration::ZERO)
+                .build_validated()
+                .await
+                .unwrap();
+            assert_eq!(result.config.port, 8080);
+            assert!(!result.degraded);
+        }
+
+        #[tokio::test]
+        async fn test_build_validated_strict_failure() {
+            let err = builder(0, Duration::ZERO)
+                .build_validated()
+                .await
+                .unwrap_err();
+            match err {
+                ConfigError::CrossFieldValidation { errors } => {
+                    assert_eq!(errors.violations()[0].fields, ["port"]);
+                }
+                other => panic!("unexpected error: {other:?}"),
+            }
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn test_build_validated_timeout() {
+            let err = builder(8080, Duration::from_secs(60))
+                .async_validation_timeout(Duration::from_secs(1))
+                .build_validated()
+                .await
+                .unwrap_err();
+            assert!(matches!(err, ConfigError::Timeout { duration_ms: 1000 }));
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn test_build_validated_non_strict_warns() {
+            let result = builder(0, Duration::from_secs(60))
+                .with_async_validator(Box::new(PortCheck {
+                    delay: Duration::ZERO,
+                }))
+                .async_validation_timeout(Duration::from_secs(1))
+                .async_validation_strict(false)
+                .build_validated()
+                .await
+                .unwrap();
+            assert!(result.degraded);
+            assert_eq!(result.warnings.len(), 2);
+            assert!(result
+                .warnings
+                .iter()
+                .all(|w| w.code == WarningCode::AsyncValidationFailed));
+            assert!(result.warnings[0].message.contains("port-check"));
+        }
+
+        #[cfg(feature = "prometheus")]
+        #[tokio::test]
+        async fn test_build_validated_counts_failures() {
+            let registry = prometheus::Registry::new();
+            let metrics = Arc::new(crate::metrics::ConfigMetrics::register(&registry).unwrap());
+            builder(0, Duration::ZERO)
+                .with_config_metrics(metrics)
+                .async_validation_strict(false)
+                .build_validated()
+                .await
+                .unwrap();
+
+            let body = crate::metrics::encode_text(&registry).unwrap();
+            assert!(
+                body.contains("confers_validation_failures_total{validator=\"port-check\"} 1"),
+                "{body}"
+            );
+        }
+    }
+
     #[cfg(feature = "config-bus")]
     #[test]
     fn test_builder_config_bus_setter() {
@@ -924,4 +2837,528 @@ mod tests {
         let _builder: ConfigBuilder<TestConfig> =
             ConfigBuilder::new().register_lifecycle("dummy", Arc::new(DummyLifecycle));
     }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_builder_with_profile_overlays_file_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(
+            &path,
+            "name = \"base\"\nport = 8080\n\n[profile.production]\nport = 443\n\n[profile.staging]\nname = \"staging\"\n",
+        )
+        .unwrap();
+
+        let build = |profile: &str| {
+            ConfigBuilder::<TestConfig>::new()
+                .allow_absolute_paths()
+                .file(&path)
+                .with_profile(profile)
+                .build()
+                .unwrap()
+        };
+        assert_eq!(
+            build("production"),
+            TestConfig {
+                name: "base".into(),
+                port: 443
+            }
+        );
+        assert_eq!(
+            build("staging"),
+            TestConfig {
+                name: "staging".into(),
+                port: 8080
+            }
+        );
+        assert_eq!(
+            build("dev"),
+            TestConfig {
+                name: "base".into(),
+                port: 8080
+            }
+        );
+
+        // Higher-priority sources override profile values.
+        let config = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .memory(HashMap::from([(
+                "port".to_string(),
+                ConfigValue::uint(9000),
+            )]))
+            .with_profile("production")
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 9000);
+
+        let annotated = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .with_profile("production")
+            .build_annotated()
+            .unwrap();
+        let ConfigValue::Map(map) = &annotated.inner else {
+            panic!("expected map");
+        };
+        assert!(!map.contains_key("profile"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_builder_with_includes_merges_and_watches_fragments() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("conf.d")).unwrap();
+        std::fs::write(
+            dir.path().join("conf.d/10-name.toml"),
+            "name = \"included\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("conf.d/20-port.toml"), "port = 1\n").unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "__include = \"conf.d/*.toml\"\nport = 8080\n").unwrap();
+
+        let builder = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .with_includes(true);
+        assert_eq!(builder.watch_paths().len(), 3);
+        assert_eq!(
+            builder.build().unwrap(),
+            TestConfig {
+                name: "included".into(),
+                port: 8080
+            }
+        );
+
+        // Without the switch the directive is an ordinary key.
+        let builder = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .file(&path);
+        assert_eq!(builder.watch_paths(), vec![path.clone()]);
+        assert_eq!(builder.build().unwrap().name, "");
+    }
+
+    #[cfg(all(feature = "toml", feature = "json"))]
+    #[test]
+    fn test_builder_string_sources_merge_in_order() {
+        let config = ConfigBuilder::<TestConfig>::new()
+            .with_toml_str("name = \"toml\"\nport = 1\n")
+            .with_json_str(r#"{"port": 2}"#)
+            .build()
+            .unwrap();
+        assert_eq!(
+            config,
+            TestConfig {
+                name: "toml".into(),
+                port: 2
+            }
+        );
+
+        // Higher-priority sources still win over string sources.
+        let config = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([("port".to_string(), ConfigValue::uint(3))]))
+            .with_json_str(r#"{"port": 2}"#)
+            .build()
+            .unwrap();
+        assert_eq!(config.port, 3);
+
+        let err = ConfigBuilder::<TestConfig>::new()
+            .with_toml_str("port = ")
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::ParseError { .. }), "{err}");
+    }
+
+    #[cfg(all(feature = "toml", feature = "json"))]
+    #[test]
+    fn test_builder_with_config_dir_merges_in_lexical_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let conf_d = dir.path().join("conf.d");
+        std::fs::create_dir(&conf_d).unwrap();
+        std::fs::write(conf_d.join("20-port.json"), r#"{"port": 2}"#).unwrap();
+        std::fs::write(conf_d.join("10-base.toml"), "name = \"base\"\nport = 1\n").unwrap();
+        std::fs::write(conf_d.join("README.md"), "not config").unwrap();
+
+        let builder = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .with_config_dir(&conf_d);
+        assert_eq!(builder.watch_paths().len(), 3);
+        assert_eq!(
+            builder.build().unwrap(),
+            TestConfig {
+                name: "base".into(),
+                port: 2
+            }
+        );
+
+        let missing = ConfigBuilder::<TestConfig>::new()
+            .allow_absolute_paths()
+            .with_config_dir(dir.path().join("missing"))
+            .build();
+        assert!(matches!(missing, Err(ConfigError::FileNotFound { .. })));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_builder_decrypts_encrypted_values() {
+        use crate::secret::{CipherAlgorithm, SecretBytes};
+
+        let key = || SecretBytes::new(b"0123456789abcdef0123456789abcdef".to_vec());
+        let secret = ConfigEncryption::new()
+            .with_key(key())
+            .encrypt(CipherAlgorithm::XChaCha20, "hidden")
+            .unwrap();
+
+        let config = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string(secret),
+            )]))
+            .encryption(ConfigEncryption::new().with_key(key()))
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "hidden");
+    }
+
+    #[cfg(feature = "encryption")]
+    #[tokio::test]
+    async fn test_health_check_reports_files_and_keys() {
+        use crate::secret::{CipherAlgorithm, SecretBytes};
+
+        let key = |byte: u8| SecretBytes::new(vec![byte; 32]);
+        let secret = ConfigEncryption::new()
+            .with_key(key(1))
+            .encrypt(CipherAlgorithm::XChaCha20, "hidden")
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, format!("name = \"{}\"\n", secret)).unwrap();
+        let builder = |byte| {
+            ConfigBuilder::<TestConfig>::new()
+                .allow_absolute_paths()
+                .file(&path)
+                .file(dir.path().join("missing.toml"))
+                .file_optional(dir.path().join("optional.toml"))
+                .encryption(ConfigEncryption::new().with_key(key(byte)))
+        };
+
+        let report = builder(1).health_check().await;
+        let summary: Vec<_> = report
+            .iter()
+            .map(|entry| (entry.kind, entry.healthy))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ProviderKind::File, true),
+                (ProviderKind::Key, true),
+                (ProviderKind::File, false)
+            ]
+        );
+        assert_eq!(report[0].name, path.display().to_string());
+        assert_eq!(report[1].message, "1 encrypted value(s) decrypted");
+        assert_eq!(report[2].message, "not found");
+
+        let report = builder(2).health_check().await;
+        assert!(!report[1].healthy);
+        assert!(report[1].message.starts_with("cannot decrypt"));
+        assert!(!report[1].message.contains("hidden"));
+    }
+
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_builder_reports_values_on_old_key_versions() {
+        use crate::secret::{CipherAlgorithm, SecretBytes};
+
+        let key = |byte: u8| SecretBytes::new(vec![byte; 32]);
+        let old = ConfigEncryption::new()
+            .with_key_versions(vec![(1, key(1))])
+            .encrypt(CipherAlgorithm::Aes256Gcm, "old")
+            .unwrap();
+        assert!(old.starts_with("enc:v1:AES256GCM:"));
+
+        let result = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string(old),
+            )]))
+            .encryption(ConfigEncryption::new().with_key_versions(vec![(2, key(2)), (1, key(1))]))
+            .build_resilient()
+            .unwrap();
+        assert_eq!(result.config.name, "old");
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, WarningCode::StaleEncryptionKey);
+        assert_eq!(
+            result.warnings[0].message,
+            "'name' is encrypted with key version 1 (current: 2)"
+        );
+    }
+
+    #[cfg(feature = "age")]
+    #[test]
+    fn test_builder_age_identity_file() {
+        use crate::secret::{AgeCipher, CipherAlgorithm};
+        use age::secrecy::ExposeSecret;
+
+        let identity = age::x25519::Identity::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let identity_path = dir.path().join("identity.txt");
+        std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+
+        let secret = ConfigEncryption::new()
+            .with_age(
+                AgeCipher::new()
+                    .with_recipient(&identity.to_public().to_string())
+                    .unwrap(),
+            )
+            .encrypt(CipherAlgorithm::Age, "from-age")
+            .unwrap();
+
+        let config = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string(secret),
+            )]))
+            .age_identity_file(&identity_path)
+            .build()
+            .unwrap();
+        assert_eq!(config.name, "from-age");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_permission_policy_flags_readable_config_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        std::fs::write(&path, "name = \"file\"\n").unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let builder = |policy| {
+            ConfigBuilder::<TestConfig>::new()
+                .allow_absolute_paths()
+                .file(&path)
+                .with_permission_policy(policy)
+        };
+
+        let result = builder(PermissionPolicy::Warn).build_resilient().unwrap();
+        assert_eq!(result.config.name, "file");
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, WarningCode::InsecurePermissions);
+        assert!(result.warnings[0].message.contains("mode 644"));
+
+        let err = builder(PermissionPolicy::Strict).build().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InsecurePermissions { .. }),
+            "got {:?}",
+            err
+        );
+
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        assert_eq!(
+            builder(PermissionPolicy::Strict).build().unwrap().name,
+            "file"
+        );
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_signature_verification_rejects_tampered_files() {
+        use crate::signing::{embed_signature, SigningKey};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let signed = embed_signature("name = \"file\"\n", &key);
+        std::fs::write(&path, &signed).unwrap();
+        let builder = |strict| {
+            ConfigBuilder::<TestConfig>::new()
+                .allow_absolute_paths()
+                .file(&path)
+                .with_signature_verification(key.verifying_key())
+                .signature_verification_strict(strict)
+        };
+
+        assert_eq!(builder(true).build().unwrap().name, "file");
+
+        std::fs::write(&path, signed.replace("file", "evil")).unwrap();
+        let err = builder(true).build().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::SignatureInvalid { .. }),
+            "got {:?}",
+            err
+        );
+
+        let result = builder(false).build_resilient().unwrap();
+        assert_eq!(result.config.name, "evil");
+        assert_eq!(result.warnings.len(), 1);
+        assert_eq!(result.warnings[0].code, WarningCode::UnverifiedSignature);
+        assert!(result.warnings[0].message.contains("does not match"));
+    }
+
+    #[test]
+    fn test_build_resilient_reports_deprecated_alias() {
+        let result = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([
+                ("app_name".to_string(), ConfigValue::string("legacy")),
+                ("listen_port".to_string(), ConfigValue::uint(1)),
+            ]))
+            .with_deprecated_alias("app_name", "name", "1.2")
+            .with_alias("listen_port", "port")
+            .build_resilient()
+            .unwrap();
+
+        assert_eq!(
+            result.config,
+            TestConfig {
+                name: "legacy".into(),
+                port: 1
+            }
+        );
+        let messages: Vec<_> = result.warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "`app_name` is deprecated since 1.2, use `name` instead",
+                "`listen_port` is deprecated, use `port` instead"
+            ]
+        );
+        assert!(
+            result
+                .warnings
+                .iter()
+                .all(|w| w.code == WarningCode::DeprecatedKey
+                    && w.source.as_deref() == Some("memory"))
+        );
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_deprecated_alias_is_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = crate::audit::AuditWriter::builder()
+            .log_dir(dir.path().to_path_buf())
+            .build();
+
+        ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "app_name".to_string(),
+                ConfigValue::string("legacy"),
+            )]))
+            .with_deprecated_alias("app_name", "name", "1.2")
+            .with_audit(Arc::new(audit))
+            .build()
+            .unwrap();
+
+        let log = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+            .collect::<String>();
+        assert!(log.contains("DeprecatedKey"), "{log}");
+        assert!(log.contains("\"app_name\""), "{log}");
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_with_audit_sink_adds_to_existing_writer() {
+        let existing = tempfile::tempdir().unwrap();
+        let extra = tempfile::tempdir().unwrap();
+        let audit = crate::audit::AuditWriter::builder()
+            .log_dir(existing.path().to_path_buf())
+            .build();
+
+        ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "app_name".to_string(),
+                ConfigValue::string("legacy"),
+            )]))
+            .with_deprecated_alias("app_name", "name", "1.2")
+            .with_audit(Arc::new(audit))
+            .with_audit_sink(crate::audit::FileSink::new(extra.path()))
+            .build()
+            .unwrap();
+
+        for dir in [existing.path(), extra.path()] {
+            let log = std::fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| std::fs::read_to_string(entry.unwrap().path()).unwrap())
+                .collect::<String>();
+            assert!(log.contains("\"event\":\"DeprecatedKey\""), "{log}");
+        }
+    }
+
+    #[cfg(feature = "audit")]
+    fn audit_events(dir: &std::path::Path) -> Vec<serde_json::Value> {
+        std::fs::read_dir(dir)
+            .unwrap()
+            .flat_map(|entry| {
+                std::fs::read_to_string(entry.unwrap().path())
+                    .unwrap()
+                    .lines()
+                    .map(|line| serde_json::from_str(line).unwrap())
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_build_emits_config_loaded() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = crate::audit::AuditWriter::builder()
+            .log_dir(dir.path().to_path_buf())
+            .build();
+
+        ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string("app"),
+            )]))
+            .with_audit(Arc::new(audit))
+            .with_correlation_id("deploy-42")
+            .build()
+            .unwrap();
+
+        let events = audit_events(dir.path());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "ConfigLoaded");
+        assert_eq!(events[0]["correlation_id"], "deploy-42");
+        assert_eq!(events[0]["sources"], serde_json::json!(["memory"]));
+    }
+
+    #[cfg(all(feature = "audit", feature = "encryption"))]
+    #[test]
+    fn test_build_emits_decryption_failed() {
+        use crate::secret::{CipherAlgorithm, SecretBytes};
+
+        let secret = ConfigEncryption::new()
+            .with_key(SecretBytes::new(
+                b"0123456789abcdef0123456789abcdef".to_vec(),
+            ))
+            .encrypt(CipherAlgorithm::XChaCha20, "hidden")
+            .unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let audit = crate::audit::AuditWriter::builder()
+            .log_dir(dir.path().to_path_buf())
+            .build();
+
+        let result = ConfigBuilder::<TestConfig>::new()
+            .memory(HashMap::from([(
+                "name".to_string(),
+                ConfigValue::string(secret),
+            )]))
+            .encryption(ConfigEncryption::new().with_key(SecretBytes::new(
+                b"fedcba9876543210fedcba9876543210".to_vec(),
+            )))
+            .with_audit(Arc::new(audit))
+            .build();
+        assert!(matches!(result, Err(ConfigError::DecryptionFailed { .. })));
+
+        let events = audit_events(dir.path());
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "DecryptionFailed");
+        assert!(events[0]["reason"].as_str().unwrap().contains("'name'"));
+        assert!(!events[0]["reason"].as_str().unwrap().contains("hidden"));
+    }
 }
@@ -8,13 +8,28 @@
 //! The source chain manages multiple sources with priority ordering
 //! and merges their values according to merge strategies.
 
-use crate::error::{ConfigError, ConfigResult};
+#[cfg(feature = "parse-cache")]
+use super::parse_cache::{ParseCache, ParseCacheSlot};
+#[cfg(feature = "encryption")]
+use super::source::EncryptionSlot;
+#[cfg(feature = "schema")]
+use super::source::EnvSchemaSlot;
+use super::source::IncludeFlag;
+use crate::error::{ConfigError, ConfigResult, ValidationErrors};
 use crate::impl_::merger::{MergeEngine, MergeStrategy};
 use crate::interface::Source;
 use crate::types::{AnnotatedValue, ConfigValue, SourceKind};
 use indexmap::IndexMap;
 use std::sync::Arc;
 
+/// Top-level key holding per-profile sections (`[profile.production]`).
+pub const PROFILE_SECTION: &str = "profile";
+
+/// Check run on one section of each source as it is collected, see
+/// [`SourceChain::with_section_validator`].
+pub type SectionValidator =
+    Arc<dyn Fn(&AnnotatedValue) -> Result<(), ValidationErrors> + Send + Sync>;
+
 /// A chain of configuration sources with priority ordering.
 ///
 /// Sources are collected and merged in order of priority.
@@ -26,6 +41,10 @@ pub struct SourceChain {
     merge_engine: MergeEngine,
     /// Whether to stop on first error.
     fail_fast: bool,
+    /// Active profile whose `profile.<name>` section overlays each source.
+    profile: Option<Arc<str>>,
+    /// Checks run on each source's sections before it is merged.
+    section_validators: Vec<(String, SectionValidator)>,
 }
 
 impl Default for SourceChain {
@@ -41,6 +60,8 @@ impl SourceChain {
             sources: Vec::new(),
             merge_engine: MergeEngine::new(),
             fail_fast: true,
+            profile: None,
+            section_validators: Vec::new(),
         }
     }
 
@@ -50,6 +71,8 @@ impl SourceChain {
             sources: Vec::new(),
             merge_engine: MergeEngine::new().with_default_strategy(strategy),
             fail_fast: true,
+            profile: None,
+            section_validators: Vec::new(),
         }
     }
 
@@ -78,6 +101,40 @@ impl SourceChain {
         self
     }
 
+    /// Select the active profile.
+    ///
+    /// Each source's top-level `profile` table is removed, and its
+    /// `profile.<name>` section is merged over that source's own values before
+    /// the next source is applied. Base values, profile values and
+    /// higher-priority sources such as env therefore layer in a fixed order.
+    pub fn with_profile(mut self, profile: impl Into<Arc<str>>) -> Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Check the section at dotted `section` of every source as soon as that
+    /// source is collected, before later sources are loaded or anything is
+    /// merged.
+    ///
+    /// The validator sees only the values that source sets. Its first
+    /// violation becomes a [`ConfigError::ValidationFailed`] naming the full
+    /// key and the source that set it; with
+    /// [`fail_fast`](Self::fail_fast) that aborts the collection, otherwise
+    /// the source is skipped like one that failed to load.
+    pub fn with_section_validator(
+        mut self,
+        section: impl Into<String>,
+        validator: SectionValidator,
+    ) -> Self {
+        self.section_validators.push((section.into(), validator));
+        self
+    }
+
+    /// The active profile, if any.
+    pub fn profile(&self) -> Option<&str> {
+        self.profile.as_deref()
+    }
+
     /// Set a field-specific merge strategy.
     pub fn with_field_strategy(
         mut self,
@@ -109,13 +166,21 @@ impl SourceChain {
         let merge_engine = self.merge_engine;
         let fail_fast = self.fail_fast;
 
-        Self::collect_and_merge(sources, merge_engine, fail_fast)
+        Self::collect_and_merge(
+            sources,
+            merge_engine,
+            fail_fast,
+            self.profile.as_deref(),
+            &self.section_validators,
+        )
     }
 
     fn collect_and_merge(
         sources: Vec<Box<dyn Source>>,
         merge_engine: MergeEngine,
         fail_fast: bool,
+        profile: Option<&str>,
+        validators: &[(String, SectionValidator)],
     ) -> ConfigResult<AnnotatedValue> {
         if sources.is_empty() {
             return Ok(AnnotatedValue::new(
@@ -126,15 +191,18 @@ impl SourceChain {
         }
 
         // Collect all source values
-        let mut values: Vec<(String, ConfigResult<AnnotatedValue>)> = Vec::new();
+        let mut values: Vec<(u8, AnnotatedValue)> = Vec::new();
         let mut errors: Vec<(String, ConfigError)> = Vec::new();
 
         for source in &sources {
             let name = source.name().to_string();
-            let result = source.collect();
+            let result = source.collect().and_then(|value| {
+                validate_sections(&value, validators)?;
+                Ok(value)
+            });
 
             match result {
-                Ok(value) => values.push((name, Ok(value))),
+                Ok(value) => values.push((source.priority(), value)),
                 Err(e) => {
                     if fail_fast && !source.is_optional() {
                         return Err(e);
@@ -150,12 +218,10 @@ impl SourceChain {
             return Err(ConfigError::MultiSource { source: multi_err });
         }
 
-        // Sort by priority (lower priority first)
-        let mut sorted_values: Vec<_> = values
-            .into_iter()
-            .filter_map(|(_, result)| result.ok())
-            .collect();
-        sorted_values.sort_by_key(|v| v.priority);
+        // Sort by source priority (lower priority first); the sort is stable,
+        // so sources of equal priority merge in the order they were added.
+        values.sort_by_key(|(priority, _)| *priority);
+        let sorted_values = values.into_iter().map(|(_, value)| value);
 
         // Merge all values
         let mut merged = AnnotatedValue::new(
@@ -165,7 +231,15 @@ impl SourceChain {
         );
 
         for value in sorted_values {
-            merged = merge_engine.merge(&merged, &value)?;
+            let Some(profile) = profile else {
+                merged = merge_engine.merge(&merged, &value)?;
+                continue;
+            };
+            let (base, overlay) = split_profile(value, profile);
+            merged = merge_engine.merge(&merged, &base)?;
+            if let Some(overlay) = overlay {
+                merged = merge_engine.merge(&merged, &overlay)?;
+            }
         }
 
         Ok(merged)
@@ -182,11 +256,125 @@ impl SourceChain {
     }
 }
 
+/// Run `validators` on their sections of one source's `value`, returning
+/// the first violation with the location, or else the source, of the
+/// offending value.
+fn validate_sections(
+    value: &AnnotatedValue,
+    validators: &[(String, SectionValidator)],
+) -> ConfigResult<()> {
+    for (section, validator) in validators {
+        let Some(tree) = value.get_path(section) else {
+            continue;
+        };
+        let Err(errors) = validator(tree) else {
+            continue;
+        };
+        let Some(violation) = errors.violations().first() else {
+            continue;
+        };
+
+        let field = violation.fields.first().map(String::as_str).unwrap_or("");
+        let node = tree.get_path(field).unwrap_or(tree);
+        let location = match &node.location {
+            Some(location) => location.to_string(),
+            None => node.source.as_str().to_string(),
+        };
+        let key = match (section.is_empty(), field.is_empty()) {
+            (_, true) => section.clone(),
+            (true, false) => field.to_string(),
+            (false, false) => format!("{}.{}", section, field),
+        };
+        return Err(ConfigError::ValidationFailed {
+            field: key,
+            rule: "section".to_string(),
+            message: format!("{} (at {})", violation.message, location),
+        });
+    }
+    Ok(())
+}
+
+/// Remove the `profile` table from a source's value and return the
+/// `profile.<name>` section, re-rooted so it merges over the base values.
+fn split_profile(value: AnnotatedValue, profile: &str) -> (AnnotatedValue, Option<AnnotatedValue>) {
+    let ConfigValue::Map(map) = &value.inner else {
+        return (value, None);
+    };
+    if !map.contains_key(PROFILE_SECTION) {
+        return (value, None);
+    }
+
+    let mut base_map = map.as_ref().clone();
+    let profiles = base_map.shift_remove(PROFILE_SECTION);
+    let overlay = profiles.and_then(|profiles| match &profiles.inner {
+        ConfigValue::Map(sections) => sections.get(profile).map(|section| {
+            let prefix = format!("{}.{}", PROFILE_SECTION, profile);
+            rebase_paths(section, &prefix)
+        }),
+        _ => None,
+    });
+
+    let base = AnnotatedValue {
+        inner: ConfigValue::Map(Arc::new(base_map)),
+        ..value
+    };
+    (base, overlay)
+}
+
+/// Copy `value`, dropping `prefix` from the front of every path.
+fn rebase_paths(value: &AnnotatedValue, prefix: &str) -> AnnotatedValue {
+    let path = match value.path.strip_prefix(prefix) {
+        Some(rest) => Arc::from(rest.strip_prefix('.').unwrap_or(rest)),
+        None => value.path.clone(),
+    };
+    let inner = match &value.inner {
+        ConfigValue::Map(map) => ConfigValue::Map(Arc::new(
+            map.iter()
+                .map(|(k, v)| (k.clone(), rebase_paths(v, prefix)))
+                .collect(),
+        )),
+        ConfigValue::Array(items) => {
+            ConfigValue::Array(items.iter().map(|v| rebase_paths(v, prefix)).collect())
+        }
+        other => other.clone(),
+    };
+    AnnotatedValue {
+        inner,
+        source: value.source.clone(),
+        path,
+        priority: value.priority,
+        version: value.version,
+        location: value.location.clone(),
+    }
+}
+
+/// Priority given to the first kind of a priority order; each following kind
+/// gets 10 less.
+const ORDER_TOP_PRIORITY: u8 = 200;
+
+/// Priority of `kind` under `order` (highest first), if it is listed.
+pub(crate) fn order_priority(order: &[SourceKind], kind: SourceKind) -> Option<u8> {
+    order
+        .iter()
+        .position(|listed| *listed == kind)
+        .map(|rank| ORDER_TOP_PRIORITY.saturating_sub(rank as u8 * 10))
+}
+
 /// Builder for creating source chains with a fluent API.
 pub struct SourceChainBuilder {
     chain: SourceChain,
     /// Whether to allow absolute paths for file sources.
     allow_absolute_paths: bool,
+    /// Encryption settings shared with file sources, filled in at build time.
+    #[cfg(feature = "encryption")]
+    encryption: EncryptionSlot,
+    includes: IncludeFlag,
+    /// Parse cache shared with file sources.
+    #[cfg(feature = "parse-cache")]
+    parse_cache: ParseCacheSlot,
+    /// Schema shared with env sources.
+    #[cfg(feature = "schema")]
+    env_schema: EnvSchemaSlot,
 }
 
 impl Default for SourceChainBuilder {
@@ -201,6 +389,13 @@ impl SourceChainBuilder {
         Self {
             chain: SourceChain::new(),
             allow_absolute_paths: false,
+            #[cfg(feature = "encryption")]
+            encryption: EncryptionSlot::default(),
+            includes: IncludeFlag::default(),
+            #[cfg(feature = "parse-cache")]
+            parse_cache: ParseCacheSlot::default(),
+            #[cfg(feature = "schema")]
+            env_schema: EnvSchemaSlot::default(),
         }
     }
 
@@ -211,47 +406,156 @@ impl SourceChainBuilder {
     }
 
     /// Add a file source.
+    ///
+    /// A path containing glob metacharacters (`configs/*.yaml`) that does not
+    /// name an existing file adds a [`FileGlobSource`](super::source::FileGlobSource);
+    /// it fails to load if nothing matches.
     pub fn file(self, path: impl Into<std::path::PathBuf>) -> Self {
-        use super::source::FileSource;
-        let mut source = FileSource::new(path);
+        self.file_source(path.into(), false)
+    }
+
+    /// Add an optional file source. A glob pattern may match no files.
+    pub fn file_optional(self, path: impl Into<std::path::PathBuf>) -> Self {
+        self.file_source(path.into(), true)
+    }
+
+    fn file_source(self, path: std::path::PathBuf, optional: bool) -> Self {
+        use super::source::{FileGlobSource, FileSource};
+        if !path.exists() && path.to_str().is_some_and(super::include::is_glob) {
+            let mut source = FileGlobSource::new(path).with_include_flag(self.includes.clone());
+            if optional {
+                source = source.optional();
+            }
+            if self.allow_absolute_paths {
+                source = source.allow_absolute_paths();
+            }
+            #[cfg(feature = "encryption")]
+            {
+                source = source.with_encryption_slot(self.encryption.clone());
+            }
+            #[cfg(feature = "parse-cache")]
+            {
+                source = source.with_parse_cache_slot(self.parse_cache.clone());
+            }
+            return self.source(Box::new(source));
+        }
+
+        let mut source = FileSource::new(path).with_include_flag(self.includes.clone());
+        if optional {
+            source = source.optional();
+        }
         if self.allow_absolute_paths {
             source = source.allow_absolute_paths();
         }
+        #[cfg(feature = "encryption")]
+        {
+            source = source.with_encryption_slot(self.encryption.clone());
+        }
+        #[cfg(feature = "parse-cache")]
+        {
+            source = source.with_parse_cache_slot(self.parse_cache.clone());
+        }
         self.source(Box::new(source))
     }
 
-    /// Add an optional file source.
-    pub fn file_optional(self, path: impl Into<std::path::PathBuf>) -> Self {
-        use super::source::FileSource;
-        let mut source = FileSource::new(path).optional();
+    /// Add a drop-in directory source: every recognized file in `path`, in
+    /// lexical order.
+    pub fn config_dir(self, path: impl Into<std::path::PathBuf>) -> Self {
+        use super::source::ConfigDirSource;
+        let mut source = ConfigDirSource::new(path).with_include_flag(self.includes.clone());
         if self.allow_absolute_paths {
             source = source.allow_absolute_paths();
         }
+        #[cfg(feature = "encryption")]
+        {
+            source = source.with_encryption_slot(self.encryption.clone());
+        }
+        #[cfg(feature = "parse-cache")]
+        {
+            source = source.with_parse_cache_slot(self.parse_cache.clone());
+        }
         self.source(Box::new(source))
     }
 
+    /// Slot through which file sources added by this builder receive their
+    /// encryption settings.
+    #[cfg(feature = "encryption")]
+    pub(crate) fn encryption_slot(&self) -> &EncryptionSlot {
+        &self.encryption
+    }
+
+    /// Reuse parsed files from `cache` in file sources added by this builder,
+    /// including ones added before this call. Only the first cache set applies.
+    #[cfg(feature = "parse-cache")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "parse-cache")))]
+    pub fn parse_cache(self, cache: Arc<ParseCache>) -> Self {
+        let _ = self.parse_cache.set(cache);
+        self
+    }
+
     /// Allow absolute paths for file sources (use with caution, mainly for testing).
     pub fn allow_absolute_paths(mut self) -> Self {
         self.allow_absolute_paths = true;
         self
     }
 
+    /// Honour the `__include` directive in file sources added by this builder,
+    /// including ones added before this call.
+    pub fn includes(self, enabled: bool) -> Self {
+        self.includes
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+        self
+    }
+
     /// Add an environment source.
     pub fn env(self) -> Self {
         use super::source::EnvSource;
-        self.source(Box::new(EnvSource::new()))
+        self.env_source(EnvSource::new())
     }
 
     /// Add an environment source with prefix.
     pub fn env_with_prefix(self, prefix: impl Into<String>) -> Self {
         use super::source::EnvSource;
-        self.source(Box::new(EnvSource::with_prefix(prefix)))
+        self.env_source(EnvSource::with_prefix(prefix))
+    }
+
+    /// Add an environment source with prefix whose variable names separate
+    /// nested keys with `separator`.
+    pub fn env_with_separator(
+        self,
+        prefix: impl Into<String>,
+        separator: impl Into<String>,
+    ) -> Self {
+        use super::source::EnvSource;
+        self.env_source(EnvSource::with_prefix(prefix).separator(separator))
+    }
+
+    fn env_source(self, source: super::source::EnvSource) -> Self {
+        #[cfg(feature = "schema")]
+        let source = source.with_schema_slot(self.env_schema.clone());
+        self.source(Box::new(source))
+    }
+
+    /// Coerce the variables of env sources added by this builder, including
+    /// ones added before this call, to the types of `schema`; see
+    /// [`EnvSource::with_schema`](super::source::EnvSource::with_schema).
+    /// Only the first schema set applies.
+    #[cfg(feature = "schema")]
+    pub fn env_schema(self, schema: serde_json::Value) -> Self {
+        let _ = self.env_schema.set(Arc::new(schema));
+        self
     }
 
     /// Add a default source.
-    pub fn defaults(self, defaults: std::collections::HashMap<String, ConfigValue>) -> Self {
+    ///
+    /// It is placed before every other source, so sources that share its
+    /// priority of 0 (files, by default) still override it.
+    pub fn defaults(mut self, defaults: std::collections::HashMap<String, ConfigValue>) -> Self {
         use super::source::DefaultSource;
-        self.source(Box::new(DefaultSource::with_defaults(defaults)))
+        self.chain
+            .sources
+            .insert(0, Box::new(DefaultSource::with_defaults(defaults)));
+        self
     }
 
     /// Add a memory source.
@@ -272,6 +576,39 @@ impl SourceChainBuilder {
         ))
     }
 
+    /// Merge the most recently added source at `priority` instead of its own.
+    ///
+    /// Has no effect while no source has been added.
+    pub fn priority(mut self, priority: u8) -> Self {
+        use super::source::PrioritizedSource;
+        if let Some(source) = self.chain.sources.pop() {
+            self.chain
+                .sources
+                .push(Box::new(PrioritizedSource::new(source, priority)));
+        }
+        self
+    }
+
+    /// Merge the sources of each kind in `order` (highest priority first)
+    /// above every source whose kind is not listed.
+    ///
+    /// The first kind is merged at priority 200 and each following kind at
+    /// 10 less, replacing the priorities the sources had. Sources of the same
+    /// kind keep the order they were added in.
+    pub fn priority_order(mut self, order: &[SourceKind]) -> Self {
+        use super::source::PrioritizedSource;
+        self.chain.sources = std::mem::take(&mut self.chain.sources)
+            .into_iter()
+            .map(|source| match order_priority(order, source.source_kind()) {
+                Some(priority) => {
+                    Box::new(PrioritizedSource::new(source, priority)) as Box<dyn Source>
+                }
+                None => source,
+            })
+            .collect();
+        self
+    }
+
     /// Set merge strategy.
     pub fn strategy(mut self, strategy: MergeStrategy) -> Self {
         self.chain.merge_engine = self.chain.merge_engine.with_default_strategy(strategy);
@@ -290,18 +627,41 @@ impl SourceChainBuilder {
         self
     }
 
+    /// Check a section of each source as it is collected (see
+    /// [`SourceChain::with_section_validator`]).
+    pub fn section_validator(
+        mut self,
+        section: impl Into<String>,
+        validator: SectionValidator,
+    ) -> Self {
+        self.chain = self.chain.with_section_validator(section, validator);
+        self
+    }
+
+    /// Select the active profile (see [`SourceChain::with_profile`]).
+    pub fn profile(mut self, profile: impl Into<Arc<str>>) -> Self {
+        self.chain = self.chain.with_profile(profile);
+        self
+    }
+
     /// Build the source chain.
     pub fn build(self) -> SourceChain {
         self.chain
     }
 
-    /// Get file paths from file sources for watching.
+    /// The chain built so far.
+    pub(crate) fn chain(&self) -> &SourceChain {
+        &self.chain
+    }
+
+    /// Get file paths from file sources for watching, including files pulled
+    /// in through `__include`.
     pub fn get_watch_paths(&self) -> Vec<std::path::PathBuf> {
         self.chain
             .sources
             .iter()
             .filter(|s| s.source_kind() == SourceKind::File)
-            .filter_map(|s| s.file_path().map(|p| p.to_path_buf()))
+            .flat_map(|s| s.watch_paths())
             .collect()
     }
 }
@@ -482,6 +842,55 @@ mod tests {
         }
     }
 
+    fn top_level<'a>(value: &'a AnnotatedValue, key: &str) -> Option<&'a AnnotatedValue> {
+        match &value.inner {
+            ConfigValue::Map(map) => map.get(key),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_chain_profile_overlay_per_source() {
+        let profiled = || {
+            MemorySource::new()
+                .set("port", ConfigValue::uint(1))
+                .set("profile.prod.port", ConfigValue::uint(2))
+                .set("profile.prod.host", ConfigValue::string("file"))
+                .with_priority(10)
+        };
+
+        let result = SourceChain::new()
+            .push(Box::new(profiled()))
+            .push(Box::new(
+                MemorySource::new()
+                    .set("host", ConfigValue::string("env"))
+                    .with_priority(20),
+            ))
+            .with_profile("prod")
+            .collect()
+            .unwrap();
+
+        let port = top_level(&result, "port").unwrap();
+        assert_eq!(port.inner, ConfigValue::uint(2));
+        assert_eq!(port.path.as_ref(), "port");
+        assert_eq!(
+            top_level(&result, "host").unwrap().inner,
+            ConfigValue::string("env")
+        );
+        assert!(top_level(&result, PROFILE_SECTION).is_none());
+
+        // Without an active profile the table is left alone.
+        let result = SourceChain::new()
+            .push(Box::new(profiled()))
+            .collect()
+            .unwrap();
+        assert_eq!(
+            top_level(&result, "port").unwrap().inner,
+            ConfigValue::uint(1)
+        );
+        assert!(top_level(&result, PROFILE_SECTION).is_some());
+    }
+
     #[test]
     fn test_chain_fail_fast_required_error() {
         // fail_fast=true + required source fails → immediate error
@@ -617,6 +1026,26 @@ mod tests {
         assert_eq!(paths.len(), 2);
     }
 
+    #[test]
+    fn test_builder_file_glob_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        let pattern = dir.path().join("*.toml");
+
+        let required = SourceChainBuilder::new()
+            .allow_absolute_paths()
+            .file(&pattern)
+            .build()
+            .collect();
+        assert!(matches!(required, Err(ConfigError::FileNotFound { .. })));
+
+        let optional = SourceChainBuilder::new()
+            .allow_absolute_paths()
+            .file_optional(&pattern)
+            .build()
+            .collect();
+        assert!(optional.is_ok());
+    }
+
     #[test]
     fn test_chain_source_names_multi() {
         let chain = SourceChain::new()
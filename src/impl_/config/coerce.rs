@@ -0,0 +1,353 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Coercion of environment variable values for [`EnvSource`].
+//!
+//! Variables are plain strings. Without a schema their type is inferred with
+//! [`EnvSource::infer_config_value`], and JSON array and object literals
+//! (`["a","b"]`, `{"k":1}`) are parsed as such. With a JSON Schema of the
+//! configuration (`schema` feature) the value is converted to the type of
+//! the key it sets: `1`/`yes`/`on` become `true` for a boolean, `a,b,c` an
+//! array, and `0123` stays a string for a string field. A value that does
+//! not fit is an error naming the variable.
+//!
+//! [`EnvSource`]: super::source::EnvSource
+//! [`EnvSource::infer_config_value`]: super::source::EnvSource::infer_config_value
+
+use super::source::EnvSource;
+#[cfg(feature = "schema")]
+use crate::error::ConfigError;
+use crate::error::ConfigResult;
+use crate::types::{ConfigValue, SourceId};
+
+/// Convert `raw`, the value of variable `var` that sets dotted `path`.
+pub(crate) fn coerce_env_value(
+    raw: &str,
+    var: &str,
+    path: &str,
+    source: &SourceId,
+    #[cfg(feature = "schema")] schema: Option<&serde_json::Value>,
+) -> ConfigResult<ConfigValue> {
+    #[cfg(feature = "schema")]
+    if let Some(ty) = schema.and_then(|root| schema_at(root, path).map(|s| (root, s))) {
+        return coerce_to_schema(raw, var, path, source, ty.0, ty.1);
+    }
+    let _ = var;
+    Ok(infer(raw, path, source))
+}
+
+/// Infer the type of `raw`, parsing JSON array and object literals.
+fn infer(raw: &str, path: &str, source: &SourceId) -> ConfigValue {
+    #[cfg(feature = "json")]
+    if let Some(value) = json_literal(raw, path, source) {
+        return value;
+    }
+    let _ = (path, source);
+    EnvSource::infer_config_value(raw)
+}
+
+/// `raw` as a JSON array or object, if it is one.
+#[cfg(feature = "json")]
+fn json_literal(raw: &str, path: &str, source: &SourceId) -> Option<ConfigValue> {
+    let trimmed = raw.trim();
+    let delimited = (trimmed.starts_with('[') && trimmed.ends_with(']'))
+        || (trimmed.starts_with('{') && trimmed.ends_with('}'));
+    if !delimited {
+        return None;
+    }
+    let json: serde_json::Value = serde_json::from_str(trimmed).ok()?;
+    Some(crate::impl_::convert::json_to_config_value(
+        &json, source, path,
+    ))
+}
+
+/// The schema of the value at dotted `path`, following `$ref`s and the
+/// `anyOf: [T, null]` of optional fields.
+#[cfg(feature = "schema")]
+fn schema_at<'a>(root: &'a serde_json::Value, path: &str) -> Option<&'a serde_json::Value> {
+    let mut schema = resolve(root, root);
+    for segment in path.split('.') {
+        let property = schema.get("properties").and_then(|p| p.get(segment));
+        let item = schema
+            .get("items")
+            .filter(|_| segment.parse::<usize>().is_ok());
+        let entry = schema.get("additionalProperties").filter(|v| v.is_object());
+        schema = resolve(root, property.or(item).or(entry)?);
+    }
+    Some(schema)
+}
+
+#[cfg(feature = "schema")]
+fn resolve<'a>(
+    root: &'a serde_json::Value,
+    mut schema: &'a serde_json::Value,
+) -> &'a serde_json::Value {
+    // Bounded, so that a `$ref` cycle cannot loop forever
+    for _ in 0..32 {
+        if let Some(reference) = schema.get("$ref").and_then(|r| r.as_str()) {
+            match reference.strip_prefix('#').and_then(|p| root.pointer(p)) {
+                Some(target) => {
+                    schema = target;
+                    continue;
+                }
+                None => break,
+            }
+        }
+        let variants = schema
+            .get("anyOf")
+            .or_else(|| schema.get("oneOf"))
+            .and_then(|v| v.as_array());
+        if let Some(variants) = variants {
+            let mut non_null = variants.iter().filter(|v| schema_type(v) != Some("null"));
+            if let (Some(only), None) = (non_null.next(), non_null.next()) {
+                schema = only;
+                continue;
+            }
+        }
+        break;
+    }
+    schema
+}
+
+/// The `type` of `schema`, ignoring `null` in a list of types.
+#[cfg(feature = "schema")]
+fn schema_type(schema: &serde_json::Value) -> Option<&str> {
+    match schema.get("type")? {
+        serde_json::Value::String(ty) => Some(ty),
+        serde_json::Value::Array(types) => types
+            .iter()
+            .filter_map(|ty| ty.as_str())
+            .find(|ty| *ty != "null"),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "schema")]
+fn coerce_to_schema(
+    raw: &str,
+    var: &str,
+    path: &str,
+    source: &SourceId,
+    root: &serde_json::Value,
+    schema: &serde_json::Value,
+) -> ConfigResult<ConfigValue> {
+    let Some(ty) = schema_type(schema) else {
+        return Ok(infer(raw, path, source));
+    };
+    let invalid = |expected: &str| ConfigError::InvalidValue {
+        key: path.to_string(),
+        expected_type: ty.to_string(),
+        message: format!("environment variable {} is not {}", var, expected),
+    };
+    let trimmed = raw.trim();
+    match ty {
+        "string" => Ok(ConfigValue::String(raw.to_string())),
+        "boolean" => match trimmed.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(ConfigValue::Bool(true)),
+            "false" | "0" | "no" | "off" => Ok(ConfigValue::Bool(false)),
+            _ => Err(invalid("a boolean (true/false, 1/0, yes/no or on/off)")),
+        },
+        "integer" => match EnvSource::infer_config_value(trimmed) {
+            value @ (ConfigValue::I64(_) | ConfigValue::U64(_)) => Ok(value),
+            _ => Err(invalid("an integer")),
+        },
+        "number" => match EnvSource::infer_config_value(trimmed) {
+            value @ (ConfigValue::I64(_) | ConfigValue::U64(_) | ConfigValue::F64(_)) => Ok(value),
+            _ => Err(invalid("a number")),
+        },
+        "array" => {
+            #[cfg(feature = "json")]
+            if trimmed.starts_with('[') {
+                return json_literal(trimmed, path, source).ok_or_else(|| invalid("a JSON array"));
+            }
+            if trimmed.is_empty() {
+                return Ok(ConfigValue::Array(Vec::new().into()));
+            }
+            let items = trimmed
+                .split(',')
+                .enumerate()
+                .map(|(i, item)| {
+                    let item_path = format!("{}.{}", path, i);
+                    let value = match schema.get("items") {
+                        Some(items) => coerce_to_schema(
+                            item.trim(),
+                            var,
+                            &item_path,
+                            source,
+                            root,
+                            resolve(root, items),
+                        )?,
+                        None => infer(item.trim(), &item_path, source),
+                    };
+                    Ok(crate::types::AnnotatedValue::new(
+                        value,
+                        source.clone(),
+                        item_path,
+                    ))
+                })
+                .collect::<ConfigResult<Vec<_>>>()?;
+            Ok(ConfigValue::Array(items.into()))
+        }
+        "object" => {
+            #[cfg(feature = "json")]
+            if let Some(value) = json_literal(trimmed, path, source) {
+                return Ok(value);
+            }
+            Err(invalid("a JSON object"))
+        }
+        _ => Ok(infer(raw, path, source)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn coerce(raw: &str, path: &str) -> ConfigResult<ConfigValue> {
+        coerce_env_value(
+            raw,
+            "APP_VAR",
+            path,
+            &SourceId::new("env"),
+            #[cfg(feature = "schema")]
+            None,
+        )
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json_literals_without_schema() {
+        let value = coerce("[1, 2]", "ports").unwrap();
+        let items = value.as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1].inner, ConfigValue::I64(2));
+
+        let value = coerce(r#"{"a": true}"#, "flags").unwrap();
+        assert_eq!(value.as_map().unwrap()["a"].inner, ConfigValue::Bool(true));
+
+        assert_eq!(
+            coerce("[not json", "x").unwrap(),
+            ConfigValue::String("[not json".to_string())
+        );
+    }
+
+    #[test]
+    fn test_inference_without_schema() {
+        assert_eq!(coerce("8080", "port").unwrap(), ConfigValue::I64(8080));
+        assert_eq!(
+            coerce("a,b", "x").unwrap(),
+            ConfigValue::String("a,b".into())
+        );
+    }
+
+    #[cfg(feature = "schema")]
+    fn schema() -> serde_json::Value {
+        serde_json::json!({
+            "type": "object",
+            "properties": {
+                "debug": { "type": "boolean" },
+                "port": { "type": "integer" },
+                "ratio": { "type": "number" },
+                "code": { "type": "string" },
+                "tags": { "type": "array", "items": { "type": "string" } },
+                "ports": { "type": "array", "items": { "type": "integer" } },
+                "db": { "$ref": "#/$defs/Db" },
+                "replica": { "anyOf": [{ "$ref": "#/$defs/Db" }, { "type": "null" }] }
+            },
+            "$defs": {
+                "Db": {
+                    "type": "object",
+                    "properties": { "pool": { "type": ["integer", "null"] } }
+                }
+            }
+        })
+    }
+
+    #[cfg(feature = "schema")]
+    fn coerce_schema(raw: &str, path: &str) -> ConfigResult<ConfigValue> {
+        let schema = schema();
+        coerce_env_value(raw, "APP_VAR", path, &SourceId::new("env"), Some(&schema))
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_schema_coerces_scalars() {
+        assert_eq!(
+            coerce_schema("1", "debug").unwrap(),
+            ConfigValue::Bool(true)
+        );
+        assert_eq!(
+            coerce_schema("Off", "debug").unwrap(),
+            ConfigValue::Bool(false)
+        );
+        assert_eq!(coerce_schema(" 42 ", "port").unwrap(), ConfigValue::I64(42));
+        assert_eq!(
+            coerce_schema("0.5", "ratio").unwrap(),
+            ConfigValue::F64(0.5)
+        );
+        assert_eq!(
+            coerce_schema("0123", "code").unwrap(),
+            ConfigValue::String("0123".into())
+        );
+        assert_eq!(coerce_schema("8", "db.pool").unwrap(), ConfigValue::I64(8));
+        assert_eq!(
+            coerce_schema("8", "replica.pool").unwrap(),
+            ConfigValue::I64(8)
+        );
+        assert_eq!(
+            coerce_schema("x", "unknown").unwrap(),
+            ConfigValue::String("x".into())
+        );
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_schema_splits_comma_separated_arrays() {
+        let value = coerce_schema("a, b,c", "tags").unwrap();
+        let items: Vec<_> = value
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|v| v.inner.clone())
+            .collect();
+        assert_eq!(
+            items,
+            vec![
+                ConfigValue::String("a".into()),
+                ConfigValue::String("b".into()),
+                ConfigValue::String("c".into()),
+            ]
+        );
+
+        let value = coerce_schema("80,443", "ports").unwrap();
+        assert_eq!(value.as_array().unwrap()[1].inner, ConfigValue::I64(443));
+        assert!(coerce_schema("", "tags")
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .is_empty());
+    }
+
+    #[cfg(feature = "schema")]
+    #[test]
+    fn test_schema_errors_name_the_variable() {
+        let err = coerce_schema("maybe", "debug").unwrap_err();
+        let ConfigError::InvalidValue {
+            key,
+            expected_type,
+            message,
+        } = &err
+        else {
+            panic!("expected InvalidValue, got {:?}", err);
+        };
+        assert_eq!(key, "debug");
+        assert_eq!(expected_type, "boolean");
+        assert!(message.contains("APP_VAR"), "{}", message);
+        assert!(!message.contains("maybe"), "{}", message);
+
+        let err = coerce_schema("80,https", "ports").unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { key, .. } if key == "ports.1"));
+    }
+}
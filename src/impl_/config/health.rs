@@ -0,0 +1,61 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Readiness report of the configured sources, produced by
+//! [`ConfigBuilder::health_check`](super::ConfigBuilder::health_check).
+
+use std::time::{Duration, Instant};
+
+use serde::{Serialize, Serializer};
+
+/// What a [`ProviderHealth`] entry checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProviderKind {
+    /// A config file is readable, and validly signed when signatures are
+    /// verified.
+    File,
+    /// A remote provider passes its health check.
+    Remote,
+    /// Decryption keys load and decrypt the `enc:` values of a file.
+    Key,
+}
+
+/// Result of checking one configured source.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderHealth {
+    /// File path, remote provider name or key source.
+    pub name: String,
+    pub kind: ProviderKind,
+    pub healthy: bool,
+    /// What was found, or why the check failed.
+    pub message: String,
+    /// Time the check took.
+    #[serde(rename = "latency_ms", serialize_with = "serialize_millis")]
+    pub latency: Duration,
+}
+
+impl ProviderHealth {
+    /// Entry for a check started at `started`, healthy when `result` is `Ok`.
+    pub(crate) fn new(
+        name: impl Into<String>,
+        kind: ProviderKind,
+        started: Instant,
+        result: Result<String, String>,
+    ) -> Self {
+        let healthy = result.is_ok();
+        Self {
+            name: name.into(),
+            kind,
+            healthy,
+            message: result.unwrap_or_else(|e| e),
+            latency: started.elapsed(),
+        }
+    }
+}
+
+fn serialize_millis<S: Serializer>(latency: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(latency.as_millis() as u64)
+}
@@ -0,0 +1,294 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! `__include` directive for file sources.
+//!
+//! A configuration file may list fragment files under a top-level `__include`
+//! key, either as a single string or as an array of strings:
+//!
+//! ```toml
+//! __include = ["db.toml", "features/*.yaml"]
+//!
+//! [server]
+//! port = 8080
+//! ```
+//!
+//! Paths are resolved relative to the including file and may contain glob
+//! patterns, which expand in lexical order. Fragments are merged first, in the
+//! order listed, and the including file's own values are merged over them.
+//! Fragments can include further files; an include cycle is reported as
+//! [`ConfigError::CircularReference`].
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::impl_::merger::MergeEngine;
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// Top-level key listing fragment files to merge into a configuration file.
+pub const INCLUDE_KEY: &str = "__include";
+
+/// Load `path` together with the fragments named in its `__include` key.
+///
+/// Returns the merged tree and every file that was read, in load order.
+pub(crate) fn load_with_includes<F>(
+    path: &Path,
+    load: &F,
+) -> ConfigResult<(AnnotatedValue, Vec<PathBuf>)>
+where
+    F: Fn(&Path) -> ConfigResult<AnnotatedValue>,
+{
+    let mut stack = Vec::new();
+    let mut files = Vec::new();
+    let value = load_recursive(path, load, &mut stack, &mut files)?;
+    Ok((value, files))
+}
+
+fn load_recursive<F>(
+    path: &Path,
+    load: &F,
+    stack: &mut Vec<PathBuf>,
+    files: &mut Vec<PathBuf>,
+) -> ConfigResult<AnnotatedValue>
+where
+    F: Fn(&Path) -> ConfigResult<AnnotatedValue>,
+{
+    let identity = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if stack.contains(&identity) {
+        let chain: Vec<String> = stack
+            .iter()
+            .chain(std::iter::once(&identity))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(ConfigError::CircularReference {
+            path: chain.join(" -> "),
+        });
+    }
+
+    let value = load(path)?;
+    files.push(path.to_path_buf());
+
+    let (value, patterns) = take_includes(value)?;
+    if patterns.is_empty() {
+        return Ok(value);
+    }
+
+    stack.push(identity);
+    let base_dir = path.parent().unwrap_or(Path::new(""));
+    let engine = MergeEngine::new();
+    let mut merged = AnnotatedValue::new(
+        ConfigValue::Map(Arc::new(IndexMap::new())),
+        value.source.clone(),
+        "",
+    );
+    for pattern in &patterns {
+        for include in expand(base_dir, pattern)? {
+            let fragment = load_recursive(&include, load, stack, files)?;
+            merged = engine.merge(&merged, &fragment)?;
+        }
+    }
+    stack.pop();
+
+    engine.merge(&merged, &value)
+}
+
+/// Remove the `__include` key and return the patterns it lists.
+fn take_includes(value: AnnotatedValue) -> ConfigResult<(AnnotatedValue, Vec<String>)> {
+    let ConfigValue::Map(map) = &value.inner else {
+        return Ok((value, Vec::new()));
+    };
+    let Some(include) = map.get(INCLUDE_KEY) else {
+        return Ok((value, Vec::new()));
+    };
+
+    let invalid = || ConfigError::InvalidValue {
+        key: INCLUDE_KEY.to_string(),
+        expected_type: "string or array of strings".to_string(),
+        message: format!("unexpected value {:?}", include.inner),
+    };
+    let patterns = match &include.inner {
+        ConfigValue::String(s) => vec![s.clone()],
+        ConfigValue::Array(items) => items
+            .iter()
+            .map(|item| item.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect::<ConfigResult<_>>()?,
+        _ => return Err(invalid()),
+    };
+
+    let mut map = map.as_ref().clone();
+    map.shift_remove(INCLUDE_KEY);
+    let value = AnnotatedValue {
+        inner: ConfigValue::Map(Arc::new(map)),
+        ..value
+    };
+    Ok((value, patterns))
+}
+
+/// Resolve an include pattern against `base_dir`.
+///
+/// Literal paths must exist; glob patterns may match nothing.
+fn expand(base_dir: &Path, pattern: &str) -> ConfigResult<Vec<PathBuf>> {
+    let joined = base_dir.join(pattern);
+    if !is_glob(pattern) {
+        if !joined.is_file() {
+            return Err(ConfigError::FileNotFound {
+                filename: joined,
+                source: None,
+            });
+        }
+        return Ok(vec![joined]);
+    }
+
+    glob_files(&joined, INCLUDE_KEY)
+}
+
+/// Files matching the glob `pattern`, sorted by path. Directories are skipped.
+/// `key` names the setting the pattern came from in error messages.
+#[cfg(feature = "glob")]
+pub(crate) fn glob_files(pattern: &Path, key: &str) -> ConfigResult<Vec<PathBuf>> {
+    let invalid = |message: String| ConfigError::InvalidValue {
+        key: key.to_string(),
+        expected_type: "glob pattern".to_string(),
+        message,
+    };
+    let pattern = pattern
+        .to_str()
+        .ok_or_else(|| invalid(format!("non UTF-8 pattern {}", pattern.display())))?;
+    let mut paths = glob::glob(pattern)
+        .map_err(|e| invalid(format!("'{}': {}", pattern, e)))?
+        .filter_map(Result::ok)
+        .filter(|p| p.is_file())
+        .collect::<Vec<_>>();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Glob patterns need the `glob` feature.
+#[cfg(not(feature = "glob"))]
+pub(crate) fn glob_files(pattern: &Path, key: &str) -> ConfigResult<Vec<PathBuf>> {
+    Err(ConfigError::InvalidValue {
+        key: key.to_string(),
+        expected_type: "glob pattern".to_string(),
+        message: format!(
+            "'{}' is a glob pattern, which requires the `glob` feature",
+            pattern.display()
+        ),
+    })
+}
+
+/// Whether `pattern` contains glob metacharacters.
+pub(crate) fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?', '['])
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use super::*;
+    use crate::impl_::loader::{self, LoaderConfig};
+
+    fn load(path: &Path) -> ConfigResult<AnnotatedValue> {
+        loader::load_file(path, &LoaderConfig::default().allow_absolute())
+    }
+
+    fn get<'a>(value: &'a AnnotatedValue, path: &str) -> Option<&'a ConfigValue> {
+        path.split('.')
+            .try_fold(value, |node, key| match &node.inner {
+                ConfigValue::Map(map) => map.get(key),
+                _ => None,
+            })
+            .map(|node| &node.inner)
+    }
+
+    #[test]
+    fn test_includes_merge_under_main_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("features")).unwrap();
+        std::fs::write(
+            dir.path().join("db.toml"),
+            "[db]\nhost = \"db\"\nport = 5432\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("features/a.toml"), "flag = \"a\"\n").unwrap();
+        std::fs::write(
+            dir.path().join("features/b.toml"),
+            "flag = \"b\"\nother = 1\n",
+        )
+        .unwrap();
+        let main = dir.path().join("app.toml");
+        std::fs::write(
+            &main,
+            "__include = [\"db.toml\", \"features/*.toml\"]\n\n[db]\nport = 6543\n",
+        )
+        .unwrap();
+
+        let (value, files) = load_with_includes(&main, &load).unwrap();
+
+        assert_eq!(get(&value, "db.host"), Some(&ConfigValue::string("db")));
+        assert_eq!(get(&value, "db.port"), Some(&ConfigValue::I64(6543)));
+        assert_eq!(get(&value, "flag"), Some(&ConfigValue::string("b")));
+        assert!(get(&value, "other").is_some());
+        assert!(get(&value, INCLUDE_KEY).is_none());
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["app.toml", "db.toml", "a.toml", "b.toml"]);
+    }
+
+    #[test]
+    fn test_nested_includes_resolve_relative_to_fragment() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("conf")).unwrap();
+        std::fs::write(
+            dir.path().join("conf/base.toml"),
+            "__include = \"shared.toml\"\nname = \"base\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("conf/shared.toml"), "shared = true\n").unwrap();
+        let main = dir.path().join("app.toml");
+        std::fs::write(&main, "__include = \"conf/base.toml\"\n").unwrap();
+
+        let (value, _) = load_with_includes(&main, &load).unwrap();
+        assert_eq!(get(&value, "shared"), Some(&ConfigValue::Bool(true)));
+        assert_eq!(get(&value, "name"), Some(&ConfigValue::string("base")));
+    }
+
+    #[test]
+    fn test_include_cycle_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        std::fs::write(&a, "__include = \"b.toml\"\n").unwrap();
+        std::fs::write(dir.path().join("b.toml"), "__include = \"a.toml\"\n").unwrap();
+
+        let err = load_with_includes(&a, &load).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::CircularReference { .. }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_missing_literal_include_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("app.toml");
+        std::fs::write(&main, "__include = [\"missing.toml\", \"none/*.toml\"]\n").unwrap();
+
+        let err = load_with_includes(&main, &load).unwrap_err();
+        assert!(matches!(err, ConfigError::FileNotFound { .. }), "{err}");
+    }
+
+    #[test]
+    fn test_invalid_include_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let main = dir.path().join("app.toml");
+        std::fs::write(&main, "__include = 3\n").unwrap();
+
+        let err = load_with_includes(&main, &load).unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }), "{err}");
+    }
+}
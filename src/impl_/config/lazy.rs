@@ -0,0 +1,178 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Deferred deserialization, produced by
+//! [`ConfigBuilder::build_lazy`](super::ConfigBuilder::build_lazy).
+
+use std::fmt;
+use std::sync::OnceLock;
+
+use serde::de::DeserializeOwned;
+
+use super::builder::deserialize_section;
+use super::unknown_keys::EnvNaming;
+use crate::error::{ConfigResult, Redactor};
+use crate::types::AnnotatedValue;
+
+/// The merged tree may hold decrypted plaintext; wipe it on drop.
+#[cfg(feature = "encryption")]
+type Tree = crate::secret::ZeroizingValue;
+#[cfg(not(feature = "encryption"))]
+type Tree = AnnotatedValue;
+
+/// A merged configuration whose deserialization into `T` is deferred until
+/// first access.
+///
+/// Useful for very large configurations where most code paths only read one
+/// section: [`section`](Self::section) deserializes a sub-tree without
+/// touching the rest, and [`get`](Self::get) builds `T` once and caches it.
+///
+/// ```rust
+/// use confers::ConfigBuilder;
+/// use confers::types::ConfigValue;
+///
+/// #[derive(Debug, Default, serde::Deserialize)]
+/// struct Database {
+///     port: u16,
+/// }
+///
+/// let lazy = ConfigBuilder::<serde_json::Value>::new()
+///     .default("database.port", ConfigValue::uint(5432))
+///     .build_lazy()?;
+/// let database: Database = lazy.section("database")?;
+/// assert_eq!(database.port, 5432);
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+pub struct LazyConfig<T> {
+    tree: Tree,
+    deny_unknown: Option<Option<EnvNaming>>,
+    redactor: Redactor,
+    config: OnceLock<T>,
+}
+
+impl<T> LazyConfig<T> {
+    pub(super) fn new(
+        tree: AnnotatedValue,
+        deny_unknown: Option<Option<EnvNaming>>,
+        redactor: Redactor,
+    ) -> Self {
+        #[cfg(feature = "encryption")]
+        let tree = crate::secret::ZeroizingValue::new(tree);
+        Self {
+            tree,
+            deny_unknown,
+            redactor,
+            config: OnceLock::new(),
+        }
+    }
+
+    /// The merged tree, with source locations.
+    pub fn tree(&self) -> &AnnotatedValue {
+        &self.tree
+    }
+
+    /// Whether [`get`](Self::get) has already deserialized `T`.
+    pub fn is_loaded(&self) -> bool {
+        self.config.get().is_some()
+    }
+
+    /// Deserialize the section at dotted `path` into `S`. The result is not
+    /// cached. A missing section is a
+    /// [`ConfigError::InvalidValue`](crate::ConfigError::InvalidValue) for
+    /// `path`.
+    pub fn section<S: DeserializeOwned>(&self, path: &str) -> ConfigResult<S> {
+        deserialize_section(&self.tree, path, &self.deny_unknown, &self.redactor)
+    }
+}
+
+impl<T: DeserializeOwned> LazyConfig<T> {
+    /// The whole configuration, deserialized on the first call. A failure is
+    /// returned again on every call until one succeeds.
+    pub fn get(&self) -> ConfigResult<&T> {
+        if let Some(config) = self.config.get() {
+            return Ok(config);
+        }
+        let config = deserialize_section(&self.tree, "", &self.deny_unknown, &self.redactor)?;
+        Ok(self.config.get_or_init(|| config))
+    }
+
+    /// Take the whole configuration, deserializing it if needed.
+    pub fn into_inner(mut self) -> ConfigResult<T> {
+        match self.config.take() {
+            Some(config) => Ok(config),
+            None => deserialize_section(&self.tree, "", &self.deny_unknown, &self.redactor),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for LazyConfig<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The tree may hold decrypted values; only show what was loaded.
+        f.debug_struct("LazyConfig")
+            .field("config", &self.config.get())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ConfigError;
+    use crate::types::ConfigValue;
+    use crate::ConfigBuilder;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize, PartialEq)]
+    struct Database {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    struct AppConfig {
+        database: Database,
+        name: String,
+    }
+
+    fn builder() -> ConfigBuilder<AppConfig> {
+        ConfigBuilder::new()
+            .default("name", ConfigValue::string("app"))
+            .default("database.host", ConfigValue::string("localhost"))
+            .default("database.port", ConfigValue::uint(5432))
+    }
+
+    #[test]
+    fn test_build_section() {
+        let database: Database = builder().build_section("database").unwrap();
+        assert_eq!(database.port, 5432);
+
+        let err = builder().build_section::<Database>("cache").unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::InvalidValue { key, .. } if key == "cache"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_build_section_reports_unknown_keys_with_full_path() {
+        let err = builder()
+            .default("database.hots", ConfigValue::string("x"))
+            .with_deny_unknown(true)
+            .build_section::<Database>("database")
+            .unwrap_err();
+        assert!(err.to_string().contains("database.hots"), "{err}");
+    }
+
+    #[test]
+    fn test_lazy_config_defers_deserialization() {
+        let lazy = builder().build_lazy().unwrap();
+        let port: u16 = lazy.section("database.port").unwrap();
+        assert_eq!(port, 5432);
+        assert!(!lazy.is_loaded());
+
+        assert_eq!(lazy.get().unwrap().name, "app");
+        assert!(lazy.is_loaded());
+        assert_eq!(lazy.into_inner().unwrap().database.host, "localhost");
+    }
+}
@@ -8,15 +8,33 @@
 //! Concrete implementations of `ConfigBuilder`, `SourceChain`, configuration sources,
 //! and resource limits. Public API surface is re-exported by `src/config.rs` facade.
 
+pub(crate) mod aliases;
 pub(crate) mod builder;
 pub(crate) mod chain;
+pub(crate) mod coerce;
+pub(crate) mod health;
+pub(crate) mod include;
+pub(crate) mod lazy;
 pub(crate) mod limits;
+#[cfg(feature = "parse-cache")]
+pub(crate) mod parse_cache;
+pub(crate) mod plan;
 pub(crate) mod source;
+pub(crate) mod unknown_keys;
 
-pub use builder::{config, ConfigBuilder, ReloadStrategy};
-pub use chain::{SourceChain, SourceChainBuilder};
+pub use builder::{config, ConfigBuilder, ReloadStrategy, OVERRIDE_SOURCE};
+pub use chain::{SectionValidator, SourceChain, SourceChainBuilder};
+pub use health::{ProviderHealth, ProviderKind};
+pub use include::INCLUDE_KEY;
+pub use lazy::LazyConfig;
 pub use limits::ConfigLimits;
-pub use source::{DefaultSource, EnvSource, FileSource, MemorySource};
+#[cfg(feature = "parse-cache")]
+pub use parse_cache::ParseCache;
+pub use plan::{LoadPlan, PlannedProvider, PlannedSource};
+pub use source::{
+    ConfigDirSource, DefaultSource, EnvSource, FileGlobSource, FileSource, MemorySource,
+    StringSource,
+};
 
 #[cfg(feature = "remote")]
 pub use crate::interface::AsyncSource;
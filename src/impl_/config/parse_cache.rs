@@ -0,0 +1,243 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Parsed configuration files kept between builds, for
+//! [`ConfigBuilder::with_parse_cache`](super::ConfigBuilder::with_parse_cache).
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, SystemTime};
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::impl_::loader::{self, LoaderConfig};
+use crate::types::AnnotatedValue;
+
+/// Parse cache shared between a builder and its file sources, filled in by
+/// [`ConfigBuilder::with_parse_cache`](super::ConfigBuilder::with_parse_cache).
+pub(crate) type ParseCacheSlot = Arc<OnceLock<Arc<ParseCache>>>;
+
+/// Files modified this recently are hashed on every load: a second write
+/// within the same mtime tick could keep both the mtime and the length.
+const RACY_WINDOW: Duration = Duration::from_secs(2);
+
+/// Modification time and length of a file, trusted to detect changes once
+/// the file is older than [`RACY_WINDOW`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FileStamp {
+    modified: SystemTime,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(metadata: &std::fs::Metadata) -> Option<Self> {
+        let modified = metadata.modified().ok()?;
+        let age = SystemTime::now().duration_since(modified).ok()?;
+        (age >= RACY_WINDOW).then_some(Self {
+            modified,
+            len: metadata.len(),
+        })
+    }
+}
+
+/// A parsed file and the content it was parsed from.
+#[derive(Debug)]
+struct CachedFile {
+    stamp: Option<FileStamp>,
+    digest: [u8; 32],
+    value: AnnotatedValue,
+}
+
+/// Parsed configuration files, reused across builds while their content is
+/// unchanged.
+///
+/// Rebuilding on every reload normally reads and parses every file again.
+/// Share one cache between the builders of successive reloads and a file
+/// whose modification time and length are unchanged is neither read nor
+/// parsed. Otherwise it is read and its SHA-256 compared, so it is only
+/// re-parsed when its content changed and a reload pays only for the files
+/// that were edited:
+///
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use confers::config::ParseCache;
+/// use confers::ConfigBuilder;
+///
+/// let cache = Arc::new(ParseCache::new());
+/// let load = || {
+///     ConfigBuilder::<serde_json::Value>::new()
+///         .file("config.toml")
+///         .file("features.toml")
+///         .with_parse_cache(cache.clone())
+///         .build()
+/// };
+/// let initial = load()?;
+/// // ... on each file change:
+/// let reloaded = load()?;
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+///
+/// Whole-file encrypted sources (`*.enc`) are never cached, so decrypted
+/// content does not outlive a build.
+#[derive(Debug, Default)]
+pub struct ParseCache {
+    files: Mutex<HashMap<PathBuf, CachedFile>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl ParseCache {
+    /// An empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads answered from the cache without parsing.
+    pub fn hits(&self) -> u64 {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Loads that had to parse the file.
+    pub fn misses(&self) -> u64 {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    /// Number of cached files.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Whether no file is cached.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Drop every cached file.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Load `path` like [`loader::load_file`], reusing the previous parse
+    /// when the file is unchanged.
+    pub(crate) fn load(&self, path: &Path, config: &LoaderConfig) -> ConfigResult<AnnotatedValue> {
+        let (validated_path, metadata) = loader::check_file(path, config)?;
+        let stamp = FileStamp::of(&metadata);
+        if let Some(cached) = self.lock().get(&validated_path) {
+            if stamp.is_some() && cached.stamp == stamp {
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let content = std::fs::read_to_string(&validated_path).map_err(ConfigError::IoError)?;
+        let digest: [u8; 32] = Sha256::digest(content.as_bytes()).into();
+        if let Some(cached) = self.lock().get_mut(&validated_path) {
+            if cached.digest == digest {
+                // Touched but not edited: trust the new stamp from now on.
+                cached.stamp = stamp;
+                self.hits.fetch_add(1, Ordering::Relaxed);
+                return Ok(cached.value.clone());
+            }
+        }
+
+        let value = loader::parse_file(&validated_path, &content)?;
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        self.lock().insert(
+            validated_path,
+            CachedFile {
+                stamp,
+                digest,
+                value: value.clone(),
+            },
+        );
+        Ok(value)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, CachedFile>> {
+        // The map holds no invariant a panicking thread could break.
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(all(test, feature = "toml"))]
+mod tests {
+    use super::*;
+    use crate::ConfigBuilder;
+
+    #[test]
+    fn test_unchanged_files_are_not_parsed_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app.toml");
+        let flags = dir.path().join("flags.toml");
+        std::fs::write(&app, "port = 8080\n").unwrap();
+        std::fs::write(&flags, "beta = false\n").unwrap();
+
+        let cache = Arc::new(ParseCache::new());
+        let build = || {
+            ConfigBuilder::<serde_json::Value>::new()
+                .allow_absolute_paths()
+                .file(&app)
+                .with_parse_cache(cache.clone())
+                .file(&flags)
+                .build()
+                .unwrap()
+        };
+
+        assert_eq!(build()["port"], 8080);
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (0, 2, 2));
+
+        build();
+        assert_eq!((cache.hits(), cache.misses()), (2, 2));
+
+        std::fs::write(&flags, "beta = true\n").unwrap();
+        assert_eq!(build()["beta"], true);
+        assert_eq!((cache.hits(), cache.misses()), (3, 3));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_settled_files_are_not_read_again() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("app.toml");
+        let hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        let write = |content: &str| {
+            std::fs::write(&app, content).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&app)
+                .unwrap()
+                .set_modified(hour_ago)
+                .unwrap();
+        };
+
+        let cache = Arc::new(ParseCache::new());
+        let build = || {
+            ConfigBuilder::<serde_json::Value>::new()
+                .allow_absolute_paths()
+                .file(&app)
+                .with_parse_cache(cache.clone())
+                .build()
+                .unwrap()
+        };
+
+        write("port = 8080\n");
+        assert_eq!(build()["port"], 8080);
+        assert_eq!((cache.hits(), cache.misses()), (0, 1));
+
+        // Same mtime and length: the stamp answers without reading the file.
+        write("port = 9090\n");
+        assert_eq!(build()["port"], 8080);
+        assert_eq!((cache.hits(), cache.misses()), (1, 1));
+
+        write("port = 10000\n");
+        assert_eq!(build()["port"], 10000);
+        assert_eq!((cache.hits(), cache.misses()), (1, 2));
+    }
+}
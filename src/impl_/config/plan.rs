@@ -0,0 +1,128 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Dry-run description of a build, produced by
+//! [`ConfigBuilder::plan`](super::ConfigBuilder::plan).
+
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
+
+use crate::interface::Source;
+use crate::types::SourceKind;
+
+/// What a build would load, without loading it.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct LoadPlan {
+    /// Locations searched by [`discover`](super::ConfigBuilder::discover),
+    /// whether or not a file exists there.
+    pub search_paths: Vec<PathBuf>,
+    /// Sources in merge order: later entries override earlier ones.
+    pub sources: Vec<PlannedSource>,
+    /// Remote providers [`build_async`](super::ConfigBuilder::build_async)
+    /// would fetch.
+    pub remote_providers: Vec<PlannedProvider>,
+    /// Names of the environment variables that would apply, sorted. Values
+    /// are never recorded.
+    pub env_vars: Vec<String>,
+    /// The selected profile.
+    pub profile: Option<String>,
+}
+
+impl LoadPlan {
+    /// Every file the sources would read, in merge order.
+    pub fn files(&self) -> Vec<&Path> {
+        self.sources
+            .iter()
+            .flat_map(|source| source.files.iter().map(PathBuf::as_path))
+            .collect()
+    }
+}
+
+/// A source of a [`LoadPlan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedSource {
+    pub name: String,
+    pub kind: SourceKind,
+    pub priority: u8,
+    pub optional: bool,
+    /// The path the source was configured with, for file sources.
+    pub path: Option<PathBuf>,
+    /// Existing files the source would read, including `__include`
+    /// fragments and glob or directory matches.
+    pub files: Vec<PathBuf>,
+}
+
+impl PlannedSource {
+    pub(crate) fn from_source(source: &dyn Source) -> Self {
+        Self {
+            name: source.name().to_string(),
+            kind: source.source_kind(),
+            priority: source.priority(),
+            optional: source.is_optional(),
+            path: source.file_path().map(Path::to_path_buf),
+            files: source
+                .watch_paths()
+                .into_iter()
+                .filter(|path| path.is_file())
+                .collect(),
+        }
+    }
+}
+
+/// A remote provider of a [`LoadPlan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedProvider {
+    pub name: String,
+    pub priority: u8,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::ConfigBuilder;
+    use crate::types::SourceKind;
+
+    #[test]
+    fn test_plan_lists_sources_without_loading() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("app.toml");
+        // Invalid content: planning must not parse it.
+        std::fs::write(&present, "port = ").unwrap();
+        let missing = dir.path().join("local.toml");
+        std::env::set_var("CONFERS_PLAN_TEST_PORT", "1");
+
+        let plan = ConfigBuilder::<serde_json::Value>::new()
+            .allow_absolute_paths()
+            .env_prefix("CONFERS_PLAN_TEST_")
+            .file(&present)
+            .file_optional(&missing)
+            .with_profile("dev")
+            .plan();
+
+        let kinds: Vec<SourceKind> = plan.sources.iter().map(|s| s.kind).collect();
+        assert_eq!(
+            kinds,
+            [SourceKind::File, SourceKind::File, SourceKind::Environment]
+        );
+        assert_eq!(plan.files(), [present.as_path()]);
+        assert!(plan.sources[1].optional);
+        assert_eq!(plan.sources[1].path.as_deref(), Some(missing.as_path()));
+        assert_eq!(plan.env_vars, ["CONFERS_PLAN_TEST_PORT"]);
+        assert_eq!(plan.profile.as_deref(), Some("dev"));
+        std::env::remove_var("CONFERS_PLAN_TEST_PORT");
+    }
+
+    #[test]
+    fn test_plan_records_search_paths() {
+        let plan = ConfigBuilder::<serde_json::Value>::new()
+            .discover("confers-plan-test")
+            .plan();
+        assert_eq!(
+            plan.search_paths,
+            crate::discovery_paths("confers-plan-test")
+        );
+        assert!(plan.sources.is_empty());
+    }
+}
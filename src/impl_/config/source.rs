@@ -5,14 +5,39 @@
 //! This module provides concrete implementations: FileSource, EnvSource,
 //! MemorySource, DefaultSource.
 
+use super::include;
 use crate::error::{ConfigError, ConfigResult};
 use crate::impl_::loader::{self, Format};
+use crate::impl_::merger::MergeEngine;
 use crate::interface::Source;
 use crate::types::{AnnotatedValue, ConfigValue, SourceId, SourceKind};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+#[cfg(feature = "encryption")]
+use crate::secret::ConfigEncryption;
+
+/// `__include` switch shared between a builder and its file sources, so it
+/// applies to files added before it was turned on.
+pub(crate) type IncludeFlag = Arc<AtomicBool>;
+
+/// Encryption settings shared between a builder and its file sources.
+///
+/// The builder fills the slot when the configuration is built, so sources can
+/// be added before the encryption settings are known.
+#[cfg(feature = "encryption")]
+pub(crate) type EncryptionSlot = Arc<std::sync::OnceLock<Arc<ConfigEncryption>>>;
+
+/// Schema shared between a builder and its env sources, used to coerce
+/// variables to the type of the key they set.
+#[cfg(feature = "schema")]
+pub(crate) type EnvSchemaSlot = Arc<std::sync::OnceLock<Arc<serde_json::Value>>>;
+
+#[cfg(feature = "parse-cache")]
+use super::parse_cache::ParseCacheSlot;
+
 /// File-based configuration source.
 #[derive(Debug)]
 pub struct FileSource {
@@ -28,6 +53,14 @@ pub struct FileSource {
     source_id: SourceId,
     /// Loader configuration for security settings.
     loader_config: loader::LoaderConfig,
+    /// Decryption for whole-file encrypted sources (`*.enc`).
+    #[cfg(feature = "encryption")]
+    encryption: EncryptionSlot,
+    /// Whether the `__include` directive is honoured.
+    includes: IncludeFlag,
+    /// Parsed files reused across builds.
+    #[cfg(feature = "parse-cache")]
+    parse_cache: ParseCacheSlot,
 }
 
 impl FileSource {
@@ -42,6 +75,11 @@ impl FileSource {
             optional: false,
             source_id,
             loader_config: loader::LoaderConfig::default(),
+            #[cfg(feature = "encryption")]
+            encryption: EncryptionSlot::default(),
+            includes: IncludeFlag::default(),
+            #[cfg(feature = "parse-cache")]
+            parse_cache: ParseCacheSlot::default(),
         }
     }
 
@@ -75,10 +113,110 @@ impl FileSource {
         self
     }
 
+    /// Check the permissions and owner of the file, and of any file it
+    /// includes, before loading it.
+    pub fn with_permission_policy(mut self, policy: loader::PermissionPolicy) -> Self {
+        self.loader_config = self.loader_config.permission_policy(policy);
+        self
+    }
+
+    /// Decrypt the file with `encryption` if it is a whole-file encrypted
+    /// source (e.g. `app.toml.enc`).
+    #[cfg(feature = "encryption")]
+    pub fn with_encryption(mut self, encryption: Arc<ConfigEncryption>) -> Self {
+        let slot = EncryptionSlot::default();
+        let _ = slot.set(encryption);
+        self.encryption = slot;
+        self
+    }
+
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_encryption_slot(mut self, slot: EncryptionSlot) -> Self {
+        self.encryption = slot;
+        self
+    }
+
+    /// Merge the fragment files listed under the top-level `__include` key
+    /// (see [`INCLUDE_KEY`](super::include::INCLUDE_KEY)).
+    pub fn with_includes(mut self, enabled: bool) -> Self {
+        self.includes = Arc::new(AtomicBool::new(enabled));
+        self
+    }
+
+    pub(crate) fn with_include_flag(mut self, flag: IncludeFlag) -> Self {
+        self.includes = flag;
+        self
+    }
+
+    #[cfg(feature = "parse-cache")]
+    pub(crate) fn with_parse_cache_slot(mut self, slot: ParseCacheSlot) -> Self {
+        self.parse_cache = slot;
+        self
+    }
+
     /// Get the file path.
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    fn includes_enabled(&self) -> bool {
+        self.includes.load(Ordering::Relaxed)
+    }
+
+    /// Load a single file, without following includes.
+    fn load_path(&self, path: &Path) -> ConfigResult<AnnotatedValue> {
+        #[cfg(feature = "encryption")]
+        if ConfigEncryption::is_encrypted_file(path) {
+            return self.load_encrypted(path);
+        }
+
+        #[cfg(feature = "parse-cache")]
+        if let Some(cache) = self.parse_cache.get() {
+            return cache.load(path, &self.loader_config);
+        }
+        loader::load_file(path, &self.loader_config)
+    }
+
+    /// Load a whole-file encrypted source; the format comes from the inner
+    /// extension (`app.toml.enc` is TOML).
+    #[cfg(feature = "encryption")]
+    fn load_encrypted(&self, path: &Path) -> ConfigResult<AnnotatedValue> {
+        let encryption = self
+            .encryption
+            .get()
+            .ok_or_else(|| ConfigError::DecryptionFailed {
+                message: format!(
+                    "'{}' is encrypted but no encryption is configured",
+                    path.display()
+                ),
+            })?;
+
+        let (validated_path, content) = loader::read_file(path, &self.loader_config)?;
+        let plaintext = encryption.decrypt_file_contents(&content).map_err(|e| {
+            ConfigError::DecryptionFailed {
+                message: format!("'{}': {}", path.display(), e),
+            }
+        })?;
+
+        let format = self
+            .format
+            .or_else(|| loader::detect_format_from_path(&validated_path.with_extension("")))
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "unknown".into(),
+                message: format!(
+                    "Cannot determine format of encrypted file {}",
+                    path.display()
+                ),
+                location: None,
+                source: None,
+            })?;
+        loader::parse_content(
+            plaintext.expose(),
+            format,
+            self.source_id.clone(),
+            Some(&validated_path),
+        )
+    }
 }
 
 impl Source for FileSource {
@@ -97,7 +235,12 @@ impl Source for FileSource {
             });
         }
 
-        loader::load_file(&self.path, &self.loader_config).map(|v| v.with_priority(self.priority))
+        let value = if self.includes_enabled() {
+            include::load_with_includes(&self.path, &|p| self.load_path(p))?.0
+        } else {
+            self.load_path(&self.path)?
+        };
+        Ok(value.with_priority(self.priority))
     }
 
     fn priority(&self) -> u8 {
@@ -122,6 +265,414 @@ impl Source for FileSource {
     fn file_path(&self) -> Option<&Path> {
         Some(&self.path)
     }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        if !self.includes_enabled() {
+            return vec![self.path.clone()];
+        }
+        match include::load_with_includes(&self.path, &|p| self.load_path(p)) {
+            Ok((_, files)) => files,
+            Err(_) => vec![self.path.clone()],
+        }
+    }
+}
+
+/// Settings shared by the [`FileSource`]s that multi-file sources create.
+#[derive(Debug, Default)]
+struct FileSettings {
+    /// Loader configuration applied to every file.
+    loader_config: loader::LoaderConfig,
+    /// Decryption for `*.enc` files.
+    #[cfg(feature = "encryption")]
+    encryption: EncryptionSlot,
+    /// Whether the `__include` directive is honoured.
+    includes: IncludeFlag,
+    /// Parsed files reused across builds.
+    #[cfg(feature = "parse-cache")]
+    parse_cache: ParseCacheSlot,
+}
+
+impl FileSettings {
+    fn file_source(&self, path: PathBuf, priority: u8) -> FileSource {
+        let source = FileSource::new(path)
+            .with_priority(priority)
+            .with_loader_config(self.loader_config.clone())
+            .with_include_flag(self.includes.clone());
+        #[cfg(feature = "encryption")]
+        let source = source.with_encryption_slot(self.encryption.clone());
+        #[cfg(feature = "parse-cache")]
+        let source = source.with_parse_cache_slot(self.parse_cache.clone());
+        source
+    }
+
+    /// Load `files` in order, later files overriding earlier ones.
+    fn merge_files(
+        &self,
+        files: Vec<PathBuf>,
+        priority: u8,
+        source_id: &SourceId,
+    ) -> ConfigResult<AnnotatedValue> {
+        let engine = MergeEngine::new();
+        let mut merged = empty_map(source_id);
+        for path in files {
+            let value = self.file_source(path, priority).collect()?;
+            merged = engine.merge(&merged, &value)?;
+        }
+        Ok(merged.with_priority(priority))
+    }
+
+    fn watch_paths(&self, files: Vec<PathBuf>) -> Vec<PathBuf> {
+        files
+            .into_iter()
+            .flat_map(|file| self.file_source(file, 0).watch_paths())
+            .collect()
+    }
+}
+
+fn empty_map(source_id: &SourceId) -> AnnotatedValue {
+    AnnotatedValue::new(
+        ConfigValue::Map(Arc::new(indexmap::IndexMap::new())),
+        source_id.clone(),
+        "",
+    )
+}
+
+/// Drop-in directory source (`conf.d`).
+///
+/// Loads every file with a recognized extension in the directory, in lexical
+/// file name order, and merges them so later files override earlier ones.
+/// Subdirectories are not descended into.
+#[derive(Debug)]
+pub struct ConfigDirSource {
+    /// Path to the directory.
+    path: PathBuf,
+    /// Priority of this source.
+    priority: u8,
+    /// Whether a missing directory is tolerated.
+    optional: bool,
+    /// Source ID for tracking.
+    source_id: SourceId,
+    /// Settings for the files in the directory.
+    files: FileSettings,
+}
+
+impl ConfigDirSource {
+    /// Create a new directory source.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        let source_id = SourceId::new(path.file_name().and_then(|n| n.to_str()).unwrap_or("dir"));
+        Self {
+            path,
+            priority: 0,
+            optional: false,
+            source_id,
+            files: FileSettings::default(),
+        }
+    }
+
+    /// Set the priority.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Treat a missing directory as empty.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Allow absolute paths (use with caution, mainly for testing).
+    pub fn allow_absolute_paths(mut self) -> Self {
+        self.files.loader_config = self.files.loader_config.allow_absolute();
+        self
+    }
+
+    /// Set custom loader configuration.
+    pub fn with_loader_config(mut self, config: loader::LoaderConfig) -> Self {
+        self.files.loader_config = config;
+        self
+    }
+
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_encryption_slot(mut self, slot: EncryptionSlot) -> Self {
+        self.files.encryption = slot;
+        self
+    }
+
+    pub(crate) fn with_include_flag(mut self, flag: IncludeFlag) -> Self {
+        self.files.includes = flag;
+        self
+    }
+
+    #[cfg(feature = "parse-cache")]
+    pub(crate) fn with_parse_cache_slot(mut self, slot: ParseCacheSlot) -> Self {
+        self.files.parse_cache = slot;
+        self
+    }
+
+    /// Get the directory path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Recognized configuration files in the directory, in load order.
+    pub fn files(&self) -> ConfigResult<Vec<PathBuf>> {
+        let entries = std::fs::read_dir(&self.path).map_err(|e| ConfigError::FileNotFound {
+            filename: self.path.clone(),
+            source: Some(e),
+        })?;
+        let mut files = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_file() && Self::is_config_file(&path) {
+                files.push(path);
+            }
+        }
+        files.sort_by(|a, b| a.file_name().cmp(&b.file_name()));
+        Ok(files)
+    }
+
+    fn is_config_file(path: &Path) -> bool {
+        #[cfg(feature = "encryption")]
+        if ConfigEncryption::is_encrypted_file(path) {
+            return loader::detect_format_from_path(&path.with_extension("")).is_some();
+        }
+        loader::detect_format_from_path(path).is_some()
+    }
+}
+
+impl Source for ConfigDirSource {
+    fn collect(&self) -> ConfigResult<AnnotatedValue> {
+        if !self.path.is_dir() {
+            if self.optional {
+                return Ok(empty_map(&self.source_id));
+            }
+            return Err(ConfigError::FileNotFound {
+                filename: self.path.clone(),
+                source: None,
+            });
+        }
+
+        self.files
+            .merge_files(self.files()?, self.priority, &self.source_id)
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("dir")
+    }
+
+    fn source_kind(&self) -> SourceKind {
+        SourceKind::File
+    }
+
+    fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    fn file_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+
+    /// The directory itself, so files added to it are picked up, plus every
+    /// file currently loaded from it.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = vec![self.path.clone()];
+        paths.extend(self.files.watch_paths(self.files().unwrap_or_default()));
+        paths
+    }
+}
+
+/// File source whose path is a glob pattern (`configs/*.yaml`).
+///
+/// The pattern is expanded on every load. Matches are merged in sorted path
+/// order, so later files override earlier ones. A pattern that matches no
+/// files is an error unless the source is optional.
+#[derive(Debug)]
+pub struct FileGlobSource {
+    /// The glob pattern.
+    pattern: PathBuf,
+    /// Priority of this source.
+    priority: u8,
+    /// Whether zero matches are tolerated.
+    optional: bool,
+    /// Source ID for tracking.
+    source_id: SourceId,
+    /// Settings for the matched files.
+    files: FileSettings,
+}
+
+impl FileGlobSource {
+    /// Create a new glob source.
+    pub fn new(pattern: impl Into<PathBuf>) -> Self {
+        let pattern = pattern.into();
+        let source_id = SourceId::new(pattern.to_string_lossy());
+        Self {
+            pattern,
+            priority: 0,
+            optional: false,
+            source_id,
+            files: FileSettings::default(),
+        }
+    }
+
+    /// Set the priority.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Treat a pattern with no matches as empty.
+    pub fn optional(mut self) -> Self {
+        self.optional = true;
+        self
+    }
+
+    /// Allow absolute paths (use with caution, mainly for testing).
+    pub fn allow_absolute_paths(mut self) -> Self {
+        self.files.loader_config = self.files.loader_config.allow_absolute();
+        self
+    }
+
+    /// Set custom loader configuration.
+    pub fn with_loader_config(mut self, config: loader::LoaderConfig) -> Self {
+        self.files.loader_config = config;
+        self
+    }
+
+    #[cfg(feature = "encryption")]
+    pub(crate) fn with_encryption_slot(mut self, slot: EncryptionSlot) -> Self {
+        self.files.encryption = slot;
+        self
+    }
+
+    pub(crate) fn with_include_flag(mut self, flag: IncludeFlag) -> Self {
+        self.files.includes = flag;
+        self
+    }
+
+    #[cfg(feature = "parse-cache")]
+    pub(crate) fn with_parse_cache_slot(mut self, slot: ParseCacheSlot) -> Self {
+        self.files.parse_cache = slot;
+        self
+    }
+
+    /// Get the glob pattern.
+    pub fn pattern(&self) -> &Path {
+        &self.pattern
+    }
+
+    /// Files currently matching the pattern, in load order.
+    pub fn files(&self) -> ConfigResult<Vec<PathBuf>> {
+        include::glob_files(&self.pattern, "file")
+    }
+
+    /// Longest leading part of the pattern without glob metacharacters.
+    fn base_dir(&self) -> PathBuf {
+        self.pattern
+            .components()
+            .take_while(|c| !c.as_os_str().to_str().is_some_and(include::is_glob))
+            .collect()
+    }
+}
+
+impl Source for FileGlobSource {
+    fn collect(&self) -> ConfigResult<AnnotatedValue> {
+        let files = self.files()?;
+        if files.is_empty() {
+            if self.optional {
+                return Ok(empty_map(&self.source_id));
+            }
+            return Err(ConfigError::FileNotFound {
+                filename: self.pattern.clone(),
+                source: None,
+            });
+        }
+
+        self.files
+            .merge_files(files, self.priority, &self.source_id)
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn name(&self) -> &str {
+        self.source_id.as_str()
+    }
+
+    fn source_kind(&self) -> SourceKind {
+        SourceKind::File
+    }
+
+    fn is_optional(&self) -> bool {
+        self.optional
+    }
+
+    /// The directory the pattern is rooted in, so new matches are picked up,
+    /// plus every file currently matched.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        let base = self.base_dir();
+        if base.is_dir() {
+            paths.push(base);
+        }
+        paths.extend(self.files.watch_paths(self.files().unwrap_or_default()));
+        paths
+    }
+}
+
+/// A source merged at a priority other than its own.
+pub(crate) struct PrioritizedSource {
+    inner: Box<dyn Source>,
+    priority: u8,
+}
+
+impl PrioritizedSource {
+    pub(crate) fn new(inner: Box<dyn Source>, priority: u8) -> Self {
+        Self { inner, priority }
+    }
+}
+
+impl Source for PrioritizedSource {
+    fn collect(&self) -> ConfigResult<AnnotatedValue> {
+        Ok(self.inner.collect()?.with_priority(self.priority))
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn source_kind(&self) -> SourceKind {
+        self.inner.source_kind()
+    }
+
+    fn is_optional(&self) -> bool {
+        self.inner.is_optional()
+    }
+
+    fn file_path(&self) -> Option<&Path> {
+        self.inner.file_path()
+    }
+
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.inner.watch_paths()
+    }
+
+    fn env_var_names(&self) -> Vec<String> {
+        self.inner.env_var_names()
+    }
 }
 
 /// Environment variable configuration source.
@@ -139,6 +690,9 @@ pub struct EnvSource {
     file_suffix_enabled: bool,
     /// The file suffix for Docker secrets convention (default: "_FILE").
     file_suffix: &'static str,
+    /// Schema of the configuration, used to coerce values.
+    #[cfg(feature = "schema")]
+    schema: EnvSchemaSlot,
 }
 
 impl EnvSource {
@@ -151,6 +705,8 @@ impl EnvSource {
             source_id: SourceId::new("env"),
             file_suffix_enabled: true,
             file_suffix: "_FILE",
+            #[cfg(feature = "schema")]
+            schema: EnvSchemaSlot::default(),
         }
     }
 
@@ -163,10 +719,17 @@ impl EnvSource {
             source_id: SourceId::new("env"),
             file_suffix_enabled: true,
             file_suffix: "_FILE",
+            #[cfg(feature = "schema")]
+            schema: EnvSchemaSlot::default(),
         }
     }
 
-    /// Set the separator for nested keys.
+    /// Set the separator for nested keys (default: `_`).
+    ///
+    /// With `__`, `APP_SERVER__HTTP__PORT` sets `server.http.port` while the
+    /// single underscores of `APP_MAX_CONNECTIONS` stay in `max_connections`.
+    /// Variables with an empty segment, such as `APP_SERVER____PORT`, are
+    /// ignored.
     pub fn separator(mut self, separator: impl Into<String>) -> Self {
         self.separator = separator.into();
         self
@@ -190,6 +753,40 @@ impl EnvSource {
         self
     }
 
+    /// Coerce each variable to the type `schema`, the JSON Schema of the
+    /// configuration, gives the key it sets.
+    ///
+    /// A boolean key accepts `true`/`false`, `1`/`0`, `yes`/`no` and
+    /// `on`/`off`, an array key a comma-separated list or a JSON array, and a
+    /// string key keeps the value as written, so `0123` is not read as a
+    /// number. A value of the wrong type fails [`collect`](Source::collect)
+    /// with a [`ConfigError::InvalidValue`] naming the variable. Keys the
+    /// schema does not describe are inferred as without a schema.
+    #[cfg(feature = "schema")]
+    pub fn with_schema(mut self, schema: serde_json::Value) -> Self {
+        self.schema = Arc::new(std::sync::OnceLock::from(Arc::new(schema)));
+        self
+    }
+
+    /// Share `slot` with the builder that adds this source.
+    #[cfg(feature = "schema")]
+    pub(crate) fn with_schema_slot(mut self, slot: EnvSchemaSlot) -> Self {
+        self.schema = slot;
+        self
+    }
+
+    /// The value of variable `var`, which sets `config_path`.
+    fn coerce(&self, raw: &str, var: &str, config_path: &str) -> ConfigResult<ConfigValue> {
+        super::coerce::coerce_env_value(
+            raw,
+            var,
+            config_path,
+            &self.source_id,
+            #[cfg(feature = "schema")]
+            self.schema.get().map(|schema| schema.as_ref()),
+        )
+    }
+
     /// Parse an environment variable name into a config path.
     fn parse_key(&self, env_key: &str) -> Option<String> {
         let key = if let Some(ref prefix) = self.prefix {
@@ -220,8 +817,28 @@ impl EnvSource {
                 key
             };
 
-        // Convert UPPER_SNAKE_CASE to lower.snake.case
-        Some(actual_key.to_lowercase().replace(&self.separator, "."))
+        // A prefix given without its trailing separator, e.g. `APP` for
+        // `APP_PORT` or `APP__SERVER__PORT`
+        let actual_key = match &self.prefix {
+            Some(_) => actual_key
+                .strip_prefix(self.separator.as_str())
+                .or_else(|| actual_key.strip_prefix('_'))
+                .unwrap_or(actual_key),
+            None => actual_key,
+        };
+
+        // Convert SERVER__HTTP_PORT to server.http_port with separator `__`
+        if self.separator.is_empty() {
+            return Some(actual_key.to_lowercase());
+        }
+        let segments: Vec<String> = actual_key
+            .split(self.separator.as_str())
+            .map(str::to_lowercase)
+            .collect();
+        if segments.iter().any(String::is_empty) {
+            return None;
+        }
+        Some(segments.join("."))
     }
 
     /// Resolve the value, handling _FILE suffix mode for Docker secrets.
@@ -326,7 +943,7 @@ impl Source for EnvSource {
                     if let Some(config_path) = self.parse_key(&item.0) {
                         let resolved = self.resolve_value(&item.1, &item.0)?;
                         let value = AnnotatedValue::new(
-                            Self::infer_config_value(&resolved),
+                            self.coerce(&resolved, &item.0, &config_path)?,
                             self.source_id.clone(),
                             std::sync::Arc::from(config_path.as_str()),
                         )
@@ -343,7 +960,7 @@ impl Source for EnvSource {
             if let Some(config_path) = self.parse_key(&key) {
                 let resolved = self.resolve_value(&value, &key)?;
                 let value = AnnotatedValue::new(
-                    Self::infer_config_value(&resolved),
+                    self.coerce(&resolved, &key, &config_path)?,
                     self.source_id.clone(),
                     std::sync::Arc::from(config_path.as_str()),
                 )
@@ -373,6 +990,20 @@ impl Source for EnvSource {
     fn source_kind(&self) -> SourceKind {
         SourceKind::Environment
     }
+
+    /// Variables from `.env` and the process environment that map to a key.
+    fn env_var_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = Vec::new();
+        #[cfg(feature = "env")]
+        if let Ok(iter) = dotenvy::dotenv_iter() {
+            names.extend(iter.flatten().map(|(name, _)| name));
+        }
+        names.extend(std::env::vars_os().filter_map(|(name, _)| name.into_string().ok()));
+        names.retain(|name| self.parse_key(name).is_some());
+        names.sort();
+        names.dedup();
+        names
+    }
 }
 
 impl EnvSource {
@@ -419,7 +1050,7 @@ impl EnvSource {
     }
 
     /// Insert a value into a nested map structure.
-    fn insert_nested(
+    pub(crate) fn insert_nested(
         map: &mut indexmap::IndexMap<std::sync::Arc<str>, AnnotatedValue>,
         parts: &[&str],
         value: AnnotatedValue,
@@ -507,9 +1138,10 @@ impl MemorySource {
         self
     }
 
-    /// Set the source name.
+    /// Set the source name used in provenance and error messages.
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = name.into();
+        self.source_id = SourceId::new(self.name.as_str());
         self
     }
 }
@@ -557,6 +1189,72 @@ impl Source for MemorySource {
     }
 }
 
+/// In-memory configuration text in a given format.
+///
+/// Lets tests and embedded tools supply TOML/YAML/JSON content without a
+/// file. The text is parsed when the chain is collected, and the source takes
+/// part in priority ordering like a file source.
+#[derive(Debug)]
+pub struct StringSource {
+    /// Configuration text.
+    content: String,
+    /// Format of the text.
+    format: Format,
+    /// Priority of this source.
+    priority: u8,
+    /// Source ID for tracking.
+    source_id: SourceId,
+}
+
+impl StringSource {
+    /// Create a new string source.
+    pub fn new(content: impl Into<String>, format: Format) -> Self {
+        let source_id = SourceId::new(format!("{}-string", format.to_string().to_lowercase()));
+        Self {
+            content: content.into(),
+            format,
+            priority: 0,
+            source_id,
+        }
+    }
+
+    /// Set the priority.
+    pub fn with_priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Set the source name used in provenance and error messages.
+    pub fn with_name(mut self, name: impl Into<Arc<str>>) -> Self {
+        self.source_id = SourceId::new(name);
+        self
+    }
+
+    /// Get the format.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+}
+
+impl Source for StringSource {
+    fn collect(&self) -> ConfigResult<AnnotatedValue> {
+        loader::parse_content(&self.content, self.format, self.source_id.clone(), None)
+            .map(|v| v.with_priority(self.priority))
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn name(&self) -> &str {
+        self.source_id.as_str()
+    }
+
+    fn source_kind(&self) -> SourceKind {
+        SourceKind::Memory
+    }
+}
+
 /// Default value source.
 #[derive(Debug)]
 pub struct DefaultSource {
@@ -800,6 +1498,52 @@ mod tests {
         let _ = source.collect();
     }
 
+    #[test]
+    fn test_env_source_double_underscore_separator() {
+        let source = EnvSource::with_prefix("APP").separator("__");
+        assert_eq!(
+            source.parse_key("APP_SERVER__HTTP__PORT").as_deref(),
+            Some("server.http.port")
+        );
+        assert_eq!(
+            source.parse_key("APP__SERVER__MAX_CONNECTIONS").as_deref(),
+            Some("server.max_connections")
+        );
+        assert_eq!(source.parse_key("APP_SERVER____PORT"), None);
+        assert_eq!(source.parse_key("APP_SERVER__"), None);
+        assert_eq!(source.parse_key("OTHER_SERVER__PORT"), None);
+
+        let source = EnvSource::with_prefix("APP");
+        assert_eq!(source.parse_key("APP_DB_HOST").as_deref(), Some("db.host"));
+    }
+
+    #[serial_test::serial]
+    #[test]
+    fn test_env_source_collect_nested_with_separator() {
+        std::env::set_var("NESTEDCFG_SERVER__HTTP__PORT", "8080");
+        std::env::set_var("NESTEDCFG_SERVER__MAX_CONNECTIONS", "64");
+
+        let source = EnvSource::with_prefix("NESTEDCFG_").separator("__");
+        let result = source.collect();
+
+        std::env::remove_var("NESTEDCFG_SERVER__HTTP__PORT");
+        std::env::remove_var("NESTEDCFG_SERVER__MAX_CONNECTIONS");
+
+        let result = result.expect("collect should succeed");
+        assert_eq!(
+            result
+                .get_path("server.http.port")
+                .and_then(|v| v.inner.as_i64()),
+            Some(8080)
+        );
+        assert_eq!(
+            result
+                .get_path("server.max_connections")
+                .and_then(|v| v.inner.as_i64()),
+            Some(64)
+        );
+    }
+
     #[test]
     fn test_env_source_with_file_suffix_disabled() {
         let source = EnvSource::with_prefix("X_").with_file_suffix(false);
@@ -839,6 +1583,77 @@ mod tests {
         assert_eq!(source.path(), Path::new("/some/path/config.json"));
     }
 
+    #[test]
+    fn test_config_dir_source_missing_optional() {
+        let source = ConfigDirSource::new("/nonexistent/conf.d").optional();
+        assert!(source.collect().unwrap().is_map());
+        assert_eq!(
+            source.watch_paths(),
+            vec![PathBuf::from("/nonexistent/conf.d")]
+        );
+    }
+
+    #[test]
+    fn test_config_dir_source_lists_recognized_files_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in ["b.yaml", "a.json", "c.toml", "notes.txt"] {
+            std::fs::write(dir.path().join(name), "").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("nested.toml")).unwrap();
+
+        let files = ConfigDirSource::new(dir.path()).files().unwrap();
+        let names: Vec<_> = files
+            .iter()
+            .map(|p| p.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(names, ["a.json", "b.yaml", "c.toml"]);
+    }
+
+    #[cfg(all(feature = "toml", feature = "json"))]
+    #[test]
+    fn test_file_glob_source_merges_sorted_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("b.json"), r#"{"port": 2, "b": true}"#).unwrap();
+        std::fs::write(dir.path().join("a.toml"), "port = 1\na = true\n").unwrap();
+
+        let source = FileGlobSource::new(dir.path().join("*.*")).allow_absolute_paths();
+        let value = source.collect().unwrap();
+        let ConfigValue::Map(map) = &value.inner else {
+            panic!("expected map");
+        };
+        assert_eq!(map["port"].inner, ConfigValue::I64(2));
+        assert!(map.contains_key("a") && map.contains_key("b"));
+        assert_eq!(source.watch_paths().len(), 3);
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_string_source_parses_on_collect() {
+        let source = StringSource::new("server:\n  port: 80\n", Format::Yaml)
+            .with_priority(5)
+            .with_name("embedded");
+        assert_eq!(source.name(), "embedded");
+        assert_eq!(source.source_kind(), SourceKind::Memory);
+
+        let value = source.collect().unwrap();
+        assert_eq!(value.priority, 5);
+        let ConfigValue::Map(map) = &value.inner else {
+            panic!("expected map");
+        };
+        assert!(map.contains_key("server"));
+    }
+
+    #[test]
+    fn test_file_glob_source_zero_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        let pattern = dir.path().join("*.toml");
+
+        let err = FileGlobSource::new(&pattern).collect().unwrap_err();
+        assert!(matches!(err, ConfigError::FileNotFound { .. }));
+        let value = FileGlobSource::new(&pattern).optional().collect().unwrap();
+        assert!(value.is_map());
+    }
+
     #[test]
     fn test_file_source_missing_required_error() {
         let source = FileSource::new("/nonexistent/required.toml");
@@ -850,6 +1665,34 @@ mod tests {
         ));
     }
 
+    #[cfg(feature = "encryption")]
+    #[test]
+    fn test_file_source_encrypted_file() {
+        use crate::secret::{CipherAlgorithm, SecretBytes};
+
+        let encryption =
+            Arc::new(ConfigEncryption::new().with_key(SecretBytes::new(vec![9u8; 32])));
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml.enc");
+        let encrypted = encryption
+            .encrypt_file_contents(CipherAlgorithm::XChaCha20, "port = 8080\n")
+            .unwrap();
+        std::fs::write(&path, encrypted).unwrap();
+
+        let locked = FileSource::new(&path).allow_absolute_paths();
+        assert!(matches!(
+            locked.collect().unwrap_err(),
+            ConfigError::DecryptionFailed { .. }
+        ));
+
+        let source = FileSource::new(&path)
+            .allow_absolute_paths()
+            .with_encryption(encryption);
+        let value = source.collect().unwrap();
+        let port = value.inner.as_map().unwrap().get("port").unwrap();
+        assert_eq!(port.inner, ConfigValue::I64(8080));
+    }
+
     #[test]
     fn test_file_source_new_no_file_name() {
         // Path with no file_name component → source_id defaults to "file"
@@ -874,8 +1717,15 @@ mod tests {
 
     #[test]
     fn test_memory_source_with_name() {
-        let source = MemorySource::new().with_name("custom");
+        let source = MemorySource::new()
+            .with_name("custom")
+            .set("key", ConfigValue::string("val"));
         assert_eq!(source.name(), "custom");
+        let collected = source.collect().unwrap();
+        assert_eq!(
+            collected.inner.as_map().unwrap()["key"].source.as_str(),
+            "custom"
+        );
     }
 
     #[test]
@@ -1050,6 +1900,7 @@ mod tests {
 
     // ===== infer_config_value (fix-0.4.1 Bug 2) =====
 
+    #[allow(clippy::approx_constant)]
     #[test]
     fn test_infer_config_value() {
         // bool
@@ -0,0 +1,412 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Unknown key detection for [`ConfigBuilder::with_deny_unknown`].
+//!
+//! The merged JSON tree is deserialized through [`Tracked`], a thin wrapper
+//! around `serde_json`'s `&Value` deserializer that records every map entry
+//! the target type skips with `IgnoredAny` — which is what `#[derive(Deserialize)]`
+//! does for keys that match no field. Entries consumed through
+//! `#[serde(flatten)]` or untagged enums are buffered by serde and are never
+//! reported.
+//!
+//! [`ConfigBuilder::with_deny_unknown`]: super::builder::ConfigBuilder::with_deny_unknown
+
+use std::cell::RefCell;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::forward_to_deserialize_any;
+use serde_json::Value;
+
+use crate::error::{suggest_key, ConfigError, ValidationIssue, ValidationReport};
+use crate::types::AnnotatedValue;
+
+/// A key no field consumed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct UnknownKey {
+    /// Dotted path of the key
+    pub path: String,
+    /// Closest field of the enclosing struct
+    pub suggestion: Option<String>,
+}
+
+/// Deserialize `value`, the tree at dotted `path`, returning the result and
+/// every key it ignored.
+pub(crate) fn deserialize_tracking<T: serde::de::DeserializeOwned>(
+    value: &Value,
+    path: &str,
+) -> Result<(T, Vec<UnknownKey>), serde_json::Error> {
+    let unknown = RefCell::new(Vec::new());
+    let config = T::deserialize(Tracked {
+        value,
+        path: path.to_string(),
+        fields: &[],
+        unknown: &unknown,
+    })?;
+    Ok((config, unknown.into_inner()))
+}
+
+/// How the prefixed env source of a builder names its variables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct EnvNaming {
+    pub prefix: String,
+    pub separator: String,
+}
+
+impl EnvNaming {
+    /// The variable that sets dotted `path`.
+    pub fn var_name(&self, path: &str) -> String {
+        let joiner = if self.prefix.is_empty() || self.prefix.ends_with('_') {
+            ""
+        } else {
+            "_"
+        };
+        format!(
+            "{}{}{}",
+            self.prefix,
+            joiner,
+            path.to_uppercase().replace('.', &self.separator)
+        )
+    }
+}
+
+/// Turn unknown keys into a [`ConfigError::UnknownKeys`] naming where each
+/// key was set.
+///
+/// Keys set only by an unprefixed environment source are skipped: such a
+/// source merges the whole process environment. Keys from a prefixed source
+/// are reported with their variable name, e.g. `env:APP_SERVER_PROT`.
+pub(crate) fn unknown_keys_error(
+    unknown: Vec<UnknownKey>,
+    tree: &AnnotatedValue,
+    env: Option<&EnvNaming>,
+) -> Option<ConfigError> {
+    let mut report = ValidationReport::new();
+    for key in unknown {
+        report.push(ValidationIssue::new(key.path, "unknown key").with_suggestion(key.suggestion));
+    }
+    report.attach_sources(tree);
+    report.retain(|issue| issue.source.as_deref() != Some("env") || env.is_some());
+
+    let mut issues = ValidationReport::new();
+    for mut issue in report.issues().iter().cloned() {
+        if let (Some("env"), Some(env)) = (issue.source.as_deref(), env) {
+            issue.source = Some(format!("env:{}", env.var_name(&issue.path)));
+        }
+        issues.push(issue);
+    }
+    if issues.is_empty() {
+        None
+    } else {
+        Some(ConfigError::UnknownKeys { report: issues })
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+struct Tracked<'a> {
+    value: &'a Value,
+    path: String,
+    /// Fields of the struct this value is an entry of
+    fields: &'static [&'static str],
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> Tracked<'de> {
+    fn visit_object<V: Visitor<'de>>(
+        self,
+        map: &'de serde_json::Map<String, Value>,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, serde_json::Error> {
+        visitor.visit_map(TrackedMap {
+            iter: map.iter(),
+            entry: None,
+            path: self.path,
+            fields,
+            unknown: self.unknown,
+        })
+    }
+}
+
+impl<'de> Deserializer<'de> for Tracked<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => self.visit_object(map, &[], visitor),
+            Value::Array(items) => visitor.visit_seq(TrackedSeq {
+                iter: items.iter().enumerate(),
+                path: self.path,
+                unknown: self.unknown,
+            }),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Object(map) => self.visit_object(map, fields, visitor),
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.value.deserialize_enum(name, variants, visitor)
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        let key = self.path.rsplit('.').next().unwrap_or(&self.path);
+        let suggestion = suggest_key(key, self.fields.iter().copied());
+        self.unknown.borrow_mut().push(UnknownKey {
+            path: self.path,
+            suggestion,
+        });
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map identifier
+    }
+}
+
+struct TrackedMap<'a> {
+    iter: serde_json::map::Iter<'a>,
+    entry: Option<(&'a String, &'a Value)>,
+    path: String,
+    fields: &'static [&'static str],
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> MapAccess<'de> for TrackedMap<'de> {
+    type Error = serde_json::Error;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, Self::Error> {
+        let Some((key, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        self.entry = Some((key, value));
+        seed.deserialize(KeyDeserializer(key)).map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(
+        &mut self,
+        seed: V,
+    ) -> Result<V::Value, Self::Error> {
+        let (key, value) = self
+            .entry
+            .take()
+            .ok_or_else(|| de::Error::custom("map value requested before its key"))?;
+        seed.deserialize(Tracked {
+            value,
+            path: join(&self.path, key),
+            fields: self.fields,
+            unknown: self.unknown,
+        })
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct TrackedSeq<'a> {
+    iter: std::iter::Enumerate<std::slice::Iter<'a, Value>>,
+    path: String,
+    unknown: &'a RefCell<Vec<UnknownKey>>,
+}
+
+impl<'de> SeqAccess<'de> for TrackedSeq<'de> {
+    type Error = serde_json::Error;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, Self::Error> {
+        let Some((i, value)) = self.iter.next() else {
+            return Ok(None);
+        };
+        seed.deserialize(Tracked {
+            value,
+            path: join(&self.path, &i.to_string()),
+            fields: &[],
+            unknown: self.unknown,
+        })
+        .map(Some)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Map key deserializer; like `serde_json`'s, it parses numeric and boolean
+/// keys for maps such as `HashMap<u16, _>`.
+struct KeyDeserializer<'a>(&'a str);
+
+macro_rules! parse_key {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                match self.0.parse() {
+                    Ok(n) => visitor.$visit(n),
+                    Err(_) => Err(de::Error::invalid_value(de::Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for KeyDeserializer<'de> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_borrowed_str(self.0)
+    }
+
+    parse_key! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.0))
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Server {
+        host: String,
+        port: u16,
+    }
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct App {
+        name: String,
+        server: Option<Server>,
+        #[serde(default)]
+        limits: HashMap<u16, String>,
+        #[serde(default)]
+        upstreams: Vec<Server>,
+    }
+
+    #[test]
+    fn test_reports_unknown_keys_with_suggestions() {
+        let value = json!({
+            "name": "app",
+            "nmae": "typo",
+            "server": { "host": "h", "port": 1, "prot": 2 },
+            "limits": { "80": "http" },
+            "upstreams": [{ "host": "a", "port": 2, "hots": "b" }]
+        });
+        let (app, unknown) = deserialize_tracking::<App>(&value, "").unwrap();
+        assert_eq!(app.limits.get(&80).map(String::as_str), Some("http"));
+        assert_eq!(app.server.unwrap().port, 1);
+        assert_eq!(
+            unknown,
+            vec![
+                UnknownKey {
+                    path: "nmae".into(),
+                    suggestion: Some("name".into()),
+                },
+                UnknownKey {
+                    path: "server.prot".into(),
+                    suggestion: Some("port".into()),
+                },
+                UnknownKey {
+                    path: "upstreams.0.hots".into(),
+                    suggestion: Some("host".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_known_keys_and_errors_match_serde_json() {
+        let value = json!({ "name": "app", "server": null });
+        let (app, unknown) = deserialize_tracking::<App>(&value, "").unwrap();
+        assert!(app.server.is_none());
+        assert!(unknown.is_empty());
+
+        let value = json!({ "name": 1 });
+        let tracked = deserialize_tracking::<App>(&value, "").unwrap_err();
+        let plain = App::deserialize(&value).unwrap_err();
+        assert_eq!(tracked.to_string(), plain.to_string());
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Key-level comparison of two configuration trees.
+//!
+//! Both sides are compared after parsing, so formatting, comments, key order
+//! and the source format do not show up as changes. Integers and floats of the
+//! same value compare equal. Arrays are compared as a whole and reported as a
+//! single change.
+
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// One key-level difference between two trees.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KeyChange {
+    /// The key only exists in the new tree.
+    Added { path: Vec<String>, value: Value },
+    /// The key only exists in the old tree.
+    Removed { path: Vec<String>, value: Value },
+    /// The key exists in both trees with different values.
+    Changed {
+        path: Vec<String>,
+        old: Value,
+        new: Value,
+    },
+}
+
+impl KeyChange {
+    /// The key path as segments, e.g. `["server", "port"]`.
+    pub fn path(&self) -> &[String] {
+        match self {
+            KeyChange::Added { path, .. }
+            | KeyChange::Removed { path, .. }
+            | KeyChange::Changed { path, .. } => path,
+        }
+    }
+
+    /// The dotted key path, e.g. `server.port`.
+    pub fn key(&self) -> String {
+        self.path().join(".")
+    }
+
+    /// The RFC 6901 JSON Pointer of the key, e.g. `/server/port`.
+    pub fn pointer(&self) -> String {
+        self.path()
+            .iter()
+            .map(|segment| format!("/{}", segment.replace('~', "~0").replace('/', "~1")))
+            .collect()
+    }
+}
+
+impl std::fmt::Display for KeyChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyChange::Added { value, .. } => write!(f, "+ {} = {}", self.key(), value),
+            KeyChange::Removed { value, .. } => write!(f, "- {} = {}", self.key(), value),
+            KeyChange::Changed { old, new, .. } => {
+                write!(f, "~ {}: {} → {}", self.key(), old, new)
+            }
+        }
+    }
+}
+
+/// The key-level changes between two configuration trees, sorted by key path.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConfigDiff {
+    changes: Vec<KeyChange>,
+}
+
+impl ConfigDiff {
+    /// Diff two serializable values, e.g. the old and new config struct in a
+    /// reload callback.
+    pub fn between<T: Serialize + ?Sized>(old: &T, new: &T) -> ConfigResult<Self> {
+        let to_json = |value: &T| {
+            serde_json::to_value(value).map_err(|e| ConfigError::InvalidValue {
+                key: String::new(),
+                expected_type: "serializable value".to_string(),
+                message: e.to_string(),
+            })
+        };
+        Ok(diff_values(&to_json(old)?, &to_json(new)?))
+    }
+
+    /// All changes, sorted by key path.
+    pub fn changes(&self) -> &[KeyChange] {
+        &self.changes
+    }
+
+    /// True when the trees are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Number of changed keys.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+
+    /// Dotted paths of all changed keys.
+    pub fn changed_paths(&self) -> Vec<String> {
+        self.changes.iter().map(KeyChange::key).collect()
+    }
+
+    /// True when `path` or any key below it changed, or when `path` lies
+    /// inside a changed value.
+    pub fn touches(&self, path: &str) -> bool {
+        let wanted: Vec<&str> = path.split('.').filter(|s| !s.is_empty()).collect();
+        self.changes.iter().any(|change| {
+            change
+                .path()
+                .iter()
+                .zip(&wanted)
+                .all(|(segment, wanted)| segment == wanted)
+        })
+    }
+
+    /// The changes as an RFC 6902 JSON Patch that turns the old tree into the
+    /// new one.
+    pub fn to_json_patch(&self) -> Value {
+        Value::Array(
+            self.changes
+                .iter()
+                .map(|change| match change {
+                    KeyChange::Added { value, .. } => serde_json::json!({
+                        "op": "add",
+                        "path": change.pointer(),
+                        "value": value,
+                    }),
+                    KeyChange::Removed { .. } => serde_json::json!({
+                        "op": "remove",
+                        "path": change.pointer(),
+                    }),
+                    KeyChange::Changed { new, .. } => serde_json::json!({
+                        "op": "replace",
+                        "path": change.pointer(),
+                        "value": new,
+                    }),
+                })
+                .collect(),
+        )
+    }
+}
+
+impl<'a> IntoIterator for &'a ConfigDiff {
+    type Item = &'a KeyChange;
+    type IntoIter = std::slice::Iter<'a, KeyChange>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.changes.iter()
+    }
+}
+
+/// Compare `old` with `new` key by key.
+///
+/// # Example
+///
+/// ```rust
+/// use serde_json::json;
+///
+/// let diff = confers::diff::diff_values(
+///     &json!({"server": {"port": 8080, "host": "localhost"}}),
+///     &json!({"server": {"port": 9090, "host": "localhost"}}),
+/// );
+/// assert_eq!(diff.changed_paths(), vec!["server.port"]);
+/// assert!(diff.touches("server"));
+/// assert!(!diff.touches("database"));
+/// ```
+pub fn diff_values(old: &Value, new: &Value) -> ConfigDiff {
+    let mut changes = Vec::new();
+    diff_at(&mut Vec::new(), old, new, &mut changes);
+    changes.sort_by(|a, b| a.path().cmp(b.path()));
+    ConfigDiff { changes }
+}
+
+fn diff_at(path: &mut Vec<String>, old: &Value, new: &Value, changes: &mut Vec<KeyChange>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => diff_maps(path, old, new, changes),
+        _ if values_equal(old, new) => {}
+        _ => changes.push(KeyChange::Changed {
+            path: path.clone(),
+            old: old.clone(),
+            new: new.clone(),
+        }),
+    }
+}
+
+fn diff_maps(
+    path: &mut Vec<String>,
+    old: &Map<String, Value>,
+    new: &Map<String, Value>,
+    changes: &mut Vec<KeyChange>,
+) {
+    for (key, old_value) in old {
+        path.push(key.clone());
+        match new.get(key) {
+            Some(new_value) => diff_at(path, old_value, new_value, changes),
+            None => changes.push(KeyChange::Removed {
+                path: path.clone(),
+                value: old_value.clone(),
+            }),
+        }
+        path.pop();
+    }
+    for (key, new_value) in new {
+        if !old.contains_key(key) {
+            let mut added = path.clone();
+            added.push(key.clone());
+            changes.push(KeyChange::Added {
+                path: added,
+                value: new_value.clone(),
+            });
+        }
+    }
+}
+
+/// Structural equality that treats `1` and `1.0` as the same value.
+fn values_equal(a: &Value, b: &Value) -> bool {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a == b || a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| values_equal(a, b))
+        }
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len()
+                && a.iter()
+                    .all(|(key, a)| b.get(key).is_some_and(|b| values_equal(a, b)))
+        }
+        _ => a == b,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_reports_key_level_changes() {
+        let old = json!({
+            "server": {"host": "localhost", "port": 8080, "tags": ["a"]},
+            "legacy": true
+        });
+        let new = json!({
+            "server": {"port": 9090, "tags": ["a"], "host": "localhost", "tls": {"on": true}},
+        });
+
+        let diff = diff_values(&old, &new);
+        let lines: Vec<String> = diff.into_iter().map(ToString::to_string).collect();
+        assert_eq!(
+            lines,
+            vec![
+                "- legacy = true",
+                "~ server.port: 8080 → 9090",
+                "+ server.tls = {\"on\":true}",
+            ]
+        );
+        assert_eq!(diff.len(), 3);
+    }
+
+    #[test]
+    fn test_diff_normalizes_numbers() {
+        assert!(diff_values(&json!({"ratio": 1}), &json!({"ratio": 1.0})).is_empty());
+        assert!(diff_values(&json!({"list": [1, 2]}), &json!({"list": [1.0, 2]})).is_empty());
+        assert_eq!(
+            diff_values(&json!({"list": [1, 2]}), &json!({"list": [2, 1]})).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_to_json_patch() {
+        let old = json!({"a/b": {"x": 1}, "gone": "y", "c~d": 1});
+        let new = json!({"a/b": {"x": 2}, "c~d": 1, "new": [1]});
+
+        assert_eq!(
+            diff_values(&old, &new).to_json_patch(),
+            json!([
+                {"op": "replace", "path": "/a~1b/x", "value": 2},
+                {"op": "remove", "path": "/gone"},
+                {"op": "add", "path": "/new", "value": [1]},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_diff_replaces_root_of_different_type() {
+        assert_eq!(
+            diff_values(&json!({"a": 1}), &json!([1])).to_json_patch(),
+            json!([{"op": "replace", "path": "", "value": [1]}])
+        );
+    }
+
+    #[test]
+    fn test_touches() {
+        let diff = diff_values(
+            &json!({"db": {"pool": {"max": 5}}, "log": "info"}),
+            &json!({"db": {"pool": {"max": 10}}, "log": "info", "tls": {"on": true}}),
+        );
+        assert!(diff.touches("db"));
+        assert!(diff.touches("db.pool.max"));
+        assert!(!diff.touches("db.url"));
+        assert!(!diff.touches("log"));
+        assert!(diff.touches("tls.on"));
+    }
+
+    #[test]
+    fn test_between_structs() {
+        #[derive(Serialize)]
+        struct App {
+            name: String,
+            port: u16,
+        }
+
+        let diff = ConfigDiff::between(
+            &App {
+                name: "a".into(),
+                port: 1,
+            },
+            &App {
+                name: "a".into(),
+                port: 2,
+            },
+        )
+        .unwrap();
+        assert_eq!(diff.changed_paths(), vec!["port"]);
+    }
+}
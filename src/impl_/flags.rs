@@ -0,0 +1,990 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Feature flags evaluated locally against an [`EvaluationContext`].
+//!
+//! Flag definitions are ordinary configuration: a [`FlagSet`] deserializes
+//! from a map of flag names to [`FlagDefinition`]s, so it can be a field of
+//! the application config or loaded on its own, and reloads with it.
+//!
+//! ```toml
+//! [flags.new_ui]
+//! rollout = 10                      # 10% of everyone else
+//!
+//! [[flags.new_ui.rules]]            # first matching rule decides
+//! conditions = [{ attribute = "plan", op = "in", values = ["enterprise"] }]
+//!
+//! [flags.legacy_export]
+//! enabled = false                   # kill switch
+//! ```
+//!
+//! Percentage rollouts bucket the context's targeting key with a stable
+//! hash of the flag name and key, so a user keeps their answer across
+//! processes and stays in a rollout as its percentage grows.
+//!
+//! A flag with an [`Experiment`] also assigns every context it is on for to
+//! one of the experiment's weighted variants:
+//!
+//! ```toml
+//! [flags.checkout_button]
+//! rollout = 20                      # 20% of users take part
+//!
+//! [flags.checkout_button.experiment]
+//! salt = "checkout-2025-06"         # change to reshuffle assignments
+//! variants = [
+//!     { name = "control", weight = 50 },
+//!     { name = "green", weight = 50, value = { color = "green" } },
+//! ]
+//! ```
+//!
+//! # Example
+//!
+//! ```
+//! use confers::context::EvaluationContext;
+//! use confers::flags::{FlagDefinition, FlagOperator, FlagRule, FlagSet, Flags};
+//!
+//! let set = FlagSet::new().with(
+//!     "new_ui",
+//!     FlagDefinition::new()
+//!         .rollout(0.0)
+//!         .rule(FlagRule::new().when("plan", FlagOperator::In, ["enterprise"])),
+//! );
+//! let flags = Flags::new(set);
+//!
+//! let ctx = EvaluationContext::new().with_key("user-1").attr("plan", "enterprise");
+//! assert!(flags.enabled("new_ui", &ctx));
+//! assert!(!flags.enabled("new_ui", &EvaluationContext::new().with_key("user-2")));
+//! ```
+
+use std::collections::BTreeMap;
+use std::sync::{Arc, RwLock};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::impl_::context::{ContextValue, EvaluationContext};
+
+/// Number of rollout buckets; percentages resolve to 0.01%.
+const BUCKETS: u64 = 10_000;
+
+/// All flag definitions, keyed by flag name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FlagSet {
+    flags: BTreeMap<String, FlagDefinition>,
+}
+
+impl FlagSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the flag `name`.
+    pub fn with(mut self, name: impl Into<String>, definition: FlagDefinition) -> Self {
+        self.flags.insert(name.into(), definition);
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&FlagDefinition> {
+        self.flags.get(name)
+    }
+
+    /// Flag names, sorted.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.flags.keys().map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.flags.is_empty()
+    }
+
+    /// Evaluate `name` for `ctx`. Unknown flags are off.
+    pub fn evaluate(&self, name: &str, ctx: &EvaluationContext) -> FlagEvaluation {
+        match self.flags.get(name) {
+            Some(definition) => definition.evaluate(name, ctx),
+            None => FlagEvaluation {
+                enabled: false,
+                reason: FlagReason::Unknown,
+            },
+        }
+    }
+
+    /// Whether `name` is on for `ctx`.
+    pub fn enabled(&self, name: &str, ctx: &EvaluationContext) -> bool {
+        self.evaluate(name, ctx).enabled
+    }
+
+    /// The experiment variant `ctx` is assigned to, if `name` is an
+    /// experiment that is on for `ctx` and `ctx` has a targeting key.
+    pub fn variant(&self, name: &str, ctx: &EvaluationContext) -> Option<&Variant> {
+        let experiment = self.flags.get(name)?.experiment.as_ref()?;
+        let key = ctx.targeting_key()?;
+        if !self.enabled(name, ctx) {
+            return None;
+        }
+        experiment.assign(name, key)
+    }
+}
+
+/// One flag.
+///
+/// A disabled flag is off for everyone. Otherwise the first rule whose
+/// conditions all match decides; a context no rule matches gets the flag's
+/// own `rollout`, which defaults to 100% for a flag without rules and 0% for
+/// one with rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FlagDefinition {
+    #[serde(default = "enabled_by_default")]
+    enabled: bool,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_percentage",
+        skip_serializing_if = "Option::is_none"
+    )]
+    rollout: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    rules: Vec<FlagRule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    experiment: Option<Experiment>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Default for FlagDefinition {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlagDefinition {
+    /// An enabled flag without rules: on for everyone.
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            rollout: None,
+            rules: Vec::new(),
+            experiment: None,
+            description: None,
+        }
+    }
+
+    /// Turn the flag off (or back on) for everyone.
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Percentage (0-100) of contexts no rule matches that get the flag.
+    /// Values outside the range are clamped.
+    pub fn rollout(mut self, percent: f64) -> Self {
+        self.rollout = Some(percent.clamp(0.0, 100.0));
+        self
+    }
+
+    pub fn rule(mut self, rule: FlagRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Split the contexts the flag is on for between `experiment`'s variants.
+    pub fn experiment(mut self, experiment: Experiment) -> Self {
+        self.experiment = Some(experiment);
+        self
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    fn evaluate(&self, name: &str, ctx: &EvaluationContext) -> FlagEvaluation {
+        if !self.enabled {
+            return FlagEvaluation {
+                enabled: false,
+                reason: FlagReason::Disabled,
+            };
+        }
+        if let Some((index, rule)) = self
+            .rules
+            .iter()
+            .enumerate()
+            .find(|(_, rule)| rule.matches(ctx))
+        {
+            return FlagEvaluation {
+                enabled: in_rollout(name, ctx, rule.rollout.unwrap_or(100.0)),
+                reason: FlagReason::Rule { index },
+            };
+        }
+        let fallthrough = self
+            .rollout
+            .unwrap_or(if self.rules.is_empty() { 100.0 } else { 0.0 });
+        FlagEvaluation {
+            enabled: in_rollout(name, ctx, fallthrough),
+            reason: FlagReason::Fallthrough,
+        }
+    }
+}
+
+/// Targets the contexts matching all of its conditions, optionally only a
+/// percentage of them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FlagRule {
+    #[serde(default)]
+    conditions: Vec<FlagCondition>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_percentage",
+        skip_serializing_if = "Option::is_none"
+    )]
+    rollout: Option<f64>,
+}
+
+impl FlagRule {
+    /// A rule without conditions, matching every context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also require `attribute` to satisfy `op` against `values`.
+    pub fn when(
+        mut self,
+        attribute: impl Into<String>,
+        op: FlagOperator,
+        values: impl IntoIterator<Item = impl Into<ContextValue>>,
+    ) -> Self {
+        self.conditions.push(FlagCondition {
+            attribute: attribute.into(),
+            op,
+            values: values.into_iter().map(Into::into).collect(),
+        });
+        self
+    }
+
+    /// Percentage (0-100) of matching contexts that get the flag. Values
+    /// outside the range are clamped.
+    pub fn rollout(mut self, percent: f64) -> Self {
+        self.rollout = Some(percent.clamp(0.0, 100.0));
+        self
+    }
+
+    fn matches(&self, ctx: &EvaluationContext) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(ctx))
+    }
+}
+
+/// A test of one context attribute.
+///
+/// `targeting_key`, `environment` and `region` resolve to the context's
+/// fields unless an attribute of that name is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct FlagCondition {
+    attribute: String,
+    op: FlagOperator,
+    #[serde(
+        deserialize_with = "deserialize_values",
+        serialize_with = "serialize_values"
+    )]
+    values: Vec<ContextValue>,
+}
+
+impl FlagCondition {
+    fn matches(&self, ctx: &EvaluationContext) -> bool {
+        let Some(actual) = attribute(ctx, &self.attribute) else {
+            // A missing attribute is "not in" anything, and nothing else.
+            return self.op == FlagOperator::NotIn;
+        };
+        match self.op {
+            FlagOperator::In => self.values.contains(&actual),
+            FlagOperator::NotIn => !self.values.contains(&actual),
+            FlagOperator::Gt => self.compare(&actual, |a, b| a > b),
+            FlagOperator::Gte => self.compare(&actual, |a, b| a >= b),
+            FlagOperator::Lt => self.compare(&actual, |a, b| a < b),
+            FlagOperator::Lte => self.compare(&actual, |a, b| a <= b),
+            FlagOperator::StartsWith => actual.as_str().is_some_and(|actual| {
+                self.values
+                    .iter()
+                    .filter_map(ContextValue::as_str)
+                    .any(|prefix| actual.starts_with(prefix))
+            }),
+        }
+    }
+
+    /// Numeric comparison against the first value.
+    fn compare(&self, actual: &ContextValue, op: fn(f64, f64) -> bool) -> bool {
+        match (
+            actual.as_number(),
+            self.values.first().and_then(ContextValue::as_number),
+        ) {
+            (Some(actual), Some(expected)) => op(actual, expected),
+            _ => false,
+        }
+    }
+}
+
+/// Weighted variants of a flag, for A/B tests.
+///
+/// A context's variant depends only on its targeting key and the salt
+/// (the flag name unless set), so it is the same in every process and only
+/// changes when the salt or the variants do.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Experiment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    salt: Option<String>,
+    #[serde(deserialize_with = "deserialize_variants")]
+    variants: Vec<Variant>,
+}
+
+impl Experiment {
+    /// An experiment without variants; add them with [`variant`](Self::variant).
+    pub fn new() -> Self {
+        Self {
+            salt: None,
+            variants: Vec::new(),
+        }
+    }
+
+    /// Hash keys with `salt` instead of the flag name.
+    pub fn salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = Some(salt.into());
+        self
+    }
+
+    pub fn variant(mut self, variant: Variant) -> Self {
+        self.variants.push(variant);
+        self
+    }
+
+    pub fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
+    fn assign(&self, flag: &str, key: &str) -> Option<&Variant> {
+        let total: u64 = self.variants.iter().map(|v| u64::from(v.weight)).sum();
+        if total == 0 {
+            return None;
+        }
+        let seed = format!("{}/variant", self.salt.as_deref().unwrap_or(flag));
+        let mut point = hash(&seed, key) % total;
+        self.variants.iter().find(|variant| {
+            let weight = u64::from(variant.weight);
+            if point < weight {
+                return true;
+            }
+            point -= weight;
+            false
+        })
+    }
+}
+
+impl Default for Experiment {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One arm of an [`Experiment`], with an optional payload.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Variant {
+    name: String,
+    weight: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<serde_json::Value>,
+}
+
+impl Variant {
+    pub fn new(name: impl Into<String>, weight: u32) -> Self {
+        Self {
+            name: name.into(),
+            weight,
+            value: None,
+        }
+    }
+
+    /// Attach a payload, e.g. the settings this arm runs with.
+    pub fn with_value(mut self, value: serde_json::Value) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    pub fn value(&self) -> Option<&serde_json::Value> {
+        self.value.as_ref()
+    }
+}
+
+/// How a condition compares an attribute with its values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlagOperator {
+    /// Equal to one of the values.
+    In,
+    /// Equal to none of the values, or missing.
+    NotIn,
+    /// Numerically greater than the first value.
+    Gt,
+    /// Numerically greater than or equal to the first value.
+    Gte,
+    /// Numerically less than the first value.
+    Lt,
+    /// Numerically less than or equal to the first value.
+    Lte,
+    /// A string starting with one of the values.
+    StartsWith,
+}
+
+/// The result of evaluating a flag, and why.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagEvaluation {
+    pub enabled: bool,
+    pub reason: FlagReason,
+}
+
+/// What decided a [`FlagEvaluation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagReason {
+    /// No flag of that name is defined.
+    Unknown,
+    /// The flag is switched off.
+    Disabled,
+    /// The rule at this index matched.
+    Rule { index: usize },
+    /// No rule matched; the flag's own rollout applied.
+    Fallthrough,
+}
+
+/// The live flag definitions, shared by every clone.
+///
+/// Replace the definitions after a reload with [`replace`](Self::replace),
+/// or let a [`ProgressiveReloader`](crate::watcher::ProgressiveReloader) do
+/// it with [`follow`](Self::follow).
+#[derive(Clone, Default)]
+pub struct Flags {
+    set: Arc<RwLock<Arc<FlagSet>>>,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<crate::audit::AuditWriter>>,
+}
+
+impl Flags {
+    pub fn new(set: FlagSet) -> Self {
+        Self {
+            set: Arc::new(RwLock::new(Arc::new(set))),
+            #[cfg(feature = "audit")]
+            audit: None,
+        }
+    }
+
+    /// Record an `ExperimentExposure` audit event for every variant handed
+    /// out by [`variant`](Self::variant).
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_audit(mut self, audit: Arc<crate::audit::AuditWriter>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Evaluate with `set` from now on.
+    pub fn replace(&self, set: FlagSet) {
+        *self.set.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(set);
+    }
+
+    /// The current definitions.
+    pub fn current(&self) -> Arc<FlagSet> {
+        self.set.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Whether `name` is on for `ctx`. Unknown flags are off.
+    pub fn enabled(&self, name: &str, ctx: &EvaluationContext) -> bool {
+        self.current().enabled(name, ctx)
+    }
+
+    /// Evaluate `name` for `ctx`, with the reason for the result.
+    pub fn evaluate(&self, name: &str, ctx: &EvaluationContext) -> FlagEvaluation {
+        self.current().evaluate(name, ctx)
+    }
+
+    /// The experiment variant `ctx` is assigned to; see
+    /// [`FlagSet::variant`]. Each assignment counts as an exposure and is
+    /// audited, so call this where the variant is actually used.
+    pub fn variant(&self, name: &str, ctx: &EvaluationContext) -> Option<Variant> {
+        let variant = self.current().variant(name, ctx).cloned()?;
+        #[cfg(feature = "audit")]
+        if let (Some(audit), Some(key)) = (&self.audit, ctx.targeting_key()) {
+            audit.log_experiment_exposure(name, variant.name(), key);
+        }
+        Some(variant)
+    }
+
+    /// Take the definitions from `field` of the reloader's configuration,
+    /// now and after every committed reload that changes them. Dropping the
+    /// returned subscription stops following.
+    #[cfg(feature = "progressive-reload")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "progressive-reload")))]
+    pub fn follow<T: Clone + Send + Sync + 'static>(
+        &self,
+        reloader: &crate::watcher::ProgressiveReloader<T>,
+        field: fn(&T) -> &FlagSet,
+    ) -> crate::watcher::ReloadSubscription {
+        self.replace(field(&reloader.current()).clone());
+        let flags = self.clone();
+        reloader.on_field_change(field, move |_, new: &FlagSet| flags.replace(new.clone()))
+    }
+}
+
+impl std::fmt::Debug for Flags {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flags")
+            .field("flags", &self.current().names().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// The value of a context attribute, or of the context field it names.
+fn attribute(ctx: &EvaluationContext, name: &str) -> Option<ContextValue> {
+    if let Some(value) = ctx.attributes().get(name) {
+        return Some(value.clone());
+    }
+    match name {
+        "targeting_key" => ctx.targeting_key().map(ContextValue::from),
+        "environment" => Some(ContextValue::from(ctx.environment().clone())),
+        "region" => Some(ContextValue::from(ctx.region().clone())),
+        _ => None,
+    }
+}
+
+/// Whether `ctx` falls in the first `percent` of the flag's buckets.
+/// Partial rollouts need a targeting key.
+fn in_rollout(flag: &str, ctx: &EvaluationContext, percent: f64) -> bool {
+    if percent >= 100.0 {
+        return true;
+    }
+    if percent <= 0.0 {
+        return false;
+    }
+    let Some(key) = ctx.targeting_key() else {
+        return false;
+    };
+    hash(flag, key) % BUCKETS < (percent * (BUCKETS as f64 / 100.0)).round() as u64
+}
+
+/// Stable hash of `key` under `seed`: FNV-1a, so it never changes between
+/// builds or processes.
+fn hash(seed: &str, key: &str) -> u64 {
+    seed.bytes()
+        .chain(std::iter::once(b':'))
+        .chain(key.bytes())
+        .fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+            (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+        })
+}
+
+fn deserialize_percentage<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<f64>, D::Error> {
+    let percent = f64::deserialize(deserializer)?;
+    if !(0.0..=100.0).contains(&percent) {
+        return Err(serde::de::Error::custom(format!(
+            "rollout must be between 0 and 100, got {}",
+            percent
+        )));
+    }
+    Ok(Some(percent))
+}
+
+/// At least one variant, with distinct names and a positive total weight.
+fn deserialize_variants<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Variant>, D::Error> {
+    use serde::de::Error;
+
+    let variants = Vec::<Variant>::deserialize(deserializer)?;
+    if variants.iter().all(|v| v.weight == 0) {
+        return Err(D::Error::custom(
+            "an experiment needs a variant with a positive weight",
+        ));
+    }
+    for (i, variant) in variants.iter().enumerate() {
+        if variants[..i].iter().any(|v| v.name == variant.name) {
+            return Err(D::Error::custom(format!(
+                "duplicate variant '{}'",
+                variant.name
+            )));
+        }
+    }
+    Ok(variants)
+}
+
+/// One scalar or a list of them.
+fn deserialize_values<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<ContextValue>, D::Error> {
+    fn scalar<E: serde::de::Error>(value: serde_json::Value) -> Result<ContextValue, E> {
+        match value {
+            serde_json::Value::String(s) => Ok(s.into()),
+            serde_json::Value::Bool(b) => Ok(b.into()),
+            serde_json::Value::Number(n) => n
+                .as_f64()
+                .map(ContextValue::from)
+                .ok_or_else(|| E::custom("number out of range")),
+            other => Err(E::custom(format!(
+                "expected a string, number or boolean, got {}",
+                other
+            ))),
+        }
+    }
+    match serde_json::Value::deserialize(deserializer)? {
+        serde_json::Value::Array(items) => items.into_iter().map(scalar).collect(),
+        value => Ok(vec![scalar(value)?]),
+    }
+}
+
+fn serialize_values<S: serde::Serializer>(
+    values: &[ContextValue],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use serde::ser::SerializeSeq;
+
+    let mut seq = serializer.serialize_seq(Some(values.len()))?;
+    for value in values {
+        match value {
+            ContextValue::String(s) => seq.serialize_element(s.as_ref())?,
+            ContextValue::Number(n) => seq.serialize_element(n)?,
+            ContextValue::Boolean(b) => seq.serialize_element(b)?,
+        }
+    }
+    seq.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: serde_json::Value) -> FlagSet {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_boolean_flags_and_kill_switch() {
+        let set = parse(serde_json::json!({
+            "on": {},
+            "off": { "enabled": false, "rules": [{}] },
+        }));
+        let ctx = EvaluationContext::new();
+        assert!(set.enabled("on", &ctx));
+        assert_eq!(set.evaluate("off", &ctx).reason, FlagReason::Disabled);
+        assert!(!set.enabled("off", &ctx));
+        assert_eq!(
+            set.evaluate("missing", &ctx),
+            FlagEvaluation {
+                enabled: false,
+                reason: FlagReason::Unknown
+            }
+        );
+    }
+
+    #[test]
+    fn test_attribute_rules() {
+        let set = parse(serde_json::json!({
+            "new_ui": {
+                "rules": [
+                    { "conditions": [
+                        { "attribute": "plan", "op": "in", "values": ["enterprise", "team"] },
+                        { "attribute": "seats", "op": "gte", "values": 10 },
+                    ]},
+                    { "conditions": [{ "attribute": "region", "op": "starts_with", "values": "eu-" }] },
+                ],
+            },
+        }));
+        let team = EvaluationContext::new()
+            .attr("plan", "team")
+            .attr("seats", 12);
+        assert_eq!(
+            set.evaluate("new_ui", &team),
+            FlagEvaluation {
+                enabled: true,
+                reason: FlagReason::Rule { index: 0 }
+            }
+        );
+        let small = team.clone().attr("seats", 3);
+        assert!(!set.enabled("new_ui", &small));
+        assert_eq!(
+            set.evaluate("new_ui", &small).reason,
+            FlagReason::Fallthrough
+        );
+
+        let eu = EvaluationContext::new().with_region("eu-west-1");
+        assert_eq!(
+            set.evaluate("new_ui", &eu).reason,
+            FlagReason::Rule { index: 1 }
+        );
+    }
+
+    #[test]
+    fn test_missing_attribute_only_matches_not_in() {
+        let set = FlagSet::new()
+            .with(
+                "a",
+                FlagDefinition::new().rule(FlagRule::new().when(
+                    "plan",
+                    FlagOperator::NotIn,
+                    ["free"],
+                )),
+            )
+            .with(
+                "b",
+                FlagDefinition::new().rule(FlagRule::new().when(
+                    "plan",
+                    FlagOperator::In,
+                    ["free"],
+                )),
+            );
+        let ctx = EvaluationContext::new();
+        assert!(set.enabled("a", &ctx));
+        assert!(!set.enabled("b", &ctx));
+    }
+
+    #[test]
+    fn test_percentage_rollout_is_stable_and_monotonic() {
+        let at = |percent: f64| FlagSet::new().with("f", FlagDefinition::new().rollout(percent));
+        let enabled = |set: &FlagSet| {
+            (0..1000)
+                .filter(|i| {
+                    set.enabled("f", &EvaluationContext::new().with_key(format!("user-{i}")))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let ten = enabled(&at(10.0));
+        let fifty = enabled(&at(50.0));
+        assert!((60..140).contains(&ten.len()), "{}", ten.len());
+        assert!((420..580).contains(&fifty.len()), "{}", fifty.len());
+        assert!(ten.iter().all(|i| fifty.contains(i)));
+        assert_eq!(ten, enabled(&at(10.0)));
+        assert_eq!(enabled(&at(0.0)).len(), 0);
+        assert_eq!(enabled(&at(100.0)).len(), 1000);
+
+        // Partial rollouts need a targeting key.
+        assert!(!at(99.0).enabled("f", &EvaluationContext::new()));
+    }
+
+    #[test]
+    fn test_invalid_definitions_are_rejected() {
+        for json in [
+            serde_json::json!({ "f": { "rollout": 150 } }),
+            serde_json::json!({ "f": { "rules": [{ "rollout": -1 }] } }),
+            serde_json::json!({ "f": { "rules": [{ "conditions": [
+                { "attribute": "plan", "op": "matches", "values": "x" }
+            ]}]}}),
+            serde_json::json!({ "f": { "rules": [{ "conditions": [
+                { "attribute": "plan", "op": "in", "values": [{ "nested": true }] }
+            ]}]}}),
+        ] {
+            assert!(
+                serde_json::from_value::<FlagSet>(json.clone()).is_err(),
+                "{json}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_flags_round_trip_through_serialization() {
+        let set = FlagSet::new().with(
+            "f",
+            FlagDefinition::new()
+                .rollout(25.0)
+                .with_description("new checkout")
+                .rule(
+                    FlagRule::new()
+                        .when("plan", FlagOperator::In, ["pro"])
+                        .rollout(50.0),
+                ),
+        );
+        let json = serde_json::to_value(&set).unwrap();
+        assert_eq!(serde_json::from_value::<FlagSet>(json).unwrap(), set);
+    }
+
+    #[test]
+    fn test_experiment_assignment_is_deterministic_and_weighted() {
+        let set = parse(serde_json::json!({
+            "checkout": {
+                "rollout": 50,
+                "experiment": {
+                    "salt": "2025-06",
+                    "variants": [
+                        { "name": "control", "weight": 3 },
+                        { "name": "green", "weight": 1, "value": { "color": "green" } },
+                    ],
+                },
+            },
+        }));
+        let ctx = |i: usize| EvaluationContext::new().with_key(format!("user-{i}"));
+        let assigned: Vec<_> = (0..2000)
+            .map(|i| set.variant("checkout", &ctx(i)).map(Variant::name))
+            .collect();
+
+        let exposed = assigned.iter().flatten().count();
+        let green = assigned.iter().flatten().filter(|v| **v == "green").count();
+        assert!((850..1150).contains(&exposed), "{exposed}");
+        assert!((150..350).contains(&green), "{green}/{exposed}");
+        for (i, variant) in assigned.iter().enumerate() {
+            assert_eq!(
+                set.variant("checkout", &ctx(i)).map(Variant::name),
+                *variant
+            );
+            assert_eq!(variant.is_some(), set.enabled("checkout", &ctx(i)));
+        }
+        let green = set
+            .variant(
+                "checkout",
+                &ctx(assigned.iter().position(|v| *v == Some("green")).unwrap()),
+            )
+            .unwrap();
+        assert_eq!(
+            green.value(),
+            Some(&serde_json::json!({ "color": "green" }))
+        );
+
+        // A new salt reshuffles the assignments.
+        let resalted = FlagSet::new().with(
+            "checkout",
+            FlagDefinition::new().experiment(
+                Experiment::new()
+                    .salt("2025-07")
+                    .variant(Variant::new("control", 3))
+                    .variant(Variant::new("green", 1)),
+            ),
+        );
+        let original = FlagSet::new().with(
+            "checkout",
+            FlagDefinition::new().experiment(
+                Experiment::new()
+                    .salt("2025-06")
+                    .variant(Variant::new("control", 3))
+                    .variant(Variant::new("green", 1)),
+            ),
+        );
+        assert!(
+            (0..200).any(|i| resalted.variant("checkout", &ctx(i)).map(Variant::name)
+                != original.variant("checkout", &ctx(i)).map(Variant::name))
+        );
+        assert!(original
+            .variant("checkout", &EvaluationContext::new())
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_experiments_are_rejected() {
+        for variants in [
+            serde_json::json!([]),
+            serde_json::json!([{ "name": "a", "weight": 0 }]),
+            serde_json::json!([{ "name": "a", "weight": 1 }, { "name": "a", "weight": 1 }]),
+        ] {
+            let json = serde_json::json!({ "f": { "experiment": { "variants": variants } } });
+            assert!(
+                serde_json::from_value::<FlagSet>(json.clone()).is_err(),
+                "{json}"
+            );
+        }
+    }
+
+    #[cfg(feature = "audit")]
+    #[test]
+    fn test_exposures_are_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            crate::audit::AuditWriter::builder()
+                .log_dir(dir.path().to_path_buf())
+                .build(),
+        );
+        let flags = Flags::new(FlagSet::new().with(
+            "f",
+            FlagDefinition::new().experiment(Experiment::new().variant(Variant::new("only", 1))),
+        ))
+        .with_audit(audit);
+        let ctx = EvaluationContext::new().with_key("user-7");
+        assert_eq!(flags.variant("f", &ctx).unwrap().name(), "only");
+        assert!(flags.variant("f", &EvaluationContext::new()).is_none());
+
+        let log = std::fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let events: Vec<serde_json::Value> = std::fs::read_to_string(log.path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0]["event"], "ExperimentExposure");
+        assert_eq!(events[0]["experiment"], "f");
+        assert_eq!(events[0]["variant"], "only");
+        assert_eq!(events[0]["subject"], "user-7");
+    }
+
+    #[test]
+    fn test_replace_is_seen_by_clones() {
+        let flags = Flags::new(FlagSet::new().with("f", FlagDefinition::new()));
+        let clone = flags.clone();
+        let ctx = EvaluationContext::new();
+        assert!(clone.enabled("f", &ctx));
+
+        flags.replace(FlagSet::new().with("f", FlagDefinition::new().enabled(false)));
+        assert!(!clone.enabled("f", &ctx));
+    }
+
+    #[cfg(feature = "progressive-reload")]
+    #[tokio::test]
+    async fn test_follow_reloader() {
+        use crate::watcher::ProgressiveReloader;
+
+        #[derive(Clone)]
+        struct AppConfig {
+            flags: FlagSet,
+        }
+
+        struct Empty;
+        impl crate::interface::ConfigProvider for Empty {
+            fn get_raw(&self, _key: &str) -> Option<&crate::types::AnnotatedValue> {
+                None
+            }
+            fn keys(&self) -> Vec<String> {
+                vec![]
+            }
+        }
+
+        let config = |enabled| AppConfig {
+            flags: FlagSet::new().with("f", FlagDefinition::new().enabled(enabled)),
+        };
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(config(false)))
+            .build();
+        let flags = Flags::default();
+        let _follow = flags.follow(&reloader, |config: &AppConfig| &config.flags);
+        let ctx = EvaluationContext::new();
+        assert!(!flags.enabled("f", &ctx));
+
+        reloader
+            .begin_reload(Arc::new(config(true)), Arc::new(Empty))
+            .await
+            .unwrap();
+        assert!(flags.enabled("f", &ctx));
+    }
+}
@@ -0,0 +1,285 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Journal of configuration changes.
+//!
+//! Every committed reload of a
+//! [`ProgressiveReloader`](crate::watcher::ProgressiveReloader) with a
+//! [`JournalSink`] appends one [`JournalEntry`] per changed key: the dotted
+//! key, short hashes of the old and new value, the source the new value came
+//! from and the time. Values themselves are never recorded, and the hashes of
+//! sensitive keys (`password`, `api_key`, ...) are replaced by
+//! [`REDACTED_HASH`], since a short secret could be recovered from its hash.
+//!
+//! [`ChangeJournal`] keeps the journal as JSON lines in a local file, which
+//! `confers history <key>` reads.
+
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::error::{ConfigError, ConfigResult, Redactor};
+
+/// Default location of the journal, next to the known-good snapshot store.
+pub const DEFAULT_CHANGE_JOURNAL: &str = "config-snapshots/changes.jsonl";
+
+/// Hash recorded for the values of sensitive keys.
+pub const REDACTED_HASH: &str = "[REDACTED]";
+
+/// Hex digits of SHA-256 kept in a value hash.
+const HASH_HEX_DIGITS: usize = 16;
+
+/// One changed key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Dotted key path, e.g. `server.port`.
+    pub key: String,
+    /// Hash of the previous value; `None` if the key was added.
+    pub old_hash: Option<String>,
+    /// Hash of the new value; `None` if the key was removed.
+    pub new_hash: Option<String>,
+    /// Source of the new value, when known.
+    pub source: Option<String>,
+    pub changed_at: DateTime<Utc>,
+}
+
+impl JournalEntry {
+    /// The entries for the keys that differ between `old` and `new`.
+    ///
+    /// `source` names the source of a key's new value. Keys that `redactor`
+    /// considers sensitive get [`REDACTED_HASH`] instead of hashes.
+    pub fn diff(
+        old: &Value,
+        new: &Value,
+        source: impl Fn(&str) -> Option<String>,
+        redactor: &Redactor,
+    ) -> Vec<Self> {
+        use crate::impl_::diff::KeyChange;
+
+        let changed_at = Utc::now();
+        crate::impl_::diff::diff_values(old, new)
+            .changes()
+            .iter()
+            .map(|change| {
+                let key = change.key();
+                let (old, new) = match change {
+                    KeyChange::Added { value, .. } => (None, Some(value)),
+                    KeyChange::Removed { value, .. } => (Some(value), None),
+                    KeyChange::Changed { old, new, .. } => (Some(old), Some(new)),
+                };
+                let hash = |value: &Value| match redactor.is_sensitive_key(&key) {
+                    true => REDACTED_HASH.to_string(),
+                    false => value_hash(value),
+                };
+                Self {
+                    old_hash: old.map(hash),
+                    new_hash: new.map(hash),
+                    source: new.and_then(|_| source(&key)),
+                    key,
+                    changed_at,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether this entry is about `key`, a key below it or a key above it.
+    pub fn concerns(&self, key: &str) -> bool {
+        let below = |parent: &str, child: &str| {
+            parent.is_empty()
+                || child
+                    .strip_prefix(parent)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+        };
+        below(key, &self.key) || below(&self.key, key)
+    }
+}
+
+/// `sha256:<16 hex>` digest of `value`, independent of object key order.
+pub fn value_hash(value: &Value) -> String {
+    let canonical = serde_json::to_vec(&sorted(value)).unwrap_or_default();
+    let digest = Sha256::digest(&canonical);
+    let hex: String = digest.iter().map(|b| format!("{:02x}", b)).collect();
+    format!("sha256:{}", &hex[..HASH_HEX_DIGITS])
+}
+
+fn sorted(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key.clone(), sorted(value)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(sorted).collect()),
+        other => other.clone(),
+    }
+}
+
+/// A destination for journal entries.
+pub trait JournalSink: Send + Sync {
+    /// Record the entries of one reload.
+    fn append(&self, entries: &[JournalEntry]) -> ConfigResult<()>;
+}
+
+/// Journal kept as JSON lines in a local file.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use confers::snapshot::ChangeJournal;
+///
+/// let journal = ChangeJournal::new("config-snapshots/changes.jsonl");
+/// for entry in journal.history("server.port")? {
+///     println!("{} changed at {} from {:?}", entry.key, entry.changed_at, entry.source);
+/// }
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+#[derive(Debug)]
+pub struct ChangeJournal {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl ChangeJournal {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Location of the journal file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Every recorded entry, oldest first. A missing journal has none.
+    pub fn entries(&self) -> ConfigResult<Vec<JournalEntry>> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut entries = Vec::new();
+        for (index, line) in std::io::BufReader::new(file).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line).map_err(|e| ConfigError::ParseError {
+                format: "json".to_string(),
+                message: format!("{} line {}: {}", self.path.display(), index + 1, e),
+                location: None,
+                source: Some(Box::new(e)),
+            })?;
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+
+    /// The entries concerning `key` (the key itself, a key below it or a key
+    /// above it), newest first.
+    pub fn history(&self, key: &str) -> ConfigResult<Vec<JournalEntry>> {
+        let mut entries: Vec<_> = self
+            .entries()?
+            .into_iter()
+            .filter(|entry| entry.concerns(key))
+            .collect();
+        entries.reverse();
+        Ok(entries)
+    }
+}
+
+impl JournalSink for ChangeJournal {
+    fn append(&self, entries: &[JournalEntry]) -> ConfigResult<()> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry).unwrap_or_default());
+            lines.push('\n');
+        }
+
+        // One write per reload keeps concurrent reloads from interleaving.
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(lines.as_bytes())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_records_hashes_and_sources() {
+        let old = json!({"server": {"port": 8080, "host": "a"}, "db": {"password": "hunter22"}});
+        let new = json!({"server": {"host": "a", "port": 9090, "tls": true}, "db": {"password": "hunter23"}});
+        let entries = JournalEntry::diff(
+            &old,
+            &new,
+            |key| Some(format!("{}.toml", key)),
+            &Redactor::new(),
+        );
+
+        let keys: Vec<_> = entries.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, ["db.password", "server.port", "server.tls"]);
+        assert_eq!(entries[0].new_hash.as_deref(), Some(REDACTED_HASH));
+        assert_eq!(entries[1].old_hash, Some(value_hash(&json!(8080))));
+        assert_eq!(entries[1].new_hash, Some(value_hash(&json!(9090))));
+        assert_eq!(entries[1].source.as_deref(), Some("server.port.toml"));
+        assert_eq!(entries[2].old_hash, None);
+        assert_eq!(value_hash(&json!(9090)).len(), "sha256:".len() + 16);
+        assert_eq!(
+            value_hash(&json!({"a": 1, "b": 2})),
+            value_hash(&json!({"b": 2, "a": 1}))
+        );
+    }
+
+    #[test]
+    fn test_history_filters_by_key_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let journal = ChangeJournal::new(dir.path().join("journal/changes.jsonl"));
+        assert!(journal.history("server.port").unwrap().is_empty());
+
+        let redactor = Redactor::new();
+        let none = |_: &str| None;
+        let v1 = json!({"server": {"port": 1}, "debug": false});
+        let v2 = json!({"server": {"port": 2}, "debug": true});
+        let v3 = json!({"server": {"port": 3}, "debug": true});
+        journal
+            .append(&JournalEntry::diff(&v1, &v2, none, &redactor))
+            .unwrap();
+        journal
+            .append(&JournalEntry::diff(&v2, &v3, none, &redactor))
+            .unwrap();
+
+        assert_eq!(journal.entries().unwrap().len(), 3);
+        let history = journal.history("server.port").unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].new_hash, Some(value_hash(&json!(3))));
+        assert_eq!(journal.history("server").unwrap().len(), 2);
+        assert!(journal.history("server.po").unwrap().is_empty());
+        assert_eq!(journal.history("debug").unwrap().len(), 1);
+    }
+}
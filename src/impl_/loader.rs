@@ -18,13 +18,8 @@
 #[cfg(any(feature = "toml", feature = "yaml"))]
 use crate::error::ParseLocation;
 use crate::error::{ConfigError, ConfigResult};
-use crate::types::{AnnotatedValue, SourceId};
+use crate::types::{AnnotatedValue, ConfigValue, SourceId};
 use std::path::{Component, Path, PathBuf};
-
-#[cfg(feature = "ini")]
-use crate::types::ConfigValue;
-
-#[cfg(feature = "ini")]
 use std::sync::Arc;
 
 #[cfg(feature = "json")]
@@ -55,6 +50,9 @@ pub struct LoaderConfig {
     pub allow_absolute: bool,
     /// Whether to check for symlink traversal (default: true).
     pub check_symlinks: bool,
+    /// How files readable by other users or owned by another user are
+    /// treated (default: not checked).
+    pub permission_policy: PermissionPolicy,
 }
 
 impl Default for LoaderConfig {
@@ -67,6 +65,7 @@ impl Default for LoaderConfig {
                 .collect(),
             allow_absolute: false,
             check_symlinks: true,
+            permission_policy: PermissionPolicy::Off,
         }
     }
 }
@@ -109,6 +108,90 @@ impl LoaderConfig {
         self.check_symlinks = false;
         self
     }
+
+    /// Check file permissions and ownership before loading.
+    pub fn permission_policy(mut self, policy: PermissionPolicy) -> Self {
+        self.permission_policy = policy;
+        self
+    }
+}
+
+// =============================================================================
+// File Permission Checks
+// =============================================================================
+
+/// What to do with a config or key file that other users can read or that
+/// another user owns, in the spirit of ssh's `StrictModes`.
+///
+/// Config files may not be readable or writable by others; key files may not
+/// be accessible to the group or others at all. Files owned by root are
+/// accepted. The checks only apply on Unix, and the owner check needs the
+/// `permissions` feature (on by default); without it only the mode is checked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PermissionPolicy {
+    /// Do not check.
+    #[default]
+    Off,
+    /// Report the problem and load the file anyway.
+    Warn,
+    /// Refuse to load the file with [`ConfigError::InsecurePermissions`].
+    Strict,
+}
+
+impl PermissionPolicy {
+    /// Check `path`, treating it as a key file when `private` is set.
+    ///
+    /// Returns the problem found under [`Warn`](Self::Warn) and fails under
+    /// [`Strict`](Self::Strict).
+    pub fn check(self, path: &Path, private: bool) -> ConfigResult<Option<String>> {
+        if self == PermissionPolicy::Off {
+            return Ok(None);
+        }
+        match permission_problem(path, private) {
+            Some(reason) if self == PermissionPolicy::Strict => {
+                Err(ConfigError::InsecurePermissions {
+                    path: path.to_path_buf(),
+                    reason,
+                })
+            }
+            problem => Ok(problem),
+        }
+    }
+}
+
+/// Why `path` is unsafe to load, if it is.
+#[cfg(unix)]
+fn permission_problem(path: &Path, private: bool) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+
+    let metadata = std::fs::metadata(path).ok()?;
+    let mode = metadata.mode() & 0o777;
+    #[cfg(feature = "permissions")]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail.
+        let euid = unsafe { libc::geteuid() };
+        if metadata.uid() != euid && metadata.uid() != 0 {
+            return Some(format!(
+                "owned by uid {}, not the current user",
+                metadata.uid()
+            ));
+        }
+    }
+    if private && mode & 0o077 != 0 {
+        return Some(format!(
+            "mode {:o} gives the group or other users access",
+            mode
+        ));
+    }
+    if !private && mode & 0o006 != 0 {
+        return Some(format!("mode {:o} gives other users access", mode));
+    }
+    None
+}
+
+#[cfg(not(unix))]
+fn permission_problem(_path: &Path, _private: bool) -> Option<String> {
+    None
 }
 
 // =============================================================================
@@ -493,6 +576,66 @@ pub fn detect_format_from_content(content: &str) -> Option<Format> {
 /// - File size exceeds the configured limit
 /// - File cannot be read or parsed
 pub fn load_file(path: &Path, config: &LoaderConfig) -> ConfigResult<AnnotatedValue> {
+    let (validated_path, metadata) = check_file(path, config)?;
+    let len = metadata.len();
+    #[cfg(feature = "mmap")]
+    if len >= MMAP_THRESHOLD {
+        return load_mapped(&validated_path, config);
+    }
+    #[cfg(not(feature = "mmap"))]
+    let _ = len;
+    let content = std::fs::read_to_string(&validated_path).map_err(ConfigError::IoError)?;
+    parse_file(&validated_path, &content)
+}
+
+/// Top-level key holding constraints embedded in a configuration file.
+///
+/// It maps dotted keys to JSON Schema keywords, plus `required = true`, and
+/// is enforced by `confers validate`. Files loaded from disk never expose it
+/// as configuration.
+pub const EMBEDDED_SCHEMA_KEY: &str = "__schema__";
+
+/// Parse `content` read from `validated_path`, taking the format from its
+/// extension. A top-level [`EMBEDDED_SCHEMA_KEY`] block is dropped.
+pub(crate) fn parse_file(validated_path: &Path, content: &str) -> ConfigResult<AnnotatedValue> {
+    let format =
+        detect_format_from_path(validated_path).ok_or_else(|| ConfigError::ParseError {
+            format: "unknown".into(),
+            message: format!("Unknown extension: {:?}", validated_path.extension()),
+            location: None,
+            source: None,
+        })?;
+    let source = SourceId::new(
+        validated_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown"),
+    );
+    let mut value = parse_content(content, format, source, Some(validated_path))?;
+    if let ConfigValue::Map(map) = &mut value.inner {
+        if map.contains_key(EMBEDDED_SCHEMA_KEY) {
+            Arc::make_mut(map).shift_remove(EMBEDDED_SCHEMA_KEY);
+        }
+    }
+    Ok(value)
+}
+
+/// Read a configuration file from disk without parsing it.
+///
+/// Applies the same path validation and size limit as [`load_file`] and
+/// returns the validated path together with the file content.
+pub fn read_file(path: &Path, config: &LoaderConfig) -> ConfigResult<(PathBuf, String)> {
+    let (validated_path, _) = check_file(path, config)?;
+    let content = std::fs::read_to_string(&validated_path).map_err(ConfigError::IoError)?;
+    Ok((validated_path, content))
+}
+
+/// Validate `path` and check its size against the limit, returning the
+/// validated path and the file metadata.
+pub(crate) fn check_file(
+    path: &Path,
+    config: &LoaderConfig,
+) -> ConfigResult<(PathBuf, std::fs::Metadata)> {
     // Path traversal protection: validate the path before loading
     let validated_path =
         validate_path_with_config(path, config).map_err(|e| ConfigError::InvalidValue {
@@ -505,27 +648,46 @@ pub fn load_file(path: &Path, config: &LoaderConfig) -> ConfigResult<AnnotatedVa
         filename: path.to_path_buf(),
         source: Some(e),
     })?;
-    if metadata.len() as usize > config.max_size {
+    check_size(metadata.len(), config)?;
+    if let Some(problem) = config.permission_policy.check(&validated_path, false)? {
+        #[cfg(feature = "tracing")]
+        tracing::warn!(path = %validated_path.display(), "insecure config file: {}", problem);
+        #[cfg(not(feature = "tracing"))]
+        let _ = problem;
+    }
+    Ok((validated_path, metadata))
+}
+
+fn check_size(len: u64, config: &LoaderConfig) -> ConfigResult<()> {
+    if len as usize > config.max_size {
         return Err(ConfigError::SizeLimitExceeded {
-            actual: metadata.len() as usize,
+            actual: len as usize,
             limit: config.max_size,
         });
     }
-    let format =
-        detect_format_from_path(&validated_path).ok_or_else(|| ConfigError::ParseError {
-            format: "unknown".into(),
-            message: format!("Unknown extension: {:?}", validated_path.extension()),
-            location: None,
-            source: None,
-        })?;
-    let content = std::fs::read_to_string(&validated_path).map_err(ConfigError::IoError)?;
-    let source = SourceId::new(
-        validated_path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown"),
-    );
-    parse_content(&content, format, source, Some(&validated_path))
+    Ok(())
+}
+
+/// Files at least this large are memory-mapped by [`load_file`] and parsed
+/// from the mapped bytes instead of being copied into a `String` first.
+#[cfg(feature = "mmap")]
+pub const MMAP_THRESHOLD: u64 = 1024 * 1024;
+
+/// Parse a file straight from a read-only memory map.
+#[cfg(feature = "mmap")]
+fn load_mapped(validated_path: &Path, config: &LoaderConfig) -> ConfigResult<AnnotatedValue> {
+    let file = std::fs::File::open(validated_path).map_err(ConfigError::IoError)?;
+    // SAFETY: the map is read-only and dropped before this function returns.
+    // Truncating the file while it is parsed would fault; configuration files
+    // are replaced by renaming (see `write_atomic`), which leaves the mapped
+    // inode intact.
+    let map = unsafe { memmap2::Mmap::map(&file) }.map_err(ConfigError::IoError)?;
+    // The file may have grown since its size was checked.
+    check_size(map.len() as u64, config)?;
+    let content = std::str::from_utf8(&map).map_err(|e| {
+        ConfigError::IoError(std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    })?;
+    parse_file(validated_path, content)
 }
 
 pub fn parse_content(
@@ -542,6 +704,243 @@ pub fn parse_content(
     }
 }
 
+/// JSON Schema dialect declared by [`write_schema`] when the schema has none.
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// Write a JSON Schema to `path` as pretty-printed JSON.
+///
+/// A `$schema` keyword naming [`JSON_SCHEMA_DIALECT`] is added when the
+/// schema does not declare one. The file is replaced atomically with
+/// [`write_atomic`].
+pub fn write_schema(schema: &serde_json::Value, path: impl AsRef<Path>) -> ConfigResult<()> {
+    write_atomic(path.as_ref(), schema_to_string(schema)?.as_bytes())
+}
+
+/// Pretty-print a JSON Schema the way [`write_schema`] writes it.
+pub(crate) fn schema_to_string(schema: &serde_json::Value) -> ConfigResult<String> {
+    let mut schema = schema.clone();
+    if let serde_json::Value::Object(map) = &mut schema {
+        if !map.contains_key("$schema") {
+            // serde_json maps are sorted, so the position of the key is not kept
+            map.insert(
+                "$schema".to_string(),
+                serde_json::Value::String(JSON_SCHEMA_DIALECT.to_string()),
+            );
+        }
+    }
+    let mut content =
+        serde_json::to_string_pretty(&schema).map_err(|e| ConfigError::ParseError {
+            format: "json".to_string(),
+            message: e.to_string(),
+            location: None,
+            source: Some(Box::new(e)),
+        })?;
+    content.push('\n');
+    Ok(content)
+}
+
+/// File extensions tried for each discovery location, in order.
+const DISCOVERY_EXTENSIONS: &[&str] = &["toml", "yaml", "yml", "json"];
+
+/// Candidate configuration files for an application, lowest priority first.
+///
+/// For `app_name = "myapp"` the locations are:
+///
+/// 1. `/etc/myapp/config.<ext>` (Unix only)
+/// 2. `$XDG_CONFIG_HOME/myapp/config.<ext>`, or `~/.config/myapp/config.<ext>`
+/// 3. `config/myapp.<ext>`
+/// 4. `myapp.<ext>`
+///
+/// where `<ext>` is each of `toml`, `yaml`, `yml` and `json`. The paths are
+/// returned whether or not they exist; see
+/// [`ConfigBuilder::discover`](crate::ConfigBuilder::discover).
+pub fn discovery_paths(app_name: &str) -> Vec<PathBuf> {
+    let mut dirs: Vec<(PathBuf, String)> = Vec::new();
+    #[cfg(unix)]
+    dirs.push((Path::new("/etc").join(app_name), "config".to_string()));
+    let user_config = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")));
+    if let Some(dir) = user_config {
+        dirs.push((dir.join(app_name), "config".to_string()));
+    }
+    dirs.push((PathBuf::from("config"), app_name.to_string()));
+    dirs.push((PathBuf::new(), app_name.to_string()));
+
+    dirs.into_iter()
+        .flat_map(|(dir, stem)| {
+            DISCOVERY_EXTENSIONS
+                .iter()
+                .map(move |ext| dir.join(format!("{}.{}", stem, ext)))
+        })
+        .collect()
+}
+
+/// Serialize a configuration value in the given format.
+///
+/// INI output is not supported.
+pub fn serialize_content<T: serde::Serialize + ?Sized>(
+    value: &T,
+    format: Format,
+) -> ConfigResult<String> {
+    match format {
+        #[cfg(feature = "toml")]
+        Format::Toml => toml::to_string_pretty(value).map_err(|e| ConfigError::ParseError {
+            format: "toml".to_string(),
+            message: e.to_string(),
+            location: None,
+            source: Some(Box::new(e)),
+        }),
+        #[cfg(feature = "json")]
+        Format::Json => serde_json::to_string_pretty(value)
+            .map(|mut json| {
+                json.push('\n');
+                json
+            })
+            .map_err(|e| ConfigError::ParseError {
+                format: "json".to_string(),
+                message: e.to_string(),
+                location: None,
+                source: Some(Box::new(e)),
+            }),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => serde_yaml_ng::to_string(value).map_err(|e| ConfigError::ParseError {
+            format: "yaml".to_string(),
+            message: e.to_string(),
+            location: None,
+            source: Some(Box::new(e)),
+        }),
+        #[cfg(not(feature = "toml"))]
+        Format::Toml => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "toml".to_string(),
+            message: "enable toml feature".to_string(),
+        }),
+        #[cfg(not(feature = "json"))]
+        Format::Json => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "json".to_string(),
+            message: "enable json feature".to_string(),
+        }),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "yaml".to_string(),
+            message: "enable yaml feature".to_string(),
+        }),
+        Format::Ini => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "toml, json or yaml".to_string(),
+            message: "INI output is not supported".to_string(),
+        }),
+    }
+}
+
+/// Serialize `config` and write it to `path` atomically.
+///
+/// The format is detected from the file extension. See [`write_atomic`] for
+/// how the file is replaced.
+///
+/// Secrets are not written: `SecureString` fields,
+/// including `#[config(secret)]` ones, serialize as `"[REDACTED]"`, so saving
+/// over a file that holds secrets replaces them with that placeholder. Keep
+/// secrets in their own file or environment variables.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #[derive(serde::Serialize)]
+/// # struct AppConfig { port: u16 }
+/// let config = AppConfig { port: 8080 };
+/// confers::save_file(&config, std::path::Path::new("config.toml"))?;
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+pub fn save_file<T: serde::Serialize + ?Sized>(config: &T, path: &Path) -> ConfigResult<()> {
+    let format = detect_format_from_path(path).ok_or_else(|| ConfigError::ParseError {
+        format: "unknown".into(),
+        message: format!("Unknown extension: {:?}", path.extension()),
+        location: None,
+        source: None,
+    })?;
+    let content = serialize_content(config, format)?;
+    write_atomic(path, content.as_bytes())
+}
+
+/// Replace the content of `path` without readers ever seeing a partial file.
+///
+/// The content is written to a new, uniquely named temporary file in the same
+/// directory, synced to disk and renamed over the target, so a file watcher
+/// observes either the old or the new file. The temporary file gets its
+/// permissions before any content is written: those of the existing file, or
+/// owner-only (`600`) on Unix for a new one. A symlinked path is written
+/// through to its target.
+pub fn write_atomic(path: &Path, content: &[u8]) -> ConfigResult<()> {
+    use std::io::Write;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static TMP_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let permissions = std::fs::metadata(&path).ok().map(|m| m.permissions());
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    // create_new refuses existing files and symlinks, so the temporary file
+    // is always our own.
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(permissions.as_ref().map_or(0o600, |p| p.mode() & 0o7777));
+    }
+    let (tmp_path, mut file) = loop {
+        let mut tmp_name = std::ffi::OsString::from(".");
+        tmp_name.push(path.file_name().unwrap_or_default());
+        tmp_name.push(format!(
+            ".{}.{}.tmp",
+            std::process::id(),
+            TMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let tmp_path = path.with_file_name(tmp_name);
+        match options.open(&tmp_path) {
+            Ok(file) => break (tmp_path, file),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(ConfigError::IoError(e)),
+        }
+    };
+
+    let written = (|| {
+        // The mode passed to open is reduced by the umask; restore it exactly.
+        #[cfg(unix)]
+        if let Some(permissions) = &permissions {
+            file.set_permissions(permissions.clone())?;
+        }
+        file.write_all(content)?;
+        #[cfg(not(unix))]
+        if let Some(permissions) = permissions {
+            file.set_permissions(permissions)?;
+        }
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, &path)
+    })();
+    if let Err(e) = written {
+        let _ = std::fs::remove_file(&tmp_path);
+        return Err(ConfigError::IoError(e));
+    }
+
+    // Persist the rename itself.
+    #[cfg(unix)]
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        if let Ok(dir) = std::fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    Ok(())
+}
+
 #[cfg(feature = "toml")]
 pub fn parse_toml(
     content: &str,
@@ -769,6 +1168,97 @@ pub fn parse_ini(
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    #[test]
+    fn test_write_schema_adds_dialect() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.schema.json");
+        write_schema(&serde_json::json!({ "type": "object" }), &path).unwrap();
+
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written["$schema"], JSON_SCHEMA_DIALECT);
+        assert_eq!(written["type"], "object");
+    }
+
+    #[test]
+    fn test_discovery_paths_order() {
+        let paths = discovery_paths("myapp");
+        let position = |p: &str| paths.iter().position(|c| c == Path::new(p)).unwrap();
+        assert!(position("config/myapp.toml") < position("myapp.toml"));
+        assert!(position("myapp.toml") < position("myapp.json"));
+        #[cfg(unix)]
+        assert_eq!(paths[0], Path::new("/etc/myapp/config.toml"));
+        assert_eq!(paths.last().unwrap(), Path::new("myapp.json"));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_save_file_round_trip() {
+        #[derive(serde::Serialize)]
+        struct Server {
+            host: String,
+            port: u16,
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        let server = Server {
+            host: "localhost".to_string(),
+            port: 8080,
+        };
+        save_file(&server, &path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("port = 8080"), "{content}");
+        parse_toml(&content, SourceId::new("app"), Some(&path)).unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        assert!(save_file(&server, &dir.path().join("app.conf")).is_err());
+        assert!(save_file(&server, &dir.path().join("app.ini")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_preserves_permissions_and_symlinks() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("real.json");
+        std::fs::write(&target, "{}").unwrap();
+        std::fs::set_permissions(&target, std::fs::Permissions::from_mode(0o640)).unwrap();
+        let link = dir.path().join("link.json");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        write_atomic(&link, b"{\"a\": 1}").unwrap();
+
+        assert!(std::fs::symlink_metadata(&link)
+            .unwrap()
+            .file_type()
+            .is_symlink());
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "{\"a\": 1}");
+        let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_atomic_creates_new_files_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("new.json");
+        // A file planted at the old fixed temporary name is left alone.
+        let planted = dir.path().join("new.json.tmp");
+        std::fs::write(&planted, "planted").unwrap();
+
+        write_atomic(&path, b"{}").unwrap();
+
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(std::fs::read_to_string(&planted).unwrap(), "planted");
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
     #[test]
     fn test_format_display() {
         assert_eq!(Format::Toml.to_string(), "TOML");
@@ -1503,6 +1993,22 @@ mod tests {
         let _ = std::fs::remove_file(test_file);
     }
 
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_load_file_drops_embedded_schema() {
+        let dir = tempfile::tempdir().unwrap();
+        let test_file = dir.path().join("app.toml");
+        std::fs::write(
+            &test_file,
+            "port = 8080\n\n[__schema__.port]\ntype = \"integer\"\n",
+        )
+        .unwrap();
+
+        let value = load_file(&test_file, &LoaderConfig::new().allow_absolute()).unwrap();
+        assert!(value.get_path("port").is_some());
+        assert!(value.get_path(EMBEDDED_SCHEMA_KEY).is_none());
+    }
+
     #[cfg(feature = "toml")]
     #[test]
     fn test_load_file_size_limit_exceeded() {
@@ -1516,4 +2022,66 @@ mod tests {
 
         let _ = std::fs::remove_file(test_file);
     }
+
+    #[cfg(all(unix, feature = "toml"))]
+    #[test]
+    fn test_load_file_strict_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let test_file = dir.path().join("app.toml");
+        std::fs::write(&test_file, "key = \"value\"\n").unwrap();
+        std::fs::set_permissions(&test_file, std::fs::Permissions::from_mode(0o604)).unwrap();
+
+        let config = LoaderConfig::new()
+            .allow_absolute()
+            .permission_policy(PermissionPolicy::Strict);
+        let err = load_file(&test_file, &config).unwrap_err();
+        assert_eq!(err.code(), crate::error::ErrorCode::FilePermission);
+        assert!(err.to_string().contains("mode 604"), "{}", err);
+
+        let warn = config.clone().permission_policy(PermissionPolicy::Warn);
+        assert!(load_file(&test_file, &warn).is_ok());
+
+        std::fs::set_permissions(&test_file, std::fs::Permissions::from_mode(0o640)).unwrap();
+        assert!(load_file(&test_file, &config).is_ok());
+        assert_eq!(
+            PermissionPolicy::Warn.check(&test_file, true).unwrap(),
+            Some("mode 640 gives the group or other users access".to_string())
+        );
+    }
+
+    #[cfg(all(feature = "mmap", feature = "json"))]
+    #[test]
+    fn test_load_file_memory_maps_large_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let test_file = dir.path().join("large.json");
+        let padding = "x".repeat(MMAP_THRESHOLD as usize);
+        std::fs::write(
+            &test_file,
+            format!("{{\"port\": 8080, \"padding\": \"{padding}\"}}"),
+        )
+        .unwrap();
+
+        let config = LoaderConfig::new().allow_absolute();
+        let value = load_file(&test_file, &config).unwrap();
+        assert_eq!(value.get_path("port").unwrap().as_i64(), Some(8080));
+
+        let err = load_file(&test_file, &config.max_size(1024)).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::SizeLimitExceeded { .. }),
+            "{err}"
+        );
+
+        std::fs::write(
+            &test_file,
+            [b'"', 0xff, b'"'].repeat(MMAP_THRESHOLD as usize),
+        )
+        .unwrap();
+        assert!(load_file(
+            &test_file,
+            &LoaderConfig::new().allow_absolute().max_size(usize::MAX)
+        )
+        .is_err());
+    }
 }
@@ -0,0 +1,205 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Prometheus metrics for configuration health.
+//!
+//! [`ConfigMetrics`] registers its collectors into a caller-owned
+//! [`Registry`], so they are scraped together with the service's own metrics.
+
+use prometheus::{Gauge, IntCounterVec, IntGaugeVec, Opts, Registry, TextEncoder};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// Counter of reloads. Label `outcome` is `committed` or `rejected`.
+pub const RELOADS_TOTAL: &str = "confers_reloads_total";
+
+/// Unix time of the last committed reload, in seconds.
+pub const LAST_RELOAD_TIMESTAMP: &str = "confers_last_reload_timestamp_seconds";
+
+/// Counter of failed validations. Label `validator`.
+pub const VALIDATION_FAILURES_TOTAL: &str = "confers_validation_failures_total";
+
+/// Current primary version of each key ring. Label `key_id`.
+pub const ACTIVE_KEY_VERSION: &str = "confers_active_key_version";
+
+/// `1` when the last fetch from a remote provider succeeded, `0` otherwise.
+/// Label `provider`.
+pub const REMOTE_PROVIDER_UP: &str = "confers_remote_provider_up";
+
+/// Configuration health metrics.
+///
+/// Share one instance (behind an `Arc`) between the builder, the reloader and
+/// the key rotation service.
+///
+/// # Example
+///
+/// ```rust
+/// use confers::metrics::{encode_text, ConfigMetrics};
+///
+/// let registry = prometheus::Registry::new();
+/// let metrics = ConfigMetrics::register(&registry).unwrap();
+/// metrics.record_reload(true);
+/// metrics.set_provider_health("consul", false);
+///
+/// let body = encode_text(&registry).unwrap();
+/// assert!(body.contains("confers_remote_provider_up{provider=\"consul\"} 0"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ConfigMetrics {
+    reloads: IntCounterVec,
+    last_reload: Gauge,
+    validation_failures: IntCounterVec,
+    active_key_version: IntGaugeVec,
+    remote_provider_up: IntGaugeVec,
+}
+
+fn metric_error(name: &str, e: prometheus::Error) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: name.to_string(),
+        expected_type: "prometheus metric".to_string(),
+        message: e.to_string(),
+    }
+}
+
+impl ConfigMetrics {
+    /// Create the collectors and register them into `registry`.
+    ///
+    /// Fails if `registry` already holds metrics with the same names.
+    pub fn register(registry: &Registry) -> ConfigResult<Self> {
+        let counter = |name: &str, help: &str, label: &str| {
+            IntCounterVec::new(Opts::new(name, help), &[label]).map_err(|e| metric_error(name, e))
+        };
+        let gauge = |name: &str, help: &str, label: &str| {
+            IntGaugeVec::new(Opts::new(name, help), &[label]).map_err(|e| metric_error(name, e))
+        };
+
+        let metrics = Self {
+            reloads: counter(RELOADS_TOTAL, "Configuration reloads", "outcome")?,
+            last_reload: Gauge::new(
+                LAST_RELOAD_TIMESTAMP,
+                "Unix time of the last committed configuration reload",
+            )
+            .map_err(|e| metric_error(LAST_RELOAD_TIMESTAMP, e))?,
+            validation_failures: counter(
+                VALIDATION_FAILURES_TOTAL,
+                "Failed configuration validations",
+                "validator",
+            )?,
+            active_key_version: gauge(
+                ACTIVE_KEY_VERSION,
+                "Current primary version of each key ring",
+                "key_id",
+            )?,
+            remote_provider_up: gauge(
+                REMOTE_PROVIDER_UP,
+                "Whether the last fetch from a remote provider succeeded",
+                "provider",
+            )?,
+        };
+
+        registry
+            .register(Box::new(metrics.reloads.clone()))
+            .map_err(|e| metric_error(RELOADS_TOTAL, e))?;
+        registry
+            .register(Box::new(metrics.last_reload.clone()))
+            .map_err(|e| metric_error(LAST_RELOAD_TIMESTAMP, e))?;
+        registry
+            .register(Box::new(metrics.validation_failures.clone()))
+            .map_err(|e| metric_error(VALIDATION_FAILURES_TOTAL, e))?;
+        registry
+            .register(Box::new(metrics.active_key_version.clone()))
+            .map_err(|e| metric_error(ACTIVE_KEY_VERSION, e))?;
+        registry
+            .register(Box::new(metrics.remote_provider_up.clone()))
+            .map_err(|e| metric_error(REMOTE_PROVIDER_UP, e))?;
+        Ok(metrics)
+    }
+
+    /// Count a reload; a committed one also updates the last reload time.
+    pub fn record_reload(&self, committed: bool) {
+        let outcome = if committed { "committed" } else { "rejected" };
+        self.reloads.with_label_values(&[outcome]).inc();
+        if committed {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            self.last_reload.set(now.as_secs_f64());
+        }
+    }
+
+    pub fn record_validation_failure(&self, validator: &str) {
+        self.validation_failures
+            .with_label_values(&[validator])
+            .inc();
+    }
+
+    pub fn set_active_key_version(&self, key_id: &str, version: u32) {
+        self.active_key_version
+            .with_label_values(&[key_id])
+            .set(i64::from(version));
+    }
+
+    /// Set the active version of every key ring in `manager`.
+    #[cfg(feature = "key")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "key")))]
+    pub fn observe_keys(&self, manager: &crate::key::KeyManager) {
+        for key in manager.list_keys() {
+            self.set_active_key_version(&key.key_id, key.current_version);
+        }
+    }
+
+    pub fn set_provider_health(&self, provider: &str, healthy: bool) {
+        self.remote_provider_up
+            .with_label_values(&[provider])
+            .set(i64::from(healthy));
+    }
+}
+
+/// Encode every metric in `registry` in the Prometheus text format, as
+/// served from a `/metrics` endpoint with content type
+/// `text/plain; version=0.0.4`.
+pub fn encode_text(registry: &Registry) -> ConfigResult<String> {
+    TextEncoder::new()
+        .encode_to_string(&registry.gather())
+        .map_err(|e| metric_error("registry", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_are_exported() {
+        let registry = Registry::new();
+        let metrics = ConfigMetrics::register(&registry).unwrap();
+        metrics.record_reload(true);
+        metrics.record_reload(false);
+        metrics.record_validation_failure("db");
+        metrics.set_active_key_version("prod", 3);
+        metrics.set_provider_health("etcd", true);
+
+        let body = encode_text(&registry).unwrap();
+        for line in [
+            "confers_reloads_total{outcome=\"committed\"} 1",
+            "confers_reloads_total{outcome=\"rejected\"} 1",
+            "confers_validation_failures_total{validator=\"db\"} 1",
+            "confers_active_key_version{key_id=\"prod\"} 3",
+            "confers_remote_provider_up{provider=\"etcd\"} 1",
+        ] {
+            assert!(body.contains(line), "missing {line} in\n{body}");
+        }
+        assert!(metrics.last_reload.get() > 0.0);
+    }
+
+    #[test]
+    fn test_register_twice_fails() {
+        let registry = Registry::new();
+        ConfigMetrics::register(&registry).unwrap();
+        assert!(matches!(
+            ConfigMetrics::register(&registry),
+            Err(ConfigError::InvalidValue { .. })
+        ));
+    }
+}
@@ -25,6 +25,9 @@
 #[cfg(feature = "audit")]
 #[allow(dead_code)]
 pub(crate) mod audit;
+#[cfg(feature = "audit")]
+#[allow(dead_code)]
+pub(crate) mod audit_sink;
 #[allow(dead_code)]
 pub(crate) mod config;
 #[cfg(feature = "context-aware")]
@@ -34,14 +37,20 @@ pub(crate) mod context;
 pub(crate) mod convert;
 #[allow(dead_code)]
 pub(crate) mod default;
+#[allow(dead_code)]
+pub(crate) mod diff;
 #[cfg(feature = "dynamic")]
 #[allow(dead_code)]
 pub(crate) mod dynamic;
+#[cfg(feature = "flags")]
+pub(crate) mod flags;
 #[allow(dead_code)]
 pub(crate) mod format;
 #[cfg(feature = "interpolation")]
 #[allow(dead_code)]
 pub(crate) mod interpolation;
+#[cfg(feature = "snapshot")]
+pub(crate) mod journal;
 #[allow(dead_code)]
 pub(crate) mod lifecycle;
 #[allow(dead_code)]
@@ -50,18 +59,35 @@ pub(crate) mod loader;
 pub(crate) mod memory;
 #[allow(dead_code)]
 pub(crate) mod merger;
+#[cfg(feature = "prometheus")]
+#[allow(dead_code)]
+pub(crate) mod metrics;
 #[cfg(feature = "migration")]
 #[allow(dead_code)]
 pub(crate) mod migration;
 #[cfg(feature = "modules")]
 #[allow(dead_code)]
 pub(crate) mod modules;
+#[cfg(feature = "otel")]
+#[allow(dead_code)]
+pub(crate) mod otel;
 #[cfg(feature = "schema")]
 #[allow(dead_code)]
 pub(crate) mod schema;
+#[cfg(feature = "server")]
+pub(crate) mod server;
+#[cfg(feature = "signing")]
+pub(crate) mod signing;
 #[cfg(feature = "snapshot")]
 #[allow(dead_code)]
 pub(crate) mod snapshot;
-#[cfg(feature = "validation")]
+#[cfg(feature = "snapshot")]
+pub(crate) mod snapshot_store;
+#[allow(dead_code)]
+pub(crate) mod template;
+#[cfg(feature = "testing")]
+#[allow(dead_code)]
+pub(crate) mod testing;
+#[cfg(any(feature = "validation", feature = "async-validation"))]
 #[allow(dead_code)]
 pub(crate) mod validator;
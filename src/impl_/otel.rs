@@ -0,0 +1,204 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! OpenTelemetry instrumentation.
+//!
+//! Spans (`confers.load`, `confers.decrypt`, `confers.remote.fetch`,
+//! `confers.validate`, `confers.reload`) are emitted through `tracing`; a
+//! `tracing-opentelemetry` layer exports them over OTLP. Metrics are recorded
+//! on the global OpenTelemetry meter provider under the `confers` meter.
+//! Without an installed provider every instrument is a no-op.
+
+use std::time::Duration;
+
+use opentelemetry::metrics::Meter;
+use opentelemetry::{global, KeyValue};
+
+use crate::interface::MetricsBackend;
+
+/// Name of the meter every confers instrument is created on.
+pub const METER_NAME: &str = "confers";
+
+/// Histogram of build durations in seconds. Attribute `outcome` is `ok` or
+/// `error`.
+pub const LOAD_DURATION: &str = "confers.load.duration";
+
+/// Histogram of reload latencies in seconds, from `begin_reload` until the
+/// candidate is committed or rolled back. Attributes `strategy` and
+/// `outcome` (`committed`, `rolled_back` or `error`).
+pub const RELOAD_LATENCY: &str = "confers.reload.latency";
+
+/// Counter of failed remote provider fetches. Attribute `provider`.
+pub const REMOTE_FETCH_FAILURES: &str = "confers.remote.fetch_failures";
+
+/// Counter of failed async validators. Attribute `validator`.
+pub const VALIDATION_FAILURES: &str = "confers.validation.failures";
+
+fn meter() -> Meter {
+    global::meter(METER_NAME)
+}
+
+pub(crate) fn record_load(duration: Duration, ok: bool) {
+    meter()
+        .f64_histogram(LOAD_DURATION)
+        .with_unit("s")
+        .with_description("Duration of configuration builds")
+        .build()
+        .record(
+            duration.as_secs_f64(),
+            &[KeyValue::new("outcome", if ok { "ok" } else { "error" })],
+        );
+}
+
+pub(crate) fn record_reload(strategy: &'static str, outcome: &'static str, latency: Duration) {
+    meter()
+        .f64_histogram(RELOAD_LATENCY)
+        .with_unit("s")
+        .with_description("Latency of progressive reloads")
+        .build()
+        .record(
+            latency.as_secs_f64(),
+            &[
+                KeyValue::new("strategy", strategy),
+                KeyValue::new("outcome", outcome),
+            ],
+        );
+}
+
+pub(crate) fn record_remote_fetch_failure(provider: &str) {
+    meter()
+        .u64_counter(REMOTE_FETCH_FAILURES)
+        .with_description("Failed remote provider fetches")
+        .build()
+        .add(1, &[KeyValue::new("provider", provider.to_string())]);
+}
+
+pub(crate) fn record_validation_failure(validator: &str) {
+    meter()
+        .u64_counter(VALIDATION_FAILURES)
+        .with_description("Failed async validators")
+        .build()
+        .add(1, &[KeyValue::new("validator", validator.to_string())]);
+}
+
+/// [`MetricsBackend`] that records on an OpenTelemetry [`Meter`].
+///
+/// Counters become `u64` counters and histograms `f64` histograms named after
+/// the metric; labels become attributes.
+#[derive(Debug, Clone)]
+pub struct OtelMetrics {
+    meter: Meter,
+}
+
+impl OtelMetrics {
+    /// Record on the global `confers` meter.
+    pub fn new() -> Self {
+        Self::with_meter(meter())
+    }
+
+    /// Record on `meter`.
+    pub fn with_meter(meter: Meter) -> Self {
+        Self { meter }
+    }
+}
+
+impl Default for OtelMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn attributes(labels: &[(&str, &str)]) -> Vec<KeyValue> {
+    labels
+        .iter()
+        .map(|(key, value)| KeyValue::new(key.to_string(), value.to_string()))
+        .collect()
+}
+
+impl MetricsBackend for OtelMetrics {
+    fn counter(&self, name: &str, labels: &[(&str, &str)]) {
+        self.meter
+            .u64_counter(name.to_string())
+            .build()
+            .add(1, &attributes(labels));
+    }
+
+    fn histogram(&self, name: &str, value: f64, labels: &[(&str, &str)]) {
+        self.meter
+            .f64_histogram(name.to_string())
+            .build()
+            .record(value, &attributes(labels));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::metrics::MeterProvider;
+    use opentelemetry_sdk::metrics::data::{AggregatedMetrics, MetricData};
+    use opentelemetry_sdk::metrics::{InMemoryMetricExporter, PeriodicReader, SdkMeterProvider};
+
+    fn provider() -> (SdkMeterProvider, InMemoryMetricExporter) {
+        let exporter = InMemoryMetricExporter::default();
+        let provider = SdkMeterProvider::builder()
+            .with_reader(PeriodicReader::builder(exporter.clone()).build())
+            .build();
+        (provider, exporter)
+    }
+
+    fn metric_names(provider: &SdkMeterProvider, exporter: &InMemoryMetricExporter) -> Vec<String> {
+        provider.force_flush().unwrap();
+        exporter
+            .get_finished_metrics()
+            .unwrap()
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .map(|metric| metric.name().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_otel_metrics_backend() {
+        let (provider, exporter) = provider();
+        let metrics = OtelMetrics::with_meter(provider.meter(METER_NAME));
+        metrics.counter("requests", &[("method", "GET")]);
+        metrics.histogram("latency", 0.5, &[]);
+
+        let mut names = metric_names(&provider, &exporter);
+        names.sort();
+        assert_eq!(names, ["latency", "requests"]);
+    }
+
+    #[test]
+    fn test_builds_record_load_duration() {
+        let (provider, exporter) = provider();
+        global::set_meter_provider(provider.clone());
+
+        crate::config::ConfigBuilder::<serde_json::Value>::new()
+            .with_json_str(r#"{"port": 1}"#)
+            .build()
+            .unwrap();
+
+        provider.force_flush().unwrap();
+        let metrics = exporter.get_finished_metrics().unwrap();
+        let load = metrics
+            .iter()
+            .flat_map(|resource| resource.scope_metrics())
+            .flat_map(|scope| scope.metrics())
+            .find(|metric| metric.name() == LOAD_DURATION)
+            .expect("load duration recorded");
+        match load.data() {
+            AggregatedMetrics::F64(MetricData::Histogram(histogram)) => {
+                assert!(histogram
+                    .data_points()
+                    .any(|point| point
+                        .attributes()
+                        .any(|kv| kv.key.as_str() == "outcome" && kv.value.as_str() == "ok")));
+            }
+            other => panic!("unexpected data {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,632 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Read-only HTTP endpoints for the merged configuration.
+//!
+//! The server answers plain HTTP/1.1 `GET` and `HEAD` requests, one per
+//! connection:
+//!
+//! | Path | Response |
+//! |------|----------|
+//! | `/config` | The sanitized configuration as JSON, with an `ETag` |
+//! | `/config/<dotted.key>` | One subtree of it |
+//! | `/health` | `{"status": "ok", "version": n}` |
+//! | `/metadata` | Version, `ETag`, last reload time, key count and sources |
+//!
+//! `If-None-Match` with the current `ETag` is answered with
+//! `304 Not Modified`, so sidecars can poll cheaply.
+//!
+//! With [`ConfigServer::with_reload_endpoint`], `POST /reload` carrying
+//! `Authorization: Bearer <token>` triggers a reload and answers
+//! `202 Accepted`.
+//!
+//! At most [`DEFAULT_MAX_CONNECTIONS`] connections are handled at once (see
+//! [`ConfigServer::with_max_connections`]); connections accepted beyond that
+//! are closed without a response.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+
+use crate::error::{redact_sensitive, ConfigResult};
+use crate::types::{AnnotatedValue, ConfigValue, SerializeMode};
+use crate::watcher::ReloadHandle;
+
+/// Address `confers serve` binds to by default.
+pub const DEFAULT_SERVER_BIND: &str = "127.0.0.1:7777";
+
+/// Largest request head accepted, in bytes.
+const MAX_REQUEST_HEAD: usize = 8 * 1024;
+
+/// Time a client gets to send its request head.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Connections handled at once unless
+/// [`ConfigServer::with_max_connections`] says otherwise.
+pub const DEFAULT_MAX_CONNECTIONS: usize = 256;
+
+/// Serves a configuration tree over HTTP.
+///
+/// Values under the sensitive keys, and values that look like secrets, are
+/// redacted before anything is served. Clones share state, so a clone kept
+/// by a reload callback can [`update`](Self::update) the served tree.
+///
+/// # Examples
+///
+/// ```no_run
+/// # async fn example() -> confers::ConfigResult<()> {
+/// use confers::server::ConfigServer;
+/// use confers::ConfigBuilder;
+///
+/// let tree = ConfigBuilder::<serde_json::Value>::new()
+///     .file("config.toml")
+///     .build_annotated()?;
+/// let server = ConfigServer::new(&tree).with_sensitive_keys(["database.dsn"]);
+/// server.listen("127.0.0.1:7777").await
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ConfigServer {
+    state: Arc<RwLock<ServedConfig>>,
+    reload: Option<Arc<ReloadEndpoint>>,
+    max_connections: usize,
+}
+
+/// Where `POST /reload` sends its triggers, and the token it requires.
+struct ReloadEndpoint {
+    handle: ReloadHandle,
+    token: String,
+}
+
+/// The tree being served, rendered once per update.
+struct ServedConfig {
+    tree: AnnotatedValue,
+    sensitive_keys: Vec<String>,
+    json: serde_json::Value,
+    etag: String,
+    version: u64,
+    last_reload: String,
+}
+
+impl ServedConfig {
+    fn render(&mut self) {
+        let paths: Vec<&str> = self.sensitive_keys.iter().map(String::as_str).collect();
+        let mut json = self.tree.to_json_with_mode(SerializeMode::Redacted, &paths);
+        redact_sensitive(&mut json, "");
+        let etag = content_etag(&json);
+        if etag != self.etag {
+            self.version += 1;
+            self.etag = etag;
+        }
+        self.json = json;
+    }
+}
+
+impl ConfigServer {
+    /// Serve `tree`.
+    pub fn new(tree: &AnnotatedValue) -> Self {
+        let mut served = ServedConfig {
+            tree: tree.clone(),
+            sensitive_keys: Vec::new(),
+            json: serde_json::Value::Null,
+            etag: String::new(),
+            version: 0,
+            last_reload: chrono::Utc::now().to_rfc3339(),
+        };
+        served.render();
+        Self {
+            state: Arc::new(RwLock::new(served)),
+            reload: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+        }
+    }
+
+    /// Redact the values at these dotted keys and below them.
+    pub fn with_sensitive_keys(self, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        {
+            let mut served = self.write();
+            served
+                .sensitive_keys
+                .extend(keys.into_iter().map(Into::into));
+            served.render();
+        }
+        self
+    }
+
+    /// Accept `POST /reload` with `Authorization: Bearer <token>` and pass
+    /// it to `handle` as an `"http"` trigger. Requests without the token are
+    /// refused with `401`; an empty token refuses every request.
+    pub fn with_reload_endpoint(mut self, handle: ReloadHandle, token: impl Into<String>) -> Self {
+        self.reload = Some(Arc::new(ReloadEndpoint {
+            handle,
+            token: token.into(),
+        }));
+        self
+    }
+
+    /// Handle at most `max` connections at once (default
+    /// [`DEFAULT_MAX_CONNECTIONS`]). Connections accepted while all are in
+    /// use are closed straight away.
+    pub fn with_max_connections(mut self, max: usize) -> Self {
+        self.max_connections = max;
+        self
+    }
+
+    /// Serve `tree` from now on, e.g. after a reload. The version is bumped
+    /// when the served content changes.
+    pub fn update(&self, tree: &AnnotatedValue) {
+        let mut served = self.write();
+        served.tree = tree.clone();
+        served.last_reload = chrono::Utc::now().to_rfc3339();
+        served.render();
+    }
+
+    /// Version of the served content, starting at 1.
+    pub fn version(&self) -> u64 {
+        self.read().version
+    }
+
+    /// `ETag` of the served content.
+    pub fn etag(&self) -> String {
+        self.read().etag.clone()
+    }
+
+    /// Bind `addr` and serve until the task is dropped.
+    pub async fn listen(self, addr: &str) -> ConfigResult<()> {
+        let listener = TcpListener::bind(addr).await?;
+        self.serve(listener).await
+    }
+
+    /// Serve connections accepted from `listener` until the task is dropped.
+    pub async fn serve(self, listener: TcpListener) -> ConfigResult<()> {
+        let permits = Arc::new(Semaphore::new(self.max_connections));
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let Ok(permit) = permits.clone().try_acquire_owned() else {
+                // Dropping the stream closes it; the client may retry.
+                continue;
+            };
+            let server = self.clone();
+            tokio::spawn(async move {
+                // A broken connection only affects that client.
+                let _ = server.handle(stream).await;
+                drop(permit);
+            });
+        }
+    }
+
+    async fn handle(&self, mut stream: TcpStream) -> std::io::Result<()> {
+        let response = match tokio::time::timeout(REQUEST_TIMEOUT, read_head(&mut stream)).await {
+            Ok(Ok(Some(head))) => self.respond(&head),
+            Ok(Ok(None)) => Response::error(400, "Bad Request"),
+            Ok(Err(e)) => return Err(e),
+            Err(_) => Response::error(408, "Request Timeout"),
+        };
+        stream.write_all(&response.to_bytes()).await?;
+        stream.shutdown().await
+    }
+
+    fn respond(&self, head: &str) -> Response {
+        let mut request_line = head.split("\r\n").next().unwrap_or_default().split(' ');
+        let (Some(method), Some(target)) = (request_line.next(), request_line.next()) else {
+            return Response::error(400, "Bad Request");
+        };
+        let path = target.split('?').next().unwrap_or_default();
+        if path == "/reload" {
+            return self.reload(method, header(head, "authorization"));
+        }
+        let head_only = match method {
+            "GET" => false,
+            "HEAD" => true,
+            _ => return Response::error(405, "Method Not Allowed").header("Allow", "GET, HEAD"),
+        };
+        let if_none_match = header(head, "if-none-match");
+
+        let served = self.read();
+        let response = match path {
+            "/health" => Response::json(
+                200,
+                "OK",
+                &serde_json::json!({"status": "ok", "version": served.version}),
+            ),
+            "/metadata" => Response::json(
+                200,
+                "OK",
+                &serde_json::json!({
+                    "version": served.version,
+                    "etag": served.etag,
+                    "last_reload": served.last_reload,
+                    "keys": count_leaves(&served.tree),
+                    "sources": sources(&served.tree),
+                }),
+            ),
+            "/config" | "/config/" => served.conditional(&served.json, if_none_match),
+            _ => match path.strip_prefix("/config/") {
+                Some(key) => match lookup(&served.json, key) {
+                    Some(value) => served.conditional(value, if_none_match),
+                    None => Response::error(404, "Not Found"),
+                },
+                None => Response::error(404, "Not Found"),
+            },
+        };
+        if head_only {
+            response.without_body()
+        } else {
+            response
+        }
+    }
+
+    fn reload(&self, method: &str, authorization: Option<&str>) -> Response {
+        let Some(endpoint) = &self.reload else {
+            return Response::error(404, "Not Found");
+        };
+        if method != "POST" {
+            return Response::error(405, "Method Not Allowed").header("Allow", "POST");
+        }
+        let token = authorization.and_then(|value| value.strip_prefix("Bearer "));
+        let authorized = token.is_some_and(|token| {
+            !endpoint.token.is_empty() && constant_time_eq(token.trim(), &endpoint.token)
+        });
+        if !authorized {
+            return Response::error(401, "Unauthorized").header("WWW-Authenticate", "Bearer");
+        }
+        if endpoint.handle.trigger("http") {
+            Response::json(202, "Accepted", &serde_json::json!({"status": "accepted"}))
+        } else {
+            Response::error(503, "Service Unavailable")
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, ServedConfig> {
+        self.state.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, ServedConfig> {
+        self.state.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl ServedConfig {
+    /// `value` tagged with the content `ETag`, or `304` if the client has it.
+    fn conditional(&self, value: &serde_json::Value, if_none_match: Option<&str>) -> Response {
+        let matches = if_none_match.is_some_and(|tags| {
+            tags.split(',')
+                .any(|tag| tag.trim() == self.etag || tag.trim() == "*")
+        });
+        let response = if matches {
+            Response::new(304, "Not Modified")
+        } else {
+            Response::json(200, "OK", value)
+        };
+        response
+            .header("ETag", &self.etag)
+            .header("X-Config-Version", &self.version.to_string())
+    }
+}
+
+/// An HTTP response; the connection is closed after it.
+struct Response {
+    status: u16,
+    reason: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+    content_length: usize,
+}
+
+impl Response {
+    fn new(status: u16, reason: &'static str) -> Self {
+        Self {
+            status,
+            reason,
+            headers: Vec::new(),
+            body: Vec::new(),
+            content_length: 0,
+        }
+    }
+
+    fn json(status: u16, reason: &'static str, value: &serde_json::Value) -> Self {
+        let mut body = serde_json::to_vec_pretty(value).unwrap_or_default();
+        body.push(b'\n');
+        let mut response = Self::new(status, reason).header("Content-Type", "application/json");
+        response.content_length = body.len();
+        response.body = body;
+        response
+    }
+
+    fn error(status: u16, reason: &'static str) -> Self {
+        Self::json(status, reason, &serde_json::json!({ "error": reason }))
+    }
+
+    fn header(mut self, name: &'static str, value: &str) -> Self {
+        self.headers.push((name, value.to_string()));
+        self
+    }
+
+    /// Keep the headers of a `GET` response for a `HEAD` request.
+    fn without_body(mut self) -> Self {
+        self.body.clear();
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut out = format!("HTTP/1.1 {} {}\r\n", self.status, self.reason);
+        for (name, value) in &self.headers {
+            out.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        out.push_str(&format!(
+            "Content-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+            self.content_length
+        ));
+        let mut bytes = out.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+/// Read up to the blank line ending the request head. `None` when the head
+/// is too large or the client stops early.
+async fn read_head(stream: &mut TcpStream) -> std::io::Result<Option<String>> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.ends_with(b"\r\n\r\n") {
+        let n = stream.read(&mut buf).await?;
+        if n == 0 || head.len() + n > MAX_REQUEST_HEAD {
+            return Ok(None);
+        }
+        head.extend_from_slice(&buf[..n]);
+        if let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") {
+            head.truncate(end + 4);
+        }
+    }
+    Ok(String::from_utf8(head).ok())
+}
+
+/// Value of the first `name` header, trimmed.
+fn header<'a>(head: &'a str, name: &str) -> Option<&'a str> {
+    head.split("\r\n").skip(1).find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case(name)
+            .then_some(value.trim())
+    })
+}
+
+/// Compare without returning early, so the token can't be guessed from
+/// response timing.
+fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0u8, |diff, (x, y)| diff | (x ^ y))
+            == 0
+}
+
+/// Strong `ETag` of the served JSON.
+fn content_etag(json: &serde_json::Value) -> String {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    json.to_string().hash(&mut hasher);
+    format!("\"{:016x}\"", hasher.finish())
+}
+
+/// The value at a dotted key; array elements are addressed by index.
+fn lookup<'a>(json: &'a serde_json::Value, key: &str) -> Option<&'a serde_json::Value> {
+    key.trim_end_matches('/')
+        .split('.')
+        .try_fold(json, |node, segment| match node {
+            serde_json::Value::Object(map) => map.get(segment),
+            serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+fn count_leaves(tree: &AnnotatedValue) -> usize {
+    match &tree.inner {
+        ConfigValue::Map(map) => map.values().map(count_leaves).sum(),
+        ConfigValue::Array(items) => items.iter().map(count_leaves).sum(),
+        _ => 1,
+    }
+}
+
+/// Distinct sources of the tree's values, in first-seen order.
+fn sources(tree: &AnnotatedValue) -> Vec<String> {
+    fn collect(tree: &AnnotatedValue, out: &mut Vec<String>) {
+        match &tree.inner {
+            ConfigValue::Map(map) => map.values().for_each(|v| collect(v, out)),
+            ConfigValue::Array(items) => items.iter().for_each(|v| collect(v, out)),
+            _ => {
+                let source = tree.source.to_string();
+                if !out.contains(&source) {
+                    out.push(source);
+                }
+            }
+        }
+    }
+    let mut out = Vec::new();
+    collect(tree, &mut out);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConfigBuilder;
+    use std::collections::HashMap;
+
+    fn tree(port: u64) -> AnnotatedValue {
+        ConfigBuilder::<serde_json::Value>::new()
+            .memory(HashMap::from([
+                ("server.port".to_string(), ConfigValue::uint(port)),
+                (
+                    "database.password".to_string(),
+                    ConfigValue::String("hunter2".to_string()),
+                ),
+                (
+                    "database.dsn".to_string(),
+                    ConfigValue::String("postgres://db/app".to_string()),
+                ),
+            ]))
+            .build_annotated()
+            .unwrap()
+    }
+
+    async fn get(addr: std::net::SocketAddr, request: &str) -> String {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        stream.write_all(request.as_bytes()).await.unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    async fn start(server: ConfigServer) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(server.serve(listener));
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_serves_sanitized_config_with_etag() {
+        let server = ConfigServer::new(&tree(8080)).with_sensitive_keys(["database.dsn"]);
+        let etag = server.etag();
+        let addr = start(server).await;
+
+        let response = get(addr, "GET /config HTTP/1.1\r\nHost: x\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+        assert!(response.contains(&format!("ETag: {}\r\n", etag)));
+        assert!(response.contains("\"port\": 8080"));
+        assert!(!response.contains("hunter2"));
+        assert!(!response.contains("postgres://"));
+
+        let response = get(
+            addr,
+            &format!("GET /config HTTP/1.1\r\nIf-None-Match: {}\r\n\r\n", etag),
+        )
+        .await;
+        assert!(
+            response.starts_with("HTTP/1.1 304 Not Modified\r\n"),
+            "{}",
+            response
+        );
+
+        let response = get(addr, "GET /config/server.port HTTP/1.1\r\n\r\n").await;
+        assert!(response.ends_with("\r\n\r\n8080\n"), "{}", response);
+
+        let response = get(addr, "GET /config/server.missing HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let response = get(addr, "POST /config HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_update_bumps_version_when_content_changes() {
+        let server = ConfigServer::new(&tree(8080));
+        assert_eq!(server.version(), 1);
+        server.update(&tree(8080));
+        assert_eq!(server.version(), 1);
+        server.update(&tree(9090));
+        assert_eq!(server.version(), 2);
+
+        let addr = start(server.clone()).await;
+        let response = get(addr, "GET /metadata HTTP/1.1\r\n\r\n").await;
+        assert!(response.contains("\"version\": 2"), "{}", response);
+        assert!(response.contains("\"keys\": 3"), "{}", response);
+        assert!(response.contains("\"last_reload\""));
+        assert!(response.contains(&format!("\"etag\": {:?}", server.etag())));
+
+        let response = get(addr, "HEAD /health HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.ends_with("\r\n\r\n"));
+
+        let response = get(addr, "POST /reload HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_reload_endpoint_requires_token() {
+        let (handle, mut triggers) = ReloadHandle::new();
+        let server = ConfigServer::new(&tree(8080)).with_reload_endpoint(handle, "s3cret");
+        let addr = start(server).await;
+
+        for auth in [
+            "",
+            "Authorization: Bearer wrong\r\n",
+            "Authorization: s3cret\r\n",
+        ] {
+            let response = get(addr, &format!("POST /reload HTTP/1.1\r\n{}\r\n", auth)).await;
+            assert!(
+                response.starts_with("HTTP/1.1 401 Unauthorized\r\n"),
+                "{}",
+                response
+            );
+            assert!(response.contains("WWW-Authenticate: Bearer\r\n"));
+        }
+
+        let response = get(
+            addr,
+            "GET /reload HTTP/1.1\r\nAuthorization: Bearer s3cret\r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 405 Method Not Allowed\r\n"));
+
+        let response = get(
+            addr,
+            "POST /reload HTTP/1.1\r\nauthorization: Bearer s3cret\r\n\r\n",
+        )
+        .await;
+        assert!(
+            response.starts_with("HTTP/1.1 202 Accepted\r\n"),
+            "{}",
+            response
+        );
+        assert_eq!(triggers.recv().await.as_deref(), Some("http"));
+    }
+
+    #[tokio::test]
+    async fn test_empty_reload_token_refuses_everything() {
+        let (handle, _triggers) = ReloadHandle::new();
+        let server = ConfigServer::new(&tree(8080)).with_reload_endpoint(handle, "");
+        let addr = start(server).await;
+
+        let response = get(
+            addr,
+            "POST /reload HTTP/1.1\r\nAuthorization: Bearer \r\n\r\n",
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_connections_beyond_the_limit_are_closed() {
+        let server = ConfigServer::new(&tree(8080)).with_max_connections(1);
+        let addr = start(server).await;
+
+        // Holds the only permit until it sends its request.
+        let mut idle = TcpStream::connect(addr).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let mut refused = TcpStream::connect(addr).await.unwrap();
+        let mut response = String::new();
+        // Closed unanswered, possibly with a reset.
+        let _ = refused.read_to_string(&mut response).await;
+        assert_eq!(response, "");
+
+        idle.write_all(b"GET /health HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        idle.read_to_string(&mut response).await.unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+
+        // The permit is released once the handler returns.
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let response = get(addr, "GET /health HTTP/1.1\r\n\r\n").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{}", response);
+    }
+}
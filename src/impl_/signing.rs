@@ -0,0 +1,229 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Ed25519 signatures over config files.
+//!
+//! A file is signed either with a detached signature next to it
+//! (`app.yaml.sig`) or with a footer comment on its last line:
+//!
+//! ```text
+//! # confers-signature: ed25519:<base64>
+//! ```
+//!
+//! The embedded signature covers every byte before the footer line, so the
+//! file still parses as before. JSON has no comments and needs a detached
+//! signature. Keys are PEM files as written by
+//! `openssl genpkey -algorithm ed25519` (PKCS#8) and
+//! `openssl pkey -pubout` (SubjectPublicKeyInfo).
+
+use std::path::{Path, PathBuf};
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use ed25519_dalek::pkcs8::{DecodePrivateKey, DecodePublicKey};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// Extension appended to a config file's name for its detached signature.
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Start of the footer line holding an embedded signature.
+pub const SIGNATURE_FOOTER_PREFIX: &str = "# confers-signature: ";
+
+const SIGNATURE_ALGORITHM: &str = "ed25519";
+
+/// Path of the detached signature of `path` (`app.yaml` → `app.yaml.sig`).
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(SIGNATURE_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Read a PKCS#8 PEM Ed25519 private key.
+pub fn load_signing_key(path: &Path) -> ConfigResult<SigningKey> {
+    let pem = read_key_file(path)?;
+    SigningKey::from_pkcs8_pem(&pem).map_err(|e| ConfigError::KeyError {
+        message: format!("{} is not a PEM Ed25519 private key: {}", path.display(), e),
+    })
+}
+
+/// Read a PEM Ed25519 public key.
+pub fn load_verifying_key(path: &Path) -> ConfigResult<VerifyingKey> {
+    let pem = read_key_file(path)?;
+    VerifyingKey::from_public_key_pem(&pem).map_err(|e| ConfigError::KeyError {
+        message: format!("{} is not a PEM Ed25519 public key: {}", path.display(), e),
+    })
+}
+
+fn read_key_file(path: &Path) -> ConfigResult<String> {
+    std::fs::read_to_string(path).map_err(|e| ConfigError::KeyError {
+        message: format!("Failed to read key file {}: {}", path.display(), e),
+    })
+}
+
+/// Sign `content`, returning the text of a detached signature
+/// (`ed25519:<base64>`).
+pub fn sign_config(content: &[u8], key: &SigningKey) -> String {
+    format!(
+        "{}:{}",
+        SIGNATURE_ALGORITHM,
+        STANDARD.encode(key.sign(content).to_bytes())
+    )
+}
+
+/// Sign `content` and append the signature as a footer comment.
+///
+/// An existing footer is replaced, so re-signing an edited file is safe.
+pub fn embed_signature(content: &str, key: &SigningKey) -> String {
+    let (body, _) = split_footer(content);
+    let mut signed = body.to_string();
+    if !signed.is_empty() && !signed.ends_with('\n') {
+        signed.push('\n');
+    }
+    let signature = sign_config(signed.as_bytes(), key);
+    signed.push_str(SIGNATURE_FOOTER_PREFIX);
+    signed.push_str(&signature);
+    signed.push('\n');
+    signed
+}
+
+/// Check the signature of the config file at `path`.
+///
+/// An embedded footer is used when present, otherwise the detached
+/// signature next to the file. Fails with [`ConfigError::SignatureInvalid`]
+/// when neither exists or the signature does not match.
+pub fn verify_config_file(path: &Path, key: &VerifyingKey) -> ConfigResult<()> {
+    let invalid = |reason: String| ConfigError::SignatureInvalid {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let content = std::fs::read(path).map_err(|e| invalid(format!("cannot read file: {}", e)))?;
+    let footer = std::str::from_utf8(&content)
+        .ok()
+        .map(split_footer)
+        .and_then(|(body, footer)| Some((body.len(), footer?.to_string())));
+    let (signed, signature) = match footer {
+        Some((body_len, signature)) => (&content[..body_len], signature),
+        None => {
+            let sig_path = signature_path(path);
+            let signature = match std::fs::read_to_string(&sig_path) {
+                Ok(signature) => signature,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                    return Err(invalid("no signature found".to_string()));
+                }
+                Err(e) => {
+                    return Err(invalid(format!(
+                        "cannot read {}: {}",
+                        sig_path.display(),
+                        e
+                    )))
+                }
+            };
+            (&content[..], signature)
+        }
+    };
+
+    let signature = parse_signature(signature.trim()).map_err(invalid)?;
+    key.verify(signed, &signature)
+        .map_err(|_| invalid("the signature does not match the file contents".to_string()))
+}
+
+/// Split `content` into the signed body and the signature on its footer
+/// line, if there is one.
+fn split_footer(content: &str) -> (&str, Option<&str>) {
+    let trimmed = content.trim_end_matches(['\n', '\r']);
+    let line_start = trimmed.rfind('\n').map_or(0, |i| i + 1);
+    match trimmed[line_start..].strip_prefix(SIGNATURE_FOOTER_PREFIX) {
+        Some(signature) => (&content[..line_start], Some(signature)),
+        None => (content, None),
+    }
+}
+
+fn parse_signature(text: &str) -> Result<Signature, String> {
+    let encoded = text
+        .strip_prefix(SIGNATURE_ALGORITHM)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(|| format!("expected an '{}:' signature", SIGNATURE_ALGORITHM))?;
+    let bytes = STANDARD
+        .decode(encoded)
+        .map_err(|_| "the signature is not valid base64".to_string())?;
+    Signature::from_slice(&bytes).map_err(|_| "the signature has the wrong length".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+    use ed25519_dalek::pkcs8::{EncodePrivateKey, EncodePublicKey};
+
+    fn key() -> SigningKey {
+        SigningKey::from_bytes(&[7; 32])
+    }
+
+    #[test]
+    fn test_detached_signature_detects_tampering() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.yaml");
+        std::fs::write(&path, "name: app\n").unwrap();
+        let verifying = key().verifying_key();
+
+        let err = verify_config_file(&path, &verifying).unwrap_err();
+        assert!(err.to_string().contains("no signature found"), "{}", err);
+
+        let signature = sign_config(b"name: app\n", &key());
+        assert!(signature.starts_with("ed25519:"));
+        std::fs::write(signature_path(&path), format!("{}\n", signature)).unwrap();
+        verify_config_file(&path, &verifying).unwrap();
+
+        std::fs::write(&path, "name: evil\n").unwrap();
+        let err = verify_config_file(&path, &verifying).unwrap_err();
+        assert!(err.to_string().contains("does not match"), "{}", err);
+
+        let other = SigningKey::from_bytes(&[8; 32]).verifying_key();
+        std::fs::write(&path, "name: app\n").unwrap();
+        assert!(verify_config_file(&path, &other).is_err());
+    }
+
+    #[test]
+    fn test_embedded_signature_round_trips_and_is_replaced() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml");
+        let signed = embed_signature("name = \"app\"", &key());
+        assert!(signed.starts_with("name = \"app\"\n# confers-signature: ed25519:"));
+        std::fs::write(&path, &signed).unwrap();
+        verify_config_file(&path, &key().verifying_key()).unwrap();
+
+        let resigned = embed_signature(&signed.replace("app", "api"), &key());
+        assert_eq!(resigned.matches(SIGNATURE_FOOTER_PREFIX).count(), 1);
+        std::fs::write(&path, &resigned).unwrap();
+        verify_config_file(&path, &key().verifying_key()).unwrap();
+
+        std::fs::write(&path, resigned.replace("api", "evil")).unwrap();
+        assert!(verify_config_file(&path, &key().verifying_key()).is_err());
+    }
+
+    #[test]
+    fn test_keys_load_from_pem() {
+        let dir = tempfile::tempdir().unwrap();
+        let private = dir.path().join("signing.pem");
+        let public = dir.path().join("signing.pub.pem");
+        std::fs::write(&private, key().to_pkcs8_pem(LineEnding::LF).unwrap()).unwrap();
+        std::fs::write(
+            &public,
+            key()
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(load_signing_key(&private).unwrap(), key());
+        assert_eq!(load_verifying_key(&public).unwrap(), key().verifying_key());
+        assert!(load_verifying_key(&private).is_err());
+        assert!(load_signing_key(&dir.path().join("missing.pem")).is_err());
+    }
+}
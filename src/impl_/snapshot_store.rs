@@ -0,0 +1,341 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Last-known-good configuration store.
+//!
+//! [`ConfigSnapshotStore`] keeps a single JSON file holding the last
+//! configuration that loaded and validated, together with the contents of the
+//! configuration files it was loaded from and a SHA-256 checksum over both.
+//! The reload subsystem writes it after every committed reload; `confers
+//! rollback` writes the recorded files back to disk.
+
+use crate::error::{ConfigError, ConfigResult};
+use chrono::{DateTime, Utc};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Default location of the store, next to [`SnapshotConfig`]'s default
+/// snapshot directory.
+///
+/// [`SnapshotConfig`]: crate::snapshot::SnapshotConfig
+pub const DEFAULT_SNAPSHOT_STORE: &str = "config-snapshots/known-good.json";
+
+/// A configuration file recorded in a [`KnownGoodSnapshot`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// Absolute path the file was read from and is restored to.
+    pub path: PathBuf,
+    pub content: String,
+}
+
+/// The record persisted by [`ConfigSnapshotStore`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KnownGoodSnapshot {
+    pub saved_at: DateTime<Utc>,
+    /// `sha256:<hex>` over `config` and `files`.
+    pub checksum: String,
+    /// The validated configuration, serialized as JSON.
+    pub config: serde_json::Value,
+    pub files: Vec<SnapshotFile>,
+}
+
+impl KnownGoodSnapshot {
+    fn new(config: serde_json::Value, files: Vec<SnapshotFile>) -> ConfigResult<Self> {
+        let checksum = checksum(&config, &files)?;
+        Ok(Self {
+            saved_at: Utc::now(),
+            checksum,
+            config,
+            files,
+        })
+    }
+
+    /// Whether `checksum` still matches the recorded configuration and files.
+    pub fn verify(&self) -> bool {
+        checksum(&self.config, &self.files).is_ok_and(|c| c == self.checksum)
+    }
+
+    /// Deserialize the recorded configuration.
+    pub fn config<T: DeserializeOwned>(&self) -> ConfigResult<T> {
+        T::deserialize(&self.config).map_err(|e| ConfigError::InvalidValue {
+            key: String::new(),
+            expected_type: std::any::type_name::<T>().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Write every recorded file back to its path, atomically, and return
+    /// the paths written.
+    pub fn restore_files(&self) -> ConfigResult<Vec<PathBuf>> {
+        let mut restored = Vec::with_capacity(self.files.len());
+        for file in &self.files {
+            crate::loader::write_atomic(&file.path, file.content.as_bytes())?;
+            restored.push(file.path.clone());
+        }
+        Ok(restored)
+    }
+}
+
+fn checksum(config: &serde_json::Value, files: &[SnapshotFile]) -> ConfigResult<String> {
+    use sha2::{Digest, Sha256};
+
+    let bytes = serde_json::to_vec(&(config, files)).map_err(json_error)?;
+    let hash = Sha256::digest(&bytes);
+    let hex: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    Ok(format!("sha256:{}", hex))
+}
+
+fn json_error(e: serde_json::Error) -> ConfigError {
+    ConfigError::ParseError {
+        format: "json".to_string(),
+        message: e.to_string(),
+        location: None,
+        source: Some(Box::new(e)),
+    }
+}
+
+/// Persists the last successfully validated configuration.
+///
+/// The store file holds the configuration in plaintext, including values that
+/// were decrypted while loading. On Unix a new store file is created with mode
+/// `0600`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use confers::snapshot::ConfigSnapshotStore;
+///
+/// # #[derive(serde::Serialize, serde::Deserialize, Default)]
+/// # struct AppConfig { port: u16 }
+/// let store = ConfigSnapshotStore::new("/var/lib/myapp/known-good.json")
+///     .track_file("/etc/myapp/config.toml");
+///
+/// let config: AppConfig = match confers::ConfigBuilder::<AppConfig>::new()
+///     .file("/etc/myapp/config.toml")
+///     .build()
+/// {
+///     Ok(config) => {
+///         store.save(&config)?;
+///         config
+///     }
+///     // Serve the last known-good configuration instead
+///     Err(e) => store.load_config()?.ok_or(e)?,
+/// };
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+#[derive(Debug)]
+pub struct ConfigSnapshotStore {
+    path: PathBuf,
+    files: Vec<PathBuf>,
+    checksum: Mutex<Option<String>>,
+}
+
+impl ConfigSnapshotStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            files: Vec::new(),
+            checksum: Mutex::new(None),
+        }
+    }
+
+    /// Record the contents of `path` with every snapshot, so that
+    /// [`restore_files`](Self::restore_files) can put it back. Relative paths
+    /// are resolved against the current directory.
+    pub fn track_file(mut self, path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        self.files.push(std::path::absolute(&path).unwrap_or(path));
+        self
+    }
+
+    /// Path of the store file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Files recorded with every snapshot.
+    pub fn tracked_files(&self) -> &[PathBuf] {
+        &self.files
+    }
+
+    /// Checksum of the snapshot last saved or loaded through this store.
+    pub fn checksum(&self) -> Option<String> {
+        self.checksum.lock().ok().and_then(|c| c.clone())
+    }
+
+    /// Persist `config` and the current contents of the tracked files as the
+    /// last known-good snapshot, replacing the previous one atomically.
+    ///
+    /// Call this only with a configuration that passed validation.
+    pub fn save<T: Serialize + ?Sized>(&self, config: &T) -> ConfigResult<KnownGoodSnapshot> {
+        let config = serde_json::to_value(config).map_err(json_error)?;
+        let files = self
+            .files
+            .iter()
+            .map(|path| {
+                Ok(SnapshotFile {
+                    path: path.clone(),
+                    content: std::fs::read_to_string(path)?,
+                })
+            })
+            .collect::<ConfigResult<Vec<_>>>()?;
+        let snapshot = KnownGoodSnapshot::new(config, files)?;
+
+        let content = serde_json::to_string_pretty(&snapshot).map_err(json_error)?;
+        self.create_private()?;
+        crate::loader::write_atomic(&self.path, content.as_bytes())?;
+        self.remember(&snapshot);
+        Ok(snapshot)
+    }
+
+    /// Read the last known-good snapshot, or `None` if nothing was saved yet.
+    ///
+    /// Fails if the file was modified after it was written.
+    pub fn load(&self) -> ConfigResult<Option<KnownGoodSnapshot>> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let snapshot: KnownGoodSnapshot = serde_json::from_str(&content).map_err(json_error)?;
+        if !snapshot.verify() {
+            return Err(ConfigError::ParseError {
+                format: "snapshot".to_string(),
+                message: format!("checksum mismatch in {}", self.path.display()),
+                location: None,
+                source: None,
+            });
+        }
+        self.remember(&snapshot);
+        Ok(Some(snapshot))
+    }
+
+    /// Deserialize the configuration of the last known-good snapshot.
+    pub fn load_config<T: DeserializeOwned>(&self) -> ConfigResult<Option<T>> {
+        self.load()?.map(|snapshot| snapshot.config()).transpose()
+    }
+
+    /// Write the files recorded in the last known-good snapshot back to disk
+    /// and return their paths.
+    pub fn restore_files(&self) -> ConfigResult<Vec<PathBuf>> {
+        match self.load()? {
+            Some(snapshot) => snapshot.restore_files(),
+            None => Err(ConfigError::FileNotFound {
+                filename: self.path.clone(),
+                source: None,
+            }),
+        }
+    }
+
+    fn remember(&self, snapshot: &KnownGoodSnapshot) {
+        if let Ok(mut checksum) = self.checksum.lock() {
+            *checksum = Some(snapshot.checksum.clone());
+        }
+    }
+
+    /// Create the store file owner-only, so that `write_atomic` keeps those
+    /// permissions on every later write.
+    fn create_private(&self) -> ConfigResult<()> {
+        #[cfg(unix)]
+        if !self.path.exists() {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&self.path)?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConfigSnapshotStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_SNAPSHOT_STORE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct AppConfig {
+        port: u16,
+    }
+
+    #[test]
+    fn test_save_and_load_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConfigSnapshotStore::new(dir.path().join("store/known-good.json"));
+        assert!(store.load().unwrap().is_none());
+
+        let saved = store.save(&AppConfig { port: 8080 }).unwrap();
+        assert!(saved.checksum.starts_with("sha256:"));
+        assert_eq!(store.checksum(), Some(saved.checksum.clone()));
+
+        let config: AppConfig = store.load_config().unwrap().unwrap();
+        assert_eq!(config, AppConfig { port: 8080 });
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(store.path())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+    }
+
+    #[test]
+    fn test_tampered_snapshot_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConfigSnapshotStore::new(dir.path().join("known-good.json"));
+        store.save(&AppConfig { port: 8080 }).unwrap();
+
+        let content = std::fs::read_to_string(store.path()).unwrap();
+        std::fs::write(store.path(), content.replace("8080", "9090")).unwrap();
+
+        let err = store.load().unwrap_err();
+        assert!(err.to_string().contains("checksum mismatch"));
+    }
+
+    #[test]
+    fn test_restore_tracked_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        std::fs::write(&config_path, "port = 8080\n").unwrap();
+
+        let store =
+            ConfigSnapshotStore::new(dir.path().join("known-good.json")).track_file(&config_path);
+        store.save(&AppConfig { port: 8080 }).unwrap();
+
+        std::fs::write(&config_path, "port = \"broken\"\n").unwrap();
+        let restored = store.restore_files().unwrap();
+
+        assert_eq!(restored, vec![config_path.clone()]);
+        assert_eq!(
+            std::fs::read_to_string(&config_path).unwrap(),
+            "port = 8080\n"
+        );
+    }
+
+    #[test]
+    fn test_restore_without_snapshot_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = ConfigSnapshotStore::new(dir.path().join("known-good.json"));
+        assert!(matches!(
+            store.restore_files(),
+            Err(ConfigError::FileNotFound { .. })
+        ));
+    }
+}
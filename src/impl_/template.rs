@@ -0,0 +1,499 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Configuration template generation from a JSON Schema.
+
+use serde_json::Value;
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::impl_::loader::Format;
+
+/// Schema nesting followed before a property is emitted as a plain value;
+/// guards against recursive `$ref`s.
+const MAX_DEPTH: usize = 32;
+
+/// How much of the schema a generated template covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TemplateLevel {
+    /// Only the keys that must be set: required properties and properties
+    /// without a default. No comments.
+    Minimal,
+    /// Every property, with its description and allowed values as comments.
+    #[default]
+    Full,
+}
+
+impl std::str::FromStr for TemplateLevel {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> ConfigResult<Self> {
+        match s.to_lowercase().as_str() {
+            "minimal" => Ok(TemplateLevel::Minimal),
+            "full" => Ok(TemplateLevel::Full),
+            other => Err(ConfigError::InvalidValue {
+                key: "level".to_string(),
+                expected_type: "minimal or full".to_string(),
+                message: format!("unknown template level: {}", other),
+            }),
+        }
+    }
+}
+
+/// A key in the template.
+struct Entry {
+    key: String,
+    comments: Vec<String>,
+    node: Node,
+}
+
+enum Node {
+    /// A value; `commented` values are optional keys without a default and
+    /// are written commented out.
+    Leaf {
+        value: Value,
+        commented: bool,
+    },
+    Table(Vec<Entry>),
+}
+
+/// Generate a configuration template from a JSON Schema.
+///
+/// Each property is written with its `default`, or a placeholder for its
+/// type when it has none. With [`TemplateLevel::Full`] the `description`
+/// (or `title`) and the allowed `enum` values are written as comments;
+/// optional properties without a default are written commented out. JSON
+/// has no comments, so JSON templates only contain the values.
+///
+/// Local `$ref`s (`#/$defs/...`, `#/definitions/...`) are followed, so
+/// schemas exported by `schemars` work as well as those from
+/// `#[derive(ConfigSchema)]`.
+///
+/// # Example
+///
+/// ```rust
+/// use confers::template::{generate_template, TemplateLevel};
+/// use confers::Format;
+///
+/// let schema = serde_json::json!({
+///     "type": "object",
+///     "properties": {
+///         "port": { "type": "integer", "default": 8080, "description": "Listen port" }
+///     }
+/// });
+/// let template = generate_template(&schema, Format::Json, TemplateLevel::Full).unwrap();
+/// assert!(template.contains("8080"));
+/// ```
+pub fn generate_template(
+    schema: &Value,
+    format: Format,
+    level: TemplateLevel,
+) -> ConfigResult<String> {
+    let entries = build_entries(schema, schema, None, level, 0);
+    match format {
+        Format::Json => {
+            crate::impl_::loader::serialize_content(&Value::Object(to_json(&entries)), format)
+        }
+        #[cfg(feature = "toml")]
+        Format::Toml => {
+            let mut out = String::new();
+            write_toml(&entries, &[], &mut out)?;
+            Ok(out.trim_start().to_string())
+        }
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let mut out = String::new();
+            write_yaml(&entries, 0, &mut out)?;
+            Ok(out)
+        }
+        #[cfg(not(feature = "toml"))]
+        Format::Toml => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "toml".to_string(),
+            message: "enable toml feature".to_string(),
+        }),
+        #[cfg(not(feature = "yaml"))]
+        Format::Yaml => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "yaml".to_string(),
+            message: "enable yaml feature".to_string(),
+        }),
+        Format::Ini => Err(ConfigError::InvalidValue {
+            key: "format".to_string(),
+            expected_type: "toml, json or yaml".to_string(),
+            message: "INI templates are not supported".to_string(),
+        }),
+    }
+}
+
+/// Follow `$ref`s and pick the object branch of `allOf`/`anyOf`/`oneOf`.
+pub(crate) fn resolve<'a>(schema: &'a Value, root: &'a Value) -> &'a Value {
+    let mut current = schema;
+    for _ in 0..MAX_DEPTH {
+        if let Some(pointer) = current.get("$ref").and_then(Value::as_str) {
+            match pointer
+                .strip_prefix('#')
+                .and_then(|pointer| root.pointer(pointer))
+            {
+                Some(target) => {
+                    current = target;
+                    continue;
+                }
+                None => return current,
+            }
+        }
+        let branch = ["allOf", "anyOf", "oneOf"].iter().find_map(|keyword| {
+            current
+                .get(keyword)
+                .and_then(Value::as_array)?
+                .iter()
+                .find(|branch| !is_null_type(branch))
+        });
+        match branch {
+            Some(branch) if current.get("properties").is_none() => current = branch,
+            _ => return current,
+        }
+    }
+    current
+}
+
+fn is_null_type(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Whether `null` is an accepted value of the property.
+pub(crate) fn is_nullable(schema: &Value) -> bool {
+    match schema.get("type") {
+        Some(Value::Array(types)) => types.iter().any(|t| t == "null"),
+        Some(Value::String(t)) => t == "null",
+        _ => ["anyOf", "oneOf"].iter().any(|keyword| {
+            schema
+                .get(keyword)
+                .and_then(Value::as_array)
+                .is_some_and(|branches| branches.iter().any(is_null_type))
+        }),
+    }
+}
+
+/// Placeholder value for a schema without a default.
+fn placeholder(schema: &Value) -> Value {
+    if let Some(first) = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .and_then(|values| values.iter().find(|v| !v.is_null()))
+    {
+        return first.clone();
+    }
+    let kind = match schema.get("type") {
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|t| *t != "null"),
+        Some(Value::String(t)) => Some(t.as_str()),
+        _ => None,
+    };
+    match kind {
+        Some("integer") => Value::from(0),
+        Some("number") => Value::from(0.0),
+        Some("boolean") => Value::Bool(false),
+        Some("array") => Value::Array(Vec::new()),
+        Some("object") => Value::Object(serde_json::Map::new()),
+        _ => Value::String(String::new()),
+    }
+}
+
+fn build_entries(
+    schema: &Value,
+    root: &Value,
+    parent_default: Option<&Value>,
+    level: TemplateLevel,
+    depth: usize,
+) -> Vec<Entry> {
+    let schema = resolve(schema, root);
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut entries = Vec::new();
+    for (key, property) in properties {
+        let resolved = resolve(property, root);
+        let default = parent_default
+            .and_then(|parent| parent.get(key))
+            .or_else(|| property.get("default"))
+            .or_else(|| resolved.get("default"));
+        let is_required = required.contains(&key.as_str());
+
+        let node = if resolved.get("properties").is_some() && depth < MAX_DEPTH {
+            let children = build_entries(resolved, root, default, level, depth + 1);
+            if level == TemplateLevel::Minimal && children.is_empty() && !is_required {
+                continue;
+            }
+            Node::Table(children)
+        } else {
+            let nullable = is_nullable(property) || is_nullable(resolved);
+            if level == TemplateLevel::Minimal && !is_required && (default.is_some() || nullable) {
+                continue;
+            }
+            match default {
+                Some(Value::Null) | None if nullable => Node::Leaf {
+                    value: placeholder(resolved),
+                    commented: true,
+                },
+                Some(value) => Node::Leaf {
+                    value: value.clone(),
+                    commented: false,
+                },
+                None => Node::Leaf {
+                    value: placeholder(resolved),
+                    commented: false,
+                },
+            }
+        };
+
+        let mut comments = Vec::new();
+        if level == TemplateLevel::Full {
+            let description = property
+                .get("description")
+                .or_else(|| resolved.get("description"))
+                .or_else(|| property.get("title"))
+                .and_then(Value::as_str);
+            if let Some(description) = description {
+                comments.extend(description.lines().map(|line| line.trim().to_string()));
+            }
+            if let Some(values) = resolved.get("enum").and_then(Value::as_array) {
+                let values: Vec<String> = values.iter().map(Value::to_string).collect();
+                comments.push(format!("One of: {}", values.join(", ")));
+            }
+            if is_required {
+                comments.push("Required.".to_string());
+            }
+        }
+
+        entries.push(Entry {
+            key: key.clone(),
+            comments,
+            node,
+        });
+    }
+    entries
+}
+
+fn to_json(entries: &[Entry]) -> serde_json::Map<String, Value> {
+    entries
+        .iter()
+        .filter_map(|entry| match &entry.node {
+            Node::Leaf {
+                commented: true, ..
+            } => None,
+            Node::Leaf { value, .. } => Some((entry.key.clone(), value.clone())),
+            Node::Table(children) => Some((entry.key.clone(), Value::Object(to_json(children)))),
+        })
+        .collect()
+}
+
+#[cfg(feature = "toml")]
+fn toml_key(key: &str) -> String {
+    let bare = !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if bare {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+#[cfg(feature = "toml")]
+fn write_toml(entries: &[Entry], path: &[String], out: &mut String) -> ConfigResult<()> {
+    for entry in entries {
+        if let Node::Leaf { value, commented } = &entry.node {
+            for comment in &entry.comments {
+                out.push_str(&format!("# {}\n", comment));
+            }
+            let rendered = toml::Value::try_from(value)
+                .map_err(|e| ConfigError::ParseError {
+                    format: "toml".to_string(),
+                    message: format!("{}: {}", entry.key, e),
+                    location: None,
+                    source: Some(Box::new(e)),
+                })?
+                .to_string();
+            let prefix = if *commented { "# " } else { "" };
+            out.push_str(&format!(
+                "{}{} = {}\n",
+                prefix,
+                toml_key(&entry.key),
+                rendered
+            ));
+        }
+    }
+    for entry in entries {
+        if let Node::Table(children) = &entry.node {
+            let mut table_path = path.to_vec();
+            table_path.push(toml_key(&entry.key));
+            out.push('\n');
+            for comment in &entry.comments {
+                out.push_str(&format!("# {}\n", comment));
+            }
+            out.push_str(&format!("[{}]\n", table_path.join(".")));
+            write_toml(children, &table_path, out)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "yaml")]
+fn yaml_scalar<T: serde::Serialize>(value: &T) -> ConfigResult<String> {
+    serde_yaml_ng::to_string(value)
+        .map(|s| s.trim_end().to_string())
+        .map_err(|e| ConfigError::ParseError {
+            format: "yaml".to_string(),
+            message: e.to_string(),
+            location: None,
+            source: Some(Box::new(e)),
+        })
+}
+
+#[cfg(feature = "yaml")]
+fn write_yaml(entries: &[Entry], indent: usize, out: &mut String) -> ConfigResult<()> {
+    let pad = " ".repeat(indent);
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 && !entry.comments.is_empty() {
+            out.push('\n');
+        }
+        for comment in &entry.comments {
+            out.push_str(&format!("{}# {}\n", pad, comment));
+        }
+        let key = yaml_scalar(&entry.key)?;
+        match &entry.node {
+            Node::Leaf { value, commented } => {
+                // Non-empty collections are written in flow style, which is
+                // valid YAML and keeps the entry on one line
+                let rendered = match value {
+                    Value::Array(items) if !items.is_empty() => value.to_string(),
+                    Value::Object(map) if !map.is_empty() => value.to_string(),
+                    _ => yaml_scalar(value)?,
+                };
+                let prefix = if *commented { "# " } else { "" };
+                out.push_str(&format!("{}{}{}: {}\n", pad, prefix, key, rendered));
+            }
+            Node::Table(children) if children.is_empty() => {
+                out.push_str(&format!("{}{}: {{}}\n", pad, key));
+            }
+            Node::Table(children) => {
+                out.push_str(&format!("{}{}:\n", pad, key));
+                write_yaml(children, indent + 2, out)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string", "description": "Application name" },
+                "port": { "type": "integer", "default": 8080, "description": "Listen port" },
+                "log_level": {
+                    "type": "string",
+                    "enum": ["debug", "info", "warn"],
+                    "default": "info"
+                },
+                "proxy": { "type": ["string", "null"], "description": "Outbound proxy URL" },
+                "database": { "$ref": "#/$defs/Database" }
+            },
+            "required": ["name"],
+            "$defs": {
+                "Database": {
+                    "type": "object",
+                    "description": "Database connection",
+                    "properties": {
+                        "url": { "type": "string" },
+                        "pool": { "type": "integer", "default": 10 },
+                        "tags": { "type": "array", "default": ["primary"] }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_template_level_from_str() {
+        assert_eq!(
+            "FULL".parse::<TemplateLevel>().unwrap(),
+            TemplateLevel::Full
+        );
+        assert_eq!(
+            "minimal".parse::<TemplateLevel>().unwrap(),
+            TemplateLevel::Minimal
+        );
+        assert!("verbose".parse::<TemplateLevel>().is_err());
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_generate_toml_full() {
+        let out = generate_template(&schema(), Format::Toml, TemplateLevel::Full).unwrap();
+        assert!(out.contains("# Listen port\nport = 8080\n"), "{out}");
+        assert!(out.contains("# One of: \"debug\", \"info\", \"warn\"\nlog_level = \"info\"\n"));
+        assert!(out.contains("# Application name\n# Required.\nname = \"\"\n"));
+        assert!(out.contains("# Outbound proxy URL\n# proxy = \"\"\n"));
+        assert!(out.contains("# Database connection\n[database]\n"));
+        assert!(out.contains("tags = [\"primary\"]\n"));
+
+        let parsed: toml::Table = toml::from_str(&out).unwrap();
+        assert_eq!(parsed["database"]["pool"].as_integer(), Some(10));
+        assert!(!parsed.contains_key("proxy"));
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn test_generate_yaml_round_trips() {
+        let out = generate_template(&schema(), Format::Yaml, TemplateLevel::Full).unwrap();
+        assert!(out.contains("# Listen port\nport: 8080\n"), "{out}");
+        assert!(out.contains("database:\n  pool: 10\n"));
+        assert!(out.contains("# proxy: ''\n"));
+
+        let parsed: serde_yaml_ng::Value = serde_yaml_ng::from_str(&out).unwrap();
+        assert_eq!(parsed["database"]["tags"][0].as_str(), Some("primary"));
+        assert_eq!(parsed["log_level"].as_str(), Some("info"));
+    }
+
+    #[test]
+    fn test_generate_minimal_json() {
+        let out = generate_template(&schema(), Format::Json, TemplateLevel::Minimal).unwrap();
+        let parsed: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed, json!({ "name": "", "database": { "url": "" } }));
+    }
+
+    #[test]
+    fn test_generate_uses_parent_default() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "server": {
+                    "type": "object",
+                    "default": { "host": "0.0.0.0" },
+                    "properties": { "host": { "type": "string", "default": "localhost" } }
+                }
+            }
+        });
+        let out = generate_template(&schema, Format::Json, TemplateLevel::Full).unwrap();
+        let parsed: Value = serde_json::from_str(&out).unwrap();
+        assert_eq!(parsed["server"]["host"], "0.0.0.0");
+        assert!(generate_template(&schema, Format::Ini, TemplateLevel::Full).is_err());
+    }
+}
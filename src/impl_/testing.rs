@@ -0,0 +1,317 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Test harness for code that loads configuration.
+//!
+//! [`TestConfig`] writes configuration files into a temporary directory, sets
+//! environment variables for as long as it lives and hands out
+//! [`ConfigBuilder`]s that read exactly those fixtures.
+
+use std::ffi::{OsStr, OsString};
+use std::path::{Component, Path, PathBuf};
+
+use tempfile::TempDir;
+
+use crate::config::ConfigBuilder;
+use crate::error::{ConfigError, ConfigResult};
+
+/// Sets or removes an environment variable and restores the previous state
+/// when dropped, including while a panicking test unwinds.
+///
+/// The environment is shared by the whole process: tests that touch the same
+/// variables must not run concurrently (e.g. mark them `#[serial]`).
+#[derive(Debug)]
+#[must_use = "the variable is restored when the guard is dropped"]
+pub struct EnvGuard {
+    key: OsString,
+    previous: Option<OsString>,
+}
+
+impl EnvGuard {
+    /// Set `key` to `value`.
+    pub fn set(key: impl Into<OsString>, value: impl AsRef<OsStr>) -> Self {
+        let key = key.into();
+        let previous = std::env::var_os(&key);
+        std::env::set_var(&key, value);
+        Self { key, previous }
+    }
+
+    /// Remove `key`.
+    pub fn remove(key: impl Into<OsString>) -> Self {
+        let key = key.into();
+        let previous = std::env::var_os(&key);
+        std::env::remove_var(&key);
+        Self { key, previous }
+    }
+}
+
+impl Drop for EnvGuard {
+    fn drop(&mut self) {
+        match self.previous.take() {
+            Some(value) => std::env::set_var(&self.key, value),
+            None => std::env::remove_var(&self.key),
+        }
+    }
+}
+
+/// Builder for [`TestConfig`].
+#[derive(Debug, Default)]
+pub struct TestConfigBuilder {
+    files: Vec<(PathBuf, String)>,
+    env: Vec<(String, Option<String>)>,
+    env_prefix: Option<String>,
+}
+
+impl TestConfigBuilder {
+    /// Write `content` to `name` inside the temporary directory.
+    ///
+    /// `name` is relative and may contain subdirectories (`conf.d/10-db.toml`).
+    /// Files are added to the loader in the order they are declared, so later
+    /// files override earlier ones.
+    pub fn file(mut self, name: impl Into<PathBuf>, content: impl Into<String>) -> Self {
+        self.files.push((name.into(), content.into()));
+        self
+    }
+
+    /// Set an environment variable while the [`TestConfig`] lives.
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), Some(value.into())));
+        self
+    }
+
+    /// Remove an environment variable while the [`TestConfig`] lives.
+    pub fn unset_env(mut self, key: impl Into<String>) -> Self {
+        self.env.push((key.into(), None));
+        self
+    }
+
+    /// Load environment variables with `prefix` (see
+    /// [`ConfigBuilder::env_prefix`]).
+    pub fn env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Write the files and set the environment variables.
+    pub fn build(self) -> ConfigResult<TestConfig> {
+        let dir = tempfile::tempdir()?;
+        let mut files = Vec::with_capacity(self.files.len());
+        for (name, content) in self.files {
+            if !name
+                .components()
+                .all(|component| matches!(component, Component::Normal(_)))
+            {
+                return Err(ConfigError::InvalidValue {
+                    key: name.display().to_string(),
+                    expected_type: "relative file name".to_string(),
+                    message: "fixture files must stay inside the temporary directory".to_string(),
+                });
+            }
+            let path = dir.path().join(&name);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, content)?;
+            files.push(path);
+        }
+
+        let env = self
+            .env
+            .into_iter()
+            .map(|(key, value)| match value {
+                Some(value) => EnvGuard::set(key, value),
+                None => EnvGuard::remove(key),
+            })
+            .collect();
+
+        Ok(TestConfig {
+            dir,
+            files,
+            env_prefix: self.env_prefix,
+            env,
+        })
+    }
+}
+
+/// Configuration fixtures for one test.
+///
+/// Dropping it deletes the files and restores the environment.
+///
+/// # Example
+///
+/// ```rust
+/// use confers::testing::TestConfig;
+///
+/// #[derive(Debug, Default, serde::Deserialize)]
+/// struct App {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// let fixture = TestConfig::builder()
+///     .file("app.toml", "host = \"localhost\"\nport = 8080\n")
+///     .env("DOC_TEST_APP_PORT", "9090")
+///     .env_prefix("DOC_TEST_APP_")
+///     .build()
+///     .unwrap();
+///
+/// let app: App = fixture.load().unwrap();
+/// assert_eq!(app.host, "localhost");
+/// assert_eq!(app.port, 9090);
+///
+/// drop(fixture);
+/// assert!(std::env::var("DOC_TEST_APP_PORT").is_err());
+/// ```
+#[derive(Debug)]
+pub struct TestConfig {
+    dir: TempDir,
+    files: Vec<PathBuf>,
+    env_prefix: Option<String>,
+    env: Vec<EnvGuard>,
+}
+
+impl TestConfig {
+    /// Start declaring fixtures.
+    pub fn builder() -> TestConfigBuilder {
+        TestConfigBuilder::default()
+    }
+
+    /// The temporary directory holding the files.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Absolute path of a fixture file.
+    pub fn path(&self, name: impl AsRef<Path>) -> PathBuf {
+        self.dir.path().join(name)
+    }
+
+    /// A builder that reads the fixture files and, with an
+    /// [`env_prefix`](TestConfigBuilder::env_prefix), the environment.
+    pub fn loader<T>(&self) -> ConfigBuilder<T> {
+        self.apply(ConfigBuilder::new())
+    }
+
+    /// Add the fixtures to `builder` and drop its remote providers, so a
+    /// builder set up by application code loads only local fixtures.
+    pub fn apply<T>(&self, builder: ConfigBuilder<T>) -> ConfigBuilder<T> {
+        #[cfg(feature = "remote")]
+        let builder = builder.without_remote_providers();
+        let mut builder = builder.allow_absolute_paths();
+        for path in &self.files {
+            builder = builder.file(path);
+        }
+        if let Some(prefix) = &self.env_prefix {
+            builder = builder.env_prefix(prefix);
+        }
+        builder
+    }
+
+    /// Build `T` from the fixtures.
+    pub fn load<T>(&self) -> ConfigResult<T>
+    where
+        T: serde::de::DeserializeOwned + Default,
+    {
+        self.loader().build()
+    }
+}
+
+impl Drop for TestConfig {
+    fn drop(&mut self) {
+        // Restore in reverse, so a variable set twice ends up as before.
+        while self.env.pop().is_some() {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize)]
+    struct App {
+        #[serde(default)]
+        name: String,
+        #[serde(default)]
+        port: u16,
+    }
+
+    #[test]
+    fn test_fixture_files_and_env() {
+        let fixture = TestConfig::builder()
+            .file("base.toml", "name = \"base\"\nport = 1\n")
+            .file("conf.d/local.toml", "port = 2\n")
+            .env("CONFERS_HARNESS_TEST_NAME", "env")
+            .env_prefix("CONFERS_HARNESS_TEST_")
+            .build()
+            .unwrap();
+        assert!(fixture.path("conf.d/local.toml").is_file());
+
+        let app: App = fixture.load().unwrap();
+        assert_eq!(app.name, "env");
+        assert_eq!(app.port, 2);
+
+        let dir = fixture.dir().to_path_buf();
+        drop(fixture);
+        assert!(!dir.exists());
+        assert!(std::env::var_os("CONFERS_HARNESS_TEST_NAME").is_none());
+    }
+
+    #[test]
+    fn test_env_restored_on_panic() {
+        std::env::set_var("CONFERS_HARNESS_PANIC", "before");
+        let result = std::panic::catch_unwind(|| {
+            let _fixture = TestConfig::builder()
+                .env("CONFERS_HARNESS_PANIC", "first")
+                .env("CONFERS_HARNESS_PANIC", "second")
+                .unset_env("CONFERS_HARNESS_PANIC_UNSET")
+                .build()
+                .unwrap();
+            assert_eq!(std::env::var("CONFERS_HARNESS_PANIC").unwrap(), "second");
+            panic!("test failure");
+        });
+        assert!(result.is_err());
+        assert_eq!(std::env::var("CONFERS_HARNESS_PANIC").unwrap(), "before");
+        std::env::remove_var("CONFERS_HARNESS_PANIC");
+    }
+
+    #[test]
+    fn test_fixture_names_stay_inside_dir() {
+        for name in ["../escape.toml", "/tmp/escape.toml"] {
+            let err = TestConfig::builder().file(name, "").build().unwrap_err();
+            assert!(matches!(err, ConfigError::InvalidValue { .. }), "{err}");
+        }
+    }
+
+    #[cfg(feature = "remote")]
+    #[test]
+    fn test_apply_drops_remote_providers() {
+        use crate::remote::provider::RemoteConfigSource;
+        use crate::types::AnnotatedValue;
+
+        struct Unreachable;
+
+        #[async_trait::async_trait]
+        impl RemoteConfigSource for Unreachable {
+            async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+                unreachable!("remote providers are disabled in tests")
+            }
+
+            fn name(&self) -> &str {
+                "unreachable"
+            }
+        }
+
+        let fixture = TestConfig::builder()
+            .file("app.toml", "port = 3\n")
+            .build()
+            .unwrap();
+        let app: App = fixture
+            .apply(ConfigBuilder::new().with_remote_provider(Box::new(Unreachable)))
+            .build()
+            .unwrap();
+        assert_eq!(app.port, 3);
+    }
+}
@@ -119,11 +119,44 @@ impl ValidationRule {
     }
 }
 
+/// Validation that needs I/O, such as checking that a database accepts
+/// connections or that a key exists in a remote store.
+///
+/// Validators are registered with
+/// [`ConfigBuilder::with_async_validator`](crate::ConfigBuilder::with_async_validator)
+/// and run concurrently by
+/// [`ConfigBuilder::build_validated`](crate::ConfigBuilder::build_validated),
+/// each bounded by the builder's async validation timeout.
+///
+/// ```rust,ignore
+/// struct DatabaseReachable;
+///
+/// #[async_trait::async_trait]
+/// impl AsyncValidate<AppConfig> for DatabaseReachable {
+///     async fn validate(&self, config: &AppConfig) -> Result<(), ValidationErrors> {
+///         let mut errors = ValidationErrors::new();
+///         if tokio::net::TcpStream::connect(&config.database_addr).await.is_err() {
+///             errors.add(&["database_addr"], "database is not reachable");
+///         }
+///         errors.into_result()
+///     }
+/// }
+/// ```
+#[cfg(feature = "async-validation")]
+#[async_trait::async_trait]
+pub trait AsyncValidate<T>: Send + Sync {
+    /// Name used in timeout errors and build warnings.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+
+    /// Check the loaded configuration.
+    async fn validate(&self, config: &T) -> Result<(), crate::error::ValidationErrors>;
+}
+
 /// No-op module when validation is disabled.
 #[cfg(not(feature = "validation"))]
 pub(crate) mod no_validation {
-    //! Placeholder module when validation feature is disabled.
-
     /// Placeholder trait for Validate when validation is disabled.
     pub trait Validate {}
 
@@ -132,6 +165,7 @@ pub(crate) mod no_validation {
 }
 
 #[cfg(not(feature = "validation"))]
+#[allow(unused_imports)]
 pub use no_validation::{Validate, ValidationResult};
 
 #[cfg(all(test, feature = "validation"))]
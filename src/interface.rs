@@ -13,12 +13,13 @@
 //! - `ConfigProviderExt` - Extension trait with convenience methods
 //! - `AsyncConfigProvider` - Asynchronous configuration access
 //! - `KeyProvider` - Encryption key provider
+//! - `ConfigSchema` - JSON Schema export
 //! - `MetricsBackend` - Metrics collection interface
 
 use crate::error::{ConfersResult, ConfigResult};
 use crate::types::{AnnotatedValue, KeyCachePolicy, SourceKind, ZeroizingBytes};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[cfg(feature = "progressive-reload")]
 use crate::HealthStatus;
@@ -353,6 +354,24 @@ pub fn filter_sensitive_keys(keys: Vec<String>, sensitive_paths: &[&str]) -> Vec
         .collect()
 }
 
+/// JSON Schema of a configuration struct.
+///
+/// Implemented by `#[derive(ConfigSchema)]`. The schema carries field
+/// descriptions, `#[config(default)]` values and the `#[garde(...)]`
+/// constraints that map to schema keywords.
+pub trait ConfigSchema {
+    /// The JSON Schema of this configuration.
+    fn json_schema() -> serde_json::Value;
+
+    /// Write the JSON Schema to `path`; see [`write_schema`](crate::loader::write_schema).
+    fn write_json_schema(path: impl AsRef<Path>) -> ConfigResult<()>
+    where
+        Self: Sized,
+    {
+        crate::loader::write_schema(&Self::json_schema(), path)
+    }
+}
+
 /// Synchronous encryption key provider.
 ///
 /// Implementations provide encryption keys for sensitive field encryption.
@@ -426,6 +445,23 @@ pub trait Source: Send + Sync {
     fn file_path(&self) -> Option<&Path> {
         None
     }
+
+    /// Files a watcher should observe for this source.
+    ///
+    /// Defaults to [`file_path`](Self::file_path); sources that read further
+    /// files (e.g. via `__include`) list those as well.
+    fn watch_paths(&self) -> Vec<PathBuf> {
+        self.file_path()
+            .map(Path::to_path_buf)
+            .into_iter()
+            .collect()
+    }
+
+    /// Names of the environment variables this source would read, without
+    /// their values. Empty for sources that do not read the environment.
+    fn env_var_names(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
 
 /// Trait for asynchronous configuration sources.
@@ -8,6 +8,7 @@
 //! Implementation lives in `crate::impl_::interpolation`.
 
 pub use crate::impl_::interpolation::{
-    interpolate, interpolate_tracked, InterpolationConfig, InterpolationContext,
-    InterpolationResult, InterpolationWarning,
+    interpolate, interpolate_tracked, Base64Function, EnvFunction, FileFunction,
+    InterpolationConfig, InterpolationContext, InterpolationFunction, InterpolationResult,
+    InterpolationWarning, Interpolator,
 };
@@ -0,0 +1,385 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Split-knowledge escrow of the key store master key.
+//!
+//! [`KeyManager::split_master_key`] cuts the master key into shares with
+//! Shamir's Secret Sharing so that any `threshold` of them rebuild it and
+//! fewer reveal nothing. Each share is a single line of text that can be
+//! handed to one operator:
+//!
+//! ```text
+//! confers-share:v1:<threshold>:<index>:<fingerprint>:<hex>
+//! ```
+//!
+//! The fingerprint identifies the master key the share belongs to, so
+//! [`KeyManager::recover_master_key`] rejects shares from different splits and
+//! detects a wrong reconstruction (e.g. a mistyped share).
+//!
+//! Each byte of the key is the constant term of its own random polynomial
+//! over GF(256) (reduction polynomial `0x11d`); every other coefficient is
+//! drawn uniformly from `0..=255`, and share `i` holds the values at `x = i`.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::error::ConfigError;
+use crate::key::KeyManager;
+
+/// Leading tag of an encoded [`MasterKeyShare`].
+pub const MASTER_KEY_SHARE_PREFIX: &str = "confers-share";
+
+const SHARE_FORMAT_VERSION: &str = "v1";
+
+/// One share of a split master key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MasterKeyShare {
+    threshold: u8,
+    index: u8,
+    fingerprint: String,
+    payload: Zeroizing<Vec<u8>>,
+}
+
+impl MasterKeyShare {
+    /// Number of shares needed to recover the master key.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Position of this share in the split, starting at 1.
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    /// Fingerprint of the master key this share belongs to.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
+impl fmt::Display for MasterKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}:{}:{}:{}",
+            MASTER_KEY_SHARE_PREFIX,
+            SHARE_FORMAT_VERSION,
+            self.threshold,
+            self.index,
+            self.fingerprint,
+            hex::encode(self.payload.as_slice())
+        )
+    }
+}
+
+impl fmt::Debug for MasterKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MasterKeyShare")
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .field("fingerprint", &self.fingerprint)
+            .field("payload", &"[REDACTED]")
+            .finish()
+    }
+}
+
+impl FromStr for MasterKeyShare {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| ConfigError::KeyError {
+            message: format!("Invalid master key share: {}", reason),
+        };
+
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        let [prefix, version, threshold, index, fingerprint, payload] = parts[..] else {
+            return Err(invalid("expected 6 ':'-separated fields"));
+        };
+        if prefix != MASTER_KEY_SHARE_PREFIX {
+            return Err(invalid(&format!(
+                "expected the '{}' prefix",
+                MASTER_KEY_SHARE_PREFIX
+            )));
+        }
+        if version != SHARE_FORMAT_VERSION {
+            return Err(invalid(&format!("unsupported version '{}'", version)));
+        }
+        let threshold = threshold
+            .parse::<u8>()
+            .map_err(|_| invalid("the threshold is not a number"))?;
+        let index = index
+            .parse::<u8>()
+            .ok()
+            .filter(|index| *index > 0)
+            .ok_or_else(|| invalid("the index is not a number from 1 to 255"))?;
+        let payload =
+            Zeroizing::new(hex::decode(payload).map_err(|_| invalid("the share data is not hex"))?);
+        if payload.is_empty() {
+            return Err(invalid("the share data is empty"));
+        }
+
+        Ok(Self {
+            threshold,
+            index,
+            fingerprint: fingerprint.to_ascii_lowercase(),
+            payload,
+        })
+    }
+}
+
+impl KeyManager {
+    /// Split a master key into `shares` shares, any `threshold` of which
+    /// recover it with [`KeyManager::recover_master_key`].
+    pub fn split_master_key(
+        master_key: &[u8; 32],
+        shares: u8,
+        threshold: u8,
+    ) -> Result<Vec<MasterKeyShare>, ConfigError> {
+        if threshold < 2 {
+            return Err(ConfigError::KeyError {
+                message: "The share threshold must be at least 2".to_string(),
+            });
+        }
+        if shares < threshold {
+            return Err(ConfigError::KeyError {
+                message: format!(
+                    "Cannot split into {} share(s) with a threshold of {}",
+                    shares, threshold
+                ),
+            });
+        }
+
+        // Coefficients of x^1..x^(threshold - 1) for each key byte.
+        let degree = usize::from(threshold) - 1;
+        let mut coefficients = Zeroizing::new(vec![0u8; master_key.len() * degree]);
+        OsRng.fill_bytes(&mut coefficients);
+
+        let fingerprint = master_key_fingerprint(master_key);
+        Ok((1..=shares)
+            .map(|x| {
+                let payload = master_key
+                    .iter()
+                    .zip(coefficients.chunks(degree))
+                    .map(|(secret, higher)| {
+                        // Horner's rule, from the highest coefficient down.
+                        let acc = higher.iter().rev().fold(0, |acc, c| gf_mul(acc, x) ^ c);
+                        gf_mul(acc, x) ^ secret
+                    })
+                    .collect();
+                MasterKeyShare {
+                    threshold,
+                    index: x,
+                    fingerprint: fingerprint.clone(),
+                    payload: Zeroizing::new(payload),
+                }
+            })
+            .collect())
+    }
+
+    /// Rebuild a master key from shares made by
+    /// [`KeyManager::split_master_key`].
+    ///
+    /// Fails if the shares come from different splits, if fewer than the
+    /// threshold of distinct shares are given, or if the result does not
+    /// match the fingerprint recorded in the shares.
+    pub fn recover_master_key(
+        shares: &[MasterKeyShare],
+    ) -> Result<Zeroizing<[u8; 32]>, ConfigError> {
+        let first = shares.first().ok_or_else(|| ConfigError::KeyError {
+            message: "No master key shares given".to_string(),
+        })?;
+        if shares.iter().any(|share| {
+            share.threshold != first.threshold || share.fingerprint != first.fingerprint
+        }) {
+            return Err(ConfigError::KeyError {
+                message: "The shares belong to different master keys or splits".to_string(),
+            });
+        }
+
+        let mut distinct = BTreeMap::new();
+        for share in shares {
+            if let Some(previous) = distinct.insert(share.index, share) {
+                if previous.payload != share.payload {
+                    return Err(ConfigError::KeyError {
+                        message: format!(
+                            "Share {} was given twice with different data",
+                            share.index
+                        ),
+                    });
+                }
+            }
+        }
+        if distinct.len() < usize::from(first.threshold) {
+            return Err(ConfigError::KeyError {
+                message: format!(
+                    "{} distinct share(s) given, {} are needed",
+                    distinct.len(),
+                    first.threshold
+                ),
+            });
+        }
+
+        let mut master_key = Zeroizing::new([0u8; 32]);
+        if distinct
+            .values()
+            .any(|share| share.payload.len() != master_key.len())
+        {
+            return Err(ConfigError::KeyError {
+                message: "The shares do not hold a 32-byte master key".to_string(),
+            });
+        }
+        // Lagrange interpolation at x = 0; subtraction in GF(256) is XOR.
+        for (&x_i, share) in &distinct {
+            let basis = distinct
+                .keys()
+                .filter(|&&x_j| x_j != x_i)
+                .fold(1, |acc, &x_j| gf_mul(acc, gf_div(x_j, x_j ^ x_i)));
+            for (byte, y) in master_key.iter_mut().zip(share.payload.iter()) {
+                *byte ^= gf_mul(basis, *y);
+            }
+        }
+        if master_key_fingerprint(&master_key) != first.fingerprint {
+            return Err(ConfigError::KeyError {
+                message: "The recovered master key does not match the share fingerprint; \
+                          check the shares for typos"
+                    .to_string(),
+            });
+        }
+        Ok(master_key)
+    }
+}
+
+/// Multiplication in GF(256) modulo `x^8 + x^4 + x^3 + x^2 + 1`, without
+/// data-dependent branches or table lookups.
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0;
+    for _ in 0..8 {
+        product ^= (b & 1).wrapping_neg() & a;
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (carry & 0x1d);
+        b >>= 1;
+    }
+    product
+}
+
+/// Division in GF(256); `b` must not be 0.
+fn gf_div(a: u8, b: u8) -> u8 {
+    // b^254 is the inverse of b, since b^255 = 1.
+    let mut inverse = 1;
+    let mut power = b;
+    for _ in 0..7 {
+        power = gf_mul(power, power);
+        inverse = gf_mul(inverse, power);
+    }
+    gf_mul(a, inverse)
+}
+
+/// Short, non-secret identifier of a master key.
+fn master_key_fingerprint(master_key: &[u8; 32]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(b"confers-master-key-share");
+    hasher.update(master_key);
+    hex::encode(&hasher.finalize()[..8])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MASTER_KEY: [u8; 32] = *b"0123456789abcdef0123456789abcdef"; // pragma: allowlist secret
+
+    fn split(shares: u8, threshold: u8) -> Vec<MasterKeyShare> {
+        KeyManager::split_master_key(&MASTER_KEY, shares, threshold).unwrap()
+    }
+
+    #[test]
+    fn test_any_threshold_shares_recover_the_master_key() {
+        let shares = split(5, 3);
+        assert_eq!(
+            shares.iter().map(MasterKeyShare::index).collect::<Vec<_>>(),
+            [1, 2, 3, 4, 5]
+        );
+
+        for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+            let picked: Vec<_> = subset.iter().map(|i| shares[*i].clone()).collect();
+            let recovered = KeyManager::recover_master_key(&picked).unwrap();
+            assert_eq!(*recovered, MASTER_KEY);
+        }
+        assert_eq!(
+            *KeyManager::recover_master_key(&shares).unwrap(),
+            MASTER_KEY
+        );
+    }
+
+    #[test]
+    fn test_too_few_shares_are_rejected() {
+        let shares = split(5, 3);
+        let err = KeyManager::recover_master_key(&[
+            shares[0].clone(),
+            shares[1].clone(),
+            shares[1].clone(),
+        ])
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("2 distinct share(s) given, 3 are needed"));
+        assert!(KeyManager::recover_master_key(&[]).is_err());
+    }
+
+    #[test]
+    fn test_shares_round_trip_through_text() {
+        let share = split(3, 2).remove(1);
+        let text = share.to_string();
+        assert!(text.starts_with("confers-share:v1:2:2:"));
+        assert_eq!(text.parse::<MasterKeyShare>().unwrap(), share);
+        assert!(!format!("{:?}", share).contains(&text[text.len() - 16..]));
+
+        for bad in [
+            "confers-share:v1:2:2:abcd",
+            "other:v1:2:2:abcd:00ff",
+            "confers-share:v9:2:2:abcd:00ff",
+            "confers-share:v1:2:0:abcd:00ff",
+            "confers-share:v1:2:2:abcd:xyz",
+        ] {
+            assert!(bad.parse::<MasterKeyShare>().is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_mixed_or_damaged_shares_are_detected() {
+        let shares = split(3, 2);
+        let other = KeyManager::split_master_key(&[7; 32], 3, 2).unwrap();
+        let err =
+            KeyManager::recover_master_key(&[shares[0].clone(), other[1].clone()]).unwrap_err();
+        assert!(err.to_string().contains("different master keys"));
+
+        let mut damaged = shares[1].clone();
+        damaged.payload[0] ^= 0xff;
+        let err = KeyManager::recover_master_key(&[shares[0].clone(), damaged]).unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+
+    #[test]
+    fn test_field_division_inverts_multiplication() {
+        for a in 0..=255u8 {
+            for b in 1..=255u8 {
+                assert_eq!(gf_div(gf_mul(a, b), b), a);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_split_parameters_are_rejected() {
+        assert!(KeyManager::split_master_key(&MASTER_KEY, 3, 1).is_err());
+        assert!(KeyManager::split_master_key(&MASTER_KEY, 2, 3).is_err());
+        assert_eq!(split(255, 255).len(), 255);
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! OS keychain storage for the key store master key.
+//!
+//! The master key is kept in the platform credential store (macOS Keychain,
+//! Windows Credential Manager, or the Secret Service on Linux) instead of an
+//! environment variable or a plaintext file. It is stored hex-encoded under a
+//! service/account pair; [`KeychainMasterKey::for_store`] derives the account
+//! from the key store directory so several stores can coexist on one machine.
+
+use std::path::{Path, PathBuf};
+
+use rand::RngCore;
+use zeroize::Zeroizing;
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::interface::KeyProvider;
+use crate::key::KeyStorage;
+use crate::types::ZeroizingBytes;
+
+/// Keychain service name used by [`KeychainMasterKey::for_store`].
+pub const DEFAULT_KEYCHAIN_SERVICE: &str = "confers";
+
+/// A master key entry in the OS keychain.
+pub struct KeychainMasterKey {
+    entry: keyring::Entry,
+    service: String,
+    account: String,
+}
+
+impl KeychainMasterKey {
+    pub fn new(service: &str, account: &str) -> ConfigResult<Self> {
+        let entry = keyring::Entry::new(service, account).map_err(keychain_error)?;
+        Ok(Self {
+            entry,
+            service: service.to_string(),
+            account: account.to_string(),
+        })
+    }
+
+    /// Entry for a key store directory: service `confers`, account set to the
+    /// absolute directory path.
+    pub fn for_store(store_dir: &Path) -> ConfigResult<Self> {
+        let dir = std::path::absolute(store_dir)?;
+        Self::new(DEFAULT_KEYCHAIN_SERVICE, &dir.to_string_lossy())
+    }
+
+    pub fn service(&self) -> &str {
+        &self.service
+    }
+
+    pub fn account(&self) -> &str {
+        &self.account
+    }
+
+    /// Read the master key from the keychain.
+    pub fn load(&self) -> ConfigResult<Zeroizing<[u8; 32]>> {
+        let encoded = Zeroizing::new(self.entry.get_password().map_err(|e| match e {
+            keyring::Error::NoEntry => ConfigError::KeyError {
+                message: format!(
+                    "No master key in the OS keychain for {}/{}",
+                    self.service, self.account
+                ),
+            },
+            other => keychain_error(other),
+        })?);
+
+        let mut key = Zeroizing::new([0u8; 32]);
+        hex::decode_to_slice(encoded.trim(), &mut *key).map_err(|_| ConfigError::KeyError {
+            message: format!(
+                "Keychain entry {}/{} does not hold a 32-byte master key",
+                self.service, self.account
+            ),
+        })?;
+        Ok(key)
+    }
+
+    /// Save `master_key`, replacing any key already stored in this entry.
+    pub fn store(&self, master_key: &[u8; 32]) -> ConfigResult<()> {
+        let encoded = Zeroizing::new(hex::encode(master_key));
+        self.entry.set_password(&encoded).map_err(keychain_error)
+    }
+
+    /// Generate a random master key and save it to the keychain.
+    pub fn generate(&self) -> ConfigResult<Zeroizing<[u8; 32]>> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        rand::rngs::OsRng.fill_bytes(&mut *key);
+        self.store(&key)?;
+        Ok(key)
+    }
+
+    pub fn exists(&self) -> ConfigResult<bool> {
+        match self.entry.get_password() {
+            Ok(_) => Ok(true),
+            Err(keyring::Error::NoEntry) => Ok(false),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    /// Remove the entry from the keychain; a missing entry is not an error.
+    pub fn delete(&self) -> ConfigResult<()> {
+        match self.entry.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    #[cfg(test)]
+    fn with_credential(
+        credential: Box<keyring::credential::Credential>,
+        service: &str,
+        account: &str,
+    ) -> Self {
+        Self {
+            entry: keyring::Entry::new_with_credential(credential),
+            service: service.to_string(),
+            account: account.to_string(),
+        }
+    }
+}
+
+impl KeyProvider for KeychainMasterKey {
+    fn get_key(&self) -> ConfigResult<ZeroizingBytes> {
+        Ok(ZeroizingBytes::new(self.load()?.to_vec()))
+    }
+
+    fn provider_type(&self) -> &'static str {
+        "keychain"
+    }
+}
+
+impl std::fmt::Debug for KeychainMasterKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("KeychainMasterKey")
+            .field("service", &self.service)
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+impl KeyStorage {
+    /// Open a key store whose master key is held in the OS keychain.
+    pub fn open_with_keychain(
+        storage_path: PathBuf,
+        keychain: &KeychainMasterKey,
+    ) -> ConfigResult<Self> {
+        let master_key = keychain.load()?;
+        let mut storage = Self::new(storage_path)?;
+        storage.set_master_key(&master_key);
+        storage.load()?;
+        Ok(storage)
+    }
+
+    /// Create a key store with a freshly generated master key that is saved
+    /// only to the OS keychain. Fails if the store already exists or the
+    /// entry already holds a key.
+    pub fn initialize_with_keychain(
+        storage_path: PathBuf,
+        keychain: &KeychainMasterKey,
+        key_id: String,
+        created_by: String,
+    ) -> ConfigResult<Self> {
+        if storage_path.join("keys.json").exists() {
+            return Err(ConfigError::KeyError {
+                message: format!("Key store {} already exists", storage_path.display()),
+            });
+        }
+        if keychain.exists()? {
+            return Err(ConfigError::KeyError {
+                message: format!(
+                    "OS keychain entry {}/{} already holds a master key",
+                    keychain.service(),
+                    keychain.account()
+                ),
+            });
+        }
+
+        let master_key = keychain.generate()?;
+        let mut storage = Self::new(storage_path)?;
+        if let Err(e) = storage.initialize_with_master_key(&master_key, key_id, created_by) {
+            let _ = keychain.delete();
+            return Err(e);
+        }
+        Ok(storage)
+    }
+}
+
+fn keychain_error(err: keyring::Error) -> ConfigError {
+    ConfigError::KeyError {
+        message: format!("OS keychain error: {}", err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keyring::credential::CredentialApi;
+    use std::sync::{Arc, Mutex};
+
+    /// In-memory credential shared between entries, standing in for the OS store.
+    #[derive(Debug, Clone, Default)]
+    struct MemoryCredential(Arc<Mutex<Option<Vec<u8>>>>);
+
+    impl CredentialApi for MemoryCredential {
+        fn set_secret(&self, secret: &[u8]) -> keyring::Result<()> {
+            *self.0.lock().unwrap() = Some(secret.to_vec());
+            Ok(())
+        }
+
+        fn get_secret(&self) -> keyring::Result<Vec<u8>> {
+            self.0
+                .lock()
+                .unwrap()
+                .clone()
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn delete_credential(&self) -> keyring::Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .take()
+                .map(|_| ())
+                .ok_or(keyring::Error::NoEntry)
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    fn keychain(credential: &MemoryCredential) -> KeychainMasterKey {
+        KeychainMasterKey::with_credential(Box::new(credential.clone()), "confers", "test")
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let credential = MemoryCredential::default();
+        let keychain = keychain(&credential);
+        assert!(!keychain.exists().unwrap());
+
+        keychain.store(&[0x5a; 32]).unwrap();
+        assert!(keychain.exists().unwrap());
+        assert_eq!(*keychain.load().unwrap(), [0x5a; 32]);
+        assert_eq!(
+            credential.get_password().unwrap(),
+            hex::encode([0x5a; 32]),
+            "stored hex-encoded"
+        );
+    }
+
+    #[test]
+    fn test_missing_entry_is_key_error() {
+        let err = keychain(&MemoryCredential::default()).load().unwrap_err();
+        assert!(matches!(err, ConfigError::KeyError { .. }));
+        assert!(err.to_string().contains("confers/test"), "{}", err);
+    }
+
+    #[test]
+    fn test_malformed_entry_rejected() {
+        let credential = MemoryCredential::default();
+        credential.set_password("not-a-key").unwrap();
+        let err = keychain(&credential).load().unwrap_err();
+        assert!(err.to_string().contains("32-byte"), "{}", err);
+    }
+
+    #[test]
+    fn test_delete_is_idempotent() {
+        let credential = MemoryCredential::default();
+        let keychain = keychain(&credential);
+        keychain.store(&[1; 32]).unwrap();
+        keychain.delete().unwrap();
+        keychain.delete().unwrap();
+        assert!(!keychain.exists().unwrap());
+    }
+
+    #[test]
+    fn test_key_store_initialized_and_reopened_from_keychain() {
+        let dir = tempfile::tempdir().unwrap();
+        let credential = MemoryCredential::default();
+
+        let storage = KeyStorage::initialize_with_keychain(
+            dir.path().to_path_buf(),
+            &keychain(&credential),
+            "app".to_string(),
+            "dev".to_string(),
+        )
+        .unwrap();
+        let master_key = keychain(&credential).load().unwrap();
+        let expected = storage
+            .get_key_manager()
+            .decryption_keys(&master_key, None)
+            .unwrap();
+        drop(storage);
+
+        let reopened =
+            KeyStorage::open_with_keychain(dir.path().to_path_buf(), &keychain(&credential))
+                .unwrap();
+        let keys = reopened
+            .get_key_manager()
+            .decryption_keys(&master_key, None)
+            .unwrap();
+        assert_eq!(keys.len(), expected.len());
+        assert_eq!(keys[0].1.as_slice(), expected[0].1.as_slice());
+    }
+
+    #[test]
+    fn test_initialize_refuses_to_overwrite_existing_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let credential = MemoryCredential::default();
+        keychain(&credential).store(&[3; 32]).unwrap();
+
+        let Err(err) = KeyStorage::initialize_with_keychain(
+            dir.path().to_path_buf(),
+            &keychain(&credential),
+            "app".to_string(),
+            "dev".to_string(),
+        ) else {
+            panic!("expected an error");
+        };
+        assert!(err.to_string().contains("already holds"), "{}", err);
+        assert_eq!(*keychain(&credential).load().unwrap(), [3; 32]);
+    }
+
+    #[test]
+    fn test_debug_omits_key_material() {
+        let credential = MemoryCredential::default();
+        let keychain = keychain(&credential);
+        keychain.store(&[0xab; 32]).unwrap();
+        let debug = format!("{:?}", keychain);
+        assert!(debug.contains("confers"));
+        assert!(!debug.contains("abab"));
+    }
+}
@@ -5,13 +5,15 @@
 
 use crate::error::ConfigError;
 use crate::key::{
-    now_timestamp, KeyBundle, KeyRing, KeyRotationSchedule, KeyStatus, RotationPlan,
+    now_timestamp, KeyBundle, KeyMetadata, KeyRing, KeyRotationSchedule, KeyStatus, RotationPlan,
     RotationResult, CURRENT_KEY_VERSION, SECONDS_PER_DAY,
 };
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+#[cfg(feature = "encryption")]
+use crate::secret::SecretBytes;
 #[cfg(feature = "encryption")]
 use rand::Rng;
 
@@ -393,6 +395,58 @@ impl KeyManager {
         Ok(key_ring.get_key_by_version(version))
     }
 
+    /// Metadata of every version in a key ring, newest first.
+    pub fn get_key_versions(&self, key_id: &str) -> Result<Vec<KeyMetadata>, ConfigError> {
+        let key_ring = self
+            .key_rings
+            .get(key_id)
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "key".to_string(),
+                message: format!("Key ring '{}' not found", key_id),
+                location: None,
+                source: None,
+            })?;
+
+        let mut versions: Vec<KeyMetadata> = std::iter::once(&key_ring.primary_key)
+            .chain(&key_ring.secondary_keys)
+            .map(|bundle| bundle.metadata.clone())
+            .collect();
+        versions.sort_by_key(|metadata| std::cmp::Reverse(metadata.version));
+        Ok(versions)
+    }
+
+    /// Decrypt every key version of a ring, primary first.
+    ///
+    /// Older versions are kept so values encrypted before a rotation can
+    /// still be decrypted.
+    #[cfg(feature = "encryption")]
+    pub fn decryption_keys(
+        &self,
+        master_key: &[u8; 32],
+        key_id: Option<&str>,
+    ) -> Result<Vec<(u32, SecretBytes)>, ConfigError> {
+        let key_id = key_id.unwrap_or(&self.default_key_id);
+        let key_ring = self
+            .key_rings
+            .get(key_id)
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "key".to_string(),
+                message: format!("Key ring '{}' not found", key_id),
+                location: None,
+                source: None,
+            })?;
+
+        let mut bundles: Vec<&KeyBundle> = key_ring.secondary_keys.iter().collect();
+        bundles.sort_by_key(|b| std::cmp::Reverse(b.metadata.version));
+        std::iter::once(&key_ring.primary_key)
+            .chain(bundles)
+            .map(|bundle| {
+                let key = bundle.get_plaintext_key(master_key)?;
+                Ok((bundle.metadata.version, SecretBytes::new(key.to_vec())))
+            })
+            .collect()
+    }
+
     pub fn deprecate_version(&mut self, key_id: &str, version: u32) -> Result<(), ConfigError> {
         let key_ring = self
             .key_rings
@@ -444,6 +498,10 @@ impl KeyManager {
         Ok((initial_count - key_ring.secondary_keys.len()) as u32)
     }
 
+    pub(crate) fn key_ring(&self, key_id: &str) -> Option<&KeyRing> {
+        self.key_rings.get(key_id)
+    }
+
     pub fn get_default_key_id(&self) -> &str {
         &self.default_key_id
     }
@@ -787,6 +845,29 @@ mod tests {
         assert_eq!(v1.metadata.status, KeyStatus::Deprecated);
     }
 
+    #[test]
+    fn test_key_manager_get_key_versions_newest_first() {
+        let master_key = [0x0e; 32];
+        let mut km = make_manager_with_default_ring(&master_key);
+        km.rotate_key(&master_key, None, "u".to_string(), None)
+            .unwrap();
+        km.rotate_key(&master_key, None, "u".to_string(), None)
+            .unwrap();
+        km.deprecate_version("prod", 1).unwrap();
+
+        let versions = km.get_key_versions("prod").expect("get_key_versions");
+        let summary: Vec<_> = versions.iter().map(|m| (m.version, m.status)).collect();
+        assert_eq!(
+            summary,
+            [
+                (3, KeyStatus::Active),
+                (2, KeyStatus::Active),
+                (1, KeyStatus::Deprecated)
+            ]
+        );
+        assert!(km.get_key_versions("nonexistent").is_err());
+    }
+
     #[test]
     fn test_key_manager_deprecate_current_version_errors() {
         let master_key = [0x10; 32];
@@ -887,6 +968,23 @@ mod tests {
         assert!(post_status.next_rotation >= pre_status.next_rotation);
     }
 
+    #[test]
+    fn test_key_manager_decryption_keys_primary_first() {
+        let master_key = [0x15; 32];
+        let mut km = make_manager();
+        km.create_key_ring(&master_key, "k".to_string(), "u".to_string(), None)
+            .unwrap();
+        km.rotate_key(&master_key, Some("k".to_string()), "u".to_string(), None)
+            .unwrap();
+
+        let keys = km.decryption_keys(&master_key, Some("k")).unwrap();
+        let versions: Vec<u32> = keys.iter().map(|(v, _)| *v).collect();
+        assert_eq!(versions, vec![2, 1]);
+        assert_ne!(keys[0].1.as_slice(), keys[1].1.as_slice());
+
+        assert!(km.decryption_keys(&master_key, Some("missing")).is_err());
+    }
+
     #[test]
     fn test_key_version_struct_construction() {
         let v = KeyVersion {
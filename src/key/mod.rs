@@ -3,16 +3,36 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
+#[cfg(feature = "encryption")]
+mod escrow;
+#[cfg(feature = "keychain")]
+mod keychain;
 mod manager;
+#[cfg(feature = "encryption")]
+mod reencrypt;
 mod rotation;
 #[cfg(feature = "encryption")]
 mod storage;
+#[cfg(feature = "encryption")]
+mod usage;
 mod version;
 
+#[cfg(feature = "encryption")]
+pub use escrow::{MasterKeyShare, MASTER_KEY_SHARE_PREFIX};
+#[cfg(feature = "keychain")]
+pub use keychain::{KeychainMasterKey, DEFAULT_KEYCHAIN_SERVICE};
 pub use manager::{KeyInfo, KeyManager, KeyVersion};
-pub use rotation::{KeyRotationPolicy, KeyRotationService, RotationResult};
+#[cfg(feature = "encryption")]
+pub use reencrypt::{ReencryptionResult, RotationJournal, ROTATION_JOURNAL_FILE};
+pub use rotation::{KeyRotationPolicy, KeyRotationService, RotationHistory, RotationResult};
+#[cfg(feature = "encryption")]
+pub use rotation::{
+    RotationReport, RotationServiceConfig, RotationServiceHandle, ScheduledRotation,
+};
 #[cfg(feature = "encryption")]
 pub use storage::{ErrorSanitizer, KeyStorage, SanitizationLevel};
+#[cfg(feature = "encryption")]
+pub use usage::EncryptedValueUsage;
 pub use version::KeyFormatVersion;
 
 use crate::error::ConfigError;
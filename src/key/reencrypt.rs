@@ -0,0 +1,491 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Key rotation with re-encryption of configuration files.
+//!
+//! Before anything is written, the original contents of every affected file
+//! and of the key store are recorded in a rollback journal
+//! (`rotation-journal.json` in the key store directory). A rotation that fails
+//! part-way is rolled back from the journal; a journal left behind by an
+//! interrupted process is replayed with [`RotationJournal::rollback`].
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::error::ConfigError;
+use crate::key::{now_timestamp, KeyManager, KeyStorage, RotationResult};
+use crate::secret::ConfigEncryption;
+
+/// File name of the rollback journal inside the key store directory.
+pub const ROTATION_JOURNAL_FILE: &str = "rotation-journal.json";
+
+const KEY_STORE_FILE: &str = "keys.json";
+
+/// Original state of the files touched by a rotation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RotationJournal {
+    pub key_id: String,
+    pub from_version: u32,
+    pub to_version: u32,
+    pub created_at: u64,
+    /// Previous `keys.json`, or `None` if the store had not been saved yet.
+    key_store: Option<String>,
+    files: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalEntry {
+    path: PathBuf,
+    original: String,
+}
+
+impl RotationJournal {
+    /// Journal path for a key store directory.
+    pub fn path(store_dir: &Path) -> PathBuf {
+        store_dir.join(ROTATION_JOURNAL_FILE)
+    }
+
+    /// Load the journal left by an interrupted rotation, if any.
+    pub fn load(store_dir: &Path) -> Result<Option<Self>, ConfigError> {
+        let path = Self::path(store_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&contents)
+            .map(Some)
+            .map_err(|e| ConfigError::ParseError {
+                format: "key".to_string(),
+                message: format!("Invalid rotation journal {}: {}", path.display(), e),
+                location: None,
+                source: None,
+            })
+    }
+
+    /// Files recorded in the journal.
+    pub fn files(&self) -> impl Iterator<Item = &Path> {
+        self.files.iter().map(|entry| entry.path.as_path())
+    }
+
+    /// Restore every recorded file and the key store, then remove the journal.
+    pub fn rollback(&self, store_dir: &Path) -> Result<(), ConfigError> {
+        for entry in &self.files {
+            write_atomic(&entry.path, &entry.original)?;
+        }
+        let key_store = store_dir.join(KEY_STORE_FILE);
+        match &self.key_store {
+            Some(contents) => write_atomic(&key_store, contents)?,
+            None if key_store.exists() => std::fs::remove_file(&key_store)?,
+            None => {}
+        }
+        self.remove(store_dir)
+    }
+
+    fn record(
+        store_dir: &Path,
+        rotation: &RotationResult,
+        files: &[PathBuf],
+    ) -> Result<Self, ConfigError> {
+        let key_store = store_dir.join(KEY_STORE_FILE);
+        let journal = Self {
+            key_id: rotation.key_id.clone(),
+            from_version: rotation.previous_version,
+            to_version: rotation.new_version,
+            created_at: now_timestamp(),
+            key_store: if key_store.exists() {
+                Some(std::fs::read_to_string(&key_store)?)
+            } else {
+                None
+            },
+            files: files
+                .iter()
+                .map(|path| {
+                    Ok(JournalEntry {
+                        path: path.clone(),
+                        original: read_file(path)?,
+                    })
+                })
+                .collect::<Result<_, ConfigError>>()?,
+        };
+
+        let json = serde_json::to_string_pretty(&journal).map_err(|e| ConfigError::ParseError {
+            format: "key".to_string(),
+            message: format!("Failed to serialize rotation journal: {}", e),
+            location: None,
+            source: None,
+        })?;
+        // The journal holds a copy of keys.json, so it is as private as the
+        // store; write_atomic keeps the mode of the file it replaces.
+        let path = Self::path(store_dir);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .mode(0o600)
+                .open(&path)?;
+        }
+        write_atomic(&path, &json)?;
+        Ok(journal)
+    }
+
+    fn remove(&self, store_dir: &Path) -> Result<(), ConfigError> {
+        std::fs::remove_file(Self::path(store_dir))?;
+        Ok(())
+    }
+}
+
+/// Result of [`KeyStorage::rotate_and_reencrypt`].
+#[derive(Debug, Clone)]
+pub struct ReencryptionResult {
+    pub rotation: RotationResult,
+    /// Rewritten files and the number of values re-encrypted in each.
+    /// Files without encrypted values are left untouched and not listed.
+    pub files: Vec<(PathBuf, usize)>,
+}
+
+impl ReencryptionResult {
+    pub fn reencrypted_values(&self) -> usize {
+        self.files.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl KeyStorage {
+    /// Rotate `key_id` (default: the default key ring) and re-encrypt every
+    /// `enc:` value in `files` with the new primary key.
+    ///
+    /// All files are decrypted before anything is written, so a value that
+    /// cannot be decrypted aborts the rotation with no changes. Write failures
+    /// after that point are rolled back from the [`RotationJournal`].
+    pub fn rotate_and_reencrypt(
+        &mut self,
+        key_id: Option<&str>,
+        files: &[PathBuf],
+        rotated_by: String,
+        reason: Option<String>,
+    ) -> Result<ReencryptionResult, ConfigError> {
+        let store_dir = self.storage_path().to_path_buf();
+        if RotationJournal::path(&store_dir).exists() {
+            return Err(ConfigError::KeyError {
+                message: format!(
+                    "An interrupted rotation left {}; roll it back before rotating again",
+                    RotationJournal::path(&store_dir).display()
+                ),
+            });
+        }
+
+        let master_key = Zeroizing::new(self.get_master_key_bytes()?);
+        let key_id = key_id
+            .unwrap_or(self.get_key_manager().get_default_key_id())
+            .to_string();
+        let backup = self.get_key_manager().clone();
+
+        let rotation = self.get_key_manager_mut().rotate_key(
+            &master_key,
+            Some(key_id.clone()),
+            rotated_by,
+            reason,
+        )?;
+
+        let rewritten =
+            key_encryption(self.get_key_manager(), &master_key, &key_id).and_then(|encryption| {
+                files
+                    .iter()
+                    .map(|path| Ok((path, reencrypt_file(&encryption, path)?)))
+                    .filter(|result| !matches!(result, Ok((_, (_, 0)))))
+                    .collect::<Result<Vec<_>, ConfigError>>()
+            });
+        let rewritten = match rewritten {
+            Ok(rewritten) => rewritten,
+            Err(e) => {
+                *self.get_key_manager_mut() = backup;
+                return Err(e);
+            }
+        };
+
+        let touched: Vec<PathBuf> = rewritten.iter().map(|(path, _)| (*path).clone()).collect();
+        let journal = match RotationJournal::record(&store_dir, &rotation, &touched) {
+            Ok(journal) => journal,
+            Err(e) => {
+                *self.get_key_manager_mut() = backup;
+                return Err(e);
+            }
+        };
+
+        let written = self.save().and_then(|_| {
+            rewritten
+                .iter()
+                .try_for_each(|(path, (contents, _))| write_atomic(path, contents))
+        });
+        if let Err(e) = written {
+            *self.get_key_manager_mut() = backup;
+            return Err(match journal.rollback(&store_dir) {
+                Ok(()) => e,
+                Err(rollback) => with_rollback_failure(e, rollback, &rotation, &store_dir),
+            });
+        }
+        journal.remove(&store_dir)?;
+
+        Ok(ReencryptionResult {
+            rotation,
+            files: rewritten
+                .into_iter()
+                .map(|(path, (_, count))| (path.clone(), count))
+                .collect(),
+        })
+    }
+}
+
+impl KeyStorage {
+    /// Encryption for `enc:` values backed by the versions of `key_id`
+    /// (default: the default key ring): values are written with the primary
+    /// version under an `enc:v<k>:` header and each value is read with the
+    /// version it names. Pass it to `ConfigBuilder::encryption` to load
+    /// configuration encrypted with this key store.
+    pub fn config_encryption(&self, key_id: Option<&str>) -> Result<ConfigEncryption, ConfigError> {
+        let master_key = Zeroizing::new(self.get_master_key_bytes()?);
+        let key_id = key_id.unwrap_or(self.get_key_manager().get_default_key_id());
+        key_encryption(self.get_key_manager(), &master_key, key_id)
+    }
+}
+
+/// Encryption that writes with the primary version of `key_id` and reads all versions.
+pub(crate) fn key_encryption(
+    manager: &KeyManager,
+    master_key: &[u8; 32],
+    key_id: &str,
+) -> Result<ConfigEncryption, ConfigError> {
+    let keys = manager.decryption_keys(master_key, Some(key_id))?;
+    if keys.is_empty() {
+        return Err(ConfigError::KeyError {
+            message: format!("Key ring '{}' has no keys", key_id),
+        });
+    }
+    Ok(ConfigEncryption::new().with_key_versions(keys))
+}
+
+pub(crate) fn reencrypt_file(
+    encryption: &ConfigEncryption,
+    path: &Path,
+) -> Result<(String, usize), ConfigError> {
+    let contents = read_file(path)?;
+    encryption
+        .reencrypt_text(&contents)
+        .map_err(|e| ConfigError::DecryptionFailed {
+            message: format!("{}: {}", path.display(), e),
+        })
+}
+
+pub(super) fn read_file(path: &Path) -> Result<String, ConfigError> {
+    std::fs::read_to_string(path).map_err(|e| ConfigError::FileNotFound {
+        filename: path.to_path_buf(),
+        source: Some(e),
+    })
+}
+
+/// Attach a failed rollback to the error that caused it. An I/O error keeps
+/// its kind.
+fn with_rollback_failure(
+    error: ConfigError,
+    rollback: ConfigError,
+    rotation: &RotationResult,
+    store_dir: &Path,
+) -> ConfigError {
+    let note = format!(
+        "rolling back also failed ({}); {} was kept for `confers key rollback`",
+        rollback,
+        RotationJournal::path(store_dir).display()
+    );
+    match error {
+        ConfigError::IoError(e) => {
+            ConfigError::IoError(std::io::Error::new(e.kind(), format!("{}; {}", e, note)))
+        }
+        error => ConfigError::KeyRotationFailed {
+            from_version: rotation.previous_version.to_string(),
+            to_version: rotation.new_version.to_string(),
+            reason: format!("{}; {}", error, note),
+        },
+    }
+}
+
+fn write_atomic(path: &Path, contents: &str) -> Result<(), ConfigError> {
+    crate::loader::write_atomic(path, contents.as_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::secret::{CipherAlgorithm, SecretBytes};
+
+    const MASTER_KEY: [u8; 32] = [0x24; 32];
+
+    fn storage(dir: &Path) -> KeyStorage {
+        let mut storage = KeyStorage::new(dir.join("keys")).unwrap();
+        storage
+            .initialize_with_master_key(&MASTER_KEY, "prod".to_string(), "team".to_string())
+            .unwrap();
+        storage
+    }
+
+    fn encrypted_config(storage: &KeyStorage, path: &Path, value: &str) {
+        let encryption = key_encryption(storage.get_key_manager(), &MASTER_KEY, "prod").unwrap();
+        let secret = encryption
+            .encrypt(CipherAlgorithm::Aes256Gcm, value)
+            .unwrap();
+        std::fs::write(path, format!("# comment\nsecret = \"{}\"\n", secret)).unwrap();
+    }
+
+    fn current_version(storage: &KeyStorage) -> u32 {
+        storage
+            .get_key_manager()
+            .get_key_info("prod")
+            .unwrap()
+            .current_version
+    }
+
+    #[test]
+    fn test_rotate_and_reencrypt_rewrites_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = storage(dir.path());
+        let a = dir.path().join("a.toml");
+        let plain = dir.path().join("plain.toml");
+        encrypted_config(&storage, &a, "alpha");
+        std::fs::write(&plain, "port = 8080\n").unwrap();
+
+        let result = storage
+            .rotate_and_reencrypt(None, &[a.clone(), plain.clone()], "ops".to_string(), None)
+            .unwrap();
+
+        assert_eq!(
+            result.rotation.new_version,
+            result.rotation.previous_version + 1
+        );
+        assert_eq!(result.files, vec![(a.clone(), 1)]);
+        assert_eq!(result.reencrypted_values(), 1);
+        assert!(RotationJournal::load(storage.storage_path())
+            .unwrap()
+            .is_none());
+
+        let (_, primary) = storage
+            .get_key_manager()
+            .decryption_keys(&MASTER_KEY, Some("prod"))
+            .unwrap()
+            .remove(0);
+        let (text, _) = ConfigEncryption::new()
+            .with_key(primary)
+            .decrypt_text(&std::fs::read_to_string(&a).unwrap())
+            .unwrap();
+        assert_eq!(text, "# comment\nsecret = \"alpha\"\n");
+    }
+
+    #[test]
+    fn test_undecryptable_value_aborts_without_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = storage(dir.path());
+        let a = dir.path().join("a.toml");
+        let b = dir.path().join("b.toml");
+        encrypted_config(&storage, &a, "alpha");
+        let foreign = ConfigEncryption::new()
+            .with_key(SecretBytes::new(vec![7u8; 32]))
+            .encrypt(CipherAlgorithm::XChaCha20, "x")
+            .unwrap();
+        std::fs::write(&b, format!("token = \"{}\"\n", foreign)).unwrap();
+        let original_a = std::fs::read_to_string(&a).unwrap();
+        let version = current_version(&storage);
+
+        let err = storage
+            .rotate_and_reencrypt(Some("prod"), &[a.clone(), b], "ops".to_string(), None)
+            .unwrap_err();
+
+        assert!(err.to_string().contains("b.toml"), "{}", err);
+        assert_eq!(current_version(&storage), version);
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), original_a);
+        assert!(RotationJournal::load(storage.storage_path())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_journal_rollback_restores_files_and_key_store() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = storage(dir.path());
+        let store_dir = storage.storage_path().to_path_buf();
+        let a = dir.path().join("a.toml");
+        encrypted_config(&storage, &a, "alpha");
+        let original_a = std::fs::read_to_string(&a).unwrap();
+        let original_store = std::fs::read_to_string(store_dir.join(KEY_STORE_FILE)).unwrap();
+
+        // Simulate a process that died after the journal was written.
+        let rotation = RotationResult::new("prod".to_string(), 1, 2, now_timestamp(), true);
+        RotationJournal::record(&store_dir, &rotation, std::slice::from_ref(&a)).unwrap();
+        std::fs::write(&a, "half-written").unwrap();
+        std::fs::write(store_dir.join(KEY_STORE_FILE), "{}").unwrap();
+
+        let err = storage
+            .rotate_and_reencrypt(None, std::slice::from_ref(&a), "ops".to_string(), None)
+            .unwrap_err();
+        assert!(err.to_string().contains("roll it back"), "{}", err);
+
+        let journal = RotationJournal::load(&store_dir).unwrap().unwrap();
+        assert_eq!(journal.files().collect::<Vec<_>>(), vec![a.as_path()]);
+        journal.rollback(&store_dir).unwrap();
+
+        assert_eq!(std::fs::read_to_string(&a).unwrap(), original_a);
+        assert_eq!(
+            std::fs::read_to_string(store_dir.join(KEY_STORE_FILE)).unwrap(),
+            original_store
+        );
+        assert!(RotationJournal::load(&store_dir).unwrap().is_none());
+        storage.load().unwrap();
+        assert_eq!(current_version(&storage), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_journal_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = storage(dir.path());
+        let store_dir = storage.storage_path().to_path_buf();
+        let a = dir.path().join("a.toml");
+        encrypted_config(&storage, &a, "alpha");
+
+        let rotation = RotationResult::new("prod".to_string(), 1, 2, now_timestamp(), true);
+        RotationJournal::record(&store_dir, &rotation, std::slice::from_ref(&a)).unwrap();
+
+        let mode = std::fs::metadata(RotationJournal::path(&store_dir))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_rollback_failure_keeps_the_original_error() {
+        let rotation = RotationResult::new("prod".to_string(), 1, 2, now_timestamp(), true);
+        let original = ConfigError::IoError(std::io::Error::new(
+            std::io::ErrorKind::StorageFull,
+            "disk full",
+        ));
+        let rollback = ConfigError::KeyError {
+            message: "cannot restore".to_string(),
+        };
+
+        let err = with_rollback_failure(original, rollback, &rotation, Path::new("keys"));
+        let ConfigError::IoError(io) = &err else {
+            panic!("expected an I/O error, got {:?}", err);
+        };
+        assert_eq!(io.kind(), std::io::ErrorKind::StorageFull);
+        let message = err.to_string();
+        assert!(message.contains("disk full"), "{}", message);
+        assert!(message.contains("cannot restore"), "{}", message);
+        assert!(message.contains("rotation-journal.json"), "{}", message);
+    }
+}
@@ -3,9 +3,27 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
+#[cfg(feature = "audit")]
+use crate::audit::AuditWriter;
 use crate::error::ConfigError;
+#[cfg(feature = "encryption")]
+use crate::key::reencrypt::{key_encryption, reencrypt_file};
+#[cfg(feature = "encryption")]
+use crate::key::KeyStorage;
 use crate::key::{now_timestamp, KeyMetadata, KeyRing, KeyStatus, RotationPlan, SECONDS_PER_DAY};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "encryption")]
+use std::path::PathBuf;
+#[cfg(feature = "encryption")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "encryption")]
+use std::time::Duration;
+#[cfg(feature = "encryption")]
+use tokio::sync::{oneshot, watch};
+#[cfg(feature = "encryption")]
+use tokio::time::Instant;
+#[cfg(feature = "encryption")]
+use zeroize::Zeroizing;
 
 const CRITICAL_EXPIRY_DAYS: u64 = 7;
 const WARNING_EXPIRY_DAYS: u64 = 30;
@@ -403,6 +421,308 @@ pub struct RotationRecommendation {
     pub estimated_downtime_minutes: Option<u32>,
 }
 
+/// Settings for the background task started by [`KeyRotationService::spawn`].
+#[cfg(feature = "encryption")]
+#[derive(Clone)]
+pub struct RotationServiceConfig {
+    pub check_interval: Duration,
+    pub dry_run: bool,
+    pub rotated_by: String,
+    /// Config files to re-encrypt, keyed by the key ring that encrypts them.
+    pub files: Vec<(String, PathBuf)>,
+    #[cfg(feature = "audit")]
+    pub audit: Option<Arc<AuditWriter>>,
+    /// Active key versions are published here after every check.
+    #[cfg(feature = "prometheus")]
+    pub metrics: Option<Arc<crate::metrics::ConfigMetrics>>,
+}
+
+#[cfg(feature = "encryption")]
+impl RotationServiceConfig {
+    pub fn new(check_interval: Duration) -> Self {
+        Self {
+            check_interval,
+            dry_run: false,
+            rotated_by: "rotation-service".to_string(),
+            files: Vec::new(),
+            #[cfg(feature = "audit")]
+            audit: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+        }
+    }
+
+    /// Report due rotations without changing keys or files.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    pub fn with_rotated_by(mut self, rotated_by: impl Into<String>) -> Self {
+        self.rotated_by = rotated_by.into();
+        self
+    }
+
+    /// Re-encrypt `path` whenever key ring `key_id` is rotated.
+    pub fn with_file(mut self, key_id: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        self.files.push((key_id.into(), path.into()));
+        self
+    }
+
+    #[cfg(feature = "audit")]
+    pub fn with_audit(mut self, audit: Arc<AuditWriter>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    #[cfg(feature = "prometheus")]
+    pub fn with_metrics(mut self, metrics: Arc<crate::metrics::ConfigMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
+    fn files_for<'a>(&'a self, key_id: &'a str) -> impl Iterator<Item = &'a PathBuf> + 'a {
+        self.files
+            .iter()
+            .filter(move |(id, _)| id == key_id)
+            .map(|(_, path)| path)
+    }
+}
+
+/// A key rotation performed (or, in dry-run mode, planned) by the service.
+#[cfg(feature = "encryption")]
+#[derive(Debug, Clone)]
+pub struct ScheduledRotation {
+    pub key_id: String,
+    pub from_version: u32,
+    /// `None` when the rotation was only planned.
+    pub to_version: Option<u32>,
+    pub files: Vec<PathBuf>,
+    pub reencrypted_values: usize,
+    pub dry_run: bool,
+}
+
+/// Outcome of one rotation check.
+#[cfg(feature = "encryption")]
+#[derive(Debug, Clone, Default)]
+pub struct RotationReport {
+    pub rotations: Vec<ScheduledRotation>,
+    pub errors: Vec<String>,
+}
+
+/// Handle to a running rotation service; the task stops when the handle is dropped.
+#[cfg(feature = "encryption")]
+pub struct RotationServiceHandle {
+    task: tokio::task::JoinHandle<()>,
+    reports: watch::Receiver<RotationReport>,
+    stop: Option<oneshot::Sender<()>>,
+}
+
+#[cfg(feature = "encryption")]
+impl RotationServiceHandle {
+    /// Receiver updated with the report of every check.
+    pub fn reports(&self) -> watch::Receiver<RotationReport> {
+        self.reports.clone()
+    }
+
+    pub fn is_running(&self) -> bool {
+        !self.task.is_finished()
+    }
+
+    /// Stop the service and wait for the task to exit. An in-flight
+    /// rotation always completes first.
+    pub async fn shutdown(mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        let _ = (&mut self.task).await;
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl Drop for RotationServiceHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(feature = "encryption")]
+impl KeyRotationService {
+    /// Run [`KeyRotationService::run_once`] on a tokio interval.
+    ///
+    /// `storage` must have its master key set; it is saved after every rotation.
+    /// Checks do key store and file I/O, so they run on tokio's blocking thread
+    /// pool. Must be called from within a tokio runtime.
+    pub fn spawn(
+        self,
+        storage: Arc<Mutex<KeyStorage>>,
+        config: RotationServiceConfig,
+    ) -> RotationServiceHandle {
+        let (tx, reports) = watch::channel(RotationReport::default());
+        let (stop, mut stopped) = oneshot::channel();
+        let config = Arc::new(config);
+        let task = tokio::spawn(async move {
+            let mut service = self;
+            let mut next = Instant::now();
+            // Wait for the next check unless the service is stopped first.
+            while tokio::time::timeout_at(next, &mut stopped).await.is_err() {
+                // A late check pushes the following ones back.
+                next = Instant::now() + config.check_interval;
+                let storage = storage.clone();
+                let config = config.clone();
+                let checked = tokio::task::spawn_blocking(move || {
+                    let report = match storage.lock() {
+                        Ok(mut storage) => service.run_once(&mut storage, &config),
+                        Err(_) => RotationReport {
+                            errors: vec!["key storage lock poisoned".to_string()],
+                            ..RotationReport::default()
+                        },
+                    };
+                    (service, report)
+                })
+                .await;
+                let Ok((returned, report)) = checked else {
+                    return;
+                };
+                service = returned;
+                let _ = tx.send(report);
+            }
+        });
+
+        RotationServiceHandle {
+            task,
+            reports,
+            stop: Some(stop),
+        }
+    }
+
+    /// Rotate every key ring whose schedule is due and re-encrypt its files.
+    ///
+    /// Key rings are due when their schedule is overdue and allows automatic
+    /// rotation. If the policy disables `auto_rotate`, due rotations are only
+    /// reported, as in dry-run mode.
+    pub fn run_once(
+        &mut self,
+        storage: &mut KeyStorage,
+        config: &RotationServiceConfig,
+    ) -> RotationReport {
+        let mut report = RotationReport::default();
+        let master_key = match storage.get_master_key_bytes() {
+            Ok(key) => Zeroizing::new(key),
+            Err(e) => {
+                report.errors.push(e.to_string());
+                return report;
+            }
+        };
+
+        let due: Vec<String> = storage
+            .get_key_manager()
+            .get_rotation_status()
+            .into_iter()
+            .filter(|status| status.is_overdue && status.auto_rotate)
+            .map(|status| status.key_id)
+            .collect();
+
+        let dry_run = config.dry_run || !self.policy.auto_rotate;
+        for key_id in due {
+            match self.rotate_due_key(storage, &master_key, &key_id, config, dry_run) {
+                Ok(rotation) => report.rotations.push(rotation),
+                Err(e) => report.errors.push(format!("{}: {}", key_id, e)),
+            }
+        }
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &config.metrics {
+            metrics.observe_keys(storage.get_key_manager());
+        }
+        report
+    }
+
+    pub fn history(&self) -> &[RotationHistory] {
+        &self.history
+    }
+
+    fn rotate_due_key(
+        &mut self,
+        storage: &mut KeyStorage,
+        master_key: &[u8; 32],
+        key_id: &str,
+        config: &RotationServiceConfig,
+        dry_run: bool,
+    ) -> Result<ScheduledRotation, ConfigError> {
+        let manager = storage.get_key_manager();
+        let key_ring = manager
+            .key_ring(key_id)
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "key".to_string(),
+                message: format!("Key ring '{}' not found", key_id),
+                location: None,
+                source: None,
+            })?;
+        Self::can_rotate(key_ring, &self.policy)?;
+        let from_version = key_ring.current_version;
+        let files: Vec<PathBuf> = config.files_for(key_id).cloned().collect();
+
+        if dry_run {
+            // Re-encrypting with the current key checks every file is readable.
+            let encryption = key_encryption(manager, master_key, key_id)?;
+            let mut reencrypted_values = 0;
+            for path in &files {
+                reencrypted_values += reencrypt_file(&encryption, path)?.1;
+            }
+            return Ok(ScheduledRotation {
+                key_id: key_id.to_string(),
+                from_version,
+                to_version: None,
+                files,
+                reencrypted_values,
+                dry_run: true,
+            });
+        }
+
+        let reencrypted = storage.rotate_and_reencrypt(
+            Some(key_id),
+            &files,
+            config.rotated_by.clone(),
+            Some("Scheduled rotation".to_string()),
+        )?;
+        let result = &reencrypted.rotation;
+        let reencrypted_values = reencrypted.reencrypted_values();
+
+        self.history.push(RotationHistory {
+            rotation_id: format!("rot_{}_{}", key_id, result.rotated_at),
+            key_id: key_id.to_string(),
+            from_version: result.previous_version,
+            to_version: result.new_version,
+            rotated_at: result.rotated_at,
+            rotated_by: config.rotated_by.clone(),
+            reason: Some("Scheduled rotation".to_string()),
+            reencryption_count: reencrypted_values as u32,
+            status: RotationStatus::Completed,
+        });
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &config.audit {
+            audit.log_key_rotated(
+                &crate::audit::new_correlation_id(),
+                key_id,
+                result.previous_version,
+                result.new_version,
+                reencrypted_values,
+            );
+        }
+
+        Ok(ScheduledRotation {
+            key_id: key_id.to_string(),
+            from_version,
+            to_version: Some(result.new_version),
+            files,
+            reencrypted_values,
+            dry_run: false,
+        })
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -993,4 +1313,280 @@ mod tests {
         assert_eq!(ring.current_version, original_version + 1);
         assert_eq!(ring.secondary_keys.len(), 1);
     }
+
+    #[cfg(feature = "encryption")]
+    mod service {
+        use super::*;
+        use crate::secret::{CipherAlgorithm, ConfigEncryption, SecretBytes};
+        use std::path::Path;
+
+        const MASTER_KEY: [u8; 32] = [0x42; 32];
+
+        fn due_storage(dir: &Path) -> KeyStorage {
+            let mut storage = KeyStorage::new(dir.join("keys")).unwrap();
+            storage
+                .initialize_with_master_key(&MASTER_KEY, "prod".to_string(), "team".to_string())
+                .unwrap();
+            storage
+                .get_key_manager_mut()
+                .set_rotation_interval("prod", 0)
+                .unwrap();
+            storage
+        }
+
+        fn write_encrypted_config(storage: &KeyStorage, path: &Path) {
+            let encryption =
+                key_encryption(storage.get_key_manager(), &MASTER_KEY, "prod").unwrap();
+            let secret = encryption
+                .encrypt(CipherAlgorithm::XChaCha20, "db-pass")
+                .unwrap();
+            std::fs::write(path, format!("password = \"{}\"\n", secret)).unwrap();
+        }
+
+        fn service() -> KeyRotationService {
+            KeyRotationService::new(KeyRotationPolicy::default().with_auto_rotate(true))
+        }
+
+        #[test]
+        fn test_run_once_rotates_due_key_and_reencrypts_files() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+            let config_path = dir.path().join("app.toml");
+            write_encrypted_config(&storage, &config_path);
+            let original = std::fs::read_to_string(&config_path).unwrap();
+
+            let mut service = service();
+            let config =
+                RotationServiceConfig::new(Duration::from_secs(60)).with_file("prod", &config_path);
+            let report = service.run_once(&mut storage, &config);
+
+            assert!(report.errors.is_empty(), "{:?}", report.errors);
+            assert_eq!(report.rotations.len(), 1);
+            let rotation = &report.rotations[0];
+            assert_eq!(rotation.to_version, Some(rotation.from_version + 1));
+            assert_eq!(rotation.reencrypted_values, 1);
+            assert_eq!(service.history().len(), 1);
+
+            let rewritten = std::fs::read_to_string(&config_path).unwrap();
+            assert_ne!(rewritten, original);
+            let (_, keys) = storage
+                .get_key_manager()
+                .decryption_keys(&MASTER_KEY, Some("prod"))
+                .unwrap()
+                .into_iter()
+                .next()
+                .unwrap();
+            let (plain, _) = ConfigEncryption::new()
+                .with_key(keys)
+                .decrypt_text(&rewritten)
+                .unwrap();
+            assert_eq!(plain, "password = \"db-pass\"\n");
+
+            let mut reloaded = KeyStorage::new(dir.path().join("keys")).unwrap();
+            reloaded.set_master_key(&MASTER_KEY);
+            reloaded.load().unwrap();
+            assert_eq!(
+                reloaded
+                    .get_key_manager()
+                    .get_key_info("prod")
+                    .unwrap()
+                    .current_version,
+                rotation.from_version + 1
+            );
+        }
+
+        #[test]
+        fn test_run_once_skips_keys_that_are_not_due() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = KeyStorage::new(dir.path().join("keys")).unwrap();
+            storage
+                .initialize_with_master_key(&MASTER_KEY, "prod".to_string(), "team".to_string())
+                .unwrap();
+
+            let config = RotationServiceConfig::new(Duration::from_secs(60));
+            let report = service().run_once(&mut storage, &config);
+            assert!(report.rotations.is_empty());
+            assert!(report.errors.is_empty());
+        }
+
+        #[test]
+        fn test_dry_run_reports_without_changes() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+            let config_path = dir.path().join("app.toml");
+            write_encrypted_config(&storage, &config_path);
+            let original = std::fs::read_to_string(&config_path).unwrap();
+
+            let mut service = service();
+            let config = RotationServiceConfig::new(Duration::from_secs(60))
+                .with_file("prod", &config_path)
+                .with_dry_run(true);
+            let report = service.run_once(&mut storage, &config);
+
+            let rotation = &report.rotations[0];
+            assert!(rotation.dry_run);
+            assert_eq!(rotation.to_version, None);
+            assert_eq!(rotation.reencrypted_values, 1);
+            assert!(service.history().is_empty());
+            assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+            assert_eq!(
+                storage
+                    .get_key_manager()
+                    .get_key_info("prod")
+                    .unwrap()
+                    .current_version,
+                rotation.from_version
+            );
+        }
+
+        #[test]
+        fn test_policy_without_auto_rotate_only_reports() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+
+            let mut service = KeyRotationService::new(KeyRotationPolicy::default());
+            let config = RotationServiceConfig::new(Duration::from_secs(60));
+            let report = service.run_once(&mut storage, &config);
+
+            assert!(report.rotations[0].dry_run);
+            assert!(service.history().is_empty());
+        }
+
+        #[test]
+        fn test_unreadable_file_rolls_back_rotation() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+            let version = storage
+                .get_key_manager()
+                .get_key_info("prod")
+                .unwrap()
+                .current_version;
+            let foreign = ConfigEncryption::new()
+                .with_key(SecretBytes::new(vec![9u8; 32]))
+                .encrypt(CipherAlgorithm::XChaCha20, "x")
+                .unwrap();
+            let config_path = dir.path().join("app.toml");
+            std::fs::write(&config_path, format!("token = \"{}\"\n", foreign)).unwrap();
+
+            let mut service = service();
+            let config =
+                RotationServiceConfig::new(Duration::from_secs(60)).with_file("prod", &config_path);
+            let report = service.run_once(&mut storage, &config);
+
+            assert!(report.rotations.is_empty());
+            assert_eq!(report.errors.len(), 1);
+            assert!(report.errors[0].contains("app.toml"), "{:?}", report.errors);
+            assert_eq!(
+                storage
+                    .get_key_manager()
+                    .get_key_info("prod")
+                    .unwrap()
+                    .current_version,
+                version
+            );
+        }
+
+        #[cfg(feature = "audit")]
+        #[test]
+        fn test_rotation_emits_audit_event() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+            let audit_dir = dir.path().join("audit");
+            std::fs::create_dir(&audit_dir).unwrap();
+            let audit = Arc::new(AuditWriter::builder().log_dir(audit_dir.clone()).build());
+
+            let config = RotationServiceConfig::new(Duration::from_secs(60)).with_audit(audit);
+            service().run_once(&mut storage, &config);
+
+            let entry = std::fs::read_dir(&audit_dir)
+                .unwrap()
+                .next()
+                .unwrap()
+                .unwrap();
+            let log = std::fs::read_to_string(entry.path()).unwrap();
+            let event: serde_json::Value =
+                serde_json::from_str(log.lines().next().unwrap()).unwrap();
+            assert_eq!(event["event"], "KeyRotated", "{}", log);
+            assert_eq!(event["key_id"], "prod");
+            assert_eq!(event["from_version"], 1);
+            assert_eq!(event["to_version"], 2);
+            assert!(event["correlation_id"].is_string());
+        }
+
+        #[cfg(feature = "prometheus")]
+        #[test]
+        fn test_rotation_publishes_active_key_version() {
+            let dir = tempfile::tempdir().unwrap();
+            let mut storage = due_storage(dir.path());
+            let registry = prometheus::Registry::new();
+            let metrics = Arc::new(crate::metrics::ConfigMetrics::register(&registry).unwrap());
+
+            let config = RotationServiceConfig::new(Duration::from_secs(60)).with_metrics(metrics);
+            service().run_once(&mut storage, &config);
+
+            let body = crate::metrics::encode_text(&registry).unwrap();
+            assert!(
+                body.contains("confers_active_key_version{key_id=\"prod\"} 2"),
+                "{body}"
+            );
+        }
+
+        #[tokio::test]
+        async fn test_spawned_service_publishes_reports() {
+            let dir = tempfile::tempdir().unwrap();
+            let storage = Arc::new(Mutex::new(due_storage(dir.path())));
+
+            let handle = service().spawn(
+                storage.clone(),
+                RotationServiceConfig::new(Duration::from_millis(10)),
+            );
+            let mut reports = handle.reports();
+            reports.changed().await.unwrap();
+            assert_eq!(reports.borrow().rotations.len(), 1);
+            assert!(handle.is_running());
+
+            handle.shutdown().await;
+            let current_version = storage
+                .lock()
+                .unwrap()
+                .get_key_manager()
+                .get_key_info("prod")
+                .unwrap()
+                .current_version;
+            assert!(current_version >= 2);
+        }
+
+        #[tokio::test]
+        async fn test_spawned_service_does_not_block_the_runtime() {
+            let dir = tempfile::tempdir().unwrap();
+            let storage = Arc::new(Mutex::new(due_storage(dir.path())));
+
+            // Hold the storage lock on another thread while a check is due.
+            let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+            let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+            let holder = {
+                let storage = storage.clone();
+                std::thread::spawn(move || {
+                    let _guard = storage.lock().unwrap();
+                    locked_tx.send(()).unwrap();
+                    let _ = release_rx.recv();
+                })
+            };
+            locked_rx.recv().unwrap();
+
+            let handle = service().spawn(
+                storage.clone(),
+                RotationServiceConfig::new(Duration::from_millis(10)),
+            );
+            // The current-thread runtime keeps running other tasks.
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            release_tx.send(()).unwrap();
+            holder.join().unwrap();
+
+            let mut reports = handle.reports();
+            reports.changed().await.unwrap();
+            assert_eq!(reports.borrow().rotations.len(), 1);
+            handle.shutdown().await;
+        }
+    }
 }
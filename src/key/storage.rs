@@ -4,6 +4,7 @@
 // See LICENSE file in the project root for full license information.
 
 use crate::error::ConfigError;
+use crate::impl_::loader::PermissionPolicy;
 use crate::key::{now_timestamp, KeyManager};
 use crate::secret::{SecretBytes, XChaCha20Crypto};
 use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
@@ -11,7 +12,7 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs::{self, File, OpenOptions};
 use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::LazyLock;
 
 /// 十六进制模式 - 全局缓存
@@ -157,6 +158,7 @@ pub struct KeyStorage {
     master_key: Option<SecretBytes>,
     key_manager: KeyManager,
     error_sanitizer: Option<ErrorSanitizer>,
+    permission_policy: PermissionPolicy,
 }
 
 impl KeyStorage {
@@ -175,9 +177,22 @@ impl KeyStorage {
             master_key: None,
             key_manager,
             error_sanitizer: None,
+            permission_policy: PermissionPolicy::Off,
         })
     }
 
+    /// Check that `keys.json` is private to the current user before reading
+    /// it: no group or other access, owned by this user or root.
+    pub fn with_permission_policy(mut self, policy: PermissionPolicy) -> Self {
+        self.permission_policy = policy;
+        self
+    }
+
+    /// Directory holding `keys.json`.
+    pub fn storage_path(&self) -> &Path {
+        &self.storage_path
+    }
+
     pub fn set_master_key(&mut self, master_key: &[u8; 32]) {
         self.master_key = Some(SecretBytes::new(master_key.to_vec()));
         self.error_sanitizer = Some(ErrorSanitizer::new(master_key, SanitizationLevel::Standard));
@@ -188,7 +203,7 @@ impl KeyStorage {
         self.error_sanitizer = None;
     }
 
-    fn get_master_key_bytes(&self) -> Result<[u8; 32], ConfigError> {
+    pub(crate) fn get_master_key_bytes(&self) -> Result<[u8; 32], ConfigError> {
         let secret = self
             .master_key
             .as_ref()
@@ -422,14 +437,13 @@ impl KeyStorage {
         // 原子写入：先写到临时文件 → fsync → rename 到目标路径
         // 避免进程崩溃或断电导致 keys.json 被截断/损坏
         {
-            let mut file = OpenOptions::new()
-                .write(true)
-                .create(true)
-                .truncate(true)
-                .open(&tmp_path)
-                .map_err(|e| {
-                    std::io::Error::new(e.kind(), format!("Failed to open temp key store: {}", e))
-                })?;
+            let mut options = OpenOptions::new();
+            options.write(true).create(true).truncate(true);
+            #[cfg(unix)]
+            std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+            let mut file = options.open(&tmp_path).map_err(|e| {
+                std::io::Error::new(e.kind(), format!("Failed to open temp key store: {}", e))
+            })?;
 
             file.write_all(json.as_bytes()).map_err(|e| {
                 std::io::Error::new(e.kind(), format!("Failed to write key store: {}", e))
@@ -450,6 +464,12 @@ impl KeyStorage {
 
     fn read_store(&self) -> Result<EncryptedKeyStore, ConfigError> {
         let store_path = self.storage_path.join("keys.json");
+        if let Some(problem) = self.permission_policy.check(&store_path, true)? {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(path = %store_path.display(), "insecure key store: {}", problem);
+            #[cfg(not(feature = "tracing"))]
+            let _ = problem;
+        }
         let mut file = File::open(&store_path).map_err(|e| {
             std::io::Error::new(e.kind(), format!("Failed to open key store: {}", e))
         })?;
@@ -956,6 +976,37 @@ mod tests {
         assert_eq!(km_b.list_keys().len(), 1);
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_key_storage_strict_permissions_reject_readable_store() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let master_key = [0x30; 32];
+        let mut storage = KeyStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        storage
+            .initialize_with_master_key(&master_key, "prod".to_string(), "team".to_string())
+            .expect("initialize");
+
+        let keys_file = temp_dir.path().join("keys.json");
+        let mode = std::fs::metadata(&keys_file).unwrap().permissions().mode();
+        assert_eq!(mode & 0o077, 0, "key store written with mode {:o}", mode);
+
+        let mut strict = KeyStorage::new(temp_dir.path().to_path_buf())
+            .unwrap()
+            .with_permission_policy(PermissionPolicy::Strict);
+        strict.set_master_key(&master_key);
+        strict.load().expect("private key store loads");
+
+        std::fs::set_permissions(&keys_file, std::fs::Permissions::from_mode(0o644)).unwrap();
+        let err = strict.load().unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InsecurePermissions { .. }),
+            "got {:?}",
+            err
+        );
+    }
+
     #[test]
     fn test_key_storage_load_wrong_master_key_errors() {
         let temp_dir = tempfile::tempdir().unwrap();
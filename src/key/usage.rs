@@ -0,0 +1,119 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Which key versions the `enc:` values of configuration files were
+//! encrypted with.
+//!
+//! Values written with an `enc:v<k>:` header name their version; older values
+//! only carry their algorithm and are attributed to a version by trying the
+//! versions of the key ring in turn. Files that still
+//! depend on deprecated or superseded versions are the ones to re-encrypt
+//! before those versions are retired.
+
+use std::path::PathBuf;
+
+use crate::error::ConfigError;
+use crate::key::reencrypt::read_file;
+use crate::key::KeyStorage;
+use crate::secret::encryption::encrypted_tokens;
+
+/// An `enc:` value found in a configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedValueUsage {
+    pub path: PathBuf,
+    /// 1-based line of the value.
+    pub line: usize,
+    /// Algorithm tag of the value, e.g. `XCHACHA20`.
+    pub algorithm: String,
+    /// Key version the value decrypts with, or `None` if no version of the
+    /// ring decrypts it (another key, an `AGE` value, or a damaged token).
+    pub version: Option<u32>,
+}
+
+impl KeyStorage {
+    /// Find every `enc:` value in `files` and the version of `key_id`
+    /// (default: the default key ring) it was encrypted with.
+    pub fn scan_key_usage(
+        &self,
+        key_id: Option<&str>,
+        files: &[PathBuf],
+    ) -> Result<Vec<EncryptedValueUsage>, ConfigError> {
+        let encryption = self.config_encryption(key_id)?;
+
+        let mut usages = Vec::new();
+        for path in files {
+            let contents = read_file(path)?;
+            for (offset, algorithm, token) in encrypted_tokens(&contents) {
+                let version = encryption
+                    .decrypt_versioned(token)
+                    .ok()
+                    .and_then(|(_, version)| version);
+                usages.push(EncryptedValueUsage {
+                    path: path.clone(),
+                    line: contents[..offset].matches('\n').count() + 1,
+                    algorithm: algorithm.to_string(),
+                    version,
+                });
+            }
+        }
+        Ok(usages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key::reencrypt::key_encryption;
+    use crate::secret::{CipherAlgorithm, ConfigEncryption};
+
+    const MASTER_KEY: [u8; 32] = [0x31; 32];
+
+    #[test]
+    fn test_scan_key_usage_attributes_values_to_versions() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut storage = KeyStorage::new(dir.path().join("keys")).unwrap();
+        storage
+            .initialize_with_master_key(&MASTER_KEY, "prod".to_string(), "team".to_string())
+            .unwrap();
+        let encrypt = |storage: &KeyStorage, algorithm| {
+            key_encryption(storage.get_key_manager(), &MASTER_KEY, "prod")
+                .unwrap()
+                .encrypt(algorithm, "secret")
+                .unwrap()
+        };
+
+        let old = encrypt(&storage, CipherAlgorithm::XChaCha20);
+        storage
+            .get_key_manager_mut()
+            .rotate_key(&MASTER_KEY, None, "team".to_string(), None)
+            .unwrap();
+        let new = encrypt(&storage, CipherAlgorithm::Aes256Gcm);
+        let foreign = ConfigEncryption::new()
+            .with_key(crate::secret::SecretBytes::new(vec![7; 32]))
+            .encrypt(CipherAlgorithm::XChaCha20, "secret")
+            .unwrap();
+
+        let path = dir.path().join("app.toml");
+        std::fs::write(
+            &path,
+            format!("a = \"{old}\"\n\n[db]\nb = \"{new}\"\nc = \"{foreign}\"\n"),
+        )
+        .unwrap();
+
+        let usages = storage.scan_key_usage(None, &[path]).unwrap();
+        let summary: Vec<_> = usages
+            .iter()
+            .map(|u| (u.line, u.algorithm.as_str(), u.version))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (1, "XCHACHA20", Some(1)),
+                (4, "AES256GCM", Some(2)),
+                (5, "XCHACHA20", None)
+            ]
+        );
+    }
+}
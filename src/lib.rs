@@ -51,6 +51,7 @@
 // ============== Public Modules ==============
 
 pub mod config;
+pub mod diff;
 pub mod error;
 pub mod format;
 pub mod interface;
@@ -63,7 +64,7 @@ mod impl_;
 
 // ============== Feature-gated Public Modules ==============
 
-#[cfg(feature = "validation")]
+#[cfg(any(feature = "validation", feature = "async-validation"))]
 pub mod validator;
 
 #[cfg(feature = "interpolation")]
@@ -77,9 +78,20 @@ pub mod secret;
 
 pub mod lifecycle;
 
+pub mod template;
+
 #[cfg(feature = "audit")]
 pub mod audit;
 
+#[cfg(feature = "otel")]
+pub mod otel;
+
+#[cfg(feature = "prometheus")]
+pub mod metrics;
+
+#[cfg(feature = "testing")]
+pub mod testing;
+
 #[cfg(feature = "dynamic")]
 pub mod dynamic;
 
@@ -95,6 +107,9 @@ pub mod modules;
 #[cfg(feature = "context-aware")]
 pub mod context;
 
+#[cfg(feature = "flags")]
+pub mod flags;
+
 #[cfg(feature = "config-bus")]
 pub mod bus;
 
@@ -110,9 +125,15 @@ pub mod security;
 #[cfg(feature = "key")]
 pub mod key;
 
+#[cfg(feature = "signing")]
+pub mod signing;
+
 #[cfg(feature = "remote")]
 pub mod remote;
 
+#[cfg(feature = "server")]
+pub mod server;
+
 // ============== Core Re-exports ==============
 
 pub use lifecycle::Lifecycle;
@@ -120,20 +141,24 @@ pub use lifecycle::Lifecycle;
 #[cfg(feature = "snapshot")]
 pub use config::SnapshotConfig;
 pub use config::{
-    config, ConfigBuilder, ConfigLimits, DefaultSource, EnvSource, FileSource, MemorySource,
-    ReloadStrategy, Source, SourceChain, SourceChainBuilder, SourceKind,
+    config, ConfigBuilder, ConfigDirSource, ConfigLimits, DefaultSource, EnvSource, FileGlobSource,
+    FileSource, LazyConfig, LoadPlan, MemorySource, ProviderHealth, ProviderKind, ReloadStrategy,
+    Source, SourceChain, SourceChainBuilder, SourceKind, StringSource,
 };
 
+pub use diff::{diff_values, ConfigDiff, KeyChange};
+
 // Error types (BrickArchitecture compliant)
 pub use error::{
     BuildResult, ConfersError, ConfersResult, ConfigConfigError, ConfigError, ConfigErrorCode,
-    ConfigResult, ErrorCode, InitResult, ParseLocation, SourceWarning,
+    ConfigResult, ErrorCode, FieldViolation, InitResult, ParseLocation, Redactor, SourceWarning,
+    ValidationErrors, ValidationIssue, ValidationReport,
 };
 
 // Interface traits (BrickArchitecture)
 pub use interface::{
-    ConfigConnector, ConfigProvider, ConfigProviderExt, ConfigReader, ConfigWriter, KeyProvider,
-    TypedConfigKey,
+    ConfigConnector, ConfigProvider, ConfigProviderExt, ConfigReader, ConfigSchema, ConfigWriter,
+    KeyProvider, TypedConfigKey,
 };
 
 // Public types
@@ -143,8 +168,8 @@ pub use types::{
 };
 
 pub use loader::{
-    detect_format_from_content, detect_format_from_path, load_file, parse_content, Format,
-    LoaderConfig,
+    detect_format_from_content, detect_format_from_path, discovery_paths, load_file, parse_content,
+    save_file, write_schema, Format, LoaderConfig, PermissionPolicy,
 };
 
 // Re-export derive macros
@@ -156,23 +181,27 @@ pub use confers_macros::ConfigSchema;
 
 // ============== Feature-gated Re-exports ==============
 
+#[cfg(feature = "async-validation")]
+pub use validator::AsyncValidate;
 #[cfg(feature = "validation")]
 pub use validator::{Validate, ValidationResult, ValidationRule};
 
 #[cfg(feature = "interpolation")]
 pub use interpolation::{
     interpolate, interpolate_tracked, InterpolationConfig, InterpolationContext,
-    InterpolationResult, InterpolationWarning,
+    InterpolationFunction, InterpolationResult, InterpolationWarning, Interpolator,
 };
 
 #[cfg(feature = "watch")]
 pub use watcher::{
-    AdaptiveDebouncer, FsWatcher, MultiFsWatcher, WatcherConfig, WatcherConfigBuilder, WatcherGuard,
+    AdaptiveDebouncer, FsWatcher, MultiFsWatcher, ReloadHandle, ReloadTriggers, WatchFilter,
+    WatcherConfig, WatcherConfigBuilder, WatcherGuard,
 };
 
 #[cfg(feature = "progressive-reload")]
 pub use watcher::{
-    HealthStatus, ProgressiveReloader, ProgressiveReloaderBuilder, ReloadHealthCheck, ReloadOutcome,
+    HealthStatus, ProgressiveReloader, ProgressiveReloaderBuilder, ReloadHealthCheck,
+    ReloadLatencyMetrics, ReloadOutcome, ReloadSubscription,
 };
 
 #[cfg(feature = "encryption")]
@@ -182,7 +211,8 @@ pub use secret::{
 
 #[cfg(feature = "audit")]
 pub use audit::{
-    AuditConfig, AuditConfigBuilder, AuditEvent, AuditLevel, AuditWriter, AuditWriterBuilder,
+    AuditConfig, AuditConfigBuilder, AuditEvent, AuditLevel, AuditSink, AuditWriter,
+    AuditWriterBuilder,
 };
 
 #[cfg(feature = "dynamic")]
@@ -192,7 +222,7 @@ pub use dynamic::{CallbackGuard, DynamicField, DynamicFieldBuilder};
 pub use migration::{MigrationFn, MigrationOnReload, MigrationRegistry, Versioned};
 
 #[cfg(feature = "snapshot")]
-pub use snapshot::{SnapshotFormat, SnapshotInfo, SnapshotManager};
+pub use snapshot::{ConfigSnapshotStore, SnapshotFormat, SnapshotInfo, SnapshotManager};
 
 #[cfg(feature = "modules")]
 pub use modules::{ModuleConfig, ModuleRegistry};
@@ -206,7 +236,10 @@ pub use context::{
 pub use bus::{BusBuilder, BusEventLimiter, ConfigBus, ConfigChangeEvent, InMemoryBus};
 
 #[cfg(feature = "remote")]
-pub use remote::{HttpPolledSource, HttpPolledSourceBuilder, PolledSource};
+pub use remote::{
+    CircuitBreakerConfig, HttpPolledSource, HttpPolledSourceBuilder, PolledSource, RemoteCache,
+    RemoteConfig, RemoteConfigSource, RetryPolicy,
+};
 
 // ============== Factory Functions (BrickArchitecture) ==============
 
@@ -234,6 +267,203 @@ pub fn new_in_memory() -> impl ConfigConnector {
     impl_::memory::InMemoryConfig::new()
 }
 
+// ============== Macro Support ==============
+
+/// Items used by code generated by the derive macros. Not public API.
+#[doc(hidden)]
+pub mod __private {
+    use std::marker::PhantomData;
+
+    use crate::{ConfigSchema, ValidationErrors};
+
+    /// Asks whether `T` implements [`ConfigSchema`] without requiring it:
+    /// `(&&SchemaProbe::<T>::new()).nested_schema()` resolves to
+    /// [`NestedSchema`] when it does and to [`NoNestedSchema`] otherwise.
+    pub struct SchemaProbe<T>(PhantomData<T>);
+
+    impl<T> SchemaProbe<T> {
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<T> Default for SchemaProbe<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    pub trait NestedSchema {
+        fn nested_schema(&self) -> Option<serde_json::Value>;
+    }
+
+    impl<T: ConfigSchema> NestedSchema for &SchemaProbe<T> {
+        fn nested_schema(&self) -> Option<serde_json::Value> {
+            Some(T::json_schema())
+        }
+    }
+
+    pub trait NoNestedSchema {
+        fn nested_schema(&self) -> Option<serde_json::Value>;
+    }
+
+    impl<T> NoNestedSchema for SchemaProbe<T> {
+        fn nested_schema(&self) -> Option<serde_json::Value> {
+            None
+        }
+    }
+
+    /// `validate_with` checks and map-entry checks of a `Config` struct,
+    /// implemented by the derive when it has any.
+    pub trait CrossFieldChecks {
+        fn check_fields(&self) -> Result<(), ValidationErrors>;
+    }
+
+    /// Runs the [`CrossFieldChecks`] of a map entry when its type has them:
+    /// `(&&ChecksProbe::new(value)).entry_checks()` resolves to
+    /// [`EntryChecks`] or, without checks, to [`NoEntryChecks`].
+    pub struct ChecksProbe<'a, T>(&'a T);
+
+    impl<'a, T> ChecksProbe<'a, T> {
+        pub fn new(value: &'a T) -> Self {
+            Self(value)
+        }
+    }
+
+    pub trait EntryChecks {
+        fn entry_checks(&self) -> Result<(), ValidationErrors>;
+    }
+
+    impl<T: CrossFieldChecks> EntryChecks for &ChecksProbe<'_, T> {
+        fn entry_checks(&self) -> Result<(), ValidationErrors> {
+            self.0.check_fields()
+        }
+    }
+
+    pub trait NoEntryChecks {
+        fn entry_checks(&self) -> Result<(), ValidationErrors>;
+    }
+
+    impl<T> NoEntryChecks for ChecksProbe<'_, T> {
+        fn entry_checks(&self) -> Result<(), ValidationErrors> {
+            Ok(())
+        }
+    }
+
+    /// One environment variable of a `Config` struct and the key it sets.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct EnvPath {
+        /// Dotted key, relative to the struct
+        pub key: String,
+        /// Variable name, without the prefix unless `absolute`
+        pub env: String,
+        /// Whether `env` was set with `#[config(env = "...")]` and is used as is
+        pub absolute: bool,
+    }
+
+    impl EnvPath {
+        pub fn leaf(key: &str, env: &str, absolute: bool) -> Self {
+            Self {
+                key: key.to_string(),
+                env: env.to_string(),
+                absolute,
+            }
+        }
+
+        /// This path seen from the parent field `key`, whose variables
+        /// start with `env`. Flattened fields keep the path unchanged.
+        pub fn under(self, key: &str, env: &str, absolute: bool, flatten: bool) -> Self {
+            if flatten {
+                return self;
+            }
+            Self {
+                key: format!("{}.{}", key, self.key),
+                env: if self.absolute {
+                    self.env
+                } else {
+                    format!("{}_{}", env, self.env)
+                },
+                absolute: self.absolute || absolute,
+            }
+        }
+
+        /// The full variable name under `prefix`.
+        pub fn env_name(&self, prefix: &str) -> String {
+            if self.absolute {
+                self.env.clone()
+            } else {
+                format!("{}{}", prefix, self.env)
+            }
+        }
+    }
+
+    /// The environment variables of a `Config` struct, nested fields
+    /// included; implemented by the derive.
+    pub trait EnvPaths {
+        fn env_paths() -> Vec<EnvPath>;
+    }
+
+    /// Lists the [`EnvPaths`] of a field type when it has them:
+    /// `(&&EnvProbe::<T>::new()).nested_env_paths()` resolves to
+    /// [`NestedEnvPaths`] or, for plain values, to [`NoNestedEnvPaths`].
+    pub struct EnvProbe<T>(PhantomData<T>);
+
+    impl<T> EnvProbe<T> {
+        pub fn new() -> Self {
+            Self(PhantomData)
+        }
+    }
+
+    impl<T> Default for EnvProbe<T> {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    pub trait NestedEnvPaths {
+        fn nested_env_paths(&self) -> Option<Vec<EnvPath>>;
+    }
+
+    impl<T: EnvPaths> NestedEnvPaths for &EnvProbe<T> {
+        fn nested_env_paths(&self) -> Option<Vec<EnvPath>> {
+            Some(T::env_paths())
+        }
+    }
+
+    pub trait NoNestedEnvPaths {
+        fn nested_env_paths(&self) -> Option<Vec<EnvPath>>;
+    }
+
+    impl<T> NoNestedEnvPaths for EnvProbe<T> {
+        fn nested_env_paths(&self) -> Option<Vec<EnvPath>> {
+            None
+        }
+    }
+
+    /// Parses a `--set KEY=VALUE` argument of a `ConfigClap` struct.
+    pub fn parse_set_arg(arg: &str) -> Result<(String, String), String> {
+        match arg.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                Ok((key.trim().to_string(), value.to_string()))
+            }
+            _ => Err(format!("expected KEY=VALUE, got `{}`", arg)),
+        }
+    }
+
+    /// The merged tree for `--print-config`: values under `sensitive_paths`
+    /// and values that look like secrets are redacted.
+    #[cfg(feature = "json")]
+    pub fn sanitized_config(
+        tree: &crate::types::AnnotatedValue,
+        sensitive_paths: &[&str],
+    ) -> serde_json::Value {
+        let mut json =
+            tree.to_json_with_mode(crate::types::SerializeMode::Redacted, sensitive_paths);
+        crate::error::redact_sensitive(&mut json, "");
+        json
+    }
+}
+
 // ============== Prelude ==============
 
 /// Prelude for common imports.
@@ -0,0 +1,17 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Prometheus metrics — public facade.
+//!
+//! Implementation lives in `crate::impl_::metrics`. Register a
+//! [`ConfigMetrics`] into the service's `prometheus::Registry` and hand it to
+//! `ConfigBuilder::with_config_metrics`, `ProgressiveReloader::with_metrics`
+//! and `RotationServiceConfig::with_metrics`; [`encode_text`] renders the
+//! registry for a `/metrics` endpoint.
+
+pub use crate::impl_::metrics::{
+    encode_text, ConfigMetrics, ACTIVE_KEY_VERSION, LAST_RELOAD_TIMESTAMP, RELOADS_TOTAL,
+    REMOTE_PROVIDER_UP, VALIDATION_FAILURES_TOTAL,
+};
@@ -0,0 +1,18 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! OpenTelemetry integration — public facade.
+//!
+//! Implementation lives in `crate::impl_::otel`. With the `otel` feature,
+//! builds, decryption, remote fetches, async validation and progressive
+//! reloads run inside `tracing` spans, and their durations and failures are
+//! recorded on the global OpenTelemetry meter (see the metric name constants
+//! below). [`OtelMetrics`] forwards a custom
+//! [`MetricsBackend`](crate::interface::MetricsBackend) to the same pipeline.
+
+pub use crate::impl_::otel::{
+    OtelMetrics, LOAD_DURATION, METER_NAME, RELOAD_LATENCY, REMOTE_FETCH_FAILURES,
+    VALIDATION_FAILURES,
+};
@@ -0,0 +1,288 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! On-disk cache of remote provider payloads.
+//!
+//! [`ConfigBuilder::with_remote_cache`](crate::ConfigBuilder::with_remote_cache)
+//! stores the last tree fetched from each remote provider in a JSON file, keyed
+//! by provider name. When a provider cannot be reached at startup, the cached
+//! tree is used instead as long as it is younger than the TTL. The file may
+//! contain secrets, so on Unix it is created readable by the owner only.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::types::AnnotatedValue;
+
+/// Cache of remote payloads in a single JSON file.
+#[derive(Debug, Clone)]
+pub struct RemoteCache {
+    path: PathBuf,
+    ttl: Duration,
+}
+
+/// A payload read back from the cache.
+#[derive(Debug, Clone)]
+pub struct CachedValue {
+    /// The cached tree.
+    pub value: AnnotatedValue,
+    /// When the tree was fetched from the provider.
+    pub fetched_at: SystemTime,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    entries: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Seconds since the Unix epoch.
+    fetched_at: u64,
+    value: AnnotatedValue,
+}
+
+impl RemoteCache {
+    /// Cache stored at `path`; entries older than `ttl` are not used.
+    pub fn new(path: impl Into<PathBuf>, ttl: Duration) -> Self {
+        Self {
+            path: path.into(),
+            ttl,
+        }
+    }
+
+    /// Path of the cache file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Store the payload fetched from the provider named `name`.
+    pub fn store(&self, name: &str, value: &AnnotatedValue) -> ConfigResult<()> {
+        let mut file = self.read().unwrap_or_default();
+        let fetched_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        file.entries.insert(
+            name.to_string(),
+            CacheEntry {
+                fetched_at,
+                value: value.clone(),
+            },
+        );
+
+        let json = serde_json::to_vec(&file).map_err(|e| ConfigError::InvalidValue {
+            key: "remote_cache".to_string(),
+            expected_type: "serializable configuration".to_string(),
+            message: e.to_string(),
+        })?;
+        self.write_atomic(&json)
+    }
+
+    /// The payload cached for `name`, unless missing, unreadable or expired.
+    pub fn load(&self, name: &str) -> Option<CachedValue> {
+        let entry = self.read()?.entries.remove(name)?;
+        let fetched_at = UNIX_EPOCH + Duration::from_secs(entry.fetched_at);
+        let age = SystemTime::now()
+            .duration_since(fetched_at)
+            .unwrap_or_default();
+        (age <= self.ttl).then_some(CachedValue {
+            value: entry.value,
+            fetched_at,
+        })
+    }
+
+    fn read(&self) -> Option<CacheFile> {
+        let content = std::fs::read(&self.path).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Write via a temporary file and rename, so readers never see a partial file.
+    fn write_atomic(&self, content: &[u8]) -> ConfigResult<()> {
+        use std::io::Write;
+
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let tmp = PathBuf::from(tmp);
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options.open(&tmp)?;
+        file.write_all(content)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConfigValue, SourceId};
+    use indexmap::IndexMap;
+    use std::sync::Arc;
+
+    fn tree(port: u64) -> AnnotatedValue {
+        let source = SourceId::new("remote");
+        let mut map = IndexMap::new();
+        map.insert(
+            Arc::from("port"),
+            AnnotatedValue::new(ConfigValue::uint(port), source.clone(), "port"),
+        );
+        AnnotatedValue::new(ConfigValue::Map(Arc::new(map)), source, "")
+    }
+
+    #[test]
+    fn test_store_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RemoteCache::new(
+            dir.path().join("nested/cache.json"),
+            Duration::from_secs(60),
+        );
+
+        assert!(cache.load("a").is_none());
+        cache.store("a", &tree(1)).unwrap();
+        cache.store("b", &tree(2)).unwrap();
+
+        let cached = cache.load("a").unwrap();
+        assert_eq!(cached.value, tree(1));
+        assert_eq!(cached.value.source.as_str(), "remote");
+        assert_eq!(cache.load("b").unwrap().value, tree(2));
+        assert!(cached.fetched_at <= SystemTime::now());
+    }
+
+    #[test]
+    fn test_expired_and_corrupt_entries_are_ignored() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("cache.json");
+        RemoteCache::new(&path, Duration::from_secs(60))
+            .store("a", &tree(1))
+            .unwrap();
+
+        let mut file: CacheFile = serde_json::from_slice(&std::fs::read(&path).unwrap()).unwrap();
+        file.entries.get_mut("a").unwrap().fetched_at -= 120;
+        std::fs::write(&path, serde_json::to_vec(&file).unwrap()).unwrap();
+        assert!(RemoteCache::new(&path, Duration::from_secs(60))
+            .load("a")
+            .is_none());
+
+        std::fs::write(&path, b"not json").unwrap();
+        assert!(RemoteCache::new(&path, Duration::from_secs(60))
+            .load("a")
+            .is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_cache_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let cache = RemoteCache::new(dir.path().join("cache.json"), Duration::from_secs(60));
+        cache.store("a", &tree(1)).unwrap();
+        let mode = std::fs::metadata(cache.path())
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    mod build {
+        use super::*;
+        use crate::error::ConfigResult;
+        use crate::remote::RemoteConfigSource;
+        use crate::ConfigBuilder;
+        use async_trait::async_trait;
+
+        struct Provider(Option<u64>);
+
+        #[async_trait]
+        impl RemoteConfigSource for Provider {
+            async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+                match self.0 {
+                    Some(port) => Ok(tree(port)),
+                    None => Err(ConfigError::RemoteUnavailable {
+                        error_type: "unreachable".to_string(),
+                        retryable: true,
+                    }),
+                }
+            }
+
+            fn name(&self) -> &str {
+                "app"
+            }
+        }
+
+        #[derive(Debug, Default, serde::Deserialize)]
+        struct AppConfig {
+            #[serde(default)]
+            port: u64,
+        }
+
+        async fn build(path: &Path, provider: Provider) -> ConfigResult<AppConfig> {
+            ConfigBuilder::new()
+                .with_remote_provider(Box::new(provider))
+                .with_remote_cache(path, Duration::from_secs(3600))
+                .build_async()
+                .await
+        }
+
+        #[tokio::test]
+        async fn test_unreachable_provider_falls_back_to_cache() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("remote-cache.json");
+
+            assert!(build(&path, Provider(None)).await.is_err());
+            assert_eq!(build(&path, Provider(Some(8080))).await.unwrap().port, 8080);
+            assert_eq!(build(&path, Provider(None)).await.unwrap().port, 8080);
+            assert_eq!(build(&path, Provider(Some(9090))).await.unwrap().port, 9090);
+        }
+
+        #[cfg(feature = "audit")]
+        #[tokio::test]
+        async fn test_fallback_is_audited_as_offline() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("remote-cache.json");
+            build(&path, Provider(Some(8080))).await.unwrap();
+
+            let audit_dir = dir.path().join("audit");
+            std::fs::create_dir(&audit_dir).unwrap();
+            let audit = Arc::new(
+                crate::audit::AuditWriter::builder()
+                    .log_dir(audit_dir.clone())
+                    .build(),
+            );
+            let config: AppConfig = ConfigBuilder::new()
+                .with_remote_provider(Box::new(Provider(None)))
+                .with_remote_cache(&path, Duration::from_secs(3600))
+                .with_audit(audit)
+                .build_async()
+                .await
+                .unwrap();
+            assert_eq!(config.port, 8080);
+
+            let log = std::fs::read_dir(&audit_dir)
+                .unwrap()
+                .map(|e| std::fs::read_to_string(e.unwrap().path()).unwrap())
+                .collect::<String>();
+            assert!(log.contains("OfflineFallback"), "{log}");
+            assert!(log.contains("\"source\":\"app\""), "{log}");
+            assert!(log.contains("\"event\":\"ProviderFetched\""), "{log}");
+            assert!(log.contains("\"success\":false"), "{log}");
+        }
+    }
+}
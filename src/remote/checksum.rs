@@ -0,0 +1,171 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Checksums pinning the content of remote payloads.
+//!
+//! [`payload_checksum`] hashes a fetched tree as compact JSON with object keys
+//! sorted, ignoring source metadata, so the digest depends only on the
+//! configuration itself and not on the provider or payload format it came in.
+
+use std::fmt::Write as _;
+
+use base64::Engine as _;
+use sha2::{Digest, Sha256};
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::types::{AnnotatedValue, ConfigValue};
+
+const CHECKSUM_ALGORITHM: &str = "sha256";
+
+/// `sha256:<hex>` digest of `value`'s keys and values.
+pub fn payload_checksum(value: &AnnotatedValue) -> String {
+    let mut canonical = String::new();
+    write_canonical(&value.inner, &mut canonical);
+    let hash = Sha256::digest(canonical.as_bytes());
+    let mut checksum = format!("{}:", CHECKSUM_ALGORITHM);
+    for byte in hash {
+        let _ = write!(checksum, "{:02x}", byte);
+    }
+    checksum
+}
+
+/// Check the payload of the provider `name` against the pinned `expected`
+/// checksum.
+pub(crate) fn verify_payload_checksum(
+    name: &str,
+    value: &AnnotatedValue,
+    expected: &str,
+) -> ConfigResult<()> {
+    let expected = expected.trim().to_ascii_lowercase();
+    let well_formed = expected
+        .strip_prefix(CHECKSUM_ALGORITHM)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .is_some_and(|hex| hex.len() == 64 && hex.bytes().all(|b| b.is_ascii_hexdigit()));
+    if !well_formed {
+        return Err(ConfigError::InvalidValue {
+            key: format!("remote_checksum.{}", name),
+            expected_type: "sha256:<64 hex digits>".to_string(),
+            message: format!("'{}' is not a sha256 checksum", expected),
+        });
+    }
+
+    let actual = payload_checksum(value);
+    if actual == expected {
+        return Ok(());
+    }
+    Err(ConfigError::ChecksumMismatch {
+        source_name: name.to_string(),
+        expected,
+        actual,
+    })
+}
+
+fn write_canonical(value: &ConfigValue, out: &mut String) {
+    match value {
+        ConfigValue::Null => out.push_str("null"),
+        ConfigValue::Bool(b) => push_json(out, b),
+        ConfigValue::I64(i) => push_json(out, i),
+        ConfigValue::U64(u) => push_json(out, u),
+        // JSON has no NaN or infinity; serde_json writes them as null too.
+        ConfigValue::F64(f) => push_json(out, f),
+        ConfigValue::String(s) => push_json(out, s),
+        ConfigValue::Bytes(b) => {
+            push_json(out, &base64::engine::general_purpose::STANDARD.encode(b))
+        }
+        ConfigValue::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(&item.inner, out);
+            }
+            out.push(']');
+        }
+        ConfigValue::Map(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                push_json(out, key.as_ref());
+                out.push(':');
+                write_canonical(&item.inner, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn push_json<T: serde::Serialize + ?Sized>(out: &mut String, value: &T) {
+    // Serializing a scalar or string to a string cannot fail.
+    out.push_str(&serde_json::to_string(value).unwrap_or_default());
+}
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+
+    fn parse(json: &str, source: &str) -> AnnotatedValue {
+        crate::loader::parse_content(
+            json,
+            crate::loader::Format::Json,
+            SourceId::new(source),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_checksum_ignores_key_order_and_source() {
+        let a = parse(
+            r#"{"db": {"host": "h", "port": 5}, "tags": ["x", 1.5]}"#,
+            "a",
+        );
+        let b = parse(
+            r#"{"tags": ["x", 1.5], "db": {"port": 5, "host": "h"}}"#,
+            "b",
+        );
+        assert_eq!(payload_checksum(&a), payload_checksum(&b));
+        assert!(payload_checksum(&a).starts_with("sha256:"));
+        assert_eq!(payload_checksum(&a).len(), "sha256:".len() + 64);
+
+        let c = parse(
+            r#"{"db": {"host": "evil", "port": 5}, "tags": ["x", 1.5]}"#,
+            "a",
+        );
+        assert_ne!(payload_checksum(&a), payload_checksum(&c));
+    }
+
+    #[test]
+    fn test_verify_reports_mismatch_and_malformed_pins() {
+        let value = parse(r#"{"a": 1}"#, "remote");
+        let pinned = payload_checksum(&value);
+        verify_payload_checksum(
+            "remote",
+            &value,
+            &pinned.to_uppercase().replace("SHA256", "sha256"),
+        )
+        .unwrap();
+
+        let other = parse(r#"{"a": 2}"#, "remote");
+        let err = verify_payload_checksum("remote", &other, &pinned).unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::ChecksumMismatch { actual, .. } if *actual == payload_checksum(&other)),
+            "got {:?}",
+            err
+        );
+
+        let err = verify_payload_checksum("remote", &value, "md5:abc").unwrap_err();
+        assert!(
+            matches!(err, ConfigError::InvalidValue { .. }),
+            "got {:?}",
+            err
+        );
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! HTTP client shared by remote sources.
+//!
+//! Building a [`reqwest::Client`] per source means a connection pool, TLS
+//! configuration and TLS sessions per source. Sources whose settings fit a
+//! common client reuse the one returned by [`shared_http_client`] instead, so
+//! polls of the same server share connections. It is built on first use and
+//! never follows redirects; per-request timeouts are set on the request.
+//!
+//! A source builds its own client when it needs something the shared one
+//! cannot provide: an explicit proxy, a Unix socket, followed redirects or
+//! skipped TLS verification.
+
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+use reqwest::{Client, ClientBuilder};
+
+use crate::error::{ConfigError, ConfigResult};
+
+type ClientHook = Box<dyn FnOnce(ClientBuilder) -> ClientBuilder + Send>;
+
+/// How long an idle pooled connection is kept.
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+static SHARED_CLIENT: OnceLock<Client> = OnceLock::new();
+static CLIENT_HOOK: Mutex<Option<ClientHook>> = Mutex::new(None);
+
+/// Customize the shared client before it is built, e.g. to add root
+/// certificates, default headers or pool limits.
+///
+/// `hook` receives the default builder. Redirects stay disabled whatever it
+/// sets, since the SSRF checks only cover the requested URL. Fails once the
+/// shared client has been built, so call it at startup before building any
+/// remote source.
+pub fn configure_shared_http_client(
+    hook: impl FnOnce(ClientBuilder) -> ClientBuilder + Send + 'static,
+) -> ConfigResult<()> {
+    let mut slot = CLIENT_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    if SHARED_CLIENT.get().is_some() {
+        return Err(ConfigError::InvalidValue {
+            key: "http_client".to_string(),
+            expected_type: "hook set before the first remote source".to_string(),
+            message: "The shared HTTP client is already in use".to_string(),
+        });
+    }
+    *slot = Some(Box::new(hook));
+    Ok(())
+}
+
+/// The HTTP client shared by remote sources, built on first use.
+///
+/// Clones share the connection pool, so custom
+/// [`RemoteConfigSource`](super::RemoteConfigSource)s can use it too.
+pub fn shared_http_client() -> ConfigResult<Client> {
+    if let Some(client) = SHARED_CLIENT.get() {
+        return Ok(client.clone());
+    }
+    let mut slot = CLIENT_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(client) = SHARED_CLIENT.get() {
+        return Ok(client.clone());
+    }
+
+    let mut builder = Client::builder()
+        .use_rustls_tls()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT);
+    if let Some(hook) = slot.take() {
+        builder = hook(builder);
+    }
+    let client = builder
+        .redirect(reqwest::redirect::Policy::none())
+        .build()
+        .map_err(|e| ConfigError::InvalidValue {
+            key: "http_client".to_string(),
+            expected_type: "HTTP client".to_string(),
+            message: format!("Failed to create the shared HTTP client: {}", e),
+        })?;
+    Ok(SHARED_CLIENT.get_or_init(|| client).clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_is_rejected_once_the_client_is_built() {
+        shared_http_client().unwrap();
+        shared_http_client().unwrap();
+        let err = configure_shared_http_client(|builder| builder).unwrap_err();
+        assert!(err.to_string().contains("already in use"), "{}", err);
+    }
+}
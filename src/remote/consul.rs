@@ -9,6 +9,8 @@
 //! using the Consul KV REST API via reqwest.
 
 use super::common::{merge_into_map, try_parse_value};
+use super::proxy::{apply_proxy, ProxyConfig};
+use super::ssrf::SsrfPolicy;
 use crate::error::{ConfigError, ConfigResult};
 use crate::loader::Format;
 use crate::types::{AnnotatedValue, SourceId};
@@ -54,6 +56,8 @@ pub struct ConsulSourceBuilder {
     tls_skip_verify: bool,
     max_response_bytes: usize,
     max_kv_entries: usize,
+    ssrf_policy: Option<SsrfPolicy>,
+    proxy: Option<ProxyConfig>,
 }
 
 /// TLS configuration for Consul connection.
@@ -76,6 +80,8 @@ impl ConsulSourceBuilder {
             tls_skip_verify: false,
             max_response_bytes: DEFAULT_MAX_CONSUL_RESPONSE_BYTES,
             max_kv_entries: DEFAULT_MAX_CONSUL_KV_ENTRIES,
+            ssrf_policy: None,
+            proxy: None,
         }
     }
 
@@ -147,16 +153,48 @@ impl ConsulSourceBuilder {
         self
     }
 
+    /// Check the agent address against `policy` when building, and apply it
+    /// to redirects. Without a policy any address is accepted, since the
+    /// agent usually runs on localhost.
+    pub fn ssrf_policy(mut self, policy: SsrfPolicy) -> Self {
+        self.ssrf_policy = Some(policy);
+        self
+    }
+
+    /// Reach the agent through `proxy` instead of the one named by the
+    /// proxy environment variables.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
     /// Build the Consul source.
     pub fn build(self) -> ConfigResult<ConsulSource> {
-        let client = Client::builder()
-            .danger_accept_invalid_certs(self.tls_skip_verify)
-            .build()
-            .map_err(|e| ConfigError::InvalidValue {
+        if let Some(policy) = &self.ssrf_policy {
+            policy.check_endpoint(&self.address, "http")?;
+        }
+        // Without an SSRF policy the client follows redirects, which the
+        // shared client never does.
+        let shared = !self.tls_skip_verify
+            && self.proxy.is_none()
+            && self
+                .ssrf_policy
+                .as_ref()
+                .is_some_and(SsrfPolicy::denies_redirects);
+        let client = if shared {
+            crate::remote::shared_http_client()?
+        } else {
+            let mut client = Client::builder().danger_accept_invalid_certs(self.tls_skip_verify);
+            if let Some(policy) = &self.ssrf_policy {
+                client = client.redirect(policy.redirect_policy());
+            }
+            let client = apply_proxy(client, self.proxy.as_ref())?;
+            client.build().map_err(|e| ConfigError::InvalidValue {
                 key: "consul".to_string(),
                 expected_type: "HTTP client".to_string(),
                 message: format!("Failed to create HTTP client: {}", e),
-            })?;
+            })?
+        };
 
         Ok(ConsulSource {
             client: Arc::new(client),
@@ -166,6 +204,7 @@ impl ConsulSourceBuilder {
             interval: self.interval.unwrap_or(DEFAULT_CONSUL_POLL_INTERVAL),
             token: self.token.map(Arc::from),
             last_index: Arc::new(std::sync::Mutex::new(0u64)),
+            blocking_queries: std::sync::atomic::AtomicBool::new(true),
             cached_value: Arc::new(std::sync::RwLock::new(None)),
             max_response_bytes: self.max_response_bytes,
             max_kv_entries: self.max_kv_entries,
@@ -189,6 +228,7 @@ pub struct ConsulSource {
     interval: Duration,
     token: Option<Arc<str>>,
     last_index: Arc<std::sync::Mutex<u64>>,
+    blocking_queries: std::sync::atomic::AtomicBool,
     cached_value: Arc<std::sync::RwLock<Option<AnnotatedValue>>>,
     max_response_bytes: usize,
     max_kv_entries: usize,
@@ -200,14 +240,19 @@ impl ConsulSource {
         SourceId::new(format!("consul:{}", self.prefix))
     }
 
-    /// Poll Consul for configuration.
-    async fn poll_internal(&self) -> ConfigResult<AnnotatedValue> {
-        // Build the KV request URL
-        let base_url = if self.address.contains("://") {
+    /// Agent URL, defaulting to plain HTTP for a bare `host:port`.
+    fn base_url(&self) -> String {
+        if self.address.contains("://") {
             self.address.to_string()
         } else {
             format!("http://{}", self.address)
-        };
+        }
+    }
+
+    /// Poll Consul for configuration.
+    async fn poll_internal(&self) -> ConfigResult<AnnotatedValue> {
+        // Build the KV request URL
+        let base_url = self.base_url();
 
         let path = if self.prefix.is_empty() {
             format!("{}/v1/kv/?recurse=true", base_url)
@@ -235,7 +280,11 @@ impl ConsulSource {
         }
 
         // Add index for blocking wait (wait for changes)
-        if current_index > 0 {
+        if current_index > 0
+            && self
+                .blocking_queries
+                .load(std::sync::atomic::Ordering::Relaxed)
+        {
             let wait_path = format!("{}&wait=30s&index={}", path, current_index);
             request = self.client.get(&wait_path);
 
@@ -453,6 +502,17 @@ impl crate::remote::PolledSource for ConsulSource {
     fn source_id(&self) -> SourceId {
         Self::source_id(self)
     }
+
+    fn endpoints(&self) -> Vec<String> {
+        vec![self.base_url()]
+    }
+
+    /// Blocking queries on the last `X-Consul-Index` are Consul's
+    /// conditional requests.
+    fn set_conditional_requests(&self, enabled: bool) {
+        self.blocking_queries
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
 }
 
 #[async_trait]
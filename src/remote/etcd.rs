@@ -7,8 +7,13 @@
 //!
 //! This module provides an etcd-backed implementation of the `PolledSource` trait,
 //! using the etcd-client SDK (gRPC) to interact with etcd's KV store.
+//!
+//! With several endpoints the client balances requests across the members
+//! it can reach. A read that fails because a member is unreachable is retried,
+//! up to once per endpoint, so a poll fails over to the remaining members.
 
 use super::common::{merge_into_map, try_parse_value};
+use super::ssrf::SsrfPolicy;
 use crate::error::{ConfigError, ConfigResult};
 use crate::loader::Format;
 use crate::types::{AnnotatedValue, SourceId};
@@ -31,6 +36,9 @@ pub struct EtcdSourceBuilder {
     format: Option<Format>,
     interval: Option<Duration>,
     tls: Option<EtcdTlsConfig>,
+    ssrf_policy: Option<SsrfPolicy>,
+    connect_timeout: Option<Duration>,
+    request_timeout: Option<Duration>,
 }
 
 /// TLS configuration for etcd connection.
@@ -52,10 +60,14 @@ impl EtcdSourceBuilder {
             format: None,
             interval: None,
             tls: None,
+            ssrf_policy: None,
+            connect_timeout: None,
+            request_timeout: None,
         }
     }
 
-    /// Set the etcd endpoints.
+    /// Set the etcd endpoints. Requests are balanced across them and fail
+    /// over to the others when one is unreachable.
     pub fn endpoints(mut self, endpoints: impl Into<Vec<String>>) -> Self {
         self.endpoints = endpoints.into();
         self
@@ -103,14 +115,59 @@ impl EtcdSourceBuilder {
         self
     }
 
+    /// Check every endpoint against `policy` before connecting. A bare
+    /// `host:port` endpoint is checked as `http://`, or `https://` with TLS.
+    pub fn ssrf_policy(mut self, policy: SsrfPolicy) -> Self {
+        self.ssrf_policy = Some(policy);
+        self
+    }
+
+    /// Give up connecting to an endpoint after `timeout`, so an unreachable
+    /// member is skipped quickly.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Fail a request after `timeout`. A timed-out read is retried on
+    /// another endpoint.
+    pub fn request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
     /// Build the etcd source.
     pub async fn build(self) -> ConfigResult<EtcdSource> {
+        let scheme = if self.tls.is_some() { "https" } else { "http" };
+        if let Some(policy) = &self.ssrf_policy {
+            for endpoint in &self.endpoints {
+                policy.check_endpoint(endpoint, scheme)?;
+            }
+        }
+        let endpoint_urls = self
+            .endpoints
+            .iter()
+            .map(|e| {
+                if e.contains("://") {
+                    e.clone()
+                } else {
+                    format!("{}://{}", scheme, e)
+                }
+            })
+            .collect();
+
         // Build connect options
         let mut options = ConnectOptions::new();
 
         if let (Some(username), Some(password)) = (self.username, self.password) {
             options = options.with_user(&username, &password);
         }
+        if let Some(timeout) = self.connect_timeout {
+            options = options.with_connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.request_timeout {
+            options = options.with_timeout(timeout);
+        }
 
         // Connect to etcd using the SDK
         let endpoints: Vec<&str> = self.endpoints.iter().map(|s| s.as_str()).collect();
@@ -124,6 +181,7 @@ impl EtcdSourceBuilder {
 
         Ok(EtcdSource {
             client: Arc::new(client),
+            endpoints: endpoint_urls,
             prefix: Arc::from(self.prefix),
             format: self.format,
             interval: self.interval.unwrap_or(DEFAULT_ETCD_POLL_INTERVAL),
@@ -142,6 +200,7 @@ impl Default for EtcdSourceBuilder {
 /// Etcd-backed configuration source using the etcd-client SDK.
 pub struct EtcdSource {
     client: Arc<Client>,
+    endpoints: Vec<String>,
     prefix: Arc<str>,
     #[allow(dead_code)] // reserved for future format-specific polling
     format: Option<Format>,
@@ -163,15 +222,26 @@ impl EtcdSource {
         let client = self.client.clone();
         let mut kv_client = client.kv_client();
 
-        // Get all keys with the prefix
-        let get_response = kv_client
-            .get(self.prefix.as_ref(), Some(GetOptions::new().with_prefix()))
-            .await
-            .map_err(|e| ConfigError::InvalidValue {
-                key: "etcd".to_string(),
-                expected_type: "etcd KV response".to_string(),
-                message: format!("Failed to fetch from etcd: {}", e),
-            })?;
+        // Get all keys with the prefix. The balanced channel routes a retry
+        // to another member, so try once per endpoint before giving up.
+        let mut attempts = self.endpoints.len().max(1);
+        let get_response = loop {
+            attempts -= 1;
+            match kv_client
+                .get(self.prefix.as_ref(), Some(GetOptions::new().with_prefix()))
+                .await
+            {
+                Ok(response) => break response,
+                Err(e) if attempts > 0 && is_unreachable(&e) => continue,
+                Err(e) => {
+                    return Err(ConfigError::InvalidValue {
+                        key: "etcd".to_string(),
+                        expected_type: "etcd KV response".to_string(),
+                        message: format!("Failed to fetch from etcd: {}", e),
+                    })
+                }
+            }
+        };
 
         // Get header with revision
         let header = get_response.header();
@@ -268,6 +338,24 @@ impl EtcdSource {
     }
 }
 
+/// gRPC `UNAVAILABLE` status code.
+const GRPC_UNAVAILABLE: i32 = 14;
+/// gRPC `DEADLINE_EXCEEDED` status code.
+const GRPC_DEADLINE_EXCEEDED: i32 = 4;
+
+/// Whether `error` means the member could not be reached, as opposed to
+/// etcd rejecting the request.
+fn is_unreachable(error: &etcd_client::Error) -> bool {
+    match error {
+        etcd_client::Error::TransportError(_) | etcd_client::Error::IoError(_) => true,
+        etcd_client::Error::GRpcStatus(status) => matches!(
+            i32::from(status.code()),
+            GRPC_UNAVAILABLE | GRPC_DEADLINE_EXCEEDED
+        ),
+        _ => false,
+    }
+}
+
 #[async_trait]
 impl crate::remote::PolledSource for EtcdSource {
     async fn poll(&self) -> ConfigResult<AnnotatedValue> {
@@ -281,6 +369,10 @@ impl crate::remote::PolledSource for EtcdSource {
     fn source_id(&self) -> SourceId {
         Self::source_id(self)
     }
+
+    fn endpoints(&self) -> Vec<String> {
+        self.endpoints.clone()
+    }
 }
 
 #[async_trait]
@@ -330,6 +422,24 @@ mod tests {
         assert_eq!(builder.prefix, "my-app");
     }
 
+    #[test]
+    fn test_builder_timeouts() {
+        let builder = EtcdSourceBuilder::new()
+            .connect_timeout(Duration::from_secs(2))
+            .request_timeout(Duration::from_secs(5));
+        assert_eq!(builder.connect_timeout, Some(Duration::from_secs(2)));
+        assert_eq!(builder.request_timeout, Some(Duration::from_secs(5)));
+    }
+
+    #[test]
+    fn test_unreachable_errors_fail_over() {
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert!(is_unreachable(&etcd_client::Error::IoError(refused)));
+        assert!(!is_unreachable(&etcd_client::Error::InvalidArgs(
+            "bad key".to_string()
+        )));
+    }
+
     #[test]
     fn test_builder_default_impl() {
         let builder = EtcdSourceBuilder::default();
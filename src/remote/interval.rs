@@ -3,9 +3,17 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
-//! Polling interval presets for remote sources.
+//! Polling interval presets and limits for remote sources.
+//!
+//! [`PollPolicy`] keeps a fleet of instances from polling the same endpoint
+//! in lockstep: each delay is spread by a random jitter, no source is polled
+//! more often than a global minimum interval, and conditional requests can be
+//! turned off for servers that mishandle them. The instance id places the
+//! instance in the cohorts of [staged rollouts](super::rollout).
 
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
 use std::time::Duration;
 
 use crate::error::{ConfigError, ConfigResult};
@@ -81,6 +89,99 @@ impl std::fmt::Display for PollInterval {
     }
 }
 
+/// Jitter, minimum interval, conditional requests and rollout instance id for
+/// polled sources.
+///
+/// Used by [`RemoteWatcher::with_poll_policy`](super::RemoteWatcher::with_poll_policy);
+/// [`RemoteConfig`](super::RemoteConfig) carries one so a single setting
+/// covers every watcher of an application. The default polls at each
+/// source's own interval, without jitter, with conditional requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollPolicy {
+    jitter: f64,
+    min_interval: Duration,
+    conditional_requests: bool,
+    instance_id: Option<String>,
+}
+
+impl Default for PollPolicy {
+    fn default() -> Self {
+        Self {
+            jitter: 0.0,
+            min_interval: Duration::ZERO,
+            conditional_requests: true,
+            instance_id: None,
+        }
+    }
+}
+
+impl PollPolicy {
+    /// The default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spread each delay randomly by up to `fraction` of the interval in
+    /// either direction (0.0 to 1.0), e.g. `0.1` turns 30s into 27s to 33s.
+    pub fn jitter(mut self, fraction: f64) -> Self {
+        self.jitter = fraction.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Never poll a source more often than every `interval`, whatever its
+    /// own interval and the jitter.
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Send conditional requests (ETag / Last-Modified, Consul blocking
+    /// queries) when the source supports them (default: `true`). `false`
+    /// turns them off on every watched source; `true` leaves each source's
+    /// own setting.
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.conditional_requests = enabled;
+        self
+    }
+
+    /// Identify this instance in staged rollouts (default:
+    /// [`default_instance_id`](super::default_instance_id)). Give every
+    /// instance of a fleet a distinct, stable id, e.g. its pod name.
+    pub fn instance_id(mut self, id: impl Into<String>) -> Self {
+        self.instance_id = Some(id.into());
+        self
+    }
+
+    /// Configured jitter fraction.
+    pub fn jitter_fraction(&self) -> f64 {
+        self.jitter
+    }
+
+    /// Configured minimum interval.
+    pub fn min_poll_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Whether conditional requests are enabled.
+    pub fn uses_conditional_requests(&self) -> bool {
+        self.conditional_requests
+    }
+
+    /// Id this instance is bucketed by in staged rollouts.
+    pub fn rollout_instance_id(&self) -> String {
+        self.instance_id
+            .clone()
+            .unwrap_or_else(super::default_instance_id)
+    }
+
+    /// Delay before the next poll of a source polled every `interval`.
+    pub fn next_delay(&self, interval: Duration) -> Duration {
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        let spread = 1.0 + self.jitter * (2.0 * random - 1.0);
+        interval.mul_f64(spread).max(self.min_interval)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -122,4 +223,27 @@ mod tests {
         assert_eq!(format!("{}", PollInterval::Slow), "60s");
         assert_eq!(format!("{}", PollInterval::Custom(45)), "45s");
     }
+
+    #[test]
+    fn test_poll_policy_jitter_stays_in_range_and_respects_minimum() {
+        let interval = Duration::from_secs(30);
+        assert_eq!(PollPolicy::new().next_delay(interval), interval);
+
+        let policy = PollPolicy::new().jitter(0.1);
+        let delays: Vec<_> = (0..200).map(|_| policy.next_delay(interval)).collect();
+        assert!(delays
+            .iter()
+            .all(|d| *d >= Duration::from_secs(27) && *d <= Duration::from_secs(33)));
+        assert!(delays.iter().any(|d| *d != delays[0]));
+
+        let floored = PollPolicy::new()
+            .jitter(1.0)
+            .min_interval(Duration::from_secs(20));
+        assert!((0..200).all(|_| floored.next_delay(interval) >= Duration::from_secs(20)));
+        assert_eq!(
+            floored.next_delay(Duration::from_secs(1)),
+            Duration::from_secs(20)
+        );
+        assert_eq!(PollPolicy::new().jitter(5.0).jitter_fraction(), 1.0);
+    }
 }
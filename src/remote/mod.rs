@@ -5,6 +5,9 @@
 
 //! Remote configuration sources.
 
+pub(crate) mod cache;
+pub(crate) mod checksum;
+mod client;
 pub(crate) mod common;
 mod interval;
 
@@ -12,12 +15,44 @@ mod interval;
 pub(crate) mod consul;
 #[cfg(feature = "etcd")]
 pub(crate) mod etcd;
+#[cfg(feature = "nacos")]
+pub(crate) mod nacos;
 pub(crate) mod poll;
+pub(crate) mod provider;
+pub(crate) mod proxy;
+#[cfg(feature = "redis")]
+pub(crate) mod redis;
+pub(crate) mod resilience;
+pub(crate) mod rollout;
+pub(crate) mod ssrf;
+pub(crate) mod watcher;
+#[cfg(feature = "zookeeper")]
+pub(crate) mod zookeeper;
 
-pub use interval::PollInterval;
+pub use cache::{CachedValue, RemoteCache};
+pub use checksum::payload_checksum;
+pub use client::{configure_shared_http_client, shared_http_client};
+pub use interval::{PollInterval, PollPolicy};
 
 #[cfg(feature = "consul")]
 pub use consul::{ConsulSource, ConsulSourceBuilder, ConsulTlsConfig};
 #[cfg(feature = "etcd")]
 pub use etcd::{EtcdSource, EtcdSourceBuilder, EtcdTlsConfig};
+#[cfg(feature = "nacos")]
+pub use nacos::{NacosSource, NacosSourceBuilder};
 pub use poll::{HttpPolledSource, HttpPolledSourceBuilder, PolledSource};
+pub use provider::RemoteConfigSource;
+pub use proxy::ProxyConfig;
+#[cfg(feature = "redis")]
+pub use redis::{RedisSource, RedisSourceBuilder, RedisSubscription, RedisTlsConfig};
+pub use resilience::{
+    CircuitBreaker, CircuitBreakerConfig, CircuitState, DegradationPolicy, RemoteConfig,
+    ResilientProvider, RetryPolicy,
+};
+pub use rollout::{
+    default_instance_id, Rollout, DEFAULT_ROLLOUT_DELAY, INSTANCE_ID_ENV, ROLLOUT_KEY,
+};
+pub use ssrf::{is_ip_blocked, SsrfPolicy};
+pub use watcher::{content_hash, RemoteWatcher};
+#[cfg(feature = "zookeeper")]
+pub use zookeeper::{ZookeeperSource, ZookeeperSourceBuilder};
@@ -0,0 +1,738 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Nacos configuration center source.
+//!
+//! A configuration is addressed by namespace (tenant), group and data ID and is
+//! fetched through the Nacos Open API (`/nacos/v1/cs/configs`). Changes are
+//! detected with the long-polling listener endpoint, which holds the request
+//! open until the content MD5 differs from the one last seen. Servers with
+//! authentication enabled are supported either with a static access token or
+//! with username/password, in which case the token is obtained from
+//! `/nacos/v1/auth/login` and refreshed before it expires.
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::loader::{detect_format_from_content, detect_format_from_path, parse_content, Format};
+use crate::remote::proxy::{apply_proxy, ProxyConfig};
+use crate::remote::RemoteConfigSource;
+use crate::types::{AnnotatedValue, SourceId};
+use async_trait::async_trait;
+use md5::{Digest, Md5};
+use reqwest::{Client, Url};
+use serde::Deserialize;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Default poll interval for Nacos (30 seconds).
+pub const DEFAULT_NACOS_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default long-polling timeout; Nacos holds the listener request this long.
+pub const DEFAULT_NACOS_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default maximum Nacos response body size in bytes (16 MB).
+pub const DEFAULT_MAX_NACOS_RESPONSE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Group used when none is set, matching the Nacos default.
+pub const DEFAULT_NACOS_GROUP: &str = "DEFAULT_GROUP";
+
+/// Field and record separators of the listener `Listening-Configs` payload.
+const WORD_SEPARATOR: char = '\u{2}';
+const LINE_SEPARATOR: char = '\u{1}';
+
+/// Refresh a login token this long before Nacos reports it expiring.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+/// `/nacos/v1/auth/login` response.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginResponse {
+    access_token: String,
+    #[serde(default)]
+    token_ttl: Option<u64>,
+}
+
+/// Builder for creating Nacos configuration sources.
+pub struct NacosSourceBuilder {
+    server_addr: String,
+    context_path: String,
+    namespace: String,
+    group: String,
+    data_id: Option<String>,
+    format: Option<Format>,
+    interval: Option<Duration>,
+    long_poll_timeout: Duration,
+    access_token: Option<String>,
+    username: Option<String>,
+    password: Option<String>,
+    tls_skip_verify: bool,
+    max_response_bytes: usize,
+    proxy: Option<ProxyConfig>,
+}
+
+impl NacosSourceBuilder {
+    /// Create a new Nacos source builder.
+    pub fn new() -> Self {
+        Self {
+            server_addr: "127.0.0.1:8848".to_string(),
+            context_path: "/nacos".to_string(),
+            namespace: String::new(),
+            group: DEFAULT_NACOS_GROUP.to_string(),
+            data_id: None,
+            format: None,
+            interval: None,
+            long_poll_timeout: DEFAULT_NACOS_LONG_POLL_TIMEOUT,
+            access_token: None,
+            username: None,
+            password: None,
+            tls_skip_verify: false,
+            max_response_bytes: DEFAULT_MAX_NACOS_RESPONSE_BYTES,
+            proxy: None,
+        }
+    }
+
+    /// Set the Nacos server address (`host:port` or a full URL).
+    pub fn server_addr(mut self, addr: impl Into<String>) -> Self {
+        self.server_addr = addr.into();
+        self
+    }
+
+    /// Set the context path the server is mounted under (default `/nacos`).
+    pub fn context_path(mut self, path: impl Into<String>) -> Self {
+        self.context_path = path.into();
+        self
+    }
+
+    /// Set the namespace ID (tenant). Empty selects the public namespace.
+    pub fn namespace(mut self, namespace: impl Into<String>) -> Self {
+        self.namespace = namespace.into();
+        self
+    }
+
+    /// Set the configuration group.
+    pub fn group(mut self, group: impl Into<String>) -> Self {
+        self.group = group.into();
+        self
+    }
+
+    /// Set the data ID of the configuration (required).
+    pub fn data_id(mut self, data_id: impl Into<String>) -> Self {
+        self.data_id = Some(data_id.into());
+        self
+    }
+
+    /// Set the configuration format.
+    ///
+    /// Defaults to the data ID extension (`app.yaml`), then to detection
+    /// from the content.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Set the poll interval.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Set how long the server may hold a long-polling request.
+    pub fn long_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.long_poll_timeout = timeout;
+        self
+    }
+
+    /// Use a pre-issued access token.
+    pub fn access_token(mut self, token: impl Into<String>) -> Self {
+        self.access_token = Some(token.into());
+        self
+    }
+
+    /// Log in with username and password to obtain access tokens.
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.username = Some(username.into());
+        self.password = Some(password.into());
+        self
+    }
+
+    /// Skip TLS verification (for development only).
+    ///
+    /// This option is only effective in debug builds.
+    /// In release builds, TLS verification is always enforced for security.
+    pub fn tls_skip_verify(mut self, skip: bool) -> Self {
+        self.tls_skip_verify = cfg!(debug_assertions) && skip;
+        self
+    }
+
+    /// Set the maximum HTTP response body size in bytes.
+    pub fn max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// Reach the server through `proxy` instead of the one named by the
+    /// proxy environment variables.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Build the Nacos source.
+    pub fn build(self) -> ConfigResult<NacosSource> {
+        let data_id =
+            self.data_id
+                .filter(|id| !id.is_empty())
+                .ok_or_else(|| ConfigError::InvalidValue {
+                    key: "nacos.data_id".to_string(),
+                    expected_type: "non-empty string".to_string(),
+                    message: "A Nacos data ID is required".to_string(),
+                })?;
+
+        let base = if self.server_addr.contains("://") {
+            self.server_addr
+        } else {
+            format!("http://{}", self.server_addr)
+        };
+        let base_url = format!(
+            "{}/{}",
+            base.trim_end_matches('/'),
+            self.context_path.trim_matches('/')
+        );
+        let base_url = base_url.trim_end_matches('/').to_string();
+
+        let client = http_client(
+            self.tls_skip_verify,
+            self.long_poll_timeout,
+            self.proxy.as_ref(),
+        )?;
+
+        let format = self
+            .format
+            .or_else(|| detect_format_from_path(Path::new(&data_id)));
+        let credentials = self.username.zip(self.password);
+
+        Ok(NacosSource {
+            client: Arc::new(client),
+            base_url: Arc::from(base_url),
+            namespace: Arc::from(self.namespace),
+            group: Arc::from(self.group),
+            data_id: Arc::from(data_id),
+            format,
+            interval: self.interval.unwrap_or(DEFAULT_NACOS_POLL_INTERVAL),
+            long_poll_timeout: self.long_poll_timeout,
+            tls_skip_verify: self.tls_skip_verify,
+            static_token: self.access_token.map(Arc::from),
+            credentials: credentials.map(|(u, p)| (Arc::from(u), Arc::from(p))),
+            login_token: Arc::new(Mutex::new(None)),
+            last_md5: Arc::new(Mutex::new(String::new())),
+            max_response_bytes: self.max_response_bytes,
+        })
+    }
+}
+
+impl Default for NacosSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// HTTP client for a Nacos source.
+fn http_client(
+    tls_skip_verify: bool,
+    long_poll_timeout: Duration,
+    proxy: Option<&ProxyConfig>,
+) -> ConfigResult<Client> {
+    // The listener request is held open for the long-poll timeout, so the
+    // client timeout has to leave room for it.
+    let builder = Client::builder()
+        .danger_accept_invalid_certs(tls_skip_verify)
+        .timeout(long_poll_timeout + Duration::from_secs(10));
+    apply_proxy(builder, proxy)?
+        .build()
+        .map_err(|e| ConfigError::InvalidValue {
+            key: "nacos".to_string(),
+            expected_type: "HTTP client".to_string(),
+            message: format!("Failed to create HTTP client: {}", e),
+        })
+}
+
+/// A token obtained from the login endpoint.
+struct LoginToken {
+    token: Arc<str>,
+    refresh_at: Instant,
+}
+
+/// Nacos-backed configuration source.
+pub struct NacosSource {
+    client: Arc<Client>,
+    base_url: Arc<str>,
+    namespace: Arc<str>,
+    group: Arc<str>,
+    data_id: Arc<str>,
+    format: Option<Format>,
+    interval: Duration,
+    long_poll_timeout: Duration,
+    tls_skip_verify: bool,
+    static_token: Option<Arc<str>>,
+    credentials: Option<(Arc<str>, Arc<str>)>,
+    login_token: Arc<Mutex<Option<LoginToken>>>,
+    last_md5: Arc<Mutex<String>>,
+    max_response_bytes: usize,
+}
+
+impl NacosSource {
+    /// Get the source identifier.
+    pub fn source_id(&self) -> SourceId {
+        SourceId::new(format!(
+            "nacos:{}/{}/{}",
+            self.namespace, self.group, self.data_id
+        ))
+    }
+
+    /// MD5 of the content last fetched, as sent to the listener endpoint.
+    pub fn content_md5(&self) -> String {
+        self.last_md5.lock().map(|m| m.clone()).unwrap_or_default()
+    }
+
+    /// Fetch and parse the configuration.
+    async fn fetch_internal(&self) -> ConfigResult<AnnotatedValue> {
+        let mut params = vec![
+            ("dataId", self.data_id.to_string()),
+            ("group", self.group.to_string()),
+        ];
+        if !self.namespace.is_empty() {
+            params.push(("tenant", self.namespace.to_string()));
+        }
+        if let Some(token) = self.token().await? {
+            params.push(("accessToken", token.to_string()));
+        }
+        let url = self.url("/v1/cs/configs", &params)?;
+
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| request_error("Failed to fetch from Nacos", e))?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(ConfigError::InvalidValue {
+                key: "nacos".to_string(),
+                expected_type: "Nacos configuration".to_string(),
+                message: format!(
+                    "No configuration '{}' in group '{}'",
+                    self.data_id, self.group
+                ),
+            });
+        }
+        let content = self.read_body(response).await?;
+
+        let md5 = hex_md5(&content);
+        if let Ok(mut last) = self.last_md5.lock() {
+            *last = md5;
+        }
+        self.parse(&content)
+    }
+
+    /// Long-poll the listener endpoint; `true` if the configuration changed.
+    async fn wait_for_change(&self) -> ConfigResult<bool> {
+        let mut listening = format!(
+            "{}{WORD_SEPARATOR}{}{WORD_SEPARATOR}{}",
+            self.data_id,
+            self.group,
+            self.content_md5()
+        );
+        if !self.namespace.is_empty() {
+            listening.push(WORD_SEPARATOR);
+            listening.push_str(&self.namespace);
+        }
+        listening.push(LINE_SEPARATOR);
+
+        let mut params = Vec::new();
+        if let Some(token) = self.token().await? {
+            params.push(("accessToken", token.to_string()));
+        }
+        let url = self.url("/v1/cs/configs/listener", &params)?;
+        let body = form_encode(&[("Listening-Configs", listening.as_str())]);
+
+        let response = self
+            .client
+            .post(url)
+            .header(
+                "Long-Pulling-Timeout",
+                self.long_poll_timeout.as_millis().to_string(),
+            )
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| request_error("Nacos listener request failed", e))?;
+        let changed = self.read_body(response).await?;
+        Ok(!changed.trim().is_empty())
+    }
+
+    /// Current access token, logging in when credentials are configured.
+    async fn token(&self) -> ConfigResult<Option<Arc<str>>> {
+        if let Some(token) = &self.static_token {
+            return Ok(Some(token.clone()));
+        }
+        let Some((username, password)) = &self.credentials else {
+            return Ok(None);
+        };
+
+        if let Ok(guard) = self.login_token.lock() {
+            if let Some(login) = guard.as_ref().filter(|t| Instant::now() < t.refresh_at) {
+                return Ok(Some(login.token.clone()));
+            }
+        }
+
+        let url = self.url("/v1/auth/login", &[])?;
+        let body = form_encode(&[("username", username), ("password", password)]);
+        let response = self
+            .client
+            .post(url)
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| request_error("Nacos login failed", e))?;
+        let body = self
+            .read_body(response)
+            .await
+            .map_err(|_| ConfigError::KeyError {
+                message: "Nacos login was rejected".to_string(),
+            })?;
+        let login: LoginResponse =
+            serde_json::from_str(&body).map_err(|e| ConfigError::InvalidValue {
+                key: "nacos".to_string(),
+                expected_type: "Nacos login response".to_string(),
+                message: format!("Failed to parse login response: {}", e),
+            })?;
+
+        let ttl = Duration::from_secs(login.token_ttl.unwrap_or(18_000));
+        let token: Arc<str> = Arc::from(login.access_token);
+        if let Ok(mut guard) = self.login_token.lock() {
+            *guard = Some(LoginToken {
+                token: token.clone(),
+                refresh_at: Instant::now() + ttl.saturating_sub(TOKEN_REFRESH_MARGIN),
+            });
+        }
+        Ok(Some(token))
+    }
+
+    fn url(&self, path: &str, params: &[(&str, String)]) -> ConfigResult<Url> {
+        Url::parse_with_params(&format!("{}{}", self.base_url, path), params).map_err(|e| {
+            ConfigError::InvalidValue {
+                key: "nacos.server_addr".to_string(),
+                expected_type: "URL".to_string(),
+                message: format!("Invalid Nacos URL: {}", e),
+            }
+        })
+    }
+
+    /// Read a successful response body, bounded by `max_response_bytes`.
+    async fn read_body(&self, mut response: reqwest::Response) -> ConfigResult<String> {
+        if !response.status().is_success() {
+            return Err(ConfigError::InvalidValue {
+                key: "nacos".to_string(),
+                expected_type: "Nacos response".to_string(),
+                message: format!("Nacos returned status: {}", response.status()),
+            });
+        }
+        if let Some(len) = response.content_length() {
+            if len as usize > self.max_response_bytes {
+                return Err(ConfigError::SizeLimitExceeded {
+                    actual: len as usize,
+                    limit: self.max_response_bytes,
+                });
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| request_error("Failed to read Nacos response body", e))?
+        {
+            if body.len() + chunk.len() > self.max_response_bytes {
+                return Err(ConfigError::SizeLimitExceeded {
+                    actual: body.len() + chunk.len(),
+                    limit: self.max_response_bytes,
+                });
+            }
+            body.extend_from_slice(&chunk);
+        }
+        String::from_utf8(body).map_err(|e| ConfigError::InvalidValue {
+            key: "nacos".to_string(),
+            expected_type: "UTF-8 text".to_string(),
+            message: format!("Nacos response is not valid UTF-8: {}", e),
+        })
+    }
+
+    fn parse(&self, content: &str) -> ConfigResult<AnnotatedValue> {
+        let format = self
+            .format
+            .or_else(|| detect_format_from_content(content))
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "unknown".into(),
+                message: format!(
+                    "Cannot determine format of Nacos data ID '{}'",
+                    self.data_id
+                ),
+                location: None,
+                source: None,
+            })?;
+        parse_content(content, format, self.source_id(), None)
+    }
+}
+
+fn request_error(context: &str, err: reqwest::Error) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: "nacos".to_string(),
+        expected_type: "Nacos response".to_string(),
+        message: format!("{}: {}", context, err),
+    }
+}
+
+/// `application/x-www-form-urlencoded` body for `pairs`.
+fn form_encode(pairs: &[(&str, &str)]) -> String {
+    let mut url = Url::parse("http://localhost/").expect("static URL");
+    url.query_pairs_mut().extend_pairs(pairs);
+    url.query().unwrap_or_default().to_string()
+}
+
+fn hex_md5(content: &str) -> String {
+    Md5::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[async_trait]
+impl crate::remote::PolledSource for NacosSource {
+    async fn poll(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn source_id(&self) -> SourceId {
+        Self::source_id(self)
+    }
+}
+
+#[async_trait]
+impl crate::interface::AsyncSource for NacosSource {
+    async fn load(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn source_id(&self) -> &SourceId {
+        static SOURCE_ID: std::sync::OnceLock<SourceId> = std::sync::OnceLock::new();
+        SOURCE_ID.get_or_init(|| SourceId::new("nacos"))
+    }
+
+    fn name(&self) -> &str {
+        "nacos"
+    }
+}
+
+#[async_trait]
+impl RemoteConfigSource for NacosSource {
+    async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    /// Long-poll until the content MD5 changes, then fetch the new content.
+    async fn watch(&self) -> ConfigResult<Option<AnnotatedValue>> {
+        if self.content_md5().is_empty() {
+            self.fetch_internal().await?;
+        }
+        while !self.wait_for_change().await? {}
+        self.fetch_internal().await.map(Some)
+    }
+
+    fn name(&self) -> &str {
+        "nacos"
+    }
+
+    fn endpoints(&self) -> Vec<String> {
+        vec![self.base_url.to_string()]
+    }
+
+    fn use_proxy(&mut self, proxy: &ProxyConfig) -> ConfigResult<()> {
+        self.client = Arc::new(http_client(
+            self.tls_skip_verify,
+            self.long_poll_timeout,
+            Some(proxy),
+        )?);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ConfigValue;
+    use std::io::{Read, Write};
+
+    /// Minimal HTTP/1.1 server answering one request per `(status, body)`
+    /// entry. Returns the server address and a receiver for the raw requests.
+    fn mock_http_server(
+        responses: Vec<(u16, String)>,
+    ) -> (String, std::sync::mpsc::Receiver<String>) {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let (tx, rx) = std::sync::mpsc::channel();
+        std::thread::spawn(move || {
+            for (status, body) in responses {
+                let Ok((mut stream, _)) = listener.accept() else {
+                    continue;
+                };
+                let _ = tx.send(read_request(&mut stream));
+                let response = format!(
+                    "HTTP/1.1 {status} OK\r\nConnection: close\r\nContent-Length: {len}\r\n\r\n{body}",
+                    len = body.len(),
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+        (format!("127.0.0.1:{}", addr.port()), rx)
+    }
+
+    /// Read one request, headers and `Content-Length` body.
+    fn read_request(stream: &mut std::net::TcpStream) -> String {
+        let mut data = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let text = String::from_utf8_lossy(&data).into_owned();
+            if let Some(end) = text.find("\r\n\r\n") {
+                let len = text
+                    .lines()
+                    .find_map(|l| {
+                        l.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                    })
+                    .unwrap_or(0);
+                if data.len() >= end + 4 + len {
+                    return text;
+                }
+            }
+            match stream.read(&mut buf) {
+                Ok(0) | Err(_) => return String::from_utf8_lossy(&data).into_owned(),
+                Ok(n) => data.extend_from_slice(&buf[..n]),
+            }
+        }
+    }
+
+    #[test]
+    fn test_builder_requires_data_id() {
+        assert!(NacosSourceBuilder::new().build().is_err());
+        let source = NacosSourceBuilder::new()
+            .data_id("app.yaml")
+            .namespace("prod")
+            .build()
+            .unwrap();
+        assert_eq!(source.format, Some(Format::Yaml));
+        assert_eq!(source.group.as_ref(), DEFAULT_NACOS_GROUP);
+        assert_eq!(
+            source.source_id().as_str(),
+            "nacos:prod/DEFAULT_GROUP/app.yaml"
+        );
+    }
+
+    #[test]
+    fn test_hex_md5_and_form_encode() {
+        assert_eq!(hex_md5(""), "d41d8cd98f00b204e9800998ecf8427e");
+        assert_eq!(
+            form_encode(&[("Listening-Configs", "a\u{2}b\u{1}")]),
+            "Listening-Configs=a%02b%01"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_parses_content_and_records_md5() {
+        let (addr, requests) = mock_http_server(vec![(200, "port = 8080\n".to_string())]);
+        let source = NacosSourceBuilder::new()
+            .server_addr(addr)
+            .namespace("prod")
+            .data_id("app.toml")
+            .access_token("secret-token")
+            .build()
+            .unwrap();
+
+        let value = source.fetch_internal().await.unwrap();
+        let ConfigValue::Map(map) = &value.inner else {
+            panic!("expected map");
+        };
+        assert_eq!(map["port"].inner, ConfigValue::I64(8080));
+        assert_eq!(source.content_md5(), hex_md5("port = 8080\n"));
+
+        let request = requests.recv().unwrap();
+        assert!(
+            request.starts_with("GET /nacos/v1/cs/configs?"),
+            "{request}"
+        );
+        assert!(request.contains("dataId=app.toml"), "{request}");
+        assert!(request.contains("tenant=prod"), "{request}");
+        assert!(request.contains("accessToken=secret-token"), "{request}");
+    }
+
+    #[tokio::test]
+    async fn test_missing_config_is_an_error() {
+        let (addr, _requests) = mock_http_server(vec![(404, "config data not exist".to_string())]);
+        let source = NacosSourceBuilder::new()
+            .server_addr(addr)
+            .data_id("app.toml")
+            .build()
+            .unwrap();
+        let err = source.fetch_internal().await.unwrap_err();
+        assert!(err.to_string().contains("app.toml"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_login_then_watch_long_polls_until_change() {
+        let (addr, requests) = mock_http_server(vec![
+            (
+                200,
+                r#"{"accessToken":"issued","tokenTtl":18000}"#.to_string(),
+            ),
+            (200, "port = 1\n".to_string()),
+            (200, String::new()),
+            (200, "app.toml%02DEFAULT_GROUP%01\n".to_string()),
+            (200, "port = 2\n".to_string()),
+        ]);
+        let source = NacosSourceBuilder::new()
+            .server_addr(addr)
+            .data_id("app.toml")
+            .credentials("nacos", "pw")
+            .build()
+            .unwrap();
+
+        let value = source.watch().await.unwrap().expect("change");
+        let ConfigValue::Map(map) = &value.inner else {
+            panic!("expected map");
+        };
+        assert_eq!(map["port"].inner, ConfigValue::I64(2));
+
+        let requests: Vec<String> = requests.try_iter().collect();
+        assert!(requests[0].starts_with("POST /nacos/v1/auth/login"));
+        assert!(requests[0].contains("username=nacos&password=pw"));
+        assert!(requests[2].starts_with("POST /nacos/v1/cs/configs/listener?accessToken=issued"));
+        assert!(requests[2]
+            .to_ascii_lowercase()
+            .contains("long-pulling-timeout: 30000"));
+        assert!(requests[2].contains(&format!(
+            "Listening-Configs=app.toml%02DEFAULT_GROUP%02{}%01",
+            hex_md5("port = 1\n")
+        )));
+        assert!(
+            requests[4].contains("accessToken=issued"),
+            "token is reused"
+        );
+    }
+}
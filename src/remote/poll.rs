@@ -12,24 +12,33 @@
 //!
 //! # SSRF Protection
 //!
-//! This module implements comprehensive Server-Side Request Forgery protection:
-//! - Blocked IP ranges: private networks, loopback, link-local, documentation ranges
-//! - DNS resolution validation: resolved IPs are checked against blocked ranges
-//! - DNS rebinding protection: domain names are resolved at build time
-//! - Configurable whitelist: specific domains can be allowed via builder
-//! - All blocked attempts return errors with full context
-//! - IPv6 support: handles IPv6 addresses and IPv4-mapped IPv6 addresses
+//! URLs are checked against an [`SsrfPolicy`] when the source is built;
+//! domain names are resolved then and every resolved IP is validated.
+//!
+//! # Unix Sockets
+//!
+//! A sidecar agent listening on a Unix domain socket is reached with an
+//! `http+unix://` URL whose host is the percent-encoded socket path, e.g.
+//! `http+unix://%2Frun%2Fagent.sock/config.json`. The scheme has to be
+//! allowed by the source's [`SsrfPolicy`].
+//!
+//! # Connection Pooling
+//!
+//! Sources without a proxy, a Unix socket or followed redirects use the
+//! [shared HTTP client](super::shared_http_client), so sources polling the
+//! same server share its connections.
 
+use super::client::shared_http_client;
+use super::proxy::{apply_proxy, ProxyConfig};
+use super::ssrf::{SsrfPolicy, UNIX_SOCKET_SCHEME};
 use crate::error::{ConfigError, ConfigResult};
 use crate::loader::{detect_format_from_content, parse_content, Format};
 use crate::types::{AnnotatedValue, SourceId};
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use reqwest::Client;
-use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
-use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
-use std::sync::LazyLock;
 
 use std::time::Duration;
 use tokio::sync::RwLock;
@@ -37,192 +46,6 @@ use tokio::sync::RwLock;
 /// Default poll interval when not specified (60 seconds).
 pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(60);
 
-static BLOCKED_NETWORKS: LazyLock<Vec<ipnet::IpNet>> = LazyLock::new(|| {
-    vec![
-        "127.0.0.0/8".parse().unwrap(),
-        "10.0.0.0/8".parse().unwrap(),
-        "172.16.0.0/12".parse().unwrap(),
-        "192.168.0.0/16".parse().unwrap(),
-        "169.254.0.0/16".parse().unwrap(),
-        "100.64.0.0/10".parse().unwrap(),
-        "192.0.2.0/24".parse().unwrap(),
-        "198.51.100.0/24".parse().unwrap(),
-        "203.0.113.0/24".parse().unwrap(),
-        "192.0.0.0/24".parse().unwrap(),
-        "fc00::/7".parse().unwrap(),
-        "fe80::/10".parse().unwrap(),
-    ]
-});
-
-/// Check if an IP address is in a blocked range.
-pub fn is_ip_blocked(ip: IpAddr) -> bool {
-    if let IpAddr::V6(ipv6) = ip {
-        let octets = ipv6.octets();
-        if octets[..10] == [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
-            && octets[10] == 0xff
-            && octets[11] == 0xff
-        {
-            return true;
-        }
-    }
-
-    if ip.is_loopback() {
-        return true;
-    }
-
-    BLOCKED_NETWORKS.iter().any(|net| net.contains(&ip))
-}
-
-/// Resolve a hostname and check all resolved IPs against blocked ranges.
-///
-/// This provides DNS rebinding protection by validating that ALL resolved IPs
-/// are safe to connect to. If any IP is blocked, the connection is rejected.
-///
-/// # Arguments
-///
-/// * `host` - The hostname to resolve
-/// * `port` - The port to use for resolution
-///
-/// # Returns
-///
-/// Returns `Ok(Vec<IpAddr>)` with all resolved IPs if all are safe,
-/// or an error if any IP is blocked.
-fn resolve_host_with_validation(host: &str, port: u16) -> ConfigResult<Vec<IpAddr>> {
-    let addr_string = if port == 0 {
-        format!("{}:80", host)
-    } else {
-        format!("{}:{}", host, port)
-    };
-
-    let addrs: Vec<SocketAddr> = addr_string
-        .to_socket_addrs()
-        .map_err(|_| ConfigError::InvalidValue {
-            key: "url".to_string(),
-            expected_type: "resolvable hostname".to_string(),
-            message: format!("Cannot resolve hostname: {}", host),
-        })?
-        .collect();
-
-    if addrs.is_empty() {
-        return Err(ConfigError::InvalidValue {
-            key: "url".to_string(),
-            expected_type: "resolvable hostname".to_string(),
-            message: format!("No addresses resolved for hostname: {}", host),
-        });
-    }
-
-    let mut resolved_ips = Vec::new();
-    for addr in &addrs {
-        let ip = addr.ip();
-        resolved_ips.push(ip);
-
-        if is_ip_blocked(ip) {
-            // SSRF attempt detected - return error without logging
-            return Err(ConfigError::InvalidValue {
-                key: "url".to_string(),
-                expected_type: "public IP".to_string(),
-                message: "SSRF attempt detected: resolved IP address is in a blocked private range"
-                    .to_string(),
-            });
-        }
-    }
-
-    Ok(resolved_ips)
-}
-
-/// Validate URL for security (SSRF protection).
-///
-/// Performs comprehensive SSRF protection checks:
-/// 1. Only HTTPS URLs are allowed (unless HTTPS-only is disabled)
-/// 2. Domain names are resolved and all resolved IPs are validated
-/// 3. Direct IP addresses are validated against blocked ranges
-/// 4. IPv4-mapped IPv6 addresses are blocked
-/// 5. Domains can be whitelisted via the builder
-fn validate_url(url: &str, allowed_domains: &[String]) -> ConfigResult<Vec<IpAddr>> {
-    let parsed = url::Url::parse(url).map_err(|_| ConfigError::InvalidValue {
-        key: "url".to_string(),
-        expected_type: "valid URL".to_string(),
-        message: "Invalid URL format".to_string(),
-    })?;
-
-    // Only allow HTTPS by default for security
-    if parsed.scheme() != "https" {
-        // Non-HTTPS URL rejected - return error without logging
-        return Err(ConfigError::InvalidValue {
-            key: "url".to_string(),
-            expected_type: "https URL".to_string(),
-            message: "Only HTTPS URLs are allowed for remote configuration".to_string(),
-        });
-    }
-
-    let host = match parsed.host() {
-        Some(h) => h,
-        None => {
-            return Err(ConfigError::InvalidValue {
-                key: "url".to_string(),
-                expected_type: "valid URL with host".to_string(),
-                message: "URL must have a host".to_string(),
-            });
-        }
-    };
-
-    match host {
-        url::Host::Domain(domain) => {
-            // Check whitelist first
-            let domain_str = domain.to_string();
-            let is_whitelisted = allowed_domains.iter().any(|allowed| {
-                // Exact match
-                if allowed == &domain_str {
-                    return true;
-                }
-                // Wildcard match: *.example.com matches sub.example.com
-                if let Some(suffix) = allowed.strip_prefix("*.") {
-                    // Domain must be a proper subdomain (e.g., sub.example.com, not example.com)
-                    return domain_str.ends_with(&format!(".{suffix}"))
-                        && !domain_str.ends_with(suffix);
-                }
-                // Subdomain match for non-wildcard entries (e.g., "example.com" matches "sub.example.com")
-                domain_str.ends_with(&format!(".{allowed}"))
-            });
-
-            if is_whitelisted {
-                // Whitelisted domains are allowed without IP validation
-                return Ok(Vec::new());
-            }
-
-            // Resolve the domain and validate all IPs (DNS rebinding protection)
-            let port = parsed.port().unwrap_or(443);
-            let resolved = resolve_host_with_validation(&domain_str, port)?;
-
-            Ok(resolved)
-        }
-        url::Host::Ipv4(ip) => {
-            if is_ip_blocked(IpAddr::V4(ip)) {
-                // Private IPv4 rejected - return error without logging
-                return Err(ConfigError::InvalidValue {
-                    key: "url".to_string(),
-                    expected_type: "public IP".to_string(),
-                    message: "Connection to private/internal IP addresses is not allowed"
-                        .to_string(),
-                });
-            }
-            Ok(vec![IpAddr::V4(ip)])
-        }
-        url::Host::Ipv6(ip) => {
-            if is_ip_blocked(IpAddr::V6(ip)) {
-                // Private IPv6 rejected - return error without logging
-                return Err(ConfigError::InvalidValue {
-                    key: "url".to_string(),
-                    expected_type: "public IP".to_string(),
-                    message: "Connection to private/internal IP addresses is not allowed"
-                        .to_string(),
-                });
-            }
-            Ok(vec![IpAddr::V6(ip)])
-        }
-    }
-}
-
 /// Trait for polled configuration sources.
 ///
 /// Sources implementing this trait will be polled at regular intervals
@@ -237,6 +60,18 @@ pub trait PolledSource: Send + Sync {
 
     /// Get the source identifier.
     fn source_id(&self) -> SourceId;
+
+    /// URLs or `host:port` addresses the source connects to, checked by
+    /// [`RemoteWatcher::with_policy`](super::RemoteWatcher::with_policy).
+    /// Defaults to none.
+    fn endpoints(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Turn conditional requests on or off, as set by
+    /// [`PollPolicy::conditional_requests`](super::PollPolicy::conditional_requests).
+    /// Sources without conditional requests ignore it.
+    fn set_conditional_requests(&self, _enabled: bool) {}
 }
 
 /// HTTP-polled configuration source.
@@ -250,7 +85,8 @@ pub trait PolledSource: Send + Sync {
 ///
 /// # SSRF Protection
 ///
-/// By default, connections to the following are blocked:
+/// By default ([`SsrfPolicy::default`]), connections to the following are
+/// blocked, and redirects are not followed:
 /// - Private IP ranges (10.x.x.x, 172.16-31.x.x, 192.168.x.x)
 /// - Loopback addresses (127.x.x.x, ::1)
 /// - Link-local addresses (169.254.x.x, fe80::/10)
@@ -282,14 +118,17 @@ pub trait PolledSource: Send + Sync {
 #[derive(Debug)]
 pub struct HttpPolledSource {
     url: Arc<str>,
+    request_url: Arc<str>,
     interval: Duration,
     client: Client,
+    timeout: Option<Duration>,
     format: Option<Format>,
     #[allow(dead_code)] // reserved for cache invalidation tracking
     cache_generation: AtomicU64,
     cached: RwLock<Option<AnnotatedValue>>,
     last_etag: ArcSwap<Option<String>>,
     last_modified: ArcSwap<Option<String>>,
+    conditional: AtomicBool,
     source_id: SourceId,
 }
 
@@ -300,6 +139,9 @@ pub struct HttpPolledSourceBuilder {
     format: Option<Format>,
     timeout: Option<Duration>,
     allowed_domains: Vec<String>,
+    ssrf_policy: SsrfPolicy,
+    proxy: Option<ProxyConfig>,
+    conditional_requests: bool,
 }
 
 impl HttpPolledSourceBuilder {
@@ -311,6 +153,9 @@ impl HttpPolledSourceBuilder {
             format: None,
             timeout: None,
             allowed_domains: Vec::new(),
+            ssrf_policy: SsrfPolicy::default(),
+            proxy: None,
+            conditional_requests: true,
         }
     }
 
@@ -353,8 +198,8 @@ impl HttpPolledSourceBuilder {
     ///
     /// Supports:
     /// - Exact match: `internal.example.com`
-    /// - Subdomain wildcard: `example.com` matches `api.example.com`
-    /// - Explicit wildcard: `*.example.com` (via prefix check)
+    /// - Subdomain wildcard: `*.example.com` matches `api.example.com` but
+    ///   not `example.com`
     ///
     /// # Security Note
     ///
@@ -373,13 +218,35 @@ impl HttpPolledSourceBuilder {
         self
     }
 
+    /// Set the SSRF policy the URL and any redirect must satisfy. Domains
+    /// added with [`allowed_domain`](Self::allowed_domain) are allowed on
+    /// top of it.
+    pub fn ssrf_policy(mut self, policy: SsrfPolicy) -> Self {
+        self.ssrf_policy = policy;
+        self
+    }
+
+    /// Fetch through `proxy` instead of the one named by the proxy
+    /// environment variables.
+    pub fn proxy(mut self, proxy: ProxyConfig) -> Self {
+        self.proxy = Some(proxy);
+        self
+    }
+
+    /// Send `If-None-Match` / `If-Modified-Since` with each poll (default:
+    /// `true`). Turn off for servers that answer them incorrectly.
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.conditional_requests = enabled;
+        self
+    }
+
     /// Build the `HttpPolledSource`.
     ///
     /// # Errors
     ///
     /// Returns an error if:
     /// - URL is missing
-    /// - URL uses non-HTTPS scheme
+    /// - URL uses a scheme the [SSRF policy](Self::ssrf_policy) does not allow
     /// - URL host is a blocked private IP
     /// - URL host is a domain that resolves to a blocked IP (DNS rebinding protection)
     pub fn build(self) -> ConfigResult<HttpPolledSource> {
@@ -390,39 +257,102 @@ impl HttpPolledSourceBuilder {
         })?;
 
         // Validate URL for security (SSRF protection with DNS resolution)
-        validate_url(&url, &self.allowed_domains)?;
+        let policy = self.ssrf_policy.allow_hosts(self.allowed_domains);
+        policy.check(&url)?;
 
         let url_arc: Arc<str> = url.clone().into();
         let source_id = SourceId::new(format!("http:{}", url_arc));
 
-        // Build HTTP client with TLS enabled by default
-        let mut client_builder = Client::builder().use_rustls_tls();
-
-        if let Some(timeout) = self.timeout {
-            client_builder = client_builder.timeout(timeout);
-        }
-
-        let client = client_builder
-            .build()
-            .map_err(|_e| ConfigError::RemoteUnavailable {
-                error_type: "ClientBuild".to_string(),
-                retryable: false,
-            })?;
+        let unix_socket = split_unix_socket_url(&url)?;
+        let (client, request_url) =
+            if unix_socket.is_none() && self.proxy.is_none() && policy.denies_redirects() {
+                (shared_http_client()?, url.clone())
+            } else {
+                // Build HTTP client with TLS enabled by default
+                let client_builder = Client::builder()
+                    .use_rustls_tls()
+                    .redirect(policy.redirect_policy());
+                let mut client_builder = apply_proxy(client_builder, self.proxy.as_ref())?;
+                let request_url = match unix_socket {
+                    Some((socket, request_url)) => {
+                        client_builder = with_unix_socket(client_builder, socket)?;
+                        request_url
+                    }
+                    None => url.clone(),
+                };
+                let client =
+                    client_builder
+                        .build()
+                        .map_err(|_e| ConfigError::RemoteUnavailable {
+                            error_type: "ClientBuild".to_string(),
+                            retryable: false,
+                        })?;
+                (client, request_url)
+            };
 
         Ok(HttpPolledSource {
             url: url_arc,
+            request_url: request_url.into(),
             interval: self.interval.unwrap_or(DEFAULT_POLL_INTERVAL),
             client,
+            timeout: self.timeout,
             format: self.format,
             cache_generation: AtomicU64::new(0),
             cached: RwLock::new(None),
             last_etag: ArcSwap::new(Arc::new(None)),
             last_modified: ArcSwap::new(Arc::new(None)),
+            conditional: AtomicBool::new(self.conditional_requests),
             source_id,
         })
     }
 }
 
+/// Split an `http+unix://` URL into the socket path and the URL requested
+/// over the socket. Other URLs give `None`.
+fn split_unix_socket_url(url: &str) -> ConfigResult<Option<(String, String)>> {
+    let invalid = |message: &str| ConfigError::InvalidValue {
+        key: "url".to_string(),
+        expected_type: "http+unix://<percent-encoded socket path>/<path> URL".to_string(),
+        message: message.to_string(),
+    };
+    let parsed = url::Url::parse(url).map_err(|_| invalid("Invalid URL format"))?;
+    if parsed.scheme() != UNIX_SOCKET_SCHEME {
+        return Ok(None);
+    }
+    let socket = percent_encoding::percent_decode_str(parsed.host_str().unwrap_or_default())
+        .decode_utf8()
+        .map_err(|_| invalid("socket path is not valid UTF-8"))?;
+    if socket.is_empty() {
+        return Err(invalid("URL must name a socket path"));
+    }
+    let mut request_url = format!("http://localhost{}", parsed.path());
+    if let Some(query) = parsed.query() {
+        request_url.push('?');
+        request_url.push_str(query);
+    }
+    Ok(Some((socket.into_owned(), request_url)))
+}
+
+#[cfg(unix)]
+fn with_unix_socket(
+    builder: reqwest::ClientBuilder,
+    socket: String,
+) -> ConfigResult<reqwest::ClientBuilder> {
+    Ok(builder.unix_socket(socket))
+}
+
+#[cfg(not(unix))]
+fn with_unix_socket(
+    _builder: reqwest::ClientBuilder,
+    _socket: String,
+) -> ConfigResult<reqwest::ClientBuilder> {
+    Err(ConfigError::InvalidValue {
+        key: "url".to_string(),
+        expected_type: "http or https URL".to_string(),
+        message: "Unix socket URLs are only supported on Unix".to_string(),
+    })
+}
+
 impl Default for HttpPolledSourceBuilder {
     fn default() -> Self {
         Self::new()
@@ -436,14 +366,19 @@ impl PolledSource for HttpPolledSource {
     /// Uses ETag and Last-Modified headers for conditional requests.
     /// Returns cached value on 304 Not Modified responses.
     async fn poll(&self) -> ConfigResult<AnnotatedValue> {
-        let mut request = self.client.get(self.url.as_ref());
-
-        if let Some(etag) = self.last_etag.load().as_ref() {
-            request = request.header("If-None-Match", etag.as_str());
+        let mut request = self.client.get(self.request_url.as_ref());
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
         }
 
-        if let Some(modified) = self.last_modified.load().as_ref() {
-            request = request.header("If-Modified-Since", modified.as_str());
+        if self.conditional.load(Ordering::Relaxed) {
+            if let Some(etag) = self.last_etag.load().as_ref() {
+                request = request.header("If-None-Match", etag.as_str());
+            }
+
+            if let Some(modified) = self.last_modified.load().as_ref() {
+                request = request.header("If-Modified-Since", modified.as_str());
+            }
         }
 
         let response = request
@@ -473,18 +408,17 @@ impl PolledSource for HttpPolledSource {
             });
         }
 
-        if let Some(etag) = response.headers().get("etag") {
-            if let Ok(etag_str) = etag.to_str() {
-                self.last_etag.store(Arc::new(Some(etag_str.to_string())));
-            }
-        }
-
-        if let Some(modified) = response.headers().get("last-modified") {
-            if let Ok(modified_str) = modified.to_str() {
-                self.last_modified
-                    .store(Arc::new(Some(modified_str.to_string())));
-            }
-        }
+        // Replace (or clear) the validators so a server that stops sending
+        // them is not asked with stale ones.
+        let header = |name: &str| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        self.last_etag.store(Arc::new(header("etag")));
+        self.last_modified.store(Arc::new(header("last-modified")));
 
         let body = response
             .text()
@@ -513,6 +447,14 @@ impl PolledSource for HttpPolledSource {
     fn source_id(&self) -> SourceId {
         self.source_id.clone()
     }
+
+    fn endpoints(&self) -> Vec<String> {
+        vec![self.url.to_string()]
+    }
+
+    fn set_conditional_requests(&self, enabled: bool) {
+        self.conditional.store(enabled, Ordering::Relaxed);
+    }
 }
 
 /// Parse content from a remote source using the unified parser.
@@ -539,275 +481,6 @@ fn is_retryable_error(error: &reqwest::Error) -> bool {
 mod tests {
     use super::*;
 
-    // =============================================================================
-    // SSRF Protection Tests (9.1.7)
-    // =============================================================================
-
-    #[test]
-    fn test_is_ip_blocked_loopback_v4() {
-        // 127.0.0.0/8 - all loopback
-        assert!(is_ip_blocked(IpAddr::V4("127.0.0.1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4(
-            "127.255.255.255".parse().unwrap()
-        )));
-        assert!(is_ip_blocked(IpAddr::V4("127.0.0.0".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_private_v4() {
-        // 10.0.0.0/8
-        assert!(is_ip_blocked(IpAddr::V4("10.0.0.1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4("10.255.255.255".parse().unwrap())));
-        // 172.16.0.0/12
-        assert!(is_ip_blocked(IpAddr::V4("172.16.0.0".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4("172.31.255.255".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4("172.20.0.1".parse().unwrap())));
-        // 172.15.x.x and 172.32.x.x are NOT blocked
-        assert!(!is_ip_blocked(IpAddr::V4("172.15.0.1".parse().unwrap())));
-        assert!(!is_ip_blocked(IpAddr::V4("172.32.0.1".parse().unwrap())));
-        // 192.168.0.0/16
-        assert!(is_ip_blocked(IpAddr::V4("192.168.0.1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4(
-            "192.168.255.255".parse().unwrap()
-        )));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_link_local_v4() {
-        // 169.254.0.0/16
-        assert!(is_ip_blocked(IpAddr::V4("169.254.0.0".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4(
-            "169.254.255.255".parse().unwrap()
-        )));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_carrier_nat_v4() {
-        // 100.64.0.0/10
-        assert!(is_ip_blocked(IpAddr::V4("100.64.0.1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V4(
-            "100.127.255.255".parse().unwrap()
-        )));
-        // 100.0-63 and 100.128+ are NOT blocked
-        assert!(!is_ip_blocked(IpAddr::V4("100.0.0.1".parse().unwrap())));
-        assert!(!is_ip_blocked(IpAddr::V4("100.128.0.1".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_documentation_v4() {
-        // 192.0.2.0/24 (DOC-1)
-        assert!(is_ip_blocked(IpAddr::V4("192.0.2.1".parse().unwrap())));
-        // 198.51.100.0/24 (DOC-2)
-        assert!(is_ip_blocked(IpAddr::V4("198.51.100.1".parse().unwrap())));
-        // 203.0.113.0/24 (DOC-3)
-        assert!(is_ip_blocked(IpAddr::V4("203.0.113.1".parse().unwrap())));
-        // 192.0.0.0/24 (IETF Protocol)
-        assert!(is_ip_blocked(IpAddr::V4("192.0.0.1".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_public_v4() {
-        // Public IPs should not be blocked
-        assert!(!is_ip_blocked(IpAddr::V4("8.8.8.8".parse().unwrap())));
-        assert!(!is_ip_blocked(IpAddr::V4("1.1.1.1".parse().unwrap())));
-        assert!(!is_ip_blocked(IpAddr::V4("93.184.216.34".parse().unwrap()))); // example.com
-        assert!(!is_ip_blocked(IpAddr::V4("52.94.236.248".parse().unwrap()))); // AWS
-    }
-
-    #[test]
-    fn test_is_ip_blocked_loopback_v6() {
-        // ::1/128
-        assert!(is_ip_blocked(IpAddr::V6("::1".parse().unwrap())));
-        // ::0/128 is not blocked
-        assert!(!is_ip_blocked(IpAddr::V6("::0".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_unique_local_v6() {
-        // fc00::/7
-        assert!(is_ip_blocked(IpAddr::V6("fc00::1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V6("fd00::1".parse().unwrap())));
-        // fdFF::/8 is the random local address range
-        assert!(is_ip_blocked(IpAddr::V6("fdff::1".parse().unwrap())));
-        // fe00::/7 is NOT unique local (fe00 is)
-        assert!(!is_ip_blocked(IpAddr::V6("fe00::1".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_link_local_v6() {
-        // fe80::/10
-        assert!(is_ip_blocked(IpAddr::V6("fe80::1".parse().unwrap())));
-        assert!(is_ip_blocked(IpAddr::V6(
-            "fe80:ffff:ffff:ffff::".parse().unwrap()
-        )));
-        // fe81:: is also blocked (still in fe80::/10)
-        assert!(is_ip_blocked(IpAddr::V6("fe81::1".parse().unwrap())));
-        // fe7f:: is NOT blocked (just outside fe80::/10)
-        assert!(!is_ip_blocked(IpAddr::V6("fe7f::1".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_ipv4_mapped_v6() {
-        // IPv4-mapped IPv6 addresses (::ffff:0:0/96)
-        assert!(is_ip_blocked(IpAddr::V6(
-            "::ffff:127.0.0.1".parse().unwrap()
-        )));
-        assert!(is_ip_blocked(IpAddr::V6("::ffff:0:0".parse().unwrap())));
-        // IPv4-mapped public IPs are still blocked
-        assert!(is_ip_blocked(IpAddr::V6("::ffff:8.8.8.8".parse().unwrap())));
-    }
-
-    #[test]
-    fn test_is_ip_blocked_public_v6() {
-        // Public IPv6 addresses should not be blocked
-        assert!(!is_ip_blocked(IpAddr::V6(
-            "2001:4860:4860::8888".parse().unwrap()
-        ))); // Google DNS
-        assert!(!is_ip_blocked(IpAddr::V6(
-            "2606:4700:4700::1111".parse().unwrap()
-        ))); // Cloudflare DNS
-    }
-
-    // =============================================================================
-    // URL Validation Tests (9.1.7)
-    // =============================================================================
-
-    #[test]
-    fn test_validate_url_rejects_non_https() {
-        let result = validate_url("http://example.com/config.json", &[]);
-        assert!(result.is_err());
-        let err = result.unwrap_err();
-        assert!(matches!(err, ConfigError::InvalidValue { .. }));
-    }
-
-    #[test]
-    fn test_validate_url_rejects_private_ipv4() {
-        // 127.0.0.1
-        let result = validate_url("https://127.0.0.1/config.json", &[]);
-        assert!(result.is_err());
-        // 10.x.x.x
-        let result = validate_url("https://10.0.0.1/config.json", &[]);
-        assert!(result.is_err());
-        // 192.168.x.x
-        let result = validate_url("https://192.168.1.1/config.json", &[]);
-        assert!(err_if_blocked(&result));
-        assert!(result.is_err());
-        // 172.16.x.x
-        let result = validate_url("https://172.16.0.1/config.json", &[]);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_validate_url_rejects_private_ipv6() {
-        // ::1
-        let result = validate_url("https://[::1]/config.json", &[]);
-        assert!(result.is_err());
-        // fe80:: (link-local)
-        let result = validate_url("https://[fe80::1]/config.json", &[]);
-        assert!(result.is_err());
-        // fc00:: (unique local)
-        let result = validate_url("https://[fc00::1]/config.json", &[]);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_validate_url_rejects_ipv4_mapped() {
-        // ::ffff:127.0.0.1
-        let result = validate_url("https://[::ffff:127.0.0.1]/config.json", &[]);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_validate_url_rejects_documentation_ips() {
-        let result = validate_url("https://192.0.2.1/config.json", &[]);
-        assert!(result.is_err());
-        let result = validate_url("https://198.51.100.1/config.json", &[]);
-        assert!(result.is_err());
-        let result = validate_url("https://203.0.113.1/config.json", &[]);
-        assert!(result.is_err());
-        let result = validate_url("https://192.0.0.1/config.json", &[]);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_validate_url_accepts_public_ips() {
-        // These should NOT block on IP check alone (though DNS resolution may fail)
-        // We test with an IP that won't resolve
-        let _result = validate_url("https://8.8.8.8/config.json", &[]);
-        // Should not be blocked by SSRF check (8.8.8.8 is public)
-        // The DNS resolution will fail for the IP-as-hostname case
-        // but that's a different error
-    }
-
-    #[test]
-    fn test_validate_url_whitelist_exact_match() {
-        let result = validate_url(
-            "https://internal.example.com/config.json",
-            &["internal.example.com".to_string()],
-        );
-        // T-C-1 B1: the original test had a redundant duplicate
-        // `assert!(result.is_ok())`. A single, well-messaged assertion is
-        // sufficient — the key behavior is that the whitelist accepts the
-        // exact domain. DNS resolution may return an empty IP list for
-        // example domains, so we only assert Ok here.
-        assert!(
-            result.is_ok(),
-            "whitelisted domain should be accepted: {:?}",
-            result.err()
-        );
-    }
-
-    #[test]
-    fn test_validate_url_whitelist_subdomain_match() {
-        // "example.com" in whitelist should match "api.example.com"
-        let result = validate_url(
-            "https://api.example.com/config.json",
-            &["example.com".to_string()],
-        );
-        // T-C-1 B2: removed redundant duplicate assertion. The key
-        // behavior is that the whitelist accepts subdomains.
-        assert!(
-            result.is_ok(),
-            "subdomain of whitelisted domain should be accepted: {:?}",
-            result.err()
-        );
-    }
-
-    #[test]
-    fn test_validate_url_whitelist_no_match() {
-        // Non-whitelisted domain should be rejected by SSRF check.
-        // T-C-1 D4b: old code discarded the result with `let _result = ...`.
-        let result = validate_url(
-            "https://untrusted.example.com/config.json",
-            &["trusted.example.com".to_string()],
-        );
-        assert!(
-            result.is_err(),
-            "non-whitelisted domain should be rejected: {result:?}"
-        );
-    }
-
-    #[test]
-    fn test_validate_url_whitelist_mixed() {
-        let domains = vec![
-            "internal.corp.com".to_string(),
-            "config-service.prod".to_string(),
-        ];
-        let result = validate_url("https://internal.corp.com/config.json", &domains);
-        assert!(result.is_ok());
-        let result = validate_url("https://config-service.prod/config.json", &domains);
-        assert!(result.is_ok());
-    }
-
-    // Helper for test assertions
-    fn err_if_blocked(result: &Result<Vec<IpAddr>, ConfigError>) -> bool {
-        if let Err(e) = result {
-            matches!(e, ConfigError::InvalidValue { .. })
-        } else {
-            false
-        }
-    }
-
     // =============================================================================
     // Builder Tests
     // =============================================================================
@@ -917,20 +590,64 @@ mod tests {
     }
 
     #[test]
-    fn test_resolve_host_with_validation_public() {
-        // Test with a well-known public DNS
-        // Note: This test requires network access. If it fails, the host doesn't resolve.
-        let result = resolve_host_with_validation("example.com", 443);
-        if let Ok(ips) = result {
-            assert!(!ips.is_empty());
-            for ip in &ips {
-                assert!(
-                    !is_ip_blocked(*ip),
-                    "example.com resolved to a blocked IP: {}",
-                    ip
-                );
-            }
-        }
-        // If network is unavailable, test is skipped
+    fn test_split_unix_socket_url() {
+        let (socket, request_url) =
+            split_unix_socket_url("http+unix://%2Frun%2Fagent.sock/v1/config.json?env=prod")
+                .unwrap()
+                .unwrap();
+        assert_eq!(socket, "/run/agent.sock");
+        assert_eq!(request_url, "http://localhost/v1/config.json?env=prod");
+
+        assert!(split_unix_socket_url("https://example.com/config.json")
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_unix_socket_url_needs_allowed_scheme() {
+        let result = HttpPolledSourceBuilder::new()
+            .url("http+unix://%2Frun%2Fagent.sock/config.json")
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_poll_over_unix_socket() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket = dir.path().join("agent.sock");
+        let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let n = stream.read(&mut buf).await.unwrap();
+            assert!(String::from_utf8_lossy(&buf[..n]).starts_with("GET /config.json "));
+            let body = r#"{"name": "agent"}"#;
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let encoded: String = percent_encoding::utf8_percent_encode(
+            socket.to_str().unwrap(),
+            percent_encoding::NON_ALPHANUMERIC,
+        )
+        .collect();
+        let source = HttpPolledSourceBuilder::new()
+            .url(format!("http+unix://{}/config.json", encoded))
+            .format(Format::Json)
+            .ssrf_policy(SsrfPolicy::new().allowed_schemes(["http+unix"]))
+            .build()
+            .unwrap();
+        let value = source.poll().await.unwrap();
+        assert_eq!(
+            value.get_path("name").map(|v| v.inner.clone()),
+            Some(crate::types::ConfigValue::String("agent".to_string()))
+        );
     }
 }
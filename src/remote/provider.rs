@@ -0,0 +1,507 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Pluggable remote configuration backends.
+//!
+//! [`RemoteConfigSource`] is the extension point for backends confers does not
+//! ship (ZooKeeper, Nacos, internal config APIs). Register an implementation
+//! with [`ConfigBuilder::with_remote_provider`](crate::ConfigBuilder::with_remote_provider)
+//! and build with [`ConfigBuilder::build_async`](crate::ConfigBuilder::build_async);
+//! the fetched tree is merged like any other source at the provider's priority.
+
+use async_trait::async_trait;
+
+use super::proxy::ProxyConfig;
+use crate::error::ConfigResult;
+use crate::interface::Source;
+use crate::types::{AnnotatedValue, SourceKind};
+
+/// A user-defined remote configuration backend.
+#[async_trait]
+pub trait RemoteConfigSource: Send + Sync {
+    /// Fetch the current configuration tree.
+    async fn fetch(&self) -> ConfigResult<AnnotatedValue>;
+
+    /// Wait for the next change and return the updated tree.
+    ///
+    /// Returns `Ok(None)` when the backend has no change notifications, in
+    /// which case callers poll [`fetch`](Self::fetch) instead. This is the
+    /// default.
+    async fn watch(&self) -> ConfigResult<Option<AnnotatedValue>> {
+        Ok(None)
+    }
+
+    /// Check that the backend is reachable. Defaults to a full fetch.
+    async fn health(&self) -> ConfigResult<()> {
+        self.fetch().await.map(|_| ())
+    }
+
+    /// Name of the backend, used in diagnostics.
+    fn name(&self) -> &str;
+
+    /// Priority of the fetched values (higher = more important).
+    fn priority(&self) -> u8 {
+        50
+    }
+
+    /// URLs or `host:port` addresses the backend connects to, checked
+    /// against [`ConfigBuilder::with_ssrf_policy`](crate::ConfigBuilder::with_ssrf_policy)
+    /// before fetching. Defaults to none.
+    fn endpoints(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Route the backend's HTTP requests through `proxy`. Called before
+    /// fetching when [`RemoteConfig::with_proxy`](crate::RemoteConfig::with_proxy)
+    /// is set. Defaults to ignoring it, for backends without an HTTP client.
+    fn use_proxy(&mut self, proxy: &ProxyConfig) -> ConfigResult<()> {
+        let _ = proxy;
+        Ok(())
+    }
+}
+
+/// A tree fetched from a [`RemoteConfigSource`], fed into the source chain.
+#[derive(Debug)]
+pub(crate) struct FetchedSource {
+    value: AnnotatedValue,
+    name: String,
+    priority: u8,
+}
+
+impl FetchedSource {
+    pub(crate) fn new(value: AnnotatedValue, name: impl Into<String>, priority: u8) -> Self {
+        Self {
+            value: value.with_priority(priority),
+            name: name.into(),
+            priority,
+        }
+    }
+}
+
+impl Source for FetchedSource {
+    fn collect(&self) -> ConfigResult<AnnotatedValue> {
+        Ok(self.value.clone())
+    }
+
+    fn priority(&self) -> u8 {
+        self.priority
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn source_kind(&self) -> SourceKind {
+        SourceKind::Remote
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConfigValue, SourceId};
+    use crate::ConfigBuilder;
+    use indexmap::IndexMap;
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    struct StaticProvider {
+        port: u64,
+        priority: u8,
+    }
+
+    #[async_trait]
+    impl RemoteConfigSource for StaticProvider {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            let source = SourceId::new("static");
+            let mut map = IndexMap::new();
+            map.insert(
+                Arc::from("port"),
+                AnnotatedValue::new(ConfigValue::uint(self.port), source.clone(), "port"),
+            );
+            Ok(AnnotatedValue::new(
+                ConfigValue::Map(Arc::new(map)),
+                source,
+                "",
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "static"
+        }
+
+        fn priority(&self) -> u8 {
+            self.priority
+        }
+    }
+
+    #[derive(Debug, Default, Deserialize)]
+    struct AppConfig {
+        #[serde(default)]
+        port: u64,
+    }
+
+    #[tokio::test]
+    async fn test_default_watch_and_health() {
+        let provider = StaticProvider {
+            port: 1,
+            priority: 50,
+        };
+        assert!(provider.watch().await.unwrap().is_none());
+        provider.health().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_build_async_merges_provider_by_priority() {
+        let config: AppConfig = ConfigBuilder::new()
+            .default("port", ConfigValue::uint(1))
+            .with_remote_provider(Box::new(StaticProvider {
+                port: 2,
+                priority: 50,
+            }))
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 2);
+
+        let config: AppConfig = ConfigBuilder::new()
+            .memory(std::collections::HashMap::from([(
+                "port".to_string(),
+                ConfigValue::uint(3),
+            )]))
+            .memory_priority(60)
+            .with_remote_provider(Box::new(StaticProvider {
+                port: 2,
+                priority: 50,
+            }))
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 3);
+    }
+
+    struct RolloutProvider(&'static str);
+
+    #[async_trait]
+    impl RemoteConfigSource for RolloutProvider {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            crate::loader::parse_content(
+                self.0,
+                crate::loader::Format::Json,
+                SourceId::new("rollout"),
+                None,
+            )
+        }
+
+        fn name(&self) -> &str {
+            "rollout"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_async_applies_and_strips_rollout_stanza() {
+        #[derive(Debug, Default, Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Strict {
+            port: u64,
+        }
+
+        let config: Strict = ConfigBuilder::new()
+            .with_remote_provider(Box::new(RolloutProvider(
+                r#"{"port": 2, "__rollout": {"percentage": 0, "delay_secs": 600}}"#,
+            )))
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 2);
+    }
+
+    #[tokio::test]
+    async fn test_build_async_rejects_invalid_rollout_stanza() {
+        let err = ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(RolloutProvider(
+                r#"{"port": 2, "__rollout": {"percentage": 101}}"#,
+            )))
+            .build_async()
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(&err, crate::error::ConfigError::InvalidValue { key, .. } if key == "__rollout.percentage"),
+            "got {:?}",
+            err
+        );
+    }
+
+    #[tokio::test]
+    async fn test_remote_checksum_rejects_changed_payloads() {
+        let pinned = crate::remote::payload_checksum(
+            &StaticProvider {
+                port: 2,
+                priority: 50,
+            }
+            .fetch()
+            .await
+            .unwrap(),
+        );
+        let build = |port| {
+            ConfigBuilder::<AppConfig>::new()
+                .with_remote_provider(Box::new(StaticProvider { port, priority: 50 }))
+                .with_remote_checksum("static", pinned.clone())
+                .build_async()
+        };
+
+        assert_eq!(build(2).await.unwrap().port, 2);
+        let err = build(3).await.unwrap_err();
+        assert!(
+            matches!(&err, crate::error::ConfigError::ChecksumMismatch { source_name, .. } if source_name == "static"),
+            "got {:?}",
+            err
+        );
+    }
+
+    struct SlowProvider {
+        port: u64,
+        delay: std::time::Duration,
+    }
+
+    #[async_trait]
+    impl RemoteConfigSource for SlowProvider {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            tokio::time::sleep(self.delay).await;
+            StaticProvider {
+                port: self.port,
+                priority: 50,
+            }
+            .fetch()
+            .await
+        }
+
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn priority(&self) -> u8 {
+            50
+        }
+    }
+
+    #[tokio::test]
+    async fn test_build_async_fetches_providers_concurrently_in_order() {
+        let delay = std::time::Duration::from_millis(200);
+        let started = std::time::Instant::now();
+        let config: AppConfig = ConfigBuilder::new()
+            .with_remote_provider(Box::new(SlowProvider { port: 1, delay }))
+            .with_remote_provider(Box::new(SlowProvider {
+                port: 2,
+                delay: std::time::Duration::ZERO,
+            }))
+            .with_remote_provider(Box::new(SlowProvider { port: 3, delay }))
+            .build_async()
+            .await
+            .unwrap();
+        assert!(started.elapsed() < delay * 2, "{:?}", started.elapsed());
+        // Equal priorities merge in registration order, not completion order.
+        assert_eq!(config.port, 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_load_deadline_abandons_slow_providers() {
+        let build = |remote_config| {
+            ConfigBuilder::<AppConfig>::new()
+                .with_remote_provider(Box::new(StaticProvider {
+                    port: 1,
+                    priority: 50,
+                }))
+                .with_remote_provider(Box::new(SlowProvider {
+                    port: 2,
+                    delay: std::time::Duration::from_secs(3600),
+                }))
+                .with_remote_config(remote_config)
+                .with_load_deadline(std::time::Duration::from_secs(2))
+                .build_async()
+        };
+
+        let err = build(crate::remote::RemoteConfig::new()).await.unwrap_err();
+        assert!(
+            matches!(
+                err,
+                crate::error::ConfigError::Timeout { duration_ms: 2000 }
+            ),
+            "got {:?}",
+            err
+        );
+
+        let started = tokio::time::Instant::now();
+        let config = build(
+            crate::remote::RemoteConfig::new()
+                .provider_degradation("slow", crate::remote::DegradationPolicy::Skip),
+        )
+        .await
+        .unwrap();
+        assert_eq!(config.port, 1);
+        assert_eq!(started.elapsed(), std::time::Duration::from_secs(2));
+    }
+
+    #[cfg(feature = "audit")]
+    #[tokio::test(start_paused = true)]
+    async fn test_missed_load_deadline_is_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            crate::audit::AuditWriter::builder()
+                .log_dir(dir.path().to_path_buf())
+                .build(),
+        );
+        ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(SlowProvider {
+                port: 2,
+                delay: std::time::Duration::from_secs(3600),
+            }))
+            .with_remote_config(
+                crate::remote::RemoteConfig::new()
+                    .degradation(crate::remote::DegradationPolicy::Skip),
+            )
+            .with_load_deadline(std::time::Duration::from_millis(500))
+            .with_audit(audit)
+            .build_async()
+            .await
+            .unwrap();
+
+        let log = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|e| std::fs::read_to_string(e.unwrap().path()).unwrap())
+            .collect::<String>();
+        assert!(log.contains("\"event\":\"LoadDeadlineExceeded\""), "{log}");
+        assert!(log.contains("\"deadline_ms\":500"), "{log}");
+        assert!(log.contains("ProviderDegraded"), "{log}");
+    }
+
+    struct LocalProvider;
+
+    #[async_trait]
+    impl RemoteConfigSource for LocalProvider {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            StaticProvider {
+                port: 4,
+                priority: 50,
+            }
+            .fetch()
+            .await
+        }
+
+        fn name(&self) -> &str {
+            "local"
+        }
+
+        fn endpoints(&self) -> Vec<String> {
+            vec!["127.0.0.1:8848".to_string()]
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_check_reports_remote_providers() {
+        let mut builder = ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(LocalProvider))
+            .with_remote_provider(Box::new(SlowProvider {
+                port: 1,
+                delay: std::time::Duration::from_secs(5),
+            }))
+            .with_remote_config(
+                crate::remote::RemoteConfig::new()
+                    .provider_timeout("slow", std::time::Duration::from_millis(20)),
+            );
+        let report = builder.health_check().await;
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0].name, "local");
+        assert!(report[0].healthy, "{:?}", report[0]);
+        assert_eq!(report[0].kind, crate::ProviderKind::Remote);
+        assert!(!report[1].healthy);
+        assert!(report[1].message.contains("20ms"), "{}", report[1].message);
+
+        let report = builder
+            .with_ssrf_policy(crate::remote::SsrfPolicy::new())
+            .health_check()
+            .await;
+        assert!(!report[0].healthy);
+    }
+
+    #[tokio::test]
+    async fn test_ssrf_policy_checks_provider_endpoints() {
+        let result = ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(LocalProvider))
+            .with_ssrf_policy(crate::remote::SsrfPolicy::new())
+            .build_async()
+            .await;
+        assert!(result.is_err());
+
+        let config = ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(LocalProvider))
+            .with_ssrf_policy(
+                crate::remote::SsrfPolicy::new()
+                    .allowed_schemes(["http"])
+                    .allow_network("127.0.0.1/32".parse().unwrap()),
+            )
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 4);
+    }
+
+    /// Reports port 1 until it is handed a proxy.
+    #[derive(Default)]
+    struct ProxiedProvider {
+        proxy: Option<String>,
+    }
+
+    #[async_trait]
+    impl RemoteConfigSource for ProxiedProvider {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            let port = if self.proxy.is_some() { 2 } else { 1 };
+            StaticProvider { port, priority: 50 }.fetch().await
+        }
+
+        fn name(&self) -> &str {
+            "proxied"
+        }
+
+        fn use_proxy(&mut self, proxy: &ProxyConfig) -> ConfigResult<()> {
+            self.proxy = Some(format!("{:?}", proxy));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_config_proxy_is_passed_to_providers() {
+        let config: AppConfig = ConfigBuilder::new()
+            .with_remote_provider(Box::new(ProxiedProvider::default()))
+            .with_remote_config(crate::RemoteConfig::new())
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 1);
+
+        let config: AppConfig = ConfigBuilder::new()
+            .with_remote_provider(Box::new(ProxiedProvider::default()))
+            .with_remote_config(
+                crate::RemoteConfig::new()
+                    .with_proxy("socks5h://proxy.corp:1080")
+                    .no_proxy("localhost"),
+            )
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(config.port, 2);
+    }
+
+    #[test]
+    fn test_sync_build_rejects_remote_providers() {
+        let result = ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(Box::new(StaticProvider {
+                port: 2,
+                priority: 50,
+            }))
+            .build();
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,827 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Redis remote configuration source.
+//!
+//! The source reads a single Redis key. The key's type selects the layout:
+//!
+//! - a **hash** maps each field to a configuration key. Dotted field names
+//!   (`db.port`) become nested keys, and values are typed the same way as
+//!   environment variables (`true`, `42`, `1.5`, otherwise a string). This
+//!   layout suits feature flags toggled with `HSET`.
+//! - a **string** holds a whole document. Its format is set on the builder or
+//!   detected from the content.
+//!
+//! Updates are picked up by polling, or by subscribing with
+//! [`RedisSourceBuilder::subscribe`]: either to the key's keyspace
+//! notifications (the server needs `notify-keyspace-events` to include `K` and
+//! the relevant event classes, e.g. `Kh$`) or to a pub/sub channel the writer
+//! publishes to after each change.
+//!
+//! `rediss://` URLs connect over TLS (rustls). Redis 6 ACL users authenticate
+//! with [`RedisSourceBuilder::credentials`].
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::impl_::config::EnvSource;
+use crate::loader::{detect_format_from_content, parse_content, Format};
+use crate::remote::RemoteConfigSource;
+use crate::types::{AnnotatedValue, ConfigValue, SourceId};
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use indexmap::IndexMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default poll interval for Redis (30 seconds).
+pub const DEFAULT_REDIS_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default maximum size of the configuration value in bytes (16 MB).
+pub const DEFAULT_MAX_REDIS_VALUE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Default maximum number of hash fields (10,000).
+pub const DEFAULT_MAX_REDIS_HASH_FIELDS: usize = 10_000;
+
+/// Change notifications a [`RedisSource`] subscribes to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedisSubscription {
+    /// Keyspace notifications for the configured key (`__keyspace@<db>__:<key>`).
+    Keyspace,
+    /// A pub/sub channel; any message on it triggers a re-read.
+    Channel(String),
+}
+
+/// TLS settings for `rediss://` connections.
+#[derive(Debug, Clone, Default)]
+pub struct RedisTlsConfig {
+    /// PEM-encoded root certificate. The system trust store is used when unset.
+    pub ca_cert: Option<Vec<u8>>,
+    /// PEM-encoded client certificate and private key for mutual TLS.
+    pub client_cert: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+/// Builder for creating Redis configuration sources.
+pub struct RedisSourceBuilder {
+    url: String,
+    key: String,
+    format: Option<Format>,
+    credentials: Option<(String, String)>,
+    tls: Option<RedisTlsConfig>,
+    tls_skip_verify: bool,
+    subscription: Option<RedisSubscription>,
+    interval: Option<Duration>,
+    connect_timeout: Duration,
+    max_value_bytes: usize,
+    max_hash_fields: usize,
+}
+
+impl RedisSourceBuilder {
+    /// Create a new Redis source builder.
+    pub fn new() -> Self {
+        Self {
+            url: "redis://127.0.0.1:6379".to_string(),
+            key: "config".to_string(),
+            format: None,
+            credentials: None,
+            tls: None,
+            tls_skip_verify: false,
+            subscription: None,
+            interval: None,
+            connect_timeout: Duration::from_secs(10),
+            max_value_bytes: DEFAULT_MAX_REDIS_VALUE_BYTES,
+            max_hash_fields: DEFAULT_MAX_REDIS_HASH_FIELDS,
+        }
+    }
+
+    /// Set the server URL (`redis://host:port/db` or `rediss://` for TLS).
+    pub fn url(mut self, url: impl Into<String>) -> Self {
+        self.url = url.into();
+        self
+    }
+
+    /// Set the key holding the configuration.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// Set the format of a string value. Detected from the content when unset.
+    pub fn format(mut self, format: Format) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Authenticate as an ACL user.
+    ///
+    /// Overrides credentials in the URL. Use `"default"` as the username
+    /// for servers that only have `requirepass`.
+    pub fn credentials(mut self, username: impl Into<String>, password: impl Into<String>) -> Self {
+        self.credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Set custom TLS certificates. Only used with `rediss://` URLs.
+    pub fn tls(mut self, tls: RedisTlsConfig) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+
+    /// Skip TLS verification (for development only).
+    ///
+    /// This option is only effective in debug builds.
+    /// In release builds, TLS verification is always enforced for security.
+    pub fn tls_skip_verify(mut self, skip: bool) -> Self {
+        self.tls_skip_verify = cfg!(debug_assertions) && skip;
+        self
+    }
+
+    /// Subscribe to change notifications, used by [`RemoteConfigSource::watch`].
+    pub fn subscribe(mut self, subscription: RedisSubscription) -> Self {
+        self.subscription = Some(subscription);
+        self
+    }
+
+    /// Set the poll interval.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Set the timeout for establishing a connection.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Set the maximum size of the configuration value in bytes.
+    pub fn max_value_bytes(mut self, bytes: usize) -> Self {
+        self.max_value_bytes = bytes;
+        self
+    }
+
+    /// Set the maximum number of hash fields accepted.
+    pub fn max_hash_fields(mut self, fields: usize) -> Self {
+        self.max_hash_fields = fields;
+        self
+    }
+
+    /// Build the Redis source.
+    pub fn build(self) -> ConfigResult<RedisSource> {
+        let mut url = url::Url::parse(&self.url).map_err(|e| ConfigError::InvalidValue {
+            key: "redis.url".to_string(),
+            expected_type: "redis:// or rediss:// URL".to_string(),
+            message: e.to_string(),
+        })?;
+        let tls = match url.scheme() {
+            "redis" => false,
+            "rediss" => true,
+            scheme => {
+                return Err(ConfigError::InvalidValue {
+                    key: "redis.url".to_string(),
+                    expected_type: "redis:// or rediss:// URL".to_string(),
+                    message: format!("Unsupported scheme '{}'", scheme),
+                })
+            }
+        };
+        if self.key.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                key: "redis.key".to_string(),
+                expected_type: "non-empty key".to_string(),
+                message: "The configuration key must not be empty".to_string(),
+            });
+        }
+        if let Some((username, password)) = &self.credentials {
+            let invalid = |_| ConfigError::InvalidValue {
+                key: "redis.url".to_string(),
+                expected_type: "URL with a host".to_string(),
+                message: "Cannot set credentials on this URL".to_string(),
+            };
+            url.set_username(username).map_err(invalid)?;
+            url.set_password(Some(password)).map_err(invalid)?;
+        }
+        // The client reads `#insecure` from the URL, so only the builder
+        // may set it.
+        url.set_fragment(None);
+        if tls && self.tls_skip_verify {
+            url.set_fragment(Some("insecure"));
+        }
+        let db = url
+            .path()
+            .trim_start_matches('/')
+            .parse::<i64>()
+            .unwrap_or(0);
+
+        let client = match (&self.tls, tls) {
+            (Some(config), true) => {
+                install_crypto_provider();
+                ::redis::Client::build_with_tls(
+                    url.as_str(),
+                    ::redis::TlsCertificates {
+                        client_tls: config.client_cert.as_ref().map(|(cert, key)| {
+                            ::redis::ClientTlsConfig {
+                                client_cert: cert.clone(),
+                                client_key: key.clone(),
+                            }
+                        }),
+                        root_cert: config.ca_cert.clone(),
+                    },
+                )
+            }
+            (_, tls) => {
+                if tls {
+                    install_crypto_provider();
+                }
+                ::redis::Client::open(url.as_str())
+            }
+        }
+        .map_err(|e| redis_error("client", &e))?;
+
+        let channel = self.subscription.map(|subscription| match subscription {
+            RedisSubscription::Keyspace => format!("__keyspace@{}__:{}", db, self.key),
+            RedisSubscription::Channel(channel) => channel,
+        });
+
+        Ok(RedisSource {
+            client,
+            key: Arc::from(self.key),
+            format: self.format,
+            channel,
+            interval: self.interval.unwrap_or(DEFAULT_REDIS_POLL_INTERVAL),
+            connect_timeout: self.connect_timeout,
+            max_value_bytes: self.max_value_bytes,
+            max_hash_fields: self.max_hash_fields,
+            pubsub: Mutex::new(None),
+            subscribed: AtomicBool::new(false),
+        })
+    }
+}
+
+impl Default for RedisSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Redis-backed configuration source.
+pub struct RedisSource {
+    client: ::redis::Client,
+    key: Arc<str>,
+    format: Option<Format>,
+    channel: Option<String>,
+    interval: Duration,
+    connect_timeout: Duration,
+    max_value_bytes: usize,
+    max_hash_fields: usize,
+    pubsub: Mutex<Option<::redis::aio::PubSub>>,
+    subscribed: AtomicBool,
+}
+
+impl RedisSource {
+    /// Get the source identifier.
+    pub fn source_id(&self) -> SourceId {
+        SourceId::new(format!("redis:{}", self.key))
+    }
+
+    /// Channel subscribed to by [`RemoteConfigSource::watch`], if any.
+    pub fn channel(&self) -> Option<&str> {
+        self.channel.as_deref()
+    }
+
+    async fn fetch_internal(&self) -> ConfigResult<AnnotatedValue> {
+        let mut conn = tokio::time::timeout(
+            self.connect_timeout,
+            self.client.get_multiplexed_async_connection(),
+        )
+        .await
+        .map_err(|_| ConfigError::RemoteUnavailable {
+            error_type: "redis_connection: timed out".to_string(),
+            retryable: true,
+        })?
+        .map_err(|e| redis_error("connection", &e))?;
+
+        let kind: String = ::redis::cmd("TYPE")
+            .arg(&*self.key)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| redis_error("type", &e))?;
+
+        match kind.as_str() {
+            "hash" => {
+                let flat: Vec<String> = ::redis::cmd("HGETALL")
+                    .arg(&*self.key)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(|e| redis_error("hgetall", &e))?;
+                self.parse_hash(flat)
+            }
+            "string" => {
+                let content: Vec<u8> = ::redis::cmd("GET")
+                    .arg(&*self.key)
+                    .query_async(&mut conn)
+                    .await
+                    .map_err(|e| redis_error("get", &e))?;
+                self.parse_blob(content)
+            }
+            "none" => Err(ConfigError::InvalidValue {
+                key: "redis".to_string(),
+                expected_type: "hash or string".to_string(),
+                message: format!("Key '{}' does not exist", self.key),
+            }),
+            other => Err(ConfigError::InvalidValue {
+                key: "redis".to_string(),
+                expected_type: "hash or string".to_string(),
+                message: format!("Key '{}' has unsupported type '{}'", self.key, other),
+            }),
+        }
+    }
+
+    /// Map hash fields (`[field, value, field, value, ...]`) to nested keys.
+    fn parse_hash(&self, flat: Vec<String>) -> ConfigResult<AnnotatedValue> {
+        let fields = flat.len() / 2;
+        if fields > self.max_hash_fields {
+            return Err(ConfigError::SizeLimitExceeded {
+                actual: fields,
+                limit: self.max_hash_fields,
+            });
+        }
+        let bytes = flat.iter().map(String::len).sum::<usize>();
+        if bytes > self.max_value_bytes {
+            return Err(ConfigError::SizeLimitExceeded {
+                actual: bytes,
+                limit: self.max_value_bytes,
+            });
+        }
+
+        let source = self.source_id();
+        let mut pairs: Vec<(String, String)> = flat
+            .chunks_exact(2)
+            .map(|pair| (pair[0].clone(), pair[1].clone()))
+            .collect();
+        pairs.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut map = IndexMap::new();
+        for (field, value) in pairs {
+            let parts: Vec<&str> = field.split('.').filter(|p| !p.is_empty()).collect();
+            let value = AnnotatedValue::new(
+                EnvSource::infer_config_value(&value),
+                source.clone(),
+                field.as_str(),
+            );
+            EnvSource::insert_nested(&mut map, &parts, value);
+        }
+        Ok(AnnotatedValue::new(
+            ConfigValue::Map(Arc::new(map)),
+            source,
+            "",
+        ))
+    }
+
+    /// Parse a string value holding a whole document.
+    fn parse_blob(&self, content: Vec<u8>) -> ConfigResult<AnnotatedValue> {
+        if content.len() > self.max_value_bytes {
+            return Err(ConfigError::SizeLimitExceeded {
+                actual: content.len(),
+                limit: self.max_value_bytes,
+            });
+        }
+        let content = String::from_utf8(content).map_err(|_| ConfigError::InvalidValue {
+            key: "redis".to_string(),
+            expected_type: "UTF-8 text".to_string(),
+            message: format!("Key '{}' is not valid UTF-8", self.key),
+        })?;
+        let format = self
+            .format
+            .or_else(|| detect_format_from_content(&content))
+            .ok_or_else(|| ConfigError::ParseError {
+                format: "unknown".to_string(),
+                message: format!("Cannot detect the format of key '{}'", self.key),
+                location: None,
+                source: None,
+            })?;
+        parse_content(&content, format, self.source_id(), None)
+    }
+
+    /// Subscribe to the notification channel.
+    async fn subscribe(&self, channel: &str) -> ConfigResult<::redis::aio::PubSub> {
+        let mut pubsub = tokio::time::timeout(self.connect_timeout, self.client.get_async_pubsub())
+            .await
+            .map_err(|_| ConfigError::RemoteUnavailable {
+                error_type: "redis_pubsub: timed out".to_string(),
+                retryable: true,
+            })?
+            .map_err(|e| redis_error("pubsub", &e))?;
+        pubsub
+            .subscribe(channel)
+            .await
+            .map_err(|e| redis_error("subscribe", &e))?;
+        Ok(pubsub)
+    }
+}
+
+/// rustls needs a process-wide crypto provider when more than one is compiled in.
+fn install_crypto_provider() {
+    let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+}
+
+fn redis_error(context: &str, err: &::redis::RedisError) -> ConfigError {
+    ConfigError::RemoteUnavailable {
+        error_type: format!("redis_{}: {}", context, err),
+        retryable: err.is_io_error() || err.is_timeout() || err.is_connection_dropped(),
+    }
+}
+
+#[async_trait]
+impl crate::remote::PolledSource for RedisSource {
+    async fn poll(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn source_id(&self) -> SourceId {
+        Self::source_id(self)
+    }
+}
+
+#[async_trait]
+impl crate::interface::AsyncSource for RedisSource {
+    async fn load(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn source_id(&self) -> &SourceId {
+        static SOURCE_ID: std::sync::OnceLock<SourceId> = std::sync::OnceLock::new();
+        SOURCE_ID.get_or_init(|| SourceId::new("redis"))
+    }
+
+    fn name(&self) -> &str {
+        "redis"
+    }
+}
+
+#[async_trait]
+impl RemoteConfigSource for RedisSource {
+    async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    /// Wait for a notification on the subscribed channel, then re-read the key.
+    ///
+    /// Returns `Ok(None)` without a subscription. The subscription is made on
+    /// the first call; if it drops, it is re-established and the key re-read
+    /// right away, since notifications may have been missed in between.
+    async fn watch(&self) -> ConfigResult<Option<AnnotatedValue>> {
+        let Some(channel) = &self.channel else {
+            return Ok(None);
+        };
+
+        let mut guard = self.pubsub.lock().await;
+        if guard.is_none() {
+            let resubscribing = self.subscribed.swap(true, Ordering::Relaxed);
+            *guard = Some(self.subscribe(channel).await?);
+            if resubscribing {
+                drop(guard);
+                return self.fetch_internal().await.map(Some);
+            }
+        }
+
+        let pubsub = guard.as_mut().expect("subscribed");
+        if pubsub.on_message().next().await.is_none() {
+            // Connection dropped: subscribe again before re-reading.
+            *guard = None;
+            *guard = Some(self.subscribe(channel).await?);
+        }
+        drop(guard);
+        self.fetch_internal().await.map(Some)
+    }
+
+    fn name(&self) -> &str {
+        "redis"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::{TcpListener, TcpStream};
+
+    #[derive(Clone)]
+    enum Entry {
+        Hash(Vec<(&'static str, &'static str)>),
+        Text(&'static str),
+    }
+
+    type Store = Arc<std::sync::Mutex<HashMap<String, Entry>>>;
+    type Subscribers = Arc<std::sync::Mutex<Vec<(String, TcpStream)>>>;
+
+    fn bulk(out: &mut Vec<u8>, value: &str) {
+        out.extend_from_slice(format!("${}\r\n{}\r\n", value.len(), value).as_bytes());
+    }
+
+    fn read_command(reader: &mut BufReader<TcpStream>) -> Option<Vec<String>> {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let count: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+        let mut args = Vec::with_capacity(count);
+        for _ in 0..count {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+            let mut data = vec![0u8; len + 2];
+            std::io::Read::read_exact(reader, &mut data).ok()?;
+            data.truncate(len);
+            args.push(String::from_utf8(data).ok()?);
+        }
+        Some(args)
+    }
+
+    /// Minimal RESP2 server answering the commands the source sends.
+    fn serve(store: Store, subscribers: Subscribers, password: Option<&'static str>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { break };
+                let store = store.clone();
+                let subscribers = subscribers.clone();
+                std::thread::spawn(move || {
+                    let mut writer = stream.try_clone().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    let mut authed = password.is_none();
+                    while let Some(args) = read_command(&mut reader) {
+                        let mut out = Vec::new();
+                        let command = args[0].to_ascii_uppercase();
+                        let entry = args
+                            .get(1)
+                            .and_then(|k| store.lock().unwrap().get(k).cloned());
+                        match command.as_str() {
+                            "AUTH" => {
+                                if args.len() == 3
+                                    && args[1] == "app"
+                                    && Some(args[2].as_str()) == password
+                                {
+                                    authed = true;
+                                    out.extend_from_slice(b"+OK\r\n");
+                                } else {
+                                    out.extend_from_slice(
+                                        b"-WRONGPASS invalid username-password pair\r\n",
+                                    );
+                                }
+                            }
+                            _ if !authed => {
+                                out.extend_from_slice(b"-NOAUTH Authentication required.\r\n")
+                            }
+                            "TYPE" => out.extend_from_slice(match entry {
+                                Some(Entry::Hash(_)) => b"+hash\r\n",
+                                Some(Entry::Text(_)) => b"+string\r\n",
+                                None => b"+none\r\n",
+                            }),
+                            "HGETALL" => {
+                                let Some(Entry::Hash(fields)) = entry else {
+                                    unreachable!()
+                                };
+                                out.extend_from_slice(
+                                    format!("*{}\r\n", fields.len() * 2).as_bytes(),
+                                );
+                                for (field, value) in fields {
+                                    bulk(&mut out, field);
+                                    bulk(&mut out, value);
+                                }
+                            }
+                            "GET" => {
+                                let Some(Entry::Text(text)) = entry else {
+                                    unreachable!()
+                                };
+                                bulk(&mut out, text);
+                            }
+                            "SUBSCRIBE" => {
+                                out.extend_from_slice(b"*3\r\n");
+                                bulk(&mut out, "subscribe");
+                                bulk(&mut out, &args[1]);
+                                out.extend_from_slice(b":1\r\n");
+                                subscribers
+                                    .lock()
+                                    .unwrap()
+                                    .push((args[1].clone(), writer.try_clone().unwrap()));
+                            }
+                            _ => out.extend_from_slice(b"+OK\r\n"),
+                        }
+                        if writer.write_all(&out).is_err() {
+                            break;
+                        }
+                    }
+                });
+            }
+        });
+        format!("redis://{}", addr)
+    }
+
+    fn publish(subscribers: &Subscribers, channel: &str, payload: &str) -> bool {
+        let mut out = b"*3\r\n".to_vec();
+        bulk(&mut out, "message");
+        bulk(&mut out, channel);
+        bulk(&mut out, payload);
+        let mut subscribers = subscribers.lock().unwrap();
+        let mut sent = false;
+        for (subscribed, stream) in subscribers.iter_mut() {
+            if subscribed == channel {
+                sent |= stream.write_all(&out).is_ok();
+            }
+        }
+        sent
+    }
+
+    fn get<'a>(value: &'a AnnotatedValue, path: &str) -> Option<&'a ConfigValue> {
+        path.split('.')
+            .try_fold(value, |node, key| match &node.inner {
+                ConfigValue::Map(map) => map.get(key),
+                _ => None,
+            })
+            .map(|node| &node.inner)
+    }
+
+    fn store(entries: &[(&str, Entry)]) -> Store {
+        Arc::new(std::sync::Mutex::new(
+            entries
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.clone()))
+                .collect(),
+        ))
+    }
+
+    #[test]
+    fn test_builder_validates_url_and_key() {
+        assert!(RedisSourceBuilder::new()
+            .url("http://localhost")
+            .build()
+            .is_err());
+        assert!(RedisSourceBuilder::new().key("").build().is_err());
+
+        let source = RedisSourceBuilder::new()
+            .url("redis://127.0.0.1:6379/2")
+            .key("flags")
+            .subscribe(RedisSubscription::Keyspace)
+            .build()
+            .unwrap();
+        assert_eq!(source.channel(), Some("__keyspace@2__:flags"));
+        assert_eq!(source.source_id().as_str(), "redis:flags");
+    }
+
+    #[test]
+    fn test_insecure_fragment_in_url_is_ignored() {
+        let insecure = |builder: RedisSourceBuilder| match builder
+            .build()
+            .unwrap()
+            .client
+            .get_connection_info()
+            .addr()
+        {
+            ::redis::ConnectionAddr::TcpTls { insecure, .. } => *insecure,
+            addr => panic!("expected a TLS address, got {:?}", addr),
+        };
+        let builder = || RedisSourceBuilder::new().url("rediss://127.0.0.1:6380/#insecure");
+
+        assert!(!insecure(builder()));
+        assert_eq!(
+            insecure(builder().tls_skip_verify(true)),
+            cfg!(debug_assertions)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_hash_fields_become_nested_typed_keys() {
+        let url = serve(
+            store(&[(
+                "flags",
+                Entry::Hash(vec![
+                    ("features.new_ui", "true"),
+                    ("features.rollout", "25"),
+                    ("name", "demo"),
+                ]),
+            )]),
+            Default::default(),
+            None,
+        );
+        let source = RedisSourceBuilder::new()
+            .url(url)
+            .key("flags")
+            .build()
+            .unwrap();
+
+        let value = source.fetch().await.unwrap();
+        assert_eq!(
+            get(&value, "features.new_ui"),
+            Some(&ConfigValue::Bool(true))
+        );
+        assert_eq!(get(&value, "features.rollout"), Some(&ConfigValue::I64(25)));
+        assert_eq!(get(&value, "name"), Some(&ConfigValue::string("demo")));
+    }
+
+    #[cfg(feature = "json")]
+    #[tokio::test]
+    async fn test_string_value_is_parsed_as_document() {
+        let url = serve(
+            store(&[("app", Entry::Text(r#"{"server": {"port": 8080}}"#))]),
+            Default::default(),
+            None,
+        );
+        let source = RedisSourceBuilder::new()
+            .url(url)
+            .key("app")
+            .build()
+            .unwrap();
+
+        let value = source.fetch().await.unwrap();
+        assert!(get(&value, "server.port").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_acl_credentials_are_sent() {
+        let url = serve(
+            store(&[("flags", Entry::Hash(vec![("on", "1")]))]),
+            Default::default(),
+            Some("secret"),
+        );
+        let denied = RedisSourceBuilder::new()
+            .url(url.clone())
+            .key("flags")
+            .build()
+            .unwrap();
+        assert!(denied.fetch().await.is_err());
+
+        let source = RedisSourceBuilder::new()
+            .url(url)
+            .key("flags")
+            .credentials("app", "secret")
+            .build()
+            .unwrap();
+        let value = source.fetch().await.unwrap();
+        assert_eq!(get(&value, "on"), Some(&ConfigValue::I64(1)));
+    }
+
+    #[tokio::test]
+    async fn test_missing_key_is_an_error() {
+        let url = serve(store(&[]), Default::default(), None);
+        let source = RedisSourceBuilder::new()
+            .url(url)
+            .key("absent")
+            .build()
+            .unwrap();
+        let err = source.fetch().await.unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_watch_without_subscription_returns_none() {
+        let source = RedisSourceBuilder::new().build().unwrap();
+        assert!(source.watch().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_watch_rereads_after_channel_message() {
+        let data = store(&[("flags", Entry::Hash(vec![("new_ui", "false")]))]);
+        let subscribers = Subscribers::default();
+        let url = serve(data.clone(), subscribers.clone(), None);
+        let source = Arc::new(
+            RedisSourceBuilder::new()
+                .url(url)
+                .key("flags")
+                .subscribe(RedisSubscription::Channel("config-updates".to_string()))
+                .build()
+                .unwrap(),
+        );
+
+        let watcher = tokio::spawn({
+            let source = source.clone();
+            async move { source.watch().await }
+        });
+
+        // Publish once the watcher has subscribed.
+        data.lock()
+            .unwrap()
+            .insert("flags".to_string(), Entry::Hash(vec![("new_ui", "true")]));
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while !publish(&subscribers, "config-updates", "changed") {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "watcher never subscribed"
+            );
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let value = tokio::time::timeout(Duration::from_secs(5), watcher)
+            .await
+            .expect("watch should wake up")
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(get(&value, "new_ui"), Some(&ConfigValue::Bool(true)));
+    }
+}
@@ -0,0 +1,754 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Retry, timeout and circuit breaker policy for remote providers.
+//!
+//! [`RemoteConfig`] describes how remote fetches are made resilient:
+//!
+//! - failed fetches are retried with exponential backoff and jitter
+//!   ([`RetryPolicy`]);
+//! - each attempt is bounded by a timeout, which can be overridden per
+//!   provider name;
+//! - a [`CircuitBreaker`] per provider opens after repeated failed fetches.
+//!   While it is open, fetches fail immediately without contacting the
+//!   backend, and [`ConfigBuilder::build_async`](crate::ConfigBuilder::build_async)
+//!   skips the provider. After the open period one trial fetch is let through.
+//!
+//! Breaker state is shared by clones of a `RemoteConfig`, so reusing it across
+//! builds (e.g. on reload) keeps a failing provider skipped.
+//!
+//! When a provider still cannot be fetched and has no usable cached payload,
+//! its [`DegradationPolicy`] decides whether the build fails, continues
+//! without it, or uses fallback values in its place.
+//!
+//! An explicit outbound proxy set with [`RemoteConfig::with_proxy`] is handed
+//! to every provider before it is fetched.
+//!
+//! The [`PollPolicy`] of a `RemoteConfig` (jitter, minimum interval,
+//! conditional requests) is meant for the [`RemoteWatcher`](super::RemoteWatcher)s
+//! of the same application.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::remote::{PollPolicy, ProxyConfig, RemoteConfigSource};
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// Exponential backoff with jitter between retries of a failed fetch.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt (0 disables retrying).
+    pub max_retries: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    /// Upper bound for a single delay.
+    pub max_backoff: Duration,
+    /// Factor the delay grows by per retry.
+    pub multiplier: f64,
+    /// Fraction of each delay that is randomized (0.0 to 1.0).
+    pub jitter: f64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(10),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries.
+    pub fn none() -> Self {
+        Self {
+            max_retries: 0,
+            ..Self::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (starting at 0).
+    ///
+    /// The backoff grows by `multiplier` per retry up to `max_backoff`; the
+    /// jitter then subtracts a random part of up to `jitter` of it.
+    pub fn delay(&self, retry: u32) -> Duration {
+        let base = self.initial_backoff.as_secs_f64() * self.multiplier.max(1.0).powi(retry as i32);
+        let base = base.min(self.max_backoff.as_secs_f64());
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        let random = RandomState::new().build_hasher().finish() as f64 / u64::MAX as f64;
+        Duration::from_secs_f64(base * (1.0 - jitter * random))
+    }
+}
+
+/// When a provider's circuit opens and for how long.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failed fetches that open the circuit.
+    pub failure_threshold: u32,
+    /// How long the circuit stays open before a trial fetch.
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+/// State of a [`CircuitBreaker`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    /// Fetches go through.
+    Closed,
+    /// Fetches fail immediately.
+    Open,
+    /// The open period has elapsed; the next fetch decides.
+    HalfOpen,
+}
+
+/// Circuit breaker guarding one provider.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    state: Mutex<BreakerState>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerState {
+    failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Create a closed circuit breaker.
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BreakerState::default()),
+        }
+    }
+
+    /// Current state.
+    pub fn state(&self) -> CircuitState {
+        let state = self.lock();
+        match state.opened_at {
+            None => CircuitState::Closed,
+            Some(at) if at.elapsed() < self.config.open_duration => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a fetch may be attempted.
+    pub fn allows(&self) -> bool {
+        self.state() != CircuitState::Open
+    }
+
+    /// Record a successful fetch, closing the circuit.
+    pub fn record_success(&self) {
+        *self.lock() = BreakerState::default();
+    }
+
+    /// Record a failed fetch. Opens the circuit at the threshold, or again
+    /// right away if the trial fetch of a half-open circuit failed.
+    pub fn record_failure(&self) {
+        let mut state = self.lock();
+        state.failures = state.failures.saturating_add(1);
+        if state.opened_at.is_some() || state.failures >= self.config.failure_threshold.max(1) {
+            state.opened_at = Some(Instant::now());
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BreakerState> {
+        // The state is two plain counters; a panic mid-update cannot corrupt it.
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// What [`ConfigBuilder::build_async`](crate::ConfigBuilder::build_async)
+/// does when a provider cannot be fetched and has no usable cached payload.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum DegradationPolicy {
+    /// Fail the build.
+    #[default]
+    Fail,
+    /// Build without the provider's values.
+    Skip,
+    /// Merge these values, keyed by dotted path, at the provider's priority
+    /// instead.
+    Fallback(HashMap<String, ConfigValue>),
+}
+
+impl DegradationPolicy {
+    /// Lower-case policy name, as recorded in audit events.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DegradationPolicy::Fail => "fail",
+            DegradationPolicy::Skip => "skip",
+            DegradationPolicy::Fallback(_) => "fallback",
+        }
+    }
+}
+
+/// Resilience settings for remote providers.
+#[derive(Debug, Clone, Default)]
+pub struct RemoteConfig {
+    retry: RetryPolicy,
+    timeout: Option<Duration>,
+    provider_timeouts: HashMap<String, Duration>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    breakers: Arc<Mutex<HashMap<String, Arc<CircuitBreaker>>>>,
+    degradation: DegradationPolicy,
+    provider_degradation: HashMap<String, DegradationPolicy>,
+    strict: bool,
+    proxy: Option<String>,
+    no_proxy: Option<String>,
+    polling: PollPolicy,
+}
+
+impl RemoteConfig {
+    /// Default settings: 3 retries with backoff, no timeout, no circuit breaker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the retry policy.
+    pub fn retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Set the timeout for each fetch attempt.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Override the attempt timeout for the provider named `name`.
+    pub fn provider_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.provider_timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// Enable a circuit breaker per provider.
+    pub fn circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.circuit_breaker = Some(config);
+        self
+    }
+
+    /// Set what happens when a provider is unavailable (default:
+    /// [`DegradationPolicy::Fail`]).
+    pub fn degradation(mut self, policy: DegradationPolicy) -> Self {
+        self.degradation = policy;
+        self
+    }
+
+    /// Override the degradation policy for the provider named `name`.
+    pub fn provider_degradation(
+        mut self,
+        name: impl Into<String>,
+        policy: DegradationPolicy,
+    ) -> Self {
+        self.provider_degradation.insert(name.into(), policy);
+        self
+    }
+
+    /// Send the providers' HTTP requests through the proxy at `url`
+    /// (`http`, `https`, `socks5` or `socks5h`) instead of the one named by
+    /// the `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` environment variables.
+    pub fn with_proxy(mut self, url: impl Into<String>) -> Self {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Hosts the proxy set with [`with_proxy`](Self::with_proxy) is bypassed
+    /// for, in `NO_PROXY` syntax.
+    pub fn no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// The proxy set with [`with_proxy`](Self::with_proxy), if any.
+    pub fn proxy(&self) -> Option<ProxyConfig> {
+        let proxy = ProxyConfig::new(self.proxy.as_ref()?);
+        Some(match &self.no_proxy {
+            Some(hosts) => proxy.no_proxy(hosts),
+            None => proxy,
+        })
+    }
+
+    /// Attempt timeout for the provider named `name`.
+    pub fn timeout_for(&self, name: &str) -> Option<Duration> {
+        self.provider_timeouts.get(name).copied().or(self.timeout)
+    }
+
+    /// Fail the build whenever a provider is unavailable, ignoring cached
+    /// payloads, open circuits and degradation policies (default: `false`).
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Whether [`strict`](Self::strict) mode is on.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Degradation policy for the provider named `name`;
+    /// [`DegradationPolicy::Fail`] in [strict](Self::strict) mode.
+    pub fn degradation_for(&self, name: &str) -> &DegradationPolicy {
+        if self.strict {
+            return &DegradationPolicy::Fail;
+        }
+        self.provider_degradation
+            .get(name)
+            .unwrap_or(&self.degradation)
+    }
+
+    /// Circuit breaker for the provider named `name`, if breakers are enabled.
+    pub fn breaker_for(&self, name: &str) -> Option<Arc<CircuitBreaker>> {
+        let config = self.circuit_breaker.as_ref()?;
+        let mut breakers = self.breakers.lock().unwrap_or_else(|e| e.into_inner());
+        Some(
+            breakers
+                .entry(name.to_string())
+                .or_insert_with(|| Arc::new(CircuitBreaker::new(config.clone())))
+                .clone(),
+        )
+    }
+
+    /// Spread the poll delays of remote watchers by up to `fraction` of
+    /// their interval (see [`PollPolicy::jitter`]).
+    pub fn poll_jitter(mut self, fraction: f64) -> Self {
+        self.polling = self.polling.jitter(fraction);
+        self
+    }
+
+    /// Never poll a remote source more often than every `interval`.
+    pub fn min_poll_interval(mut self, interval: Duration) -> Self {
+        self.polling = self.polling.min_interval(interval);
+        self
+    }
+
+    /// Send conditional requests when polling (default: `true`, see
+    /// [`PollPolicy::conditional_requests`]).
+    pub fn conditional_requests(mut self, enabled: bool) -> Self {
+        self.polling = self.polling.conditional_requests(enabled);
+        self
+    }
+
+    /// Identify this instance in staged rollouts (see
+    /// [`PollPolicy::instance_id`]).
+    pub fn instance_id(mut self, id: impl Into<String>) -> Self {
+        self.polling = self.polling.instance_id(id);
+        self
+    }
+
+    /// Poll schedule for
+    /// [`RemoteWatcher::with_poll_policy`](super::RemoteWatcher::with_poll_policy).
+    pub fn poll_policy(&self) -> &PollPolicy {
+        &self.polling
+    }
+
+    /// Wrap `provider` so its fetches follow these settings.
+    pub fn wrap(&self, provider: Box<dyn RemoteConfigSource>) -> ResilientProvider {
+        let name = provider.name().to_string();
+        ResilientProvider {
+            retry: self.retry.clone(),
+            timeout: self.timeout_for(&name),
+            breaker: self.breaker_for(&name),
+            inner: provider,
+        }
+    }
+}
+
+/// A [`RemoteConfigSource`] with retry, timeout and circuit breaker applied.
+pub struct ResilientProvider {
+    inner: Box<dyn RemoteConfigSource>,
+    retry: RetryPolicy,
+    timeout: Option<Duration>,
+    breaker: Option<Arc<CircuitBreaker>>,
+}
+
+impl ResilientProvider {
+    /// State of the provider's circuit breaker, if one is configured.
+    pub fn circuit_state(&self) -> Option<CircuitState> {
+        self.breaker.as_ref().map(|b| b.state())
+    }
+
+    /// Run one attempt of `call` under the timeout.
+    async fn attempt<F, R>(&self, call: F) -> ConfigResult<R>
+    where
+        F: std::future::Future<Output = ConfigResult<R>>,
+    {
+        match self.timeout {
+            Some(timeout) => {
+                tokio::time::timeout(timeout, call)
+                    .await
+                    .map_err(|_| ConfigError::Timeout {
+                        duration_ms: timeout.as_millis() as u64,
+                    })?
+            }
+            None => call.await,
+        }
+    }
+
+    fn circuit_open(&self) -> ConfigError {
+        ConfigError::RemoteUnavailable {
+            error_type: format!("circuit_open: {}", self.inner.name()),
+            retryable: true,
+        }
+    }
+
+    fn record<R>(&self, result: &ConfigResult<R>) {
+        if let Some(breaker) = &self.breaker {
+            match result {
+                Ok(_) => breaker.record_success(),
+                Err(_) => breaker.record_failure(),
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteConfigSource for ResilientProvider {
+    /// Fetch with retries. Every error is retried; the breaker counts the
+    /// fetch as failed only once all retries are exhausted.
+    async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+        if self.breaker.as_ref().is_some_and(|b| !b.allows()) {
+            return Err(self.circuit_open());
+        }
+
+        let mut retry = 0;
+        let result = loop {
+            match self.attempt(self.inner.fetch()).await {
+                Ok(value) => break Ok(value),
+                Err(e) if retry >= self.retry.max_retries => break Err(e),
+                Err(_) => {
+                    tokio::time::sleep(self.retry.delay(retry)).await;
+                    retry += 1;
+                }
+            }
+        };
+        self.record(&result);
+        result
+    }
+
+    /// Wait for a change. No timeout or retry applies, since waiting is the
+    /// point; failures still count towards the circuit breaker.
+    async fn watch(&self) -> ConfigResult<Option<AnnotatedValue>> {
+        if self.breaker.as_ref().is_some_and(|b| !b.allows()) {
+            return Err(self.circuit_open());
+        }
+        let result = self.inner.watch().await;
+        self.record(&result);
+        result
+    }
+
+    async fn health(&self) -> ConfigResult<()> {
+        self.attempt(self.inner.health()).await
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn priority(&self) -> u8 {
+        self.inner.priority()
+    }
+
+    fn endpoints(&self) -> Vec<String> {
+        self.inner.endpoints()
+    }
+
+    fn use_proxy(&mut self, proxy: &ProxyConfig) -> ConfigResult<()> {
+        self.inner.use_proxy(proxy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConfigValue, SourceId};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fails the first `failures` fetches, then succeeds.
+    struct Flaky {
+        failures: u32,
+        calls: Arc<AtomicU32>,
+        delay: Duration,
+    }
+
+    #[async_trait]
+    impl RemoteConfigSource for Flaky {
+        async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(self.delay).await;
+            if call < self.failures {
+                return Err(ConfigError::RemoteUnavailable {
+                    error_type: "down".to_string(),
+                    retryable: true,
+                });
+            }
+            Ok(AnnotatedValue::new(
+                ConfigValue::Bool(true),
+                SourceId::new("flaky"),
+                "",
+            ))
+        }
+
+        fn name(&self) -> &str {
+            "flaky"
+        }
+    }
+
+    fn flaky(failures: u32) -> (Box<dyn RemoteConfigSource>, Arc<AtomicU32>) {
+        let calls = Arc::new(AtomicU32::new(0));
+        let provider = Flaky {
+            failures,
+            calls: calls.clone(),
+            delay: Duration::ZERO,
+        };
+        (Box::new(provider), calls)
+    }
+
+    fn fast_retry(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+            ..RetryPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped_with_jitter() {
+        let policy = RetryPolicy {
+            max_retries: 5,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            multiplier: 2.0,
+            jitter: 0.5,
+        };
+        for retry in 0..6 {
+            let base = (100.0 * 2f64.powi(retry as i32)).min(1000.0);
+            let delay = policy.delay(retry).as_secs_f64() * 1000.0;
+            assert!(delay <= base + 0.001, "retry {retry}: {delay} > {base}");
+            assert!(
+                delay >= base * 0.5 - 0.001,
+                "retry {retry}: {delay} < {base}/2"
+            );
+        }
+
+        let exact = RetryPolicy {
+            jitter: 0.0,
+            ..policy
+        };
+        assert_eq!(exact.delay(2), Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_retries_until_success() {
+        let (provider, calls) = flaky(2);
+        let provider = RemoteConfig::new().retry(fast_retry(3)).wrap(provider);
+        provider.fetch().await.unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_gives_up_after_max_retries() {
+        let (provider, calls) = flaky(10);
+        let provider = RemoteConfig::new().retry(fast_retry(2)).wrap(provider);
+        assert!(provider.fetch().await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_provider_timeout_overrides_default() {
+        let config = RemoteConfig::new()
+            .retry(RetryPolicy::none())
+            .timeout(Duration::from_secs(5))
+            .provider_timeout("flaky", Duration::from_millis(10));
+        assert_eq!(config.timeout_for("other"), Some(Duration::from_secs(5)));
+
+        let provider = config.wrap(Box::new(Flaky {
+            failures: 0,
+            calls: Default::default(),
+            delay: Duration::from_secs(1),
+        }));
+        let err = provider.fetch().await.unwrap_err();
+        assert!(
+            matches!(err, ConfigError::Timeout { duration_ms: 10 }),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_circuit_opens_and_recovers_after_open_period() {
+        let config = RemoteConfig::new()
+            .retry(RetryPolicy::none())
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 2,
+                open_duration: Duration::from_millis(50),
+            });
+        let (inner, calls) = flaky(2);
+        let provider = config.wrap(inner);
+
+        assert!(provider.fetch().await.is_err());
+        assert_eq!(provider.circuit_state(), Some(CircuitState::Closed));
+        assert!(provider.fetch().await.is_err());
+        assert_eq!(provider.circuit_state(), Some(CircuitState::Open));
+
+        // Open: fails fast without calling the backend.
+        assert!(provider.fetch().await.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Breaker state is shared with later wrappers of the same provider.
+        let (again, _) = flaky(0);
+        assert_eq!(config.wrap(again).circuit_state(), Some(CircuitState::Open));
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(provider.circuit_state(), Some(CircuitState::HalfOpen));
+        provider.fetch().await.unwrap();
+        assert_eq!(provider.circuit_state(), Some(CircuitState::Closed));
+    }
+
+    #[test]
+    fn test_failed_trial_reopens_circuit() {
+        let breaker = CircuitBreaker::new(CircuitBreakerConfig {
+            failure_threshold: 3,
+            open_duration: Duration::ZERO,
+        });
+        for _ in 0..3 {
+            breaker.record_failure();
+        }
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        breaker.record_failure();
+        assert!(breaker.lock().opened_at.is_some());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_build_async_skips_provider_with_open_circuit() {
+        #[derive(Debug, Default, serde::Deserialize)]
+        struct AppConfig {
+            #[serde(default)]
+            port: u64,
+        }
+
+        let config = RemoteConfig::new()
+            .retry(RetryPolicy::none())
+            .circuit_breaker(CircuitBreakerConfig {
+                failure_threshold: 1,
+                open_duration: Duration::from_secs(60),
+            });
+
+        let (provider, _) = flaky(1);
+        let result = crate::ConfigBuilder::<AppConfig>::new()
+            .with_remote_provider(provider)
+            .with_remote_config(config.clone())
+            .build_async()
+            .await;
+        assert!(result.is_err());
+
+        let (provider, calls) = flaky(0);
+        let app: AppConfig = crate::ConfigBuilder::new()
+            .default("port", ConfigValue::uint(8080))
+            .with_remote_provider(provider)
+            .with_remote_config(config)
+            .build_async()
+            .await
+            .unwrap();
+        assert_eq!(app.port, 8080);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_provider_degradation_overrides_default() {
+        let config = RemoteConfig::new()
+            .degradation(DegradationPolicy::Skip)
+            .provider_degradation("flaky", DegradationPolicy::Fail);
+        assert_eq!(config.degradation_for("flaky"), &DegradationPolicy::Fail);
+        assert_eq!(config.degradation_for("other"), &DegradationPolicy::Skip);
+        assert_eq!(
+            RemoteConfig::new().degradation_for("flaky"),
+            &DegradationPolicy::Fail
+        );
+        assert_eq!(
+            config.strict(true).degradation_for("other"),
+            &DegradationPolicy::Fail
+        );
+    }
+
+    #[tokio::test]
+    async fn test_build_async_applies_degradation_policy() {
+        #[derive(Debug, Default, serde::Deserialize)]
+        struct AppConfig {
+            #[serde(default)]
+            port: u64,
+        }
+
+        let build = |policy: DegradationPolicy, strict: bool| {
+            let (provider, _) = flaky(1);
+            crate::ConfigBuilder::<AppConfig>::new()
+                .default("port", ConfigValue::uint(8080))
+                .with_remote_provider(provider)
+                .with_remote_config(
+                    RemoteConfig::new()
+                        .retry(RetryPolicy::none())
+                        .provider_degradation("flaky", policy)
+                        .strict(strict),
+                )
+                .build_async()
+        };
+
+        assert!(build(DegradationPolicy::Fail, false).await.is_err());
+        assert_eq!(
+            build(DegradationPolicy::Skip, false).await.unwrap().port,
+            8080
+        );
+        assert!(build(DegradationPolicy::Skip, true).await.is_err());
+
+        let fallback = HashMap::from([("port".to_string(), ConfigValue::uint(9090))]);
+        let app = build(DegradationPolicy::Fallback(fallback), false)
+            .await
+            .unwrap();
+        assert_eq!(app.port, 9090);
+    }
+
+    #[cfg(feature = "async-validation")]
+    #[tokio::test]
+    async fn test_build_validated_reports_degraded_provider() {
+        let (provider, _) = flaky(1);
+        let result = crate::ConfigBuilder::<serde_json::Value>::new()
+            .with_remote_provider(provider)
+            .with_remote_config(
+                RemoteConfig::new()
+                    .retry(RetryPolicy::none())
+                    .degradation(DegradationPolicy::Skip),
+            )
+            .build_validated()
+            .await
+            .unwrap();
+        assert!(result.degraded);
+        assert_eq!(
+            result.degraded_reason.as_deref(),
+            Some("1 remote provider(s) degraded")
+        );
+        assert!(result.warnings[0].message.contains("flaky degraded (skip)"));
+    }
+}
@@ -0,0 +1,226 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Staged rollout of remote configuration.
+//!
+//! A remote payload may carry a top-level `__rollout` stanza:
+//!
+//! ```json
+//! { "__rollout": { "percentage": 20, "salt": "release-42", "delay_secs": 600 } }
+//! ```
+//!
+//! When a [`RemoteWatcher`](super::RemoteWatcher) sees a new version, it hashes
+//! the salt and its instance id into a bucket from 0 to 99. Instances whose
+//! bucket is below `percentage` apply the version immediately; the others
+//! apply it `delay_secs` (default [`DEFAULT_ROLLOUT_DELAY`]) after they first
+//! saw it. Changing the salt picks a different cohort for the next push.
+//!
+//! The stanza is removed from the tree before it is merged. At startup
+//! ([`ConfigBuilder::build_async`](crate::ConfigBuilder::build_async)) the
+//! current version is always applied, since a new instance has no previous
+//! version to keep; an invalid stanza fails the build.
+
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// Top-level key holding the rollout stanza of a remote payload.
+pub const ROLLOUT_KEY: &str = "__rollout";
+
+/// Delay for instances outside the rollout percentage when the stanza names
+/// none.
+pub const DEFAULT_ROLLOUT_DELAY: Duration = Duration::from_secs(300);
+
+/// Environment variable naming this instance for rollout bucketing.
+pub const INSTANCE_ID_ENV: &str = "CONFERS_INSTANCE_ID";
+
+/// A parsed `__rollout` stanza.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rollout {
+    /// Share of instances, 0 to 100, that apply the version immediately.
+    pub percentage: u8,
+    /// Mixed into the bucket hash, so each push can pick a different cohort.
+    pub salt: String,
+    /// How long the remaining instances wait.
+    pub delay: Duration,
+}
+
+impl Rollout {
+    /// Remove the `__rollout` stanza from `tree` and parse it.
+    ///
+    /// The stanza is removed even if it is invalid. `percentage` defaults to
+    /// 100, `salt` to `""` and `delay_secs` to [`DEFAULT_ROLLOUT_DELAY`].
+    pub fn take(tree: &mut AnnotatedValue) -> ConfigResult<Option<Self>> {
+        let ConfigValue::Map(map) = &mut tree.inner else {
+            return Ok(None);
+        };
+        if !map.contains_key(ROLLOUT_KEY) {
+            return Ok(None);
+        }
+        let Some(stanza) = Arc::make_mut(map).shift_remove(ROLLOUT_KEY) else {
+            return Ok(None);
+        };
+        Self::parse(&stanza).map(Some)
+    }
+
+    fn parse(stanza: &AnnotatedValue) -> ConfigResult<Self> {
+        let invalid = |field: &str, expected: &str| ConfigError::InvalidValue {
+            key: format!("{}.{}", ROLLOUT_KEY, field),
+            expected_type: expected.to_string(),
+            message: format!("unexpected value {:?}", stanza.inner),
+        };
+        let Some(map) = stanza.inner.as_map() else {
+            return Err(invalid("", "table"));
+        };
+        let number = |field: &str| {
+            map.get(field).map(|value| {
+                value
+                    .as_u64()
+                    .or_else(|| value.as_str()?.trim().parse().ok())
+                    .ok_or_else(|| invalid(field, "non-negative integer"))
+            })
+        };
+
+        let percentage = match number("percentage").transpose()? {
+            Some(percentage) if percentage > 100 => {
+                return Err(invalid("percentage", "integer from 0 to 100"))
+            }
+            Some(percentage) => percentage as u8,
+            None => 100,
+        };
+        let salt = match map.get("salt") {
+            Some(salt) => salt
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| invalid("salt", "string"))?,
+            None => String::new(),
+        };
+        let delay = number("delay_secs")
+            .transpose()?
+            .map_or(DEFAULT_ROLLOUT_DELAY, Duration::from_secs);
+        Ok(Self {
+            percentage,
+            salt,
+            delay,
+        })
+    }
+
+    /// Bucket of `instance_id` for this rollout, from 0 to 99.
+    pub fn bucket(&self, instance_id: &str) -> u8 {
+        let digest = Sha256::digest(format!("{}:{}", self.salt, instance_id));
+        let mut head = [0u8; 8];
+        head.copy_from_slice(&digest[..8]);
+        (u64::from_be_bytes(head) % 100) as u8
+    }
+
+    /// Whether `instance_id` applies the version immediately.
+    pub fn includes(&self, instance_id: &str) -> bool {
+        self.bucket(instance_id) < self.percentage
+    }
+
+    /// How long `instance_id` waits before applying the version.
+    pub fn delay_for(&self, instance_id: &str) -> Duration {
+        match self.includes(instance_id) {
+            true => Duration::ZERO,
+            false => self.delay,
+        }
+    }
+}
+
+/// Instance id used when none is configured: `CONFERS_INSTANCE_ID`, then
+/// `HOSTNAME`, then an id chosen once per process.
+pub fn default_instance_id() -> String {
+    static FALLBACK: OnceLock<String> = OnceLock::new();
+    [INSTANCE_ID_ENV, "HOSTNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|id| !id.is_empty()))
+        .unwrap_or_else(|| {
+            FALLBACK
+                .get_or_init(|| {
+                    use std::hash::{BuildHasher, Hasher};
+                    let random = std::collections::hash_map::RandomState::new()
+                        .build_hasher()
+                        .finish();
+                    format!("{}-{:016x}", std::process::id(), random)
+                })
+                .clone()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+
+    fn payload(json: serde_json::Value) -> AnnotatedValue {
+        crate::loader::parse_content(
+            &json.to_string(),
+            crate::loader::Format::Json,
+            SourceId::new("remote"),
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_take_strips_and_parses_stanza() {
+        let mut tree = payload(serde_json::json!({
+            "port": 8080,
+            "__rollout": { "percentage": 20, "salt": "r42", "delay_secs": 60 }
+        }));
+        let rollout = Rollout::take(&mut tree).unwrap().unwrap();
+        assert_eq!(
+            rollout,
+            Rollout {
+                percentage: 20,
+                salt: "r42".to_string(),
+                delay: Duration::from_secs(60),
+            }
+        );
+        assert_eq!(tree.to_json(), serde_json::json!({ "port": 8080 }));
+        assert_eq!(Rollout::take(&mut tree).unwrap(), None);
+
+        let mut tree = payload(serde_json::json!({ "__rollout": { "percentage": "50" } }));
+        let rollout = Rollout::take(&mut tree).unwrap().unwrap();
+        assert_eq!(rollout.percentage, 50);
+        assert_eq!(rollout.delay, DEFAULT_ROLLOUT_DELAY);
+
+        let mut tree = payload(serde_json::json!({ "a": 1, "__rollout": { "percentage": 101 } }));
+        assert!(Rollout::take(&mut tree).is_err());
+        assert_eq!(tree.to_json(), serde_json::json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn test_buckets_are_deterministic_and_spread() {
+        let rollout = Rollout {
+            percentage: 25,
+            salt: "r42".to_string(),
+            delay: Duration::from_secs(60),
+        };
+        assert_eq!(rollout.bucket("pod-1"), rollout.bucket("pod-1"));
+
+        let ids: Vec<String> = (0..1000).map(|i| format!("pod-{}", i)).collect();
+        let included = ids.iter().filter(|id| rollout.includes(id)).count();
+        assert!((150..350).contains(&included), "{} included", included);
+
+        let resalted = Rollout {
+            salt: "r43".to_string(),
+            ..rollout.clone()
+        };
+        assert!(ids
+            .iter()
+            .any(|id| rollout.includes(id) != resalted.includes(id)));
+
+        let everyone = Rollout {
+            percentage: 100,
+            ..rollout
+        };
+        assert!(ids.iter().all(|id| everyone.delay_for(id).is_zero()));
+    }
+}
@@ -0,0 +1,667 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Server-Side Request Forgery protection for remote providers.
+//!
+//! [`SsrfPolicy`] decides which endpoints remote sources may connect to. It
+//! is applied when [`HttpPolledSource`](super::HttpPolledSource),
+//! [`ConsulSource`](super::ConsulSource) and `EtcdSource` are built, by
+//! [`RemoteWatcher::with_policy`](super::RemoteWatcher::with_policy), and by
+//! [`ConfigBuilder::with_ssrf_policy`](crate::ConfigBuilder::with_ssrf_policy)
+//! to every registered remote provider.
+//!
+//! - Blocked IP ranges: private networks, loopback, link-local, carrier-grade
+//!   NAT, documentation ranges and IPv4-mapped IPv6 addresses
+//! - DNS resolution validation: every resolved IP is checked, so a name
+//!   pointing at an internal address is rejected
+//! - Allowlists: hosts and CIDR networks exempt from the range check
+//! - Redirects: refused by default; when allowed, each target is checked and
+//!   resolved like the initial URL
+
+use crate::error::{ConfigError, ConfigResult};
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, LazyLock};
+
+/// Scheme of HTTP URLs served over a Unix domain socket.
+pub(crate) const UNIX_SOCKET_SCHEME: &str = "http+unix";
+
+/// Redirects followed when [`SsrfPolicy::deny_redirects`] is off.
+const MAX_REDIRECTS: usize = 10;
+
+static BLOCKED_NETWORKS: LazyLock<Vec<ipnet::IpNet>> = LazyLock::new(|| {
+    vec![
+        "127.0.0.0/8".parse().unwrap(),
+        "10.0.0.0/8".parse().unwrap(),
+        "172.16.0.0/12".parse().unwrap(),
+        "192.168.0.0/16".parse().unwrap(),
+        "169.254.0.0/16".parse().unwrap(),
+        "100.64.0.0/10".parse().unwrap(),
+        "192.0.2.0/24".parse().unwrap(),
+        "198.51.100.0/24".parse().unwrap(),
+        "203.0.113.0/24".parse().unwrap(),
+        "192.0.0.0/24".parse().unwrap(),
+        "fc00::/7".parse().unwrap(),
+        "fe80::/10".parse().unwrap(),
+    ]
+});
+
+/// Check if an IP address is in a blocked range.
+pub fn is_ip_blocked(ip: IpAddr) -> bool {
+    if let IpAddr::V6(ipv6) = ip {
+        let octets = ipv6.octets();
+        if octets[..10] == [0, 0, 0, 0, 0, 0, 0, 0, 0, 0]
+            && octets[10] == 0xff
+            && octets[11] == 0xff
+        {
+            return true;
+        }
+    }
+
+    if ip.is_loopback() {
+        return true;
+    }
+
+    BLOCKED_NETWORKS.iter().any(|net| net.contains(&ip))
+}
+
+/// Which endpoints remote configuration may be fetched from.
+///
+/// The default only allows HTTPS, rejects hosts in [blocked
+/// ranges](is_ip_blocked) and does not follow redirects.
+///
+/// # Examples
+///
+/// ```
+/// use confers::remote::SsrfPolicy;
+///
+/// let policy = SsrfPolicy::new()
+///     .allow_host("config.internal.example.com")
+///     .allow_network("10.20.0.0/16".parse().unwrap());
+///
+/// assert!(policy.check("http://config.example.com/app.json").is_err());
+/// assert!(policy.check("https://10.20.1.5/app.json").is_ok());
+/// assert!(policy.check("https://10.30.1.5/app.json").is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SsrfPolicy {
+    deny_private: bool,
+    allowed_hosts: Vec<String>,
+    allowed_networks: Vec<ipnet::IpNet>,
+    deny_redirects: bool,
+    allowed_schemes: Vec<String>,
+}
+
+impl Default for SsrfPolicy {
+    fn default() -> Self {
+        Self {
+            deny_private: true,
+            allowed_hosts: Vec::new(),
+            allowed_networks: Vec::new(),
+            deny_redirects: true,
+            allowed_schemes: vec!["https".to_string()],
+        }
+    }
+}
+
+impl SsrfPolicy {
+    /// Create the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Choose whether hosts in blocked ranges are rejected (default: `true`).
+    pub fn deny_private_ranges(mut self, deny: bool) -> Self {
+        self.deny_private = deny;
+        self
+    }
+
+    /// Exempt a host from the range check.
+    ///
+    /// `example.com` matches only that host; `*.example.com` matches its
+    /// subdomains but not `example.com` itself. An allowed host is trusted
+    /// whatever it resolves to, so use this sparingly.
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.allowed_hosts.push(host.into());
+        self
+    }
+
+    /// Exempt several hosts from the range check.
+    pub fn allow_hosts(mut self, hosts: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.allowed_hosts.extend(hosts.into_iter().map(Into::into));
+        self
+    }
+
+    /// Exempt addresses in `network` from the range check, e.g. the subnet
+    /// the configuration service runs in.
+    pub fn allow_network(mut self, network: ipnet::IpNet) -> Self {
+        self.allowed_networks.push(network);
+        self
+    }
+
+    /// Choose whether HTTP redirects are refused (default: `true`). When they
+    /// are followed, each target must pass the policy, and a target host is
+    /// resolved and checked like the initial URL. The lookup blocks while the
+    /// redirect is being followed.
+    pub fn deny_redirects(mut self, deny: bool) -> Self {
+        self.deny_redirects = deny;
+        self
+    }
+
+    /// Set the URL schemes endpoints may use (default: `https`).
+    ///
+    /// Add `http+unix` to let [`HttpPolledSource`](super::HttpPolledSource)
+    /// talk to a local agent over a Unix socket. Such URLs skip the host
+    /// checks, so only allow the scheme for trusted URLs.
+    pub fn allowed_schemes(mut self, schemes: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.allowed_schemes = schemes.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Check `url`, returning the addresses its host resolved to.
+    ///
+    /// The list is empty for allowed hosts and when private ranges are not
+    /// denied, as no lookup is needed then.
+    pub fn check(&self, url: &str) -> ConfigResult<Vec<IpAddr>> {
+        let parsed = url::Url::parse(url).map_err(|_| ConfigError::InvalidValue {
+            key: "url".to_string(),
+            expected_type: "valid URL".to_string(),
+            message: "Invalid URL format".to_string(),
+        })?;
+        self.check_url(&parsed, true)
+    }
+
+    /// Check an endpoint given as `host:port` or as a URL; a bare address is
+    /// taken to use `default_scheme`.
+    pub(crate) fn check_endpoint(
+        &self,
+        endpoint: &str,
+        default_scheme: &str,
+    ) -> ConfigResult<Vec<IpAddr>> {
+        if endpoint.contains("://") {
+            self.check(endpoint)
+        } else {
+            self.check(&format!("{}://{}", default_scheme, endpoint))
+        }
+    }
+
+    /// Whether redirects are refused, as by the
+    /// [shared HTTP client](super::shared_http_client).
+    pub(crate) fn denies_redirects(&self) -> bool {
+        self.deny_redirects
+    }
+
+    /// Redirect handling for HTTP clients of remote sources.
+    pub fn redirect_policy(&self) -> reqwest::redirect::Policy {
+        if self.deny_redirects {
+            return reqwest::redirect::Policy::none();
+        }
+        let policy = Arc::new(self.clone());
+        reqwest::redirect::Policy::custom(move |attempt| {
+            if attempt.previous().len() > MAX_REDIRECTS {
+                attempt.error("too many redirects")
+            } else if !policy.allows_redirect(attempt.url()) {
+                attempt.stop()
+            } else {
+                attempt.follow()
+            }
+        })
+    }
+
+    /// Whether a redirect to `url` may be followed. Hostnames are resolved,
+    /// so one pointing at a blocked address is refused.
+    fn allows_redirect(&self, url: &url::Url) -> bool {
+        self.check_url(url, true).is_ok()
+    }
+
+    fn check_url(&self, url: &url::Url, resolve: bool) -> ConfigResult<Vec<IpAddr>> {
+        if !self.allowed_schemes.iter().any(|s| s == url.scheme()) {
+            return Err(ConfigError::InvalidValue {
+                key: "url".to_string(),
+                expected_type: format!("{} URL", self.allowed_schemes.join(" or ")),
+                message: format!(
+                    "URL scheme '{}' is not allowed for remote configuration",
+                    url.scheme()
+                ),
+            });
+        }
+
+        // The host of an `http+unix` URL names a local socket, which no
+        // address range applies to; allowing the scheme is the opt-in.
+        if url.scheme() == UNIX_SOCKET_SCHEME {
+            return Ok(Vec::new());
+        }
+
+        let host = url.host().ok_or_else(|| ConfigError::InvalidValue {
+            key: "url".to_string(),
+            expected_type: "valid URL with host".to_string(),
+            message: "URL must have a host".to_string(),
+        })?;
+
+        let ip = match host {
+            url::Host::Domain(domain) => {
+                if self.is_allowed_host(domain) || !self.deny_private || !resolve {
+                    return Ok(Vec::new());
+                }
+                let port = url.port_or_known_default().unwrap_or(443);
+                return self.resolve_host_with_validation(domain, port);
+            }
+            url::Host::Ipv4(ip) => IpAddr::V4(ip),
+            url::Host::Ipv6(ip) => IpAddr::V6(ip),
+        };
+        if self.is_blocked(ip) {
+            return Err(ConfigError::InvalidValue {
+                key: "url".to_string(),
+                expected_type: "public IP".to_string(),
+                message: "Connection to private/internal IP addresses is not allowed".to_string(),
+            });
+        }
+        Ok(vec![ip])
+    }
+
+    fn is_allowed_host(&self, domain: &str) -> bool {
+        self.allowed_hosts.iter().any(|allowed| {
+            match allowed.strip_prefix("*.") {
+                // Only proper subdomains: *.example.com does not match example.com.
+                Some(suffix) => domain.ends_with(&format!(".{suffix}")),
+                None => domain == allowed,
+            }
+        })
+    }
+
+    fn is_blocked(&self, ip: IpAddr) -> bool {
+        self.deny_private
+            && is_ip_blocked(ip)
+            && !self.allowed_networks.iter().any(|net| net.contains(&ip))
+    }
+
+    /// Resolve a hostname and check all resolved IPs against blocked ranges.
+    ///
+    /// This provides DNS rebinding protection by validating that ALL resolved
+    /// IPs are safe to connect to. If any IP is blocked, the connection is
+    /// rejected.
+    fn resolve_host_with_validation(&self, host: &str, port: u16) -> ConfigResult<Vec<IpAddr>> {
+        let addrs: Vec<SocketAddr> = (host, port)
+            .to_socket_addrs()
+            .map_err(|_| ConfigError::InvalidValue {
+                key: "url".to_string(),
+                expected_type: "resolvable hostname".to_string(),
+                message: format!("Cannot resolve hostname: {}", host),
+            })?
+            .collect();
+
+        if addrs.is_empty() {
+            return Err(ConfigError::InvalidValue {
+                key: "url".to_string(),
+                expected_type: "resolvable hostname".to_string(),
+                message: format!("No addresses resolved for hostname: {}", host),
+            });
+        }
+
+        let mut resolved_ips = Vec::new();
+        for addr in &addrs {
+            let ip = addr.ip();
+            if self.is_blocked(ip) {
+                // SSRF attempt detected - return error without logging
+                return Err(ConfigError::InvalidValue {
+                    key: "url".to_string(),
+                    expected_type: "public IP".to_string(),
+                    message:
+                        "SSRF attempt detected: resolved IP address is in a blocked private range"
+                            .to_string(),
+                });
+            }
+            resolved_ips.push(ip);
+        }
+
+        Ok(resolved_ips)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn validate_url(url: &str, allowed_domains: &[String]) -> ConfigResult<Vec<IpAddr>> {
+        SsrfPolicy::new()
+            .allow_hosts(allowed_domains.iter().cloned())
+            .check(url)
+    }
+
+    // =============================================================================
+    // SSRF Protection Tests (9.1.7)
+    // =============================================================================
+
+    #[test]
+    fn test_is_ip_blocked_loopback_v4() {
+        // 127.0.0.0/8 - all loopback
+        assert!(is_ip_blocked(IpAddr::V4("127.0.0.1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4(
+            "127.255.255.255".parse().unwrap()
+        )));
+        assert!(is_ip_blocked(IpAddr::V4("127.0.0.0".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_private_v4() {
+        // 10.0.0.0/8
+        assert!(is_ip_blocked(IpAddr::V4("10.0.0.1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4("10.255.255.255".parse().unwrap())));
+        // 172.16.0.0/12
+        assert!(is_ip_blocked(IpAddr::V4("172.16.0.0".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4("172.31.255.255".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4("172.20.0.1".parse().unwrap())));
+        // 172.15.x.x and 172.32.x.x are NOT blocked
+        assert!(!is_ip_blocked(IpAddr::V4("172.15.0.1".parse().unwrap())));
+        assert!(!is_ip_blocked(IpAddr::V4("172.32.0.1".parse().unwrap())));
+        // 192.168.0.0/16
+        assert!(is_ip_blocked(IpAddr::V4("192.168.0.1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4(
+            "192.168.255.255".parse().unwrap()
+        )));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_link_local_v4() {
+        // 169.254.0.0/16
+        assert!(is_ip_blocked(IpAddr::V4("169.254.0.0".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4(
+            "169.254.255.255".parse().unwrap()
+        )));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_carrier_nat_v4() {
+        // 100.64.0.0/10
+        assert!(is_ip_blocked(IpAddr::V4("100.64.0.1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V4(
+            "100.127.255.255".parse().unwrap()
+        )));
+        // 100.0-63 and 100.128+ are NOT blocked
+        assert!(!is_ip_blocked(IpAddr::V4("100.0.0.1".parse().unwrap())));
+        assert!(!is_ip_blocked(IpAddr::V4("100.128.0.1".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_documentation_v4() {
+        // 192.0.2.0/24 (DOC-1)
+        assert!(is_ip_blocked(IpAddr::V4("192.0.2.1".parse().unwrap())));
+        // 198.51.100.0/24 (DOC-2)
+        assert!(is_ip_blocked(IpAddr::V4("198.51.100.1".parse().unwrap())));
+        // 203.0.113.0/24 (DOC-3)
+        assert!(is_ip_blocked(IpAddr::V4("203.0.113.1".parse().unwrap())));
+        // 192.0.0.0/24 (IETF Protocol)
+        assert!(is_ip_blocked(IpAddr::V4("192.0.0.1".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_public_v4() {
+        // Public IPs should not be blocked
+        assert!(!is_ip_blocked(IpAddr::V4("8.8.8.8".parse().unwrap())));
+        assert!(!is_ip_blocked(IpAddr::V4("1.1.1.1".parse().unwrap())));
+        assert!(!is_ip_blocked(IpAddr::V4("93.184.216.34".parse().unwrap()))); // example.com
+        assert!(!is_ip_blocked(IpAddr::V4("52.94.236.248".parse().unwrap()))); // AWS
+    }
+
+    #[test]
+    fn test_is_ip_blocked_loopback_v6() {
+        // ::1/128
+        assert!(is_ip_blocked(IpAddr::V6("::1".parse().unwrap())));
+        // ::0/128 is not blocked
+        assert!(!is_ip_blocked(IpAddr::V6("::0".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_unique_local_v6() {
+        // fc00::/7
+        assert!(is_ip_blocked(IpAddr::V6("fc00::1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V6("fd00::1".parse().unwrap())));
+        // fdFF::/8 is the random local address range
+        assert!(is_ip_blocked(IpAddr::V6("fdff::1".parse().unwrap())));
+        // fe00::/7 is NOT unique local (fe00 is)
+        assert!(!is_ip_blocked(IpAddr::V6("fe00::1".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_link_local_v6() {
+        // fe80::/10
+        assert!(is_ip_blocked(IpAddr::V6("fe80::1".parse().unwrap())));
+        assert!(is_ip_blocked(IpAddr::V6(
+            "fe80:ffff:ffff:ffff::".parse().unwrap()
+        )));
+        // fe81:: is also blocked (still in fe80::/10)
+        assert!(is_ip_blocked(IpAddr::V6("fe81::1".parse().unwrap())));
+        // fe7f:: is NOT blocked (just outside fe80::/10)
+        assert!(!is_ip_blocked(IpAddr::V6("fe7f::1".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_ipv4_mapped_v6() {
+        // IPv4-mapped IPv6 addresses (::ffff:0:0/96)
+        assert!(is_ip_blocked(IpAddr::V6(
+            "::ffff:127.0.0.1".parse().unwrap()
+        )));
+        assert!(is_ip_blocked(IpAddr::V6("::ffff:0:0".parse().unwrap())));
+        // IPv4-mapped public IPs are still blocked
+        assert!(is_ip_blocked(IpAddr::V6("::ffff:8.8.8.8".parse().unwrap())));
+    }
+
+    #[test]
+    fn test_is_ip_blocked_public_v6() {
+        // Public IPv6 addresses should not be blocked
+        assert!(!is_ip_blocked(IpAddr::V6(
+            "2001:4860:4860::8888".parse().unwrap()
+        ))); // Google DNS
+        assert!(!is_ip_blocked(IpAddr::V6(
+            "2606:4700:4700::1111".parse().unwrap()
+        ))); // Cloudflare DNS
+    }
+
+    // =============================================================================
+    // URL Validation Tests (9.1.7)
+    // =============================================================================
+
+    #[test]
+    fn test_validate_url_rejects_non_https() {
+        let result = validate_url("http://example.com/config.json", &[]);
+        assert!(result.is_err());
+        let err = result.unwrap_err();
+        assert!(matches!(err, ConfigError::InvalidValue { .. }));
+    }
+
+    #[test]
+    fn test_validate_url_rejects_private_ipv4() {
+        // 127.0.0.1
+        let result = validate_url("https://127.0.0.1/config.json", &[]);
+        assert!(result.is_err());
+        // 10.x.x.x
+        let result = validate_url("https://10.0.0.1/config.json", &[]);
+        assert!(result.is_err());
+        // 192.168.x.x
+        let result = validate_url("https://192.168.1.1/config.json", &[]);
+        assert!(err_if_blocked(&result));
+        assert!(result.is_err());
+        // 172.16.x.x
+        let result = validate_url("https://172.16.0.1/config.json", &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_url_rejects_private_ipv6() {
+        // ::1
+        let result = validate_url("https://[::1]/config.json", &[]);
+        assert!(result.is_err());
+        // fe80:: (link-local)
+        let result = validate_url("https://[fe80::1]/config.json", &[]);
+        assert!(result.is_err());
+        // fc00:: (unique local)
+        let result = validate_url("https://[fc00::1]/config.json", &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_url_rejects_ipv4_mapped() {
+        // ::ffff:127.0.0.1
+        let result = validate_url("https://[::ffff:127.0.0.1]/config.json", &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_url_rejects_documentation_ips() {
+        let result = validate_url("https://192.0.2.1/config.json", &[]);
+        assert!(result.is_err());
+        let result = validate_url("https://198.51.100.1/config.json", &[]);
+        assert!(result.is_err());
+        let result = validate_url("https://203.0.113.1/config.json", &[]);
+        assert!(result.is_err());
+        let result = validate_url("https://192.0.0.1/config.json", &[]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_validate_url_accepts_public_ips() {
+        let result = validate_url("https://8.8.8.8/config.json", &[]);
+        assert_eq!(
+            result.unwrap(),
+            vec![IpAddr::V4("8.8.8.8".parse().unwrap())]
+        );
+    }
+
+    #[test]
+    fn test_validate_url_whitelist_exact_match() {
+        let result = validate_url(
+            "https://internal.example.com/config.json",
+            &["internal.example.com".to_string()],
+        );
+        // T-C-1 B1: the original test had a redundant duplicate
+        // `assert!(result.is_ok())`. A single, well-messaged assertion is
+        // sufficient — the key behavior is that the whitelist accepts the
+        // exact domain. DNS resolution may return an empty IP list for
+        // example domains, so we only assert Ok here.
+        assert!(
+            result.is_ok(),
+            "whitelisted domain should be accepted: {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_validate_url_whitelist_subdomain_match() {
+        // Subdomains are only matched through an explicit "*." entry.
+        let policy = SsrfPolicy::new().allow_host("example.com");
+        assert!(policy.is_allowed_host("example.com"));
+        assert!(!policy.is_allowed_host("api.example.com"));
+
+        let result = validate_url(
+            "https://api.example.com/config.json",
+            &["*.example.com".to_string()],
+        );
+        assert!(
+            result.is_ok(),
+            "subdomain of whitelisted domain should be accepted: {:?}",
+            result.err()
+        );
+    }
+
+    #[test]
+    fn test_validate_url_whitelist_wildcard_match() {
+        let policy = SsrfPolicy::new().allow_host("*.corp.internal");
+        assert!(policy.is_allowed_host("config.corp.internal"));
+        assert!(!policy.is_allowed_host("corp.internal"));
+        assert!(!policy.is_allowed_host("evilcorp.internal"));
+    }
+
+    #[test]
+    fn test_validate_url_whitelist_no_match() {
+        // Non-whitelisted domain should be rejected by SSRF check.
+        // T-C-1 D4b: old code discarded the result with `let _result = ...`.
+        let result = validate_url(
+            "https://untrusted.example.com/config.json",
+            &["trusted.example.com".to_string()],
+        );
+        assert!(
+            result.is_err(),
+            "non-whitelisted domain should be rejected: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_validate_url_whitelist_mixed() {
+        let domains = vec![
+            "internal.corp.com".to_string(),
+            "config-service.prod".to_string(),
+        ];
+        let result = validate_url("https://internal.corp.com/config.json", &domains);
+        assert!(result.is_ok());
+        let result = validate_url("https://config-service.prod/config.json", &domains);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_policy_allowed_networks_and_schemes() {
+        let policy = SsrfPolicy::new()
+            .allow_network("10.1.0.0/16".parse().unwrap())
+            .allowed_schemes(["http", "https"]);
+        assert!(policy.check("http://10.1.2.3:8500/v1/kv").is_ok());
+        assert!(policy.check("https://10.2.0.1/").is_err());
+        assert!(policy.check("ftp://10.1.2.3/").is_err());
+
+        let open = SsrfPolicy::new().deny_private_ranges(false);
+        assert!(open.check("https://127.0.0.1/").is_ok());
+        assert!(open.check("http://127.0.0.1/").is_err());
+    }
+
+    #[test]
+    fn test_policy_check_endpoint_defaults_scheme() {
+        let policy = SsrfPolicy::new().allowed_schemes(["http"]);
+        assert!(policy.check_endpoint("127.0.0.1:8500", "http").is_err());
+        assert!(policy
+            .deny_private_ranges(false)
+            .check_endpoint("127.0.0.1:8500", "http")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_redirect_targets_are_checked() {
+        let policy = SsrfPolicy::new().deny_redirects(false);
+        let url = |s: &str| url::Url::parse(s).unwrap();
+        assert!(!policy.allows_redirect(&url("https://169.254.169.254/latest/meta-data")));
+        assert!(!policy.allows_redirect(&url("http://8.8.8.8/app.json")));
+        assert!(policy.allows_redirect(&url("https://8.8.8.8/app.json")));
+
+        // Hostnames are resolved: localhost only has loopback addresses.
+        assert!(!policy.allows_redirect(&url("https://localhost/app.json")));
+        let trusted = policy.clone().allow_host("localhost");
+        assert!(trusted.allows_redirect(&url("https://localhost/app.json")));
+        let open = policy.deny_private_ranges(false);
+        assert!(open.allows_redirect(&url("https://localhost/app.json")));
+    }
+
+    // Helper for test assertions
+    fn err_if_blocked(result: &Result<Vec<IpAddr>, ConfigError>) -> bool {
+        if let Err(e) = result {
+            matches!(e, ConfigError::InvalidValue { .. })
+        } else {
+            false
+        }
+    }
+
+    #[test]
+    fn test_resolve_host_with_validation_public() {
+        // Test with a well-known public DNS
+        // Note: This test requires network access. If it fails, the host doesn't resolve.
+        let result = SsrfPolicy::new().resolve_host_with_validation("example.com", 443);
+        if let Ok(ips) = result {
+            assert!(!ips.is_empty());
+            for ip in &ips {
+                assert!(
+                    !is_ip_blocked(*ip),
+                    "example.com resolved to a blocked IP: {}",
+                    ip
+                );
+            }
+        }
+        // If network is unavailable, test is skipped
+    }
+}
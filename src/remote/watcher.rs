@@ -0,0 +1,405 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Change notifications for polled remote sources.
+//!
+//! [`RemoteWatcher`] polls a [`PolledSource`] at its interval and emits the
+//! new tree only when its content differs from the previous poll. Sources that
+//! answer conditional requests (such as [`HttpPolledSource`](super::HttpPolledSource)
+//! with ETag / Last-Modified) return their cached tree on `304 Not Modified`,
+//! which hashes the same and therefore produces no event either.
+//!
+//! A [`PollPolicy`] adds jitter and a minimum interval to the poll schedule,
+//! so many instances watching the same endpoint do not poll it in lockstep.
+//!
+//! A payload with a [`__rollout`](super::rollout) stanza is staged: instances
+//! outside its percentage hold the new version back for its delay, and keep
+//! polling meanwhile, so a newer version or a rollback replaces it.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use crate::error::ConfigResult;
+use crate::remote::interval::PollPolicy;
+use crate::remote::poll::{PolledSource, DEFAULT_POLL_INTERVAL};
+use crate::remote::rollout::Rollout;
+use crate::remote::ssrf::SsrfPolicy;
+use crate::types::{AnnotatedValue, ConfigValue};
+
+/// Watches a polled source and reports content changes.
+pub struct RemoteWatcher {
+    receiver: mpsc::Receiver<AnnotatedValue>,
+    running: Arc<AtomicBool>,
+    task: Option<tokio::task::JoinHandle<()>>,
+}
+
+impl RemoteWatcher {
+    /// Start polling `source`. The first successful poll is the baseline and
+    /// is not reported.
+    pub fn new(source: Arc<dyn PolledSource>) -> Self {
+        Self::spawn(source, None, PollPolicy::default())
+    }
+
+    /// Start polling `source`, reporting any tree that differs from `current`
+    /// (typically the value the configuration was built from).
+    pub fn with_baseline(source: Arc<dyn PolledSource>, current: &AnnotatedValue) -> Self {
+        Self::spawn(source, Some(content_hash(current)), PollPolicy::default())
+    }
+
+    /// Start polling `source` after checking its
+    /// [endpoints](PolledSource::endpoints) against `policy`.
+    pub fn with_policy(source: Arc<dyn PolledSource>, policy: &SsrfPolicy) -> ConfigResult<Self> {
+        for endpoint in source.endpoints() {
+            policy.check_endpoint(&endpoint, "http")?;
+        }
+        Ok(Self::spawn(source, None, PollPolicy::default()))
+    }
+
+    /// Start polling `source` on the schedule of `policy`, e.g. the one of a
+    /// [`RemoteConfig`](super::RemoteConfig).
+    pub fn with_poll_policy(source: Arc<dyn PolledSource>, policy: &PollPolicy) -> Self {
+        Self::spawn(source, None, policy.clone())
+    }
+
+    fn spawn(source: Arc<dyn PolledSource>, mut last: Option<u64>, policy: PollPolicy) -> Self {
+        let (sender, receiver) = mpsc::channel(16);
+        let running = Arc::new(AtomicBool::new(true));
+        let interval = source.poll_interval().unwrap_or(DEFAULT_POLL_INTERVAL);
+        if !policy.uses_conditional_requests() {
+            source.set_conditional_requests(false);
+        }
+        let instance_id = policy.rollout_instance_id();
+
+        let task = tokio::spawn({
+            let running = running.clone();
+            async move {
+                let mut first = true;
+                // Version held back by a rollout, and when it was first seen.
+                let mut pending: Option<(u64, tokio::time::Instant)> = None;
+                let mut hold = None;
+                while running.load(Ordering::SeqCst) {
+                    if !std::mem::take(&mut first) {
+                        let delay = policy.next_delay(interval);
+                        let delay = hold.take().map_or(delay, |hold: Duration| hold.min(delay));
+                        tokio::time::sleep(delay).await;
+                    }
+                    // A failed poll keeps the last known state; try again next
+                    // time. So does an invalid rollout stanza, until it is fixed.
+                    let Ok(mut value) = source.poll().await else {
+                        continue;
+                    };
+                    let Ok(rollout) = Rollout::take(&mut value) else {
+                        continue;
+                    };
+                    let hash = content_hash(&value);
+                    let Some(applied) = last.replace(hash) else {
+                        continue;
+                    };
+                    if applied == hash {
+                        pending = None;
+                        continue;
+                    }
+
+                    let seen = match pending {
+                        Some((pending, seen)) if pending == hash => seen,
+                        _ => tokio::time::Instant::now(),
+                    };
+                    let wait = rollout
+                        .map_or(Duration::ZERO, |rollout| rollout.delay_for(&instance_id))
+                        .saturating_sub(seen.elapsed());
+                    if !wait.is_zero() {
+                        last = Some(applied);
+                        pending = Some((hash, seen));
+                        hold = Some(wait);
+                        continue;
+                    }
+                    pending = None;
+                    if sender.send(value).await.is_err() {
+                        break;
+                    }
+                }
+                running.store(false, Ordering::SeqCst);
+            }
+        });
+
+        Self {
+            receiver,
+            running,
+            task: Some(task),
+        }
+    }
+
+    /// Wait for the next changed tree. Returns `None` once the watcher stopped.
+    pub async fn recv(&mut self) -> Option<AnnotatedValue> {
+        self.receiver.recv().await
+    }
+
+    /// Stop polling.
+    pub fn stop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(task) = self.task.take() {
+            task.abort();
+        }
+        self.receiver.close();
+    }
+
+    /// Check if the watcher is still polling.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::SeqCst)
+    }
+}
+
+impl Drop for RemoteWatcher {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Hash of a tree's keys and values, ignoring source metadata.
+///
+/// Two polls that yield the same configuration hash the same even if the
+/// source re-parsed the payload.
+pub fn content_hash(value: &AnnotatedValue) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hash_value(&value.inner, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_value(value: &ConfigValue, hasher: &mut DefaultHasher) {
+    std::mem::discriminant(value).hash(hasher);
+    match value {
+        ConfigValue::Null => {}
+        ConfigValue::Bool(b) => b.hash(hasher),
+        ConfigValue::I64(i) => i.hash(hasher),
+        ConfigValue::U64(u) => u.hash(hasher),
+        ConfigValue::F64(f) => f.to_bits().hash(hasher),
+        ConfigValue::String(s) => s.hash(hasher),
+        ConfigValue::Bytes(b) => b.hash(hasher),
+        ConfigValue::Array(items) => {
+            items.len().hash(hasher);
+            for item in items.iter() {
+                hash_value(&item.inner, hasher);
+            }
+        }
+        ConfigValue::Map(map) => {
+            map.len().hash(hasher);
+            for (key, item) in map.iter() {
+                key.hash(hasher);
+                hash_value(&item.inner, hasher);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConfigResult;
+    use crate::types::SourceId;
+    use async_trait::async_trait;
+    use indexmap::IndexMap;
+    use std::sync::Mutex;
+
+    /// Returns `port = <n>` for each scripted value, repeating the last one.
+    struct Scripted {
+        ports: Mutex<Vec<u64>>,
+        polls: std::sync::atomic::AtomicUsize,
+        unconditional: AtomicBool,
+    }
+
+    impl Scripted {
+        fn new(ports: &[u64]) -> Arc<Self> {
+            Arc::new(Self {
+                ports: Mutex::new(ports.iter().rev().copied().collect()),
+                polls: Default::default(),
+                unconditional: Default::default(),
+            })
+        }
+    }
+
+    fn tree(port: u64, source: &str) -> AnnotatedValue {
+        let source = SourceId::new(source);
+        let mut map = IndexMap::new();
+        map.insert(
+            Arc::from("port"),
+            AnnotatedValue::new(ConfigValue::uint(port), source.clone(), "port"),
+        );
+        AnnotatedValue::new(ConfigValue::Map(Arc::new(map)), source, "")
+    }
+
+    #[async_trait]
+    impl PolledSource for Scripted {
+        async fn poll(&self) -> ConfigResult<AnnotatedValue> {
+            self.polls.fetch_add(1, Ordering::SeqCst);
+            let mut ports = self.ports.lock().unwrap();
+            let port = if ports.len() > 1 {
+                ports.pop().unwrap()
+            } else {
+                ports[0]
+            };
+            Ok(tree(port, "scripted"))
+        }
+
+        fn poll_interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(5))
+        }
+
+        fn source_id(&self) -> SourceId {
+            SourceId::new("scripted")
+        }
+
+        fn endpoints(&self) -> Vec<String> {
+            vec!["http://127.0.0.1:8500".to_string()]
+        }
+
+        fn set_conditional_requests(&self, enabled: bool) {
+            self.unconditional.store(!enabled, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_content_hash_ignores_metadata() {
+        assert_eq!(content_hash(&tree(1, "a")), content_hash(&tree(1, "b")));
+        assert_ne!(content_hash(&tree(1, "a")), content_hash(&tree(2, "a")));
+    }
+
+    #[tokio::test]
+    async fn test_only_changed_content_is_reported() {
+        let source = Scripted::new(&[1, 1, 1, 2, 2, 2, 3]);
+        let mut watcher = RemoteWatcher::new(source.clone());
+
+        let first = tokio::time::timeout(Duration::from_secs(5), watcher.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(content_hash(&first), content_hash(&tree(2, "x")));
+        let second = tokio::time::timeout(Duration::from_secs(5), watcher.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(content_hash(&second), content_hash(&tree(3, "x")));
+
+        // The script now repeats 3: polling continues without further events.
+        let polls = source.polls.load(Ordering::SeqCst);
+        assert!(
+            tokio::time::timeout(Duration::from_millis(50), watcher.recv())
+                .await
+                .is_err()
+        );
+        assert!(source.polls.load(Ordering::SeqCst) > polls);
+
+        watcher.stop();
+        assert!(!watcher.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_poll_policy_limits_poll_rate() {
+        let source = Scripted::new(&[1]);
+        let policy = PollPolicy::new()
+            .jitter(0.5)
+            .min_interval(Duration::from_millis(100))
+            .conditional_requests(false);
+        let mut watcher = RemoteWatcher::with_poll_policy(source.clone(), &policy);
+        assert!(source.unconditional.load(Ordering::SeqCst));
+
+        // The source asks for 5ms; the minimum interval allows about 3 polls.
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        let polls = source.polls.load(Ordering::SeqCst);
+        assert!((1..=3).contains(&polls), "{} polls", polls);
+        watcher.stop();
+
+        let source = Scripted::new(&[1]);
+        let mut watcher = RemoteWatcher::new(source.clone());
+        assert!(!source.unconditional.load(Ordering::SeqCst));
+        watcher.stop();
+    }
+
+    #[tokio::test]
+    async fn test_policy_is_checked_before_polling() {
+        let source = Scripted::new(&[1]);
+        assert!(RemoteWatcher::with_policy(source.clone(), &SsrfPolicy::new()).is_err());
+
+        let local = SsrfPolicy::new()
+            .allowed_schemes(["http"])
+            .allow_network("127.0.0.0/8".parse().unwrap());
+        let mut watcher = RemoteWatcher::with_policy(source, &local).unwrap();
+        assert!(watcher.is_running());
+        watcher.stop();
+    }
+
+    /// Port 1, then port 2 with a `__rollout` stanza, repeated.
+    struct Staged {
+        polls: std::sync::atomic::AtomicUsize,
+        rollout: serde_json::Value,
+    }
+
+    #[async_trait]
+    impl PolledSource for Staged {
+        async fn poll(&self) -> ConfigResult<AnnotatedValue> {
+            let payload = match self.polls.fetch_add(1, Ordering::SeqCst) {
+                0 => serde_json::json!({ "port": 1 }),
+                _ => serde_json::json!({ "port": 2, "__rollout": self.rollout }),
+            };
+            crate::loader::parse_content(
+                &payload.to_string(),
+                crate::loader::Format::Json,
+                SourceId::new("staged"),
+                None,
+            )
+        }
+
+        fn poll_interval(&self) -> Option<Duration> {
+            Some(Duration::from_secs(1))
+        }
+
+        fn source_id(&self) -> SourceId {
+            SourceId::new("staged")
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rollout_holds_back_instances_outside_percentage() {
+        let watch = |percentage: u8| async move {
+            let source = Arc::new(Staged {
+                polls: Default::default(),
+                rollout: serde_json::json!({
+                    "percentage": percentage,
+                    "salt": "r42",
+                    "delay_secs": 60,
+                }),
+            });
+            let policy = PollPolicy::new().instance_id("pod-7");
+            let mut watcher = RemoteWatcher::with_poll_policy(source, &policy);
+            let started = tokio::time::Instant::now();
+            let value = watcher.recv().await.unwrap();
+            assert_eq!(value.to_json(), serde_json::json!({ "port": 2 }));
+            started.elapsed()
+        };
+
+        assert!(watch(100).await < Duration::from_secs(2));
+        let held = watch(0).await;
+        assert!(
+            held >= Duration::from_secs(60) && held < Duration::from_secs(62),
+            "{:?}",
+            held
+        );
+    }
+
+    #[tokio::test]
+    async fn test_baseline_reports_first_differing_poll() {
+        let source = Scripted::new(&[5]);
+        let mut watcher = RemoteWatcher::with_baseline(source.clone(), &tree(4, "built"));
+        let value = tokio::time::timeout(Duration::from_secs(5), watcher.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(content_hash(&value), content_hash(&tree(5, "x")));
+    }
+}
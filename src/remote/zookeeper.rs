@@ -0,0 +1,966 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! ZooKeeper remote configuration source.
+//!
+//! The znode subtree under a root path is mapped onto nested configuration
+//! keys: `/config/app/db/host` with root `/config/app` becomes `db.host`.
+//! Znodes with children become tables; the data of leaf znodes becomes the
+//! string value. Data stored on znodes that also have children is ignored.
+//!
+//! The source talks the ZooKeeper client protocol directly over TCP. Every
+//! read registers a watch, so [`RemoteConfigSource::watch`] wakes up on the
+//! next data or child change and re-reads the subtree. A lost connection or an
+//! expired session is handled by opening a new session, retrying the ensemble
+//! hosts with exponential backoff, and re-reading the subtree so no change is
+//! missed.
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::remote::RemoteConfigSource;
+use crate::types::{AnnotatedValue, ConfigValue, SourceId};
+use async_trait::async_trait;
+use indexmap::IndexMap;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+/// Default poll interval for ZooKeeper (30 seconds).
+pub const DEFAULT_ZOOKEEPER_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Default session timeout requested from the ensemble.
+pub const DEFAULT_ZOOKEEPER_SESSION_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default maximum number of znodes read from the subtree.
+pub const DEFAULT_MAX_ZOOKEEPER_NODES: usize = 10_000;
+
+/// Default maximum size of a single protocol frame (ZooKeeper's `jute.maxbuffer`).
+pub const DEFAULT_MAX_ZOOKEEPER_FRAME_BYTES: usize = 1024 * 1024;
+
+// Operation codes and special xids of the ZooKeeper protocol.
+const OP_GET_DATA: i32 = 4;
+const OP_GET_CHILDREN: i32 = 8;
+const OP_PING: i32 = 11;
+const OP_AUTH: i32 = 100;
+const XID_WATCH_EVENT: i32 = -1;
+const XID_PING: i32 = -2;
+const XID_AUTH: i32 = -4;
+const ERR_NO_NODE: i32 = -101;
+const STATE_EXPIRED: i32 = -112;
+
+/// Builder for creating ZooKeeper configuration sources.
+pub struct ZookeeperSourceBuilder {
+    hosts: Vec<String>,
+    root: String,
+    session_timeout: Duration,
+    interval: Option<Duration>,
+    auth: Option<(String, Vec<u8>)>,
+    backoff_initial: Duration,
+    backoff_max: Duration,
+    connect_attempts: u32,
+    max_nodes: usize,
+    max_frame_bytes: usize,
+}
+
+impl ZookeeperSourceBuilder {
+    /// Create a new ZooKeeper source builder.
+    pub fn new() -> Self {
+        Self {
+            hosts: vec!["127.0.0.1:2181".to_string()],
+            root: "/config".to_string(),
+            session_timeout: DEFAULT_ZOOKEEPER_SESSION_TIMEOUT,
+            interval: None,
+            auth: None,
+            backoff_initial: Duration::from_millis(100),
+            backoff_max: Duration::from_secs(10),
+            connect_attempts: 5,
+            max_nodes: DEFAULT_MAX_ZOOKEEPER_NODES,
+            max_frame_bytes: DEFAULT_MAX_ZOOKEEPER_FRAME_BYTES,
+        }
+    }
+
+    /// Set the ensemble as a connect string (`zk1:2181,zk2:2181`).
+    pub fn hosts(mut self, hosts: impl AsRef<str>) -> Self {
+        self.hosts = hosts
+            .as_ref()
+            .split(',')
+            .map(str::trim)
+            .filter(|h| !h.is_empty())
+            .map(str::to_string)
+            .collect();
+        self
+    }
+
+    /// Set the root znode of the configuration subtree.
+    pub fn root(mut self, root: impl Into<String>) -> Self {
+        self.root = root.into();
+        self
+    }
+
+    /// Set the session timeout requested from the ensemble.
+    pub fn session_timeout(mut self, timeout: Duration) -> Self {
+        self.session_timeout = timeout;
+        self
+    }
+
+    /// Set the poll interval.
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = Some(interval);
+        self
+    }
+
+    /// Authenticate with the `digest` scheme (`user:password`).
+    pub fn digest_auth(mut self, username: &str, password: &str) -> Self {
+        self.auth = Some((
+            "digest".to_string(),
+            format!("{}:{}", username, password).into_bytes(),
+        ));
+        self
+    }
+
+    /// Set the reconnect backoff: the first delay, doubled per attempt up to `max`.
+    pub fn reconnect_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.backoff_initial = initial;
+        self.backoff_max = max;
+        self
+    }
+
+    /// Set how many connection attempts are made before giving up.
+    pub fn connect_attempts(mut self, attempts: u32) -> Self {
+        self.connect_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the maximum number of znodes read from the subtree.
+    pub fn max_nodes(mut self, nodes: usize) -> Self {
+        self.max_nodes = nodes;
+        self
+    }
+
+    /// Set the maximum size of a single protocol frame.
+    pub fn max_frame_bytes(mut self, bytes: usize) -> Self {
+        self.max_frame_bytes = bytes;
+        self
+    }
+
+    /// Build the ZooKeeper source.
+    pub fn build(self) -> ConfigResult<ZookeeperSource> {
+        if self.hosts.is_empty() {
+            return Err(invalid("zookeeper.hosts", "At least one host is required"));
+        }
+        if !self.root.starts_with('/') {
+            return Err(invalid(
+                "zookeeper.root",
+                format!("Root znode '{}' must be an absolute path", self.root),
+            ));
+        }
+        let root = match self.root.trim_end_matches('/') {
+            "" => "/".to_string(),
+            trimmed => trimmed.to_string(),
+        };
+
+        Ok(ZookeeperSource {
+            hosts: self.hosts.into(),
+            root: Arc::from(root),
+            session_timeout: self.session_timeout,
+            interval: self.interval.unwrap_or(DEFAULT_ZOOKEEPER_POLL_INTERVAL),
+            auth: self.auth,
+            backoff_initial: self.backoff_initial,
+            backoff_max: self.backoff_max,
+            connect_attempts: self.connect_attempts,
+            max_nodes: self.max_nodes,
+            max_frame_bytes: self.max_frame_bytes,
+            next_host: AtomicUsize::new(0),
+            connection: Mutex::new(None),
+        })
+    }
+}
+
+impl Default for ZookeeperSourceBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ZooKeeper-backed configuration source.
+pub struct ZookeeperSource {
+    hosts: Arc<[String]>,
+    root: Arc<str>,
+    session_timeout: Duration,
+    interval: Duration,
+    auth: Option<(String, Vec<u8>)>,
+    backoff_initial: Duration,
+    backoff_max: Duration,
+    connect_attempts: u32,
+    max_nodes: usize,
+    max_frame_bytes: usize,
+    next_host: AtomicUsize,
+    connection: Mutex<Option<Connection>>,
+}
+
+impl ZookeeperSource {
+    /// Get the source identifier.
+    pub fn source_id(&self) -> SourceId {
+        SourceId::new(format!("zookeeper:{}", self.root))
+    }
+
+    /// Read the subtree, connecting first if there is no live session.
+    async fn fetch_internal(&self) -> ConfigResult<AnnotatedValue> {
+        let mut guard = self.connection.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.connect().await?);
+        }
+        let result = self.read_tree(guard.as_mut().expect("connected")).await;
+        if result.is_err() {
+            // Drop the session; the next call starts a fresh one.
+            *guard = None;
+        }
+        result
+    }
+
+    /// Wait for a watch to fire (or the session to be lost), then re-read.
+    async fn watch_internal(&self) -> ConfigResult<AnnotatedValue> {
+        let mut guard = self.connection.lock().await;
+        let event = match guard.as_mut() {
+            Some(connection) => connection.next_event().await,
+            None => Err(invalid("zookeeper", "No session")),
+        };
+        let connection = match event {
+            Ok(event) if event.state != STATE_EXPIRED => guard.as_mut().expect("connected"),
+            _ => {
+                *guard = None;
+                guard.insert(self.connect().await?)
+            }
+        };
+        connection.events.clear();
+        let result = self.read_tree(connection).await;
+        if result.is_err() {
+            *guard = None;
+        }
+        result
+    }
+
+    /// Open a session, trying the hosts in turn with exponential backoff.
+    async fn connect(&self) -> ConfigResult<Connection> {
+        let mut delay = self.backoff_initial;
+        let mut last_error = None;
+        for attempt in 0..self.connect_attempts {
+            if attempt > 0 {
+                tokio::time::sleep(delay).await;
+                delay = (delay * 2).min(self.backoff_max);
+            }
+            let host =
+                &self.hosts[self.next_host.fetch_add(1, Ordering::Relaxed) % self.hosts.len()];
+            match Connection::open(
+                host,
+                self.session_timeout,
+                self.auth.as_ref(),
+                self.max_frame_bytes,
+            )
+            .await
+            {
+                Ok(connection) => return Ok(connection),
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap_or_else(|| invalid("zookeeper", "No connection attempts made")))
+    }
+
+    /// Read every znode below the root, registering watches along the way.
+    async fn read_tree(&self, connection: &mut Connection) -> ConfigResult<AnnotatedValue> {
+        let mut root = Node::default();
+        let mut pending = vec![(self.root.to_string(), Vec::<String>::new())];
+        let mut visited = 0usize;
+
+        while let Some((path, key)) = pending.pop() {
+            visited += 1;
+            if visited > self.max_nodes {
+                return Err(ConfigError::SizeLimitExceeded {
+                    actual: visited,
+                    limit: self.max_nodes,
+                });
+            }
+
+            let Some(mut children) = connection.get_children(&path).await? else {
+                if key.is_empty() {
+                    return Err(invalid(
+                        "zookeeper.root",
+                        format!("Root znode '{}' does not exist", self.root),
+                    ));
+                }
+                // Deleted while we were reading; the watch reports it.
+                continue;
+            };
+
+            if children.is_empty() && !key.is_empty() {
+                if let Some(data) = connection.get_data(&path).await? {
+                    let text = String::from_utf8(data).map_err(|_| {
+                        invalid("zookeeper", format!("Znode '{}' is not valid UTF-8", path))
+                    })?;
+                    root.insert(&key, text);
+                }
+                continue;
+            }
+
+            children.sort();
+            for child in children.into_iter().rev() {
+                let child_path = if path == "/" {
+                    format!("/{}", child)
+                } else {
+                    format!("{}/{}", path, child)
+                };
+                let mut child_key = key.clone();
+                child_key.push(child);
+                pending.push((child_path, child_key));
+            }
+        }
+
+        Ok(root.into_value(&self.source_id(), ""))
+    }
+}
+
+/// Subtree collected from the ensemble.
+#[derive(Default)]
+struct Node {
+    value: Option<String>,
+    children: IndexMap<String, Node>,
+}
+
+impl Node {
+    fn insert(&mut self, key: &[String], value: String) {
+        let mut node = self;
+        for part in key {
+            node = node.children.entry(part.clone()).or_default();
+        }
+        node.value = Some(value);
+    }
+
+    fn into_value(self, source: &SourceId, path: &str) -> AnnotatedValue {
+        if self.children.is_empty() {
+            if let Some(value) = self.value {
+                return AnnotatedValue::new(ConfigValue::String(value), source.clone(), path);
+            }
+        }
+        let map = self
+            .children
+            .into_iter()
+            .map(|(name, child)| {
+                let child_path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{}.{}", path, name)
+                };
+                (Arc::from(name), child.into_value(source, &child_path))
+            })
+            .collect();
+        AnnotatedValue::new(ConfigValue::Map(Arc::new(map)), source.clone(), path)
+    }
+}
+
+/// A watch notification.
+#[derive(Debug, Clone)]
+struct WatchEvent {
+    state: i32,
+}
+
+/// One ZooKeeper session over a TCP connection.
+struct Connection {
+    stream: TcpStream,
+    xid: i32,
+    ping_interval: Duration,
+    max_frame_bytes: usize,
+    events: VecDeque<WatchEvent>,
+}
+
+impl Connection {
+    async fn open(
+        host: &str,
+        session_timeout: Duration,
+        auth: Option<&(String, Vec<u8>)>,
+        max_frame_bytes: usize,
+    ) -> ConfigResult<Self> {
+        let stream = tokio::time::timeout(session_timeout, TcpStream::connect(host))
+            .await
+            .map_err(|_| invalid("zookeeper", format!("Timed out connecting to {}", host)))?
+            .map_err(|e| invalid("zookeeper", format!("Failed to connect to {}: {}", host, e)))?;
+
+        let mut connection = Self {
+            stream,
+            xid: 0,
+            ping_interval: session_timeout / 3,
+            max_frame_bytes,
+            events: VecDeque::new(),
+        };
+
+        // ConnectRequest: protocol version, last zxid seen, timeout, session
+        // id, password. A zero session id asks for a new session.
+        let mut request = Writer::default();
+        request.i32(0);
+        request.i64(0);
+        request.i32(session_timeout.as_millis().min(i32::MAX as u128) as i32);
+        request.i64(0);
+        request.buffer(&[0u8; 16]);
+        connection.write_frame(&request.0).await?;
+
+        let frame = connection.read_frame().await?;
+        let mut response = Reader::new(&frame);
+        let _protocol_version = response.i32()?;
+        let negotiated_timeout = response.i32()?;
+        if negotiated_timeout <= 0 {
+            return Err(invalid("zookeeper", "Session expired during handshake"));
+        }
+        connection.ping_interval = Duration::from_millis(negotiated_timeout as u64) / 3;
+
+        if let Some((scheme, credentials)) = auth {
+            let mut packet = Writer::default();
+            packet.i32(XID_AUTH);
+            packet.i32(OP_AUTH);
+            packet.i32(0);
+            packet.string(scheme);
+            packet.buffer(credentials);
+            connection.write_frame(&packet.0).await?;
+            loop {
+                let frame = connection.read_frame().await?;
+                let mut reply = Reader::new(&frame);
+                let (xid, err) = (reply.i32()?, {
+                    reply.i64()?;
+                    reply.i32()?
+                });
+                if xid == XID_AUTH {
+                    if err != 0 {
+                        return Err(ConfigError::KeyError {
+                            message: format!("ZooKeeper rejected {} authentication", scheme),
+                        });
+                    }
+                    break;
+                }
+            }
+        }
+
+        Ok(connection)
+    }
+
+    /// Children of `path` with a child watch, or `None` if it does not exist.
+    async fn get_children(&mut self, path: &str) -> ConfigResult<Option<Vec<String>>> {
+        let mut request = Writer::default();
+        request.string(path);
+        request.bool(true);
+        let Some(body) = self.call(OP_GET_CHILDREN, &request.0).await? else {
+            return Ok(None);
+        };
+        let mut reader = Reader::new(&body);
+        let count = reader.i32()?.max(0) as usize;
+        let mut children = Vec::with_capacity(count.min(1024));
+        for _ in 0..count {
+            children.push(reader.string()?);
+        }
+        Ok(Some(children))
+    }
+
+    /// Data of `path` with a data watch, or `None` if it does not exist.
+    async fn get_data(&mut self, path: &str) -> ConfigResult<Option<Vec<u8>>> {
+        let mut request = Writer::default();
+        request.string(path);
+        request.bool(true);
+        let Some(body) = self.call(OP_GET_DATA, &request.0).await? else {
+            return Ok(None);
+        };
+        Reader::new(&body).buffer().map(Some)
+    }
+
+    /// Send a request and wait for its reply, queueing watch events that
+    /// arrive in between. `None` means the znode does not exist.
+    async fn call(&mut self, op: i32, payload: &[u8]) -> ConfigResult<Option<Vec<u8>>> {
+        self.xid = self.xid.wrapping_add(1).max(1);
+        let xid = self.xid;
+        let mut request = Writer::default();
+        request.i32(xid);
+        request.i32(op);
+        request.0.extend_from_slice(payload);
+        self.write_frame(&request.0).await?;
+
+        loop {
+            let frame = self.read_frame().await?;
+            let mut reply = Reader::new(&frame);
+            let reply_xid = reply.i32()?;
+            let _zxid = reply.i64()?;
+            let err = reply.i32()?;
+            match reply_xid {
+                XID_WATCH_EVENT => self.events.push_back(WatchEvent::read(&mut reply)?),
+                id if id == xid => {
+                    return match err {
+                        0 => Ok(Some(reply.rest().to_vec())),
+                        ERR_NO_NODE => Ok(None),
+                        code => Err(invalid(
+                            "zookeeper",
+                            format!("Request failed with error code {}", code),
+                        )),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Wait for the next watch event, pinging to keep the session alive.
+    async fn next_event(&mut self) -> ConfigResult<WatchEvent> {
+        loop {
+            if let Some(event) = self.events.pop_front() {
+                return Ok(event);
+            }
+            match tokio::time::timeout(self.ping_interval, self.read_frame()).await {
+                Ok(frame) => {
+                    let frame = frame?;
+                    let mut reply = Reader::new(&frame);
+                    if reply.i32()? == XID_WATCH_EVENT {
+                        reply.i64()?;
+                        reply.i32()?;
+                        self.events.push_back(WatchEvent::read(&mut reply)?);
+                    }
+                }
+                Err(_) => {
+                    let mut ping = Writer::default();
+                    ping.i32(XID_PING);
+                    ping.i32(OP_PING);
+                    self.write_frame(&ping.0).await?;
+                }
+            }
+        }
+    }
+
+    async fn write_frame(&mut self, payload: &[u8]) -> ConfigResult<()> {
+        let mut frame = Vec::with_capacity(payload.len() + 4);
+        frame.extend_from_slice(&(payload.len() as i32).to_be_bytes());
+        frame.extend_from_slice(payload);
+        self.stream.write_all(&frame).await.map_err(connection_lost)
+    }
+
+    async fn read_frame(&mut self) -> ConfigResult<Vec<u8>> {
+        let len = self.stream.read_i32().await.map_err(connection_lost)?;
+        let len =
+            usize::try_from(len).map_err(|_| invalid("zookeeper", "Negative frame length"))?;
+        if len > self.max_frame_bytes {
+            return Err(ConfigError::SizeLimitExceeded {
+                actual: len,
+                limit: self.max_frame_bytes,
+            });
+        }
+        let mut frame = vec![0u8; len];
+        self.stream
+            .read_exact(&mut frame)
+            .await
+            .map_err(connection_lost)?;
+        Ok(frame)
+    }
+}
+
+impl WatchEvent {
+    fn read(reader: &mut Reader<'_>) -> ConfigResult<Self> {
+        let _event_type = reader.i32()?;
+        let state = reader.i32()?;
+        let _path = reader.string()?;
+        Ok(Self { state })
+    }
+}
+
+/// Big-endian encoder for the ZooKeeper (jute) wire format.
+#[derive(Default)]
+struct Writer(Vec<u8>);
+
+impl Writer {
+    fn i32(&mut self, v: i32) {
+        self.0.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn i64(&mut self, v: i64) {
+        self.0.extend_from_slice(&v.to_be_bytes());
+    }
+
+    fn bool(&mut self, v: bool) {
+        self.0.push(v as u8);
+    }
+
+    fn buffer(&mut self, v: &[u8]) {
+        self.i32(v.len() as i32);
+        self.0.extend_from_slice(v);
+    }
+
+    fn string(&mut self, v: &str) {
+        self.buffer(v.as_bytes());
+    }
+}
+
+/// Decoder for the ZooKeeper (jute) wire format.
+struct Reader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    fn take(&mut self, n: usize) -> ConfigResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(n)
+            .filter(|&end| end <= self.buf.len())
+            .ok_or_else(|| invalid("zookeeper", "Truncated response"))?;
+        let bytes = &self.buf[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn i32(&mut self) -> ConfigResult<i32> {
+        Ok(i32::from_be_bytes(
+            self.take(4)?.try_into().expect("4 bytes"),
+        ))
+    }
+
+    fn i64(&mut self) -> ConfigResult<i64> {
+        Ok(i64::from_be_bytes(
+            self.take(8)?.try_into().expect("8 bytes"),
+        ))
+    }
+
+    /// A length-prefixed buffer; a negative length encodes null (empty here).
+    fn buffer(&mut self) -> ConfigResult<Vec<u8>> {
+        let len = self.i32()?;
+        if len < 0 {
+            return Ok(Vec::new());
+        }
+        Ok(self.take(len as usize)?.to_vec())
+    }
+
+    fn string(&mut self) -> ConfigResult<String> {
+        String::from_utf8(self.buffer()?).map_err(|_| invalid("zookeeper", "Invalid UTF-8 string"))
+    }
+
+    fn rest(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+}
+
+fn invalid(key: &str, message: impl Into<String>) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: key.to_string(),
+        expected_type: "ZooKeeper response".to_string(),
+        message: message.into(),
+    }
+}
+
+fn connection_lost(err: std::io::Error) -> ConfigError {
+    invalid("zookeeper", format!("Connection lost: {}", err))
+}
+
+#[async_trait]
+impl crate::remote::PolledSource for ZookeeperSource {
+    async fn poll(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn poll_interval(&self) -> Option<Duration> {
+        Some(self.interval)
+    }
+
+    fn source_id(&self) -> SourceId {
+        Self::source_id(self)
+    }
+}
+
+#[async_trait]
+impl crate::interface::AsyncSource for ZookeeperSource {
+    async fn load(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    fn source_id(&self) -> &SourceId {
+        static SOURCE_ID: std::sync::OnceLock<SourceId> = std::sync::OnceLock::new();
+        SOURCE_ID.get_or_init(|| SourceId::new("zookeeper"))
+    }
+
+    fn name(&self) -> &str {
+        "zookeeper"
+    }
+}
+
+#[async_trait]
+impl RemoteConfigSource for ZookeeperSource {
+    async fn fetch(&self) -> ConfigResult<AnnotatedValue> {
+        self.fetch_internal().await
+    }
+
+    /// Wait for a watched znode to change, then return the re-read subtree.
+    ///
+    /// After a lost connection or an expired session, a new session is opened
+    /// and the subtree is returned right away, since changes may have been
+    /// missed in between.
+    async fn watch(&self) -> ConfigResult<Option<AnnotatedValue>> {
+        if self.connection.lock().await.is_none() {
+            self.fetch_internal().await?;
+        }
+        self.watch_internal().await.map(Some)
+    }
+
+    fn name(&self) -> &str {
+        "zookeeper"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+    use std::io::{Read, Write};
+    use std::net::{TcpListener, TcpStream as StdTcpStream};
+
+    type Tree = BTreeMap<String, Option<&'static str>>;
+
+    fn read_frame(stream: &mut StdTcpStream) -> Option<Vec<u8>> {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).ok()?;
+        let mut frame = vec![0u8; i32::from_be_bytes(len) as usize];
+        stream.read_exact(&mut frame).ok()?;
+        Some(frame)
+    }
+
+    fn write_frame(stream: &mut StdTcpStream, payload: &[u8]) {
+        let mut frame = (payload.len() as i32).to_be_bytes().to_vec();
+        frame.extend_from_slice(payload);
+        let _ = stream.write_all(&frame);
+    }
+
+    fn reply(xid: i32, err: i32, body: &[u8]) -> Vec<u8> {
+        let mut w = Writer::default();
+        w.i32(xid);
+        w.i64(1);
+        w.i32(err);
+        w.0.extend_from_slice(body);
+        w.0
+    }
+
+    /// Serve one session from `trees`: the first tree until a watch event is
+    /// sent after the `notify_after`-th request, then the next one.
+    fn serve(stream: &mut StdTcpStream, trees: &[Tree], notify_after: Option<usize>) {
+        let Some(_connect) = read_frame(stream) else {
+            return;
+        };
+        let mut w = Writer::default();
+        w.i32(0);
+        w.i32(3000);
+        w.i64(42);
+        w.buffer(&[0u8; 16]);
+        write_frame(stream, &w.0);
+
+        let mut tree = 0;
+        let mut requests = 0;
+        while let Some(frame) = read_frame(stream) {
+            let mut r = Reader::new(&frame);
+            let xid = r.i32().unwrap();
+            let op = r.i32().unwrap();
+            if op == OP_PING {
+                write_frame(stream, &reply(XID_PING, 0, &[]));
+                continue;
+            }
+            if op == OP_AUTH {
+                r.i32().unwrap();
+                r.string().unwrap();
+                let ok = r.buffer().unwrap() == b"app:secret";
+                write_frame(stream, &reply(XID_AUTH, if ok { 0 } else { -115 }, &[]));
+                continue;
+            }
+            let path = r.string().unwrap();
+            let nodes = &trees[tree];
+            let body = match op {
+                OP_GET_CHILDREN if nodes.contains_key(&path) => {
+                    let prefix = if path == "/" {
+                        "/".to_string()
+                    } else {
+                        format!("{}/", path)
+                    };
+                    let children: Vec<&str> = nodes
+                        .keys()
+                        .filter_map(|k| k.strip_prefix(&prefix))
+                        .filter(|rest| !rest.is_empty() && !rest.contains('/'))
+                        .collect();
+                    let mut w = Writer::default();
+                    w.i32(children.len() as i32);
+                    for child in children {
+                        w.string(child);
+                    }
+                    Some(w.0)
+                }
+                OP_GET_DATA if nodes.contains_key(&path) => {
+                    let mut w = Writer::default();
+                    w.buffer(nodes[&path].unwrap_or_default().as_bytes());
+                    w.0.extend_from_slice(&[0u8; 68]);
+                    Some(w.0)
+                }
+                _ => None,
+            };
+            match body {
+                Some(body) => write_frame(stream, &reply(xid, 0, &body)),
+                None => write_frame(stream, &reply(xid, ERR_NO_NODE, &[])),
+            }
+
+            requests += 1;
+            if notify_after == Some(requests) && tree + 1 < trees.len() {
+                tree += 1;
+                let mut event = Writer::default();
+                event.i32(3); // NodeDataChanged
+                event.i32(3); // SyncConnected
+                event.string("/config/app/db/port");
+                write_frame(stream, &reply(XID_WATCH_EVENT, 0, &event.0));
+            }
+        }
+    }
+
+    fn tree(entries: &[(&str, Option<&'static str>)]) -> Tree {
+        entries.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    fn get<'a>(value: &'a AnnotatedValue, path: &str) -> Option<&'a ConfigValue> {
+        path.split('.')
+            .try_fold(value, |node, key| match &node.inner {
+                ConfigValue::Map(map) => map.get(key),
+                _ => None,
+            })
+            .map(|node| &node.inner)
+    }
+
+    fn app_tree(port: &'static str) -> Tree {
+        tree(&[
+            ("/config", None),
+            ("/config/app", None),
+            ("/config/app/name", Some("demo")),
+            ("/config/app/db", None),
+            ("/config/app/db/host", Some("localhost")),
+            ("/config/app/db/port", Some(port)),
+        ])
+    }
+
+    #[test]
+    fn test_builder_validates_root_and_hosts() {
+        assert!(ZookeeperSourceBuilder::new()
+            .root("config")
+            .build()
+            .is_err());
+        assert!(ZookeeperSourceBuilder::new().hosts(" , ").build().is_err());
+        let source = ZookeeperSourceBuilder::new()
+            .hosts("zk1:2181, zk2:2181")
+            .root("/config/app/")
+            .build()
+            .unwrap();
+        assert_eq!(source.hosts.len(), 2);
+        assert_eq!(source.source_id().as_str(), "zookeeper:/config/app");
+    }
+
+    #[tokio::test]
+    async fn test_fetch_maps_subtree_to_nested_keys() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            serve(&mut stream, &[app_tree("5432")], None);
+        });
+
+        let source = ZookeeperSourceBuilder::new()
+            .hosts(addr)
+            .root("/config/app")
+            .build()
+            .unwrap();
+        let value = source.fetch_internal().await.unwrap();
+        assert_eq!(get(&value, "name"), Some(&ConfigValue::string("demo")));
+        assert_eq!(
+            get(&value, "db.host"),
+            Some(&ConfigValue::string("localhost"))
+        );
+        assert_eq!(get(&value, "db.port"), Some(&ConfigValue::string("5432")));
+    }
+
+    #[tokio::test]
+    async fn test_missing_root_is_an_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            serve(&mut stream, &[tree(&[("/other", None)])], None);
+        });
+
+        let source = ZookeeperSourceBuilder::new()
+            .hosts(addr)
+            .root("/config/app")
+            .build()
+            .unwrap();
+        let err = source.fetch_internal().await.unwrap_err();
+        assert!(err.to_string().contains("does not exist"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_watch_rereads_after_change_notification() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            // 8 requests read the initial tree; the event follows the last.
+            serve(&mut stream, &[app_tree("5432"), app_tree("6543")], Some(8));
+        });
+
+        let source = ZookeeperSourceBuilder::new()
+            .hosts(addr)
+            .root("/config/app")
+            .digest_auth("app", "secret")
+            .build()
+            .unwrap();
+        let initial = source.fetch_internal().await.unwrap();
+        assert_eq!(get(&initial, "db.port"), Some(&ConfigValue::string("5432")));
+
+        let changed = tokio::time::timeout(Duration::from_secs(5), source.watch())
+            .await
+            .expect("watch should wake up")
+            .unwrap()
+            .unwrap();
+        assert_eq!(get(&changed, "db.port"), Some(&ConfigValue::string("6543")));
+    }
+
+    #[tokio::test]
+    async fn test_watch_reconnects_after_connection_loss() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap().to_string();
+        let (dropped_tx, dropped_rx) = std::sync::mpsc::channel::<()>();
+        std::thread::spawn(move || {
+            // First session serves the tree, then the server drops it.
+            let (stream, _) = listener.accept().unwrap();
+            let mut first = stream.try_clone().unwrap();
+            std::thread::spawn(move || serve(&mut first, &[app_tree("5432")], None));
+            dropped_rx.recv().unwrap();
+            stream.shutdown(std::net::Shutdown::Both).unwrap();
+            // Second session sees the value changed while disconnected.
+            let (mut stream, _) = listener.accept().unwrap();
+            serve(&mut stream, &[app_tree("6543")], None);
+        });
+
+        let source = ZookeeperSourceBuilder::new()
+            .hosts(addr)
+            .root("/config/app")
+            .reconnect_backoff(Duration::from_millis(10), Duration::from_millis(20))
+            .build()
+            .unwrap();
+        let initial = source.fetch_internal().await.unwrap();
+        assert_eq!(get(&initial, "db.port"), Some(&ConfigValue::string("5432")));
+
+        dropped_tx.send(()).unwrap();
+        let changed = tokio::time::timeout(Duration::from_secs(5), source.watch())
+            .await
+            .expect("watch should reconnect")
+            .unwrap()
+            .unwrap();
+        assert_eq!(get(&changed, "db.port"), Some(&ConfigValue::string("6543")));
+    }
+}
@@ -0,0 +1,389 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! age (X25519) asymmetric encryption for configuration values.
+//!
+//! Values are encrypted to one or more age recipients (`age1...` public keys)
+//! and decrypted with the matching identities (`AGE-SECRET-KEY-1...`). This lets
+//! a team commit encrypted secrets that only holders of an identity file can read,
+//! without sharing a symmetric master key.
+//!
+//! Recipient and identity files use the same layout as the `age` CLI: one key per
+//! line, blank lines and lines starting with `#` are ignored.
+//!
+//! Recipients can also be addressed by name: [`AgeCipher::add_named_recipients`]
+//! reads the recipients file `<dir>/<name>` (or `<dir>/<name>.txt`), so each
+//! environment or team keeps its own list of public keys and a value can be
+//! encrypted once for several of them.
+
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use age::x25519::{Identity, Recipient};
+use zeroize::Zeroizing;
+
+use crate::error::{ConfigError, ConfigResult};
+use crate::secret::{CipherAlgorithm, CryptoError, ValueCipher};
+
+/// age cipher holding X25519 recipients (for encryption) and identities (for decryption).
+#[derive(Default)]
+pub struct AgeCipher {
+    recipients: Vec<Recipient>,
+    identities: Vec<Identity>,
+}
+
+impl AgeCipher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a recipient public key (`age1...`).
+    pub fn with_recipient(mut self, recipient: &str) -> Result<Self, CryptoError> {
+        self.add_recipient(recipient)?;
+        Ok(self)
+    }
+
+    /// Add an identity secret key (`AGE-SECRET-KEY-1...`).
+    pub fn with_identity(mut self, identity: &str) -> Result<Self, CryptoError> {
+        self.add_identity(identity)?;
+        Ok(self)
+    }
+
+    /// Load recipients from a file, one per line.
+    pub fn with_recipients_file(mut self, path: impl AsRef<Path>) -> ConfigResult<Self> {
+        self.add_recipients_file(path)?;
+        Ok(self)
+    }
+
+    /// Load identities from a file, one per line.
+    pub fn with_identity_file(mut self, path: impl AsRef<Path>) -> ConfigResult<Self> {
+        self.add_identity_file(path)?;
+        Ok(self)
+    }
+
+    pub fn add_recipient(&mut self, recipient: &str) -> Result<(), CryptoError> {
+        let recipient = Recipient::from_str(recipient.trim())
+            .map_err(|_| CryptoError::InvalidKey("malformed age recipient".to_string()))?;
+        if !self
+            .recipients
+            .iter()
+            .any(|r| r.to_string() == recipient.to_string())
+        {
+            self.recipients.push(recipient);
+        }
+        Ok(())
+    }
+
+    pub fn add_identity(&mut self, identity: &str) -> Result<(), CryptoError> {
+        let identity = Identity::from_str(identity.trim())
+            .map_err(|_| CryptoError::InvalidKey("malformed age identity".to_string()))?;
+        self.identities.push(identity);
+        Ok(())
+    }
+
+    pub fn add_recipients_file(&mut self, path: impl AsRef<Path>) -> ConfigResult<()> {
+        let path = path.as_ref();
+        let content = read_key_file(path)?;
+        for line in key_lines(&content) {
+            self.add_recipient(line)
+                .map_err(|e| key_file_error(path, &e))?;
+        }
+        Ok(())
+    }
+
+    /// Add the recipients listed for `name` in the recipients directory `dir`:
+    /// the file `<dir>/<name>`, or `<dir>/<name>.txt`.
+    pub fn add_named_recipients(&mut self, dir: impl AsRef<Path>, name: &str) -> ConfigResult<()> {
+        let dir = dir.as_ref();
+        let name = name.trim();
+        if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+            return Err(ConfigError::KeyError {
+                message: format!("Invalid recipient name '{}'", name),
+            });
+        }
+
+        let path = [dir.join(name), dir.join(format!("{}.txt", name))]
+            .into_iter()
+            .find(|path| path.is_file())
+            .ok_or_else(|| ConfigError::KeyError {
+                message: format!(
+                    "Unknown recipient '{}': neither {} nor {}.txt exists in {}",
+                    name,
+                    name,
+                    name,
+                    dir.display()
+                ),
+            })?;
+        self.add_recipients_file(path)
+    }
+
+    pub fn add_identity_file(&mut self, path: impl AsRef<Path>) -> ConfigResult<()> {
+        let path = path.as_ref();
+        let content = read_key_file(path)?;
+        for line in key_lines(&content) {
+            self.add_identity(line)
+                .map_err(|e| key_file_error(path, &e))?;
+        }
+        Ok(())
+    }
+
+    pub fn has_recipients(&self) -> bool {
+        !self.recipients.is_empty()
+    }
+
+    pub fn has_identities(&self) -> bool {
+        !self.identities.is_empty()
+    }
+
+    /// Public keys of all configured recipients.
+    pub fn recipients(&self) -> Vec<String> {
+        self.recipients.iter().map(|r| r.to_string()).collect()
+    }
+
+    /// Encrypt `plaintext` to every configured recipient (binary age format).
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if self.recipients.is_empty() {
+            return Err(CryptoError::MissingKey("AGE".to_string()));
+        }
+
+        let encryptor = age::Encryptor::with_recipients(
+            self.recipients.iter().map(|r| r as &dyn age::Recipient),
+        )
+        .map_err(|_| CryptoError::EncryptionFailed)?;
+
+        let mut ciphertext = Vec::with_capacity(plaintext.len() + 256);
+        let mut writer = encryptor
+            .wrap_output(&mut ciphertext)
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+        writer
+            .write_all(plaintext)
+            .map_err(|_| CryptoError::EncryptionFailed)?;
+        writer.finish().map_err(|_| CryptoError::EncryptionFailed)?;
+
+        Ok(ciphertext)
+    }
+
+    /// Decrypt an age payload with any of the configured identities.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        if self.identities.is_empty() {
+            return Err(CryptoError::MissingKey("AGE".to_string()));
+        }
+
+        let decryptor =
+            age::Decryptor::new_buffered(ciphertext).map_err(|_| CryptoError::DecryptionFailed)?;
+        let mut reader = decryptor
+            .decrypt(self.identities.iter().map(|i| i as &dyn age::Identity))
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        let mut plaintext = Vec::new();
+        reader
+            .read_to_end(&mut plaintext)
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+        Ok(plaintext)
+    }
+}
+
+impl ValueCipher for AgeCipher {
+    fn algorithm(&self) -> &str {
+        CipherAlgorithm::Age.as_str()
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        AgeCipher::encrypt(self, plaintext)
+    }
+
+    fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        AgeCipher::decrypt(self, payload)
+    }
+}
+
+impl std::fmt::Debug for AgeCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgeCipher")
+            .field("recipients", &self.recipients.len())
+            .field("identities", &"[REDACTED]")
+            .finish()
+    }
+}
+
+fn read_key_file(path: &Path) -> ConfigResult<Zeroizing<String>> {
+    std::fs::read_to_string(path)
+        .map(Zeroizing::new)
+        .map_err(|e| ConfigError::FileNotFound {
+            filename: path.to_path_buf(),
+            source: Some(e),
+        })
+}
+
+fn key_lines(content: &str) -> impl Iterator<Item = &str> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+}
+
+fn key_file_error(path: &Path, err: &CryptoError) -> ConfigError {
+    ConfigError::KeyError {
+        message: format!("{} in {}", err, path.display()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+
+    fn keypair() -> (String, String) {
+        let identity = Identity::generate();
+        (
+            identity.to_string().expose_secret().to_string(),
+            identity.to_public().to_string(),
+        )
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_round_trip() {
+        let (secret, public) = keypair();
+        let cipher = AgeCipher::new()
+            .with_recipient(&public)
+            .unwrap()
+            .with_identity(&secret)
+            .unwrap();
+
+        let ciphertext = cipher.encrypt(b"db-password").unwrap();
+        assert!(!ciphertext.windows(11).any(|w| w == b"db-password"));
+        assert_eq!(cipher.decrypt(&ciphertext).unwrap(), b"db-password");
+    }
+
+    #[test]
+    fn test_multiple_recipients_each_can_decrypt() {
+        let (secret_a, public_a) = keypair();
+        let (secret_b, public_b) = keypair();
+        let encryptor = AgeCipher::new()
+            .with_recipient(&public_a)
+            .unwrap()
+            .with_recipient(&public_b)
+            .unwrap();
+        let ciphertext = encryptor.encrypt(b"shared").unwrap();
+
+        for secret in [secret_a, secret_b] {
+            let decryptor = AgeCipher::new().with_identity(&secret).unwrap();
+            assert_eq!(decryptor.decrypt(&ciphertext).unwrap(), b"shared");
+        }
+    }
+
+    #[test]
+    fn test_wrong_identity_fails() {
+        let (_, public) = keypair();
+        let (other_secret, _) = keypair();
+        let ciphertext = AgeCipher::new()
+            .with_recipient(&public)
+            .unwrap()
+            .encrypt(b"x")
+            .unwrap();
+        let err = AgeCipher::new()
+            .with_identity(&other_secret)
+            .unwrap()
+            .decrypt(&ciphertext)
+            .unwrap_err();
+        assert!(matches!(err, CryptoError::DecryptionFailed));
+    }
+
+    #[test]
+    fn test_missing_keys_are_reported() {
+        let cipher = AgeCipher::new();
+        assert!(matches!(
+            cipher.encrypt(b"x").unwrap_err(),
+            CryptoError::MissingKey(_)
+        ));
+        assert!(matches!(
+            cipher.decrypt(b"x").unwrap_err(),
+            CryptoError::MissingKey(_)
+        ));
+    }
+
+    #[test]
+    fn test_malformed_keys_rejected() {
+        assert!(AgeCipher::new().with_recipient("age1nope").is_err());
+        assert!(AgeCipher::new()
+            .with_identity("AGE-SECRET-KEY-1NOPE")
+            .is_err());
+    }
+
+    #[test]
+    fn test_key_files_skip_comments_and_blank_lines() {
+        let (secret, public) = keypair();
+        let dir = tempfile::tempdir().unwrap();
+        let recipients = dir.path().join("recipients.txt");
+        let identities = dir.path().join("identity.txt");
+        std::fs::write(&recipients, format!("# team\n\n{}\n", public)).unwrap();
+        std::fs::write(
+            &identities,
+            format!("# created: today\n# public key: {}\n{}\n", public, secret),
+        )
+        .unwrap();
+
+        let cipher = AgeCipher::new()
+            .with_recipients_file(&recipients)
+            .unwrap()
+            .with_identity_file(&identities)
+            .unwrap();
+        assert_eq!(cipher.recipients(), vec![public]);
+        let ciphertext = cipher.encrypt(b"from-files").unwrap();
+        assert_eq!(cipher.decrypt(&ciphertext).unwrap(), b"from-files");
+    }
+
+    #[test]
+    fn test_named_recipients_are_read_from_the_recipients_dir() {
+        let (secret_ops, public_ops) = keypair();
+        let (secret_ci, public_ci) = keypair();
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("ops"),
+            format!("# ops team\n{}\n", public_ops),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("ci.txt"),
+            format!("{}\n{}\n", public_ci, public_ops),
+        )
+        .unwrap();
+
+        let mut cipher = AgeCipher::new();
+        for name in ["ops", "ci"] {
+            cipher.add_named_recipients(dir.path(), name).unwrap();
+        }
+        assert_eq!(cipher.recipients(), vec![public_ops, public_ci]);
+
+        let ciphertext = cipher.encrypt(b"per-environment").unwrap();
+        for secret in [secret_ops, secret_ci] {
+            let decryptor = AgeCipher::new().with_identity(&secret).unwrap();
+            assert_eq!(decryptor.decrypt(&ciphertext).unwrap(), b"per-environment");
+        }
+
+        for name in ["staging", "", "../ops"] {
+            let err = cipher.add_named_recipients(dir.path(), name).unwrap_err();
+            assert!(matches!(err, ConfigError::KeyError { .. }), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_missing_key_file_is_file_not_found() {
+        let err = AgeCipher::new()
+            .with_identity_file("/nonexistent/identity.txt")
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::FileNotFound { .. }));
+    }
+
+    #[test]
+    fn test_debug_redacts_identities() {
+        let (secret, _) = keypair();
+        let cipher = AgeCipher::new().with_identity(&secret).unwrap();
+        let debug = format!("{:?}", cipher);
+        assert!(!debug.contains(&secret));
+        assert!(debug.contains("[REDACTED]"));
+    }
+}
@@ -0,0 +1,397 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Pluggable ciphers for `enc:<ALGO>:` configuration values.
+//!
+//! Every cipher implements [`ValueCipher`] and is looked up by its algorithm tag
+//! in a [`CipherRegistry`]. The built-in symmetric ciphers store their payload as
+//! `nonce ‖ ciphertext`; custom ciphers are free to choose their own layout.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::aead::generic_array::typenum::Unsigned;
+use chacha20poly1305::aead::rand_core::RngCore;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, Nonce, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, XChaCha20Poly1305};
+
+use crate::secret::{CryptoError, SecretBytes};
+
+/// A cipher that can encrypt and decrypt configuration values.
+pub trait ValueCipher: Send + Sync {
+    /// Tag used inside `enc:<ALGO>:` values; uppercase ASCII letters, digits and `_`.
+    fn algorithm(&self) -> &str;
+
+    /// Encrypt `plaintext` into an opaque payload.
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError>;
+
+    /// Decrypt a payload produced by [`ValueCipher::encrypt`].
+    fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, CryptoError>;
+}
+
+/// Built-in cipher algorithms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CipherAlgorithm {
+    /// XChaCha20-Poly1305 with a symmetric 32-byte key (default).
+    XChaCha20,
+    /// ChaCha20-Poly1305 (RFC 8439) with a symmetric 32-byte key.
+    ChaCha20,
+    /// AES-256-GCM with a symmetric 32-byte key.
+    Aes256Gcm,
+    /// age X25519 public-key encryption.
+    #[cfg(feature = "age")]
+    Age,
+}
+
+impl CipherAlgorithm {
+    /// Tag used inside `enc:<ALGO>:` values.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CipherAlgorithm::XChaCha20 => "XCHACHA20",
+            CipherAlgorithm::ChaCha20 => "CHACHA20",
+            CipherAlgorithm::Aes256Gcm => "AES256GCM",
+            #[cfg(feature = "age")]
+            CipherAlgorithm::Age => "AGE",
+        }
+    }
+}
+
+impl AsRef<str> for CipherAlgorithm {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl std::fmt::Display for CipherAlgorithm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for CipherAlgorithm {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "XCHACHA20" | "XCHACHA20-POLY1305" => Ok(Self::XChaCha20),
+            "CHACHA20" | "CHACHA20-POLY1305" => Ok(Self::ChaCha20),
+            "AES256GCM" | "AES-256-GCM" => Ok(Self::Aes256Gcm),
+            #[cfg(feature = "age")]
+            "AGE" => Ok(Self::Age),
+            other => Err(CryptoError::UnsupportedAlgorithm(other.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SymmetricKind {
+    XChaCha20,
+    ChaCha20,
+    Aes256Gcm,
+}
+
+/// AEAD cipher keyed with a 32-byte symmetric key.
+///
+/// Previous key versions can be attached with
+/// [`SymmetricCipher::with_previous_key`]; encryption always uses the primary
+/// key while decryption falls back to older keys in order.
+pub struct SymmetricCipher {
+    kind: SymmetricKind,
+    key: SecretBytes,
+    previous: Vec<SecretBytes>,
+}
+
+impl SymmetricCipher {
+    /// XChaCha20-Poly1305 (`XCHACHA20`), 24-byte random nonce.
+    pub fn xchacha20(key: SecretBytes) -> Self {
+        Self {
+            kind: SymmetricKind::XChaCha20,
+            key,
+            previous: Vec::new(),
+        }
+    }
+
+    /// ChaCha20-Poly1305 (`CHACHA20`), 12-byte random nonce.
+    pub fn chacha20(key: SecretBytes) -> Self {
+        Self {
+            kind: SymmetricKind::ChaCha20,
+            key,
+            previous: Vec::new(),
+        }
+    }
+
+    /// AES-256-GCM (`AES256GCM`), 12-byte random nonce.
+    pub fn aes256gcm(key: SecretBytes) -> Self {
+        Self {
+            kind: SymmetricKind::Aes256Gcm,
+            key,
+            previous: Vec::new(),
+        }
+    }
+
+    /// Accept values encrypted with an older key version.
+    pub fn with_previous_key(mut self, key: SecretBytes) -> Self {
+        self.previous.push(key);
+        self
+    }
+
+    fn open_with(&self, key: &[u8], payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        match self.kind {
+            SymmetricKind::XChaCha20 => open::<XChaCha20Poly1305>(key, payload),
+            SymmetricKind::ChaCha20 => open::<ChaCha20Poly1305>(key, payload),
+            SymmetricKind::Aes256Gcm => open::<Aes256Gcm>(key, payload),
+        }
+    }
+}
+
+impl ValueCipher for SymmetricCipher {
+    fn algorithm(&self) -> &str {
+        match self.kind {
+            SymmetricKind::XChaCha20 => CipherAlgorithm::XChaCha20.as_str(),
+            SymmetricKind::ChaCha20 => CipherAlgorithm::ChaCha20.as_str(),
+            SymmetricKind::Aes256Gcm => CipherAlgorithm::Aes256Gcm.as_str(),
+        }
+    }
+
+    fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let key = self.key.as_slice();
+        match self.kind {
+            SymmetricKind::XChaCha20 => seal::<XChaCha20Poly1305>(key, plaintext),
+            SymmetricKind::ChaCha20 => seal::<ChaCha20Poly1305>(key, plaintext),
+            SymmetricKind::Aes256Gcm => seal::<Aes256Gcm>(key, plaintext),
+        }
+    }
+
+    fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+        let primary = self.open_with(self.key.as_slice(), payload);
+        if primary.is_ok() {
+            return primary;
+        }
+        self.previous
+            .iter()
+            .find_map(|key| self.open_with(key.as_slice(), payload).ok())
+            .ok_or_else(|| primary.unwrap_err())
+    }
+}
+
+impl std::fmt::Debug for SymmetricCipher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymmetricCipher")
+            .field("algorithm", &self.algorithm())
+            .field("key", &"[REDACTED]")
+            .field("previous_keys", &self.previous.len())
+            .finish()
+    }
+}
+
+fn seal<C: KeyInit + Aead>(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKeyLength(key.len()));
+    }
+    let cipher = C::new_from_slice(key).map_err(|_| CryptoError::EncryptionFailed)?;
+
+    let mut payload = vec![0u8; <C as AeadCore>::NonceSize::USIZE];
+    OsRng.fill_bytes(&mut payload);
+    let ciphertext = cipher
+        .encrypt(Nonce::<C>::from_slice(&payload), plaintext)
+        .map_err(|_| CryptoError::EncryptionFailed)?;
+
+    payload.extend_from_slice(&ciphertext);
+    Ok(payload)
+}
+
+fn open<C: KeyInit + Aead>(key: &[u8], payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    if key.len() != 32 {
+        return Err(CryptoError::InvalidKeyLength(key.len()));
+    }
+    let nonce_size = <C as AeadCore>::NonceSize::USIZE;
+    if payload.len() < nonce_size {
+        return Err(CryptoError::DecryptionFailed);
+    }
+    let cipher = C::new_from_slice(key).map_err(|_| CryptoError::DecryptionFailed)?;
+
+    let (nonce, ciphertext) = payload.split_at(nonce_size);
+    cipher
+        .decrypt(Nonce::<C>::from_slice(nonce), ciphertext)
+        .map_err(|_| CryptoError::DecryptionFailed)
+}
+
+/// Ciphers available for `enc:` values, keyed by algorithm tag.
+#[derive(Clone, Default)]
+pub struct CipherRegistry {
+    ciphers: HashMap<String, Arc<dyn ValueCipher>>,
+}
+
+impl CipherRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a cipher, replacing any cipher with the same tag.
+    ///
+    /// Fails if the tag is empty or contains characters other than uppercase
+    /// ASCII letters, digits and `_`.
+    pub fn register(&mut self, cipher: Arc<dyn ValueCipher>) -> Result<(), CryptoError> {
+        let tag = cipher.algorithm();
+        if tag.is_empty()
+            || !tag
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+        {
+            return Err(CryptoError::UnsupportedAlgorithm(tag.to_string()));
+        }
+        self.ciphers.insert(tag.to_string(), cipher);
+        Ok(())
+    }
+
+    /// Look up a cipher by tag (case-insensitive).
+    pub fn get(&self, algorithm: &str) -> Option<&Arc<dyn ValueCipher>> {
+        self.ciphers
+            .get(algorithm)
+            .or_else(|| self.ciphers.get(&algorithm.to_ascii_uppercase()))
+    }
+
+    pub fn contains(&self, algorithm: &str) -> bool {
+        self.get(algorithm).is_some()
+    }
+
+    /// Registered algorithm tags, sorted.
+    pub fn algorithms(&self) -> Vec<&str> {
+        let mut tags: Vec<&str> = self.ciphers.keys().map(String::as_str).collect();
+        tags.sort_unstable();
+        tags
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ciphers.is_empty()
+    }
+}
+
+impl std::fmt::Debug for CipherRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CipherRegistry")
+            .field("algorithms", &self.algorithms())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_KEY: &[u8; 32] = b"0123456789abcdef0123456789abcdef"; // pragma: allowlist secret
+
+    fn key() -> SecretBytes {
+        SecretBytes::new(TEST_KEY.to_vec())
+    }
+
+    #[test]
+    fn test_symmetric_ciphers_round_trip() {
+        for cipher in [
+            SymmetricCipher::xchacha20(key()),
+            SymmetricCipher::chacha20(key()),
+            SymmetricCipher::aes256gcm(key()),
+        ] {
+            let payload = cipher.encrypt(b"plaintext").unwrap();
+            assert_eq!(cipher.decrypt(&payload).unwrap(), b"plaintext");
+        }
+    }
+
+    #[test]
+    fn test_symmetric_nonce_sizes() {
+        let x = SymmetricCipher::xchacha20(key()).encrypt(b"").unwrap();
+        let c = SymmetricCipher::chacha20(key()).encrypt(b"").unwrap();
+        let a = SymmetricCipher::aes256gcm(key()).encrypt(b"").unwrap();
+        // nonce + 16-byte tag
+        assert_eq!(x.len(), 24 + 16);
+        assert_eq!(c.len(), 12 + 16);
+        assert_eq!(a.len(), 12 + 16);
+    }
+
+    #[test]
+    fn test_ciphers_are_not_interchangeable() {
+        let payload = SymmetricCipher::chacha20(key()).encrypt(b"x").unwrap();
+        assert!(SymmetricCipher::aes256gcm(key()).decrypt(&payload).is_err());
+    }
+
+    #[test]
+    fn test_previous_key_is_used_for_decryption_only() {
+        let old = SecretBytes::new(vec![7u8; 32]);
+        let payload = SymmetricCipher::chacha20(SecretBytes::new(vec![7u8; 32]))
+            .encrypt(b"old")
+            .unwrap();
+
+        let rotated = SymmetricCipher::chacha20(key()).with_previous_key(old);
+        assert_eq!(rotated.decrypt(&payload).unwrap(), b"old");
+
+        let fresh = rotated.encrypt(b"new").unwrap();
+        assert!(SymmetricCipher::chacha20(key()).decrypt(&fresh).is_ok());
+    }
+
+    #[test]
+    fn test_symmetric_rejects_short_key() {
+        let cipher = SymmetricCipher::aes256gcm(SecretBytes::new(vec![0u8; 16]));
+        assert!(matches!(
+            cipher.encrypt(b"x").unwrap_err(),
+            CryptoError::InvalidKeyLength(16)
+        ));
+    }
+
+    #[test]
+    fn test_algorithm_aliases() {
+        assert_eq!(
+            "chacha20-poly1305".parse::<CipherAlgorithm>().unwrap(),
+            CipherAlgorithm::ChaCha20
+        );
+        assert_eq!(
+            "aes-256-gcm".parse::<CipherAlgorithm>().unwrap(),
+            CipherAlgorithm::Aes256Gcm
+        );
+    }
+
+    struct Reverse;
+
+    impl ValueCipher for Reverse {
+        fn algorithm(&self) -> &str {
+            "REVERSE"
+        }
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+            Ok(plaintext.iter().rev().copied().collect())
+        }
+        fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+            self.encrypt(payload)
+        }
+    }
+
+    #[test]
+    fn test_registry_lookup_is_case_insensitive() {
+        let mut registry = CipherRegistry::new();
+        registry.register(Arc::new(Reverse)).unwrap();
+        assert!(registry.contains("reverse"));
+        assert_eq!(registry.algorithms(), vec!["REVERSE"]);
+    }
+
+    #[test]
+    fn test_registry_rejects_invalid_tag() {
+        struct Bad;
+        impl ValueCipher for Bad {
+            fn algorithm(&self) -> &str {
+                "bad:tag"
+            }
+            fn encrypt(&self, p: &[u8]) -> Result<Vec<u8>, CryptoError> {
+                Ok(p.to_vec())
+            }
+            fn decrypt(&self, p: &[u8]) -> Result<Vec<u8>, CryptoError> {
+                Ok(p.to_vec())
+            }
+        }
+
+        let mut registry = CipherRegistry::new();
+        assert!(registry.register(Arc::new(Bad)).is_err());
+        assert!(registry.is_empty());
+    }
+}
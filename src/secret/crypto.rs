@@ -21,6 +21,12 @@ pub enum CryptoError {
     InvalidKeyLength(usize),
     #[error("legacy decryption failed (AES-256-GCM)")]
     LegacyDecryptionFailed,
+    #[error("invalid key material: {0}")]
+    InvalidKey(String),
+    #[error("unsupported cipher algorithm: {0}")]
+    UnsupportedAlgorithm(String),
+    #[error("no key configured for cipher {0}")]
+    MissingKey(String),
 }
 
 pub const NONCE_SIZE: usize = 24;
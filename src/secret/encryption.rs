@@ -0,0 +1,777 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Value-level encryption for configuration files.
+//!
+//! Encrypted values are plain strings of the form `enc:<ALGO>:<base64>` so they
+//! can live in any configuration format. `<ALGO>` selects a cipher from the
+//! [`CipherRegistry`]; the built-in tags are:
+//!
+//! | Tag         | Cipher                          | Key material            |
+//! |-------------|---------------------------------|-------------------------|
+//! | `XCHACHA20` | XChaCha20-Poly1305              | 32-byte symmetric key   |
+//! | `CHACHA20`  | ChaCha20-Poly1305               | 32-byte symmetric key   |
+//! | `AES256GCM` | AES-256-GCM                     | 32-byte symmetric key   |
+//! | `AGE`       | age X25519 (`age` feature)      | recipients / identities |
+//!
+//! Values without an algorithm tag (`enc:<base64>`) are treated as `XCHACHA20`.
+//! Additional ciphers can be registered with [`ConfigEncryption::with_cipher`].
+//!
+//! # Key versions
+//!
+//! With [`ConfigEncryption::with_key_versions`] (e.g. the versions of a key
+//! ring), symmetric values are written as `enc:v<k>:<ALGO>:<base64>`, naming
+//! the key version `k` that encrypted them, and are decrypted with exactly
+//! that version. Values without the header are still accepted and tried
+//! against every version. [`ConfigEncryption::decrypt_tree_reporting`] lists
+//! the values that are not on the primary version yet, so a rotation can be
+//! rolled out gradually.
+//!
+//! # Zeroization
+//!
+//! Decrypted plaintext is wiped from memory when these types are dropped:
+//!
+//! - [`SecretString`] / [`SecretBytes`]: results of [`ConfigEncryption::decrypt`]
+//!   and [`ConfigEncryption::decrypt_file_contents`]; cipher output buffers are
+//!   held in `Zeroizing` until they have been copied into them.
+//! - [`ZeroizingValue`](crate::secret::ZeroizingValue): the merged tree inside
+//!   `ConfigBuilder::build()`, together with the JSON used to deserialize it.
+//! - `confers::security::SecureString` (`security` feature):
+//!   config fields marked `#[config(secret)]`.
+//!
+//! Not covered: plain `String` fields of the deserialized config, the tree
+//! returned by `ConfigBuilder::build_annotated()` and the output of
+//! [`ConfigEncryption::decrypt_tree`] / [`ConfigEncryption::decrypt_text`],
+//! which belong to the caller.
+
+use std::path::Path;
+use std::sync::{Arc, LazyLock};
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+
+use crate::error::{ConfigError, ConfigResult};
+#[cfg(feature = "age")]
+use crate::secret::AgeCipher;
+use crate::secret::{
+    CipherAlgorithm, CipherRegistry, CryptoError, KeyRegistry, SecretBytes, SecretString,
+    SymmetricCipher, ValueCipher,
+};
+use crate::types::AnnotatedValue;
+use regex::Regex;
+use zeroize::Zeroizing;
+
+/// Prefix marking an encrypted configuration value.
+pub const ENCRYPTED_VALUE_PREFIX: &str = "enc:";
+
+/// Extension marking a whole-file encrypted configuration (`app.toml.enc`).
+pub const ENCRYPTED_FILE_EXTENSION: &str = "enc";
+
+/// An `enc:` value embedded in a configuration file.
+static ENCRYPTED_TOKEN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"enc:(?:v[0-9]+:)?(?:[A-Z0-9_]+:)?[A-Za-z0-9+/]+={0,2}").unwrap());
+
+/// Encrypts and decrypts `enc:` configuration values.
+#[derive(Debug, Default, Clone)]
+pub struct ConfigEncryption {
+    registry: CipherRegistry,
+    /// Symmetric ciphers of each key version, primary first.
+    versions: Vec<(u32, CipherRegistry)>,
+}
+
+/// An `enc:` value found by [`ConfigEncryption::decrypt_tree_reporting`] that
+/// is not encrypted with the primary key version, or does not name its
+/// version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StaleEncryptedValue {
+    /// Dotted path of the value.
+    pub path: String,
+    /// Key version that decrypted the value.
+    pub version: u32,
+    /// Whether the value carries an `enc:v<k>:` header.
+    pub versioned: bool,
+}
+
+impl ConfigEncryption {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the built-in symmetric ciphers (`XCHACHA20`, `CHACHA20`,
+    /// `AES256GCM`) with the given 32-byte key.
+    pub fn with_key(self, key: SecretBytes) -> Self {
+        self.with_keys(key, Vec::new())
+    }
+
+    /// Like [`ConfigEncryption::with_key`], additionally accepting values
+    /// encrypted with any of the `previous` key versions.
+    pub fn with_keys(self, primary: SecretBytes, previous: Vec<SecretBytes>) -> Self {
+        let symmetric = |new: fn(SecretBytes) -> SymmetricCipher| {
+            previous.iter().fold(
+                new(SecretBytes::new(primary.as_slice().to_vec())),
+                |cipher, key| cipher.with_previous_key(SecretBytes::new(key.as_slice().to_vec())),
+            )
+        };
+        self.with_builtin(symmetric(SymmetricCipher::xchacha20))
+            .with_builtin(symmetric(SymmetricCipher::chacha20))
+            .with_builtin(symmetric(SymmetricCipher::aes256gcm))
+    }
+
+    /// Like [`ConfigEncryption::with_keys`] for numbered key versions, primary
+    /// first (the order of `KeyManager::decryption_keys`).
+    ///
+    /// Symmetric values are then encrypted as `enc:v<k>:<ALGO>:...` with the
+    /// primary version, and values carrying a version header are decrypted
+    /// with that version only.
+    pub fn with_key_versions(self, keys: Vec<(u32, SecretBytes)>) -> Self {
+        let mut keys = keys.into_iter();
+        let Some((primary_version, primary)) = keys.next() else {
+            return self;
+        };
+        let previous: Vec<(u32, SecretBytes)> = keys.collect();
+
+        let mut versions = Vec::with_capacity(previous.len() + 1);
+        for (version, key) in std::iter::once((primary_version, &primary))
+            .chain(previous.iter().map(|(version, key)| (*version, key)))
+        {
+            let single = Self::new().with_key(SecretBytes::new(key.as_slice().to_vec()));
+            versions.push((version, single.registry));
+        }
+
+        let mut encryption =
+            self.with_keys(primary, previous.into_iter().map(|(_, key)| key).collect());
+        encryption.versions = versions;
+        encryption
+    }
+
+    /// Key version used to encrypt symmetric values, if key versions were
+    /// given with [`ConfigEncryption::with_key_versions`].
+    pub fn primary_version(&self) -> Option<u32> {
+        self.versions.first().map(|(version, _)| *version)
+    }
+
+    /// Use every key version held by a [`KeyRegistry`]; the primary version
+    /// encrypts and all versions can decrypt.
+    pub fn from_key_registry(registry: &KeyRegistry) -> Result<Self, CryptoError> {
+        let (primary_version, primary) = registry.get_primary_key()?;
+        let previous = registry
+            .get_all_versions()
+            .iter()
+            .filter(|v| **v != primary_version)
+            .map(|v| registry.get_key(v).map(|k| SecretBytes::new(k.to_vec())))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::new().with_keys(SecretBytes::new(primary.to_vec()), previous))
+    }
+
+    /// Register the age cipher used for `AGE` values.
+    #[cfg(feature = "age")]
+    pub fn with_age(self, age: AgeCipher) -> Self {
+        self.with_builtin(age)
+    }
+
+    /// Register a custom cipher, replacing any cipher with the same tag.
+    pub fn with_cipher(mut self, cipher: impl ValueCipher + 'static) -> Result<Self, CryptoError> {
+        self.registry.register(Arc::new(cipher))?;
+        Ok(self)
+    }
+
+    /// The registered ciphers.
+    pub fn registry(&self) -> &CipherRegistry {
+        &self.registry
+    }
+
+    fn with_builtin(self, cipher: impl ValueCipher + 'static) -> Self {
+        self.with_cipher(cipher)
+            .expect("built-in cipher tags are valid")
+    }
+
+    /// Whether `value` carries the `enc:` prefix.
+    pub fn is_encrypted(value: &str) -> bool {
+        value.len() > ENCRYPTED_VALUE_PREFIX.len() && value.starts_with(ENCRYPTED_VALUE_PREFIX)
+    }
+
+    /// Encrypt `plaintext` and return an `enc:<ALGO>:<base64>` value, or
+    /// `enc:v<k>:<ALGO>:<base64>` for symmetric ciphers when key versions are
+    /// configured.
+    pub fn encrypt(
+        &self,
+        algorithm: impl AsRef<str>,
+        plaintext: &str,
+    ) -> Result<String, CryptoError> {
+        let cipher = self.cipher(algorithm.as_ref())?;
+        let (header, cipher) = match self.versions.first() {
+            Some((version, registry)) => match registry.get(cipher.algorithm()) {
+                Some(versioned) => (format!("v{}:", version), versioned),
+                None => (String::new(), cipher),
+            },
+            None => (String::new(), cipher),
+        };
+        let payload = cipher.encrypt(plaintext.as_bytes())?;
+
+        Ok(format!(
+            "{}{}{}:{}",
+            ENCRYPTED_VALUE_PREFIX,
+            header,
+            cipher.algorithm(),
+            BASE64.encode(payload)
+        ))
+    }
+
+    /// Decrypt an `enc:` value.
+    pub fn decrypt(&self, value: &str) -> Result<SecretString, CryptoError> {
+        self.decrypt_versioned(value).map(|(plain, _)| plain)
+    }
+
+    /// Decrypt an `enc:` value and report the key version that decrypted it:
+    /// the version named by its header, or for values without one the first
+    /// version whose key works. `None` when no key versions are configured
+    /// or the cipher does not use them (e.g. `AGE`).
+    pub(crate) fn decrypt_versioned(
+        &self,
+        value: &str,
+    ) -> Result<(SecretString, Option<u32>), CryptoError> {
+        let (version, algorithm, encoded) = parse_encrypted(value)?;
+        let payload = BASE64
+            .decode(encoded)
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+        let cipher = self.cipher(algorithm)?;
+
+        let versioned = self
+            .versions
+            .iter()
+            .filter(|(_, registry)| registry.contains(cipher.algorithm()));
+        let (plaintext, version) = match version {
+            Some(version) if !self.versions.is_empty() => {
+                let (_, registry) = self
+                    .versions
+                    .iter()
+                    .find(|(v, _)| *v == version)
+                    .ok_or_else(|| CryptoError::MissingKey(format!("key version {}", version)))?;
+                let cipher = registry
+                    .get(cipher.algorithm())
+                    .ok_or_else(|| CryptoError::UnsupportedAlgorithm(algorithm.to_string()))?;
+                (Zeroizing::new(cipher.decrypt(&payload)?), Some(version))
+            }
+            _ => match versioned
+                .map(|(version, registry)| (*version, registry.get(cipher.algorithm())))
+                .find_map(|(version, cipher)| {
+                    cipher?
+                        .decrypt(&payload)
+                        .ok()
+                        .map(|plain| (Zeroizing::new(plain), Some(version)))
+                }) {
+                Some(found) => found,
+                None => (Zeroizing::new(cipher.decrypt(&payload)?), None),
+            },
+        };
+
+        std::str::from_utf8(&plaintext)
+            .map(|plain| (SecretString::new(plain), version))
+            .map_err(|_| CryptoError::DecryptionFailed)
+    }
+
+    /// Decrypt every `enc:` string in a configuration tree.
+    pub fn decrypt_tree(&self, value: AnnotatedValue) -> ConfigResult<AnnotatedValue> {
+        self.decrypt_tree_reporting(value).map(|(value, _)| value)
+    }
+
+    /// Like [`ConfigEncryption::decrypt_tree`], also returning the values that
+    /// are not encrypted with the primary key version or lack a version
+    /// header. Always empty without [`ConfigEncryption::with_key_versions`].
+    pub fn decrypt_tree_reporting(
+        &self,
+        value: AnnotatedValue,
+    ) -> ConfigResult<(AnnotatedValue, Vec<StaleEncryptedValue>)> {
+        let primary = self.primary_version();
+        let mut stale = Vec::new();
+        let value = value.try_map_strings::<ConfigError, _>(&mut |path: &str, s: &str| {
+            if !Self::is_encrypted(s) {
+                return Ok(None);
+            }
+            let (plain, version) =
+                self.decrypt_versioned(s)
+                    .map_err(|e| ConfigError::DecryptionFailed {
+                        message: format!("'{}': {}", path, e),
+                    })?;
+            if let (Some(version), Some(primary)) = (version, primary) {
+                let versioned = matches!(parse_encrypted(s), Ok((Some(_), _, _)));
+                if version != primary || !versioned {
+                    stale.push(StaleEncryptedValue {
+                        path: path.to_string(),
+                        version,
+                        versioned,
+                    });
+                }
+            }
+            Ok(Some(plain.expose_clone()))
+        })?;
+        Ok((value, stale))
+    }
+
+    /// Decrypt every `enc:` token found in free-form text, leaving the rest of
+    /// the text (formatting, comments, quoting) untouched.
+    ///
+    /// Returns the rewritten text and the number of values decrypted.
+    pub fn decrypt_text(&self, text: &str) -> Result<(String, usize), CryptoError> {
+        // Plaintext is always shorter than its `enc:` token, so the output never
+        // reallocates and leaves no stray copies of decrypted values behind.
+        let mut output = String::with_capacity(text.len());
+        let mut count = 0;
+        let mut last = 0;
+
+        for token in ENCRYPTED_TOKEN.find_iter(text) {
+            let plain = self.decrypt(token.as_str())?;
+            output.push_str(&text[last..token.start()]);
+            output.push_str(plain.expose());
+            last = token.end();
+            count += 1;
+        }
+        output.push_str(&text[last..]);
+
+        Ok((output, count))
+    }
+
+    /// Re-encrypt every `enc:` token in free-form text with the current
+    /// primary key, keeping each value's algorithm. Used after a key rotation
+    /// so files no longer depend on retired key versions.
+    ///
+    /// Tokens whose algorithm has no registered cipher (e.g. `AGE` values when
+    /// only symmetric keys are configured) are left as they are. Returns the
+    /// rewritten text and the number of values re-encrypted.
+    pub fn reencrypt_text(&self, text: &str) -> Result<(String, usize), CryptoError> {
+        let mut output = String::with_capacity(text.len());
+        let mut count = 0;
+        let mut last = 0;
+
+        for token in ENCRYPTED_TOKEN.find_iter(text) {
+            let (_, algorithm, _) = parse_encrypted(token.as_str())?;
+            let cipher = match self.cipher(algorithm) {
+                Ok(cipher) => cipher,
+                Err(CryptoError::MissingKey(_) | CryptoError::UnsupportedAlgorithm(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            let plain = self.decrypt(token.as_str())?;
+            output.push_str(&text[last..token.start()]);
+            output.push_str(&self.encrypt(cipher.algorithm(), plain.expose())?);
+            last = token.end();
+            count += 1;
+        }
+        output.push_str(&text[last..]);
+
+        Ok((output, count))
+    }
+
+    /// Whether `path` names a whole-file encrypted configuration.
+    pub fn is_encrypted_file(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case(ENCRYPTED_FILE_EXTENSION))
+    }
+
+    /// Encrypt a whole configuration file; the result is a single
+    /// `enc:<ALGO>:<base64>` line.
+    pub fn encrypt_file_contents(
+        &self,
+        algorithm: impl AsRef<str>,
+        contents: &str,
+    ) -> Result<String, CryptoError> {
+        let mut encrypted = self.encrypt(algorithm, contents)?;
+        encrypted.push('\n');
+        Ok(encrypted)
+    }
+
+    /// Decrypt the contents of a file written by
+    /// [`ConfigEncryption::encrypt_file_contents`].
+    pub fn decrypt_file_contents(&self, contents: &str) -> Result<SecretString, CryptoError> {
+        self.decrypt(contents.trim())
+    }
+
+    /// Resolve a tag (or built-in alias) to a registered cipher.
+    fn cipher(&self, algorithm: &str) -> Result<&Arc<dyn ValueCipher>, CryptoError> {
+        let builtin = algorithm.parse::<CipherAlgorithm>().ok();
+        let tag = builtin.map_or(algorithm, |a| a.as_str());
+
+        self.registry.get(tag).ok_or_else(|| match builtin {
+            Some(a) => CryptoError::MissingKey(a.to_string()),
+            None => CryptoError::UnsupportedAlgorithm(algorithm.to_string()),
+        })
+    }
+}
+
+/// Every `enc:` token in free-form text, with its byte offset and algorithm tag.
+#[cfg(feature = "key")]
+pub(crate) fn encrypted_tokens(text: &str) -> impl Iterator<Item = (usize, &str, &str)> {
+    ENCRYPTED_TOKEN.find_iter(text).filter_map(|token| {
+        let (_, algorithm, _) = parse_encrypted(token.as_str()).ok()?;
+        Some((token.start(), algorithm, token.as_str()))
+    })
+}
+
+/// Split an `enc:` value into its key version, algorithm tag and base64
+/// payload.
+fn parse_encrypted(value: &str) -> Result<(Option<u32>, &str, &str), CryptoError> {
+    let body = value
+        .strip_prefix(ENCRYPTED_VALUE_PREFIX)
+        .ok_or(CryptoError::DecryptionFailed)?;
+
+    // Algorithm tags are upper case, so a lower-case `v<digits>:` is always a
+    // version header.
+    let (version, body) = match body.split_once(':') {
+        Some((header, rest)) if header.len() > 1 && header.starts_with('v') => {
+            let version = header[1..]
+                .parse::<u32>()
+                .map_err(|_| CryptoError::DecryptionFailed)?;
+            (Some(version), rest)
+        }
+        _ => (None, body),
+    };
+
+    // Base64 never contains ':', so a colon always separates an algorithm tag.
+    let (algorithm, payload) = body
+        .split_once(':')
+        .unwrap_or((CipherAlgorithm::XChaCha20.as_str(), body));
+    Ok((version, algorithm, payload))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ConfigValue, SourceId};
+
+    const TEST_KEY: &[u8; 32] = b"0123456789abcdef0123456789abcdef"; // pragma: allowlist secret
+
+    fn symmetric() -> ConfigEncryption {
+        ConfigEncryption::new().with_key(SecretBytes::new(TEST_KEY.to_vec()))
+    }
+
+    #[test]
+    fn test_xchacha20_round_trip() {
+        let enc = symmetric();
+        let value = enc.encrypt(CipherAlgorithm::XChaCha20, "s3cret").unwrap();
+        assert!(value.starts_with("enc:XCHACHA20:"));
+        assert_eq!(enc.decrypt(&value).unwrap().expose(), "s3cret");
+    }
+
+    #[test]
+    fn test_untagged_value_defaults_to_xchacha20() {
+        let enc = symmetric();
+        let tagged = enc.encrypt(CipherAlgorithm::XChaCha20, "legacy").unwrap();
+        let untagged = tagged.replace("enc:XCHACHA20:", "enc:");
+        assert_eq!(enc.decrypt(&untagged).unwrap().expose(), "legacy");
+    }
+
+    #[test]
+    fn test_unknown_algorithm_rejected() {
+        let err = symmetric().decrypt("enc:ROT13:AAAA").unwrap_err();
+        assert!(matches!(err, CryptoError::UnsupportedAlgorithm(ref a) if a == "ROT13"));
+    }
+
+    #[test]
+    fn test_missing_symmetric_key() {
+        let err = ConfigEncryption::new()
+            .encrypt(CipherAlgorithm::XChaCha20, "x")
+            .unwrap_err();
+        assert!(matches!(err, CryptoError::MissingKey(_)));
+    }
+
+    #[test]
+    fn test_each_symmetric_algorithm_round_trips() {
+        let enc = symmetric();
+        for algorithm in [
+            CipherAlgorithm::XChaCha20,
+            CipherAlgorithm::ChaCha20,
+            CipherAlgorithm::Aes256Gcm,
+        ] {
+            let value = enc.encrypt(algorithm, "v").unwrap();
+            assert!(value.starts_with(&format!("enc:{}:", algorithm)));
+            assert_eq!(enc.decrypt(&value).unwrap().expose(), "v");
+        }
+    }
+
+    struct Plain;
+
+    impl ValueCipher for Plain {
+        fn algorithm(&self) -> &str {
+            "PLAIN"
+        }
+        fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>, CryptoError> {
+            Ok(plaintext.to_vec())
+        }
+        fn decrypt(&self, payload: &[u8]) -> Result<Vec<u8>, CryptoError> {
+            Ok(payload.to_vec())
+        }
+    }
+
+    #[test]
+    fn test_custom_cipher_dispatch() {
+        let enc = ConfigEncryption::new().with_cipher(Plain).unwrap();
+        assert_eq!(enc.encrypt("plain", "abc").unwrap(), "enc:PLAIN:YWJj");
+        assert_eq!(enc.decrypt("enc:PLAIN:YWJj").unwrap().expose(), "abc");
+    }
+
+    #[test]
+    fn test_decrypt_text_preserves_surrounding_content() {
+        let enc = symmetric();
+        let secret = enc.encrypt(CipherAlgorithm::Aes256Gcm, "pw").unwrap();
+        let text = format!(
+            "# db\n[db]\npassword = \"{}\" # inline\nuser = \"enc\"\n",
+            secret
+        );
+
+        let (plain, count) = enc.decrypt_text(&text).unwrap();
+        assert_eq!(count, 1);
+        assert_eq!(
+            plain,
+            "# db\n[db]\npassword = \"pw\" # inline\nuser = \"enc\"\n"
+        );
+    }
+
+    #[test]
+    fn test_reencrypt_text_moves_values_to_primary_key() {
+        let old = ConfigEncryption::new().with_key(SecretBytes::new(vec![1u8; 32]));
+        let secret = old.encrypt(CipherAlgorithm::ChaCha20, "pw").unwrap();
+        let text = format!("password = \"{}\"\nother = \"enc:FOO:YWJj\"\n", secret);
+
+        let rotated = ConfigEncryption::new().with_keys(
+            SecretBytes::new(vec![2u8; 32]),
+            vec![SecretBytes::new(vec![1u8; 32])],
+        );
+        let (rewritten, count) = rotated.reencrypt_text(&text).unwrap();
+        assert_eq!(count, 1);
+        assert!(rewritten.contains("enc:CHACHA20:"));
+        assert!(rewritten.contains("other = \"enc:FOO:YWJj\""));
+
+        let current = ConfigEncryption::new().with_key(SecretBytes::new(vec![2u8; 32]));
+        let (plain, _) = current
+            .decrypt_text(&rewritten.replace("enc:FOO:YWJj", "x"))
+            .unwrap();
+        assert_eq!(plain, "password = \"pw\"\nother = \"x\"\n");
+    }
+
+    #[test]
+    fn test_from_key_registry_decrypts_older_versions() {
+        let registry = KeyRegistry::builder()
+            .initial_key("v1".to_string(), vec![1u8; 32], true)
+            .build();
+        let old = ConfigEncryption::from_key_registry(&registry).unwrap();
+        let value = old
+            .encrypt(CipherAlgorithm::XChaCha20, "v1-secret")
+            .unwrap();
+
+        registry
+            .rotate_to("v2".to_string(), SecretBytes::new(vec![2u8; 32]))
+            .unwrap();
+        let current = ConfigEncryption::from_key_registry(&registry).unwrap();
+        assert_eq!(current.decrypt(&value).unwrap().expose(), "v1-secret");
+
+        let fresh = current.encrypt(CipherAlgorithm::XChaCha20, "v2").unwrap();
+        assert!(old.decrypt(&fresh).is_err());
+    }
+
+    #[test]
+    fn test_file_contents_round_trip() {
+        let enc = symmetric();
+        let contents = "[server]\nport = 8080\n";
+        let encrypted = enc
+            .encrypt_file_contents(CipherAlgorithm::XChaCha20, contents)
+            .unwrap();
+        assert_eq!(encrypted.lines().count(), 1);
+        assert_eq!(
+            enc.decrypt_file_contents(&encrypted).unwrap().expose(),
+            contents
+        );
+        assert!(ConfigEncryption::is_encrypted_file(Path::new(
+            "app.toml.enc"
+        )));
+        assert!(!ConfigEncryption::is_encrypted_file(Path::new("app.toml")));
+    }
+
+    #[test]
+    fn test_algorithm_from_str_is_case_insensitive() {
+        assert_eq!(
+            "xchacha20".parse::<CipherAlgorithm>().unwrap(),
+            CipherAlgorithm::XChaCha20
+        );
+        assert!("des".parse::<CipherAlgorithm>().is_err());
+    }
+
+    fn versions(versions: &[u32]) -> ConfigEncryption {
+        ConfigEncryption::new().with_key_versions(
+            versions
+                .iter()
+                .map(|v| (*v, SecretBytes::new(vec![*v as u8; 32])))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_key_versions_are_written_and_honoured() {
+        let v1 = versions(&[1]);
+        let v2 = versions(&[2, 1]);
+        assert_eq!(v2.primary_version(), Some(2));
+        assert_eq!(symmetric().primary_version(), None);
+
+        let old = v1.encrypt(CipherAlgorithm::Aes256Gcm, "old").unwrap();
+        let new = v2.encrypt("chacha20", "new").unwrap();
+        assert!(old.starts_with("enc:v1:AES256GCM:"), "{}", old);
+        assert!(new.starts_with("enc:v2:CHACHA20:"), "{}", new);
+
+        assert_eq!(v2.decrypt_versioned(&old).unwrap().1, Some(1));
+        assert_eq!(v2.decrypt_versioned(&new).unwrap().1, Some(2));
+        assert!(matches!(
+            v1.decrypt(&new).unwrap_err(),
+            CryptoError::MissingKey(_)
+        ));
+
+        // The header picks the key: a value claiming the wrong version fails
+        let mislabeled = old.replacen("enc:v1:", "enc:v2:", 1);
+        assert!(v2.decrypt(&mislabeled).is_err());
+
+        // Without key versions the header is ignored and every key is tried
+        let plain = ConfigEncryption::new().with_keys(
+            SecretBytes::new(vec![2; 32]),
+            vec![SecretBytes::new(vec![1; 32])],
+        );
+        assert_eq!(plain.decrypt(&old).unwrap().expose(), "old");
+        assert_eq!(plain.decrypt(&new).unwrap().expose(), "new");
+    }
+
+    #[test]
+    fn test_unversioned_values_are_attributed_to_a_version() {
+        let legacy = ConfigEncryption::new()
+            .with_key(SecretBytes::new(vec![1; 32]))
+            .encrypt(CipherAlgorithm::XChaCha20, "legacy")
+            .unwrap();
+        let (plain, version) = versions(&[2, 1]).decrypt_versioned(&legacy).unwrap();
+        assert_eq!((plain.expose(), version), ("legacy", Some(1)));
+
+        let (text, count) = versions(&[2, 1])
+            .reencrypt_text(&format!("a = \"{}\"\n", legacy))
+            .unwrap();
+        assert_eq!(count, 1);
+        assert!(text.starts_with("a = \"enc:v2:XCHACHA20:"), "{}", text);
+    }
+
+    #[test]
+    fn test_decrypt_tree_reporting_lists_stale_values() {
+        let current = versions(&[2, 1]);
+        let leaf = |path: &str, value: String| {
+            (
+                path.to_string(),
+                AnnotatedValue::new(ConfigValue::string(value), SourceId::new("t"), path),
+            )
+        };
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![
+                leaf(
+                    "old",
+                    versions(&[1])
+                        .encrypt(CipherAlgorithm::XChaCha20, "1")
+                        .unwrap(),
+                ),
+                leaf(
+                    "current",
+                    current.encrypt(CipherAlgorithm::XChaCha20, "2").unwrap(),
+                ),
+                leaf(
+                    "legacy",
+                    ConfigEncryption::new()
+                        .with_key(SecretBytes::new(vec![2; 32]))
+                        .encrypt(CipherAlgorithm::XChaCha20, "3")
+                        .unwrap(),
+                ),
+            ]),
+            SourceId::new("t"),
+            "",
+        );
+
+        let (decrypted, stale) = current.decrypt_tree_reporting(root).unwrap();
+        assert_eq!(
+            decrypted.get_path("current").and_then(|v| v.as_str()),
+            Some("2")
+        );
+        assert_eq!(
+            stale,
+            [
+                StaleEncryptedValue {
+                    path: "old".to_string(),
+                    version: 1,
+                    versioned: true,
+                },
+                StaleEncryptedValue {
+                    path: "legacy".to_string(),
+                    version: 2,
+                    versioned: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decrypt_tree_replaces_encrypted_leaves() {
+        let enc = symmetric();
+        let secret = enc.encrypt(CipherAlgorithm::XChaCha20, "hunter2").unwrap();
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![
+                (
+                    "password",
+                    AnnotatedValue::new(
+                        ConfigValue::string(secret),
+                        SourceId::new("t"),
+                        "password",
+                    ),
+                ),
+                (
+                    "user",
+                    AnnotatedValue::new(ConfigValue::string("admin"), SourceId::new("t"), "user"),
+                ),
+            ]),
+            SourceId::new("t"),
+            "",
+        );
+
+        let decrypted = enc.decrypt_tree(root).unwrap();
+        let map = decrypted.inner.as_map().unwrap();
+        assert_eq!(map.get("password").unwrap().as_str(), Some("hunter2"));
+        assert_eq!(map.get("user").unwrap().as_str(), Some("admin"));
+    }
+
+    #[test]
+    fn test_decrypt_tree_reports_path_on_failure() {
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![(
+                "token",
+                AnnotatedValue::new(
+                    ConfigValue::string("enc:XCHACHA20:AAAA"),
+                    SourceId::new("t"),
+                    "token",
+                ),
+            )]),
+            SourceId::new("t"),
+            "",
+        );
+        let err = symmetric().decrypt_tree(root).unwrap_err();
+        assert!(
+            matches!(err, ConfigError::DecryptionFailed { ref message } if message.contains("token"))
+        );
+    }
+
+    #[cfg(feature = "age")]
+    #[test]
+    fn test_age_round_trip() {
+        use age::secrecy::ExposeSecret;
+
+        let identity = age::x25519::Identity::generate();
+        let age = AgeCipher::new()
+            .with_recipient(&identity.to_public().to_string())
+            .unwrap()
+            .with_identity(identity.to_string().expose_secret())
+            .unwrap();
+        let enc = ConfigEncryption::new().with_age(age);
+
+        let value = enc.encrypt(CipherAlgorithm::Age, "age-secret").unwrap();
+        assert!(value.starts_with("enc:AGE:"));
+        assert_eq!(enc.decrypt(&value).unwrap().expose(), "age-secret");
+    }
+}
@@ -3,11 +3,17 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
+#[cfg(feature = "age")]
+pub(crate) mod age;
 #[cfg(feature = "encryption")]
 pub(crate) mod bytes;
 #[cfg(feature = "encryption")]
+pub(crate) mod cipher;
+#[cfg(feature = "encryption")]
 pub(crate) mod crypto;
 #[cfg(feature = "encryption")]
+pub(crate) mod encryption;
+#[cfg(feature = "encryption")]
 pub(crate) mod key_provider;
 #[cfg(feature = "encryption")]
 pub(crate) mod key_registry;
@@ -18,11 +24,19 @@ pub(crate) mod string;
 #[cfg(feature = "encryption")]
 pub(crate) mod zeroizing;
 
+#[cfg(feature = "age")]
+pub use age::AgeCipher;
 #[cfg(feature = "encryption")]
 pub use bytes::SecretBytes;
 #[cfg(feature = "encryption")]
+pub use cipher::{CipherAlgorithm, CipherRegistry, SymmetricCipher, ValueCipher};
+#[cfg(feature = "encryption")]
 pub use crypto::{derive_field_key, CryptoError, XChaCha20Crypto, NONCE_SIZE};
 #[cfg(feature = "encryption")]
+pub use encryption::{
+    ConfigEncryption, StaleEncryptedValue, ENCRYPTED_FILE_EXTENSION, ENCRYPTED_VALUE_PREFIX,
+};
+#[cfg(feature = "encryption")]
 pub use key_provider::{EnvKeyProvider, EnvKeyProviderBuilder, SecretKeyProvider};
 #[cfg(feature = "encryption")]
 pub use key_registry::{KeyRegistry, KeyRegistryBuilder, KeyRotationConfig, KeyVersion};
@@ -33,4 +47,4 @@ pub use providers::{VaultKeyProvider, VaultKeyProviderBuilder};
 #[cfg(feature = "encryption")]
 pub use string::SecretString;
 #[cfg(feature = "encryption")]
-pub use zeroizing::{zeroizing_bytes, ZeroizingBytes};
+pub use zeroizing::{zeroizing_bytes, ZeroizingBytes, ZeroizingValue};
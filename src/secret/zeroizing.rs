@@ -3,6 +3,16 @@
 // Licensed under the MIT License
 // See LICENSE file in the project root for full license information.
 
+//! Zeroizing wrappers for key material and decrypted plaintext.
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use zeroize::Zeroize;
+
+use crate::types::{AnnotatedValue, ConfigValue};
+
 // Re-export unified ZeroizingBytes from crate::types (BrickArchitecture: single source of truth).
 pub use crate::types::ZeroizingBytes;
 
@@ -10,3 +20,149 @@ pub use crate::types::ZeroizingBytes;
 pub fn zeroizing_bytes(bytes: Vec<u8>) -> ZeroizingBytes {
     ZeroizingBytes::new(bytes)
 }
+
+/// A configuration tree whose string and byte leaves are wiped on drop.
+///
+/// `ConfigBuilder::build()` wraps the merged tree in this type once `enc:`
+/// values have been decrypted, so the plaintext copies it holds do not outlive
+/// deserialization. Subtrees still shared with another owner (an `Arc` with
+/// more than one reference) are left for that owner to release.
+pub struct ZeroizingValue(AnnotatedValue);
+
+impl ZeroizingValue {
+    pub fn new(value: AnnotatedValue) -> Self {
+        Self(value)
+    }
+
+    /// Take the tree back without wiping it.
+    pub fn into_inner(mut self) -> AnnotatedValue {
+        std::mem::take(&mut self.0)
+    }
+}
+
+impl Deref for ZeroizingValue {
+    type Target = AnnotatedValue;
+
+    fn deref(&self) -> &AnnotatedValue {
+        &self.0
+    }
+}
+
+impl Drop for ZeroizingValue {
+    fn drop(&mut self) {
+        zeroize_config_value(&mut self.0.inner);
+    }
+}
+
+impl fmt::Debug for ZeroizingValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ZeroizingValue([REDACTED])")
+    }
+}
+
+fn zeroize_config_value(value: &mut ConfigValue) {
+    match value {
+        ConfigValue::String(s) => s.zeroize(),
+        ConfigValue::Bytes(b) => b.zeroize(),
+        ConfigValue::Array(arr) => {
+            if let Some(items) = Arc::get_mut(arr) {
+                for item in items.iter_mut() {
+                    zeroize_config_value(&mut item.inner);
+                }
+            }
+        }
+        ConfigValue::Map(map) => {
+            if let Some(entries) = Arc::get_mut(map) {
+                for item in entries.values_mut() {
+                    zeroize_config_value(&mut item.inner);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Wipe every string in a JSON tree (keys are left intact).
+pub(crate) fn zeroize_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::String(s) => s.zeroize(),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(zeroize_json),
+        serde_json::Value::Object(map) => map.values_mut().for_each(zeroize_json),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::SourceId;
+    use indexmap::IndexMap;
+
+    fn leaf(value: ConfigValue) -> AnnotatedValue {
+        AnnotatedValue::new(value, SourceId::new("test"), "")
+    }
+
+    #[test]
+    fn test_zeroize_config_value_wipes_nested_strings() {
+        let mut map = IndexMap::new();
+        map.insert(
+            Arc::from("password"),
+            leaf(ConfigValue::String("hunter2".into())),
+        );
+        map.insert(
+            Arc::from("tokens"),
+            leaf(ConfigValue::Array(Arc::from(vec![leaf(
+                ConfigValue::String("abc".into()),
+            )]))),
+        );
+        let mut value = ConfigValue::Map(Arc::new(map));
+
+        zeroize_config_value(&mut value);
+
+        let ConfigValue::Map(map) = &value else {
+            unreachable!()
+        };
+        assert_eq!(map["password"].inner, ConfigValue::String(String::new()));
+        let ConfigValue::Array(tokens) = &map["tokens"].inner else {
+            unreachable!()
+        };
+        assert_eq!(tokens[0].inner, ConfigValue::String(String::new()));
+    }
+
+    #[test]
+    fn test_zeroize_config_value_skips_shared_subtrees() {
+        let shared = Arc::new(IndexMap::from([(
+            Arc::from("password"),
+            leaf(ConfigValue::String("hunter2".into())),
+        )]));
+        let mut value = ConfigValue::Map(shared.clone());
+
+        zeroize_config_value(&mut value);
+
+        assert_eq!(
+            shared["password"].inner,
+            ConfigValue::String("hunter2".into())
+        );
+    }
+
+    #[test]
+    fn test_into_inner_keeps_value() {
+        let value = ZeroizingValue::new(leaf(ConfigValue::String("plain".into())));
+        assert_eq!(format!("{:?}", value), "ZeroizingValue([REDACTED])");
+        assert_eq!(
+            value.into_inner().inner,
+            ConfigValue::String("plain".into())
+        );
+    }
+
+    #[test]
+    fn test_zeroize_json() {
+        let mut json =
+            serde_json::json!({"db": {"password": "hunter2", "hosts": ["a"]}, "port": 1});
+        zeroize_json(&mut json);
+        assert_eq!(
+            json,
+            serde_json::json!({"db": {"password": "", "hosts": [""]}, "port": 1})
+        );
+    }
+}
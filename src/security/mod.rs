@@ -21,8 +21,8 @@ pub(crate) fn is_encrypted_value(value: &str) -> bool {
 
 /// Validate the format of an encrypted value.
 ///
-/// Checks for proper "enc:" prefix and valid base64-encoded content.
-/// Returns an error if the format is invalid.
+/// Checks for proper "enc:" prefix, an optional `<ALGO>:` tag and valid
+/// base64-encoded content. Returns an error if the format is invalid.
 pub(crate) fn validate_encrypted_format(value: &str) -> Result<(), EnvSecurityError> {
     if !EncryptionPrefix::Enc.is_prefixed(value) {
         return Err(EnvSecurityError::InvalidValueFormat {
@@ -31,6 +31,21 @@ pub(crate) fn validate_encrypted_format(value: &str) -> Result<(), EnvSecurityEr
     }
 
     let encrypted_content = EncryptionPrefix::Enc.strip(value).unwrap_or("");
+    let encrypted_content = match encrypted_content.split_once(':') {
+        Some((tag, payload)) => {
+            if tag.is_empty()
+                || !tag
+                    .chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+            {
+                return Err(EnvSecurityError::InvalidValueFormat {
+                    reason: "Invalid algorithm tag in encrypted value".to_string(),
+                });
+            }
+            payload
+        }
+        None => encrypted_content,
+    };
     if encrypted_content.is_empty() {
         return Err(EnvSecurityError::InvalidValueFormat {
             reason: "Empty encrypted content".to_string(),
@@ -738,6 +753,14 @@ mod tests {
             .is_ok());
     }
 
+    #[test]
+    fn test_encrypted_format_accepts_algorithm_tag() {
+        assert!(validate_encrypted_format("enc:AGE:YWdlLWVuY3J5cHRpb24=").is_ok());
+        assert!(validate_encrypted_format("enc:XCHACHA20:QUJD").is_ok());
+        assert!(validate_encrypted_format("enc:age:QUJD").is_err());
+        assert!(validate_encrypted_format("enc::QUJD").is_err());
+    }
+
     #[test]
     fn test_global_config_functions() {
         let config = EnvironmentValidationConfig::new()
@@ -781,6 +804,7 @@ pub(crate) mod config_injector;
 pub(crate) mod error_sanitization;
 #[allow(dead_code)]
 pub(crate) mod input_validation;
+mod path_validator;
 #[cfg(feature = "encryption")]
 #[allow(dead_code)]
 pub(crate) mod secure_string;
@@ -789,3 +813,6 @@ pub use error_sanitization::{
     Error as SanitizationError, ErrorSanitizer, FilterResult, LogLevel, SafeResult, SecureLogger,
     SensitiveDataFilter,
 };
+pub use path_validator::{PathValidationError, PathValidator, ALLOWED_SECRET_BASE_DIRS};
+#[cfg(feature = "encryption")]
+pub use secure_string::{SecureString, SecureStringBuilder, SensitiveData, SensitivityLevel};
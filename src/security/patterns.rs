@@ -9,9 +9,10 @@
 //! used across the security module to avoid duplication.
 
 use regex::Regex;
-use std::collections::HashSet;
 use std::sync::LazyLock;
 
+pub(crate) use crate::error::redact::SENSITIVE_KEYWORDS;
+
 /// Canonical sensitive detection patterns for matching field names.
 ///
 /// Merged from `config_injector::DEFAULT_SENSITIVE_PATTERNS` and
@@ -37,29 +38,3 @@ pub(crate) static SENSITIVE_DETECTION_PATTERNS: LazyLock<Vec<Regex>> = LazyLock:
         Regex::new(r"(?i)connection_string").unwrap(),
     ]
 });
-
-/// Canonical sensitive keywords for high-sensitivity field detection.
-///
-/// Merged from `input_validation::default_high_sensitivity_keywords` and
-/// `error_sanitization::default_keywords`, deduplicated.
-pub(crate) static SENSITIVE_KEYWORDS: LazyLock<HashSet<&'static str>> = LazyLock::new(|| {
-    let mut set = HashSet::new();
-    set.insert("password");
-    set.insert("secret");
-    set.insert("token");
-    set.insert("key");
-    set.insert("credential");
-    set.insert("auth");
-    set.insert("private");
-    set.insert("encryption");
-    set.insert("private_key");
-    set.insert("master_key");
-    set.insert("encryption_key");
-    set.insert("api_secret");
-    set.insert("access_token");
-    set.insert("refresh_token");
-    set.insert("client_secret");
-    set.insert("db_password");
-    set.insert("admin_password");
-    set
-});
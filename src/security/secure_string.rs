@@ -327,8 +327,12 @@ impl ZeroizeOnDrop for SecureString {}
 
 impl fmt::Debug for SecureString {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // 调试时只显示掩码
-        write!(f, "SecureString({})", self.masked())
+        // 高敏感度数据不输出任何字符，其余只显示掩码
+        if self.is_highly_sensitive() && !self.is_empty() {
+            write!(f, "SecureString([SENSITIVE])")
+        } else {
+            write!(f, "SecureString({})", self.masked())
+        }
     }
 }
 
@@ -362,6 +366,62 @@ impl Deref for SecureString {
     }
 }
 
+impl Default for SecureString {
+    fn default() -> Self {
+        Self::from(String::new())
+    }
+}
+
+/// 序列化时只输出 `[REDACTED]`，明文永远不会进入 JSON / ConfigValue 等导出结果
+impl serde::Serialize for SecureString {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str("[REDACTED]")
+    }
+}
+
+/// 从配置值反序列化（Critical 级别）
+///
+/// 环境变量会被推断为数字或布尔值，因此标量也按其字符串形式接受。
+impl<'de> serde::Deserialize<'de> for SecureString {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SecureStringVisitor;
+
+        impl serde::de::Visitor<'_> for SecureStringVisitor {
+            type Value = SecureString;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<SecureString, E> {
+                Ok(SecureString::from(v))
+            }
+
+            fn visit_string<E: serde::de::Error>(self, v: String) -> Result<SecureString, E> {
+                Ok(SecureString::from(v))
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, v: i64) -> Result<SecureString, E> {
+                Ok(SecureString::from(v.to_string()))
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, v: u64) -> Result<SecureString, E> {
+                Ok(SecureString::from(v.to_string()))
+            }
+
+            fn visit_f64<E: serde::de::Error>(self, v: f64) -> Result<SecureString, E> {
+                Ok(SecureString::from(v.to_string()))
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, v: bool) -> Result<SecureString, E> {
+                Ok(SecureString::from(v.to_string()))
+            }
+        }
+
+        deserializer.deserialize_any(SecureStringVisitor)
+    }
+}
+
 /// 安全字符串构建器
 #[derive(Default)]
 pub struct SecureStringBuilder {
@@ -483,6 +543,26 @@ mod tests {
         assert!(debug.contains("SecureString"));
     }
 
+    #[test]
+    fn test_secure_string_debug_hides_critical_value() {
+        let critical = SecureString::from("password123");
+        assert_eq!(format!("{:?}", critical), "SecureString([SENSITIVE])");
+
+        let low = SecureString::new("password123", SensitivityLevel::Low);
+        assert!(format!("{:?}", low).contains('*'));
+    }
+
+    #[test]
+    fn test_secure_string_serde() {
+        let secret: SecureString = serde_json::from_str("\"s3cr3t\"").unwrap();
+        assert_eq!(secret.as_str(), "s3cr3t");
+        assert!(secret.is_highly_sensitive());
+        assert_eq!(serde_json::to_string(&secret).unwrap(), "\"[REDACTED]\"");
+
+        let numeric: SecureString = serde_json::from_str("12345").unwrap();
+        assert_eq!(numeric.as_str(), "12345");
+    }
+
     #[test]
     fn test_sensitivity_levels() {
         let critical = SecureString::new("secret", SensitivityLevel::Critical);
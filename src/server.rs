@@ -0,0 +1,12 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! HTTP endpoints for the live configuration — public facade.
+//!
+//! Implementation lives in `crate::impl_::server`. [`ConfigServer`] serves
+//! the merged, sanitized tree with health and metadata endpoints for
+//! sidecars and debugging tools; `confers serve` runs one from the CLI.
+
+pub use crate::impl_::server::{ConfigServer, DEFAULT_SERVER_BIND};
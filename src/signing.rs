@@ -0,0 +1,17 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Config file signing — public facade.
+//!
+//! Implementation lives in `crate::impl_::signing`. Files are signed with
+//! `confers sign` or [`sign_config`]/[`embed_signature`] and checked at load
+//! time with
+//! [`ConfigBuilder::with_signature_verification`](crate::ConfigBuilder::with_signature_verification).
+
+pub use crate::impl_::signing::{
+    embed_signature, load_signing_key, load_verifying_key, sign_config, signature_path,
+    verify_config_file, SIGNATURE_EXTENSION, SIGNATURE_FOOTER_PREFIX,
+};
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
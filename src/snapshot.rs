@@ -5,6 +5,16 @@
 
 //! Configuration snapshots — public facade.
 //!
-//! Implementation lives in `crate::impl_::snapshot`.
+//! Implementation lives in `crate::impl_::snapshot` and
+//! `crate::impl_::snapshot_store`. [`SnapshotManager`] keeps a history of
+//! redacted snapshots; [`ConfigSnapshotStore`] keeps the last known-good
+//! configuration for rollback; [`ChangeJournal`] records which keys each
+//! reload changed.
 
+pub use crate::impl_::journal::{
+    value_hash, ChangeJournal, JournalEntry, JournalSink, DEFAULT_CHANGE_JOURNAL, REDACTED_HASH,
+};
 pub use crate::impl_::snapshot::{SnapshotConfig, SnapshotFormat, SnapshotInfo, SnapshotManager};
+pub use crate::impl_::snapshot_store::{
+    ConfigSnapshotStore, KnownGoodSnapshot, SnapshotFile, DEFAULT_SNAPSHOT_STORE,
+};
@@ -0,0 +1,12 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Configuration template generation from a JSON Schema.
+//!
+//! Implementation lives in `crate::impl_::template`. Use it from a build
+//! script with a schema from `#[derive(ConfigSchema)]`, or through
+//! `confers generate --schema`.
+
+pub use crate::impl_::template::{generate_template, TemplateLevel};
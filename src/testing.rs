@@ -0,0 +1,13 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Test harness — public facade.
+//!
+//! Implementation lives in `crate::impl_::testing`. [`TestConfig`] writes
+//! fixture files into a temporary directory and sets environment variables
+//! that are restored when it is dropped; [`EnvGuard`] scopes a single
+//! variable.
+
+pub use crate::impl_::testing::{EnvGuard, TestConfig, TestConfigBuilder};
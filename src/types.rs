@@ -10,11 +10,15 @@
 //! - `AnnotatedValue` - Value with metadata (source, location, priority)
 //! - `SourceLocation` - Precise file location for error reporting
 //! - `ConflictReport` - Merge conflict information
+//! - `Duration` / `ByteSize` - Human-readable durations and byte sizes
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use std::time::Duration;
+
+mod units;
+
+pub use units::{ByteSize, Duration};
 
 // MergeStrategy is now imported directly from crate::merger.
 // This re-export was removed to fix a reverse dependency (value -> merger violates layering).
@@ -720,6 +724,23 @@ impl AnnotatedValue {
         }
     }
 
+    /// Look up a descendant by dot-notation path relative to this value,
+    /// e.g. `"database.pool"` or `"servers.0.host"` (array elements by index).
+    /// An empty path returns the value itself.
+    pub fn get_path(&self, path: &str) -> Option<&AnnotatedValue> {
+        if path.is_empty() {
+            return Some(self);
+        }
+        path.split('.')
+            .try_fold(self, |node, segment| match &node.inner {
+                ConfigValue::Map(map) => map.get(segment),
+                ConfigValue::Array(items) => {
+                    segment.parse::<usize>().ok().and_then(|i| items.get(i))
+                }
+                _ => None,
+            })
+    }
+
     /// Get all configuration paths from this value (including self).
     pub fn all_paths(&self) -> Vec<Arc<str>> {
         self.all_paths_internal(true)
@@ -856,6 +877,66 @@ impl AnnotatedValue {
             },
         )
     }
+
+    /// Rewrite every string leaf in the tree, keeping all other metadata.
+    ///
+    /// `f` receives the dot-notation path of the leaf (built the same way as
+    /// [`AnnotatedValue::all_paths`]) and the current string. Returning
+    /// `Ok(None)` leaves the leaf untouched.
+    #[cfg_attr(
+        not(any(feature = "encryption", feature = "interpolation")),
+        allow(dead_code)
+    )]
+    pub(crate) fn try_map_strings<E, F>(self, f: &mut F) -> Result<Self, E>
+    where
+        F: FnMut(&str, &str) -> Result<Option<String>, E>,
+    {
+        let mut path = self.path.to_string();
+        self.try_map_strings_at(&mut path, f)
+    }
+
+    fn try_map_strings_at<E, F>(mut self, path: &mut String, f: &mut F) -> Result<Self, E>
+    where
+        F: FnMut(&str, &str) -> Result<Option<String>, E>,
+    {
+        self.inner = match self.inner {
+            ConfigValue::String(s) => match f(path, &s)? {
+                Some(replaced) => ConfigValue::String(replaced),
+                None => ConfigValue::String(s),
+            },
+            ConfigValue::Map(map) => {
+                let mut out = IndexMap::with_capacity(map.len());
+                for (key, val) in map.iter() {
+                    let base_len = path.len();
+                    if base_len > 0 {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    let mapped = val.clone().try_map_strings_at(path, f);
+                    path.truncate(base_len);
+                    out.insert(key.clone(), mapped?);
+                }
+                ConfigValue::Map(Arc::new(out))
+            }
+            ConfigValue::Array(arr) => {
+                let mut out = Vec::with_capacity(arr.len());
+                for (i, val) in arr.iter().enumerate() {
+                    let base_len = path.len();
+                    if base_len > 0 {
+                        path.push('.');
+                    }
+                    let mut itoa_buf = itoa::Buffer::new();
+                    path.push_str(itoa_buf.format(i));
+                    let mapped = val.clone().try_map_strings_at(path, f);
+                    path.truncate(base_len);
+                    out.push(mapped?);
+                }
+                ConfigValue::Array(Arc::from(out))
+            }
+            other => other,
+        };
+        Ok(self)
+    }
 }
 
 impl Default for AnnotatedValue {
@@ -955,14 +1036,14 @@ pub enum KeyCachePolicy {
     /// Never cache keys — re-fetch on every access.
     NoCache,
     /// Cache with a time-to-live (defaults to 1 hour when constructed via [`Default`]).
-    CacheWithTtl(Duration),
+    CacheWithTtl(std::time::Duration),
     /// Cache indefinitely until explicitly invalidated.
     CacheIndefinitely,
 }
 
 impl Default for KeyCachePolicy {
     fn default() -> Self {
-        KeyCachePolicy::CacheWithTtl(Duration::from_secs(3600))
+        KeyCachePolicy::CacheWithTtl(std::time::Duration::from_secs(3600))
     }
 }
 
@@ -1042,7 +1123,8 @@ impl crate::interface::MetricsBackend for NoOpMetrics {
 // ============== Source-related data types (migrated from config/source.rs) ==============
 
 /// Kind of configuration source.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum SourceKind {
     /// File-based source
     File,
@@ -1961,11 +2043,40 @@ mod tests {
         assert!(paths.iter().any(|p| p.as_ref() == "arr.1"));
     }
 
+    #[test]
+    fn test_get_path() {
+        let servers = ConfigValue::array(vec![AnnotatedValue::new(
+            ConfigValue::map(vec![(
+                "host".to_string(),
+                AnnotatedValue::new(
+                    ConfigValue::string("a"),
+                    SourceId::new("t"),
+                    "servers.0.host",
+                ),
+            )]),
+            SourceId::new("t"),
+            "servers.0",
+        )]);
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![(
+                "servers".to_string(),
+                AnnotatedValue::new(servers, SourceId::new("t"), "servers"),
+            )]),
+            SourceId::new("t"),
+            "",
+        );
+        assert_eq!(root.get_path("servers.0.host").unwrap().as_str(), Some("a"));
+        assert_eq!(root.get_path("servers").unwrap().path.as_ref(), "servers");
+        assert_eq!(root.get_path("").unwrap().path.as_ref(), "");
+        assert!(root.get_path("servers.1").is_none());
+        assert!(root.get_path("servers.0.host.x").is_none());
+    }
+
     #[test]
     fn test_key_cache_policy_default() {
         let policy = KeyCachePolicy::default();
         match policy {
-            KeyCachePolicy::CacheWithTtl(d) => assert_eq!(d, Duration::from_secs(3600)),
+            KeyCachePolicy::CacheWithTtl(d) => assert_eq!(d, std::time::Duration::from_secs(3600)),
             other => panic!("expected CacheWithTtl, got {:?}", other),
         }
     }
@@ -1974,7 +2085,7 @@ mod tests {
     fn test_key_cache_policy_variants() {
         let no_cache = KeyCachePolicy::NoCache;
         let indefinite = KeyCachePolicy::CacheIndefinitely;
-        let with_ttl = KeyCachePolicy::CacheWithTtl(Duration::from_secs(60));
+        let with_ttl = KeyCachePolicy::CacheWithTtl(std::time::Duration::from_secs(60));
 
         assert_ne!(no_cache, indefinite);
         assert_ne!(indefinite, with_ttl);
@@ -2018,4 +2129,67 @@ mod tests {
         map.insert(a.clone(), 1);
         assert_eq!(map.get(&b), Some(&1));
     }
+
+    #[test]
+    fn test_try_map_strings_rewrites_nested_leaves() {
+        let inner = ConfigValue::map(vec![
+            (
+                "host",
+                AnnotatedValue::new(ConfigValue::string("a"), SourceId::new("t"), "db.host"),
+            ),
+            (
+                "port",
+                AnnotatedValue::new(ConfigValue::uint(5432), SourceId::new("t"), "db.port"),
+            ),
+        ]);
+        let tags = ConfigValue::array(vec![AnnotatedValue::new(
+            ConfigValue::string("b"),
+            SourceId::new("t"),
+            "tags.0",
+        )]);
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![
+                ("db", AnnotatedValue::new(inner, SourceId::new("t"), "db")),
+                (
+                    "tags",
+                    AnnotatedValue::new(tags, SourceId::new("t"), "tags"),
+                ),
+            ]),
+            SourceId::new("t"),
+            "",
+        );
+
+        let mut seen = Vec::new();
+        let mapped = root
+            .try_map_strings(&mut |path: &str, s: &str| -> Result<Option<String>, ()> {
+                seen.push(path.to_string());
+                Ok(Some(s.to_uppercase()))
+            })
+            .unwrap();
+
+        assert_eq!(seen, vec!["db.host".to_string(), "tags.0".to_string()]);
+        let db = mapped.inner.as_map().unwrap().get("db").unwrap();
+        assert_eq!(
+            db.inner.as_map().unwrap().get("host").unwrap().as_str(),
+            Some("A")
+        );
+        assert_eq!(
+            db.inner.as_map().unwrap().get("port").unwrap().as_u64(),
+            Some(5432)
+        );
+    }
+
+    #[test]
+    fn test_try_map_strings_propagates_error() {
+        let root = AnnotatedValue::new(
+            ConfigValue::map(vec![(
+                "k",
+                AnnotatedValue::new(ConfigValue::string("v"), SourceId::new("t"), "k"),
+            )]),
+            SourceId::new("t"),
+            "",
+        );
+        let result = root.try_map_strings(&mut |_: &str, _: &str| Err::<Option<String>, _>("boom"));
+        assert_eq!(result.unwrap_err(), "boom");
+    }
 }
@@ -0,0 +1,496 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! Human-readable durations and byte sizes.
+//!
+//! [`Duration`] and [`ByteSize`] deserialize from text such as `"30s"`,
+//! `"1h30m"` or `"512MB"`, so the same field can be set from a file, an
+//! environment variable or a remote source. Plain numbers are read as
+//! seconds and bytes respectively.
+
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{ConfigError, ConfigResult};
+
+/// Duration units, largest first: suffix and length in nanoseconds.
+const DURATION_UNITS: &[(&str, u128)] = &[
+    ("d", 86_400_000_000_000),
+    ("h", 3_600_000_000_000),
+    ("m", 60_000_000_000),
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ns", 1),
+];
+
+/// Byte size units, lower-case: suffix and multiplier.
+const BYTE_UNITS: &[(&str, u128)] = &[
+    ("b", 1),
+    ("kb", 1_000),
+    ("mb", 1_000_000),
+    ("gb", 1_000_000_000),
+    ("tb", 1_000_000_000_000),
+    ("k", 1 << 10),
+    ("kib", 1 << 10),
+    ("m", 1 << 20),
+    ("mib", 1 << 20),
+    ("g", 1 << 30),
+    ("gib", 1 << 30),
+    ("t", 1 << 40),
+    ("tib", 1 << 40),
+];
+
+/// Binary units used when displaying a [`ByteSize`], largest first.
+const BYTE_DISPLAY_UNITS: &[(&str, u64)] = &[
+    ("TiB", 1 << 40),
+    ("GiB", 1 << 30),
+    ("MiB", 1 << 20),
+    ("KiB", 1 << 10),
+];
+
+fn invalid(expected_type: &str, input: &str, message: impl fmt::Display) -> ConfigError {
+    ConfigError::InvalidValue {
+        key: String::new(),
+        expected_type: expected_type.to_string(),
+        message: format!("invalid {} '{}': {}", expected_type, input, message),
+    }
+}
+
+/// Report a parse error from a deserializer without the empty key.
+fn de_error<E: de::Error>(err: ConfigError) -> E {
+    match err {
+        ConfigError::InvalidValue { message, .. } => E::custom(message),
+        other => E::custom(other),
+    }
+}
+
+/// Split `text` into its leading number (digits with an optional fraction)
+/// and the rest.
+fn split_number(text: &str) -> (&str, &str) {
+    let end = text
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(text.len());
+    text.split_at(end)
+}
+
+/// `number * multiplier`, where `number` may have a decimal fraction.
+/// Fractions below one unit of the result are truncated.
+fn scale(number: &str, multiplier: u128) -> Option<u128> {
+    let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+    if whole.is_empty() && fraction.is_empty() {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // Digits past the 18th cannot change the result for any supported unit.
+    let fraction = &fraction[..fraction.len().min(18)];
+    let numerator: u128 = if fraction.is_empty() {
+        0
+    } else {
+        fraction.parse().ok()?
+    };
+    let fraction_value = numerator * multiplier / 10u128.pow(fraction.len() as u32);
+    whole.checked_mul(multiplier)?.checked_add(fraction_value)
+}
+
+/// A [`std::time::Duration`] read from text such as `"30s"`, `"5m"`,
+/// `"1h30m"` or `"250ms"`.
+///
+/// Units are `d`, `h`, `m`, `s`, `ms`, `us` (or `µs`) and `ns`; components
+/// may have a decimal fraction (`"1.5h"`) and be separated by spaces. A plain
+/// number is a number of seconds.
+///
+/// ```rust
+/// use confers::types::Duration;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Server {
+///     timeout: Duration,
+/// }
+///
+/// let server: Server = serde_json::from_str(r#"{"timeout": "1m30s"}"#).unwrap();
+/// assert_eq!(*server.timeout, std::time::Duration::from_secs(90));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Duration(pub std::time::Duration);
+
+impl Duration {
+    /// The wrapped [`std::time::Duration`].
+    pub fn into_inner(self) -> std::time::Duration {
+        self.0
+    }
+}
+
+impl FromStr for Duration {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> ConfigResult<Self> {
+        let text = s.trim();
+        if text.is_empty() {
+            return Err(invalid("duration", s, "empty duration"));
+        }
+        if scale(text, 1).is_some() {
+            let nanos = scale(text, 1_000_000_000)
+                .ok_or_else(|| invalid("duration", s, "value is too large"))?;
+            return nanos_to_duration(nanos)
+                .ok_or_else(|| invalid("duration", s, "value is too large"));
+        }
+
+        let mut nanos: u128 = 0;
+        let mut rest = text;
+        while !rest.is_empty() {
+            let (number, tail) = split_number(rest);
+            if number.is_empty() {
+                return Err(invalid("duration", s, "expected a number"));
+            }
+            let unit_end = tail
+                .find(|c: char| c.is_ascii_digit() || c == '.' || c.is_whitespace())
+                .unwrap_or(tail.len());
+            let unit = &tail[..unit_end];
+            let multiplier = DURATION_UNITS
+                .iter()
+                .find(|(suffix, _)| *suffix == unit)
+                .map(|(_, multiplier)| *multiplier)
+                .ok_or_else(|| {
+                    invalid(
+                        "duration",
+                        s,
+                        format!(
+                            "unknown unit '{}' (expected d, h, m, s, ms, us or ns)",
+                            unit
+                        ),
+                    )
+                })?;
+            nanos = scale(number, multiplier)
+                .and_then(|value| nanos.checked_add(value))
+                .ok_or_else(|| invalid("duration", s, "invalid or too large number"))?;
+            rest = tail[unit_end..].trim_start();
+        }
+        nanos_to_duration(nanos).ok_or_else(|| invalid("duration", s, "value is too large"))
+    }
+}
+
+fn nanos_to_duration(nanos: u128) -> Option<Duration> {
+    let secs = u64::try_from(nanos / 1_000_000_000).ok()?;
+    Some(Duration(std::time::Duration::new(
+        secs,
+        (nanos % 1_000_000_000) as u32,
+    )))
+}
+
+impl fmt::Display for Duration {
+    /// Formats with the largest units first, e.g. `1h30m` or `250ms`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut nanos = self.0.as_nanos();
+        if nanos == 0 {
+            return f.write_str("0s");
+        }
+        for (suffix, length) in DURATION_UNITS.iter().filter(|(s, _)| *s != "µs") {
+            if nanos >= *length {
+                write!(f, "{}{}", nanos / length, suffix)?;
+                nanos %= length;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Deref for Duration {
+    type Target = std::time::Duration;
+
+    fn deref(&self) -> &std::time::Duration {
+        &self.0
+    }
+}
+
+impl From<std::time::Duration> for Duration {
+    fn from(duration: std::time::Duration) -> Self {
+        Self(duration)
+    }
+}
+
+impl From<Duration> for std::time::Duration {
+    fn from(duration: Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Serialize for Duration {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Duration {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DurationVisitor;
+
+        impl Visitor<'_> for DurationVisitor {
+            type Value = Duration;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a duration such as \"30s\" or \"5m\", or a number of seconds")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Duration, E> {
+                v.parse().map_err(de_error)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Duration, E> {
+                Ok(Duration(std::time::Duration::from_secs(v)))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Duration, E> {
+                u64::try_from(v)
+                    .map(|secs| Duration(std::time::Duration::from_secs(secs)))
+                    .map_err(|_| E::custom("duration must not be negative"))
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Duration, E> {
+                std::time::Duration::try_from_secs_f64(v)
+                    .map(Duration)
+                    .map_err(|_| E::custom("duration must be a finite, non-negative number"))
+            }
+        }
+
+        deserializer.deserialize_any(DurationVisitor)
+    }
+}
+
+/// A number of bytes read from text such as `"512MB"`, `"1.5GiB"` or
+/// `"64k"`.
+///
+/// `KB`, `MB`, `GB` and `TB` are powers of 1000; `KiB`, `MiB`, `GiB` and
+/// `TiB`, and the short forms `k`, `m`, `g` and `t`, are powers of 1024.
+/// Units are case-insensitive and may follow a space. A plain number is a
+/// number of bytes.
+///
+/// ```rust
+/// use confers::types::ByteSize;
+///
+/// #[derive(serde::Deserialize)]
+/// struct Cache {
+///     max_size: ByteSize,
+/// }
+///
+/// let cache: Cache = serde_json::from_str(r#"{"max_size": "512MiB"}"#).unwrap();
+/// assert_eq!(cache.max_size.as_u64(), 512 * 1024 * 1024);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ByteSize(pub u64);
+
+impl ByteSize {
+    /// The size in bytes.
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
+impl FromStr for ByteSize {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> ConfigResult<Self> {
+        let text = s.trim();
+        let (number, unit) = split_number(text);
+        if number.is_empty() {
+            return Err(invalid("byte size", s, "expected a number"));
+        }
+        let unit = unit.trim().to_ascii_lowercase();
+        let multiplier = if unit.is_empty() {
+            1
+        } else {
+            BYTE_UNITS
+                .iter()
+                .find(|(suffix, _)| *suffix == unit)
+                .map(|(_, multiplier)| *multiplier)
+                .ok_or_else(|| {
+                    invalid(
+                        "byte size",
+                        s,
+                        format!(
+                            "unknown unit '{}' (expected B, KB, MB, GB, TB, KiB, MiB, GiB or TiB)",
+                            unit
+                        ),
+                    )
+                })?
+        };
+        scale(number, multiplier)
+            .and_then(|bytes| u64::try_from(bytes).ok())
+            .map(ByteSize)
+            .ok_or_else(|| invalid("byte size", s, "invalid or too large number"))
+    }
+}
+
+impl fmt::Display for ByteSize {
+    /// Formats with the largest binary unit that divides the size exactly,
+    /// e.g. `512MiB`, or in bytes, e.g. `1500B`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match BYTE_DISPLAY_UNITS
+            .iter()
+            .find(|(_, size)| self.0 != 0 && self.0.is_multiple_of(*size))
+        {
+            Some((suffix, size)) => write!(f, "{}{}", self.0 / size, suffix),
+            None => write!(f, "{}B", self.0),
+        }
+    }
+}
+
+impl From<u64> for ByteSize {
+    fn from(bytes: u64) -> Self {
+        Self(bytes)
+    }
+}
+
+impl From<ByteSize> for u64 {
+    fn from(size: ByteSize) -> Self {
+        size.0
+    }
+}
+
+impl Serialize for ByteSize {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ByteSize {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ByteSizeVisitor;
+
+        impl Visitor<'_> for ByteSizeVisitor {
+            type Value = ByteSize;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a byte size such as \"512MB\", or a number of bytes")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<ByteSize, E> {
+                v.parse().map_err(de_error)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<ByteSize, E> {
+                Ok(ByteSize(v))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<ByteSize, E> {
+                u64::try_from(v)
+                    .map(ByteSize)
+                    .map_err(|_| E::custom("byte size must not be negative"))
+            }
+        }
+
+        deserializer.deserialize_any(ByteSizeVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secs(secs: u64) -> Duration {
+        Duration(std::time::Duration::from_secs(secs))
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!("30s".parse::<Duration>().unwrap(), secs(30));
+        assert_eq!("5m".parse::<Duration>().unwrap(), secs(300));
+        assert_eq!("1h 30m".parse::<Duration>().unwrap(), secs(5400));
+        assert_eq!("1.5h".parse::<Duration>().unwrap(), secs(5400));
+        assert_eq!("2d".parse::<Duration>().unwrap(), secs(172_800));
+        assert_eq!("45".parse::<Duration>().unwrap(), secs(45));
+        assert_eq!(
+            *"250ms".parse::<Duration>().unwrap(),
+            std::time::Duration::from_millis(250)
+        );
+        assert_eq!(
+            *"1s500us".parse::<Duration>().unwrap(),
+            std::time::Duration::from_micros(1_000_500)
+        );
+    }
+
+    #[test]
+    fn test_parse_duration_errors() {
+        for input in ["", "s", "10x", "1.2.3s", "ms10", "99999999999999999999d"] {
+            let err = input.parse::<Duration>().unwrap_err();
+            assert!(
+                matches!(&err, ConfigError::InvalidValue { expected_type, .. } if expected_type == "duration"),
+                "{input}: {err}"
+            );
+        }
+        let err = "10x".parse::<Duration>().unwrap_err();
+        assert!(err.to_string().contains("unknown unit 'x'"), "{err}");
+    }
+
+    #[test]
+    fn test_duration_display_round_trips() {
+        for input in ["0s", "1h30m", "250ms", "2d1s", "1m0s5ns"] {
+            let duration: Duration = input.parse().unwrap();
+            assert_eq!(duration.to_string().parse::<Duration>().unwrap(), duration);
+        }
+        assert_eq!(secs(5400).to_string(), "1h30m");
+    }
+
+    #[test]
+    fn test_parse_byte_size() {
+        assert_eq!("512".parse::<ByteSize>().unwrap(), ByteSize(512));
+        assert_eq!("512MB".parse::<ByteSize>().unwrap(), ByteSize(512_000_000));
+        assert_eq!("512 MiB".parse::<ByteSize>().unwrap(), ByteSize(512 << 20));
+        assert_eq!("64k".parse::<ByteSize>().unwrap(), ByteSize(64 << 10));
+        assert_eq!("1.5gib".parse::<ByteSize>().unwrap(), ByteSize(3 << 29));
+        assert_eq!("10 b".parse::<ByteSize>().unwrap(), ByteSize(10));
+
+        assert!("MB".parse::<ByteSize>().is_err());
+        assert!("10 XB".parse::<ByteSize>().is_err());
+        assert!("20000000TiB".parse::<ByteSize>().is_err());
+    }
+
+    #[test]
+    fn test_byte_size_display() {
+        assert_eq!(ByteSize(512 << 20).to_string(), "512MiB");
+        assert_eq!(ByteSize(1500).to_string(), "1500B");
+        assert_eq!(ByteSize(0).to_string(), "0B");
+    }
+
+    #[test]
+    fn test_deserialize_from_strings_and_numbers() {
+        #[derive(Debug, Deserialize, Serialize, PartialEq)]
+        struct Limits {
+            timeout: Duration,
+            max_size: ByteSize,
+        }
+
+        let limits: Limits =
+            serde_json::from_str(r#"{"timeout": "2m", "max_size": "1KiB"}"#).unwrap();
+        assert_eq!(limits.timeout, secs(120));
+        assert_eq!(limits.max_size, ByteSize(1024));
+
+        let limits: Limits = serde_json::from_str(r#"{"timeout": 30, "max_size": 2048}"#).unwrap();
+        assert_eq!(limits.timeout, secs(30));
+        assert_eq!(limits.max_size, ByteSize(2048));
+
+        let json = serde_json::to_value(&limits).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"timeout": "30s", "max_size": "2KiB"})
+        );
+
+        let err =
+            serde_json::from_str::<Limits>(r#"{"timeout": "soon", "max_size": 1}"#).unwrap_err();
+        assert!(err.to_string().contains("duration"), "{err}");
+        assert!(serde_json::from_str::<Limits>(r#"{"timeout": -1, "max_size": 1}"#).is_err());
+    }
+}
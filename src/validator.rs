@@ -7,4 +7,9 @@
 //!
 //! Implementation lives in `crate::impl_::validator`.
 
+#[cfg(feature = "validation")]
 pub use crate::impl_::validator::{Validate, ValidationResult, ValidationRule};
+
+#[cfg(feature = "async-validation")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-validation")))]
+pub use crate::impl_::validator::AsyncValidate;
@@ -10,15 +10,186 @@
 
 #[cfg(feature = "watch")]
 use crate::error::{ConfigError, ConfigResult};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
 use std::time::Duration;
 use tokio::sync::mpsc;
 
 /// Default recv timeout in milliseconds for polling the debouncer.
 const DEFAULT_RECV_TIMEOUT_MS: u64 = 50;
 
+/// Which files inside a watched directory are reported.
+///
+/// Patterns without a `/` are matched against the file name (`*.toml`);
+/// patterns with a `/` are matched against the path relative to the watched
+/// directory (`overrides/*.yaml`). An exclude pattern without a `/` also
+/// matches any directory on the way, so `.git` or `*.bak` hide everything
+/// below them. With no include patterns every file is included.
+///
+/// # Example
+///
+/// ```rust
+/// use confers::watcher::WatchFilter;
+///
+/// let filter = WatchFilter::new()
+///     .recursive(true)
+///     .include("*.toml")?
+///     .exclude("*.swp")?;
+/// # Ok::<(), confers::ConfigError>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WatchFilter {
+    recursive: bool,
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl WatchFilter {
+    /// A filter that reports every file directly inside watched directories.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also report files in subdirectories, including ones created after the
+    /// watcher started.
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    /// Report only files matching `pattern` (or any other include pattern).
+    pub fn include(mut self, pattern: &str) -> ConfigResult<Self> {
+        self.include.push(Self::parse("watch.include", pattern)?);
+        Ok(self)
+    }
+
+    /// Never report files matching `pattern`.
+    pub fn exclude(mut self, pattern: &str) -> ConfigResult<Self> {
+        self.exclude.push(Self::parse("watch.exclude", pattern)?);
+        Ok(self)
+    }
+
+    /// Whether a file at `relative` (to the watched directory) is reported.
+    pub fn matches(&self, relative: &Path) -> bool {
+        if !self.recursive && relative.components().count() > 1 {
+            return false;
+        }
+        let excluded = self.exclude.iter().any(|pattern| {
+            if Self::has_separator(pattern) {
+                pattern.matches_path(relative)
+            } else {
+                relative
+                    .components()
+                    .any(|c| pattern.matches(&c.as_os_str().to_string_lossy()))
+            }
+        });
+        if excluded {
+            return false;
+        }
+        self.include.is_empty()
+            || self.include.iter().any(|pattern| {
+                if Self::has_separator(pattern) {
+                    pattern.matches_path(relative)
+                } else {
+                    relative
+                        .file_name()
+                        .is_some_and(|name| pattern.matches(&name.to_string_lossy()))
+                }
+            })
+    }
+
+    fn parse(key: &str, pattern: &str) -> ConfigResult<glob::Pattern> {
+        glob::Pattern::new(pattern).map_err(|e| ConfigError::InvalidValue {
+            key: key.to_string(),
+            expected_type: "glob pattern".to_string(),
+            message: format!("{}: {}", pattern, e),
+        })
+    }
+
+    fn has_separator(pattern: &glob::Pattern) -> bool {
+        pattern.as_str().contains('/')
+    }
+}
+
+/// Path of `event_path` relative to the canonical directory `dir`, if it is
+/// inside it. The parent is canonicalized rather than the path itself so
+/// removed files resolve too.
+fn relative_to(dir: &Path, event_path: &Path) -> Option<PathBuf> {
+    let parent = event_path.parent()?;
+    let parent = std::fs::canonicalize(parent).unwrap_or_else(|_| parent.to_path_buf());
+    let relative = parent.strip_prefix(dir).ok()?;
+    Some(relative.join(event_path.file_name()?))
+}
+
+/// Last seen content hash of each file, used to drop events for files that
+/// were touched but not changed.
+#[derive(Default)]
+struct ContentHashes {
+    enabled: AtomicBool,
+    hashes: Mutex<HashMap<PathBuf, u64>>,
+}
+
+impl ContentHashes {
+    /// Turn the check on or off. Enabling records the current content of the
+    /// watched files, so the first event after a touch-only write is dropped.
+    fn set_enabled<'a>(&self, enabled: bool, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        let mut hashes = self.hashes.lock().unwrap_or_else(|e| e.into_inner());
+        hashes.clear();
+        if enabled {
+            let mut pending: Vec<PathBuf> = paths.into_iter().cloned().collect();
+            while let Some(path) = pending.pop() {
+                if path.is_dir() {
+                    if let Ok(entries) = std::fs::read_dir(&path) {
+                        pending.extend(entries.flatten().map(|e| e.path()));
+                    }
+                } else if let Some(hash) = Self::hash_file(&path) {
+                    hashes.insert(Self::key(&path), hash);
+                }
+            }
+        }
+        self.enabled.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Whether an event for `path` should be forwarded: always when the check
+    /// is off or the file cannot be read, otherwise only when its content
+    /// differs from the last forwarded event.
+    fn changed(&self, path: &Path) -> bool {
+        if !self.enabled.load(Ordering::SeqCst) {
+            return true;
+        }
+        let key = Self::key(path);
+        let mut hashes = self.hashes.lock().unwrap_or_else(|e| e.into_inner());
+        match Self::hash_file(path) {
+            Some(hash) => hashes.insert(key, hash) != Some(hash),
+            None => {
+                hashes.remove(&key);
+                true
+            }
+        }
+    }
+
+    /// Drop the recorded hash of a removed file, so it is reported when it
+    /// comes back with the same content.
+    fn forget(&self, path: &Path) {
+        let mut hashes = self.hashes.lock().unwrap_or_else(|e| e.into_inner());
+        hashes.remove(&Self::key(path));
+    }
+
+    fn key(path: &Path) -> PathBuf {
+        std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+    }
+
+    fn hash_file(path: &Path) -> Option<u64> {
+        let content = std::fs::read(path).ok()?;
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        content.hash(&mut hasher);
+        Some(hasher.finish())
+    }
+}
+
 /// File system watcher with debouncing.
 ///
 /// This watcher monitors file changes and emits debounced events
@@ -34,6 +205,10 @@ pub struct FsWatcher {
     watcher_thread: Option<std::thread::JoinHandle<()>>,
     /// Running flag
     running: Arc<std::sync::atomic::AtomicBool>,
+    /// Content hashes for suppressing touch-only events
+    content_hashes: Arc<ContentHashes>,
+    /// Filter for files inside watched directories
+    filter: Arc<RwLock<Option<WatchFilter>>>,
 }
 
 impl Drop for FsWatcher {
@@ -97,8 +272,12 @@ impl FsWatcher {
 
         let (tx, rx) = mpsc::channel(100);
         let running = Arc::new(std::sync::atomic::AtomicBool::new(true));
+        let content_hashes = Arc::new(ContentHashes::default());
+        let filter = Arc::new(RwLock::new(None));
+        let filter_clone = Arc::clone(&filter);
         let path_clone = Arc::clone(&watch_path);
         let running_clone = Arc::clone(&running);
+        let hashes_clone = Arc::clone(&content_hashes);
         let tx_for_thread = tx.clone();
 
         // Spawn the watcher in a dedicated thread (not tokio task)
@@ -109,6 +288,8 @@ impl FsWatcher {
                 recv_timeout_ms,
                 tx_for_thread,
                 running_clone,
+                hashes_clone,
+                filter_clone,
             );
         });
 
@@ -118,9 +299,30 @@ impl FsWatcher {
             tx: Some(tx),
             watcher_thread: Some(watcher_thread),
             running,
+            content_hashes,
+            filter,
         })
     }
 
+    /// Suppress events for files whose content is byte-identical to the last
+    /// reported version (e.g. an editor saving without changes, or `touch`).
+    ///
+    /// The current content of the watched files is the baseline. Deletions
+    /// are always reported.
+    pub fn with_content_hash(self, enabled: bool) -> Self {
+        self.content_hashes
+            .set_enabled(enabled, std::iter::once(self.watch_path.as_ref()));
+        self
+    }
+
+    /// Restrict events for a watched directory to files accepted by `filter`.
+    ///
+    /// Without a filter every event below the directory is reported.
+    pub fn with_filter(self, filter: WatchFilter) -> Self {
+        *self.filter.write().unwrap_or_else(|e| e.into_inner()) = Some(filter);
+        self
+    }
+
     /// Receive the next file change event.
     ///
     /// Returns `Some(path)` when a file change is detected, `None` if the watcher is stopped.
@@ -170,6 +372,8 @@ impl FsWatcher {
         recv_timeout_ms: u64,
         tx: mpsc::Sender<PathBuf>,
         running: Arc<std::sync::atomic::AtomicBool>,
+        content_hashes: Arc<ContentHashes>,
+        filter: Arc<RwLock<Option<WatchFilter>>>,
     ) {
         use notify_debouncer_full::{
             new_debouncer, notify::EventKind, notify::RecursiveMode, DebounceEventResult,
@@ -196,6 +400,22 @@ impl FsWatcher {
             return;
         }
 
+        // A filter only applies when a directory is watched.
+        let watched_dir = path
+            .is_dir()
+            .then(|| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+        let accepted = |event_path: &Path| {
+            let filter = filter.read().unwrap_or_else(|e| e.into_inner());
+            match (filter.as_ref(), watched_dir.as_deref()) {
+                (Some(filter), Some(dir)) => {
+                    !event_path.is_dir()
+                        && relative_to(dir, event_path)
+                            .is_some_and(|relative| filter.matches(&relative))
+                }
+                _ => true,
+            }
+        };
+
         let recv_timeout = Duration::from_millis(recv_timeout_ms);
 
         // Process events
@@ -214,6 +434,11 @@ impl FsWatcher {
                                     // events on some platforms. Callers decide what to
                                     // do with the event.
                                     for event_path in &event.paths {
+                                        if !accepted(event_path)
+                                            || !content_hashes.changed(event_path)
+                                        {
+                                            continue;
+                                        }
                                         match tx.try_send(event_path.clone()) {
                                             Ok(_) => {}
                                             Err(mpsc::error::TrySendError::Full(_)) => {}
@@ -261,6 +486,10 @@ pub struct MultiFsWatcher {
     watcher_thread: Option<std::thread::JoinHandle<()>>,
     /// Running flag
     running: Arc<std::sync::atomic::AtomicBool>,
+    /// Content hashes for suppressing touch-only events
+    content_hashes: Arc<ContentHashes>,
+    /// Filter for files inside watched directories
+    filter: Arc<RwLock<Option<WatchFilter>>>,
 }
 
 impl Drop for MultiFsWatcher {
@@ -343,6 +572,10 @@ impl MultiFsWatcher {
         let paths_arc = Arc::new(watch_paths);
         let running_clone = Arc::clone(&running);
         let paths_for_thread = Arc::clone(&paths_arc);
+        let content_hashes = Arc::new(ContentHashes::default());
+        let hashes_clone = Arc::clone(&content_hashes);
+        let filter = Arc::new(RwLock::new(None));
+        let filter_clone = Arc::clone(&filter);
         let tx_for_thread = tx.clone();
 
         // Spawn the watcher in a dedicated thread (not tokio task)
@@ -353,6 +586,8 @@ impl MultiFsWatcher {
                 recv_timeout_ms,
                 tx_for_thread,
                 running_clone,
+                hashes_clone,
+                filter_clone,
             );
         });
 
@@ -362,9 +597,29 @@ impl MultiFsWatcher {
             tx: Some(tx),
             watcher_thread: Some(watcher_thread),
             running,
+            content_hashes,
+            filter,
         })
     }
 
+    /// Suppress events for files whose content is byte-identical to the last
+    /// reported version. See [`FsWatcher::with_content_hash`].
+    pub fn with_content_hash(self, enabled: bool) -> Self {
+        self.content_hashes
+            .set_enabled(enabled, self.watch_paths.iter());
+        self
+    }
+
+    /// Choose which files inside watched directories are reported.
+    ///
+    /// By default only files directly inside a watched directory are
+    /// reported; use [`WatchFilter::recursive`] for nested `conf.d/` trees.
+    /// Explicitly watched files are always reported.
+    pub fn with_filter(self, filter: WatchFilter) -> Self {
+        *self.filter.write().unwrap_or_else(|e| e.into_inner()) = Some(filter);
+        self
+    }
+
     /// Receive the next file change event.
     ///
     /// Returns `Some(path)` when a file change is detected, `None` if the watcher is stopped.
@@ -414,6 +669,8 @@ impl MultiFsWatcher {
         recv_timeout_ms: u64,
         tx: mpsc::Sender<PathBuf>,
         running: Arc<std::sync::atomic::AtomicBool>,
+        content_hashes: Arc<ContentHashes>,
+        filter: Arc<RwLock<Option<WatchFilter>>>,
     ) {
         use notify_debouncer_full::{
             new_debouncer, notify::EventKind, notify::RecursiveMode, DebounceEventResult,
@@ -434,6 +691,27 @@ impl MultiFsWatcher {
                 }
             };
 
+        // Files inside watched directories are reported too, so drop-in files
+        // that are added or removed after the watcher started are seen.
+        let watched_dirs: Vec<PathBuf> = paths
+            .iter()
+            .filter(|p| p.is_dir())
+            .map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.clone()))
+            .collect();
+        let is_watched = |event_path: &Path| {
+            if paths.contains(event_path) {
+                return true;
+            }
+            let filter = filter.read().unwrap_or_else(|e| e.into_inner());
+            let filter = filter.as_ref();
+            watched_dirs.iter().any(|dir| {
+                relative_to(dir, event_path).is_some_and(|relative| match filter {
+                    Some(filter) => filter.matches(&relative),
+                    None => relative.components().count() == 1,
+                })
+            })
+        };
+
         // Watch all paths
         for path in paths {
             if path.is_dir() {
@@ -456,7 +734,10 @@ impl MultiFsWatcher {
                             match event.kind {
                                 EventKind::Create(_) | EventKind::Modify(_) => {
                                     for event_path in &event.paths {
-                                        if event_path.is_file() && paths.contains(event_path) {
+                                        if event_path.is_file()
+                                            && is_watched(event_path)
+                                            && content_hashes.changed(event_path)
+                                        {
                                             match tx.try_send(event_path.clone()) {
                                                 Ok(_) => {}
                                                 Err(mpsc::error::TrySendError::Full(_)) => {}
@@ -474,9 +755,10 @@ impl MultiFsWatcher {
                                 EventKind::Remove(_) => {
                                     // For Remove, the path no longer exists so
                                     // is_file() returns false. Check only
-                                    // paths.contains() to forward deletions.
+                                    // is_watched() to forward deletions.
                                     for event_path in &event.paths {
-                                        if paths.contains(event_path) {
+                                        if is_watched(event_path) {
+                                            content_hashes.forget(event_path);
                                             match tx.try_send(event_path.clone()) {
                                                 Ok(_) => {}
                                                 Err(mpsc::error::TrySendError::Full(_)) => {}
@@ -13,16 +13,23 @@ pub(crate) mod progressive;
 #[cfg(feature = "watch")]
 pub(crate) mod fs_watcher;
 
+#[cfg(feature = "watch")]
+pub(crate) mod reload_handle;
+
 pub use debounce::AdaptiveDebouncer;
 
 #[cfg(feature = "progressive-reload")]
 pub use progressive::{
     HealthStatus, ProgressiveReloader, ProgressiveReloaderBuilder, ReloadHealthCheck,
-    ReloadOutcome, ReloadStrategy,
+    ReloadLatencyMetrics, ReloadOutcome, ReloadStrategy, ReloadSubscription,
+    DEFAULT_CANARY_CHECK_TIMEOUT,
 };
 
 #[cfg(feature = "watch")]
-pub use fs_watcher::{FsWatcher, MultiFsWatcher};
+pub use fs_watcher::{FsWatcher, MultiFsWatcher, WatchFilter};
+
+#[cfg(feature = "watch")]
+pub use reload_handle::{ReloadHandle, ReloadTriggers};
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -5,14 +5,20 @@
 
 //! Progressive Reload - Staged configuration deployment with health checks.
 
-use std::sync::Arc;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
+use serde_json::Value;
 
 use crate::error::{ConfigError, ConfigResult};
 use crate::interface::ConfigProvider;
+#[cfg(feature = "snapshot")]
+use crate::snapshot::{ConfigSnapshotStore, JournalEntry, JournalSink, KnownGoodSnapshot};
 
 /// Reload strategy for hot reload.
 #[derive(Debug, Clone, Default)]
@@ -29,6 +35,59 @@ pub enum ReloadStrategy {
     },
 }
 
+impl ReloadStrategy {
+    /// Lower-case strategy name, as recorded in audit events and metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReloadStrategy::Immediate => "immediate",
+            ReloadStrategy::Canary { .. } => "canary",
+            ReloadStrategy::Linear { .. } => "linear",
+        }
+    }
+}
+
+/// Latency of the reloads handled by a [`ProgressiveReloader`], measured from
+/// `begin_reload` until the candidate is committed or rolled back.
+#[derive(Debug, Default)]
+pub struct ReloadLatencyMetrics {
+    count: AtomicU64,
+    total_micros: AtomicU64,
+    last_micros: AtomicU64,
+    max_micros: AtomicU64,
+}
+
+impl ReloadLatencyMetrics {
+    pub fn record(&self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.total_micros.fetch_add(micros, Ordering::Relaxed);
+        self.last_micros.store(micros, Ordering::Relaxed);
+        self.max_micros.fetch_max(micros, Ordering::Relaxed);
+    }
+
+    /// Number of reloads recorded.
+    pub fn count(&self) -> u64 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Latency of the most recent reload.
+    pub fn last(&self) -> Option<Duration> {
+        (self.count() > 0).then(|| Duration::from_micros(self.last_micros.load(Ordering::Relaxed)))
+    }
+
+    /// Highest latency recorded.
+    pub fn max(&self) -> Duration {
+        Duration::from_micros(self.max_micros.load(Ordering::Relaxed))
+    }
+
+    /// Mean latency over all recorded reloads.
+    pub fn mean(&self) -> Option<Duration> {
+        let count = self.count();
+        (count > 0)
+            .then(|| Duration::from_micros(self.total_micros.load(Ordering::Relaxed) / count))
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReloadOutcome {
     Committed,
@@ -61,11 +120,228 @@ pub trait ReloadHealthCheck: Send + Sync {
     async fn check(&self, provider: Arc<dyn ConfigProvider>) -> HealthStatus;
 }
 
+/// Time a [canary check](ProgressiveReloader::with_canary_check) may take
+/// unless [`with_canary_check_timeout`](ProgressiveReloader::with_canary_check_timeout)
+/// says otherwise.
+pub const DEFAULT_CANARY_CHECK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Check registered with [`ProgressiveReloader::with_canary_check`]; resolves
+/// to the reason the candidate was rejected.
+type CanaryCheck<T> =
+    Arc<dyn Fn(Arc<T>) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send>> + Send + Sync>;
+
+fn canary_check<T, F, Fut, E>(check: F) -> CanaryCheck<T>
+where
+    F: Fn(Arc<T>) -> Fut + Send + Sync + 'static,
+    Fut: Future<Output = Result<(), E>> + Send + 'static,
+    E: std::fmt::Display,
+{
+    Arc::new(move |config| {
+        let check = check(config);
+        Box::pin(async move { check.await.map_err(|e| e.to_string()) })
+    })
+}
+
+/// Known-good store written after every committed reload. `save` is
+/// instantiated where `T: Serialize` is known, so the reloader itself does
+/// not need the bound.
+#[cfg(feature = "snapshot")]
+struct ReloadSnapshot<T> {
+    store: Arc<ConfigSnapshotStore>,
+    save: fn(&ConfigSnapshotStore, &T) -> ConfigResult<KnownGoodSnapshot>,
+}
+
+#[cfg(feature = "snapshot")]
+impl<T: serde::Serialize> ReloadSnapshot<T> {
+    fn new(store: Arc<ConfigSnapshotStore>) -> Self {
+        Self {
+            store,
+            save: ConfigSnapshotStore::save::<T>,
+        }
+    }
+}
+
+/// Journal appended to after every committed reload. `to_json` is
+/// instantiated where `T: Serialize` is known, like [`ReloadSnapshot::save`].
+#[cfg(feature = "snapshot")]
+struct ReloadJournal<T> {
+    sink: Arc<dyn JournalSink>,
+    to_json: fn(&T) -> Option<Value>,
+}
+
+#[cfg(feature = "snapshot")]
+impl<T: serde::Serialize> ReloadJournal<T> {
+    fn new(sink: Arc<dyn JournalSink>) -> Self {
+        Self {
+            sink,
+            to_json: |config| serde_json::to_value(config).ok(),
+        }
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<T> ReloadJournal<T> {
+    /// Append the keys that differ between `old` and `new`, with the source
+    /// `provider` reports for each new value.
+    fn record(&self, old: &T, new: &T, provider: &dyn ConfigProvider) -> ConfigResult<()> {
+        let (Some(old), Some(new)) = ((self.to_json)(old), (self.to_json)(new)) else {
+            return Ok(());
+        };
+        let source = |key: &str| provider.get_raw(key).map(|value| value.source.to_string());
+        let entries = JournalEntry::diff(&old, &new, source, &crate::error::Redactor::new());
+        self.sink.append(&entries)
+    }
+}
+
+/// Callback registered with [`ProgressiveReloader::subscribe`], called with the
+/// old and new value of its key.
+type KeyCallback = Arc<dyn Fn(Option<&Value>, Option<&Value>) + Send + Sync>;
+
+/// Callback registered with [`ProgressiveReloader::on_field_change`], which
+/// compares the old and new configuration itself.
+type FieldCallback<T> = Arc<dyn Fn(&T, &T) + Send + Sync>;
+
+/// A registered change callback.
+enum Subscriber<T> {
+    /// Compared on the serialized configurations at a dotted key.
+    Key(Arc<str>, KeyCallback),
+    /// Compared on a field of `T`.
+    Field(FieldCallback<T>),
+}
+
+impl<T> Clone for Subscriber<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Subscriber::Key(path, callback) => Subscriber::Key(path.clone(), callback.clone()),
+            Subscriber::Field(callback) => Subscriber::Field(callback.clone()),
+        }
+    }
+}
+
+/// Registered callbacks with their id.
+type Subscribers<T> = Arc<Mutex<Vec<(u64, Subscriber<T>)>>>;
+
+/// Change subscriptions. `to_json` is instantiated by `subscribe`, where
+/// `T: Serialize` is known, so the reloader itself does not need the bound.
+struct Subscriptions<T> {
+    to_json: OnceLock<fn(&T) -> Option<Value>>,
+    next_id: AtomicU64,
+    subscribers: Subscribers<T>,
+}
+
+impl<T> Default for Subscriptions<T> {
+    fn default() -> Self {
+        Self {
+            to_json: OnceLock::new(),
+            next_id: AtomicU64::new(0),
+            subscribers: Arc::default(),
+        }
+    }
+}
+
+impl<T: 'static> Subscriptions<T> {
+    /// Add `subscriber`, returning the guard that removes it again.
+    fn register(&self, subscriber: Subscriber<T>) -> ReloadSubscription {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        lock(&self.subscribers).push((id, subscriber));
+
+        let subscribers = Arc::downgrade(&self.subscribers);
+        ReloadSubscription {
+            remover: Some(Box::new(move || {
+                if let Some(subscribers) = subscribers.upgrade() {
+                    lock(&subscribers).retain(|(entry, _)| *entry != id);
+                }
+            })),
+        }
+    }
+
+    /// Call every callback whose key or field differs between `old` and
+    /// `new`. The configurations are serialized at most once, and only for
+    /// key subscribers.
+    ///
+    /// Callbacks are cloned out of the lock first, so a callback may
+    /// subscribe or drop its own guard.
+    fn notify(&self, old: &T, new: &T) {
+        let subscribers: Vec<Subscriber<T>> = lock(&self.subscribers)
+            .iter()
+            .map(|(_, subscriber)| subscriber.clone())
+            .collect();
+
+        let mut json = None;
+        for subscriber in subscribers {
+            match subscriber {
+                Subscriber::Field(callback) => callback(old, new),
+                Subscriber::Key(path, callback) => {
+                    let (old, new) = json.get_or_insert_with(|| match self.to_json.get() {
+                        Some(to_json) => (to_json(old), to_json(new)),
+                        None => (None, None),
+                    });
+                    let old = old.as_ref().and_then(|value| lookup(value, &path));
+                    let new = new.as_ref().and_then(|value| lookup(value, &path));
+                    if old != new {
+                        callback(old, new);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn lock<V>(mutex: &Mutex<V>) -> std::sync::MutexGuard<'_, V> {
+    // The list holds no invariant a panicking callback could break.
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The value at dotted `path`; numeric segments index arrays.
+fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(value, |value, segment| match value {
+            Value::Object(map) => map.get(segment),
+            Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+            _ => None,
+        })
+}
+
+/// Keeps a [`ProgressiveReloader::subscribe`] or
+/// [`ProgressiveReloader::on_field_change`] callback registered; dropping it
+/// unsubscribes.
+#[must_use = "the subscription ends when the guard is dropped"]
+pub struct ReloadSubscription {
+    remover: Option<Box<dyn FnOnce() + Send>>,
+}
+
+impl Drop for ReloadSubscription {
+    fn drop(&mut self) {
+        if let Some(remover) = self.remover.take() {
+            remover();
+        }
+    }
+}
+
+impl std::fmt::Debug for ReloadSubscription {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReloadSubscription").finish_non_exhaustive()
+    }
+}
+
 struct ProgressiveReloaderInner<T: Clone + Send + Sync + 'static> {
     current: ArcSwap<T>,
     candidate: ArcSwap<Option<Arc<T>>>,
     strategy: ReloadStrategy,
     health_check: Option<Arc<dyn ReloadHealthCheck>>,
+    canary_check: Option<CanaryCheck<T>>,
+    canary_timeout: Duration,
+    latency: ReloadLatencyMetrics,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<crate::audit::AuditWriter>>,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<Arc<crate::metrics::ConfigMetrics>>,
+    #[cfg(feature = "snapshot")]
+    snapshot: Option<ReloadSnapshot<T>>,
+    #[cfg(feature = "snapshot")]
+    journal: Option<ReloadJournal<T>>,
+    subscriptions: Subscriptions<T>,
 }
 
 pub struct ProgressiveReloader<T: Clone + Send + Sync + 'static> {
@@ -88,6 +364,18 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
                 candidate: ArcSwap::new(Arc::new(None)),
                 strategy,
                 health_check: None,
+                canary_check: None,
+                canary_timeout: DEFAULT_CANARY_CHECK_TIMEOUT,
+                latency: ReloadLatencyMetrics::default(),
+                #[cfg(feature = "audit")]
+                audit: None,
+                #[cfg(feature = "prometheus")]
+                metrics: None,
+                #[cfg(feature = "snapshot")]
+                snapshot: None,
+                #[cfg(feature = "snapshot")]
+                journal: None,
+                subscriptions: Subscriptions::default(),
             }),
         }
     }
@@ -103,6 +391,18 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
                 candidate: ArcSwap::new(Arc::new(None)),
                 strategy,
                 health_check,
+                canary_check: None,
+                canary_timeout: DEFAULT_CANARY_CHECK_TIMEOUT,
+                latency: ReloadLatencyMetrics::default(),
+                #[cfg(feature = "audit")]
+                audit: None,
+                #[cfg(feature = "prometheus")]
+                metrics: None,
+                #[cfg(feature = "snapshot")]
+                snapshot: None,
+                #[cfg(feature = "snapshot")]
+                journal: None,
+                subscriptions: Subscriptions::default(),
             }),
         }
     }
@@ -116,6 +416,11 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
         self.inner.current.load_full()
     }
 
+    /// Latency of the reloads handled so far, shared by all clones.
+    pub fn latency(&self) -> &ReloadLatencyMetrics {
+        &self.inner.latency
+    }
+
     pub fn with_health_check(mut self, health_check: Arc<dyn ReloadHealthCheck>) -> Self {
         Arc::get_mut(&mut self.inner)
             .expect("Cannot modify shared ProgressiveReloader")
@@ -123,14 +428,217 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
         self
     }
 
+    /// Run `check` on every candidate configuration before it is served.
+    ///
+    /// The check runs first, before any strategy, and can exercise the new
+    /// values, e.g. open a database connection with new credentials. If it
+    /// returns an error or takes longer than
+    /// [`with_canary_check_timeout`](Self::with_canary_check_timeout), the
+    /// current configuration stays in place and
+    /// [`begin_reload`](Self::begin_reload) fails with
+    /// [`ConfigError::ReloadRolledBack`], which is audited as
+    /// `ReloadRejected`.
+    ///
+    /// ```rust
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// use std::sync::Arc;
+    /// use confers::watcher::{ProgressiveReloader, ReloadStrategy};
+    ///
+    /// let reloader = ProgressiveReloader::new(Arc::new(8080u16), ReloadStrategy::Immediate)
+    ///     .with_canary_check(|port: Arc<u16>| async move {
+    ///         match *port {
+    ///             0 => Err("port 0 is not allowed"),
+    ///             _ => Ok(()),
+    ///         }
+    ///     });
+    /// # }
+    /// ```
+    pub fn with_canary_check<F, Fut, E>(mut self, check: F) -> Self
+    where
+        F: Fn(Arc<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .canary_check = Some(canary_check(check));
+        self
+    }
+
+    /// Reject candidates whose canary check takes longer than `timeout`
+    /// (default [`DEFAULT_CANARY_CHECK_TIMEOUT`]).
+    pub fn with_canary_check_timeout(mut self, timeout: Duration) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .canary_timeout = timeout;
+        self
+    }
+
+    /// Record a `ConfigReloaded` or `ReloadRejected` audit event for every
+    /// reload.
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_audit(mut self, audit: Arc<crate::audit::AuditWriter>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .audit = Some(audit);
+        self
+    }
+
+    /// Count reloads and record the last reload time in `metrics`.
+    #[cfg(feature = "prometheus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+    pub fn with_metrics(mut self, metrics: Arc<crate::metrics::ConfigMetrics>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .metrics = Some(metrics);
+        self
+    }
+
+    #[cfg(feature = "snapshot")]
+    fn with_snapshot(mut self, snapshot: ReloadSnapshot<T>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .snapshot = Some(snapshot);
+        self
+    }
+
+    #[cfg(feature = "snapshot")]
+    fn with_journal(mut self, journal: ReloadJournal<T>) -> Self {
+        Arc::get_mut(&mut self.inner)
+            .expect("Cannot modify shared ProgressiveReloader")
+            .journal = Some(journal);
+        self
+    }
+
+    /// Run a reload with the configured strategy.
+    ///
+    /// With a snapshot store, a committed configuration is saved as the new
+    /// known-good snapshot, and with a change journal the changed keys are
+    /// appended to it; if either fails, the new configuration is still
+    /// served and the error is returned.
     pub async fn begin_reload(
         &self,
         new_config: Arc<T>,
         provider: Arc<dyn ConfigProvider>,
     ) -> ConfigResult<ReloadOutcome> {
+        let started = Instant::now();
+        #[cfg(feature = "snapshot")]
+        let previous = (self.inner.journal.is_some()).then(|| (self.current(), provider.clone()));
+        let reload = self.reload(new_config, provider);
+        #[cfg(feature = "otel")]
+        let reload = tracing::Instrument::instrument(
+            reload,
+            tracing::info_span!("confers.reload", strategy = self.inner.strategy.name()),
+        );
+        let outcome = reload.await;
+        let latency = started.elapsed();
+        self.inner.latency.record(latency);
+
+        #[cfg(feature = "otel")]
+        crate::impl_::otel::record_reload(
+            self.inner.strategy.name(),
+            match &outcome {
+                Ok(ReloadOutcome::Committed) => "committed",
+                Ok(ReloadOutcome::RolledBack { .. })
+                | Err(ConfigError::ReloadRolledBack { .. }) => "rolled_back",
+                Err(_) => "error",
+            },
+            latency,
+        );
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.inner.metrics {
+            metrics.record_reload(matches!(outcome, Ok(ReloadOutcome::Committed)));
+        }
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &self.inner.audit {
+            let correlation_id = crate::audit::new_correlation_id();
+            match &outcome {
+                Ok(ReloadOutcome::Committed) => {
+                    audit.log_config_reloaded(&correlation_id, self.inner.strategy.name())
+                }
+                Err(ConfigError::ReloadRolledBack { reason }) => {
+                    self.audit_rejected(audit, &correlation_id, reason)
+                }
+                _ => {}
+            }
+        }
+
+        #[cfg(feature = "snapshot")]
+        if let (Ok(ReloadOutcome::Committed), Some(snapshot)) = (&outcome, &self.inner.snapshot) {
+            (snapshot.save)(&snapshot.store, &self.current())?;
+        }
+        #[cfg(feature = "snapshot")]
+        if let (Ok(ReloadOutcome::Committed), Some(journal), Some((old, provider))) =
+            (&outcome, &self.inner.journal, previous)
+        {
+            journal.record(&old, &self.current(), provider.as_ref())?;
+        }
+        outcome
+    }
+
+    /// Record a reload whose candidate configuration failed to load or
+    /// validate, so [`begin_reload`](Self::begin_reload) was never called.
+    ///
+    /// The current configuration stays in place. The failure is counted and
+    /// audited like a rolled-back reload.
+    pub fn record_failed_reload(&self, error: &ConfigError) -> ReloadOutcome {
+        let reason = error.to_string();
+
+        #[cfg(feature = "prometheus")]
+        if let Some(metrics) = &self.inner.metrics {
+            metrics.record_reload(false);
+        }
+
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &self.inner.audit {
+            self.audit_rejected(audit, &crate::audit::new_correlation_id(), &reason);
+        }
+        ReloadOutcome::RolledBack { reason }
+    }
+
+    /// `ReloadRejected`, followed by `ConfigRolledBack` with the known-good
+    /// checksum when a snapshot store is attached.
+    #[cfg(feature = "audit")]
+    fn audit_rejected(
+        &self,
+        audit: &crate::audit::AuditWriter,
+        correlation_id: &str,
+        reason: &str,
+    ) {
+        audit.log_reload_rejected(correlation_id, reason);
+        #[cfg(feature = "snapshot")]
+        if let Some(snapshot) = &self.inner.snapshot {
+            audit.log_config_rolled_back(
+                correlation_id,
+                reason,
+                snapshot.store.checksum().as_deref(),
+            );
+        }
+    }
+
+    async fn reload(
+        &self,
+        new_config: Arc<T>,
+        provider: Arc<dyn ConfigProvider>,
+    ) -> ConfigResult<ReloadOutcome> {
+        if let Some(check) = &self.inner.canary_check {
+            let timeout = self.inner.canary_timeout;
+            let reason = match tokio::time::timeout(timeout, check(new_config.clone())).await {
+                Ok(Ok(())) => None,
+                Ok(Err(reason)) => Some(format!("Canary check failed: {}", reason)),
+                Err(_) => Some(format!("Canary check timed out after {:?}", timeout)),
+            };
+            if let Some(reason) = reason {
+                return Err(ConfigError::ReloadRolledBack { reason });
+            }
+        }
         match &self.inner.strategy {
             ReloadStrategy::Immediate => {
-                self.inner.current.store(new_config);
+                self.commit(new_config);
                 Ok(ReloadOutcome::Committed)
             }
             ReloadStrategy::Canary {
@@ -147,6 +655,50 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
         }
     }
 
+    /// Call `callback` with the old and new value of the field that `field`
+    /// selects whenever a committed reload changes it.
+    ///
+    /// The typed counterpart of [`subscribe`](Self::subscribe): values are
+    /// compared with `PartialEq` and `T` does not need to be serializable.
+    /// `#[config(watch)]` on a field of a `#[derive(Config)]` struct generates
+    /// an `on_<field>_change` method that calls this.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use confers::watcher::{ProgressiveReloader, ReloadStrategy};
+    ///
+    /// #[derive(Clone)]
+    /// struct Server {
+    ///     port: u16,
+    /// }
+    ///
+    /// let reloader =
+    ///     ProgressiveReloader::new(Arc::new(Server { port: 8080 }), ReloadStrategy::Immediate);
+    /// let _subscription = reloader.on_field_change(|server| &server.port, |old, new| {
+    ///     println!("port changed from {old} to {new}");
+    /// });
+    /// ```
+    pub fn on_field_change<F: PartialEq + 'static>(
+        &self,
+        field: fn(&T) -> &F,
+        callback: impl Fn(&F, &F) + Send + Sync + 'static,
+    ) -> ReloadSubscription {
+        self.inner
+            .subscriptions
+            .register(Subscriber::Field(Arc::new(move |old: &T, new: &T| {
+                let (old, new) = (field(old), field(new));
+                if old != new {
+                    callback(old, new);
+                }
+            })))
+    }
+
+    /// Serve `new_config` and notify subscribers of the keys it changed.
+    fn commit(&self, new_config: Arc<T>) {
+        let old = self.inner.current.swap(Arc::clone(&new_config));
+        self.inner.subscriptions.notify(&old, &new_config);
+    }
+
     async fn canary_reload(
         &self,
         new_config: Arc<T>,
@@ -176,7 +728,7 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
             }
         }
 
-        self.inner.current.store(new_config);
+        self.commit(new_config);
         self.inner.candidate.store(Arc::new(None));
         Ok(ReloadOutcome::Committed)
     }
@@ -211,16 +763,95 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloader<T> {
             }
         }
 
-        self.inner.current.store(new_config);
+        self.commit(new_config);
         self.inner.candidate.store(Arc::new(None));
         Ok(ReloadOutcome::Committed)
     }
 }
 
+#[cfg(feature = "snapshot")]
+impl<T: Clone + Send + Sync + serde::Serialize + 'static> ProgressiveReloader<T> {
+    /// Save every committed configuration to `store` as the last known-good
+    /// snapshot.
+    ///
+    /// Rejected reloads, and failures reported through
+    /// [`record_failed_reload`](Self::record_failed_reload), keep serving the
+    /// current configuration and, with an audit writer, record a
+    /// `ConfigRolledBack` event. The initial configuration is not saved; call
+    /// [`ConfigSnapshotStore::save`] after the first load.
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn with_snapshot_store(self, store: Arc<ConfigSnapshotStore>) -> Self {
+        self.with_snapshot(ReloadSnapshot::new(store))
+    }
+
+    /// Append the keys each committed reload changes to `journal`, e.g. a
+    /// [`ChangeJournal`](crate::snapshot::ChangeJournal) read by
+    /// `confers history`.
+    ///
+    /// Each entry names the source the reload's provider reports for the
+    /// new value. Values are recorded as short hashes, and not at all for
+    /// keys whose names look sensitive.
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn with_change_journal(self, journal: Arc<dyn JournalSink>) -> Self {
+        self.with_journal(ReloadJournal::new(journal))
+    }
+}
+
+impl<T: Clone + Send + Sync + serde::Serialize + 'static> ProgressiveReloader<T> {
+    /// Call `callback` with the old and new value of the dotted key `path`
+    /// whenever a committed reload changes it.
+    ///
+    /// Both configurations are serialized once per reload and compared at
+    /// `path`, so components only hear about the keys they care about instead
+    /// of diffing the whole struct. A key that is missing on one side is
+    /// passed as `None`; a change anywhere below `path` counts as a change of
+    /// `path`. Callbacks run on the task that called
+    /// [`begin_reload`](Self::begin_reload), after the new configuration is
+    /// served. Drop the returned guard to unsubscribe.
+    ///
+    /// ```rust
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() -> Result<(), confers::ConfigError> {
+    /// use std::sync::Arc;
+    /// use confers::watcher::{ProgressiveReloader, ReloadStrategy};
+    ///
+    /// let reloader = ProgressiveReloader::new(
+    ///     Arc::new(serde_json::json!({ "server": { "port": 8080 }, "debug": false })),
+    ///     ReloadStrategy::Immediate,
+    /// );
+    /// let _subscription = reloader.subscribe("server.port", |old, new| {
+    ///     println!("port changed from {old:?} to {new:?}");
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn subscribe(
+        &self,
+        path: impl Into<Arc<str>>,
+        callback: impl Fn(Option<&Value>, Option<&Value>) + Send + Sync + 'static,
+    ) -> ReloadSubscription {
+        let subscriptions = &self.inner.subscriptions;
+        subscriptions
+            .to_json
+            .get_or_init(|| |config| serde_json::to_value(config).ok());
+        subscriptions.register(Subscriber::Key(path.into(), Arc::new(callback)))
+    }
+}
+
 pub struct ProgressiveReloaderBuilder<T: Clone + Send + Sync + 'static> {
     initial: Option<Arc<T>>,
     strategy: Option<ReloadStrategy>,
     health_check: Option<Arc<dyn ReloadHealthCheck>>,
+    canary_check: Option<CanaryCheck<T>>,
+    canary_timeout: Duration,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<crate::audit::AuditWriter>>,
+    #[cfg(feature = "prometheus")]
+    metrics: Option<Arc<crate::metrics::ConfigMetrics>>,
+    #[cfg(feature = "snapshot")]
+    snapshot: Option<ReloadSnapshot<T>>,
+    #[cfg(feature = "snapshot")]
+    journal: Option<ReloadJournal<T>>,
 }
 
 impl<T: Clone + Send + Sync + 'static> ProgressiveReloaderBuilder<T> {
@@ -229,6 +860,16 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloaderBuilder<T> {
             initial: None,
             strategy: Some(ReloadStrategy::Immediate),
             health_check: None,
+            canary_check: None,
+            canary_timeout: DEFAULT_CANARY_CHECK_TIMEOUT,
+            #[cfg(feature = "audit")]
+            audit: None,
+            #[cfg(feature = "prometheus")]
+            metrics: None,
+            #[cfg(feature = "snapshot")]
+            snapshot: None,
+            #[cfg(feature = "snapshot")]
+            journal: None,
         }
     }
 
@@ -247,10 +888,84 @@ impl<T: Clone + Send + Sync + 'static> ProgressiveReloaderBuilder<T> {
         self
     }
 
+    /// See [`ProgressiveReloader::with_canary_check`].
+    pub fn canary_check<F, Fut, E>(mut self, check: F) -> Self
+    where
+        F: Fn(Arc<T>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), E>> + Send + 'static,
+        E: std::fmt::Display,
+    {
+        self.canary_check = Some(canary_check(check));
+        self
+    }
+
+    /// See [`ProgressiveReloader::with_canary_check_timeout`].
+    pub fn canary_check_timeout(mut self, timeout: Duration) -> Self {
+        self.canary_timeout = timeout;
+        self
+    }
+
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn audit(mut self, audit: Arc<crate::audit::AuditWriter>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "prometheus")))]
+    pub fn metrics(mut self, metrics: Arc<crate::metrics::ConfigMetrics>) -> Self {
+        self.metrics = Some(metrics);
+        self
+    }
+
     pub fn build(self) -> ProgressiveReloader<T> {
         let initial = self.initial.expect("initial configuration is required");
         let strategy = self.strategy.unwrap_or_default();
-        ProgressiveReloader::with_dependencies(initial, strategy, self.health_check)
+        let mut reloader =
+            ProgressiveReloader::with_dependencies(initial, strategy, self.health_check);
+        if let Some(check) = self.canary_check {
+            Arc::get_mut(&mut reloader.inner)
+                .expect("Cannot modify shared ProgressiveReloader")
+                .canary_check = Some(check);
+        }
+        let reloader = reloader.with_canary_check_timeout(self.canary_timeout);
+        #[cfg(feature = "audit")]
+        let reloader = match self.audit {
+            Some(audit) => reloader.with_audit(audit),
+            None => reloader,
+        };
+        #[cfg(feature = "prometheus")]
+        let reloader = match self.metrics {
+            Some(metrics) => reloader.with_metrics(metrics),
+            None => reloader,
+        };
+        #[cfg(feature = "snapshot")]
+        let reloader = match self.snapshot {
+            Some(snapshot) => reloader.with_snapshot(snapshot),
+            None => reloader,
+        };
+        #[cfg(feature = "snapshot")]
+        let reloader = match self.journal {
+            Some(journal) => reloader.with_journal(journal),
+            None => reloader,
+        };
+        reloader
+    }
+}
+
+#[cfg(feature = "snapshot")]
+impl<T: Clone + Send + Sync + serde::Serialize + 'static> ProgressiveReloaderBuilder<T> {
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn snapshot_store(mut self, store: Arc<ConfigSnapshotStore>) -> Self {
+        self.snapshot = Some(ReloadSnapshot::new(store));
+        self
+    }
+
+    #[cfg_attr(docsrs, doc(cfg(feature = "snapshot")))]
+    pub fn change_journal(mut self, journal: Arc<dyn JournalSink>) -> Self {
+        self.journal = Some(ReloadJournal::new(journal));
+        self
     }
 }
 
@@ -313,6 +1028,52 @@ mod tests {
         assert_eq!(*reloader.current(), 2);
     }
 
+    #[tokio::test]
+    async fn test_subscribers_only_hear_about_their_keys() {
+        use std::sync::Mutex;
+
+        let config = |port: u16, debug: bool| {
+            Arc::new(serde_json::json!({ "server": { "port": port }, "debug": debug }))
+        };
+        let reloader = ProgressiveReloader::new(config(8080, false), ReloadStrategy::Immediate);
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = |key: &'static str| {
+            let seen = seen.clone();
+            move |old: Option<&Value>, new: Option<&Value>| {
+                seen.lock().unwrap().push((key, old.cloned(), new.cloned()));
+            }
+        };
+        let port = reloader.subscribe("server.port", recorder("port"));
+        let _server = reloader.subscribe("server", recorder("server"));
+        let _missing = reloader.subscribe("server.host", recorder("host"));
+
+        reloader
+            .begin_reload(config(8080, true), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        assert!(seen.lock().unwrap().is_empty());
+
+        reloader
+            .begin_reload(config(9090, true), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        let keys: Vec<_> = seen.lock().unwrap().iter().map(|(key, ..)| *key).collect();
+        assert_eq!(keys, ["port", "server"]);
+        assert_eq!(
+            seen.lock().unwrap()[0],
+            ("port", Some(8080.into()), Some(9090.into()))
+        );
+
+        drop(port);
+        seen.lock().unwrap().clear();
+        reloader
+            .begin_reload(config(7070, true), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        let keys: Vec<_> = seen.lock().unwrap().iter().map(|(key, ..)| *key).collect();
+        assert_eq!(keys, ["server"]);
+    }
+
     #[tokio::test]
     async fn test_canary_reload_healthy() {
         struct HealthyCheck;
@@ -385,4 +1146,287 @@ mod tests {
         assert!(matches!(result, ReloadOutcome::Committed));
         assert_eq!(*reloader.current(), 2);
     }
+
+    #[cfg(feature = "audit")]
+    #[tokio::test]
+    async fn test_reload_outcomes_are_audited() {
+        struct CriticalCheck;
+        #[async_trait]
+        impl ReloadHealthCheck for CriticalCheck {
+            async fn check(&self, _provider: Arc<dyn ConfigProvider>) -> HealthStatus {
+                HealthStatus::Critical {
+                    reason: "error rate".to_string(),
+                }
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            crate::audit::AuditWriter::builder()
+                .log_dir(dir.path().to_path_buf())
+                .build(),
+        );
+
+        let committed = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .audit(audit.clone())
+            .build();
+        committed
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .unwrap();
+
+        let rejected = ProgressiveReloader::new(
+            Arc::new(1i32),
+            ReloadStrategy::Linear {
+                steps: 1,
+                interval: Duration::from_millis(1),
+            },
+        )
+        .with_health_check(Arc::new(CriticalCheck))
+        .with_audit(audit.clone());
+        assert!(rejected
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .is_err());
+
+        let canary = ProgressiveReloader::new(Arc::new(1i32), ReloadStrategy::Immediate)
+            .with_canary_check(|_| async { Err("connection refused") })
+            .with_audit(audit.clone());
+        assert!(canary
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .is_err());
+
+        let hung = ProgressiveReloader::new(Arc::new(1i32), ReloadStrategy::Immediate)
+            .with_canary_check(|_| std::future::pending::<Result<(), String>>())
+            .with_canary_check_timeout(Duration::from_millis(20))
+            .with_audit(audit);
+        assert!(hung
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .is_err());
+
+        let log = std::fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let events: Vec<serde_json::Value> = std::fs::read_to_string(log.path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events[0]["event"], "ConfigReloaded");
+        assert_eq!(events[0]["strategy"], "immediate");
+        assert_eq!(events[1]["event"], "ReloadRejected");
+        assert_eq!(events[1]["reason"], "Linear step 1 failed: error rate");
+        assert_eq!(events[2]["event"], "ReloadRejected");
+        assert_eq!(
+            events[2]["reason"],
+            "Canary check failed: connection refused"
+        );
+        assert_eq!(events[3]["event"], "ReloadRejected");
+        assert_eq!(events[3]["reason"], "Canary check timed out after 20ms");
+        assert_ne!(events[0]["correlation_id"], events[1]["correlation_id"]);
+    }
+
+    #[tokio::test]
+    async fn test_canary_check_rejects_candidate() {
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(8080u16))
+            .canary_check(|port: Arc<u16>| async move {
+                match *port {
+                    0 => Err("port 0 is not allowed"),
+                    _ => Ok(()),
+                }
+            })
+            .build();
+
+        let err = reloader
+            .begin_reload(Arc::new(0), Arc::new(MockProvider))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::ReloadRolledBack { ref reason }
+                if reason == "Canary check failed: port 0 is not allowed"
+        ));
+        assert_eq!(*reloader.current(), 8080);
+
+        reloader
+            .begin_reload(Arc::new(9090), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        assert_eq!(*reloader.current(), 9090);
+    }
+
+    #[tokio::test]
+    async fn test_hung_canary_check_times_out() {
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .canary_check(|config: Arc<i32>| async move {
+                if *config == 2 {
+                    std::future::pending::<()>().await;
+                }
+                Ok::<_, String>(())
+            })
+            .canary_check_timeout(Duration::from_millis(20))
+            .build();
+
+        let err = reloader
+            .begin_reload(Arc::new(2), Arc::new(MockProvider))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::ReloadRolledBack { ref reason }
+                if reason == "Canary check timed out after 20ms"
+        ));
+        assert_eq!(*reloader.current(), 1);
+
+        // A later reload is not blocked by the hung check.
+        reloader
+            .begin_reload(Arc::new(3), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        assert_eq!(*reloader.current(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reload_latency_is_recorded() {
+        let reloader = ProgressiveReloader::new(
+            Arc::new(1i32),
+            ReloadStrategy::Linear {
+                steps: 2,
+                interval: Duration::from_millis(10),
+            },
+        );
+        assert_eq!(reloader.latency().count(), 0);
+        assert_eq!(reloader.latency().mean(), None);
+
+        reloader
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .unwrap();
+
+        let latency = reloader.clone().latency().last().unwrap();
+        assert!(latency >= Duration::from_millis(20), "{latency:?}");
+        assert_eq!(reloader.latency().count(), 1);
+        assert_eq!(reloader.latency().max(), latency);
+        assert_eq!(reloader.latency().mean(), Some(latency));
+    }
+
+    #[cfg(feature = "prometheus")]
+    #[tokio::test]
+    async fn test_reloads_are_counted() {
+        let registry = prometheus::Registry::new();
+        let metrics = Arc::new(crate::metrics::ConfigMetrics::register(&registry).unwrap());
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .metrics(metrics)
+            .build();
+
+        reloader
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .unwrap();
+
+        let body = crate::metrics::encode_text(&registry).unwrap();
+        assert!(
+            body.contains("confers_reloads_total{outcome=\"committed\"} 1"),
+            "{body}"
+        );
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[tokio::test]
+    async fn test_committed_reloads_are_snapshotted() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = Arc::new(ConfigSnapshotStore::new(dir.path().join("known-good.json")));
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .snapshot_store(store.clone())
+            .build();
+
+        reloader
+            .begin_reload(Arc::new(2i32), Arc::new(MockProvider))
+            .await
+            .unwrap();
+        assert_eq!(store.load_config::<i32>().unwrap(), Some(2));
+
+        let outcome = reloader.record_failed_reload(&ConfigError::InvalidValue {
+            key: "port".to_string(),
+            expected_type: "u16".to_string(),
+            message: "out of range".to_string(),
+        });
+        assert!(matches!(outcome, ReloadOutcome::RolledBack { .. }));
+        assert_eq!(*reloader.current(), 2);
+        assert_eq!(store.load_config::<i32>().unwrap(), Some(2));
+    }
+
+    #[cfg(feature = "snapshot")]
+    #[tokio::test]
+    async fn test_committed_reloads_are_journaled() {
+        use crate::snapshot::{value_hash, ChangeJournal};
+
+        let dir = tempfile::tempdir().unwrap();
+        let journal = Arc::new(ChangeJournal::new(dir.path().join("changes.jsonl")));
+        let config = |port: u16| Arc::new(serde_json::json!({ "server": { "port": port } }));
+        let reloader = ProgressiveReloader::builder()
+            .initial(config(8080))
+            .change_journal(journal.clone())
+            .build();
+
+        for port in [8080, 9090] {
+            reloader
+                .begin_reload(config(port), Arc::new(MockProvider))
+                .await
+                .unwrap();
+        }
+
+        let history = journal.history("server.port").unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].old_hash, Some(value_hash(&8080.into())));
+        assert_eq!(history[0].new_hash, Some(value_hash(&9090.into())));
+        assert_eq!(history[0].source, None);
+    }
+
+    #[cfg(all(feature = "snapshot", feature = "audit"))]
+    #[tokio::test]
+    async fn test_rollback_is_audited_with_snapshot_checksum() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit_dir = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            crate::audit::AuditWriter::builder()
+                .log_dir(audit_dir.path().to_path_buf())
+                .build(),
+        );
+        let store = Arc::new(ConfigSnapshotStore::new(dir.path().join("known-good.json")));
+        let checksum = store.save(&1i32).unwrap().checksum;
+
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .audit(audit)
+            .snapshot_store(store)
+            .build();
+        reloader.record_failed_reload(&ConfigError::ReloadRolledBack {
+            reason: "invalid port".to_string(),
+        });
+
+        let log = std::fs::read_dir(audit_dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let events: Vec<serde_json::Value> = std::fs::read_to_string(log.path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(events[0]["event"], "ReloadRejected");
+        assert_eq!(events[1]["event"], "ConfigRolledBack");
+        assert_eq!(events[1]["checksum"], checksum.as_str());
+        assert_eq!(events[0]["correlation_id"], events[1]["correlation_id"]);
+    }
 }
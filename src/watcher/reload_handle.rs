@@ -0,0 +1,264 @@
+// Copyright (c) 2025 Kirky.X
+//
+// Licensed under the MIT License
+// See LICENSE file in the project root for full license information.
+
+//! A single funnel for reload requests.
+//!
+//! File changes, `SIGHUP`, the config server's `POST /reload` and direct
+//! calls all end up as triggers on one [`ReloadTriggers`] receiver, so the
+//! application runs one reload pipeline no matter what asked for it.
+
+#[cfg(any(feature = "audit", feature = "progressive-reload"))]
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+use super::fs_watcher::MultiFsWatcher;
+#[cfg(feature = "progressive-reload")]
+use crate::{error::ConfigResult, interface::ConfigProvider};
+
+/// Requests a reload. Cheap to clone.
+///
+/// At most one trigger is queued: triggers arriving while one is pending are
+/// folded into it, since a single reload picks up every change made so far.
+///
+/// # Examples
+///
+/// ```
+/// # async fn example() {
+/// use confers::watcher::ReloadHandle;
+///
+/// let (handle, mut triggers) = ReloadHandle::new();
+/// handle.trigger("admin");
+/// handle.trigger("admin");
+///
+/// assert_eq!(triggers.recv().await.as_deref(), Some("admin"));
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ReloadHandle {
+    tx: mpsc::Sender<String>,
+    #[cfg(feature = "audit")]
+    audit: Option<Arc<crate::audit::AuditWriter>>,
+}
+
+/// The receiving end of a [`ReloadHandle`].
+pub struct ReloadTriggers {
+    rx: mpsc::Receiver<String>,
+}
+
+impl ReloadHandle {
+    /// Create a handle and the receiver its triggers arrive on.
+    pub fn new() -> (Self, ReloadTriggers) {
+        let (tx, rx) = mpsc::channel(1);
+        (
+            Self {
+                tx,
+                #[cfg(feature = "audit")]
+                audit: None,
+            },
+            ReloadTriggers { rx },
+        )
+    }
+
+    /// Record a `ReloadTrigger` audit event for every trigger.
+    #[cfg(feature = "audit")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "audit")))]
+    pub fn with_audit(mut self, audit: Arc<crate::audit::AuditWriter>) -> Self {
+        self.audit = Some(audit);
+        self
+    }
+
+    /// Request a reload on behalf of `source`, e.g. `"sighup"` or
+    /// `"http"`. Returns `false` if the receiver is gone.
+    pub fn trigger(&self, source: impl Into<String>) -> bool {
+        let source = source.into();
+        #[cfg(feature = "audit")]
+        if let Some(audit) = &self.audit {
+            audit.log_reload_trigger(&source);
+        }
+        match self.tx.try_send(source) {
+            Ok(()) | Err(mpsc::error::TrySendError::Full(_)) => true,
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+
+    /// Trigger a reload on every `SIGHUP`, until the receiver is dropped.
+    #[cfg(unix)]
+    pub fn listen_for_sighup(&self) -> crate::error::ConfigResult<JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangups = signal(SignalKind::hangup())?;
+        let handle = self.clone();
+        Ok(tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                if !handle.trigger("sighup") {
+                    break;
+                }
+            }
+        }))
+    }
+
+    /// Trigger a reload for every change `watcher` reports, until either
+    /// side stops.
+    pub fn forward_watcher(&self, mut watcher: MultiFsWatcher) -> JoinHandle<()> {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            while let Some(path) = watcher.recv().await {
+                if !handle.trigger(format!("file:{}", path.display())) {
+                    break;
+                }
+            }
+        })
+    }
+}
+
+impl ReloadTriggers {
+    /// Wait for the next trigger and return its source. `None` once every
+    /// handle has been dropped.
+    pub async fn recv(&mut self) -> Option<String> {
+        self.rx.recv().await
+    }
+
+    /// Run `rebuild` for every trigger and hand the result to `reloader`.
+    ///
+    /// A failed rebuild keeps the current configuration and is recorded
+    /// with [`record_failed_reload`](super::ProgressiveReloader::record_failed_reload);
+    /// a successful one goes through
+    /// [`begin_reload`](super::ProgressiveReloader::begin_reload), health
+    /// checks included. Returns when every handle has been dropped.
+    #[cfg(feature = "progressive-reload")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "progressive-reload")))]
+    pub async fn drive<T, F>(mut self, reloader: &super::ProgressiveReloader<T>, mut rebuild: F)
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnMut(&str) -> ConfigResult<(T, Arc<dyn ConfigProvider>)>,
+    {
+        while let Some(source) = self.recv().await {
+            match rebuild(&source) {
+                Ok((config, provider)) => {
+                    // Rejections are already audited and counted by the reloader.
+                    let _ = reloader.begin_reload(Arc::new(config), provider).await;
+                }
+                Err(e) => {
+                    reloader.record_failed_reload(&e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pending_triggers_are_coalesced() {
+        let (handle, mut triggers) = ReloadHandle::new();
+        assert!(handle.trigger("first"));
+        assert!(handle.clone().trigger("second"));
+
+        assert_eq!(triggers.recv().await.as_deref(), Some("first"));
+        assert!(handle.trigger("third"));
+        assert_eq!(triggers.recv().await.as_deref(), Some("third"));
+
+        drop(triggers);
+        assert!(!handle.trigger("late"));
+    }
+
+    #[tokio::test]
+    async fn test_recv_ends_when_handles_are_dropped() {
+        let (handle, mut triggers) = ReloadHandle::new();
+        drop(handle);
+        assert_eq!(triggers.recv().await, None);
+    }
+
+    #[tokio::test]
+    async fn test_file_changes_become_triggers() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.toml");
+        std::fs::write(&file, "port = 1").unwrap();
+
+        let (handle, mut triggers) = ReloadHandle::new();
+        let watcher = MultiFsWatcher::new([&file], 50).await.unwrap();
+        handle.forward_watcher(watcher);
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        std::fs::write(&file, "port = 2").unwrap();
+
+        let source = tokio::time::timeout(std::time::Duration::from_secs(5), triggers.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(source.starts_with("file:"), "{}", source);
+        assert!(source.ends_with("app.toml"), "{}", source);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_sighup_becomes_trigger() {
+        let (handle, mut triggers) = ReloadHandle::new();
+        handle.listen_for_sighup().unwrap();
+        unsafe { libc::raise(libc::SIGHUP) };
+
+        let source = tokio::time::timeout(std::time::Duration::from_secs(5), triggers.recv())
+            .await
+            .unwrap();
+        assert_eq!(source.as_deref(), Some("sighup"));
+    }
+
+    #[cfg(feature = "audit")]
+    #[tokio::test]
+    async fn test_triggers_are_audited() {
+        let dir = tempfile::tempdir().unwrap();
+        let audit = Arc::new(
+            crate::audit::AuditWriter::builder()
+                .log_dir(dir.path().to_path_buf())
+                .build(),
+        );
+        let (handle, _triggers) = ReloadHandle::new();
+        handle.with_audit(audit).trigger("http");
+
+        let log = std::fs::read_dir(dir.path())
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        let event: serde_json::Value =
+            serde_json::from_str(std::fs::read_to_string(log.path()).unwrap().trim()).unwrap();
+        assert_eq!(event["event"], "ReloadTrigger");
+        assert_eq!(event["source"], "http");
+    }
+
+    #[cfg(feature = "progressive-reload")]
+    #[tokio::test]
+    async fn test_drive_feeds_the_reloader() {
+        use crate::watcher::ProgressiveReloader;
+
+        struct Empty;
+        impl ConfigProvider for Empty {
+            fn get_raw(&self, _key: &str) -> Option<&crate::types::AnnotatedValue> {
+                None
+            }
+            fn keys(&self) -> Vec<String> {
+                vec![]
+            }
+        }
+
+        let reloader = ProgressiveReloader::builder()
+            .initial(Arc::new(1i32))
+            .build();
+        let (handle, triggers) = ReloadHandle::new();
+        handle.trigger("manual");
+        drop(handle);
+
+        triggers
+            .drive(&reloader, |source| {
+                assert_eq!(source, "manual");
+                Ok((2, Arc::new(Empty) as Arc<dyn ConfigProvider>))
+            })
+            .await;
+        assert_eq!(*reloader.current(), 2);
+    }
+}
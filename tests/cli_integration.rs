@@ -187,6 +187,46 @@ name = "validate-json-test"
     assert!(stdout.contains("valid"), "Output: {}", stdout);
 }
 
+#[test]
+#[serial]
+fn test_validate_schema_json_report() {
+    let dir = TempDir::new().expect("Failed to create temp dir");
+    let config_path = create_test_config(&dir, "config.toml", "port = 70000\nprot = 1\n");
+    let schema_path = create_test_config(
+        &dir,
+        "schema.json",
+        r#"{
+  "type": "object",
+  "additionalProperties": false,
+  "properties": { "port": { "type": "integer", "maximum": 65535 } }
+}"#,
+    );
+
+    let output = run_confers(&[
+        "--allow-absolute-paths",
+        "-c",
+        config_path.to_str().unwrap(),
+        "validate",
+        "--schema",
+        schema_path.to_str().unwrap(),
+        "-f",
+        "json",
+    ])
+    .output()
+    .expect("Failed to run validate command");
+
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let report: serde_json::Value = serde_json::from_str(&stdout).expect("JSON output");
+    let violations = report["schema_violations"].as_array().unwrap();
+    assert_eq!(violations.len(), 2, "Output: {}", stdout);
+    assert_eq!(violations[0]["path"], "port");
+    assert_eq!(violations[0]["value"], "70000");
+    assert_eq!(violations[0]["source"], "config.toml");
+    assert_eq!(violations[1]["path"], "prot");
+    assert_eq!(violations[1]["suggestion"], "port");
+}
+
 #[test]
 #[serial]
 fn test_export_command_json() {
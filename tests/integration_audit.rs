@@ -315,4 +315,33 @@ mod tests {
         writer.log_key_access("key_without_log_dir");
         // No assertion needed - test passes if no panic occurs.
     }
+
+    /// Test 14: Verify free-text reasons are redacted before they are written.
+    #[test]
+    fn test_audit_reason_redacts_secret_assignments() {
+        let temp_dir = TempDir::new().unwrap();
+        let log_dir: PathBuf = temp_dir.path().to_path_buf();
+
+        let writer = AuditWriter::builder()
+            .enabled(true)
+            .log_dir(log_dir.clone())
+            .build();
+
+        writer.log_provider_degraded(
+            "http://config",
+            "skip",
+            "bad response: password=hunter2-prod",
+        );
+
+        std::thread::sleep(std::time::Duration::from_millis(100));
+
+        let entries: Vec<_> = std::fs::read_dir(&log_dir)
+            .expect("log dir readable")
+            .filter_map(|e| e.ok())
+            .collect();
+        let content = std::fs::read_to_string(entries[0].path()).expect("log file readable");
+        assert!(content.contains("ProviderDegraded"), "{}", content);
+        assert!(content.contains("bad response"), "{}", content);
+        assert!(!content.contains("hunter2-prod"), "{}", content);
+    }
 }
@@ -117,6 +117,7 @@ struct NumericEnvConfig {
     host: String,
 }
 
+#[allow(clippy::bool_assert_comparison)]
 #[test]
 #[serial]
 fn test_numeric_env_config_default() {
@@ -136,6 +137,7 @@ fn test_numeric_env_override_u32() {
     });
 }
 
+#[allow(clippy::approx_constant)]
 #[test]
 #[serial]
 fn test_numeric_env_override_f64() {
@@ -145,6 +147,7 @@ fn test_numeric_env_override_f64() {
     });
 }
 
+#[allow(clippy::bool_assert_comparison)]
 #[test]
 #[serial]
 fn test_numeric_env_override_bool() {
@@ -154,6 +157,7 @@ fn test_numeric_env_override_bool() {
     });
 }
 
+#[allow(clippy::approx_constant, clippy::bool_assert_comparison)]
 #[test]
 #[serial]
 fn test_numeric_env_override_all() {
@@ -197,3 +201,614 @@ fn test_numeric_env_override_negative_f64() {
         assert_eq!(config.temperature, -5.5);
     });
 }
+
+// ===== #[config(secret)] fields =====
+
+#[cfg(feature = "security")]
+mod secret_fields {
+    use super::*;
+    use confers::security::SecureString;
+
+    #[derive(Debug, Config, Deserialize)]
+    #[config(env_prefix = "SECRETCFG_")]
+    struct SecretConfig {
+        #[config(default = "db.local".to_string())]
+        host: String,
+
+        #[config(secret)]
+        password: SecureString,
+
+        #[config(secret)]
+        token: Option<SecureString>,
+    }
+
+    #[test]
+    fn test_secret_fields_are_sensitive_paths() {
+        assert_eq!(SecretConfig::sensitive_paths(), &["password", "token"]);
+    }
+
+    #[test]
+    #[serial]
+    fn test_secret_field_loaded_from_env() {
+        common::with_env_var("SECRETCFG_PASSWORD", "hunter22", || {
+            let config = SecretConfig::load_sync().unwrap();
+            assert_eq!(config.host, "db.local");
+            assert_eq!(config.password.as_str(), "hunter22");
+            assert!(config.token.is_none());
+        });
+    }
+
+    #[test]
+    #[serial]
+    fn test_secret_field_not_in_debug_or_serialized_output() {
+        common::with_env_var("SECRETCFG_PASSWORD", "hunter22", || {
+            let config = SecretConfig::load_sync().unwrap();
+            let debug = format!("{:?}", config);
+            assert!(debug.contains("db.local"));
+            assert!(!debug.contains("hunter22"));
+            assert_eq!(
+                serde_json::to_string(&config.password).unwrap(),
+                "\"[REDACTED]\""
+            );
+        });
+    }
+}
+
+// ===== #[config(profiles)] =====
+
+#[derive(Debug, Config, Deserialize, PartialEq)]
+#[config(profiles, profile_env = "PROFILECFG_ENV")]
+struct ProfiledConfig {
+    #[config(default = "localhost".to_string())]
+    host: String,
+
+    #[config(default = 8080u16)]
+    port: u16,
+}
+
+fn profiled_config_file() -> (tempfile::TempDir, std::path::PathBuf) {
+    // File sources reject absolute paths, so keep the file under the cwd.
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("app.toml");
+    std::fs::write(
+        &path,
+        "host = \"base\"\nport = 8080\n\n[profile.production]\nhost = \"prod.example.com\"\nport = 443\n",
+    )
+    .unwrap();
+    (dir, path)
+}
+
+#[test]
+#[serial]
+fn test_profiles_select_section_from_env() {
+    let (_dir, path) = profiled_config_file();
+
+    let config = ProfiledConfig::load_file(&path).unwrap();
+    assert_eq!(config.host, "base");
+    assert_eq!(ProfiledConfig::active_profile(), "default");
+
+    common::with_env_var("PROFILECFG_ENV", "production", || {
+        let config = ProfiledConfig::load_file(&path).unwrap();
+        assert_eq!(config.host, "prod.example.com");
+        assert_eq!(config.port, 443);
+    });
+}
+
+#[test]
+#[serial]
+fn test_profiles_explicit_profile_with_env_override() {
+    let (_dir, path) = profiled_config_file();
+
+    common::with_env_var("PORT", "9000", || {
+        let config = ProfiledConfig::load_file_with_profile(&path, "production").unwrap();
+        assert_eq!(config.host, "prod.example.com");
+        assert_eq!(config.port, 9000);
+    });
+}
+
+/// Server settings.
+#[derive(Debug, confers::ConfigSchema, Deserialize)]
+#[allow(dead_code)]
+struct DocumentedConfig {
+    /// Listen address,
+    /// IPv4 or IPv6.
+    #[config(default = "0.0.0.0".to_string())]
+    host: String,
+
+    #[config(default = 8080u16, description = "Listen port")]
+    port: u16,
+
+    #[config(sensitive = true)]
+    api_token: String,
+}
+
+#[test]
+fn test_config_schema_includes_descriptions_and_defaults() {
+    let schema = DocumentedConfig::json_schema();
+    let properties = &schema["properties"];
+    assert_eq!(schema["description"], "Server settings.");
+    assert_eq!(
+        properties["host"]["description"],
+        "Listen address, IPv4 or IPv6."
+    );
+    assert_eq!(properties["host"]["default"], "0.0.0.0");
+    assert_eq!(properties["port"]["description"], "Listen port");
+    assert_eq!(properties["port"]["default"], 8080);
+    assert!(properties["api_token"].get("default").is_none());
+    assert_eq!(properties["api_token"]["writeOnly"], true);
+    assert!(properties["port"].get("writeOnly").is_none());
+
+    let template = confers::template::generate_template(
+        &schema,
+        confers::Format::Json,
+        confers::template::TemplateLevel::Minimal,
+    )
+    .unwrap();
+    let template: serde_json::Value = serde_json::from_str(&template).unwrap();
+    assert_eq!(template, serde_json::json!({ "api_token": "" }));
+
+    let template = confers::template::generate_template(
+        &schema,
+        confers::Format::Toml,
+        confers::template::TemplateLevel::Full,
+    )
+    .unwrap();
+    assert!(
+        template.contains("# Listen address, IPv4 or IPv6."),
+        "{}",
+        template
+    );
+}
+
+#[derive(Debug, Config, Deserialize)]
+#[config(validate_with = "check_tls")]
+struct TlsConfig {
+    #[config(default = false)]
+    tls_enabled: bool,
+
+    tls_cert: Option<String>,
+}
+
+fn check_tls(config: &TlsConfig) -> Result<(), confers::ValidationErrors> {
+    let mut errors = confers::ValidationErrors::new();
+    if config.tls_enabled && config.tls_cert.is_none() {
+        errors.add(
+            &["tls_enabled", "tls_cert"],
+            "tls_cert is required when tls_enabled is true",
+        );
+    }
+    errors.into_result()
+}
+
+#[test]
+fn test_validate_with_cross_field() {
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("tls.toml");
+
+    std::fs::write(&path, "tls_enabled = true\n").unwrap();
+    match TlsConfig::load_file(&path) {
+        Err(confers::ConfigError::CrossFieldValidation { errors }) => {
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors.violations()[0].fields, ["tls_enabled", "tls_cert"]);
+        }
+        other => panic!("expected a cross-field error, got {:?}", other),
+    }
+
+    std::fs::write(&path, "tls_enabled = true\ntls_cert = \"cert.pem\"\n").unwrap();
+    let config = TlsConfig::load_file(&path).unwrap();
+    assert!(config.validate_fields().is_ok());
+    assert_eq!(config.tls_cert.as_deref(), Some("cert.pem"));
+}
+
+#[derive(Debug, Config, Deserialize)]
+#[config(deny_unknown)]
+struct StrictListenConfig {
+    #[config(default = 8080u16)]
+    port: u16,
+}
+
+#[test]
+fn test_deny_unknown_rejects_typo() {
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("listen.toml");
+
+    std::fs::write(&path, "port = 9000\n").unwrap();
+    assert_eq!(StrictListenConfig::load_file(&path).unwrap().port, 9000);
+
+    std::fs::write(&path, "port = 9000\nprot = 9001\n").unwrap();
+    let err = StrictListenConfig::load_file(&path).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("prot: unknown key (value: 9001) [listen.toml] (did you mean `port`?)"),
+        "{}",
+        err
+    );
+}
+
+#[derive(Debug, Config, Deserialize)]
+#[config(env_prefix = "ALIASTEST_", deny_unknown)]
+struct RenamedConfig {
+    #[config(alias = "hostname", alias = "server_host", deprecated_since = "1.2")]
+    host: String,
+}
+
+#[test]
+fn test_alias_loads_old_key() {
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("renamed.toml");
+
+    std::fs::write(&path, "hostname = \"db.internal\"\n").unwrap();
+    assert_eq!(RenamedConfig::load_file(&path).unwrap().host, "db.internal");
+
+    std::fs::write(&path, "host = \"new\"\nserver_host = \"old\"\n").unwrap();
+    assert_eq!(RenamedConfig::load_file(&path).unwrap().host, "new");
+}
+
+#[test]
+fn test_alias_loads_old_env_var() {
+    std::env::set_var("ALIASTEST_SERVER_HOST", "from-env");
+    let config = RenamedConfig::load_sync();
+    std::env::remove_var("ALIASTEST_SERVER_HOST");
+    assert_eq!(config.unwrap().host, "from-env");
+}
+
+#[derive(Debug, PartialEq, Config, confers::ConfigSchema, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+enum Storage {
+    #[config(default)]
+    Local {
+        #[config(default = "data".to_string())]
+        path: String,
+    },
+    S3 {
+        bucket: String,
+    },
+}
+
+#[derive(Debug, Config, confers::ConfigSchema, Deserialize)]
+struct StorageApp {
+    #[config(default = 1u32)]
+    workers: u32,
+
+    storage: Storage,
+}
+
+#[test]
+fn test_enum_config_loads_selected_variant() {
+    assert_eq!(
+        Storage::default(),
+        Storage::Local {
+            path: "data".to_string()
+        }
+    );
+
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("storage.toml");
+    std::fs::write(&path, "kind = \"s3\"\nbucket = \"assets\"\n").unwrap();
+    assert_eq!(
+        Storage::load_file(&path).unwrap(),
+        Storage::S3 {
+            bucket: "assets".to_string()
+        }
+    );
+
+    std::fs::write(
+        &path,
+        "workers = 4\n[storage]\nkind = \"local\"\npath = \"/srv\"\n",
+    )
+    .unwrap();
+    let app = StorageApp::load_file(&path).unwrap();
+    assert_eq!(app.workers, 4);
+    assert_eq!(
+        app.storage,
+        Storage::Local {
+            path: "/srv".to_string()
+        }
+    );
+}
+
+#[test]
+fn test_enum_schema_lists_variants() {
+    let schema = Storage::json_schema();
+    let variants = schema["oneOf"].as_array().unwrap();
+    assert_eq!(variants.len(), 2);
+    assert_eq!(
+        variants[1]["properties"]["kind"]["enum"],
+        serde_json::json!(["s3"])
+    );
+    assert_eq!(variants[1]["required"], serde_json::json!(["kind"]));
+    assert_eq!(variants[1]["properties"]["bucket"]["type"], "string");
+    assert_eq!(variants[0]["properties"]["path"]["default"], "data");
+
+    let app = StorageApp::json_schema();
+    assert_eq!(app["properties"]["storage"]["oneOf"], schema["oneOf"]);
+    assert_eq!(app["properties"]["workers"]["type"], "integer");
+}
+
+#[derive(Debug, Config, confers::ConfigSchema, Deserialize)]
+#[config(validate_with = "check_database_entry")]
+struct DatabaseEntry {
+    host: String,
+    pool_max: u32,
+}
+
+fn check_database_entry(entry: &DatabaseEntry) -> Result<(), confers::ValidationErrors> {
+    let mut errors = confers::ValidationErrors::new();
+    if entry.pool_max == 0 {
+        errors.add(&["pool_max"], "pool_max must be at least 1");
+    }
+    errors.into_result()
+}
+
+#[derive(Debug, Config, confers::ConfigSchema, Deserialize)]
+#[config(env_prefix = "MAPAPP_")]
+struct DatabasesConfig {
+    databases: std::collections::BTreeMap<String, DatabaseEntry>,
+}
+
+#[test]
+#[serial]
+fn test_map_entries_from_env_are_validated() {
+    common::with_env_var("MAPAPP_DATABASES__PRIMARY__HOST", "db1", || {
+        common::with_env_var("MAPAPP_DATABASES__PRIMARY__POOL_MAX", "8", || {
+            let config = DatabasesConfig::load_sync().unwrap();
+            assert_eq!(config.databases["primary"].host, "db1");
+            assert_eq!(config.databases["primary"].pool_max, 8);
+
+            common::with_env_var("MAPAPP_DATABASES__READ_REPLICA__HOST", "db2", || {
+                common::with_env_var("MAPAPP_DATABASES__READ_REPLICA__POOL_MAX", "0", || {
+                    match DatabasesConfig::load_sync() {
+                        Err(confers::ConfigError::CrossFieldValidation { errors }) => {
+                            assert_eq!(errors.len(), 1);
+                            assert_eq!(
+                                errors.violations()[0].fields,
+                                ["databases.read_replica.pool_max"]
+                            );
+                        }
+                        other => panic!("expected a cross-field error, got {:?}", other),
+                    }
+                });
+            });
+        });
+    });
+}
+
+#[test]
+fn test_map_schema_describes_entries() {
+    let schema = DatabasesConfig::json_schema();
+    let databases = &schema["properties"]["databases"];
+    assert_eq!(databases["type"], "object");
+    assert_eq!(
+        databases["additionalProperties"]["properties"]["pool_max"]["type"],
+        "integer"
+    );
+}
+
+#[derive(Debug, Config, confers::ConfigSchema, Deserialize)]
+#[config(validate_with = "check_pooled")]
+struct Pooled<T> {
+    #[config(default = 4u32)]
+    size: u32,
+
+    settings: T,
+}
+
+fn check_pooled<T>(config: &Pooled<T>) -> Result<(), confers::ValidationErrors> {
+    let mut errors = confers::ValidationErrors::new();
+    if config.size == 0 {
+        errors.add(&["size"], "size must be at least 1");
+    }
+    errors.into_result()
+}
+
+#[test]
+fn test_generic_config() {
+    let pooled = Pooled::<SimpleConfig>::default();
+    assert_eq!(pooled.size, 4);
+    assert_eq!(pooled.settings.port, 8080);
+
+    let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+    let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("pooled.toml");
+    let settings = "[settings]\nhost = \"db\"\nport = 5432\n";
+    std::fs::write(&path, format!("size = 2\n{settings}")).unwrap();
+    let pooled = Pooled::<SimpleConfig>::load_file(&path).unwrap();
+    assert_eq!((pooled.size, pooled.settings.port), (2, 5432));
+
+    std::fs::write(&path, format!("size = 0\n{settings}")).unwrap();
+    assert!(matches!(
+        Pooled::<SimpleConfig>::load_file(&path),
+        Err(confers::ConfigError::CrossFieldValidation { .. })
+    ));
+
+    let schema = Pooled::<DatabaseEntry>::json_schema();
+    assert_eq!(schema["properties"]["size"]["default"], 4);
+    assert_eq!(schema["properties"]["settings"]["title"], "DatabaseEntry");
+}
+
+#[derive(Debug, Config, Deserialize)]
+struct PoolSettings {
+    #[config(default = 5u32)]
+    max: u32,
+}
+
+#[derive(Debug, Config, Deserialize)]
+struct DbSettings {
+    host: String,
+
+    pool: PoolSettings,
+
+    #[config(env = "NESTED_DATABASE_URL")]
+    url: Option<String>,
+}
+
+#[derive(Debug, Config, Deserialize)]
+struct Tuning {
+    threads: u32,
+}
+
+#[derive(Debug, Config, Deserialize)]
+#[config(env_prefix = "NESTAPP_")]
+struct NestedEnvApp {
+    db: DbSettings,
+
+    #[serde(flatten)]
+    tuning: Tuning,
+}
+
+#[test]
+fn test_nested_env_mapping() {
+    let mapping = NestedEnvApp::env_mapping();
+    let env_of = |key: &str| {
+        mapping
+            .iter()
+            .find(|(_, k, _)| k == key)
+            .map(|(_, _, env)| env.as_str())
+    };
+    assert_eq!(env_of("db.host"), Some("NESTAPP_DB_HOST"));
+    assert_eq!(env_of("db.pool.max"), Some("NESTAPP_DB_POOL_MAX"));
+    assert_eq!(env_of("db.url"), Some("NESTED_DATABASE_URL"));
+    assert_eq!(env_of("threads"), Some("NESTAPP_THREADS"));
+    assert_eq!(env_of("db"), None);
+}
+
+#[test]
+#[serial]
+fn test_nested_env_vars_load() {
+    common::with_env_var("NESTAPP_DB_HOST", "db.internal", || {
+        common::with_env_var("NESTAPP_DB_POOL_MAX", "20", || {
+            common::with_env_var("NESTED_DATABASE_URL", "postgres://db", || {
+                common::with_env_var("NESTAPP_THREADS", "8", || {
+                    let config = NestedEnvApp::load_sync().unwrap();
+                    assert_eq!(config.db.host, "db.internal");
+                    assert_eq!(config.db.pool.max, 20);
+                    assert_eq!(config.db.url.as_deref(), Some("postgres://db"));
+                    assert_eq!(config.tuning.threads, 8);
+                });
+            });
+        });
+    });
+}
+
+#[cfg(feature = "cli")]
+mod clap_args {
+    use confers::{ConfigClap, ConfigValue};
+
+    #[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+    enum LogFormat {
+        Text,
+        Json,
+    }
+
+    #[derive(Debug, ConfigClap)]
+    #[allow(dead_code)]
+    struct ServeConfig {
+        /// Directory to serve.
+        #[config(positional, required)]
+        root: String,
+
+        #[config(value_enum, default = LogFormat::Text)]
+        log_format: LogFormat,
+
+        #[config(name_clap_short = 'p')]
+        port: Option<u16>,
+
+        #[config(sensitive)]
+        dsn: Option<String>,
+    }
+
+    fn args(args: &[&str]) -> impl Iterator<Item = std::ffi::OsString> {
+        let args: Vec<std::ffi::OsString> = args.iter().map(Into::into).collect();
+        args.into_iter()
+    }
+
+    #[test]
+    fn test_positional_and_value_enum_args() {
+        let cli = ServeConfig::clap_args_from(args(&["app", "/srv", "--log_format", "json"]));
+        assert_eq!(cli.root, "/srv");
+        assert_eq!(cli.log_format, Some(LogFormat::Json));
+
+        let map = cli.to_config_map();
+        assert_eq!(map["root"], ConfigValue::String("/srv".into()));
+        assert_eq!(map["log_format"], ConfigValue::String("json".into()));
+
+        let err = ServeConfig::clap_app()
+            .try_get_matches_from(["app", "--log_format", "json"])
+            .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::MissingRequiredArgument);
+
+        let err = ServeConfig::clap_app()
+            .try_get_matches_from(["app", "/srv", "--log_format", "xml"])
+            .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::InvalidValue);
+    }
+
+    #[test]
+    fn test_augment_args_under_subcommand() {
+        let cli = clap::Command::new("app")
+            .arg(
+                clap::Arg::new("verbose")
+                    .long("verbose")
+                    .action(clap::ArgAction::SetTrue),
+            )
+            .subcommand(ServeConfig::augment_args(clap::Command::new("serve")));
+
+        let matches = cli
+            .try_get_matches_from([
+                "app",
+                "--verbose",
+                "serve",
+                "/srv",
+                "-p",
+                "9000",
+                "--log_format",
+                "text",
+            ])
+            .unwrap();
+        assert!(matches.get_flag("verbose"));
+        let (name, serve) = matches.subcommand().unwrap();
+        assert_eq!(name, "serve");
+
+        let args = ServeConfig::from_arg_matches(serve).unwrap();
+        assert_eq!(args.root, "/srv");
+        assert_eq!(args.port, Some(9000));
+    }
+
+    #[test]
+    fn test_builtin_config_set_and_print_config_args() {
+        let dir = tempfile::Builder::new().tempdir_in(".").unwrap();
+        let path = std::path::PathBuf::from(dir.path().file_name().unwrap()).join("serve.toml");
+        std::fs::write(
+            &path,
+            "dsn = \"postgres://db\"\n[db]\nhost = \"primary\"\npassword = \"hunter2\"\n",
+        )
+        .unwrap();
+
+        let cli = ServeConfig::clap_args_from(args(&[
+            "app",
+            "/srv",
+            "--config",
+            path.to_str().unwrap(),
+            "--set",
+            "db.host=replica",
+            "--set",
+            "db.port=5433",
+            "--print-config",
+        ]));
+        assert!(cli.print_config);
+        assert_eq!(cli.log_format, None);
+        assert!(!cli.to_config_map().contains_key("log_format"));
+
+        let config = cli
+            .sanitized_config(confers::ConfigBuilder::<serde_json::Value>::new())
+            .unwrap();
+        assert_eq!(config["db"]["host"], "replica");
+        assert_eq!(config["db"]["port"], 5433);
+        assert_eq!(config["db"]["password"], "[REDACTED]");
+        assert_eq!(config["dsn"], "[REDACTED]");
+
+        let err = ServeConfig::clap_app()
+            .try_get_matches_from(["app", "/srv", "--set", "db.port"])
+            .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+    }
+}
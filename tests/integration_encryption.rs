@@ -445,6 +445,137 @@ mod tests {
     }
 }
 
+#[cfg(feature = "encryption")]
+mod encrypted_file_tests {
+    use confers::secret::{CipherAlgorithm, ConfigEncryption, SecretBytes};
+    use confers::ConfigBuilder;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize)]
+    struct AppConfig {
+        name: String,
+        port: u16,
+    }
+
+    fn encryption() -> ConfigEncryption {
+        ConfigEncryption::new().with_key(SecretBytes::new(vec![3u8; 32]))
+    }
+
+    #[test]
+    fn test_encrypted_file_is_decrypted_by_builder() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.yaml.enc");
+        let encrypted = encryption()
+            .encrypt_file_contents(CipherAlgorithm::ChaCha20, "name: vault\nport: 9000\n")
+            .unwrap();
+        std::fs::write(&path, encrypted).unwrap();
+
+        // Encryption may be configured after the file source is added.
+        let config: AppConfig = ConfigBuilder::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .encryption(encryption())
+            .build()
+            .unwrap();
+
+        assert_eq!(config.name, "vault");
+        assert_eq!(config.port, 9000);
+    }
+
+    #[test]
+    fn test_encrypted_file_without_encryption_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("app.toml.enc");
+        let encrypted = encryption()
+            .encrypt_file_contents(CipherAlgorithm::XChaCha20, "port = 1\n")
+            .unwrap();
+        std::fs::write(&path, encrypted).unwrap();
+
+        let result = ConfigBuilder::<AppConfig>::new()
+            .allow_absolute_paths()
+            .file(&path)
+            .build();
+        assert!(matches!(
+            result.unwrap_err(),
+            confers::ConfigError::DecryptionFailed { .. }
+        ));
+    }
+}
+
+#[cfg(feature = "age")]
+mod age_tests {
+    use age::secrecy::ExposeSecret;
+    use confers::secret::{AgeCipher, CipherAlgorithm, ConfigEncryption};
+    use confers::ConfigBuilder;
+    use serde::Deserialize;
+
+    #[derive(Debug, Default, Deserialize)]
+    struct DbConfig {
+        user: String,
+        password: String,
+    }
+
+    #[test]
+    fn test_age_encrypted_value_in_config_file() {
+        let identity = age::x25519::Identity::generate();
+        let dir = tempfile::tempdir().unwrap();
+
+        let recipients_path = dir.path().join("recipients.txt");
+        std::fs::write(&recipients_path, identity.to_public().to_string()).unwrap();
+        let identity_path = dir.path().join("identity.txt");
+        std::fs::write(&identity_path, identity.to_string().expose_secret()).unwrap();
+
+        let encrypted = ConfigEncryption::new()
+            .with_age(
+                AgeCipher::new()
+                    .with_recipients_file(&recipients_path)
+                    .unwrap(),
+            )
+            .encrypt(CipherAlgorithm::Age, "p@ssw0rd")
+            .unwrap();
+
+        let config_path = dir.path().join("config.toml");
+        std::fs::write(
+            &config_path,
+            format!("user = \"admin\"\npassword = \"{}\"\n", encrypted),
+        )
+        .unwrap();
+
+        let config: DbConfig = ConfigBuilder::new()
+            .allow_absolute_paths()
+            .file(&config_path)
+            .age_identity_file(&identity_path)
+            .build()
+            .unwrap();
+
+        assert_eq!(config.user, "admin");
+        assert_eq!(config.password, "p@ssw0rd");
+    }
+
+    #[test]
+    fn test_age_value_without_identity_fails() {
+        let identity = age::x25519::Identity::generate();
+        let encrypted = ConfigEncryption::new()
+            .with_age(
+                AgeCipher::new()
+                    .with_recipient(&identity.to_public().to_string())
+                    .unwrap(),
+            )
+            .encrypt(CipherAlgorithm::Age, "secret")
+            .unwrap();
+
+        let result = ConfigBuilder::<serde_json::Value>::new()
+            .memory(std::collections::HashMap::from([(
+                "password".to_string(),
+                confers::ConfigValue::string(encrypted),
+            )]))
+            .encryption(ConfigEncryption::new())
+            .build();
+
+        assert!(result.is_err());
+    }
+}
+
 // Placeholder when encryption feature is not enabled
 #[cfg(not(feature = "encryption"))]
 mod tests {
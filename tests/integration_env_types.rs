@@ -35,6 +35,7 @@ fn cleanup_test_env() {
     std::env::remove_var(format!("{PREFIX}HOST"));
 }
 
+#[allow(clippy::bool_assert_comparison)]
 #[test]
 #[serial]
 fn test_env_vars_deserialize_into_typed_struct() {
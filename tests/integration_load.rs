@@ -138,4 +138,119 @@ servers = ["localhost:8080", "localhost:8081", "localhost:8082"]
         assert!(config["servers"].is_array());
         assert_eq!(config["servers"].as_array().unwrap().len(), 3);
     }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    struct ServerConfig {
+        #[serde(default)]
+        host: String,
+        #[serde(default)]
+        port: u16,
+    }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    struct DenyConfig {
+        #[serde(default)]
+        server: ServerConfig,
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_deny_unknown_names_key_source_and_suggestion() {
+        let (_file, path) = create_local_temp_config("[server]\nhost = \"a\"\nprot = 1\n", ".toml");
+        let file_name = path.file_name().unwrap().to_str().unwrap().to_string();
+
+        let config: DenyConfig = ConfigBuilder::new().file(&path).build().unwrap();
+        assert_eq!(config.server.host, "a");
+        assert_eq!(config.server.port, 0);
+
+        common::with_env_var("DENYTEST_SERVER_HOTS", "b", || {
+            let err = ConfigBuilder::<DenyConfig>::new()
+                .file(&path)
+                .env_prefix("DENYTEST_")
+                .with_deny_unknown(true)
+                .build()
+                .unwrap_err();
+            let confers::ConfigError::UnknownKeys { report } = &err else {
+                panic!("expected unknown keys, got {:?}", err);
+            };
+            let issues = report.issues();
+            assert_eq!(issues.len(), 2);
+            assert_eq!(issues[0].path, "server.hots");
+            assert_eq!(
+                issues[0].source.as_deref(),
+                Some("env:DENYTEST_SERVER_HOTS")
+            );
+            assert_eq!(issues[0].suggestion.as_deref(), Some("host"));
+            assert_eq!(issues[1].path, "server.prot");
+            assert_eq!(issues[1].source.as_deref(), Some(file_name.as_str()));
+            assert_eq!(issues[1].suggestion.as_deref(), Some("port"));
+            assert_eq!(err.code(), confers::ErrorCode::UnknownKey);
+        });
+    }
+
+    #[test]
+    fn test_env_with_separator_maps_nested_keys() {
+        common::with_env_var("DENYNEST_SERVER__PORT", "9000", || {
+            let config: DenyConfig = ConfigBuilder::new()
+                .env_with_separator("DENYNEST", "__")
+                .build()
+                .unwrap();
+            assert_eq!(config.server.port, 9000);
+
+            common::with_env_var("DENYNEST_SERVER__MAX_CONNS", "8", || {
+                let err = ConfigBuilder::<DenyConfig>::new()
+                    .env_with_separator("DENYNEST", "__")
+                    .with_deny_unknown(true)
+                    .build()
+                    .unwrap_err();
+                let confers::ConfigError::UnknownKeys { report } = &err else {
+                    panic!("expected unknown keys, got {:?}", err);
+                };
+                let issues = report.issues();
+                assert_eq!(issues.len(), 1);
+                assert_eq!(issues[0].path, "server.max_conns");
+                assert_eq!(
+                    issues[0].source.as_deref(),
+                    Some("env:DENYNEST_SERVER__MAX_CONNS")
+                );
+            });
+        });
+    }
+
+    #[derive(Debug, Default, serde::Deserialize)]
+    #[allow(dead_code)]
+    struct SecretConfig {
+        password: u32,
+        dsn: u32,
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn test_build_errors_redact_sensitive_values() {
+        let err = ConfigBuilder::<SecretConfig>::new()
+            .with_toml_str("password = \"hunter2-prod\"\ndsn = 1")
+            .build()
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(!message.contains("hunter2-prod"), "{}", message);
+        assert!(message.contains("[REDACTED]"), "{}", message);
+
+        let err = ConfigBuilder::<SecretConfig>::new()
+            .with_toml_str("password = 1\ndsn = \"postgres://admin:pw@db\"")
+            .with_sensitive_keys(["dsn"])
+            .build()
+            .unwrap_err();
+        assert!(!format!("{:?}", err).contains("admin:pw"), "{:?}", err);
+    }
+
+    #[test]
+    fn test_deny_unknown_skips_unprefixed_env() {
+        common::with_env_var("DENYTEST_UNPREFIXED", "1", || {
+            let config = ConfigBuilder::<DenyConfig>::new()
+                .env()
+                .with_deny_unknown(true)
+                .build();
+            assert!(config.is_ok(), "{:?}", config.err());
+        });
+    }
 }
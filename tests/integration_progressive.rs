@@ -419,3 +419,51 @@ async fn test_config_health_check_degraded() {
 
     assert!(matches!(status, HealthStatus::Degraded { .. }));
 }
+
+#[derive(Debug, Clone, confers::Config, serde::Deserialize)]
+struct WatchedConfig {
+    #[config(watch)]
+    port: u16,
+    #[config(watch)]
+    hosts: Vec<String>,
+    name: String,
+}
+
+// Test: #[config(watch)] fields get typed on_<field>_change hooks
+#[tokio::test]
+async fn test_watch_field_hooks_fire_for_their_field_only() {
+    use std::sync::Mutex;
+
+    let config = |port: u16, name: &str| {
+        Arc::new(WatchedConfig {
+            port,
+            hosts: vec!["a".to_string()],
+            name: name.to_string(),
+        })
+    };
+    let reloader = ProgressiveReloader::new(config(8080, "app"), ReloadStrategy::Immediate);
+    let ports = Arc::new(Mutex::new(Vec::new()));
+    let hosts_changed = Arc::new(Mutex::new(false));
+    let _port = WatchedConfig::on_port_change(&reloader, {
+        let ports = ports.clone();
+        move |old: &u16, new: &u16| ports.lock().unwrap().push((*old, *new))
+    });
+    let _hosts = WatchedConfig::on_hosts_change(&reloader, {
+        let hosts_changed = hosts_changed.clone();
+        move |_, _| *hosts_changed.lock().unwrap() = true
+    });
+    let provider = Arc::new(common::TestConfig::new(100, 10));
+
+    reloader
+        .begin_reload(config(8080, "renamed"), provider.clone())
+        .await
+        .unwrap();
+    reloader
+        .begin_reload(config(9090, "renamed"), provider)
+        .await
+        .unwrap();
+
+    assert_eq!(reloader.current().name, "renamed");
+    assert_eq!(*ports.lock().unwrap(), [(8080, 9090)]);
+    assert!(!*hosts_changed.lock().unwrap());
+}
@@ -51,6 +51,18 @@ fn test_host_validation_fail_empty() {
     });
 }
 
+#[test]
+fn test_garde_report_to_validation_report() {
+    let config = ValidatedConfig {
+        host: String::new(),
+        port: 0,
+    };
+    let report = confers::ValidationReport::from(&config.validate().unwrap_err());
+    let paths: Vec<&str> = report.issues().iter().map(|i| i.path.as_str()).collect();
+    assert_eq!(paths, ["host", "port"]);
+    assert_eq!(report.issues()[1].constraint, "lower than 1");
+}
+
 #[derive(Debug, Config, Deserialize, Validate)]
 #[config(validate)]
 struct EmailConfig {
@@ -255,3 +267,49 @@ fn test_unicode_validation() {
         );
     });
 }
+
+#[derive(Debug, confers::ConfigSchema, Deserialize, Validate)]
+#[allow(dead_code)]
+struct ConstrainedConfig {
+    #[garde(length(min = 1, max = 253))]
+    host: String,
+
+    #[config(default = 8080u16)]
+    #[garde(range(min = 1, max = 65535))]
+    port: u16,
+
+    #[garde(email)]
+    admin: String,
+
+    #[garde(length(min = 1), inner(length(min = 1)))]
+    upstreams: Vec<String>,
+
+    #[garde(pattern(r"^[a-z]+$"))]
+    region: String,
+
+    #[garde(url)]
+    homepage: String,
+}
+
+#[test]
+fn test_config_schema_maps_garde_constraints() {
+    let schema = <ConstrainedConfig as confers::ConfigSchema>::json_schema();
+    let properties = &schema["properties"];
+    assert_eq!(properties["host"]["minLength"], 1);
+    assert_eq!(properties["host"]["maxLength"], 253);
+    assert_eq!(properties["port"]["minimum"], 1);
+    assert_eq!(properties["port"]["maximum"], 65535);
+    assert_eq!(properties["port"]["default"], 8080);
+    assert_eq!(properties["admin"]["format"], "email");
+    assert_eq!(properties["upstreams"]["minItems"], 1);
+    assert!(properties["upstreams"].get("minLength").is_none());
+    assert_eq!(properties["region"]["pattern"], "^[a-z]+$");
+    assert_eq!(properties["homepage"]["format"], "uri");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.schema.json");
+    <ConstrainedConfig as confers::ConfigSchema>::write_json_schema(&path).unwrap();
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written["properties"], *properties);
+}
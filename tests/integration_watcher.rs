@@ -22,7 +22,8 @@ use std::time::Duration;
 use tempfile::TempDir;
 
 use confers::watcher::{
-    AdaptiveDebouncer, FsWatcher, MultiFsWatcher, WatcherConfig, WatcherConfigBuilder, WatcherGuard,
+    AdaptiveDebouncer, FsWatcher, MultiFsWatcher, WatchFilter, WatcherConfig, WatcherConfigBuilder,
+    WatcherGuard,
 };
 
 // ========================================
@@ -601,6 +602,173 @@ async fn test_multi_fs_watcher_drop_stops_watcher() {
     // Reaching this point means cleanup succeeded.
 }
 
+/// Test MultiFsWatcher reports files added to a watched drop-in directory.
+#[tokio::test]
+async fn test_multi_fs_watcher_directory_reports_new_file() {
+    let temp_dir = TempDir::new().unwrap();
+    let conf_d = temp_dir.path().join("conf.d");
+    fs::create_dir(&conf_d).unwrap();
+    fs::write(conf_d.join("10-base.toml"), "key = 1").unwrap();
+
+    let mut watcher = MultiFsWatcher::new(vec![&conf_d], 100).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let added = conf_d.join("20-extra.toml");
+    fs::write(&added, "key = 2").unwrap();
+    tokio::time::sleep(Duration::from_millis(500)).await;
+
+    let event = tokio::time::timeout(Duration::from_millis(3000), watcher.recv()).await;
+    watcher.stop();
+
+    let path = event
+        .expect("new drop-in file should be detected within 3s")
+        .expect("recv() should deliver Some(event)");
+    assert_eq!(path.file_name(), added.file_name());
+}
+
+/// Test FsWatcher with content hashing ignores rewrites with identical content.
+#[tokio::test]
+async fn test_fs_watcher_content_hash_suppresses_identical_write() {
+    let temp_dir = TempDir::new().unwrap();
+    let config_file = temp_dir.path().join("config.toml");
+    fs::write(&config_file, "key = 1").unwrap();
+
+    let mut watcher = FsWatcher::new(&config_file, 100)
+        .await
+        .unwrap()
+        .with_content_hash(true);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    fs::write(&config_file, "key = 1").unwrap();
+    let event = tokio::time::timeout(Duration::from_millis(800), watcher.recv()).await;
+    assert!(event.is_err(), "identical write was reported: {:?}", event);
+
+    fs::write(&config_file, "key = 2").unwrap();
+    let event = tokio::time::timeout(Duration::from_millis(3000), watcher.recv()).await;
+    watcher.stop();
+    assert!(
+        event
+            .expect("changed content should be detected within 3s")
+            .is_some(),
+        "recv() should deliver Some(event)"
+    );
+}
+
+/// Test MultiFsWatcher with content hashing reports only the file that changed.
+#[tokio::test]
+async fn test_multi_fs_watcher_content_hash_reports_changed_file_only() {
+    let temp_dir = TempDir::new().unwrap();
+    let file1 = temp_dir.path().join("config1.toml");
+    let file2 = temp_dir.path().join("config2.toml");
+    fs::write(&file1, "key1 = 1").unwrap();
+    fs::write(&file2, "key2 = 2").unwrap();
+
+    let mut watcher = MultiFsWatcher::new(vec![&file1, &file2], 100)
+        .await
+        .unwrap()
+        .with_content_hash(true);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    fs::write(&file1, "key1 = 1").unwrap();
+    fs::write(&file2, "key2 = 3").unwrap();
+
+    let event = tokio::time::timeout(Duration::from_millis(3000), watcher.recv()).await;
+    let path = event
+        .expect("changed file should be detected within 3s")
+        .expect("recv() should deliver Some(event)");
+    assert_eq!(path.file_name(), file2.file_name());
+
+    let event = tokio::time::timeout(Duration::from_millis(500), watcher.recv()).await;
+    watcher.stop();
+    assert!(event.is_err(), "unchanged file was reported: {:?}", event);
+}
+
+/// Test WatchFilter matching rules for names, relative paths and depth.
+#[test]
+fn test_watch_filter_matches() {
+    use std::path::Path;
+
+    let flat = WatchFilter::new();
+    assert!(flat.matches(Path::new("10-base.toml")));
+    assert!(!flat.matches(Path::new("nested/10-base.toml")));
+
+    let filter = WatchFilter::new()
+        .recursive(true)
+        .include("*.toml")
+        .unwrap()
+        .include("overrides/*.yaml")
+        .unwrap()
+        .exclude(".git")
+        .unwrap()
+        .exclude("*.swp")
+        .unwrap();
+    assert!(filter.matches(Path::new("a/b/app.toml")));
+    assert!(filter.matches(Path::new("overrides/prod.yaml")));
+    assert!(!filter.matches(Path::new("prod.yaml")));
+    assert!(!filter.matches(Path::new(".git/config.toml")));
+    assert!(!filter.matches(Path::new("app.toml.swp")));
+
+    assert!(WatchFilter::new().include("[").is_err());
+}
+
+/// Test MultiFsWatcher with a recursive filter reports new files in new subdirectories.
+#[tokio::test]
+async fn test_multi_fs_watcher_recursive_filter() {
+    let temp_dir = TempDir::new().unwrap();
+    let conf_d = temp_dir.path().join("conf.d");
+    fs::create_dir(&conf_d).unwrap();
+
+    let filter = WatchFilter::new()
+        .recursive(true)
+        .include("*.toml")
+        .unwrap();
+    let mut watcher = MultiFsWatcher::new(vec![&conf_d], 100)
+        .await
+        .unwrap()
+        .with_filter(filter);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let nested = conf_d.join("team");
+    fs::create_dir(&nested).unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    fs::write(nested.join("notes.txt"), "ignored").unwrap();
+    let added = nested.join("30-team.toml");
+    fs::write(&added, "key = 3").unwrap();
+
+    let event = tokio::time::timeout(Duration::from_millis(3000), watcher.recv()).await;
+    watcher.stop();
+
+    let path = event
+        .expect("nested drop-in file should be detected within 3s")
+        .expect("recv() should deliver Some(event)");
+    assert_eq!(path.file_name(), added.file_name());
+}
+
+/// Test FsWatcher on a directory only reports files accepted by its filter.
+#[tokio::test]
+async fn test_fs_watcher_directory_filter_excludes_files() {
+    let temp_dir = TempDir::new().unwrap();
+    let filter = WatchFilter::new().exclude("*.tmp").unwrap();
+    let mut watcher = FsWatcher::new(temp_dir.path(), 100)
+        .await
+        .unwrap()
+        .with_filter(filter);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    fs::write(temp_dir.path().join("app.toml.tmp"), "key = 1").unwrap();
+    let event = tokio::time::timeout(Duration::from_millis(800), watcher.recv()).await;
+    assert!(event.is_err(), "excluded file was reported: {:?}", event);
+
+    let config_file = temp_dir.path().join("app.toml");
+    fs::write(&config_file, "key = 1").unwrap();
+    let event = tokio::time::timeout(Duration::from_millis(3000), watcher.recv()).await;
+    watcher.stop();
+    let path = event
+        .expect("included file should be detected within 3s")
+        .expect("recv() should deliver Some(event)");
+    assert_eq!(path.file_name(), config_file.file_name());
+}
+
 // ========================================
 // Error Handling Tests (2.1.6, 2.1.7)
 // ========================================